@@ -31,7 +31,7 @@ async fn main() -> Result<()> {
     while let Some(row) = query_result.next().await {
         let values = row
             .iter()
-            .map(|value| value.to_string())
+            .map(ToString::to_string)
             .collect::<Vec<String>>();
         let row_data = values.join(", ");
         println!("{row_data}");
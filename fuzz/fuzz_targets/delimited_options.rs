@@ -0,0 +1,24 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rsql_drivers::DriverManager;
+use std::sync::LazyLock;
+use tokio::runtime::{Builder, Runtime};
+
+const DATASET: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../datasets/users.pipe");
+
+static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("runtime")
+});
+
+fuzz_target!(|options: &str| {
+    let url = format!("delimited://{DATASET}?{options}");
+    let driver_manager = DriverManager::default();
+    if let Ok(mut connection) = RUNTIME.block_on(driver_manager.connect(url.as_str())) {
+        let _ = RUNTIME.block_on(connection.query("SELECT * FROM users"));
+        let _ = RUNTIME.block_on(connection.close());
+    }
+});
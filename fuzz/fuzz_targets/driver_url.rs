@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rsql_drivers::DriverManager;
+use std::sync::LazyLock;
+use tokio::runtime::{Builder, Runtime};
+
+static RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("runtime")
+});
+
+fuzz_target!(|url: &str| {
+    let driver_manager = DriverManager::default();
+    if let Ok(mut connection) = RUNTIME.block_on(driver_manager.connect(url)) {
+        let _ = RUNTIME.block_on(connection.close());
+    }
+});
@@ -0,0 +1,48 @@
+#![no_main]
+
+use async_trait::async_trait;
+use libfuzzer_sys::fuzz_target;
+use rsql_core::executors::parse_commands;
+use rsql_drivers::{Capabilities, Connection, MemoryQueryResult, QueryResult, Result};
+
+/// Connection that only exercises the default statement classification.
+#[derive(Debug)]
+struct FuzzConnection {
+    url: String,
+}
+
+#[async_trait]
+impl Connection for FuzzConnection {
+    fn url(&self) -> &String {
+        &self.url
+    }
+
+    async fn execute(&mut self, _sql: &str) -> Result<u64> {
+        Ok(0)
+    }
+
+    async fn query(&mut self, _sql: &str) -> Result<Box<dyn QueryResult>> {
+        Ok(Box::<MemoryQueryResult>::default())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Capabilities for FuzzConnection {}
+
+fuzz_target!(|input: (&str, &str)| {
+    let (command_identifier, contents) = input;
+    let connection = FuzzConnection {
+        url: "fuzz://".to_string(),
+    };
+
+    let Ok(commands) = parse_commands(command_identifier, contents) else {
+        return;
+    };
+    for command in commands {
+        assert_eq!(command, command.trim());
+        let _ = connection.parse_sql(command.as_str());
+    }
+});
@@ -9,16 +9,21 @@ mod version;
 
 use crate::update::check_for_newer_version;
 use anyhow::Result;
-use clap::{Parser, ValueEnum};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
 use colored::Colorize;
-use rsql_core::commands::{help, quit, ShellCommand};
+use rsql_core::commands::help::write_commands_help;
+use rsql_core::commands::{help, quit, CommandManager, ShellCommand};
 use rsql_core::configuration::{Configuration, ConfigurationBuilder};
+use rsql_core::server::{mcp, serve, McpArgs, ServeArgs};
 use rsql_core::shell::{ShellArgs, ShellBuilder};
 use rsql_core::writers::{Output, StdoutWriter};
 use rust_i18n::t;
 use serde::Serialize;
+use std::io::IsTerminal;
 use std::{env, io};
 use supports_color::Stream;
+use tokio::io::AsyncReadExt;
 use tracing::{info, warn};
 
 i18n!("locales", fallback = "en");
@@ -32,9 +37,23 @@ enum Color {
     Never,
 }
 
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Serve the Model Context Protocol over stdin and stdout, so that AI assistants can list the
+    /// tables, describe tables and query the database
+    Mcp(McpArgs),
+    /// Serve an HTTP API that executes SQL statements and returns the results as JSON, CSV or
+    /// Arrow
+    Serve(ServeArgs),
+}
+
 #[derive(Debug, Default, Parser)]
 #[expect(clippy::struct_field_names)]
 pub(crate) struct Args {
+    /// The command to execute instead of starting the shell
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The shell arguments
     #[clap(flatten)]
     pub shell_args: ShellArgs,
@@ -43,10 +62,18 @@ pub(crate) struct Args {
     #[arg(long, env = "COLOR", default_value_t, value_enum)]
     color: Color,
 
+    /// Generate the completion script for a shell, e.g. bash, zsh or fish
+    #[arg(long, value_name = "SHELL")]
+    generate_completions: Option<Shell>,
+
     /// Disable the update check
     #[arg(long, env = "DISABLE_UPDATE_CHECK")]
     disable_update_check: bool,
 
+    /// Display the detailed help for all commands
+    #[arg(long)]
+    help_commands: bool,
+
     /// Display the version of this tool
     #[arg(long)]
     version: bool,
@@ -74,7 +101,7 @@ async fn main() -> Result<()> {
 }
 
 pub(crate) async fn execute(
-    args: Args,
+    mut args: Args,
     configuration: Configuration,
     mut output: Output,
 ) -> Result<i32> {
@@ -85,9 +112,29 @@ pub(crate) async fn execute(
     let exit_code = if args.version {
         writeln!(output, "{version}")?;
         0
+    } else if let Some(shell) = args.generate_completions {
+        clap_complete::generate(shell, &mut Args::command(), "rsql", &mut output);
+        0
+    } else if args.help_commands {
+        write_commands_help(&configuration, &CommandManager::default(), &mut output)?;
+        0
+    } else if let Some(command) = &args.command {
+        match command {
+            Command::Mcp(mcp_args) => mcp(configuration, mcp_args, &mut output).await?,
+            Command::Serve(serve_args) => serve(configuration, serve_args, &mut output).await?,
+        }
+        0
     } else {
-        if args.shell_args.commands.is_empty() && args.shell_args.file.is_none() {
+        let no_input = args.shell_args.commands.is_empty()
+            && args.shell_args.file.is_none()
+            && args.shell_args.migrate.is_none();
+        if no_input && io::stdin().is_terminal() {
             welcome_message(&args, &configuration, &mut io::stderr()).await?;
+        } else if no_input && args.shell_args.report.is_none() {
+            // Execute the statements piped to stdin in batch mode instead of starting the REPL
+            let mut input = String::new();
+            tokio::io::stdin().read_to_string(&mut input).await?;
+            args.shell_args.commands = vec![input];
         }
 
         let mut shell = ShellBuilder::default()
@@ -172,6 +219,37 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_generate_completions() -> Result<()> {
+        let args = Args {
+            generate_completions: Some(Shell::Bash),
+            ..Default::default()
+        };
+        let output = Output::default();
+
+        assert_eq!(0, execute(args, Configuration::default(), output).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_help_commands() -> Result<()> {
+        let configuration = Configuration {
+            command_identifier: ".".to_string(),
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let args = Args {
+            help_commands: true,
+            ..Default::default()
+        };
+        let output = Output::default();
+
+        assert_eq!(0, execute(args, configuration, output).await?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_command() -> Result<()> {
         let configuration = Configuration::default();
@@ -181,9 +259,12 @@ mod tests {
             ..Default::default()
         };
         let args = Args {
+            command: None,
             shell_args,
             color: Color::Never,
+            generate_completions: None,
             disable_update_check: false,
+            help_commands: false,
             version: false,
         };
         let output = Output::default();
@@ -193,6 +274,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_commands() -> Result<()> {
+        Args::command().debug_assert();
+        let args = Args::try_parse_from(["rsql", "serve", "--listen", "127.0.0.1:9000"])?;
+        let Some(Command::Serve(serve_args)) = args.command else {
+            panic!("Expected the serve command");
+        };
+        assert_eq!(serve_args.listen.port(), 9000);
+        assert!(!serve_args.allow_writes);
+
+        let args = Args::try_parse_from(["rsql", "mcp", "--allow-writes"])?;
+        let Some(Command::Mcp(mcp_args)) = args.command else {
+            panic!("Expected the mcp command");
+        };
+        assert!(mcp_args.allow_writes);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_welcome_message() -> Result<()> {
         let args = Args::default();
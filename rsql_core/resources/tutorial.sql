@@ -0,0 +1,23 @@
+DROP TABLE IF EXISTS orders;
+DROP TABLE IF EXISTS users;
+
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    email TEXT NOT NULL
+);
+INSERT INTO users (id, name, email) VALUES (1, 'John Doe', 'john.doe@example.com');
+INSERT INTO users (id, name, email) VALUES (2, 'Jane Smith', 'jane.smith@example.com');
+INSERT INTO users (id, name, email) VALUES (3, 'Alice Jones', 'alice.jones@example.com');
+
+CREATE TABLE orders (
+    id INTEGER PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users (id),
+    product TEXT NOT NULL,
+    quantity INTEGER NOT NULL,
+    price REAL NOT NULL
+);
+INSERT INTO orders (id, user_id, product, quantity, price) VALUES (1, 1, 'Keyboard', 1, 49.99);
+INSERT INTO orders (id, user_id, product, quantity, price) VALUES (2, 1, 'Mouse', 2, 19.99);
+INSERT INTO orders (id, user_id, product, quantity, price) VALUES (3, 2, 'Monitor', 1, 199.00);
+INSERT INTO orders (id, user_id, product, quantity, price) VALUES (4, 3, 'Cable', 3, 4.50);
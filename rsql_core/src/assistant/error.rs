@@ -0,0 +1,28 @@
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error when the provider returns an error, e.g. when the key is invalid
+    #[error("The provider returned {status}: {message}")]
+    ProviderError { status: u16, message: String },
+    /// Error when the request to the provider fails
+    #[error(transparent)]
+    RequestError(#[from] reqwest::Error),
+    /// Error when the answer of the provider does not contain a SQL statement
+    #[error("The provider did not return a SQL statement")]
+    NoStatement,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_error() {
+        let error = Error::ProviderError {
+            status: 401,
+            message: "Invalid key".to_string(),
+        };
+        assert_eq!(error.to_string(), "The provider returned 401: Invalid key");
+    }
+}
@@ -0,0 +1,149 @@
+use crate::assistant::{extract_sql, Error, Prompt, Provider, Result};
+use crate::configuration::Configuration;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::env;
+
+/// The environment variable with the key used when no key is configured
+const KEY_VARIABLE: &str = "OPENAI_API_KEY";
+
+/// Generates SQL statements with an `OpenAI` compatible chat completions API, e.g. `OpenAI`,
+/// Ollama or vLLM
+#[derive(Debug)]
+pub struct OpenAiProvider {
+    client: reqwest::Client,
+    url: String,
+    model: String,
+    key: Option<String>,
+}
+
+impl OpenAiProvider {
+    /// Create a provider for the API at the URL, e.g. `https://api.openai.com/v1`
+    #[must_use]
+    pub fn new(url: &str, model: &str, key: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            key,
+        }
+    }
+}
+
+/// Create the provider for the URL, model and key of the configuration; the key is read from the
+/// `OPENAI_API_KEY` environment variable when no key is configured.
+impl From<&Configuration> for OpenAiProvider {
+    fn from(configuration: &Configuration) -> Self {
+        let key = configuration
+            .ask_key
+            .clone()
+            .or_else(|| env::var(KEY_VARIABLE).ok());
+        Self::new(&configuration.ask_url, &configuration.ask_model, key)
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAiProvider {
+    async fn generate_sql(&self, prompt: &Prompt) -> Result<String> {
+        let url = format!("{}/chat/completions", self.url);
+        let mut request = self.client.post(url).json(&request_body(&self.model, prompt));
+        if let Some(key) = &self.key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+        if !status.is_success() {
+            return Err(Error::ProviderError {
+                status: status.as_u16(),
+                message: error_message(&text),
+            });
+        }
+        parse_response(&text)
+    }
+}
+
+/// Get the body of the chat completions request for the prompt
+fn request_body(model: &str, prompt: &Prompt) -> Value {
+    json!({
+        "model": model,
+        "temperature": 0,
+        "messages": [
+            { "role": "system", "content": prompt.instructions() },
+            { "role": "user", "content": prompt.question },
+        ],
+    })
+}
+
+/// Get the SQL statement from the message of the first choice of the response
+fn parse_response(text: &str) -> Result<String> {
+    let response: Value = serde_json::from_str(text).map_err(|_| Error::NoStatement)?;
+    let content = response["choices"][0]["message"]["content"]
+        .as_str()
+        .unwrap_or_default();
+    let sql = extract_sql(content);
+    if sql.is_empty() {
+        return Err(Error::NoStatement);
+    }
+    Ok(sql)
+}
+
+/// Get the message of an error response, or the body of the response when it is not an error
+/// document
+fn error_message(text: &str) -> String {
+    serde_json::from_str::<Value>(text)
+        .ok()
+        .and_then(|response| response["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| text.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_configuration() {
+        let configuration = Configuration {
+            ask_url: "http://localhost:11434/v1/".to_string(),
+            ask_model: "llama3".to_string(),
+            ask_key: Some("key".to_string()),
+            ..Default::default()
+        };
+        let provider = OpenAiProvider::from(&configuration);
+        assert_eq!(provider.url, "http://localhost:11434/v1");
+        assert_eq!(provider.model, "llama3");
+        assert_eq!(provider.key, Some("key".to_string()));
+    }
+
+    #[test]
+    fn test_request_body() {
+        let prompt = Prompt {
+            question: "How many users?".to_string(),
+            database: "sqlite".to_string(),
+            schema: "users (id INTEGER)\n".to_string(),
+        };
+        let body = request_body("gpt-4o-mini", &prompt);
+        assert_eq!(body["model"], "gpt-4o-mini");
+        assert_eq!(body["messages"][0]["content"], prompt.instructions());
+        assert_eq!(body["messages"][1]["content"], "How many users?");
+    }
+
+    #[test]
+    fn test_parse_response() -> anyhow::Result<()> {
+        let text = r#"{"choices": [{"message": {"content": "```sql\nSELECT 1;\n```"}}]}"#;
+        assert_eq!(parse_response(text)?, "SELECT 1;");
+        assert!(matches!(
+            parse_response(r#"{"choices": []}"#),
+            Err(Error::NoStatement)
+        ));
+        assert!(matches!(parse_response("foo"), Err(Error::NoStatement)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_message() {
+        let text = r#"{"error": {"message": "Invalid key"}}"#;
+        assert_eq!(error_message(text), "Invalid key");
+        assert_eq!(error_message(" Not found \n"), "Not found");
+    }
+}
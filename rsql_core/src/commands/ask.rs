@@ -0,0 +1,171 @@
+use crate::assistant::{OpenAiProvider, Prompt, Provider};
+use crate::commands::Error::MissingArguments;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_formatters::{FormatterOptions, Highlighter};
+use rust_i18n::t;
+
+/// Command to ask a question in natural language; the SQL statement generated from the question
+/// and the schema is displayed and loaded into the prompt, where it can be edited, and is executed
+/// once approved
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("ask_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("ask_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("ask_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("ask_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let provider = OpenAiProvider::from(&*options.configuration);
+        ask(options, &provider).await
+    }
+}
+
+/// Generate the SQL statement for the question with the provider, display it and load it into
+/// the prompt.
+async fn ask(options: CommandOptions<'_>, provider: &dyn Provider) -> Result<LoopCondition> {
+    let configuration = options.configuration;
+    let locale = configuration.locale.as_str();
+    if options.input.len() <= 1 {
+        return Err(MissingArguments {
+            command_name: Command.name(locale),
+            arguments: Command.args(locale),
+        });
+    }
+
+    let question = options.input[1..].join(" ");
+    let metadata = options.connection.metadata().await?;
+    let prompt = Prompt::new(&question, options.connection.url(), &metadata);
+    let sql = provider.generate_sql(&prompt).await?;
+
+    let formatter_options = FormatterOptions {
+        color: configuration.color,
+        ..Default::default()
+    };
+    let highlighter = Highlighter::new(&formatter_options, "sql");
+    let ask_confirm = t!("ask_confirm", locale = locale);
+    writeln!(options.output, "{}", highlighter.highlight(&sql)?)?;
+    writeln!(options.output, "{ask_confirm}")?;
+    options.state.edited_statement = Some(sql);
+    Ok(LoopCondition::Continue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assistant::{Error, MockProvider};
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, Metadata, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "ask");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "\"question\"");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Generate a SQL statement from a question in natural language"
+        );
+    }
+
+    async fn execute(
+        input: &[&str],
+        state: &mut ShellState,
+        provider: &MockProvider,
+    ) -> Result<String> {
+        let configuration = &mut Configuration {
+            color: false,
+            ..Default::default()
+        };
+        let mut connection = MockConnection::new();
+        connection
+            .expect_metadata()
+            .returning(|| Ok(Metadata::new()));
+        connection
+            .expect_url()
+            .return_const("rusqlite://".to_string());
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state,
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        let result = ask(options, provider).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        Ok(output.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut provider = MockProvider::new();
+        provider
+            .expect_generate_sql()
+            .withf(|prompt| prompt.question == "How many users?" && prompt.database == "rusqlite")
+            .returning(|_| Ok("SELECT COUNT(*) FROM users;".to_string()));
+        let state = &mut ShellState::default();
+
+        let output = execute(&[".ask", "How", "many", "users?"], state, &provider).await?;
+        assert!(output.starts_with("SELECT COUNT(*) FROM users;\n"));
+        assert_eq!(
+            state.edited_statement,
+            Some("SELECT COUNT(*) FROM users;".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_provider_error() {
+        let mut provider = MockProvider::new();
+        provider
+            .expect_generate_sql()
+            .returning(|_| Err(Error::NoStatement));
+        let state = &mut ShellState::default();
+
+        let result = execute(&[".ask", "foo"], state, &provider).await;
+        assert!(result.is_err());
+        assert_eq!(state.edited_statement, None);
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_arguments() {
+        let provider = MockProvider::new();
+        let state = &mut ShellState::default();
+        let result = execute(&[".ask"], state, &provider).await;
+        assert!(matches!(result, Err(MissingArguments { .. })));
+    }
+}
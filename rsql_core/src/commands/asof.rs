@@ -0,0 +1,175 @@
+use crate::commands::Error::IoError;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to read historical data as of a timestamp (e.g. `CockroachDB` `AS OF SYSTEM TIME`);
+/// cached results of the connection are evicted when the timestamp changes
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("asof_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("asof_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!("asof_example_arguments", locale = locale, off = off))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("asof_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let off = t!("off", locale = locale).to_string();
+
+        if options.input.len() <= 1 {
+            let timestamp = options.state.as_of.clone().unwrap_or(off);
+            let asof_setting =
+                t!("asof_setting", locale = locale, timestamp = timestamp).to_string();
+            writeln!(options.output, "{asof_setting}")?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let value = options.input[1..].join(" ");
+        let timestamp = if value.eq_ignore_ascii_case(&off) {
+            None
+        } else {
+            Some(value)
+        };
+        let historical_reads = options.connection.as_historical_reads().ok_or_else(|| {
+            IoError(anyhow!(
+                "historical reads are not supported for this connection"
+            ))
+        })?;
+        historical_reads.set_as_of(timestamp.clone()).await?;
+        options.state.as_of = timestamp;
+        options.state.cache.evict_url(options.connection.url());
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CommandManager, CommandOptions};
+    use crate::configuration::{ColumnSelection, Configuration};
+    use crate::shell::{CacheKey, ShellState};
+    use crate::writers::Output;
+    use mockall::predicate::eq;
+    use rsql_drivers::{
+        Connection, DriverManager, MemoryQueryResult, MockCapableConnection, MockConnection,
+        MockHistoricalReads,
+    };
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "asof");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[timestamp|off]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Read historical data as of a timestamp, or read current data"
+        );
+    }
+
+    async fn execute(
+        connection: &mut dyn Connection,
+        state: &mut ShellState,
+        input: &[&str],
+    ) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state,
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        Ok(output.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let connection = &mut MockConnection::new();
+        let state = &mut ShellState::default();
+        let output = execute(connection, state, &[".asof"]).await?;
+        assert_eq!(output, "As of: off\n");
+
+        state.as_of = Some("-10s".to_string());
+        let output = execute(connection, state, &[".asof"]).await?;
+        assert_eq!(output, "As of: -10s\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_and_off() -> anyhow::Result<()> {
+        let mut historical_reads = MockHistoricalReads::new();
+        historical_reads
+            .expect_set_as_of()
+            .with(eq(Some("2024-01-01 00:00:00".to_string())))
+            .times(1)
+            .returning(|_| Ok(()));
+        historical_reads
+            .expect_set_as_of()
+            .with(eq(None::<String>))
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut connection = MockCapableConnection {
+            historical_reads: Some(historical_reads),
+            ..Default::default()
+        };
+        connection
+            .connection
+            .expect_url()
+            .return_const("cockroachdb://localhost".to_string());
+        let state = &mut ShellState::default();
+        let key = CacheKey::new("cockroachdb://localhost", "SELECT 1", 100, &ColumnSelection::All);
+        state.cache.insert(key, MemoryQueryResult::default());
+
+        execute(&mut connection, state, &[".asof", "2024-01-01", "00:00:00"]).await?;
+        assert_eq!(state.as_of, Some("2024-01-01 00:00:00".to_string()));
+        assert!(state.cache.entries().is_empty());
+
+        execute(&mut connection, state, &[".asof", "OFF"]).await?;
+        assert_eq!(state.as_of, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_not_supported() {
+        let mut connection = MockConnection::new();
+        let state = &mut ShellState::default();
+        let result = execute(&mut connection, state, &[".asof", "-10s"]).await;
+        assert!(matches!(result, Err(IoError(_))));
+        assert_eq!(state.as_of, None);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::commands::Error::MissingArguments;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+
+/// Attach a database with a name so that its tables can be queried, and joined with the tables of
+/// other attached databases, as `<name>.<table>`; lists the attached databases when no arguments
+/// are specified
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("attach_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("attach_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("attach_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("attach_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        match (options.input.get(1), options.input.get(2)) {
+            (Some(name), Some(url)) => {
+                options
+                    .state
+                    .stash
+                    .attach(options.driver_manager, name, url)
+                    .await?;
+                return Ok(LoopCondition::Continue);
+            }
+            (Some(_), None) => {
+                return Err(MissingArguments {
+                    command_name: self.name(locale),
+                    arguments: self.args(locale),
+                });
+            }
+            _ => {}
+        }
+
+        let start = std::time::Instant::now();
+        let configuration = options.configuration;
+        let locale = &configuration.locale;
+        let columns = vec![
+            t!("attach_name", locale = locale).to_string(),
+            t!("attach_url", locale = locale).to_string(),
+        ];
+        let rows = options
+            .state
+            .stash
+            .attachments()
+            .into_iter()
+            .map(|(name, url)| {
+                vec![
+                    Value::String(name.to_string()),
+                    Value::String(url.to_string()),
+                ]
+            })
+            .collect();
+
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "attach");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[name url]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Attach a database to query its tables as name.table, or list attached databases"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_list() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".attach".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string().replace("\r\n", "\n"),
+            "\"name\",\"url\"\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_url() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".attach".to_string(), "pg".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_url() {
+        let state = &mut ShellState::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![
+                ".attach".to_string(),
+                "pg".to_string(),
+                "foo://bar".to_string(),
+            ],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+        assert!(state.stash.attachments().is_empty());
+    }
+}
@@ -31,6 +31,7 @@ impl ToggleShellCommand for Command {
 
 #[cfg(test)]
 mod tests {
+    use crate::shell::ShellState;
     use std::default;
 
     use rustyline::history::DefaultHistory;
@@ -74,6 +75,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".bail".to_string()],
             output: &mut output,
         };
@@ -114,6 +116,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".bail".to_string(), "on".to_string()],
             output: &mut Output::default(),
         };
@@ -138,6 +141,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".bail".to_string(), "off".to_string()],
             output: &mut Output::default(),
         };
@@ -158,6 +162,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".bail".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
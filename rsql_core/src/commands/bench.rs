@@ -0,0 +1,218 @@
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{split_examples, CommandOptions, Error, LoopCondition, Result, ShellCommand};
+use crate::executors::Executor;
+use async_trait::async_trait;
+use num_format::{Locale, ToFormattedString};
+use rust_i18n::t;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Execute a statement repeatedly and display latency statistics
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("bench_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("bench_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("bench_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("bench_description", locale = locale).to_string()
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    #[expect(clippy::cast_precision_loss)]
+    #[expect(clippy::cast_sign_loss)]
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str().to_string();
+        if options.input.len() <= 2 {
+            return Err(MissingArguments {
+                command_name: self.name(&locale),
+                arguments: self.args(&locale),
+            });
+        }
+        let iterations = usize::from_str(&options.input[1])?;
+        if iterations == 0 {
+            return Err(InvalidOption {
+                command_name: self.name(&locale),
+                option: options.input[1].to_string(),
+            });
+        }
+        let sql = options.input[2..].join(" ");
+
+        let mut executor = Executor::new(
+            &mut *options.configuration,
+            options.command_manager,
+            options.driver_manager,
+            options.formatter_manager,
+            options.history,
+            &mut *options.state,
+            &mut *options.connection,
+            &mut *options.output,
+        );
+        let mut durations = Vec::with_capacity(iterations);
+        let mut rows = 0;
+        for _ in 0..iterations {
+            let (duration, statement_rows) = executor
+                .bench(&sql)
+                .await
+                .map_err(|error| Error::IoError(error.into()))?;
+            durations.push(duration);
+            rows += statement_rows;
+        }
+
+        durations.sort();
+        let elapsed: Duration = durations.iter().sum();
+        let rows_per_second = if elapsed.is_zero() {
+            0
+        } else {
+            (rows as f64 / elapsed.as_secs_f64()).round() as u64
+        };
+        let num_locale = Locale::from_str(&locale).unwrap_or(Locale::en);
+        let summary = t!(
+            "bench_summary",
+            locale = locale,
+            iterations = iterations.to_formatted_string(&num_locale),
+            min = format!("{:?}", percentile(&durations, 0)),
+            median = format!("{:?}", percentile(&durations, 50)),
+            p95 = format!("{:?}", percentile(&durations, 95)),
+            max = format!("{:?}", percentile(&durations, 100)),
+            rows_per_second = rows_per_second.to_formatted_string(&num_locale),
+        )
+        .to_string();
+        writeln!(options.output, "{summary}")?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Get the duration at the percentile of the sorted durations using the nearest rank method
+fn percentile(durations: &[Duration], percentile: usize) -> Duration {
+    let rank = (durations.len() * percentile).div_ceil(100);
+    durations
+        .get(rank.saturating_sub(1))
+        .copied()
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use mockall::predicate::eq;
+    use rsql_drivers::{
+        DriverManager, MemoryQueryResult, MockConnection, StatementMetadata, Value,
+    };
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "bench");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<iterations> <sql>");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Execute a statement repeatedly and display latency statistics"
+        );
+    }
+
+    #[test]
+    fn test_percentile() {
+        let durations: Vec<Duration> = (1..=20).map(Duration::from_millis).collect();
+        assert_eq!(percentile(&durations, 0), Duration::from_millis(1));
+        assert_eq!(percentile(&durations, 50), Duration::from_millis(10));
+        assert_eq!(percentile(&durations, 95), Duration::from_millis(19));
+        assert_eq!(percentile(&durations, 100), Duration::from_millis(20));
+        assert_eq!(percentile(&[], 50), Duration::ZERO);
+    }
+
+    async fn test_execute_input(input: &[&str]) -> Result<LoopCondition> {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut Output::default(),
+        };
+        Command.execute(options).await
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_arguments() {
+        let result = test_execute_input(&[".bench", "10"]).await;
+        assert!(matches!(result, Err(MissingArguments { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_iterations() {
+        let result = test_execute_input(&[".bench", "0", "SELECT 1"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+        let result = test_execute_input(&[".bench", "foo", "SELECT 1"]).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let sql = "SELECT * FROM users";
+        let mut connection = MockConnection::new();
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| StatementMetadata::Query);
+        connection.expect_query().times(3).returning(|_| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![vec![Value::I64(1)], vec![Value::I64(2)]],
+            )))
+        });
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".bench".to_string(), "3".to_string(), sql.to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let output = output.to_string();
+        assert!(output.starts_with("3 executions: min "));
+        assert!(output.contains(", median "));
+        assert!(output.contains(", p95 "));
+        assert!(output.trim_end().ends_with(" rows/s"));
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use rsql_formatters::BytesFormat;
+use rust_i18n::t;
+use std::str::FromStr;
+
+/// The formats that binary values can be displayed in
+const BYTES_FORMATS: [BytesFormat; 4] = [
+    BytesFormat::Base64,
+    BytesFormat::Hex,
+    BytesFormat::Escape,
+    BytesFormat::Omit,
+];
+
+/// Command to set the format used to display binary values
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("bytes_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("bytes_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("bytes_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("bytes_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, _options: &CompletionOptions<'_>) -> Vec<String> {
+        BYTES_FORMATS.iter().map(ToString::to_string).collect()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+
+        let Some(value) = options.input.get(1) else {
+            let bytes_setting = t!(
+                "bytes_setting",
+                locale = locale,
+                format = options.configuration.results_bytes_format.to_string()
+            )
+            .to_string();
+            writeln!(options.output, "{bytes_setting}")?;
+            return Ok(LoopCondition::Continue);
+        };
+
+        let bytes_format = BytesFormat::from_str(value).map_err(|_| InvalidOption {
+            command_name: self.name(locale).to_string(),
+            option: value.to_string(),
+        })?;
+        options.configuration.results_bytes_format = bytes_format;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{execute, CommandManager};
+    use crate::configuration::Configuration;
+    use rsql_drivers::Metadata;
+    use rsql_formatters::FormatterManager;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "bytes");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[base64|hex|escape|omit]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Set the format used to display binary values");
+    }
+
+    #[test]
+    fn test_completions() {
+        let command_manager = CommandManager::default();
+        let formatter_manager = FormatterManager::default();
+        let metadata = Metadata::default();
+        let options = CompletionOptions {
+            locale: "en",
+            command_manager: &command_manager,
+            formatter_manager: &formatter_manager,
+            metadata: &metadata,
+        };
+        let completions = Command.completions(&options);
+        assert_eq!(completions, vec!["base64", "hex", "escape", "omit"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let output = execute(&Command, configuration, &[".bytes"]).await?;
+        assert_eq!(output, "Bytes: base64\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_format() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(&Command, configuration, &[".bytes", "hex"]).await?;
+        assert_eq!(configuration.results_bytes_format, BytesFormat::Hex);
+
+        let _ = execute(&Command, configuration, &[".bytes", "OMIT"]).await?;
+        assert_eq!(configuration.results_bytes_format, BytesFormat::Omit);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".bytes", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
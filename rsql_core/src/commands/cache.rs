@@ -0,0 +1,251 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+
+/// Command to enable or disable the cache of query results, list the cached results or evict
+/// them.  Disabling the cache evicts the cached results.
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("cache_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        let on = t!("on", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        let list = t!("cache_list", locale = locale).to_string();
+        let clear = t!("cache_clear", locale = locale).to_string();
+        t!(
+            "cache_argument",
+            locale = locale,
+            on = on,
+            off = off,
+            list = list,
+            clear = clear
+        )
+        .to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let on = t!("on", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        let list = t!("cache_list", locale = locale).to_string();
+        let clear = t!("cache_clear", locale = locale).to_string();
+        split_examples(&t!(
+            "cache_example_arguments",
+            locale = locale,
+            on = on,
+            off = off,
+            list = list,
+            clear = clear,
+        ))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("cache_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let locale = options.locale;
+        let on = t!("on", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        let list = t!("cache_list", locale = locale).to_string();
+        let clear = t!("cache_clear", locale = locale).to_string();
+        vec![on, off, list, clear]
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.as_str();
+        let on = t!("on", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        let list = t!("cache_list", locale = locale).to_string();
+        let clear = t!("cache_clear", locale = locale).to_string();
+
+        if options.input.len() <= 1 {
+            let setting = if configuration.results_cache { on } else { off };
+            let cache_setting = t!("cache_setting", locale = locale, setting = setting).to_string();
+            writeln!(options.output, "{cache_setting}")?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let argument = options.input[1].to_lowercase();
+        if argument == on {
+            configuration.results_cache = true;
+        } else if argument == off {
+            configuration.results_cache = false;
+            options.state.cache.clear();
+        } else if argument == list {
+            let start = std::time::Instant::now();
+            let cache = &mut options.state.cache;
+            cache.evict_expired(configuration.results_cache_ttl);
+            let columns = vec![
+                t!("cache_statement", locale = locale).to_string(),
+                t!("cache_hits", locale = locale).to_string(),
+                t!("cache_age", locale = locale).to_string(),
+            ];
+            let rows = cache
+                .entries()
+                .iter()
+                .map(|entry| {
+                    vec![
+                        Value::String(entry.statement().to_string()),
+                        Value::U64(entry.hits()),
+                        Value::String(format!("{}s", entry.age().as_secs())),
+                    ]
+                })
+                .collect();
+
+            let query_result = MemoryQueryResult::new(columns, rows);
+            let mut results = Results::Query(Box::new(query_result));
+            let formatter_options = &mut configuration.get_formatter_options();
+            let result_format = &configuration.results_format;
+            let formatter = options.formatter_manager.get(result_format).ok_or(
+                rsql_formatters::Error::UnknownFormat {
+                    format: result_format.to_string(),
+                },
+            )?;
+
+            formatter_options.elapsed = start.elapsed();
+            formatter
+                .format(formatter_options, &mut results, options.output)
+                .await?;
+        } else if argument == clear {
+            let entries = options.state.cache.clear();
+            let cache_cleared = t!("cache_cleared", locale = locale, entries = entries).to_string();
+            writeln!(options.output, "{cache_cleared}")?;
+        } else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: argument,
+            });
+        }
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::{ColumnSelection, Configuration};
+    use crate::shell::{CacheKey, ShellState};
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "cache");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "on|off|list|clear");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Enable or disable the query result cache, or list or evict the cached results"
+        );
+    }
+
+    async fn execute(
+        configuration: &mut Configuration,
+        state: &mut ShellState,
+        input: &[&str],
+    ) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        Ok(output.to_string())
+    }
+
+    fn cache_result(state: &mut ShellState) {
+        let key = CacheKey::new("rusqlite://", "SELECT 1", 100, &ColumnSelection::All);
+        state.cache.insert(key, MemoryQueryResult::default());
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let state = &mut ShellState::default();
+        let output = execute(configuration, state, &[".cache"]).await?;
+        assert_eq!(output, "Cache: off\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_on_off() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let state = &mut ShellState::default();
+        execute(configuration, state, &[".cache", "on"]).await?;
+        assert!(configuration.results_cache);
+
+        cache_result(state);
+        execute(configuration, state, &[".cache", "off"]).await?;
+        assert!(!configuration.results_cache);
+        assert!(state.cache.entries().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_list() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let state = &mut ShellState::default();
+        cache_result(state);
+        let output = execute(configuration, state, &[".cache", "list"]).await?;
+        let output = output.replace("\r\n", "\n");
+        assert!(output.starts_with("\"statement\",\"hits\",\"age\"\n\"SELECT 1\",0,"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_clear() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let state = &mut ShellState::default();
+        cache_result(state);
+        let output = execute(configuration, state, &[".cache", "clear"]).await?;
+        assert_eq!(output, "Evicted 1 cached results\n");
+        assert!(state.cache.entries().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let state = &mut ShellState::default();
+        let result = execute(configuration, state, &[".cache", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, QueryResult, Row, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+use sha2::{Digest, Sha256};
+use std::time::Instant;
+
+/// Option to compute the checksum of every row, identified by the key columns
+const KEY_OPTION: &str = "--key";
+
+/// Compute an order independent checksum of the contents of a table, or the checksum of every
+/// row when key columns are specified
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("checksum_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("checksum_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("checksum_example_arguments", locale = locale, key = KEY_OPTION))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("checksum_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let locale = options.configuration.locale.clone();
+        let locale = locale.as_str();
+
+        let Some(table) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        let keys: Vec<String> = match &options.input[2..] {
+            [] => Vec::new(),
+            [option, keys] if option == KEY_OPTION => keys
+                .split(',')
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .collect(),
+            arguments => {
+                return Err(InvalidOption {
+                    command_name: self.name(locale),
+                    option: arguments.join(" "),
+                })
+            }
+        };
+
+        let (columns, rows) = if keys.is_empty() {
+            let sql = format!("SELECT * FROM {table}");
+            let query_result = options.connection.query(&sql).await?;
+            let (rows, checksum) = table_checksum(query_result).await;
+            let columns = vec![
+                t!("checksum_rows", locale = locale).to_string(),
+                t!("checksum_column", locale = locale).to_string(),
+            ];
+            (columns, vec![vec![Value::U64(rows), Value::String(checksum)]])
+        } else {
+            let sql = format!("SELECT * FROM {table} ORDER BY {}", keys.join(", "));
+            let query_result = options.connection.query(&sql).await?;
+            let Some(rows) = row_checksums(query_result, &keys).await else {
+                return Err(InvalidOption {
+                    command_name: self.name(locale),
+                    option: keys.join(","),
+                });
+            };
+            let mut columns = keys.clone();
+            columns.push(t!("checksum_column", locale = locale).to_string());
+            (columns, rows)
+        };
+
+        let query_result = MemoryQueryResult::new(columns, rows);
+        options.state.set_last_result(query_result.clone());
+
+        let configuration = options.configuration;
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Get the number of rows and the checksum of the query result; the checksum is the sum of the
+/// row hashes, so it does not depend on the order of the rows.
+async fn table_checksum(mut query_result: Box<dyn QueryResult>) -> (u64, String) {
+    let mut rows = 0;
+    let mut checksum = 0u128;
+    while let Some(row) = query_result.next().await {
+        rows += 1;
+        checksum = checksum.wrapping_add(row_hash(&row));
+    }
+    (rows, format!("{checksum:032x}"))
+}
+
+/// Get the values of the key columns and the checksum of every row of the query result; returns
+/// `None` if a key column does not exist.
+async fn row_checksums(
+    mut query_result: Box<dyn QueryResult>,
+    keys: &[String],
+) -> Option<Vec<Row>> {
+    let columns = query_result.columns().await;
+    let indexes = keys
+        .iter()
+        .map(|key| {
+            columns
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(key))
+        })
+        .collect::<Option<Vec<usize>>>()?;
+    let mut rows = Vec::new();
+    while let Some(row) = query_result.next().await {
+        let mut checksum_row: Vec<Value> = indexes
+            .iter()
+            .map(|index| row.get(*index).cloned().unwrap_or(Value::Null))
+            .collect();
+        checksum_row.push(Value::String(format!("{:032x}", row_hash(&row))));
+        rows.push(checksum_row);
+    }
+    Some(rows)
+}
+
+/// Hash the values of a row; values are hashed by their text representation so that the same
+/// data has the same hash when it is read with different drivers, e.g. when an `INTEGER` column
+/// is read as a 32-bit value by one driver and as a 64-bit value by another driver.
+fn row_hash(row: &[Value]) -> u128 {
+    let mut hasher = Sha256::new();
+    for value in row {
+        if value.is_null() {
+            hasher.update([0]);
+        } else {
+            let value = value.to_string();
+            hasher.update([1]);
+            hasher.update((value.len() as u64).to_le_bytes());
+            hasher.update(value.as_bytes());
+        }
+    }
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    u128::from_be_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "checksum");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<table> [--key <columns>]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Compute a checksum of the contents of a table to verify copies of the table"
+        );
+    }
+
+    fn users(reverse: bool) -> MemoryQueryResult {
+        let mut rows = vec![
+            vec![Value::I32(1), Value::String("foo".to_string())],
+            vec![Value::I32(2), Value::Null],
+        ];
+        if reverse {
+            rows.reverse();
+        }
+        MemoryQueryResult::new(vec!["id".to_string(), "name".to_string()], rows)
+    }
+
+    async fn execute(
+        state: &mut ShellState,
+        connection: &mut MockConnection,
+        input: &[&str],
+    ) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                results_format: "csv".to_string(),
+                results_footer: false,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state,
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        Command.execute(options).await?;
+        Ok(output.to_string().replace("\r\n", "\n"))
+    }
+
+    #[tokio::test]
+    async fn test_execute_order_independent() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_query()
+            .withf(|sql| sql == "SELECT * FROM users")
+            .returning(|_| Ok(Box::new(users(false))));
+        let mut reversed_connection = MockConnection::new();
+        reversed_connection
+            .expect_query()
+            .returning(|_| Ok(Box::new(users(true))));
+
+        let output = execute(state, &mut connection, &[".checksum", "users"]).await?;
+        let reversed_output =
+            execute(state, &mut reversed_connection, &[".checksum", "users"]).await?;
+
+        assert!(output.starts_with("\"rows\",\"checksum\"\n2,\""));
+        assert_eq!(output, reversed_output);
+        assert!(state.previous_result.is_some());
+        assert!(state.last_result.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_key() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_query()
+            .withf(|sql| sql == "SELECT * FROM users ORDER BY id")
+            .returning(|_| Ok(Box::new(users(false))));
+
+        let input = [".checksum", "users", "--key", "id"];
+        let output = execute(state, &mut connection, &input).await?;
+
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "\"id\",\"checksum\"");
+        assert!(lines[1].starts_with("1,\""));
+        assert!(lines[2].starts_with("2,\""));
+        assert_ne!(lines[1][2..], lines[2][2..]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_errors() {
+        let state = &mut ShellState::default();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_query()
+            .returning(|_| Ok(Box::new(users(false))));
+        assert!(execute(state, &mut connection, &[".checksum"])
+            .await
+            .is_err());
+        assert!(execute(state, &mut connection, &[".checksum", "users", "--foo"])
+            .await
+            .is_err());
+        assert!(
+            execute(state, &mut connection, &[".checksum", "users", "--key", "email"])
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_row_hash() {
+        let row = vec![Value::I32(1), Value::String("foo".to_string())];
+        assert_eq!(
+            row_hash(&row),
+            row_hash(&[Value::I64(1), Value::String("foo".to_string())])
+        );
+        assert_ne!(row_hash(&row), row_hash(&[Value::I32(1), Value::Null]));
+        assert_ne!(
+            row_hash(&[Value::Null]),
+            row_hash(&[Value::String("null".to_string())])
+        );
+        assert_ne!(
+            row_hash(&[Value::String("ab".to_string()), Value::String("c".to_string())]),
+            row_hash(&[Value::String("a".to_string()), Value::String("bc".to_string())])
+        );
+    }
+}
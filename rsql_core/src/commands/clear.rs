@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rust_i18n::t;
 
@@ -12,6 +12,10 @@ impl ShellCommand for Command {
         t!("clear_command", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("clear_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("clear_description", locale = locale).to_string()
     }
@@ -0,0 +1,187 @@
+use crate::commands::last::write_no_result;
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::writers::{ClipboardWriter, Output};
+use async_trait::async_trait;
+use rsql_formatters::Results;
+use rust_i18n::t;
+use std::io::Write;
+
+/// Copy the result of the last query to the clipboard, formatted with the results format or the
+/// specified format
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("clip_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("clip_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("clip_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("clip_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .formatter_manager
+            .iter()
+            .map(|formatter| formatter.identifier().to_string())
+            .collect()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let Some(last_result) = options.state.last_result.clone() else {
+            return write_no_result(&options.configuration.locale, options.output);
+        };
+
+        let result_format = options.input.get(1).map_or_else(
+            || options.configuration.results_format.clone(),
+            |format| format.to_lowercase(),
+        );
+        let Some(formatter) = options.formatter_manager.get(&result_format) else {
+            return Err(InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: result_format,
+            });
+        };
+
+        // Colors, the terminal width and the timer are not useful when the result is pasted into
+        // other documents
+        let mut formatter_options = options.configuration.get_formatter_options();
+        formatter_options.color = false;
+        formatter_options.expanded_width = None;
+        formatter_options.timer = false;
+        let mut results = Results::Query(Box::new(last_result));
+        let mut clipboard = Output::new(Box::<ClipboardWriter>::default());
+        formatter
+            .format(&formatter_options, &mut results, &mut clipboard)
+            .await?;
+        if let Results::Query(query_result) = &mut results {
+            if let Some(error) = query_result.take_error() {
+                return Err(error.into());
+            }
+        }
+        clipboard.flush()?;
+
+        let clip_copied = t!("clip_copied", locale = locale).to_string();
+        writeln!(options.output, "{clip_copied}")?;
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use rsql_drivers::{DriverManager, MemoryQueryResult, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "clip");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[format]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Copy the result of the last query to the clipboard"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_result() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".clip".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string(), "No query result is available\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_format() {
+        let mut output = Output::default();
+        let state = &mut ShellState {
+            last_result: Some(MemoryQueryResult::new(vec!["id".to_string()], vec![])),
+            ..Default::default()
+        };
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".clip".to_string(), "foo".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let state = &mut ShellState {
+            last_result: Some(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![vec![rsql_drivers::Value::I64(42)]],
+            )),
+            ..Default::default()
+        };
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".clip".to_string(), "csv".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string(), "Result copied to the clipboard\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,210 @@
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::configuration::ColumnSelection;
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to select the columns of query results that are displayed, without changing the SQL
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("columns_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        let show = t!("columns_show", locale = locale).to_string();
+        let hide = t!("columns_hide", locale = locale).to_string();
+        let all = t!("columns_all", locale = locale).to_string();
+        t!(
+            "columns_argument",
+            locale = locale,
+            show = show,
+            hide = hide,
+            all = all
+        )
+        .to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let show = t!("columns_show", locale = locale).to_string();
+        let hide = t!("columns_hide", locale = locale).to_string();
+        let all = t!("columns_all", locale = locale).to_string();
+        split_examples(&t!(
+            "columns_example_arguments",
+            locale = locale,
+            show = show,
+            hide = hide,
+            all = all,
+        ))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("columns_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let locale = options.locale;
+        vec![
+            t!("columns_show", locale = locale).to_string(),
+            t!("columns_hide", locale = locale).to_string(),
+            t!("columns_all", locale = locale).to_string(),
+        ]
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let show = t!("columns_show", locale = locale).to_string();
+        let hide = t!("columns_hide", locale = locale).to_string();
+        let all = t!("columns_all", locale = locale).to_string();
+
+        let Some(option) = options.input.get(1) else {
+            let columns = match &options.configuration.results_columns {
+                ColumnSelection::All => all,
+                ColumnSelection::Show(columns) => format!("{show} {}", columns.join(", ")),
+                ColumnSelection::Hide(columns) => format!("{hide} {}", columns.join(", ")),
+            };
+            let columns_setting = t!("columns_setting", locale = locale, columns = columns);
+            writeln!(options.output, "{columns_setting}")?;
+            return Ok(LoopCondition::Continue);
+        };
+
+        // Columns may be separated by commas, spaces, or both
+        let columns: Vec<String> = options.input[2..]
+            .join(",")
+            .split(',')
+            .map(str::trim)
+            .filter(|column| !column.is_empty())
+            .map(ToString::to_string)
+            .collect();
+        let selection = if option.eq_ignore_ascii_case(&all) {
+            ColumnSelection::All
+        } else if option.eq_ignore_ascii_case(&show) || option.eq_ignore_ascii_case(&hide) {
+            if columns.is_empty() {
+                return Err(MissingArguments {
+                    command_name: self.name(locale),
+                    arguments: self.args(locale),
+                });
+            }
+            if option.eq_ignore_ascii_case(&show) {
+                ColumnSelection::Show(columns)
+            } else {
+                ColumnSelection::Hide(columns)
+            }
+        } else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: option.to_string(),
+            });
+        };
+        options.configuration.results_columns = selection;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{execute, CommandManager};
+    use crate::configuration::Configuration;
+    use rsql_drivers::Metadata;
+    use rsql_formatters::FormatterManager;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "columns");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[show <columns>|hide <columns>|all]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Select the columns of query results to display, without changing the SQL"
+        );
+    }
+
+    #[test]
+    fn test_completions() {
+        let options = CompletionOptions {
+            locale: "en",
+            command_manager: &CommandManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            metadata: &Metadata::default(),
+        };
+        assert_eq!(Command.completions(&options), vec!["show", "hide", "all"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let output = execute(&Command, configuration, &[".columns"]).await?;
+        assert_eq!(output, "Columns: all\n");
+
+        configuration.results_columns =
+            ColumnSelection::Hide(vec!["id".to_string(), "name".to_string()]);
+        let output = execute(&Command, configuration, &[".columns"]).await?;
+        assert_eq!(output, "Columns: hide id, name\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_show_and_hide() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(
+            &Command,
+            configuration,
+            &[".columns", "show", "id,", "name,email"],
+        )
+        .await?;
+        assert_eq!(
+            configuration.results_columns,
+            ColumnSelection::Show(vec![
+                "id".to_string(),
+                "name".to_string(),
+                "email".to_string()
+            ])
+        );
+
+        let _ = execute(
+            &Command,
+            configuration,
+            &[".columns", "hide", "description"],
+        )
+        .await?;
+        assert_eq!(
+            configuration.results_columns,
+            ColumnSelection::Hide(vec!["description".to_string()])
+        );
+
+        let _ = execute(&Command, configuration, &[".columns", "all"]).await?;
+        assert_eq!(configuration.results_columns, ColumnSelection::All);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_columns() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".columns", "show"]).await;
+        assert!(matches!(result, Err(MissingArguments { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".columns", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
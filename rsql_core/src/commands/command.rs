@@ -1,8 +1,9 @@
 use crate::commands::error::Result;
 use crate::commands::Error::InvalidOption;
 use crate::configuration::Configuration;
+use crate::shell::ShellState;
 use async_trait::async_trait;
-use rsql_drivers::{Connection, DriverManager};
+use rsql_drivers::{Connection, DriverManager, Metadata};
 use rsql_formatters::writers::Output;
 use rsql_formatters::FormatterManager;
 use rustyline::history::DefaultHistory;
@@ -25,6 +26,7 @@ pub struct CommandOptions<'a> {
     pub driver_manager: &'a DriverManager,
     pub formatter_manager: &'a FormatterManager,
     pub history: &'a DefaultHistory,
+    pub state: &'a mut ShellState,
     pub connection: &'a mut dyn Connection,
     pub input: Vec<String>,
     pub output: &'a mut Output,
@@ -38,29 +40,53 @@ impl Debug for CommandOptions<'_> {
             .field("driver_manager", &self.driver_manager)
             .field("formatter_manager", &self.formatter_manager)
             .field("connection", &self.connection)
+            .field("state", &self.state)
             .field("output", &self.output)
             .field("input", &self.input)
             .finish()
     }
 }
 
+/// Options for completing the arguments of commands
+#[derive(Debug)]
+pub struct CompletionOptions<'a> {
+    pub locale: &'a str,
+    pub command_manager: &'a CommandManager,
+    pub formatter_manager: &'a FormatterManager,
+    pub metadata: &'a Metadata,
+}
+
 /// Trait that defines a command
 #[async_trait]
-pub trait ShellCommand: Debug + Sync {
+pub trait ShellCommand: Debug + Send + Sync {
     /// Get the name of the command
     fn name(&self, locale: &str) -> String;
     /// Get the arguments for the command
     fn args(&self, _locale: &str) -> String {
         String::new()
     }
+    /// Get the examples for the command; each example contains the arguments passed to the command
+    fn examples(&self, _locale: &str) -> Vec<String> {
+        Vec::new()
+    }
     /// Get the description of the command
     fn description(&self, locale: &str) -> String;
+    /// Get the values used to complete the first argument of the command
+    fn completions(&self, _options: &CompletionOptions<'_>) -> Vec<String> {
+        Vec::new()
+    }
     /// Execute the command
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition>;
 }
 
+/// Split the example arguments of a command, read from the locale, into the examples; each line
+/// contains the arguments of an example and an empty line is an example without arguments
+pub(crate) fn split_examples(examples: &str) -> Vec<String> {
+    examples.split('\n').map(ToString::to_string).collect()
+}
+
 #[async_trait]
-pub trait ToggleShellCommand: Debug + Sync {
+pub trait ToggleShellCommand: Debug + Send + Sync {
     fn get_value(&self, options: &CommandOptions<'_>) -> bool;
     fn set_value(&self, options: &mut CommandOptions<'_>, value: bool);
 
@@ -79,9 +105,19 @@ impl<T: ToggleShellCommand> ShellCommand for T {
         let off = t!("off", locale = locale).to_string();
         t!("on_off_argument", locale = locale, on = on, off = off).to_string()
     }
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let on = t!("on", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        vec![String::new(), on, off]
+    }
     fn description(&self, locale: &str) -> String {
         t!(self.get_description(), locale = locale).to_string()
     }
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let on = t!("on", locale = options.locale).to_string();
+        let off = t!("off", locale = options.locale).to_string();
+        vec![on, off]
+    }
     async fn execute<'a>(&self, mut options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
         let on = t!("on", locale = locale).to_string();
@@ -175,34 +211,77 @@ impl Default for CommandManager {
     fn default() -> Self {
         let mut commands = CommandManager::new();
 
+        commands.add(Box::new(crate::commands::ask::Command));
+        commands.add(Box::new(crate::commands::asof::Command));
+        commands.add(Box::new(crate::commands::attach::Command));
         commands.add(Box::new(crate::commands::bail::Command));
+        commands.add(Box::new(crate::commands::bench::Command));
+        commands.add(Box::new(crate::commands::bytes::Command));
+        commands.add(Box::new(crate::commands::cache::Command));
         commands.add(Box::new(crate::commands::changes::Command));
+        commands.add(Box::new(crate::commands::checksum::Command));
         commands.add(Box::new(crate::commands::clear::Command));
+        commands.add(Box::new(crate::commands::clip::Command));
         commands.add(Box::new(crate::commands::color::Command));
+        commands.add(Box::new(crate::commands::columns::Command));
+        commands.add(Box::new(crate::commands::compare::Command));
         commands.add(Box::new(crate::commands::completions::Command));
+        commands.add(Box::new(crate::commands::connect::Command));
+        commands.add(Box::new(crate::commands::copyin::Command));
+        commands.add(Box::new(crate::commands::dateformat::Command));
+        commands.add(Box::new(crate::commands::ddl::Command));
+        commands.add(Box::new(crate::commands::demo::Command));
         commands.add(Box::new(crate::commands::describe::Command));
+        commands.add(Box::new(crate::commands::diff::Command));
         commands.add(Box::new(crate::commands::drivers::Command));
         commands.add(Box::new(crate::commands::echo::Command));
+        commands.add(Box::new(crate::commands::edit::Command));
         commands.add(Box::new(crate::commands::exit::Command));
+        commands.add(Box::new(crate::commands::expanded::Command));
+        commands.add(Box::new(crate::commands::export::Command));
+        commands.add(Box::new(crate::commands::filter::Command));
         commands.add(Box::new(crate::commands::footer::Command));
         commands.add(Box::new(crate::commands::format::Command));
+        commands.add(Box::new(crate::commands::generate::Command));
         commands.add(Box::new(crate::commands::header::Command));
         commands.add(Box::new(crate::commands::help::Command));
         commands.add(Box::new(crate::commands::history::Command));
         commands.add(Box::new(crate::commands::indexes::Command));
+        commands.add(Box::new(crate::commands::keys::Command));
+        commands.add(Box::new(crate::commands::last::Command));
         commands.add(Box::new(crate::commands::limit::Command));
+        commands.add(Box::new(crate::commands::listen::Command));
         commands.add(Box::new(crate::commands::locale::Command));
+        commands.add(Box::new(crate::commands::migrate::Command));
         commands.add(Box::new(crate::commands::output::Command));
+        commands.add(Box::new(crate::commands::parallel::Command));
         commands.add(Box::new(crate::commands::print::Command));
+        commands.add(Box::new(crate::commands::profile::Command));
         commands.add(Box::new(crate::commands::quit::Command));
         commands.add(Box::new(crate::commands::read::Command));
+        commands.add(Box::new(crate::commands::readonly::Command));
+        commands.add(Box::new(crate::commands::refresh::Command));
         commands.add(Box::new(crate::commands::rows::Command));
+        commands.add(Box::new(crate::commands::sample::Command));
         commands.add(Box::new(crate::commands::schemas::Command));
+        commands.add(Box::new(crate::commands::send::Command));
+        commands.add(Box::new(crate::commands::session::Command));
         commands.add(Box::new(crate::commands::sleep::Command));
+        commands.add(Box::new(crate::commands::snapshot::Command));
+        commands.add(Box::new(crate::commands::sort::Command));
+        commands.add(Box::new(crate::commands::stash::Command));
+        commands.add(Box::new(crate::commands::stream::Command));
+        commands.add(Box::new(crate::commands::sync::Command));
         commands.add(Box::new(crate::commands::system::Command));
         commands.add(Box::new(crate::commands::tables::Command));
         commands.add(Box::new(crate::commands::tee::Command));
+        commands.add(Box::new(crate::commands::theme::Command));
+        commands.add(Box::new(crate::commands::timeout::Command));
         commands.add(Box::new(crate::commands::timer::Command));
+        commands.add(Box::new(crate::commands::truncate::Command));
+        commands.add(Box::new(crate::commands::tutorial::Command));
+        commands.add(Box::new(crate::commands::validate::Command));
+        commands.add(Box::new(crate::commands::view::Command));
 
         commands
     }
@@ -223,6 +302,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &FileHistory::default(),
+            state: &mut ShellState::default(),
             input: vec!["42".to_string()],
             output: &mut Output::default(),
         };
@@ -280,6 +360,31 @@ mod tests {
     fn test_command_manager_default() {
         let command_manager = CommandManager::default();
 
-        assert_eq!(command_manager.commands.len(), 28);
+        assert_eq!(command_manager.commands.len(), 71);
+    }
+
+    #[test]
+    fn test_split_examples() {
+        assert_eq!(split_examples(""), vec![String::new()]);
+        assert_eq!(
+            split_examples("\non\noff"),
+            vec![String::new(), "on".to_string(), "off".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_command_examples() {
+        let command_manager = CommandManager::default();
+        for locale in crate::locales::available_locales() {
+            for command in command_manager.iter() {
+                let name = command.name(&locale);
+                let examples = command.examples(&locale);
+                assert!(!examples.is_empty(), "{locale} {name}");
+                for example in examples {
+                    assert!(!example.contains("example_arguments"), "{locale} {name}");
+                    assert!(!example.contains("%{"), "{locale} {name}: {example}");
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,374 @@
+use crate::commands::last::{column_index, columns_and_rows, write_no_result};
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, QueryResult, Row, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// The separator between the two queries to compare
+const QUERY_SEPARATOR: &str = "||";
+/// The prefix of the option with the names of the key columns
+const KEY_OPTION: &str = "key=";
+
+/// Compare the rows of two query results that are joined by their key columns, and report the
+/// rows that were added, removed or changed; when no queries are specified, the results of the
+/// last two queries are compared.
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("compare_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("compare_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("compare_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("compare_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let locale = options.configuration.locale.clone();
+        let locale = locale.as_str();
+
+        let mut arguments = &options.input[1..];
+        let mut keys = Vec::new();
+        if let Some(key_option) = arguments
+            .first()
+            .and_then(|argument| argument.strip_prefix(KEY_OPTION))
+        {
+            keys = key_option
+                .split(',')
+                .map(|key| key.trim().to_string())
+                .filter(|key| !key.is_empty())
+                .collect();
+            arguments = &arguments[1..];
+        }
+
+        let (first, second) = if arguments.is_empty() {
+            let (Some(first), Some(second)) = (
+                options.state.previous_result.clone(),
+                options.state.last_result.clone(),
+            ) else {
+                return write_no_result(&options.configuration.locale, options.output);
+            };
+            (
+                columns_and_rows(first).await?,
+                columns_and_rows(second).await?,
+            )
+        } else {
+            let sql = arguments.join(" ");
+            let Some((first_sql, second_sql)) = sql.split_once(QUERY_SEPARATOR) else {
+                return Err(MissingArguments {
+                    command_name: self.name(locale),
+                    arguments: self.args(locale),
+                });
+            };
+            let first = query(options.connection.query(first_sql.trim()).await?).await;
+            let second = query(options.connection.query(second_sql.trim()).await?).await;
+            (first, second)
+        };
+
+        let (first_columns, first_rows) = first;
+        let (second_columns, second_rows) = second;
+        if keys.is_empty() {
+            keys.extend(first_columns.first().cloned());
+        }
+        let mut first_keys = Vec::new();
+        let mut second_keys = Vec::new();
+        for key in &keys {
+            let (Some(first_key), Some(second_key)) = (
+                column_index(&first_columns, key),
+                column_index(&second_columns, key),
+            ) else {
+                return Err(InvalidOption {
+                    command_name: self.name(locale),
+                    option: key.to_string(),
+                });
+            };
+            first_keys.push(first_key);
+            second_keys.push(second_key);
+        }
+
+        let compared_columns: Vec<(usize, usize)> = first_columns
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !first_keys.contains(index))
+            .filter_map(|(index, column)| {
+                column_index(&second_columns, column).map(|second_index| (index, second_index))
+            })
+            .collect();
+        let first = Side {
+            columns: &first_columns,
+            keys: &first_keys,
+            rows: first_rows,
+        };
+        let second = Side {
+            columns: &second_columns,
+            keys: &second_keys,
+            rows: second_rows,
+        };
+        let rows = compare(locale, &first, &second, &compared_columns);
+
+        let mut columns = vec![t!("diff_change", locale = locale).to_string()];
+        columns.extend(first_keys.iter().map(|index| first_columns[*index].clone()));
+        columns.extend([
+            t!("diff_column", locale = locale).to_string(),
+            t!("diff_current", locale = locale).to_string(),
+            t!("diff_target", locale = locale).to_string(),
+        ]);
+
+        let configuration = options.configuration;
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// The columns, indexes of the key columns and rows of one of the compared results
+struct Side<'a> {
+    columns: &'a [String],
+    keys: &'a [usize],
+    rows: Vec<Row>,
+}
+
+impl Side<'_> {
+    /// Get the values of the key columns of the row
+    fn key(&self, row: &Row) -> Vec<Value> {
+        self.keys
+            .iter()
+            .map(|index| row.get(*index).cloned().unwrap_or(Value::Null))
+            .collect()
+    }
+}
+
+/// Read the columns and rows of a query result
+async fn query(mut query_result: Box<dyn QueryResult>) -> (Vec<String>, Vec<Row>) {
+    let columns = query_result.columns().await;
+    let mut rows = Vec::new();
+    while let Some(row) = query_result.next().await {
+        rows.push(row);
+    }
+    (columns, rows)
+}
+
+/// Compare the rows of the results that have the same key values; a row is reported for each
+/// row that was removed from the first result or added to the second result, and for each column
+/// value that changed.  When a key is not unique, the last row with the key is compared.
+fn compare(
+    locale: &str,
+    first: &Side<'_>,
+    second: &Side<'_>,
+    compared_columns: &[(usize, usize)],
+) -> Vec<Row> {
+    let added = t!("diff_added", locale = locale).to_string();
+    let removed = t!("diff_removed", locale = locale).to_string();
+    let changed = t!("diff_changed", locale = locale).to_string();
+    let difference = |change: &str, key: Vec<Value>, column: Value, current, target| {
+        let mut row = vec![Value::String(change.to_string())];
+        row.extend(key);
+        row.extend([column, current, target]);
+        row
+    };
+
+    let second_keys: HashMap<Vec<Value>, usize> = second
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| (second.key(row), index))
+        .collect();
+    let mut matched = vec![false; second.rows.len()];
+    let mut rows = Vec::new();
+    for first_row in &first.rows {
+        let key = first.key(first_row);
+        let Some(second_index) = second_keys.get(&key).copied() else {
+            rows.push(difference(&removed, key, Value::Null, Value::Null, Value::Null));
+            continue;
+        };
+        matched[second_index] = true;
+        let second_row = &second.rows[second_index];
+        for (first_index, second_index) in compared_columns {
+            let current = first_row.get(*first_index).cloned().unwrap_or(Value::Null);
+            let target = second_row.get(*second_index).cloned().unwrap_or(Value::Null);
+            if current != target {
+                let column = Value::String(first.columns[*first_index].clone());
+                rows.push(difference(&changed, key.clone(), column, current, target));
+            }
+        }
+    }
+    for (second_row, _) in second.rows.iter().zip(matched).filter(|(_, matched)| !matched) {
+        let key = second.key(second_row);
+        rows.push(difference(&added, key, Value::Null, Value::Null, Value::Null));
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "compare");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[key=<columns>] [<sql> || <sql>]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Compare two query results, or the last two results, by their key columns"
+        );
+    }
+
+    fn users(rows: Vec<Row>) -> MemoryQueryResult {
+        MemoryQueryResult::new(vec!["id".to_string(), "name".to_string()], rows)
+    }
+
+    async fn execute(
+        state: &mut ShellState,
+        connection: &mut MockConnection,
+        input: &[&str],
+    ) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                results_format: "csv".to_string(),
+                results_footer: false,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state,
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        Command.execute(options).await?;
+        Ok(output.to_string().replace("\r\n", "\n"))
+    }
+
+    #[tokio::test]
+    async fn test_execute_last_results() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+        state.set_last_result(users(vec![
+            vec![Value::I64(1), Value::String("foo".to_string())],
+            vec![Value::I64(2), Value::String("bar".to_string())],
+        ]));
+        state.set_last_result(users(vec![
+            vec![Value::I64(2), Value::String("baz".to_string())],
+            vec![Value::I64(3), Value::String("qux".to_string())],
+        ]));
+
+        let output = execute(state, &mut MockConnection::new(), &[".compare"]).await?;
+
+        assert_eq!(
+            output,
+            "\"change\",\"id\",\"column\",\"current\",\"target\"\n\
+             \"removed\",1,\"\",\"\",\"\"\n\
+             \"changed\",2,\"name\",\"bar\",\"baz\"\n\
+             \"added\",3,\"\",\"\",\"\"\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_queries() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection.expect_query().returning(|sql| {
+            let name = if sql.contains("users_copy") {
+                "bar"
+            } else {
+                "foo"
+            };
+            Ok(Box::new(users(vec![vec![
+                Value::I64(1),
+                Value::String(name.to_string()),
+            ]])))
+        });
+        let state = &mut ShellState::default();
+
+        let output = execute(
+            state,
+            &mut connection,
+            &[
+                ".compare",
+                "key=name",
+                "SELECT * FROM users",
+                "||",
+                "SELECT * FROM users_copy",
+            ],
+        )
+        .await?;
+
+        assert_eq!(
+            output,
+            "\"change\",\"name\",\"column\",\"current\",\"target\"\n\
+             \"removed\",\"foo\",\"\",\"\",\"\"\n\
+             \"added\",\"bar\",\"\",\"\",\"\"\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_results() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+        let output = execute(state, &mut MockConnection::new(), &[".compare"]).await?;
+        assert_eq!(output, "No query result is available\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_errors() {
+        let state = &mut ShellState::default();
+        state.set_last_result(users(Vec::new()));
+        state.set_last_result(users(Vec::new()));
+        let connection = &mut MockConnection::new();
+        assert!(execute(state, connection, &[".compare", "key=email"])
+            .await
+            .is_err());
+        assert!(execute(state, connection, &[".compare", "SELECT 1"])
+            .await
+            .is_err());
+    }
+}
@@ -33,6 +33,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions, ShellCommand};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -70,6 +71,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".completions".to_string()],
             output: &mut output,
         };
@@ -110,6 +112,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".completions".to_string(), "on".to_string()],
             output: &mut Output::default(),
         };
@@ -134,6 +137,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".completions".to_string(), "off".to_string()],
             output: &mut Output::default(),
         };
@@ -154,6 +158,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".completions".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
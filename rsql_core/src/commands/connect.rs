@@ -0,0 +1,212 @@
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+use std::time::Duration;
+
+/// Connect to a database using a connection profile defined in the configuration file or a url;
+/// the settings of the profile are applied to the configuration.  Lists the connection profiles
+/// when no arguments are specified
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("connect_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("connect_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("connect_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("connect_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        if let Some(target) = options.input.get(1) {
+            let profile = options.configuration.profiles.get(target).cloned();
+            let url = profile
+                .as_ref()
+                .map_or(target.as_str(), |profile| profile.url.as_str());
+            let connection = options.driver_manager.connect(url).await?;
+            if let Some(profile) = &profile {
+                profile.apply(options.configuration);
+            }
+            options.state.read_only = false;
+            options.state.timeout = Duration::ZERO;
+            options.state.timeout_enforced = false;
+            options.state.connection = Some(connection);
+            return Ok(LoopCondition::Continue);
+        }
+
+        let start = std::time::Instant::now();
+        let configuration = options.configuration;
+        let locale = &configuration.locale;
+        let columns = vec![
+            t!("connect_name", locale = locale).to_string(),
+            t!("connect_url", locale = locale).to_string(),
+        ];
+        let rows = configuration
+            .profiles
+            .iter()
+            .map(|(name, profile)| {
+                vec![
+                    Value::String(name.to_string()),
+                    Value::String(profile.url.to_string()),
+                ]
+            })
+            .collect();
+
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::{Configuration, Profile};
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "connect");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[profile|url]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Connect to a database using a connection profile or url, or list the connection profiles"
+        );
+    }
+
+    fn configuration() -> Configuration {
+        let mut configuration = Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        configuration.profiles.insert(
+            "local".to_string(),
+            Profile {
+                url: "rusqlite://".to_string(),
+                results_limit: Some(10),
+                read_only: Some(true),
+                ..Default::default()
+            },
+        );
+        configuration
+    }
+
+    #[tokio::test]
+    async fn test_execute_list() -> anyhow::Result<()> {
+        let configuration = &mut configuration();
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".connect".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string().replace("\r\n", "\n"),
+            "\"name\",\"url\"\n\"local\",\"rusqlite://\"\n"
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "driver-rusqlite")]
+    #[tokio::test]
+    async fn test_execute_profile() -> anyhow::Result<()> {
+        let configuration = &mut configuration();
+        let state = &mut ShellState::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".connect".to_string(), "local".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(configuration.results_limit, 10);
+        assert!(configuration.read_only);
+        let mut connection = state.connection.take().expect("connection");
+        assert_eq!(connection.url(), "rusqlite://");
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_url() {
+        let configuration = &mut configuration();
+        let state = &mut ShellState::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".connect".to_string(), "foo://bar".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+        assert!(state.connection.is_none());
+        assert!(!configuration.read_only);
+    }
+}
@@ -0,0 +1,366 @@
+use crate::commands::Error::{InvalidOption, IoError, MissingArguments, ReadOnly};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream};
+use rust_i18n::t;
+use std::fs::File;
+use std::io::Read;
+
+/// The size of the chunks that are read from the file and sent to the database
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Command to bulk load a CSV or TSV file into a table using the copy protocol of the database
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("copyin_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("copyin_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("copyin_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("copyin_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        if options.configuration.read_only {
+            return Err(ReadOnly {
+                command_name: self.name(locale),
+            });
+        }
+
+        let (Some(table), Some(file)) = (options.input.get(1), options.input.get(2)) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale).to_string(),
+                arguments: self.args(locale).to_string(),
+            });
+        };
+
+        let copy_options =
+            CopyOptions::parse(file, &options.input[3..]).map_err(|option| InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option,
+            })?;
+        let sql = copy_options.statement(table);
+
+        let reader: Box<dyn Read + Send> = if file == "-" {
+            Box::new(std::io::stdin())
+        } else {
+            Box::new(File::open(file)?)
+        };
+        let bulk_copy = options
+            .connection
+            .as_bulk_copy()
+            .ok_or_else(|| IoError(anyhow!("copy is not supported for this connection")))?;
+        let rows = bulk_copy.copy_in(&sql, read_chunks(reader)).await?;
+
+        let copyin_rows = t!("copyin_rows", locale = locale, rows = rows).to_string();
+        writeln!(options.output, "{copyin_rows}")?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Options for the format of the data that is copied
+#[derive(Debug, PartialEq)]
+struct CopyOptions {
+    delimiter: char,
+    header: bool,
+    null: Option<String>,
+}
+
+impl CopyOptions {
+    /// Parse the options; the delimiter defaults to a tab for `.tsv` files and a comma otherwise
+    fn parse(file: &str, options: &[String]) -> core::result::Result<Self, String> {
+        let delimiter = if file.to_lowercase().ends_with(".tsv") {
+            '\t'
+        } else {
+            ','
+        };
+        let mut copy_options = CopyOptions {
+            delimiter,
+            header: false,
+            null: None,
+        };
+
+        for option in options {
+            match option.split_once('=') {
+                None if option.eq_ignore_ascii_case("csv") => copy_options.delimiter = ',',
+                None if option.eq_ignore_ascii_case("tsv") => copy_options.delimiter = '\t',
+                None if option.eq_ignore_ascii_case("header") => copy_options.header = true,
+                Some((name, value)) if name.eq_ignore_ascii_case("delimiter") => {
+                    let mut chars = value.chars();
+                    copy_options.delimiter = match (chars.next(), chars.next()) {
+                        (Some(delimiter), None) => delimiter,
+                        _ => return Err(option.to_string()),
+                    };
+                }
+                Some((name, value)) if name.eq_ignore_ascii_case("null") => {
+                    copy_options.null = Some(value.to_string());
+                }
+                _ => return Err(option.to_string()),
+            }
+        }
+
+        Ok(copy_options)
+    }
+
+    /// Get the copy statement for the table
+    fn statement(&self, table: &str) -> String {
+        let delimiter = quote(&self.delimiter.to_string());
+        let mut sql = format!(
+            "COPY {table} FROM STDIN WITH (FORMAT csv, DELIMITER {delimiter}, HEADER {}",
+            self.header
+        );
+        if let Some(null) = &self.null {
+            sql.push_str(&format!(", NULL {}", quote(null)));
+        }
+        sql.push(')');
+        sql
+    }
+}
+
+/// Quote the value as a SQL string literal
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Read the data in chunks so that large files are streamed to the database
+fn read_chunks(reader: Box<dyn Read + Send>) -> BoxStream<'static, rsql_drivers::Result<Vec<u8>>> {
+    let chunks = stream::unfold(Some(reader), |reader| async move {
+        let mut reader = reader?;
+        let mut buffer = vec![0; CHUNK_SIZE];
+        match reader.read(&mut buffer) {
+            Ok(0) => None,
+            Ok(size) => {
+                buffer.truncate(size);
+                Some((Ok(buffer), Some(reader)))
+            }
+            Err(error) => Some((Err(rsql_drivers::Error::IoError(error.into())), None)),
+        }
+    });
+    Box::pin(chunks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CommandManager, CommandOptions};
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use futures_util::StreamExt;
+    use mockall::predicate::{always, eq};
+    use rsql_drivers::{
+        Connection, DriverManager, MockBulkCopy, MockCapableConnection, MockConnection,
+    };
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "copyin");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(
+            args,
+            "<table> <file|-> [csv|tsv] [header] [delimiter=<char>] [null=<string>]"
+        );
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Bulk load a CSV or TSV file, or stdin, into a table"
+        );
+    }
+
+    #[test]
+    fn test_copy_options_default() -> anyhow::Result<()> {
+        let options = CopyOptions::parse("users.csv", &[]).map_err(anyhow::Error::msg)?;
+        assert_eq!(
+            options.statement("users"),
+            "COPY users FROM STDIN WITH (FORMAT csv, DELIMITER ',', HEADER false)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_options_tsv() -> anyhow::Result<()> {
+        let options =
+            CopyOptions::parse("users.TSV", &["header".to_string(), "null=\\N".to_string()])
+                .map_err(anyhow::Error::msg)?;
+        assert_eq!(
+            options.statement("users"),
+            "COPY users FROM STDIN WITH (FORMAT csv, DELIMITER '\t', HEADER true, NULL '\\N')"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_options_delimiter() -> anyhow::Result<()> {
+        let options = CopyOptions::parse("-", &["tsv".to_string(), "delimiter='".to_string()])
+            .map_err(anyhow::Error::msg)?;
+        assert_eq!(options.delimiter, '\'');
+        assert_eq!(
+            options.statement("users(id, name)"),
+            "COPY users(id, name) FROM STDIN WITH (FORMAT csv, DELIMITER '''', HEADER false)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_copy_options_invalid() {
+        assert_eq!(
+            CopyOptions::parse("-", &["delimiter=ab".to_string()]),
+            Err("delimiter=ab".to_string())
+        );
+        assert_eq!(
+            CopyOptions::parse("-", &["foo".to_string()]),
+            Err("foo".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_chunks() {
+        let data = vec![b'a'; CHUNK_SIZE + 1];
+        let chunks: Vec<_> = read_chunks(Box::new(std::io::Cursor::new(data)))
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 2);
+    }
+
+    async fn execute(connection: &mut dyn Connection, input: Vec<String>) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input,
+            output: &mut output,
+        };
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        Ok(output.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut file = NamedTempFile::new()?;
+        write!(file, "id,name\n1,foo\n")?;
+        let path = file.as_ref().to_string_lossy().to_string();
+
+        let mut bulk_copy = MockBulkCopy::new();
+        bulk_copy
+            .expect_copy_in()
+            .with(
+                eq("COPY users FROM STDIN WITH (FORMAT csv, DELIMITER ',', HEADER true)"),
+                always(),
+            )
+            .returning(|_, _| Ok(1));
+        let mut connection = MockCapableConnection {
+            bulk_copy: Some(bulk_copy),
+            ..Default::default()
+        };
+        let output = execute(
+            &mut connection,
+            vec![
+                ".copyin".to_string(),
+                "users".to_string(),
+                path,
+                "header".to_string(),
+            ],
+        )
+        .await?;
+        assert_eq!(output, "Rows copied: 1\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_not_supported() -> anyhow::Result<()> {
+        let mut file = NamedTempFile::new()?;
+        write!(file, "id,name\n1,foo\n")?;
+        let path = file.as_ref().to_string_lossy().to_string();
+
+        let mut connection = MockConnection::new();
+        let result = execute(
+            &mut connection,
+            vec![".copyin".to_string(), "users".to_string(), path],
+        )
+        .await;
+        assert!(matches!(result, Err(IoError(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_read_only() {
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                read_only: true,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".copyin".to_string(), "users".to_string(), "-".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(matches!(result, Err(ReadOnly { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_arguments() {
+        let mut connection = MockConnection::new();
+        let result = execute(
+            &mut connection,
+            vec![".copyin".to_string(), "users".to_string()],
+        )
+        .await;
+        assert!(matches!(result, Err(MissingArguments { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let mut connection = MockConnection::new();
+        let result = execute(
+            &mut connection,
+            vec![
+                ".copyin".to_string(),
+                "users".to_string(),
+                "-".to_string(),
+                "foo".to_string(),
+            ],
+        )
+        .await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
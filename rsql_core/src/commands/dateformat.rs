@@ -0,0 +1,208 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::configuration::Configuration;
+use crate::writers::Output;
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// The kinds of values that have a date format
+const KINDS: [&str; 3] = ["date", "time", "datetime"];
+
+/// Command to set the strftime patterns used to display date, time and date time values
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("dateformat_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("dateformat_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("dateformat_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("dateformat_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, _options: &CompletionOptions<'_>) -> Vec<String> {
+        KINDS.iter().map(ToString::to_string).collect()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.clone();
+        let locale = locale.as_str();
+
+        let Some(kind) = options.input.get(1) else {
+            for kind in KINDS {
+                write_setting(options.configuration, options.output, kind)?;
+            }
+            return Ok(LoopCondition::Continue);
+        };
+
+        let kind = kind.to_lowercase();
+        if !KINDS.contains(&kind.as_str()) {
+            return Err(InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: kind,
+            });
+        }
+
+        if options.input.len() <= 2 {
+            write_setting(options.configuration, options.output, &kind)?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let pattern = options.input[2..].join(" ");
+        let default = t!("dateformat_default", locale = locale).to_string();
+        let pattern = if pattern.eq_ignore_ascii_case(&default) {
+            None
+        } else {
+            Some(pattern)
+        };
+        match kind.as_str() {
+            "date" => options.configuration.results_date_format = pattern,
+            "time" => options.configuration.results_time_format = pattern,
+            _ => options.configuration.results_datetime_format = pattern,
+        }
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Write the date format setting for the kind of value
+fn write_setting(configuration: &Configuration, output: &mut Output, kind: &str) -> Result<()> {
+    let locale = configuration.locale.as_str();
+    let pattern = match kind {
+        "date" => &configuration.results_date_format,
+        "time" => &configuration.results_time_format,
+        _ => &configuration.results_datetime_format,
+    };
+    let format = match pattern {
+        Some(pattern) => pattern.clone(),
+        None => t!("dateformat_default", locale = locale).to_string(),
+    };
+    let dateformat_setting = t!(
+        "dateformat_setting",
+        locale = locale,
+        kind = kind,
+        format = format
+    )
+    .to_string();
+    writeln!(output, "{dateformat_setting}")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{execute, CommandManager};
+    use indoc::indoc;
+    use rsql_drivers::Metadata;
+    use rsql_formatters::FormatterManager;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "dateformat");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[date|time|datetime] [pattern|default]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Set the patterns used to display dates and times"
+        );
+    }
+
+    #[test]
+    fn test_completions() {
+        let command_manager = CommandManager::default();
+        let formatter_manager = FormatterManager::default();
+        let metadata = Metadata::default();
+        let options = CompletionOptions {
+            locale: "en",
+            command_manager: &command_manager,
+            formatter_manager: &formatter_manager,
+            metadata: &metadata,
+        };
+        let completions = Command.completions(&options);
+        assert_eq!(completions, vec!["date", "time", "datetime"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_time_format: Some("%H:%M".to_string()),
+            ..Default::default()
+        };
+        let output = execute(&Command, configuration, &[".dateformat"]).await?;
+        let expected = indoc! {r"
+            Date format (date): default
+            Date format (time): %H:%M
+            Date format (datetime): default
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_kind() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let output = execute(&Command, configuration, &[".dateformat", "DATE"]).await?;
+        assert_eq!(output, "Date format (date): default\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_pattern() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(
+            &Command,
+            configuration,
+            &[".dateformat", "date", "%d/%m/%Y"],
+        )
+        .await?;
+        let _ = execute(&Command, configuration, &[".dateformat", "time", "%H:%M"]).await?;
+        let _ = execute(
+            &Command,
+            configuration,
+            &[".dateformat", "datetime", "%d/%m/%Y", "%H:%M"],
+        )
+        .await?;
+        assert_eq!(
+            configuration.results_date_format,
+            Some("%d/%m/%Y".to_string())
+        );
+        assert_eq!(configuration.results_time_format, Some("%H:%M".to_string()));
+        assert_eq!(
+            configuration.results_datetime_format,
+            Some("%d/%m/%Y %H:%M".to_string())
+        );
+
+        let _ = execute(&Command, configuration, &[".dateformat", "date", "default"]).await?;
+        assert_eq!(configuration.results_date_format, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".dateformat", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
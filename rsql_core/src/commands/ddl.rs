@@ -0,0 +1,176 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use rsql_formatters::Highlighter;
+use rust_i18n::t;
+
+/// Display the `CREATE TABLE` statements of tables, reconstructed from the metadata of the
+/// connection
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("ddl_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("ddl_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("ddl_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("ddl_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.as_str();
+        let metadata = options.connection.metadata().await?;
+        let dialect = metadata.dialect();
+        let tables = match (metadata.current_schema(), options.input.get(1)) {
+            (Some(schema), Some(table_name)) => match schema.get(table_name) {
+                Some(table) => vec![table],
+                None => {
+                    return Err(InvalidOption {
+                        command_name: self.name(locale),
+                        option: table_name.to_string(),
+                    })
+                }
+            },
+            (Some(schema), None) => schema.tables(),
+            (None, _) => Vec::new(),
+        };
+
+        let formatter_options = configuration.get_formatter_options();
+        let highlighter = Highlighter::new(&formatter_options, "sql");
+        for table in tables {
+            let ddl = table.ddl(dialect.as_ref());
+            let ddl = highlighter.highlight(&ddl)?;
+            writeln!(options.output, "{ddl}")?;
+        }
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use indoc::indoc;
+    use rsql_drivers::{Column, DriverManager, Metadata, MockConnection, Schema, Table};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    fn metadata() -> Metadata {
+        let mut schema = Schema::new("default", true);
+        let mut users = Table::new("users");
+        users.add_column(Column::new("id", "INTEGER", true, None));
+        schema.add(users);
+        let mut contacts = Table::new("contacts");
+        contacts.add_column(Column::new("email", "TEXT", false, None));
+        schema.add(contacts);
+        let mut metadata = Metadata::new();
+        metadata.add(schema);
+        metadata
+    }
+
+    async fn execute(input: Vec<String>) -> Result<String> {
+        let configuration = &mut Configuration {
+            color: false,
+            ..Default::default()
+        };
+        let mut connection = MockConnection::new();
+        connection.expect_metadata().returning(|| Ok(metadata()));
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input,
+            output,
+        };
+
+        Command.execute(options).await?;
+        Ok(output.to_string())
+    }
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "ddl");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[table]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Display the CREATE TABLE statements of tables");
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let output = execute(vec![".ddl".to_string()]).await?;
+        let expected = indoc! {"
+            CREATE TABLE users (
+                id INTEGER NOT NULL
+            );
+            CREATE TABLE contacts (
+                email TEXT
+            );
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_table() -> anyhow::Result<()> {
+        let output = execute(vec![".ddl".to_string(), "contacts".to_string()]).await?;
+        let expected = indoc! {"
+            CREATE TABLE contacts (
+                email TEXT
+            );
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_table() {
+        let result = execute(vec![".ddl".to_string(), "foo".to_string()]).await;
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::commands::Error::{InvalidOption, ReadOnly};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::demo::Dataset;
+use async_trait::async_trait;
+use num_format::{Locale, ToFormattedString};
+use rust_i18n::t;
+use std::str::FromStr;
+
+/// Create and populate the tables of a demo dataset on the current connection
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("demo_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("demo_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("demo_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("demo_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, _options: &CompletionOptions<'_>) -> Vec<String> {
+        Dataset::ALL
+            .iter()
+            .map(|dataset| dataset.name().to_string())
+            .collect()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        if options.configuration.read_only {
+            return Err(ReadOnly {
+                command_name: self.name(locale),
+            });
+        }
+        let dataset = match options.input.get(1) {
+            Some(name) => Dataset::from_str(name).map_err(|option| InvalidOption {
+                command_name: self.name(locale),
+                option,
+            })?,
+            None => Dataset::Tpch,
+        };
+        let scale = match options.input.get(2) {
+            Some(scale) => usize::from_str(scale)?,
+            None => 1,
+        };
+
+        let tables = dataset.tables(scale);
+        let mut rows = 0;
+        for table in &tables {
+            options
+                .connection
+                .execute(&table.create_statement())
+                .await?;
+            for sql in table.insert_statements() {
+                rows += options.connection.execute(&sql).await?;
+            }
+        }
+        options.connection.refresh_metadata().await?;
+
+        let num_locale = Locale::from_str(locale).unwrap_or(Locale::en);
+        let summary = t!(
+            "demo_summary",
+            locale = locale,
+            tables = tables.len().to_formatted_string(&num_locale),
+            rows = rows.to_formatted_string(&num_locale),
+            dataset = dataset.name(),
+        )
+        .to_string();
+        writeln!(options.output, "{summary}")?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::demo::Table;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, Metadata, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "demo");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[dataset] [scale]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Create and populate the tables of a demo dataset (tpch, chinook or northwind)"
+        );
+    }
+
+    #[test]
+    fn test_completions() {
+        let command_manager = CommandManager::default();
+        let formatter_manager = FormatterManager::default();
+        let metadata = Metadata::default();
+        let options = CompletionOptions {
+            locale: "en",
+            command_manager: &command_manager,
+            formatter_manager: &formatter_manager,
+            metadata: &metadata,
+        };
+        let completions = Command.completions(&options);
+        assert_eq!(completions, vec!["chinook", "northwind", "tpch"]);
+    }
+
+    async fn test_execute_input(
+        connection: &mut MockConnection,
+        input: &[&str],
+        output: &mut Output,
+    ) -> Result<LoopCondition> {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: input.iter().map(ToString::to_string).collect(),
+            output,
+        };
+        Command.execute(options).await
+    }
+
+    #[tokio::test]
+    async fn test_execute_read_only() {
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                read_only: true,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".demo".to_string(), "chinook".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(matches!(result, Err(ReadOnly { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_dataset() {
+        let result = test_execute_input(
+            &mut MockConnection::new(),
+            &[".demo", "foo"],
+            &mut Output::default(),
+        )
+        .await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_scale() {
+        let result = test_execute_input(
+            &mut MockConnection::new(),
+            &[".demo", "tpch", "foo"],
+            &mut Output::default(),
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let tables = Dataset::Chinook.tables(1);
+        let rows: usize = tables.iter().map(Table::rows).sum();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_execute()
+            .withf(|sql| sql.starts_with("CREATE TABLE "))
+            .times(tables.len())
+            .returning(|_| Ok(0));
+        connection
+            .expect_execute()
+            .withf(|sql| sql.starts_with("INSERT INTO "))
+            .returning(|sql| Ok(sql.matches("), (").count() as u64 + 1));
+        connection
+            .expect_refresh_metadata()
+            .times(1)
+            .returning(|| Ok(()));
+        let mut output = Output::default();
+
+        let result =
+            test_execute_input(&mut connection, &[".demo", "chinook"], &mut output).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string(),
+            format!(
+                "Created 9 tables with {} rows for the chinook dataset\n",
+                rows.to_formatted_string(&Locale::en)
+            )
+        );
+        Ok(())
+    }
+}
@@ -1,11 +1,14 @@
 use crate::commands::Error::{InvalidOption, MissingArguments};
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
 use async_trait::async_trait;
-use rsql_drivers::{MemoryQueryResult, Table, Value};
+use rsql_drivers::{MemoryQueryResult, QueryResult, Table, Value};
 use rsql_formatters::Results;
 use rust_i18n::t;
 
-/// Describe the specified database object
+/// Describe the specified database object, or the columns of the last result when no object is
+/// specified
 #[derive(Debug, Default)]
 pub struct Command;
 
@@ -19,16 +22,51 @@ impl ShellCommand for Command {
         t!("describe_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("describe_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("describe_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[expect(clippy::too_many_lines)]
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let configuration = options.configuration;
         let output = options.output;
         let locale = &configuration.locale;
 
         if options.input.len() <= 1 {
+            if let Some(last_result) = options.state.last_result.clone() {
+                let mut results = describe_result(&last_result, locale).await;
+                let formatter_options = &mut configuration.get_formatter_options();
+                formatter_options.header = true;
+                formatter_options.footer = false;
+                let result_format = &configuration.results_format;
+                let formatter = options.formatter_manager.get(result_format).ok_or(
+                    rsql_formatters::Error::UnknownFormat {
+                        format: result_format.to_string(),
+                    },
+                )?;
+                formatter
+                    .format(formatter_options, &mut results, output)
+                    .await?;
+                return Ok(LoopCondition::Continue);
+            }
             return Err(MissingArguments {
                 command_name: self.name(locale).to_string(),
                 arguments: self.args(locale).to_string(),
@@ -130,15 +168,48 @@ impl ShellCommand for Command {
     }
 }
 
+/// Describe the name, database type, value kind and nullability of the columns of a result;
+/// attributes that are not known are left empty.
+async fn describe_result(result: &MemoryQueryResult, locale: &str) -> Results {
+    let column_labels = vec![
+        t!("describe_column", locale = locale).to_string(),
+        t!("describe_type", locale = locale).to_string(),
+        t!("describe_kind", locale = locale).to_string(),
+        t!("describe_not_null", locale = locale).to_string(),
+    ];
+    let rows = result
+        .columns_metadata()
+        .await
+        .into_iter()
+        .map(|column| {
+            let nullable = match column.nullable {
+                Some(true) => t!("yes", locale = locale).to_string(),
+                Some(false) => t!("no", locale = locale).to_string(),
+                None => String::new(),
+            };
+            vec![
+                Value::String(column.name),
+                Value::String(column.data_type.unwrap_or_default()),
+                Value::String(column.kind.map(|kind| kind.to_string()).unwrap_or_default()),
+                Value::String(nullable),
+            ]
+        })
+        .collect();
+    Results::Query(Box::new(MemoryQueryResult::new(column_labels, rows)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use indoc::indoc;
-    use rsql_drivers::{Column, DriverManager, Index, Metadata, MockConnection, Schema, Table};
+    use rsql_drivers::{
+        Column, ColumnMetadata, DriverManager, Index, Metadata, MockConnection, Schema, Table,
+    };
     use rsql_formatters::FormatterManager;
     use rustyline::history::DefaultHistory;
     use std::default;
@@ -170,6 +241,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".describe".to_string()],
             output: &mut Output::default(),
         };
@@ -197,6 +269,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".describe".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
@@ -237,6 +310,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".describe".to_string(), table_name.to_string()],
             output: &mut output,
         };
@@ -261,4 +335,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_execute_last_result() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            color: false,
+            results_format: "psql".to_string(),
+            ..default::Default::default()
+        };
+        let last_result = MemoryQueryResult::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Value::I64(1), Value::Null],
+                vec![Value::I64(2), Value::String("foo".to_string())],
+            ],
+        )
+        .with_columns_metadata(vec![
+            ColumnMetadata::new("id").with_data_type("INTEGER"),
+            ColumnMetadata::new("name").with_data_type("TEXT"),
+        ]);
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState {
+                last_result: Some(last_result),
+                ..Default::default()
+            },
+            input: vec![".describe".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let contents = output.to_string().replace("\r\n", "\n");
+        let expected = indoc! {r"
+              Column |  Type   |  Kind  | Not null 
+             --------+---------+--------+----------
+              id     | INTEGER | i64    |          
+              name   | TEXT    | string | Yes      
+        "};
+        assert_eq!(contents, expected);
+
+        Ok(())
+    }
 }
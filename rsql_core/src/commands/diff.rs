@@ -0,0 +1,213 @@
+use crate::commands::Error::MissingArguments;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::{Change, Difference, MemoryQueryResult, ObjectKind, Row, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+
+/// Compare the schemas, tables, columns and indexes of the current connection with the database
+/// of another connection
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("diff_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("diff_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("diff_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("diff_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.as_str();
+        let Some(url) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+
+        let start = std::time::Instant::now();
+        let metadata = options.connection.metadata().await?;
+        let mut target_connection = options.driver_manager.connect(url).await?;
+        let target_metadata = target_connection.metadata().await;
+        target_connection.close().await?;
+        let differences = metadata.diff(&target_metadata?);
+
+        let columns = vec![
+            t!("diff_change", locale = locale).to_string(),
+            t!("diff_object", locale = locale).to_string(),
+            t!("diff_name", locale = locale).to_string(),
+            t!("diff_current", locale = locale).to_string(),
+            t!("diff_target", locale = locale).to_string(),
+        ];
+        let rows = differences
+            .into_iter()
+            .map(|difference| difference_row(locale, difference))
+            .collect();
+
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Convert the difference to a row with localized change and object names
+fn difference_row(locale: &str, difference: Difference) -> Row {
+    let change = match difference.change {
+        Change::Added => t!("diff_added", locale = locale),
+        Change::Removed => t!("diff_removed", locale = locale),
+        Change::Changed => t!("diff_changed", locale = locale),
+    };
+    let kind = match difference.kind {
+        ObjectKind::Schema => t!("diff_schema", locale = locale),
+        ObjectKind::Table => t!("diff_table", locale = locale),
+        ObjectKind::Column => t!("diff_column", locale = locale),
+        ObjectKind::Index => t!("diff_index", locale = locale),
+    };
+    vec![
+        Value::String(change.to_string()),
+        Value::String(kind.to_string()),
+        Value::String(difference.name),
+        difference.source.map_or(Value::Null, Value::String),
+        difference.target.map_or(Value::Null, Value::String),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use indoc::indoc;
+    use rsql_drivers::{
+        Column, DriverManager, Metadata, MockConnection, MockDriver, Schema, Table,
+    };
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    fn metadata(data_type: &str) -> Metadata {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("id", "INTEGER", true, None));
+        table.add_column(Column::new("email", data_type, false, None));
+        let mut schema = Schema::new("main", true);
+        schema.add(table);
+        let mut metadata = Metadata::new();
+        metadata.add(schema);
+        metadata
+    }
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "diff");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "url");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Compare the schema of the current database with another database"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut mock_driver = MockDriver::new();
+        mock_driver.expect_identifier().returning(|| "test");
+        mock_driver.expect_supports_file_type().returning(|_| false);
+        mock_driver.expect_connect().returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection
+                .expect_metadata()
+                .returning(|| Ok(metadata("VARCHAR(100)")));
+            connection.expect_close().returning(|| Ok(()));
+            Ok(Box::new(connection))
+        });
+        let mut driver_manager = DriverManager::new();
+        driver_manager.add(Box::new(mock_driver));
+        let mut connection = MockConnection::new();
+        connection
+            .expect_metadata()
+            .returning(|| Ok(metadata("TEXT")));
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &driver_manager,
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".diff".to_string(), "test://".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let expected = indoc! {r#"
+            "change","object","name","current","target"
+            "changed","column","main.users.email","TEXT","VARCHAR(100)"
+        "#};
+        assert_eq!(output.to_string().replace("\r\n", "\n"), expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_url() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".diff".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+}
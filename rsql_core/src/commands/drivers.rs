@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rust_i18n::t;
 
@@ -12,6 +12,10 @@ impl ShellCommand for Command {
         t!("drivers_command", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("drivers_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("drivers_description", locale = locale).to_string()
     }
@@ -39,6 +43,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -70,6 +75,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".drivers".to_string()],
             output: &mut output,
         };
@@ -81,20 +87,34 @@ mod tests {
         let drivers: Vec<&str> = vec![
             #[cfg(feature = "driver-arrow")]
             "arrow",
+            #[cfg(feature = "driver-athena")]
+            "athena",
             #[cfg(feature = "driver-avro")]
             "avro",
+            #[cfg(feature = "driver-clickhouse")]
+            "clickhouse",
             #[cfg(feature = "driver-cockroachdb")]
             "cockroachdb",
             #[cfg(feature = "driver-csv")]
             "csv",
             #[cfg(feature = "driver-delimited")]
             "delimited",
+            #[cfg(feature = "driver-dir")]
+            "dir",
             #[cfg(feature = "driver-duckdb")]
             "duckdb",
+            #[cfg(feature = "driver-dynamodb")]
+            "dynamodb",
             #[cfg(feature = "driver-excel")]
             "excel",
+            #[cfg(feature = "driver-exec")]
+            "exec",
             #[cfg(feature = "driver-file")]
             "file",
+            #[cfg(feature = "driver-flightsql")]
+            "flightsql",
+            #[cfg(feature = "driver-https")]
+            "https",
             #[cfg(feature = "driver-json")]
             "json",
             #[cfg(feature = "driver-jsonl")]
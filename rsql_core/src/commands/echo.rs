@@ -1,5 +1,7 @@
 use crate::commands::Error::InvalidOption;
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
 use crate::configuration::EchoMode;
 use async_trait::async_trait;
 use rust_i18n::t;
@@ -28,10 +30,31 @@ impl ShellCommand for Command {
         .to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let on = t!("on", locale = locale).to_string();
+        let prompt = t!("echo_prompt", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!(
+            "echo_example_arguments",
+            locale = locale,
+            on = on,
+            prompt = prompt,
+            off = off,
+        ))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("echo_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let locale = options.locale;
+        let on = t!("on", locale = locale).to_string();
+        let prompt = t!("echo_prompt", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        vec![on, prompt, off]
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
         let on = t!("on", locale = locale).to_string();
@@ -75,6 +98,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -112,6 +136,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".echo".to_string()],
             output: &mut output,
         };
@@ -157,6 +182,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".echo".to_string(), "on".to_string()],
             output: &mut Output::default(),
         };
@@ -181,6 +207,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".echo".to_string(), "prompt".to_string()],
             output: &mut Output::default(),
         };
@@ -205,6 +232,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".echo".to_string(), "off".to_string()],
             output: &mut Output::default(),
         };
@@ -225,6 +253,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".echo".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
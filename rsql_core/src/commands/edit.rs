@@ -0,0 +1,124 @@
+use crate::commands::Error::IoError;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use rust_i18n::t;
+use std::env;
+use std::io::Write;
+
+/// Command to edit the statement being entered, or the last statement executed, in an external
+/// editor and load the edited statement into the prompt
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("edit_command", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("edit_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("edit_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let state = options.state;
+        let statement = if state.statement.is_empty() {
+            state.last_statement.clone().unwrap_or_default()
+        } else {
+            state.statement.clone()
+        };
+
+        let edited_statement = edit(&statement).await?;
+        if !edited_statement.is_empty() {
+            state.edited_statement = Some(edited_statement);
+        }
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Get the external editor command from the `VISUAL` or `EDITOR` environment variables, falling
+/// back to `notepad` on Windows and `vi` on other platforms.
+fn editor() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// Edit the text in the external editor and return the edited text without the trailing line
+/// ending.
+///
+/// # Errors
+///
+/// Returns an error if the editor cannot be started or exits with an error.
+pub(crate) async fn edit(text: &str) -> Result<String> {
+    edit_with(&editor(), text).await
+}
+
+/// Edit the text with the editor command; the command may include arguments, e.g. `code --wait`.
+async fn edit_with(editor: &str, text: &str) -> Result<String> {
+    let mut file = tempfile::Builder::new().suffix(".sql").tempfile()?;
+    file.write_all(text.as_bytes())?;
+    file.flush()?;
+
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap_or_default();
+    let status = tokio::process::Command::new(program)
+        .args(args)
+        .arg(file.path())
+        .status()
+        .await?;
+    if !status.success() {
+        return Err(IoError(anyhow!("Editor {editor} exited with {status}")));
+    }
+
+    let edited_text = std::fs::read_to_string(file.path())?;
+    Ok(edited_text.trim_end_matches(['\r', '\n']).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "edit");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Edit the current or last statement in an external editor"
+        );
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[tokio::test]
+    async fn test_edit_with() -> anyhow::Result<()> {
+        let edited_text = edit_with("true", "SELECT 1;\n").await?;
+        assert_eq!(edited_text, "SELECT 1;");
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[tokio::test]
+    async fn test_edit_with_error() {
+        assert!(edit_with("false", "SELECT 1;").await.is_err());
+    }
+}
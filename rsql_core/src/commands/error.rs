@@ -1,4 +1,5 @@
 use crate::commands::LoopCondition;
+use crate::error_code::ErrorCode;
 
 pub type Result<T = LoopCondition, E = Error> = core::result::Result<T, E>;
 
@@ -25,6 +26,31 @@ pub enum Error {
         command_name: String,
         arguments: String,
     },
+    /// Error when a command that modifies the database is executed in read-only mode
+    #[error("{command_name} is not allowed in read-only mode")]
+    ReadOnly { command_name: String },
+}
+
+impl Error {
+    /// Get the error code for this error.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::DriverError(error) => ErrorCode::from(error),
+            Error::FormatterError(error) => ErrorCode::from(error),
+            Error::InvalidOption { .. } => ErrorCode::InvalidOption,
+            Error::IoError(_) => ErrorCode::Io,
+            Error::MissingArguments { .. } => ErrorCode::MissingArguments,
+            Error::ReadOnly { .. } => ErrorCode::ReadOnly,
+        }
+    }
+}
+
+/// Converts a [`crate::assistant::Error`] into an [`IoError`](Error::IoError)
+impl From<crate::assistant::Error> for Error {
+    fn from(error: crate::assistant::Error) -> Self {
+        Error::IoError(error.into())
+    }
 }
 
 /// Converts a [`clearscreen::Error`] into an [`IoError`](Error::IoError)
@@ -54,6 +80,24 @@ mod tests {
     use std::str::FromStr;
     use test_log::test;
 
+    #[test]
+    fn test_invalid_option_code() {
+        let error = Error::InvalidOption {
+            command_name: "bail".to_string(),
+            option: "foo".to_string(),
+        };
+        assert_eq!(error.code(), ErrorCode::InvalidOption);
+    }
+
+    #[test]
+    fn test_read_only_code() {
+        let error = Error::ReadOnly {
+            command_name: "generate".to_string(),
+        };
+        assert_eq!(error.code(), ErrorCode::ReadOnly);
+        assert_eq!(error.to_string(), "generate is not allowed in read-only mode");
+    }
+
     #[test]
     fn test_clear_screen_error() {
         let std_io_error = std::io::Error::new(std::io::ErrorKind::Other, "test");
@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rust_i18n::t;
 use tracing::info;
@@ -17,6 +17,10 @@ impl ShellCommand for Command {
         t!("exit_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("exit_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("exit_description", locale = locale).to_string()
     }
@@ -40,6 +44,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -75,6 +80,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".exit".to_string()],
             output: &mut Output::default(),
         };
@@ -97,6 +103,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".exit".to_string(), "1".to_string()],
             output: &mut Output::default(),
         };
@@ -116,6 +123,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".exit".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
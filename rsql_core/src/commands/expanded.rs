@@ -0,0 +1,124 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to display results that are wider than the terminal in the expanded format, one line
+/// per column; results that fit in the terminal are displayed in the results format
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("expanded_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("expanded_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!(
+            "expanded_example_arguments",
+            locale = locale,
+            off = off
+        ))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("expanded_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let auto = t!("expanded_auto", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+
+        let Some(value) = options.input.get(1) else {
+            let mode = if options.configuration.results_expanded_auto {
+                auto
+            } else {
+                off
+            };
+            let expanded_setting = t!("expanded_setting", locale = locale, mode = mode).to_string();
+            writeln!(options.output, "{expanded_setting}")?;
+            return Ok(LoopCondition::Continue);
+        };
+
+        options.configuration.results_expanded_auto = if value.eq_ignore_ascii_case(&auto) {
+            true
+        } else if value.eq_ignore_ascii_case(&off) {
+            false
+        } else {
+            return Err(InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: value.to_string(),
+            });
+        };
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::execute;
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "expanded");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[auto|off]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Display results that are wider than the terminal in the expanded format"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_expanded_auto: true,
+            ..Default::default()
+        };
+        let output = execute(&Command, configuration, &[".expanded"]).await?;
+        assert_eq!(output, "Expanded: auto\n");
+
+        configuration.results_expanded_auto = false;
+        let output = execute(&Command, configuration, &[".expanded"]).await?;
+        assert_eq!(output, "Expanded: off\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_mode() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(&Command, configuration, &[".expanded", "auto"]).await?;
+        assert!(configuration.results_expanded_auto);
+
+        let _ = execute(&Command, configuration, &[".expanded", "off"]).await?;
+        assert!(!configuration.results_expanded_auto);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".expanded", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
@@ -0,0 +1,148 @@
+use crate::commands::Error::{IoError, MissingArguments};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to export the results of a query to a destination of the database server using the
+/// unload mechanism of the database (e.g. Redshift `UNLOAD` to Amazon S3); the options are
+/// specific to the driver.
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("export_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("export_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("export_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("export_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+
+        let (Some(sql), Some(destination)) = (options.input.get(1), options.input.get(2)) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale).to_string(),
+                arguments: self.args(locale).to_string(),
+            });
+        };
+
+        let export = options
+            .connection
+            .as_export()
+            .ok_or_else(|| IoError(anyhow!("export is not supported for this connection")))?;
+        let rows = export.export(sql, destination, &options.input[3..]).await?;
+
+        let export_rows = t!("export_rows", locale = locale, rows = rows).to_string();
+        writeln!(options.output, "{export_rows}")?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CommandManager, CommandOptions};
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{
+        Connection, DriverManager, MockCapableConnection, MockConnection, MockExport,
+    };
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "export");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "\"<query>\" <destination> [option...]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Export the results of a query to a destination of the database server, e.g. Amazon S3"
+        );
+    }
+
+    async fn execute(connection: &mut dyn Connection, input: &[&str]) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        Ok(output.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut export = MockExport::new();
+        export
+            .expect_export()
+            .withf(|sql, destination, options| {
+                sql == "SELECT * FROM sales"
+                    && destination == "s3://bucket/sales/"
+                    && options == ["format=parquet".to_string()]
+            })
+            .returning(|_, _, _| Ok(42));
+        let mut connection = MockCapableConnection {
+            export: Some(export),
+            ..Default::default()
+        };
+        let output = execute(
+            &mut connection,
+            &[
+                ".export",
+                "SELECT * FROM sales",
+                "s3://bucket/sales/",
+                "format=parquet",
+            ],
+        )
+        .await?;
+        assert_eq!(output, "Rows exported: 42\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_not_supported() {
+        let mut connection = MockConnection::new();
+        let result = execute(&mut connection, &[".export", "SELECT 1", "s3://bucket/"]).await;
+        assert!(matches!(result, Err(IoError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_arguments() {
+        let mut connection = MockConnection::new();
+        let result = execute(&mut connection, &[".export", "SELECT 1"]).await;
+        assert!(matches!(result, Err(MissingArguments { .. })));
+    }
+}
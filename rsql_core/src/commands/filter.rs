@@ -0,0 +1,265 @@
+use crate::commands::last::{column_index, columns_and_rows, write_no_result, write_result};
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::Value;
+use rust_i18n::t;
+use std::cmp::Ordering;
+use std::time::Instant;
+
+/// Operators supported by the filter command; `~` matches values that contain the text, ignoring
+/// case.
+const OPERATORS: [&str; 8] = ["=", "!=", "<>", "<", "<=", ">", ">=", "~"];
+
+/// Filter the rows of the last result without re-running the query
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("filter_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("filter_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("filter_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("filter_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let locale = options.configuration.locale.as_str();
+        let (Some(column), Some(operator), Some(operand)) = (
+            options.input.get(1).cloned(),
+            options.input.get(2).cloned(),
+            options.input.get(3).cloned(),
+        ) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        if !OPERATORS.contains(&operator.as_str()) {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: operator,
+            });
+        }
+        let Some(last_result) = options.state.last_result.clone() else {
+            return write_no_result(&options.configuration.locale, options.output);
+        };
+
+        let (columns, rows) = columns_and_rows(last_result).await?;
+        let Some(index) = column_index(&columns, &column) else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: column,
+            });
+        };
+        let rows = rows
+            .into_iter()
+            .filter(|row| {
+                row.get(index)
+                    .is_some_and(|value| matches(value, &operator, &operand))
+            })
+            .collect();
+
+        write_result(options, start, columns, rows).await
+    }
+}
+
+/// Returns true if the value matches the operator and operand
+fn matches(value: &Value, operator: &str, operand: &str) -> bool {
+    if operator == "~" {
+        return value
+            .to_string()
+            .to_lowercase()
+            .contains(&operand.to_lowercase());
+    }
+
+    let Some(ordering) = compare(value, operand) else {
+        return operator == "!=" || operator == "<>";
+    };
+    match operator {
+        "=" => ordering == Ordering::Equal,
+        "!=" | "<>" => ordering != Ordering::Equal,
+        "<" => ordering == Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        ">=" => ordering != Ordering::Less,
+        _ => false,
+    }
+}
+
+/// Compare the value to the operand; numeric values are compared numerically when the operand is
+/// a number, and other values are compared as text.  Null values are only equal to `null`.
+fn compare(value: &Value, operand: &str) -> Option<Ordering> {
+    if value.is_null() {
+        return operand
+            .eq_ignore_ascii_case("null")
+            .then_some(Ordering::Equal);
+    }
+    if operand.eq_ignore_ascii_case("null") {
+        return None;
+    }
+    if let (Some(number), Ok(operand)) = (value.to_f64(), operand.parse::<f64>()) {
+        return number.partial_cmp(&operand);
+    }
+    Some(value.to_string().as_str().cmp(operand))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MemoryQueryResult, MockConnection, QueryResult};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "filter");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<column> <operator> <value>");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Filter the rows of the last query result");
+    }
+
+    #[test]
+    fn test_matches() {
+        let value = Value::I64(42);
+        assert!(matches(&value, "=", "42"));
+        assert!(matches(&value, "!=", "41"));
+        assert!(matches(&value, "<>", "41"));
+        assert!(matches(&value, "<", "100"));
+        assert!(matches(&value, "<=", "42"));
+        assert!(matches(&value, ">", "9"));
+        assert!(matches(&value, ">=", "42"));
+        assert!(!matches(&value, ">", "42"));
+
+        let value = Value::String("John Smith".to_string());
+        assert!(matches(&value, "~", "smith"));
+        assert!(matches(&value, "=", "John Smith"));
+        assert!(!matches(&value, "=", "null"));
+
+        assert!(matches(&Value::Null, "=", "NULL"));
+        assert!(matches(&Value::Null, "!=", "42"));
+        assert!(!matches(&Value::Null, ">", "42"));
+    }
+
+    fn last_result() -> MemoryQueryResult {
+        MemoryQueryResult::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Value::I64(1), Value::String("John".to_string())],
+                vec![Value::I64(2), Value::String("Jane".to_string())],
+                vec![Value::I64(3), Value::Null],
+            ],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+        state.last_result = Some(last_result());
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![
+                ".filter".to_string(),
+                "id".to_string(),
+                ">=".to_string(),
+                "2".to_string(),
+            ],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let mut last_result = state.last_result.take().expect("last result");
+        assert_eq!(
+            last_result.next().await,
+            Some(vec![Value::I64(2), Value::String("Jane".to_string())])
+        );
+        assert_eq!(
+            last_result.next().await,
+            Some(vec![Value::I64(3), Value::Null])
+        );
+        assert!(last_result.next().await.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_arguments() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState {
+                last_result: Some(last_result()),
+                ..Default::default()
+            },
+            input: vec![".filter".to_string(), "id".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_column() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState {
+                last_result: Some(last_result()),
+                ..Default::default()
+            },
+            input: vec![
+                ".filter".to_string(),
+                "email".to_string(),
+                "=".to_string(),
+                "foo".to_string(),
+            ],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+}
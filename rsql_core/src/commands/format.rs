@@ -1,5 +1,7 @@
 use crate::commands::Error::InvalidOption;
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
 use async_trait::async_trait;
 use rsql_formatters::Formatter;
 use rust_i18n::t;
@@ -18,10 +20,22 @@ impl ShellCommand for Command {
         t!("format_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("format_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("format_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .formatter_manager
+            .iter()
+            .map(|formatter| formatter.identifier().to_string())
+            .collect()
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
         let formatter_manager = options.formatter_manager;
@@ -69,8 +83,9 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
-    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_drivers::{DriverManager, Metadata, MockConnection};
     use rsql_formatters::FormatterManager;
     use rustyline::history::DefaultHistory;
     use std::default;
@@ -93,6 +108,22 @@ mod tests {
         assert_eq!(description, "Set the output format for the results");
     }
 
+    #[test]
+    fn test_completions() {
+        let command_manager = CommandManager::default();
+        let formatter_manager = FormatterManager::default();
+        let metadata = Metadata::default();
+        let options = CompletionOptions {
+            locale: "en",
+            command_manager: &command_manager,
+            formatter_manager: &formatter_manager,
+            metadata: &metadata,
+        };
+        let completions = Command.completions(&options);
+        assert!(completions.contains(&"ascii".to_string()));
+        assert!(completions.contains(&"unicode".to_string()));
+    }
+
     #[tokio::test]
     async fn test_execute_no_args() -> anyhow::Result<()> {
         let mut output = Output::default();
@@ -107,14 +138,19 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".format".to_string()],
             output: &mut output,
         };
 
         let result = Command.execute(options).await?;
         let formats: Vec<&str> = vec![
+            #[cfg(feature = "format-arrow")]
+            "arrow",
             #[cfg(feature = "format-ascii")]
             "ascii",
+            #[cfg(feature = "format-chart")]
+            "chart",
             #[cfg(feature = "format-csv")]
             "csv",
             #[cfg(feature = "format-expanded")]
@@ -125,18 +161,24 @@ mod tests {
             "json",
             #[cfg(feature = "format-jsonl")]
             "jsonl",
+            #[cfg(feature = "format-latex")]
+            "latex",
             #[cfg(feature = "format-markdown")]
             "markdown",
             #[cfg(feature = "format-plain")]
             "plain",
             #[cfg(feature = "format-psql")]
             "psql",
+            #[cfg(feature = "format-rst")]
+            "rst",
             #[cfg(feature = "format-sqlite")]
             "sqlite",
             #[cfg(feature = "format-tsv")]
             "tsv",
             #[cfg(feature = "format-unicode")]
             "unicode",
+            #[cfg(feature = "format-xlsx")]
+            "xlsx",
             #[cfg(feature = "format-xml")]
             "xml",
             #[cfg(feature = "format-yaml")]
@@ -166,6 +208,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".format".to_string(), "ascii".to_string()],
             output: &mut Output::default(),
         };
@@ -190,6 +233,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".format".to_string(), "unicode".to_string()],
             output: &mut Output::default(),
         };
@@ -210,6 +254,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".format".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
@@ -0,0 +1,451 @@
+use crate::commands::Error::{InvalidOption, MissingArguments, ReadOnly};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::shell::quote_identifier;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use fake::faker::address::en::{CityName, CountryName, StreetName, ZipCode};
+use fake::faker::company::en::CompanyName;
+use fake::faker::internet::en::{SafeEmail, Username};
+use fake::faker::lorem::en::{Sentence, Word};
+use fake::faker::name::en::{FirstName, LastName, Name};
+use fake::faker::phone_number::en::PhoneNumber;
+use fake::Fake;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rsql_drivers::Column;
+use rust_i18n::t;
+use std::str::FromStr;
+
+/// The number of rows inserted with each statement
+const BATCH_SIZE: usize = 100;
+
+/// Generate rows of synthetic data for a table, based on the names and data types of the columns
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("generate_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("generate_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("generate_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("generate_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        if options.configuration.read_only {
+            return Err(ReadOnly {
+                command_name: self.name(locale),
+            });
+        }
+        let (Some(table_name), Some(rows)) = (options.input.get(1), options.input.get(2)) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        let rows = usize::from_str(rows)?;
+        let seed = match options.input.get(3) {
+            Some(seed) => u64::from_str(seed)?,
+            None => rand::random(),
+        };
+
+        let metadata = options.connection.metadata().await?;
+        let Some(table) = metadata
+            .current_schema()
+            .and_then(|schema| schema.get(table_name))
+        else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: table_name.to_string(),
+            });
+        };
+        let columns: Vec<&Column> = table
+            .columns()
+            .into_iter()
+            .filter(|column| generated(column))
+            .collect();
+        // The dialect is not Send, so it is dropped before the rows are inserted
+        let (table_name, column_names) = {
+            let dialect = metadata.dialect();
+            let column_names = columns
+                .iter()
+                .map(|column| quote_identifier(dialect.as_ref(), column.name()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (quote_identifier(dialect.as_ref(), table.name()), column_names)
+        };
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut inserted = 0;
+        let mut remaining = rows;
+        while remaining > 0 {
+            let batch_size = remaining.min(BATCH_SIZE);
+            let values = (0..batch_size)
+                .map(|_| {
+                    let row = columns
+                        .iter()
+                        .map(|column| literal(column, &mut rng))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("({row})")
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!("INSERT INTO {table_name} ({column_names}) VALUES {values}");
+            inserted += options.connection.execute(&sql).await?;
+            remaining -= batch_size;
+        }
+
+        let generate_rows = t!(
+            "generate_rows",
+            locale = locale,
+            rows = inserted,
+            table = table.name(),
+            seed = seed
+        )
+        .to_string();
+        writeln!(options.output, "{generate_rows}")?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Columns with a default value, e.g. identity or timestamp columns, and nullable `id` columns,
+/// e.g. the `INTEGER PRIMARY KEY` row id of `SQLite`, are populated by the database
+fn generated(column: &Column) -> bool {
+    column.default().is_none() && (column.not_null() || !column.name().eq_ignore_ascii_case("id"))
+}
+
+/// Generate a SQL literal for the column; the data type of the column determines the kind of
+/// value, and the name of text columns is used to generate realistic values, e.g. emails for an
+/// `email` column. One in ten values of nullable columns are null.
+fn literal(column: &Column, rng: &mut StdRng) -> String {
+    if !column.not_null() && rng.gen_ratio(1, 10) {
+        return "NULL".to_string();
+    }
+
+    let data_type = column.data_type().to_uppercase();
+    if data_type.contains("BOOL") {
+        return if rng.gen_bool(0.5) { "TRUE" } else { "FALSE" }.to_string();
+    }
+    if data_type == "BIT" {
+        return rng.gen_range(0..=1).to_string();
+    }
+    if data_type.contains("INT") {
+        let max = if data_type.contains("TINY") {
+            100
+        } else if data_type.contains("SMALL") {
+            10_000
+        } else {
+            1_000_000
+        };
+        return rng.gen_range(1..=max).to_string();
+    }
+    if ["DECIMAL", "NUMERIC", "REAL", "FLOAT", "DOUBLE", "MONEY"]
+        .iter()
+        .any(|numeric_type| data_type.contains(numeric_type))
+    {
+        return format!("{:.2}", rng.gen_range(0.0..10_000.0));
+    }
+
+    let start = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+    let date = start + Duration::days(rng.gen_range(0..10_000));
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        rng.gen_range(0..24),
+        rng.gen_range(0..60),
+        rng.gen_range(0..60)
+    );
+    if data_type.contains("TIMESTAMP") || data_type.contains("DATETIME") {
+        return format!("'{date} {time}'");
+    }
+    if data_type.contains("DATE") {
+        return format!("'{date}'");
+    }
+    if data_type.contains("TIME") {
+        return format!("'{time}'");
+    }
+    if data_type.contains("UUID") || data_type.contains("UNIQUEIDENTIFIER") {
+        let value: u128 = rng.gen();
+        let hex = format!("{value:032x}");
+        return format!(
+            "'{}-{}-{}-{}-{}'",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        );
+    }
+
+    let mut value = text(&column.name().to_lowercase(), rng);
+    if let Some(length) = max_length(&data_type) {
+        value = value.chars().take(length).collect();
+    }
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Generate a text value based on the name of the column
+fn text(name: &str, rng: &mut StdRng) -> String {
+    let contains = |values: &[&str]| values.iter().any(|value| name.contains(value));
+    if contains(&["email"]) {
+        SafeEmail().fake_with_rng(rng)
+    } else if contains(&["username", "login"]) {
+        Username().fake_with_rng(rng)
+    } else if contains(&["first"]) {
+        FirstName().fake_with_rng(rng)
+    } else if contains(&["last", "surname"]) {
+        LastName().fake_with_rng(rng)
+    } else if contains(&["company"]) {
+        CompanyName().fake_with_rng(rng)
+    } else if contains(&["city"]) {
+        CityName().fake_with_rng(rng)
+    } else if contains(&["country"]) {
+        CountryName().fake_with_rng(rng)
+    } else if contains(&["address", "street"]) {
+        StreetName().fake_with_rng(rng)
+    } else if contains(&["zip", "postal"]) {
+        ZipCode().fake_with_rng(rng)
+    } else if contains(&["phone"]) {
+        PhoneNumber().fake_with_rng(rng)
+    } else if contains(&["name"]) {
+        Name().fake_with_rng(rng)
+    } else if contains(&["description", "comment", "note", "text"]) {
+        Sentence(3..8).fake_with_rng(rng)
+    } else {
+        Word().fake_with_rng(rng)
+    }
+}
+
+/// Get the maximum length of a character data type, e.g. `20` for `VARCHAR(20)`
+fn max_length(data_type: &str) -> Option<usize> {
+    let (_, length) = data_type.split_once('(')?;
+    let length = length.strip_suffix(')')?;
+    usize::from_str(length.trim()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use mockall::predicate::function;
+    use rsql_drivers::{DriverManager, Metadata, MockConnection, Schema, Table};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    fn metadata() -> Metadata {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("id", "INTEGER", false, None));
+        table.add_column(Column::new("email", "VARCHAR(10)", true, None));
+        table.add_column(Column::new("created", "TIMESTAMP", true, Some("now()")));
+        let mut schema = Schema::new("main", true);
+        schema.add(table);
+        let mut metadata = Metadata::new();
+        metadata.add(schema);
+        metadata
+    }
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "generate");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "table rows [seed]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Insert rows of generated data into a table");
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection.expect_metadata().returning(|| Ok(metadata()));
+        connection
+            .expect_execute()
+            .with(function(|sql: &str| {
+                sql.starts_with("INSERT INTO users (email) VALUES ('")
+                    && sql.matches("), (").count() == 99
+            }))
+            .returning(|_| Ok(100));
+        connection
+            .expect_execute()
+            .with(function(|sql: &str| {
+                sql.starts_with("INSERT INTO users (email) VALUES ('") && !sql.contains("), (")
+            }))
+            .returning(|_| Ok(1));
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![
+                ".generate".to_string(),
+                "users".to_string(),
+                "101".to_string(),
+                "42".to_string(),
+            ],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string(),
+            "Generated 101 rows in users (seed 42)\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_read_only() {
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                read_only: true,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![
+                ".generate".to_string(),
+                "users".to_string(),
+                "1".to_string(),
+            ],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(matches!(result, Err(ReadOnly { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_rows() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".generate".to_string(), "users".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_table() {
+        let mut connection = MockConnection::new();
+        connection.expect_metadata().returning(|| Ok(metadata()));
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".generate".to_string(), "foo".to_string(), "1".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_generated() {
+        assert!(!generated(&Column::new("id", "INTEGER", false, None)));
+        assert!(generated(&Column::new("id", "INTEGER", true, None)));
+        assert!(!generated(&Column::new(
+            "id",
+            "SERIAL",
+            true,
+            Some("nextval('id')")
+        )));
+        assert!(generated(&Column::new("name", "TEXT", false, None)));
+    }
+
+    #[test]
+    fn test_literal_is_reproducible() {
+        let column = Column::new("email", "TEXT", true, None);
+        let first = literal(&column, &mut StdRng::seed_from_u64(42));
+        let second = literal(&column, &mut StdRng::seed_from_u64(42));
+        assert_eq!(first, second);
+        assert!(first.contains('@'));
+    }
+
+    #[test]
+    fn test_literal_data_types() {
+        let rng = &mut StdRng::seed_from_u64(42);
+        let generate = |data_type: &str, rng: &mut StdRng| {
+            literal(&Column::new("value", data_type, true, None), rng)
+        };
+        assert!(["TRUE", "FALSE"].contains(&generate("BOOLEAN", rng).as_str()));
+        assert!(generate("BIGINT", rng).parse::<i64>().is_ok());
+        assert!(generate("DECIMAL(10,2)", rng).parse::<f64>().is_ok());
+        assert_eq!(generate("DATE", rng).len(), 12);
+        assert_eq!(generate("TIME", rng).len(), 10);
+        assert_eq!(generate("TIMESTAMP", rng).len(), 21);
+        assert_eq!(generate("UUID", rng).len(), 38);
+        assert!(generate("VARCHAR(3)", rng).len() <= 5);
+    }
+
+    #[test]
+    fn test_max_length() {
+        assert_eq!(max_length("VARCHAR(20)"), Some(20));
+        assert_eq!(max_length("DECIMAL(10,2)"), None);
+        assert_eq!(max_length("TEXT"), None);
+    }
+}
@@ -1,7 +1,13 @@
 extern crate colored;
 extern crate unicode_width;
 
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandManager, CommandOptions, CompletionOptions, LoopCondition, Result,
+    ShellCommand,
+};
+use crate::configuration::Configuration;
+use crate::writers::Output;
 use async_trait::async_trait;
 use colored::Colorize;
 use rust_i18n::t;
@@ -17,49 +23,164 @@ impl ShellCommand for Command {
         t!("help_command", locale = locale).to_string()
     }
 
+    fn args(&self, locale: &str) -> String {
+        t!("help_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!(
+            "help_example_arguments",
+            locale = locale,
+            format = crate::commands::format::Command.name(locale),
+        ))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("help_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .command_manager
+            .iter()
+            .map(|command| command.name(options.locale))
+            .collect()
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
-        let output = options.output;
-        let command_identifier = &options.configuration.command_identifier;
+        let configuration = &*options.configuration;
         let command_manager = options.command_manager;
-        let locale = options.configuration.locale.as_str();
-        let width = command_manager
-            .iter()
-            .map(|command| {
-                let command_name_width = command.name(locale).width();
-                let command_args_width = command.args(locale).width();
-                command_name_width + command_args_width + 1
-            })
-            .max()
-            .unwrap_or_default();
-
-        for command in command_manager.iter() {
-            let name = command.name(locale);
-            let name_width = name.width();
-            let args_width = width - name_width;
-            let mut args = if command.args(locale).is_empty() {
-                " ".repeat(args_width)
+        let output = options.output;
+
+        if options.input.len() <= 1 {
+            write_help(configuration, command_manager, output)?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let locale = configuration.locale.as_str();
+        let command_identifier = configuration.command_identifier.as_str();
+        let command_name = options.input[1].as_str();
+        let command_name = command_name
+            .strip_prefix(command_identifier)
+            .unwrap_or(command_name);
+        let Some(command) = command_manager.get(locale, command_name) else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: command_name.to_string(),
+            });
+        };
+
+        write_command_help(configuration, command, output)?;
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Write a summary of all the commands with their arguments and description.
+fn write_help(
+    configuration: &Configuration,
+    command_manager: &CommandManager,
+    output: &mut Output,
+) -> Result<()> {
+    let command_identifier = &configuration.command_identifier;
+    let locale = configuration.locale.as_str();
+    let width = command_manager
+        .iter()
+        .map(|command| {
+            let command_name_width = command.name(locale).width();
+            let command_args_width = command.args(locale).width();
+            command_name_width + command_args_width + 1
+        })
+        .max()
+        .unwrap_or_default();
+
+    for command in command_manager.iter() {
+        let name = command.name(locale);
+        let name_width = name.width();
+        let args_width = width - name_width;
+        let mut args = if command.args(locale).is_empty() {
+            " ".repeat(args_width)
+        } else {
+            format!(
+                " {args:args_width$}",
+                args = command.args(locale),
+                args_width = args_width - 1
+            )
+        };
+
+        let mut name = format!("{command_identifier}{name}");
+        let description = command.description(locale);
+        if configuration.color {
+            name = name.bold().to_string();
+            args = args.dimmed().to_string();
+        }
+        writeln!(output, "{name}{args}  {description}")?;
+    }
+    Ok(())
+}
+
+/// Write the detailed help for a command, including the usage, description and examples.
+///
+/// # Errors
+///
+/// Returns an error if the help cannot be written to the output.
+pub fn write_command_help(
+    configuration: &Configuration,
+    command: &dyn ShellCommand,
+    output: &mut Output,
+) -> Result<()> {
+    let command_identifier = &configuration.command_identifier;
+    let locale = configuration.locale.as_str();
+    let name = format!("{command_identifier}{}", command.name(locale));
+    let args = command.args(locale);
+    let mut usage = if args.is_empty() {
+        name.clone()
+    } else {
+        format!("{name} {args}")
+    };
+    if configuration.color {
+        usage = usage.bold().to_string();
+    }
+
+    writeln!(
+        output,
+        "{}",
+        t!("help_usage", locale = locale, usage = usage)
+    )?;
+    writeln!(output, "{}", command.description(locale))?;
+
+    let examples = command.examples(locale);
+    if !examples.is_empty() {
+        writeln!(output)?;
+        writeln!(output, "{}", t!("help_examples", locale = locale))?;
+        for example in examples {
+            let example = if example.is_empty() {
+                name.clone()
             } else {
-                format!(
-                    " {args:args_width$}",
-                    args = command.args(locale),
-                    args_width = args_width - 1
-                )
+                format!("{name} {example}")
             };
+            writeln!(output, "  {example}")?;
+        }
+    }
+    Ok(())
+}
 
-            let mut name = format!("{command_identifier}{name}");
-            let description = command.description(locale);
-            if options.configuration.color {
-                name = name.bold().to_string();
-                args = args.dimmed().to_string();
-            }
-            writeln!(output, "{name}{args}  {description}")?;
+/// Write the detailed help for all commands; used to generate an offline reference.
+///
+/// # Errors
+///
+/// Returns an error if the help cannot be written to the output.
+pub fn write_commands_help(
+    configuration: &Configuration,
+    command_manager: &CommandManager,
+    output: &mut Output,
+) -> Result<()> {
+    for (index, command) in command_manager.iter().enumerate() {
+        if index > 0 {
+            writeln!(output)?;
         }
-        Ok(LoopCondition::Continue)
+        write_command_help(configuration, command, output)?;
     }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -68,6 +189,7 @@ mod tests {
     use crate::commands::{footer, LoopCondition};
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use indoc::indoc;
     use rsql_drivers::{DriverManager, MockConnection};
@@ -80,17 +202,30 @@ mod tests {
         assert_eq!(name, "help");
     }
 
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[command]");
+    }
+
+    #[test]
+    fn test_examples() {
+        let examples = Command.examples("en");
+        assert_eq!(examples, vec![String::new(), "format".to_string()]);
+    }
+
     #[test]
     fn test_description() {
         let description = Command.description("en");
         assert_eq!(description, "Show this help message");
     }
 
-    async fn test_execute(
+    async fn test_execute_with_arguments(
         color: bool,
         command_identifier: &str,
         locale: &str,
         command: &str,
+        arguments: &[&str],
     ) -> anyhow::Result<String> {
         let mut configuration = Configuration {
             color,
@@ -103,6 +238,8 @@ mod tests {
         command_manager.add(Box::new(Command));
         let mut output = Output::default();
         let command = &format!("{command_identifier}{command}");
+        let mut input = vec![command.to_string()];
+        input.extend(arguments.iter().map(ToString::to_string));
         let options = CommandOptions {
             configuration: &mut configuration,
             command_manager: &command_manager,
@@ -110,7 +247,8 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
-            input: vec![command.to_string()],
+            state: &mut ShellState::default(),
+            input,
             output: &mut output,
         };
 
@@ -118,7 +256,18 @@ mod tests {
 
         assert_eq!(result, LoopCondition::Continue);
         let help_output = output.to_string();
-        assert!(help_output.contains(command));
+        Ok(help_output)
+    }
+
+    async fn test_execute(
+        color: bool,
+        command_identifier: &str,
+        locale: &str,
+        command: &str,
+    ) -> anyhow::Result<String> {
+        let help_output =
+            test_execute_with_arguments(color, command_identifier, locale, command, &[]).await?;
+        assert!(help_output.contains(&format!("{command_identifier}{command}")));
         Ok(help_output)
     }
 
@@ -138,8 +287,8 @@ mod tests {
     async fn test_execute_unicode_format_en() -> anyhow::Result<()> {
         let contents = test_execute(false, ".", "en", "help").await?;
         let expected = indoc! {r"
-            .footer on|off  Enable or disable result footer
-            .help           Show this help message
+            .footer on|off   Enable or disable result footer
+            .help [command]  Show this help message
         "};
         assert_eq!(contents, expected);
         Ok(())
@@ -150,7 +299,7 @@ mod tests {
         let contents = test_execute(false, ".", "de", "hilfe").await?;
         let expected = indoc! {r"
             .fußzeile ein|aus  Ergebnisfuß aktivieren oder deaktivieren
-            .hilfe             Diese Hilfemeldung anzeigen
+            .hilfe [befehl]    Diese Hilfemeldung anzeigen
         "};
         assert_eq!(contents, expected);
         Ok(())
@@ -179,4 +328,62 @@ mod tests {
         let _ = test_execute(false, ".", "zh", "帮助").await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_execute_command_help() -> anyhow::Result<()> {
+        let contents = test_execute_with_arguments(false, ".", "en", "help", &["footer"]).await?;
+        let expected = indoc! {r"
+            Usage: .footer on|off
+            Enable or disable result footer
+
+            Examples:
+              .footer
+              .footer on
+              .footer off
+        "};
+        assert_eq!(contents, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_help_with_identifier() -> anyhow::Result<()> {
+        let contents = test_execute_with_arguments(false, ".", "en", "help", &[".help"]).await?;
+        let expected = indoc! {r"
+            Usage: .help [command]
+            Show this help message
+
+            Examples:
+              .help
+              .help format
+        "};
+        assert_eq!(contents, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_help_invalid_command() {
+        let result = test_execute_with_arguments(false, ".", "en", "help", &["foo"]).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_write_commands_help() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            color: false,
+            command_identifier: ".".to_string(),
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let mut command_manager = CommandManager::new();
+        command_manager.add(Box::new(footer::Command));
+        command_manager.add(Box::new(Command));
+        let mut output = Output::default();
+
+        write_commands_help(&configuration, &command_manager, &mut output)?;
+
+        let contents = output.to_string();
+        assert!(contents.contains("Usage: .footer on|off"));
+        assert!(contents.contains("\n\nUsage: .help [command]"));
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use crate::commands::Error::InvalidOption;
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use colored::Colorize;
 use num_format::{Locale, ToFormattedString};
@@ -22,6 +22,12 @@ impl ShellCommand for Command {
         t!("on_off_argument", locale = locale, on = on, off = off).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let on = t!("on", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!("history_example_arguments", locale = locale, on = on, off = off))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("history_description", locale = locale).to_string()
     }
@@ -86,6 +92,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -128,6 +135,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &history,
+            state: &mut ShellState::default(),
             input: vec![".history".to_string()],
             output: &mut output,
         };
@@ -157,6 +165,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &history,
+            state: &mut ShellState::default(),
             input: vec![".history".to_string()],
             output: &mut output,
         };
@@ -182,6 +191,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".history".to_string(), "on".to_string()],
             output: &mut Output::default(),
         };
@@ -206,6 +216,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".history".to_string(), "off".to_string()],
             output: &mut Output::default(),
         };
@@ -226,6 +237,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".history".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
@@ -1,4 +1,6 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
 use async_trait::async_trait;
 use rsql_drivers::{MemoryQueryResult, Value};
 use rsql_formatters::Results;
@@ -18,10 +20,28 @@ impl ShellCommand for Command {
         t!("indexes_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("indexes_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("indexes_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let start = std::time::Instant::now();
         let output = options.output;
@@ -78,6 +98,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, Index, Metadata, MockConnection, Schema, Table};
     use rsql_formatters::FormatterManager;
@@ -124,6 +145,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".indexes".to_string()],
             output: &mut output,
         };
@@ -160,6 +182,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".indexes".to_string(), table_name.to_string()],
             output: &mut output,
         };
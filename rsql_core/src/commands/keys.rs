@@ -0,0 +1,130 @@
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::shell::KeyAction;
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to display the key bindings for the shell actions
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("keys_command", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("keys_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("keys_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let key_bindings = &options.configuration.key_bindings;
+        let width = key_bindings
+            .iter()
+            .map(|key_binding| key_binding.key.len())
+            .max()
+            .unwrap_or_default();
+
+        for key_binding in key_bindings {
+            let action = match key_binding.action {
+                KeyAction::Execute => t!("keys_execute", locale = locale),
+                KeyAction::ClearScreen => t!("keys_clear_screen", locale = locale),
+                KeyAction::ToggleExpanded => t!("keys_toggle_expanded", locale = locale),
+                KeyAction::Edit => t!("keys_edit", locale = locale),
+            };
+            writeln!(options.output, "{:<width$}  {action}", key_binding.key)?;
+        }
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::LoopCondition;
+    use crate::commands::{CommandManager, CommandOptions};
+    use crate::configuration::Configuration;
+    use crate::shell::{KeyBinding, ShellState};
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "keys");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Display the key bindings");
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let configuration = &mut Configuration {
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".keys".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string(),
+            "alt-enter  Execute the statement being entered\n\
+             ctrl-l     Clear the screen\n\
+             alt-x      Toggle the expanded format\n\
+             alt-e      Edit the statement in an external editor\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_configured_keys() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let configuration = &mut Configuration {
+            key_bindings: vec![KeyBinding::new("f5", KeyAction::Execute)?],
+            ..Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".keys".to_string()],
+            output: &mut output,
+        };
+
+        Command.execute(options).await?;
+
+        assert_eq!(
+            output.to_string(),
+            "f5  Execute the statement being entered\n"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, QueryResult, Row};
+use rsql_formatters::writers::Output;
+use rsql_formatters::Results;
+use rust_i18n::t;
+use std::time::Instant;
+
+/// Display the result of the last query again without re-running the query
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("last_command", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("last_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("last_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let Some(last_result) = options.state.last_result.clone() else {
+            return write_no_result(&options.configuration.locale, options.output);
+        };
+
+        let (columns, rows) = columns_and_rows(last_result).await?;
+        write_result(options, start, columns, rows).await
+    }
+}
+
+/// Get the columns and rows of a result
+///
+/// # Errors
+///
+/// Returns an error if the rows of the result cannot be read, e.g. rows written to a temporary
+/// file when the result exceeded the memory limit.
+pub(crate) async fn columns_and_rows(
+    mut result: MemoryQueryResult,
+) -> rsql_drivers::Result<(Vec<String>, Vec<Row>)> {
+    let columns = result.columns().await;
+    let mut rows = Vec::new();
+    while let Some(row) = result.next().await {
+        rows.push(row);
+    }
+    match result.take_error() {
+        Some(error) => Err(error),
+        None => Ok((columns, rows)),
+    }
+}
+
+/// Get the index of the column with the name; an exact match is preferred over a case-insensitive
+/// match.
+pub(crate) fn column_index(columns: &[String], name: &str) -> Option<usize> {
+    columns
+        .iter()
+        .position(|column| column == name)
+        .or_else(|| {
+            columns
+                .iter()
+                .position(|column| column.eq_ignore_ascii_case(name))
+        })
+}
+
+/// Write a message that there is no result to display
+pub(crate) fn write_no_result(locale: &str, output: &mut Output) -> Result<LoopCondition> {
+    let no_result = t!("last_none", locale = locale).to_string();
+    writeln!(output, "{no_result}")?;
+    Ok(LoopCondition::Continue)
+}
+
+/// Store the columns and rows as the last result and display them with the results format
+pub(crate) async fn write_result(
+    options: CommandOptions<'_>,
+    start: Instant,
+    columns: Vec<String>,
+    rows: Vec<Row>,
+) -> Result<LoopCondition> {
+    let query_result = MemoryQueryResult::new(columns, rows);
+    options.state.last_result = Some(query_result.clone());
+
+    let configuration = options.configuration;
+    let mut results = Results::Query(Box::new(query_result));
+    let formatter_options = &mut configuration.get_formatter_options();
+    let result_format = &configuration.results_format;
+    let formatter = options.formatter_manager.get(result_format).ok_or(
+        rsql_formatters::Error::UnknownFormat {
+            format: result_format.to_string(),
+        },
+    )?;
+
+    formatter_options.elapsed = start.elapsed();
+    formatter
+        .format(formatter_options, &mut results, options.output)
+        .await?;
+
+    Ok(LoopCondition::Continue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection, Value};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "last");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Display the result of the last query again");
+    }
+
+    #[test]
+    fn test_column_index() {
+        let columns = vec!["id".to_string(), "Name".to_string(), "name".to_string()];
+        assert_eq!(column_index(&columns, "id"), Some(0));
+        assert_eq!(column_index(&columns, "name"), Some(2));
+        assert_eq!(column_index(&columns, "NAME"), Some(1));
+        assert_eq!(column_index(&columns, "email"), None);
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let state = &mut ShellState::default();
+        state.last_result = Some(MemoryQueryResult::new(
+            vec!["id".to_string()],
+            vec![vec![Value::I64(1)], vec![Value::I64(2)]],
+        ));
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".last".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string().replace("\r\n", "\n"), "\"id\"\n1\n2\n");
+        assert!(state.last_result.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_result() -> anyhow::Result<()> {
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".last".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string(), "No query result is available\n");
+        Ok(())
+    }
+}
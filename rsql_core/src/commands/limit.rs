@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use num_format::{Locale, ToFormattedString};
 use rust_i18n::t;
@@ -18,6 +18,10 @@ impl ShellCommand for Command {
         t!("limit_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("limit_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("limit_description", locale = locale).to_string()
     }
@@ -47,6 +51,7 @@ mod tests {
     use super::*;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -85,6 +90,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".limit".to_string()],
             output: &mut output,
         };
@@ -110,6 +116,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".limit".to_string(), "42".to_string()],
             output: &mut Output::default(),
         };
@@ -129,6 +136,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".limit".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
@@ -0,0 +1,188 @@
+use crate::commands::Error::{IoError, MissingArguments};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use rust_i18n::t;
+use std::io::Write;
+
+/// Subscribe to a channel and display the notifications until interrupted
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("listen_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("listen_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("listen_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("listen_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let Some(channel) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+
+        let subscriber = options
+            .connection
+            .as_notifications()
+            .ok_or_else(|| IoError(anyhow!("subscribe is not supported for this connection")))?;
+        let mut notifications = subscriber.subscribe(channel).await?;
+        let started = t!("listen_started", locale = locale, channel = channel).to_string();
+        writeln!(options.output, "{started}")?;
+        options.output.flush()?;
+
+        let interrupt = tokio::signal::ctrl_c();
+        tokio::pin!(interrupt);
+        loop {
+            tokio::select! {
+                _ = &mut interrupt => break,
+                notification = notifications.next() => {
+                    let Some(notification) = notification else {
+                        break;
+                    };
+                    let notification = notification?;
+                    let message = t!(
+                        "listen_notification",
+                        locale = locale,
+                        channel = notification.channel,
+                        payload = notification.payload,
+                    )
+                    .to_string();
+                    writeln!(options.output, "{message}")?;
+                    options.output.flush()?;
+                }
+            }
+        }
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use futures_util::stream;
+    use mockall::predicate::eq;
+    use rsql_drivers::{
+        DriverManager, MockCapableConnection, MockConnection, MockNotifications, Notification,
+    };
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "listen");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<channel>");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Subscribe to a channel and display the notifications until interrupted"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".listen".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_not_supported() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".listen".to_string(), "events".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+        assert!(matches!(result, Err(IoError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut notifications = MockNotifications::new();
+        notifications
+            .expect_subscribe()
+            .with(eq("events"))
+            .returning(|_| {
+                let notification = Notification {
+                    channel: "events".to_string(),
+                    payload: "created".to_string(),
+                };
+                Ok(stream::iter(vec![Ok(notification)]).boxed())
+            });
+        let mut connection = MockCapableConnection {
+            notifications: Some(notifications),
+            ..Default::default()
+        };
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".listen".to_string(), "events".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string(),
+            "Listening on channel \"events\"; press Ctrl-C to stop\n\
+             Notification received on channel \"events\" with payload \"created\"\n"
+        );
+        Ok(())
+    }
+}
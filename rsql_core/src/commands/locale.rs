@@ -1,5 +1,8 @@
 use crate::commands::Error::InvalidOption;
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::locales::{available_locales, resolve_locale, suggest_locales};
 use async_trait::async_trait;
 use rust_i18n::t;
 
@@ -17,10 +20,18 @@ impl ShellCommand for Command {
         t!("locale_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("locale_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("locale_description", locale = locale).to_string()
     }
 
+    fn completions(&self, _options: &CompletionOptions<'_>) -> Vec<String> {
+        available_locales()
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
 
@@ -32,14 +43,20 @@ impl ShellCommand for Command {
         }
 
         let new_locale = options.input[1].as_str();
-
-        if !available_locales!().contains(&new_locale) {
+        let Some(resolved_locale) = resolve_locale(new_locale) else {
+            let suggestions = suggest_locales(new_locale);
+            if !suggestions.is_empty() {
+                let locales = suggestions.join(", ");
+                let suggestions =
+                    t!("locale_suggestions", locale = locale, locales = locales).to_string();
+                writeln!(options.output, "{suggestions}")?;
+            }
             return Err(InvalidOption {
                 command_name: self.name(locale).to_string(),
-                option: locale.to_string(),
+                option: new_locale.to_string(),
             });
-        }
-        options.configuration.locale = new_locale.to_string();
+        };
+        options.configuration.locale = resolved_locale;
 
         Ok(LoopCondition::Continue)
     }
@@ -51,6 +68,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -89,6 +107,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".locale".to_string()],
             output: &mut output,
         };
@@ -114,6 +133,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".locale".to_string(), "en-GB".to_string()],
             output: &mut Output::default(),
         };
@@ -125,6 +145,50 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_set_regional_locale() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            locale: "en".to_string(),
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".locale".to_string(), "de_AT".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(configuration.locale, "de".to_string());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option_suggestions() {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".locale".to_string(), "zz".to_string()],
+            output: &mut output,
+        };
+
+        assert!(Command.execute(options).await.is_err());
+        assert_eq!(output.to_string(), "Did you mean: zh\n");
+    }
+
     #[tokio::test]
     async fn test_execute_invalid_option() {
         let options = CommandOptions {
@@ -134,6 +198,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".locale".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
@@ -0,0 +1,460 @@
+use crate::commands::Error::{InvalidOption, IoError, MissingArguments};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::executors::Executor;
+use crate::writers::Output;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use regex::Regex;
+use rsql_drivers::{MemoryQueryResult, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+/// The table that records the migrations applied to the database
+const MIGRATIONS_TABLE: &str = "_rsql_migrations";
+
+/// A migration file; the file name starts with the version of the migration followed by the name
+/// of the migration, e.g. `0001_create_users.sql`
+#[derive(Debug)]
+struct Migration {
+    version: u64,
+    name: String,
+    checksum: String,
+    sql: String,
+}
+
+/// A migration recorded in the migrations table
+#[derive(Debug)]
+struct AppliedMigration {
+    checksum: String,
+    applied_at: String,
+}
+
+/// Apply the pending migrations in a directory to the database, or display the status of the
+/// migrations
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("migrate_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        let status = t!("migrate_status", locale = locale).to_string();
+        t!("migrate_argument", locale = locale, status = status).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let status = t!("migrate_status", locale = locale).to_string();
+        split_examples(&t!("migrate_example_arguments", locale = locale, status = status))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("migrate_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        vec![t!("migrate_status", locale = options.locale).to_string()]
+    }
+
+    async fn execute<'a>(&self, mut options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let locale = options.configuration.locale.clone();
+        let locale = locale.as_str();
+
+        let Some(directory) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        let status = t!("migrate_status", locale = locale).to_string();
+        let status_only = match options.input.get(2) {
+            None => false,
+            Some(option) if option.eq_ignore_ascii_case(&status) => true,
+            Some(option) => {
+                return Err(InvalidOption {
+                    command_name: self.name(locale),
+                    option: option.to_string(),
+                })
+            }
+        };
+
+        let migrations = read_migrations(Path::new(directory)).map_err(|error| InvalidOption {
+            command_name: self.name(locale),
+            option: error.to_string(),
+        })?;
+        let mut applied = applied_migrations(&mut options).await?;
+        if !status_only {
+            if applied.is_none() {
+                let sql = format!(
+                    "CREATE TABLE {MIGRATIONS_TABLE} (version BIGINT NOT NULL PRIMARY KEY, \
+                     name VARCHAR(255) NOT NULL, checksum VARCHAR(64) NOT NULL, \
+                     applied_at VARCHAR(32) NOT NULL);"
+                );
+                execute_sql(&mut options, &sql).await?;
+            }
+            let applied = applied.get_or_insert_with(HashMap::new);
+            for migration in &migrations {
+                if applied.contains_key(&migration.version) {
+                    continue;
+                }
+                let applied_at = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                apply_migration(&mut options, migration, &applied_at).await?;
+                applied.insert(
+                    migration.version,
+                    AppliedMigration {
+                        checksum: migration.checksum.clone(),
+                        applied_at,
+                    },
+                );
+            }
+        }
+
+        let columns = vec![
+            t!("migrate_version", locale = locale).to_string(),
+            t!("migrate_name", locale = locale).to_string(),
+            status,
+            t!("migrate_applied_at", locale = locale).to_string(),
+        ];
+        let applied = applied.unwrap_or_default();
+        let rows = migrations
+            .into_iter()
+            .map(|migration| {
+                let (status, applied_at) = match applied.get(&migration.version) {
+                    Some(applied) if applied.checksum == migration.checksum => (
+                        t!("migrate_applied", locale = locale),
+                        Value::String(applied.applied_at.clone()),
+                    ),
+                    Some(applied) => (
+                        t!("migrate_modified", locale = locale),
+                        Value::String(applied.applied_at.clone()),
+                    ),
+                    None => (t!("migrate_pending", locale = locale), Value::Null),
+                };
+                vec![
+                    Value::U64(migration.version),
+                    Value::String(migration.name),
+                    Value::String(status.to_string()),
+                    applied_at,
+                ]
+            })
+            .collect();
+
+        let configuration = options.configuration;
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Read the migration files in the directory, ordered by version; files that do not start with a
+/// version or do not have the `sql` extension are ignored.
+fn read_migrations(directory: &Path) -> anyhow::Result<Vec<Migration>> {
+    let regex = Regex::new(r"^(\d+)[_\-.]?(.*)\.sql$")?;
+    let mut migrations: Vec<Migration> = Vec::new();
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(captures) = regex.captures(&file_name) else {
+            continue;
+        };
+        let version = captures[1].parse::<u64>()?;
+        if migrations.iter().any(|migration| migration.version == version) {
+            return Err(anyhow!("duplicate migration version: {version}"));
+        }
+        let sql = fs::read_to_string(entry.path())?;
+        let checksum = format!("{:x}", Sha256::digest(sql.as_bytes()));
+        migrations.push(Migration {
+            version,
+            name: captures[2].replace('_', " "),
+            checksum,
+            sql,
+        });
+    }
+    migrations.sort_by_key(|migration| migration.version);
+    Ok(migrations)
+}
+
+/// Get the migrations recorded in the migrations table by version; returns `None` if the
+/// migrations table does not exist.  A failed read is only treated as a missing table when the
+/// table is not in the current schema; any other error is returned.
+async fn applied_migrations(
+    options: &mut CommandOptions<'_>,
+) -> Result<Option<HashMap<u64, AppliedMigration>>> {
+    let sql = format!("SELECT version, checksum, applied_at FROM {MIGRATIONS_TABLE}");
+    let mut query_result = match options.connection.query(&sql).await {
+        Ok(query_result) => query_result,
+        Err(error) if migrations_table_exists(options).await? => return Err(error.into()),
+        Err(_) => return Ok(None),
+    };
+    let mut applied = HashMap::new();
+    while let Some(row) = query_result.next().await {
+        let value = |index: usize| row.get(index).map(ToString::to_string).unwrap_or_default();
+        let Ok(version) = value(0).parse::<u64>() else {
+            continue;
+        };
+        applied.insert(
+            version,
+            AppliedMigration {
+                checksum: value(1),
+                applied_at: value(2),
+            },
+        );
+    }
+    Ok(Some(applied))
+}
+
+/// Returns true if the migrations table is in the current schema of the connection.
+async fn migrations_table_exists(options: &mut CommandOptions<'_>) -> Result<bool> {
+    let metadata = options.connection.metadata().await?;
+    let exists = metadata.current_schema().is_some_and(|schema| {
+        schema
+            .tables()
+            .iter()
+            .any(|table| table.name().eq_ignore_ascii_case(MIGRATIONS_TABLE))
+    });
+    Ok(exists)
+}
+
+/// Apply the migration and record it in the migrations table, in a transaction when the
+/// connection supports transactions; the transaction is rolled back if the migration fails.
+async fn apply_migration(
+    options: &mut CommandOptions<'_>,
+    migration: &Migration,
+    applied_at: &str,
+) -> Result<()> {
+    let transaction = options.connection.begin_transaction_statement();
+    if let Some(begin) = transaction {
+        options.connection.execute(begin).await?;
+    }
+
+    let version = migration.version;
+    let name = migration.name.replace('\'', "''");
+    let checksum = &migration.checksum;
+    let insert = format!(
+        "INSERT INTO {MIGRATIONS_TABLE} (version, name, checksum, applied_at) \
+         VALUES ({version}, '{name}', '{checksum}', '{applied_at}');"
+    );
+    let mut result = execute_sql(options, &migration.sql).await;
+    if result.is_ok() {
+        result = execute_sql(options, &insert).await;
+    }
+
+    if transaction.is_some() {
+        let end = if result.is_ok() { "COMMIT" } else { "ROLLBACK" };
+        options.connection.execute(end).await?;
+    }
+    result
+}
+
+/// Execute the SQL statements with an executor, so that the read-only mode and timeout of the
+/// configuration are enforced; the output of the statements is discarded.
+async fn execute_sql(options: &mut CommandOptions<'_>, sql: &str) -> Result<()> {
+    let mut output = Output::default();
+    let mut executor = Executor::new(
+        &mut *options.configuration,
+        options.command_manager,
+        options.driver_manager,
+        options.formatter_manager,
+        options.history,
+        &mut *options.state,
+        &mut *options.connection,
+        &mut output,
+    );
+    executor
+        .execute(sql)
+        .await
+        .map_err(|error| IoError(error.into()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use rsql_drivers::{DriverManager, Metadata, MockConnection, Schema, Table};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    const DATABASE_URL: &str = "rusqlite://";
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "migrate");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<directory> [status]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Apply the pending migrations in a directory, or display the status of the migrations"
+        );
+    }
+
+    async fn execute(
+        connection: &mut dyn rsql_drivers::Connection,
+        input: &[&str],
+    ) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                results_format: "csv".to_string(),
+                results_footer: false,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        Command.execute(options).await?;
+        Ok(output.to_string().replace("\r\n", "\n"))
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        fs::write(
+            directory.path().join("0001_create_users.sql"),
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);",
+        )?;
+        fs::write(
+            directory.path().join("0002_add_users.sql"),
+            "INSERT INTO users (id, name) VALUES (1, 'foo');\n\
+             INSERT INTO users (id, name) VALUES (2, 'bar');",
+        )?;
+        fs::write(directory.path().join("README.md"), "migrations")?;
+        let path = directory.path().to_string_lossy().to_string();
+        let mut connection = DriverManager::default().connect(DATABASE_URL).await?;
+
+        let output = execute(connection.as_mut(), &[".migrate", &path, "status"]).await?;
+        assert_eq!(
+            output,
+            "\"version\",\"name\",\"status\",\"applied at\"\n\
+             1,\"create users\",\"pending\",\"\"\n\
+             2,\"add users\",\"pending\",\"\"\n"
+        );
+
+        let output = execute(connection.as_mut(), &[".migrate", &path]).await?;
+        assert!(output.contains("1,\"create users\",\"applied\","));
+        assert!(output.contains("2,\"add users\",\"applied\","));
+        let mut query_result = connection.query("SELECT COUNT(*) FROM users").await?;
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(2)]));
+
+        // Applying the migrations again does nothing and modified migrations are reported
+        fs::write(
+            directory.path().join("0001_create_users.sql"),
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);",
+        )?;
+        let output = execute(connection.as_mut(), &[".migrate", &path]).await?;
+        assert!(output.contains("1,\"create users\",\"modified\","));
+        let mut query_result = connection.query("SELECT COUNT(*) FROM users").await?;
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(2)]));
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_rollback() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        fs::write(
+            directory.path().join("1_create_users.sql"),
+            "CREATE TABLE users (id INTEGER PRIMARY KEY);\n\
+             INSERT INTO missing (id) VALUES (1);",
+        )?;
+        let path = directory.path().to_string_lossy().to_string();
+        let mut connection = DriverManager::default().connect(DATABASE_URL).await?;
+
+        assert!(execute(connection.as_mut(), &[".migrate", &path])
+            .await
+            .is_err());
+        assert!(connection.query("SELECT * FROM users").await.is_err());
+        let output = execute(connection.as_mut(), &[".migrate", &path, "status"]).await?;
+        assert!(output.contains("1,\"create users\",\"pending\","));
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_errors() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        fs::write(directory.path().join("1_foo.sql"), "SELECT 1;")?;
+        fs::write(directory.path().join("01_bar.sql"), "SELECT 2;")?;
+        let path = directory.path().to_string_lossy().to_string();
+        let mut connection = DriverManager::default().connect(DATABASE_URL).await?;
+
+        assert!(execute(connection.as_mut(), &[".migrate"]).await.is_err());
+        assert!(execute(connection.as_mut(), &[".migrate", &path])
+            .await
+            .is_err());
+        assert!(execute(connection.as_mut(), &[".migrate", "foo", "bar"])
+            .await
+            .is_err());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_migrations_table_error() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        fs::write(directory.path().join("1_foo.sql"), "SELECT 1;")?;
+        let path = directory.path().to_string_lossy().to_string();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_query()
+            .returning(|_| Err(rsql_drivers::Error::IoError(anyhow!("permission denied"))));
+        connection.expect_metadata().returning(|| {
+            let mut schema = Schema::new("main", true);
+            schema.add(Table::new(MIGRATIONS_TABLE));
+            let mut metadata = Metadata::new();
+            metadata.add(schema);
+            Ok(metadata)
+        });
+        connection.expect_execute().never();
+
+        let error = execute(&mut connection, &[".migrate", &path])
+            .await
+            .expect_err("error");
+        assert!(error.to_string().contains("permission denied"));
+        Ok(())
+    }
+}
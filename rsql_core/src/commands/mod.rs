@@ -1,35 +1,105 @@
+pub mod ask;
+pub mod asof;
+pub mod attach;
 pub mod bail;
+pub mod bench;
+pub mod bytes;
+pub mod cache;
 pub mod changes;
+pub mod checksum;
 pub mod clear;
+pub mod clip;
 pub mod color;
+pub mod columns;
+pub mod compare;
 pub mod command;
 pub mod completions;
+pub mod connect;
+pub mod copyin;
+pub mod dateformat;
+pub mod ddl;
+pub mod demo;
 pub mod describe;
+pub mod diff;
 pub mod drivers;
 pub mod echo;
+pub mod edit;
 pub mod error;
 pub mod exit;
+pub mod expanded;
+pub mod export;
+pub mod filter;
 pub mod footer;
 pub mod format;
+pub mod generate;
 pub mod header;
 pub mod help;
 pub mod history;
 pub mod indexes;
+pub mod keys;
+pub mod last;
 pub mod limit;
+pub mod listen;
 pub mod locale;
+pub mod migrate;
 pub mod output;
+pub mod parallel;
 pub mod print;
+pub mod profile;
 pub mod quit;
 pub mod read;
+pub mod readonly;
+pub mod refresh;
 pub mod rows;
+pub mod sample;
 pub mod schemas;
+pub mod send;
+pub mod session;
 pub mod sleep;
+pub mod snapshot;
+pub mod sort;
+pub mod stash;
+pub mod stream;
+pub mod sync;
 pub mod system;
 pub mod tables;
 pub mod tee;
+pub mod theme;
+pub mod timeout;
 pub mod timer;
+pub mod truncate;
+pub mod tutorial;
+pub mod validate;
+pub mod view;
 
 pub use command::{
-    CommandManager, CommandOptions, LoopCondition, ShellCommand, ToggleShellCommand,
+    CommandManager, CommandOptions, CompletionOptions, LoopCondition, ShellCommand,
+    ToggleShellCommand,
 };
+pub(crate) use command::split_examples;
 pub use error::{Error, Result};
+
+/// Execute the command with the configuration and input, and return the output; for the tests of
+/// commands that display or change the configuration
+#[cfg(test)]
+pub(crate) async fn execute(
+    command: &dyn ShellCommand,
+    configuration: &mut crate::configuration::Configuration,
+    input: &[&str],
+) -> Result<String> {
+    let mut output = crate::writers::Output::default();
+    let options = CommandOptions {
+        configuration,
+        command_manager: &CommandManager::default(),
+        driver_manager: &rsql_drivers::DriverManager::default(),
+        formatter_manager: &rsql_formatters::FormatterManager::default(),
+        connection: &mut rsql_drivers::MockConnection::new(),
+        history: &rustyline::history::DefaultHistory::new(),
+        state: &mut crate::shell::ShellState::default(),
+        input: input.iter().map(ToString::to_string).collect(),
+        output: &mut output,
+    };
+    let result = command.execute(options).await?;
+    assert_eq!(result, LoopCondition::Continue);
+    Ok(output.to_string())
+}
@@ -1,13 +1,41 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::configuration::OutputTarget;
 use async_trait::async_trait;
-use rsql_formatters::writers::{ClipboardWriter, FileWriter, StdoutWriter};
+use rsql_formatters::writers::{ChunkedWriter, ClipboardWriter, FileWriter, StdoutWriter};
 use rust_i18n::t;
 use std::fs::File;
 
+/// Option to split the output into multiple files with the specified number of rows
+const CHUNK_ROWS_OPTION: &str = "--chunk-rows";
+
+/// Prefix of the option to insert the rows of query results into a table on the connection
+const TABLE_PREFIX: &str = "table:";
+
 /// Command to output results to a file or console
 #[derive(Debug, Default)]
 pub struct Command;
 
+impl Command {
+    /// Get the number of rows for each file when the output is split into multiple files
+    fn chunk_rows(&self, locale: &str, options: &[String]) -> Result<Option<usize>> {
+        let invalid_option = || InvalidOption {
+            command_name: self.name(locale).to_string(),
+            option: options.join(" "),
+        };
+        match options {
+            [] => Ok(None),
+            [option, rows] if option == CHUNK_ROWS_OPTION => match rows.parse::<usize>() {
+                Ok(rows) if rows > 0 => Ok(Some(rows)),
+                _ => Err(invalid_option()),
+            },
+            _ => Err(invalid_option()),
+        }
+    }
+}
+
 #[async_trait]
 impl ShellCommand for Command {
     fn name(&self, locale: &str) -> String {
@@ -26,10 +54,25 @@ impl ShellCommand for Command {
         .to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let clipboard = t!("output_clipboard", locale = locale).to_string();
+        split_examples(&t!(
+            "output_example_arguments",
+            locale = locale,
+            clipboard = clipboard,
+            table_prefix = TABLE_PREFIX,
+            chunk_rows = CHUNK_ROWS_OPTION,
+        ))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("output_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        vec![t!("output_clipboard", locale = options.locale).to_string()]
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
         let clipboard = t!("output_clipboard", locale = locale).to_string();
@@ -37,12 +80,32 @@ impl ShellCommand for Command {
 
         if option.is_empty() {
             options.output.set(Box::new(StdoutWriter));
+            options.configuration.output = OutputTarget::Stdout;
         } else if option == clipboard {
             options.output.set(Box::<ClipboardWriter>::default());
+            options.configuration.output = OutputTarget::Clipboard;
+        } else if let Some(table) = option.strip_prefix(TABLE_PREFIX) {
+            if table.is_empty() || options.input.len() > 2 {
+                return Err(InvalidOption {
+                    command_name: self.name(locale).to_string(),
+                    option: options.input[1..].join(" "),
+                });
+            }
+            options.output.set(Box::new(StdoutWriter));
+            options.configuration.output = OutputTarget::Table(table.to_string());
+        } else if let Some(rows) = self.chunk_rows(locale, &options.input[2..])? {
+            let header_lines = usize::from(options.configuration.results_header);
+            let writer = ChunkedWriter::new(option.as_str(), rows)?.with_header_lines(header_lines);
+            options.output.set(Box::new(writer));
+            options.configuration.output = OutputTarget::Chunked {
+                path: option.into(),
+                rows,
+            };
         } else {
-            let file = File::create(option)?;
+            let file = File::create(&option)?;
             let writer = FileWriter::new(file);
             options.output.set(Box::new(writer));
+            options.configuration.output = OutputTarget::File(option.into());
         }
 
         Ok(LoopCondition::Continue)
@@ -55,6 +118,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -70,7 +134,7 @@ mod tests {
     #[test]
     fn test_args() {
         let args = Command.args("en");
-        assert_eq!(args, "clipboard|<file>");
+        assert_eq!(args, "clipboard|table:<name>|<file> [--chunk-rows <n>]");
     }
 
     #[test]
@@ -93,6 +157,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".output".to_string()],
             output: &mut output,
         };
@@ -115,6 +180,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".output".to_string(), "clipboard".to_string()],
             output: &mut output,
         };
@@ -132,13 +198,15 @@ mod tests {
         assert!(output.to_string().is_empty());
         let file = NamedTempFile::new()?;
         let path = file.as_ref().to_string_lossy().to_string();
+        let configuration = &mut Configuration::default();
         let options = CommandOptions {
-            configuration: &mut Configuration::default(),
+            configuration,
             command_manager: &CommandManager::default(),
             driver_manager: &DriverManager::default(),
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".output".to_string(), path.clone()],
             output: &mut output,
         };
@@ -146,6 +214,115 @@ mod tests {
         let result = Command.execute(options).await?;
         assert_eq!(result, LoopCondition::Continue);
         assert!(output.to_string().contains("File"));
+        assert_eq!(configuration.output, OutputTarget::File(path.into()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_chunked_file() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("results-{n}.csv");
+        let path = path.to_string_lossy().to_string();
+        let configuration = &mut Configuration::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![
+                ".output".to_string(),
+                path.clone(),
+                "--chunk-rows".to_string(),
+                "1000".to_string(),
+            ],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        assert!(output.to_string().ends_with("results-1.csv"));
+        assert!(directory.path().join("results-1.csv").exists());
+        assert_eq!(
+            configuration.output,
+            OutputTarget::Chunked {
+                path: path.into(),
+                rows: 1000
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_table() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let configuration = &mut Configuration::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".output".to_string(), "table:results".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            configuration.output,
+            OutputTarget::Table("results".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_table() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".output".to_string(), "table:".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_chunk_rows() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let file = NamedTempFile::new()?;
+        let path = file.as_ref().to_string_lossy().to_string();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![
+                ".output".to_string(),
+                path,
+                "--chunk-rows".to_string(),
+                "0".to_string(),
+            ],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
         Ok(())
     }
 }
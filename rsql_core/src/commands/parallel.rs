@@ -0,0 +1,175 @@
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use num_format::{Locale, ToFormattedString};
+use rust_i18n::t;
+use std::str::FromStr;
+
+/// Command to set the number of connections used to execute consecutive queries in parallel.
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("parallel_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("parallel_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("parallel_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("parallel_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+
+        if options.input.len() <= 1 {
+            let num_locale = Locale::from_str(locale).unwrap_or(Locale::en);
+            let connections = options
+                .configuration
+                .parallel
+                .to_formatted_string(&num_locale);
+            let parallel_setting = t!(
+                "parallel_setting",
+                locale = locale,
+                connections = connections
+            )
+            .to_string();
+            writeln!(options.output, "{parallel_setting}")?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let connections = options.input[1].parse::<usize>()?;
+        options.configuration.parallel = connections.max(1);
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{CommandManager, CommandOptions};
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+    use std::default;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "parallel");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[connections]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Set the number of connections used to run consecutive queries in parallel"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let configuration = &mut Configuration {
+            parallel: 4,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".parallel".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let parallel_output = output.to_string();
+        assert_eq!(parallel_output, "Parallel connections: 4\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_parallel() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".parallel".to_string(), "8".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let _ = Command.execute(options).await?;
+
+        assert_eq!(configuration.parallel, 8);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_parallel_zero() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            parallel: 4,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".parallel".to_string(), "0".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let _ = Command.execute(options).await?;
+
+        assert_eq!(configuration.parallel, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".parallel".to_string(), "foo".to_string()],
+            output: &mut Output::default(),
+        };
+        assert!(Command.execute(options).await.is_err());
+    }
+}
@@ -0,0 +1,329 @@
+use crate::commands::sample::limit_query;
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::shell::quote_identifier;
+use async_trait::async_trait;
+use rsql_drivers::{Connection, MemoryQueryResult, Row, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+
+/// The number of most frequent values displayed for each column
+const TOP_VALUES: usize = 3;
+
+/// Display statistics for each column of a table
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("profile_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("profile_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("profile_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("profile_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[expect(clippy::too_many_lines)]
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.as_str();
+        let Some(table_name) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+
+        let start = std::time::Instant::now();
+        let metadata = options.connection.metadata().await?;
+        let Some(table) = metadata
+            .current_schema()
+            .and_then(|schema| schema.get(table_name))
+        else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: table_name.to_string(),
+            });
+        };
+
+        let connection = &mut *options.connection;
+        // The dialect is not Send, so the names are quoted before the statistics are queried
+        let (table_name, names) = {
+            let dialect = connection.dialect();
+            let names = table
+                .columns()
+                .iter()
+                .map(|column| quote_identifier(dialect.as_ref(), column.name()))
+                .collect::<Vec<_>>();
+            (quote_identifier(dialect.as_ref(), table.name()), names)
+        };
+        let list_delimiter = t!("list_delimiter", locale = locale).to_string();
+        let mut rows = Vec::new();
+        for (column, name) in table.columns().into_iter().zip(names) {
+            let counts = query_row(
+                connection,
+                &format!("SELECT COUNT(*), COUNT({name}) FROM {table_name}"),
+            )
+            .await?;
+            let total = counts.first().and_then(Value::to_f64).unwrap_or_default();
+            let non_null = counts.get(1).and_then(Value::to_f64).unwrap_or_default();
+            let nulls = if total > 0.0 {
+                Value::String(format!("{:.1}%", (total - non_null) * 100.0 / total))
+            } else {
+                Value::Null
+            };
+
+            // Not all data types support distinct counts or comparisons, e.g. JSON or boolean
+            // values, so the statistics are null when the queries fail
+            let distinct = query_row(
+                connection,
+                &format!("SELECT COUNT(DISTINCT {name}) FROM {table_name}"),
+            )
+            .await
+            .ok()
+            .and_then(|row| row.into_iter().next())
+            .unwrap_or(Value::Null);
+            let (min, max) = match query_row(
+                connection,
+                &format!("SELECT MIN({name}), MAX({name}) FROM {table_name}"),
+            )
+            .await
+            {
+                Ok(row) => {
+                    let mut values = row.into_iter();
+                    (
+                        values.next().unwrap_or(Value::Null),
+                        values.next().unwrap_or(Value::Null),
+                    )
+                }
+                Err(_) => (Value::Null, Value::Null),
+            };
+            let top_values = top_values(connection, &table_name, &name)
+                .await
+                .unwrap_or_default()
+                .join(&list_delimiter);
+
+            rows.push(vec![
+                Value::String(column.name().to_string()),
+                Value::String(column.data_type().to_string()),
+                nulls,
+                distinct,
+                min,
+                max,
+                Value::String(top_values),
+            ]);
+        }
+
+        let columns = vec![
+            t!("profile_column", locale = locale).to_string(),
+            t!("profile_type", locale = locale).to_string(),
+            t!("profile_nulls", locale = locale).to_string(),
+            t!("profile_distinct", locale = locale).to_string(),
+            t!("profile_min", locale = locale).to_string(),
+            t!("profile_max", locale = locale).to_string(),
+            t!("profile_top_values", locale = locale).to_string(),
+        ];
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Execute the query and return the first row; an empty row is returned when the query does not
+/// return any rows
+async fn query_row(connection: &mut dyn Connection, sql: &str) -> Result<Row> {
+    let mut query_result = connection.query(sql).await?;
+    Ok(query_result.next().await.unwrap_or_default())
+}
+
+/// Get the most frequent values of the column, with the number of rows for each value, e.g.
+/// `active (42)`
+async fn top_values(
+    connection: &mut dyn Connection,
+    table_name: &str,
+    column_name: &str,
+) -> Result<Vec<String>> {
+    let sql = limit_query(
+        connection.dialect().as_ref(),
+        &format!("{column_name}, COUNT(*)"),
+        &format!(
+            "FROM {table_name} WHERE {column_name} IS NOT NULL GROUP BY {column_name} ORDER BY COUNT(*) DESC"
+        ),
+        TOP_VALUES,
+    );
+    let mut query_result = connection.query(&sql).await?;
+    let mut values = Vec::new();
+    while let Some(row) = query_result.next().await {
+        if let (Some(value), Some(count)) = (row.first(), row.get(1)) {
+            values.push(format!("{value} ({count})"));
+        }
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use indoc::indoc;
+    use rsql_drivers::{Column, DriverManager, Metadata, MockConnection, Schema, Table};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+    use sqlparser::dialect::PostgreSqlDialect;
+
+    fn metadata() -> Metadata {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("status", "TEXT", false, None));
+        let mut schema = Schema::new("public", true);
+        schema.add(table);
+        let mut metadata = Metadata::new();
+        metadata.add(schema);
+        metadata
+    }
+
+    fn query_result(columns: usize, rows: Vec<Row>) -> Box<dyn rsql_drivers::QueryResult> {
+        let columns = (0..columns).map(|column| column.to_string()).collect();
+        Box::new(MemoryQueryResult::new(columns, rows))
+    }
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "profile");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "table");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Display statistics for each column of a table");
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection.expect_metadata().returning(|| Ok(metadata()));
+        connection
+            .expect_dialect()
+            .returning(|| Box::new(PostgreSqlDialect {}));
+        connection.expect_query().returning(|sql| {
+            let result = match sql {
+                "SELECT COUNT(*), COUNT(status) FROM users" => {
+                    query_result(2, vec![vec![Value::I64(4), Value::I64(3)]])
+                }
+                "SELECT COUNT(DISTINCT status) FROM users" => {
+                    query_result(1, vec![vec![Value::I64(2)]])
+                }
+                "SELECT MIN(status), MAX(status) FROM users" => query_result(
+                    2,
+                    vec![vec![
+                        Value::String("active".to_string()),
+                        Value::String("inactive".to_string()),
+                    ]],
+                ),
+                _ => query_result(
+                    2,
+                    vec![
+                        vec![Value::String("active".to_string()), Value::I64(2)],
+                        vec![Value::String("inactive".to_string()), Value::I64(1)],
+                    ],
+                ),
+            };
+            Ok(result)
+        });
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".profile".to_string(), "users".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let expected = indoc! {r#"
+            "column","type","nulls","distinct","min","max","top values"
+            "status","TEXT","25.0%",2,"active","inactive","active (2), inactive (1)"
+        "#};
+        assert_eq!(output.to_string().replace("\r\n", "\n"), expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_table() {
+        let mut connection = MockConnection::new();
+        connection.expect_metadata().returning(|| Ok(metadata()));
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".profile".to_string(), "foo".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rust_i18n::t;
 use tracing::info;
@@ -13,6 +13,10 @@ impl ShellCommand for Command {
         t!("quit_command", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("quit_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("quit_description", locale = locale).to_string()
     }
@@ -31,6 +35,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -60,6 +65,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".quit".to_string()],
             output: &mut Output::default(),
         };
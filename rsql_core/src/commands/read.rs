@@ -1,6 +1,6 @@
 use crate::commands::Error::InvalidOption;
-use crate::commands::{CommandOptions, Error, LoopCondition, Result, ShellCommand};
-use crate::executors::Executor;
+use crate::commands::{split_examples, CommandOptions, Error, LoopCondition, Result, ShellCommand};
+use crate::executors::{is_template, render_template, Executor};
 use async_trait::async_trait;
 use rust_i18n::t;
 use std::fs;
@@ -19,6 +19,10 @@ impl ShellCommand for Command {
         t!("read_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("read_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("read_description", locale = locale).to_string()
     }
@@ -26,14 +30,31 @@ impl ShellCommand for Command {
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
         let file = options.input.get(1).unwrap_or(&String::new()).to_string();
-        let contents = fs::read_to_string(file);
+        let contents = match fs::read_to_string(&file) {
+            Ok(contents) => contents,
+            Err(error) => {
+                return Err(InvalidOption {
+                    command_name: self.name(locale).to_string(),
+                    option: error.to_string(),
+                });
+            }
+        };
 
-        if let Err(error) = contents {
-            return Err(InvalidOption {
-                command_name: self.name(locale).to_string(),
-                option: error.to_string(),
-            });
+        let mut variables = options.state.variables.clone();
+        for argument in options.input.iter().skip(2) {
+            let Some((name, value)) = argument.split_once('=') else {
+                return Err(InvalidOption {
+                    command_name: self.name(locale).to_string(),
+                    option: argument.to_string(),
+                });
+            };
+            variables.insert(name.to_string(), value.to_string());
         }
+        let contents = if is_template(&file) {
+            render_template(&contents, &variables).map_err(|error| Error::IoError(error.into()))?
+        } else {
+            contents
+        };
 
         let mut executor = Executor::new(
             options.configuration,
@@ -41,11 +62,12 @@ impl ShellCommand for Command {
             options.driver_manager,
             options.formatter_manager,
             options.history,
+            options.state,
             options.connection,
             options.output,
         );
 
-        match executor.execute(contents?.as_str()).await {
+        match executor.execute(contents.as_str()).await {
             Ok(loop_condition) => Ok(loop_condition),
             Err(error) => {
                 return Err(Error::IoError(error.into()));
@@ -60,6 +82,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -76,7 +99,7 @@ mod tests {
     #[test]
     fn test_args() {
         let args = Command.args("en");
-        assert_eq!(args, "[file]");
+        assert_eq!(args, "[file] [name=value ...]");
     }
 
     #[test]
@@ -96,6 +119,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".read".to_string()],
             output: &mut output,
         };
@@ -120,6 +144,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".read".to_string(), path],
             output: &mut Output::default(),
         };
@@ -131,6 +156,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_read_template() -> anyhow::Result<()> {
+        let mut file = tempfile::Builder::new().suffix(".sql.j2").tempfile()?;
+        write!(file, "{{% if region == 'EU' %}}.locale {{{{ locale }}}}{{% endif %}}")?;
+        let path = file.as_ref().to_string_lossy().to_string();
+
+        let configuration = &mut Configuration {
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let state = &mut ShellState::default();
+        state
+            .variables
+            .insert("locale".to_string(), "en-GB".to_string());
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".read".to_string(), path, "region=EU".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(configuration.locale, "en-GB".to_string());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_error() -> anyhow::Result<()> {
         let mut file = NamedTempFile::new()?;
@@ -149,6 +207,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".read".to_string(), path.to_string()],
             output: &mut Output::default(),
         };
@@ -166,6 +225,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".read".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
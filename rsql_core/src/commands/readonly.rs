@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+
+use crate::commands::{CommandOptions, ToggleShellCommand};
+
+/// Command to refuse statements that modify the database
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ToggleShellCommand for Command {
+    fn get_name(&self) -> &'static str {
+        "readonly_command"
+    }
+
+    fn get_description(&self) -> &'static str {
+        "readonly_description"
+    }
+
+    fn get_setting_str(&self) -> &'static str {
+        "readonly_setting"
+    }
+
+    fn get_value(&self, options: &CommandOptions<'_>) -> bool {
+        options.configuration.read_only
+    }
+
+    fn set_value(&self, options: &mut CommandOptions<'_>, value: bool) {
+        options.configuration.read_only = value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shell::ShellState;
+    use std::default;
+
+    use rustyline::history::DefaultHistory;
+
+    use crate::commands::{CommandManager, CommandOptions, LoopCondition, ShellCommand};
+    use crate::configuration::Configuration;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "readonly");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "on|off");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Refuse statements that modify the database");
+    }
+
+    async fn test_execute_no_args(read_only: bool) -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let configuration = &mut Configuration {
+            read_only,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".readonly".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let read_only_output = output.to_string();
+
+        if read_only {
+            assert_eq!(read_only_output, "Read-only: on\n");
+        } else {
+            assert_eq!(read_only_output, "Read-only: off\n");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args_on() -> anyhow::Result<()> {
+        test_execute_no_args(true).await
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args_off() -> anyhow::Result<()> {
+        test_execute_no_args(false).await
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_on() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            read_only: false,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".readonly".to_string(), "on".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert!(configuration.read_only);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_off() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            read_only: true,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".readonly".to_string(), "off".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert!(!configuration.read_only);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".readonly".to_string(), "foo".to_string()],
+            output: &mut Output::default(),
+        };
+        assert!(Command.execute(options).await.is_err());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::commands::Error::MissingArguments;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use rsql_formatters::Results;
+use rust_i18n::t;
+use sqlparser::dialect::{Dialect, MsSqlDialect, MySqlDialect};
+use std::str::FromStr;
+
+/// The number of rows returned when the number of rows is not specified
+const DEFAULT_ROWS: usize = 10;
+
+/// Display a random sample of the rows of a table
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("sample_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("sample_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("sample_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("sample_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        options
+            .metadata
+            .current_schema()
+            .map(|schema| {
+                schema
+                    .tables()
+                    .iter()
+                    .map(|table| table.name().to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.as_str();
+        let Some(table) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        let rows = match options.input.get(2) {
+            Some(rows) => usize::from_str(rows)?,
+            None => DEFAULT_ROWS,
+        };
+
+        let start = std::time::Instant::now();
+        let sql = {
+            let dialect = options.connection.dialect();
+            let random = random_function(dialect.as_ref());
+            limit_query(
+                dialect.as_ref(),
+                "*",
+                &format!("FROM {table} ORDER BY {random}"),
+                rows,
+            )
+        };
+        let query_result = options.connection.query(&sql).await?;
+
+        let mut results = Results::Query(query_result);
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Get the function of the dialect that returns a random value
+fn random_function(dialect: &dyn Dialect) -> &'static str {
+    if dialect.is::<MsSqlDialect>() {
+        "NEWID()"
+    } else if dialect.is::<MySqlDialect>() {
+        "RAND()"
+    } else {
+        "RANDOM()"
+    }
+}
+
+/// Build a query that returns at most `limit` rows, using `TOP` for SQL Server and `LIMIT` for
+/// other databases; `clauses` are the clauses that follow the selected expressions, e.g.
+/// `FROM users ORDER BY id`
+pub(crate) fn limit_query(
+    dialect: &dyn Dialect,
+    expressions: &str,
+    clauses: &str,
+    limit: usize,
+) -> String {
+    if dialect.is::<MsSqlDialect>() {
+        format!("SELECT TOP {limit} {expressions} {clauses}")
+    } else {
+        format!("SELECT {expressions} {clauses} LIMIT {limit}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use mockall::predicate::eq;
+    use rsql_drivers::{DriverManager, MemoryQueryResult, MockConnection, Value};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+    use sqlparser::dialect::{GenericDialect, PostgreSqlDialect};
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "sample");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "table [rows]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Display a random sample of the rows of a table"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_dialect()
+            .returning(|| Box::new(PostgreSqlDialect {}));
+        connection
+            .expect_query()
+            .with(eq("SELECT * FROM users ORDER BY RANDOM() LIMIT 2"))
+            .returning(|_| {
+                let query_result = MemoryQueryResult::new(
+                    vec!["id".to_string()],
+                    vec![vec![Value::I64(3)], vec![Value::I64(1)]],
+                );
+                Ok(Box::new(query_result))
+            });
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection,
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".sample".to_string(), "users".to_string(), "2".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string().replace("\r\n", "\n"), "\"id\"\n3\n1\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_table() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".sample".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_random_function() {
+        assert_eq!(random_function(&GenericDialect), "RANDOM()");
+        assert_eq!(random_function(&MySqlDialect {}), "RAND()");
+        assert_eq!(random_function(&MsSqlDialect {}), "NEWID()");
+    }
+
+    #[test]
+    fn test_limit_query() {
+        assert_eq!(
+            limit_query(&GenericDialect, "*", "FROM users", 5),
+            "SELECT * FROM users LIMIT 5"
+        );
+        assert_eq!(
+            limit_query(&MsSqlDialect {}, "*", "FROM users", 5),
+            "SELECT TOP 5 * FROM users"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rsql_drivers::{MemoryQueryResult, Value};
 use rsql_formatters::Results;
@@ -14,6 +14,10 @@ impl ShellCommand for Command {
         t!("schemas_command", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("schemas_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("schemas_description", locale = locale).to_string()
     }
@@ -66,6 +70,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, Metadata, MockConnection, Schema};
     use rsql_formatters::FormatterManager;
@@ -102,6 +107,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: mock_connection,
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".schemas".to_string()],
             output: &mut output,
         };
@@ -1,5 +1,5 @@
 use crate::commands::Error::InvalidOption;
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rust_i18n::t;
 use std::thread::sleep;
@@ -19,6 +19,10 @@ impl ShellCommand for Command {
         t!("sleep_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("sleep_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("sleep_description", locale = locale).to_string()
     }
@@ -55,6 +59,7 @@ mod tests {
     use super::*;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -88,6 +93,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".sleep".to_string()],
             output: &mut Output::default(),
         };
@@ -109,6 +115,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".sleep".to_string(), ".5".to_string()],
             output: &mut Output::default(),
         };
@@ -130,6 +137,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".sleep".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
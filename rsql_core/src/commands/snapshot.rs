@@ -0,0 +1,193 @@
+use crate::commands::last::{columns_and_rows, write_no_result, write_result};
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use rsql_drivers::{read_snapshot, write_snapshot};
+use rust_i18n::t;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::time::Instant;
+
+/// Save the result of the last query to a snapshot file, or load a snapshot file as the last
+/// result without a database connection
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("snapshot_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        let save = t!("snapshot_save", locale = locale).to_string();
+        let load = t!("snapshot_load", locale = locale).to_string();
+        t!("snapshot_argument", locale = locale, save = save, load = load).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let save = t!("snapshot_save", locale = locale).to_string();
+        let load = t!("snapshot_load", locale = locale).to_string();
+        split_examples(&t!("snapshot_example_arguments", locale = locale, save = save, load = load))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("snapshot_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let save = t!("snapshot_save", locale = options.locale).to_string();
+        let load = t!("snapshot_load", locale = options.locale).to_string();
+        vec![save, load]
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let locale = options.configuration.locale.clone();
+        let locale = locale.as_str();
+
+        if options.input.len() <= 2 {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        }
+
+        let save = t!("snapshot_save", locale = locale).to_string();
+        let load = t!("snapshot_load", locale = locale).to_string();
+        let action = options.input[1].as_str();
+        let file_name = options.input[2..].join(" ");
+
+        if action.eq_ignore_ascii_case(&save) {
+            let Some(mut last_result) = options.state.last_result.clone() else {
+                return write_no_result(&options.configuration.locale, options.output);
+            };
+            let mut writer = BufWriter::new(File::create(&file_name)?);
+            write_snapshot(&mut last_result, &mut writer).await?;
+            writer.flush()?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        if action.eq_ignore_ascii_case(&load) {
+            let reader = BufReader::new(File::open(&file_name)?);
+            let result = read_snapshot(reader)?;
+            let (columns, rows) = columns_and_rows(result).await?;
+            return write_result(options, start, columns, rows).await;
+        }
+
+        Err(InvalidOption {
+            command_name: self.name(locale),
+            option: action.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MemoryQueryResult, MockConnection, QueryResult, Value};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "snapshot");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<save|load> <file>");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Save the last result to a file, or load a saved result"
+        );
+    }
+
+    async fn execute(state: &mut ShellState, input: &[&str]) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration {
+                results_format: "csv".to_string(),
+                results_footer: false,
+                ..Default::default()
+            },
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: input.iter().map(ToString::to_string).collect(),
+            output: &mut output,
+        };
+        Command.execute(options).await?;
+        Ok(output.to_string().replace("\r\n", "\n"))
+    }
+
+    #[tokio::test]
+    async fn test_execute_save_and_load() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("users.snapshot");
+        let file = file.to_string_lossy().to_string();
+        let state = &mut ShellState {
+            last_result: Some(MemoryQueryResult::new(
+                vec!["id".to_string(), "name".to_string()],
+                vec![
+                    vec![Value::I32(1), Value::String("foo".to_string())],
+                    vec![Value::I32(2), Value::Null],
+                ],
+            )),
+            ..Default::default()
+        };
+
+        execute(state, &[".snapshot", "save", &file]).await?;
+
+        let state = &mut ShellState::default();
+        let output = execute(state, &[".snapshot", "load", &file]).await?;
+
+        assert_eq!(output, "\"id\",\"name\"\n1,\"foo\"\n2,\"\"\n");
+        let mut last_result = state.last_result.clone().expect("last result");
+        assert_eq!(
+            last_result.next().await,
+            Some(vec![Value::I32(1), Value::String("foo".to_string())])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_save_no_result() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("users.snapshot");
+        let state = &mut ShellState::default();
+
+        let output = execute(state, &[".snapshot", "save", &file.to_string_lossy()]).await?;
+
+        assert_eq!(output, "No query result is available\n");
+        assert!(!file.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_errors() {
+        let state = &mut ShellState::default();
+        assert!(execute(state, &[".snapshot"]).await.is_err());
+        assert!(execute(state, &[".snapshot", "load"]).await.is_err());
+        assert!(execute(state, &[".snapshot", "foo", "bar"]).await.is_err());
+        assert!(execute(state, &[".snapshot", "load", "does-not-exist.snapshot"])
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::commands::last::{column_index, columns_and_rows, write_no_result, write_result};
+use crate::commands::Error::{InvalidOption, MissingArguments};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::Value;
+use rust_i18n::t;
+use std::cmp::Ordering;
+use std::time::Instant;
+
+/// Sort the rows of the last result without re-running the query
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("sort_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("sort_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("sort_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("sort_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let locale = options.configuration.locale.as_str();
+        let Some(column) = options.input.get(1).cloned() else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        let descending = match options.input.get(2).map(|order| order.to_lowercase()) {
+            None => false,
+            Some(order) if order == "asc" => false,
+            Some(order) if order == "desc" => true,
+            Some(order) => {
+                return Err(InvalidOption {
+                    command_name: self.name(locale),
+                    option: order,
+                });
+            }
+        };
+        let Some(last_result) = options.state.last_result.clone() else {
+            return write_no_result(&options.configuration.locale, options.output);
+        };
+
+        let (columns, mut rows) = columns_and_rows(last_result).await?;
+        let Some(index) = column_index(&columns, &column) else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: column,
+            });
+        };
+        rows.sort_by(|left, right| {
+            let ordering = compare_values(left.get(index), right.get(index));
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        write_result(options, start, columns, rows).await
+    }
+}
+
+/// Compare two values; numeric values are compared numerically, other values are compared as
+/// text, and null values are ordered after all other values.
+fn compare_values(left: Option<&Value>, right: Option<&Value>) -> Ordering {
+    let left = left.filter(|value| !value.is_null());
+    let right = right.filter(|value| !value.is_null());
+    match (left, right) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(left), Some(right)) => match (left.to_f64(), right.to_f64()) {
+            (Some(left), Some(right)) => left.total_cmp(&right),
+            _ => left.to_string().cmp(&right.to_string()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MemoryQueryResult, MockConnection, QueryResult};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "sort");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<column> [asc|desc]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Sort the rows of the last query result");
+    }
+
+    #[test]
+    fn test_compare_values() {
+        let one = Value::I64(1);
+        let two = Value::F64(2.5);
+        let text = Value::String("a".to_string());
+        assert_eq!(compare_values(Some(&one), Some(&two)), Ordering::Less);
+        assert_eq!(compare_values(Some(&two), Some(&one)), Ordering::Greater);
+        assert_eq!(compare_values(Some(&one), Some(&text)), Ordering::Less);
+        assert_eq!(
+            compare_values(Some(&Value::Null), Some(&one)),
+            Ordering::Greater
+        );
+        assert_eq!(compare_values(None, Some(&Value::Null)), Ordering::Equal);
+    }
+
+    async fn test_execute(order: Option<&str>, expected: Vec<Value>) -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+        state.last_result = Some(MemoryQueryResult::new(
+            vec!["id".to_string()],
+            vec![
+                vec![Value::I64(2)],
+                vec![Value::Null],
+                vec![Value::I64(10)],
+                vec![Value::I64(1)],
+            ],
+        ));
+        let mut input = vec![".sort".to_string(), "ID".to_string()];
+        if let Some(order) = order {
+            input.push(order.to_string());
+        }
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input,
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let mut last_result = state.last_result.take().expect("last result");
+        let mut values = Vec::new();
+        while let Some(row) = last_result.next().await {
+            values.extend(row);
+        }
+        assert_eq!(values, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_ascending() -> anyhow::Result<()> {
+        let expected = vec![Value::I64(1), Value::I64(2), Value::I64(10), Value::Null];
+        test_execute(None, expected.clone()).await?;
+        test_execute(Some("asc"), expected).await
+    }
+
+    #[tokio::test]
+    async fn test_execute_descending() -> anyhow::Result<()> {
+        let expected = vec![Value::Null, Value::I64(10), Value::I64(2), Value::I64(1)];
+        test_execute(Some("DESC"), expected).await
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_order() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".sort".to_string(), "id".to_string(), "up".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use crate::commands::last::write_no_result;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::{MemoryQueryResult, Value};
+use rsql_formatters::Results;
+use rust_i18n::t;
+
+/// Store the result of the last query as a local table that can be queried with SQL, or list the
+/// stashed tables when no name is specified
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("stash_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("stash_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("stash_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("stash_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        if let Some(name) = options.input.get(1) {
+            let Some(last_result) = options.state.last_result.clone() else {
+                return write_no_result(&options.configuration.locale, options.output);
+            };
+            options
+                .state
+                .stash
+                .add(
+                    options.driver_manager,
+                    name,
+                    last_result,
+                    options.configuration.results_memory_limit,
+                )
+                .await?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let start = std::time::Instant::now();
+        let configuration = options.configuration;
+        let locale = &configuration.locale;
+        let table_label = t!("table", locale = locale).to_string();
+        let columns = vec![table_label];
+        let rows = options
+            .state
+            .stash
+            .tables()
+            .iter()
+            .map(|table| vec![Value::String(table.to_string())])
+            .collect();
+
+        let query_result = MemoryQueryResult::new(columns, rows);
+        let mut results = Results::Query(Box::new(query_result));
+        let formatter_options = &mut configuration.get_formatter_options();
+        let result_format = &configuration.results_format;
+        let formatter = options.formatter_manager.get(result_format).ok_or(
+            rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            },
+        )?;
+
+        formatter_options.elapsed = start.elapsed();
+        formatter
+            .format(formatter_options, &mut results, options.output)
+            .await?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::CommandManager;
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "stash");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[name]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Store the last result as a table to query with SQL, or list stashed tables"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_list() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".stash".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string().replace("\r\n", "\n"), "\"Table\"\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_result() -> anyhow::Result<()> {
+        let output = &mut Output::default();
+        let state = &mut ShellState::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input: vec![".stash".to_string(), "r1".to_string()],
+            output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string(), "No query result is available\n");
+        assert!(state.stash.tables().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to write rows as they are fetched; tables buffer the first rows to calculate the widths
+/// of the columns
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("stream_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("stream_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!("stream_example_arguments", locale = locale, off = off))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("stream_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let off = t!("off", locale = locale).to_string();
+
+        let Some(value) = options.input.get(1) else {
+            let rows = match options.configuration.results_stream_rows {
+                0 => off,
+                rows => rows.to_string(),
+            };
+            let stream_setting = t!("stream_setting", locale = locale, rows = rows).to_string();
+            writeln!(options.output, "{stream_setting}")?;
+            return Ok(LoopCondition::Continue);
+        };
+
+        let rows = if value.eq_ignore_ascii_case(&off) {
+            0
+        } else {
+            value.parse::<usize>().map_err(|_| InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: value.to_string(),
+            })?
+        };
+        options.configuration.results_stream_rows = rows;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::execute;
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "stream");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[rows|off]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Write rows as they are fetched, buffering the first rows to calculate the column widths \
+             of tables"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_stream_rows: 100,
+            ..Default::default()
+        };
+        let output = execute(&Command, configuration, &[".stream"]).await?;
+        assert_eq!(output, "Stream: 100\n");
+
+        configuration.results_stream_rows = 0;
+        let output = execute(&Command, configuration, &[".stream"]).await?;
+        assert_eq!(output, "Stream: off\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_rows() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(&Command, configuration, &[".stream", "100"]).await?;
+        assert_eq!(configuration.results_stream_rows, 100);
+
+        let _ = execute(&Command, configuration, &[".stream", "off"]).await?;
+        assert_eq!(configuration.results_stream_rows, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".stream", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
 use async_trait::async_trait;
 use rust_i18n::t;
 
@@ -16,6 +16,10 @@ impl ShellCommand for Command {
         t!("system_argument", locale = locale).to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("system_example_arguments", locale = locale))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("system_description", locale = locale).to_string()
     }
@@ -49,6 +53,7 @@ mod tests {
     use super::*;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -81,6 +86,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".system".to_string()],
             output: &mut Output::default(),
         };
@@ -100,6 +106,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".system".to_string(), "echo".to_string()],
             output: &mut output,
         };
@@ -121,6 +128,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".system".to_string(), "echo".to_string(), "foo".to_string()],
             output: &mut output,
         };
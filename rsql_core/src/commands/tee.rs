@@ -1,4 +1,6 @@
-use crate::commands::{CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
 use async_trait::async_trait;
 use rsql_formatters::writers::{ClipboardWriter, FanoutWriter, FileWriter, StdoutWriter};
 use rust_i18n::t;
@@ -26,10 +28,19 @@ impl ShellCommand for Command {
         .to_string()
     }
 
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let clipboard = t!("tee_clipboard", locale = locale).to_string();
+        split_examples(&t!("tee_example_arguments", locale = locale, clipboard = clipboard))
+    }
+
     fn description(&self, locale: &str) -> String {
         t!("tee_description", locale = locale).to_string()
     }
 
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        vec![t!("tee_clipboard", locale = options.locale).to_string()]
+    }
+
     async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
         let locale = options.configuration.locale.as_str();
         let clipboard = t!("tee_clipboard", locale = locale).to_string();
@@ -62,6 +73,7 @@ mod tests {
     use crate::commands::LoopCondition;
     use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -100,6 +112,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".tee".to_string()],
             output: &mut output,
         };
@@ -122,6 +135,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".tee".to_string(), "clipboard".to_string()],
             output: &mut output,
         };
@@ -147,6 +161,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".tee".to_string(), path.clone()],
             output: &mut output,
         };
@@ -0,0 +1,225 @@
+use crate::commands::Error::{InvalidOption, IoError};
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use async_trait::async_trait;
+use indoc::indoc;
+use rsql_formatters::Highlighter;
+use rust_i18n::t;
+
+/// The SQL used to preview the syntax highlighting of a theme
+const PREVIEW_SQL: &str = indoc! {r"
+    -- Top customers by order total
+    SELECT c.name, count(*) AS orders, sum(o.total) AS total
+    FROM customers c JOIN orders o ON o.customer_id = c.id
+    WHERE o.created_at >= '2024-01-01' AND o.status <> 'cancelled'
+    GROUP BY c.name
+    ORDER BY total DESC
+    LIMIT 10;
+"};
+
+/// Command to list, preview and set the syntax highlighting theme
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("theme_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        let list = t!("theme_list", locale = locale).to_string();
+        let preview = t!("theme_preview", locale = locale).to_string();
+        t!(
+            "theme_argument",
+            locale = locale,
+            list = list,
+            preview = preview
+        )
+        .to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let list = t!("theme_list", locale = locale).to_string();
+        let preview = t!("theme_preview", locale = locale).to_string();
+        split_examples(&t!(
+            "theme_example_arguments",
+            locale = locale,
+            list = list,
+            preview = preview,
+        ))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("theme_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let list = t!("theme_list", locale = options.locale).to_string();
+        let preview = t!("theme_preview", locale = options.locale).to_string();
+        let mut completions = vec![list, preview];
+        completions.extend(Highlighter::themes());
+        completions
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.clone();
+        let locale = locale.as_str();
+
+        if options.input.len() <= 1 {
+            let theme = configuration.theme.as_str();
+            let theme_setting = t!("theme_setting", locale = locale, theme = theme).to_string();
+            writeln!(options.output, "{theme_setting}")?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let list = t!("theme_list", locale = locale).to_string();
+        let preview = t!("theme_preview", locale = locale).to_string();
+        let argument = options.input[1..].join(" ");
+
+        if argument.eq_ignore_ascii_case(&list) {
+            for theme in Highlighter::themes() {
+                let marker = if theme == configuration.theme {
+                    '*'
+                } else {
+                    ' '
+                };
+                writeln!(options.output, "{marker} {theme}")?;
+            }
+            return Ok(LoopCondition::Continue);
+        }
+
+        if options.input[1].eq_ignore_ascii_case(&preview) {
+            let theme = if options.input.len() > 2 {
+                let name = options.input[2..].join(" ");
+                find_theme(&name).ok_or_else(|| InvalidOption {
+                    command_name: self.name(locale),
+                    option: name,
+                })?
+            } else {
+                configuration.theme.clone()
+            };
+            let mut formatter_options = configuration.get_formatter_options();
+            formatter_options.theme = theme;
+            let highlighter = Highlighter::new(&formatter_options, "sql");
+            for line in PREVIEW_SQL.lines() {
+                let line = highlighter.highlight(line)?;
+                writeln!(options.output, "{line}")?;
+            }
+            return Ok(LoopCondition::Continue);
+        }
+
+        let Some(theme) = find_theme(&argument) else {
+            return Err(InvalidOption {
+                command_name: self.name(locale),
+                option: argument,
+            });
+        };
+        configuration
+            .save_setting("shell.theme", &theme)
+            .map_err(IoError)?;
+        configuration.theme = theme;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Find the theme with the name, ignoring case
+fn find_theme(name: &str) -> Option<String> {
+    Highlighter::themes()
+        .into_iter()
+        .find(|theme| theme.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::execute;
+    use crate::configuration::Configuration;
+    use std::fs;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "theme");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[list|preview [theme]|theme]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "List, preview or set the syntax highlighting theme"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let output = execute(&Command, configuration, &[".theme"]).await?;
+        assert_eq!(output, "Theme: Solarized (dark)\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_list() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let output = execute(&Command, configuration, &[".theme", "list"]).await?;
+        assert!(output.contains("* Solarized (dark)\n"));
+        assert!(output.contains("  Solarized (light)\n"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_preview() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            color: false,
+            ..Default::default()
+        };
+        let output = execute(
+            &Command,
+            configuration,
+            &[".theme", "preview", "base16-ocean.dark"],
+        )
+        .await?;
+        assert_eq!(output, PREVIEW_SQL);
+        assert_eq!(configuration.theme, "Solarized (dark)");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_theme() -> anyhow::Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let configuration = &mut Configuration {
+            program_name: "test".to_string(),
+            config_dir: Some(config_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        execute(&Command, configuration, &[".theme", "solarized", "(LIGHT)"]).await?;
+        assert_eq!(configuration.theme, "Solarized (light)");
+        let contents = fs::read_to_string(config_dir.path().join("test.toml"))?;
+        assert!(contents.contains("theme = \"Solarized (light)\""));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_theme() {
+        let configuration = &mut Configuration::default();
+        assert!(execute(&Command, configuration, &[".theme", "foo"])
+            .await
+            .is_err());
+        assert!(
+            execute(&Command, configuration, &[".theme", "preview", "foo"])
+                .await
+                .is_err()
+        );
+    }
+}
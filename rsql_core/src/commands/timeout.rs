@@ -0,0 +1,122 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use crate::configuration::parse_timeout;
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to set the maximum time that statements may run; statements that exceed the timeout
+/// are cancelled
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("timeout_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("timeout_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!("timeout_example_arguments", locale = locale, off = off))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("timeout_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+
+        let Some(value) = options.input.get(1) else {
+            let timeout = options.configuration.results_timeout;
+            let timeout = if timeout.is_zero() {
+                t!("off", locale = locale).to_string()
+            } else {
+                format!("{timeout:?}")
+            };
+            let timeout_setting =
+                t!("timeout_setting", locale = locale, timeout = timeout).to_string();
+            writeln!(options.output, "{timeout_setting}")?;
+            return Ok(LoopCondition::Continue);
+        };
+
+        let off = t!("off", locale = locale).to_string();
+        let timeout = if value.eq_ignore_ascii_case(&off) {
+            Ok(std::time::Duration::ZERO)
+        } else {
+            parse_timeout(value)
+        };
+        options.configuration.results_timeout = timeout.map_err(|error| InvalidOption {
+            command_name: self.name(locale).to_string(),
+            option: error.to_string(),
+        })?;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::execute;
+    use crate::configuration::Configuration;
+    use std::time::Duration;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "timeout");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[duration|off]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Set the maximum time that statements may run before they are cancelled"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_timeout: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let output = execute(&Command, configuration, &[".timeout"]).await?;
+        assert_eq!(output, "Timeout: 30s\n");
+
+        configuration.results_timeout = Duration::ZERO;
+        let output = execute(&Command, configuration, &[".timeout"]).await?;
+        assert_eq!(output, "Timeout: off\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_timeout() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(&Command, configuration, &[".timeout", "500ms"]).await?;
+        assert_eq!(configuration.results_timeout, Duration::from_millis(500));
+
+        let _ = execute(&Command, configuration, &[".timeout", "off"]).await?;
+        assert_eq!(configuration.results_timeout, Duration::ZERO);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".timeout", "foo"]).await;
+        assert!(result.is_err());
+    }
+}
@@ -1,30 +1,97 @@
-use crate::commands::{CommandOptions, ToggleShellCommand};
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
 use async_trait::async_trait;
+use rust_i18n::t;
 
-/// Command to enable or disable query execution timer
+/// Command to enable or disable query execution timer; the verbose option also displays the time
+/// spent parsing, executing, fetching and formatting each statement.
 #[derive(Debug, Default)]
 pub struct Command;
 
 #[async_trait]
-impl ToggleShellCommand for Command {
-    fn get_value(&self, options: &CommandOptions<'_>) -> bool {
-        options.configuration.results_timer
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("timer_command", locale = locale).to_string()
     }
 
-    fn set_value(&self, options: &mut CommandOptions<'_>, value: bool) {
-        options.configuration.results_timer = value;
+    fn args(&self, locale: &str) -> String {
+        let on = t!("on", locale = locale).to_string();
+        let verbose = t!("timer_verbose", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        t!(
+            "timer_argument",
+            locale = locale,
+            on = on,
+            verbose = verbose,
+            off = off
+        )
+        .to_string()
     }
 
-    fn get_name(&self) -> &'static str {
-        "timer_command"
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let on = t!("on", locale = locale).to_string();
+        let verbose = t!("timer_verbose", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!(
+            "timer_example_arguments",
+            locale = locale,
+            on = on,
+            verbose = verbose,
+            off = off,
+        ))
     }
 
-    fn get_description(&self) -> &'static str {
-        "timer_description"
+    fn description(&self, locale: &str) -> String {
+        t!("timer_description", locale = locale).to_string()
     }
 
-    fn get_setting_str(&self) -> &'static str {
-        "timer_setting"
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let locale = options.locale;
+        let on = t!("on", locale = locale).to_string();
+        let verbose = t!("timer_verbose", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+        vec![on, verbose, off]
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let on = t!("on", locale = locale).to_string();
+        let verbose = t!("timer_verbose", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+
+        if options.input.len() <= 1 {
+            let setting = if options.configuration.results_timer_verbose {
+                verbose
+            } else if options.configuration.results_timer {
+                on
+            } else {
+                off
+            };
+            let timer_setting = t!("timer_setting", locale = locale, setting = setting).to_string();
+            writeln!(options.output, "{timer_setting}")?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let argument = options.input[1].to_lowercase();
+        let (timer, timer_verbose) = if argument == on {
+            (true, false)
+        } else if argument == verbose {
+            (true, true)
+        } else if argument == off {
+            (false, false)
+        } else {
+            return Err(InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: argument.to_string(),
+            });
+        };
+
+        options.configuration.results_timer = timer;
+        options.configuration.results_timer_verbose = timer_verbose;
+
+        Ok(LoopCondition::Continue)
     }
 }
 
@@ -32,8 +99,9 @@ impl ToggleShellCommand for Command {
 mod tests {
     use super::*;
     use crate::commands::LoopCondition;
-    use crate::commands::{CommandManager, CommandOptions, ShellCommand};
+    use crate::commands::{CommandManager, CommandOptions};
     use crate::configuration::Configuration;
+    use crate::shell::ShellState;
     use crate::writers::Output;
     use rsql_drivers::{DriverManager, MockConnection};
     use rsql_formatters::FormatterManager;
@@ -49,7 +117,7 @@ mod tests {
     #[test]
     fn test_args() {
         let args = Command.args("en");
-        assert_eq!(args, "on|off");
+        assert_eq!(args, "on|verbose|off");
     }
 
     #[test]
@@ -71,6 +139,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".timer".to_string()],
             output: &mut output,
         };
@@ -110,6 +179,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".timer".to_string(), "on".to_string()],
             output: &mut Output::default(),
         };
@@ -121,10 +191,64 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_no_args_verbose() -> anyhow::Result<()> {
+        let mut output = Output::default();
+        let configuration = &mut Configuration {
+            results_timer: true,
+            results_timer_verbose: true,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".timer".to_string()],
+            output: &mut output,
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(output.to_string(), "Timer: verbose\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_verbose() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_timer: false,
+            ..default::Default::default()
+        };
+        let options = CommandOptions {
+            configuration,
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".timer".to_string(), "verbose".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert!(configuration.results_timer);
+        assert!(configuration.results_timer_verbose);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_set_off() -> anyhow::Result<()> {
         let configuration = &mut Configuration {
             results_timer: true,
+            results_timer_verbose: true,
             ..default::Default::default()
         };
         let options = CommandOptions {
@@ -134,6 +258,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".timer".to_string(), "off".to_string()],
             output: &mut Output::default(),
         };
@@ -142,6 +267,7 @@ mod tests {
 
         assert_eq!(result, LoopCondition::Continue);
         assert!(!configuration.results_timer);
+        assert!(!configuration.results_timer_verbose);
         Ok(())
     }
 
@@ -154,6 +280,7 @@ mod tests {
             formatter_manager: &FormatterManager::default(),
             connection: &mut MockConnection::new(),
             history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
             input: vec![".timer".to_string(), "foo".to_string()],
             output: &mut Output::default(),
         };
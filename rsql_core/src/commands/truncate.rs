@@ -0,0 +1,121 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rust_i18n::t;
+
+/// Command to set the maximum width of values displayed in tables; longer values are truncated
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("truncate_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("truncate_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!(
+            "truncate_example_arguments",
+            locale = locale,
+            off = off
+        ))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("truncate_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let off = t!("off", locale = locale).to_string();
+
+        let Some(value) = options.input.get(1) else {
+            let width = match options.configuration.results_max_column_width {
+                0 => off,
+                width => width.to_string(),
+            };
+            let truncate_setting =
+                t!("truncate_setting", locale = locale, width = width).to_string();
+            writeln!(options.output, "{truncate_setting}")?;
+            return Ok(LoopCondition::Continue);
+        };
+
+        let width = if value.eq_ignore_ascii_case(&off) {
+            0
+        } else {
+            value.parse::<usize>().map_err(|_| InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: value.to_string(),
+            })?
+        };
+        options.configuration.results_max_column_width = width;
+
+        Ok(LoopCondition::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::execute;
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "truncate");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[width|off]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Set the maximum width of values displayed in tables"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_args() -> anyhow::Result<()> {
+        let configuration = &mut Configuration {
+            results_max_column_width: 40,
+            ..Default::default()
+        };
+        let output = execute(&Command, configuration, &[".truncate"]).await?;
+        assert_eq!(output, "Truncate: 40\n");
+
+        configuration.results_max_column_width = 0;
+        let output = execute(&Command, configuration, &[".truncate"]).await?;
+        assert_eq!(output, "Truncate: off\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_set_width() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(&Command, configuration, &[".truncate", "40"]).await?;
+        assert_eq!(configuration.results_max_column_width, 40);
+
+        let _ = execute(&Command, configuration, &[".truncate", "off"]).await?;
+        assert_eq!(configuration.results_max_column_width, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".truncate", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
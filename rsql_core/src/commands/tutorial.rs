@@ -0,0 +1,377 @@
+use crate::commands::Error::InvalidOption;
+use crate::commands::{
+    split_examples, CommandOptions, CompletionOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::configuration::Configuration;
+use crate::writers::Output;
+use async_trait::async_trait;
+use rust_i18n::t;
+use std::time::Duration;
+
+/// The URL of the in-memory `SQLite` database the shell is connected to when the tutorial is
+/// started; the tutorial never modifies the database the shell was connected to
+const TUTORIAL_URL: &str = "rusqlite://";
+
+/// The dataset loaded into the in-memory `SQLite` database when the tutorial is started
+const TUTORIAL_SQL: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/resources/tutorial.sql"
+));
+
+/// The locale keys for the tutorial steps; each step also has a `<key>_title` locale key
+const STEPS: [&str; 5] = [
+    "tutorial_connect",
+    "tutorial_query",
+    "tutorial_format",
+    "tutorial_export",
+    "tutorial_explore",
+];
+
+/// The query used throughout the tutorial
+const TUTORIAL_QUERY: &str = "SELECT * FROM users;";
+
+/// Command to walk new users through the basics of rsql
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("tutorial_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        let start = t!("tutorial_start", locale = locale).to_string();
+        let next = t!("tutorial_next", locale = locale).to_string();
+        let reset = t!("tutorial_reset", locale = locale).to_string();
+        t!(
+            "tutorial_argument",
+            locale = locale,
+            start = start,
+            next = next,
+            reset = reset
+        )
+        .to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let start = t!("tutorial_start", locale = locale).to_string();
+        let next = t!("tutorial_next", locale = locale).to_string();
+        split_examples(&t!(
+            "tutorial_example_arguments",
+            locale = locale,
+            start = start,
+            next = next,
+        ))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("tutorial_description", locale = locale).to_string()
+    }
+
+    fn completions(&self, options: &CompletionOptions<'_>) -> Vec<String> {
+        let locale = options.locale;
+        let start = t!("tutorial_start", locale = locale).to_string();
+        let next = t!("tutorial_next", locale = locale).to_string();
+        let reset = t!("tutorial_reset", locale = locale).to_string();
+        vec![start, next, reset]
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let configuration = options.configuration;
+        let locale = configuration.locale.as_str();
+        let output = options.output;
+
+        if options.input.len() <= 1 {
+            write_progress(configuration, options.state.tutorial_step, output)?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let start = t!("tutorial_start", locale = locale).to_string();
+        let next = t!("tutorial_next", locale = locale).to_string();
+        let reset = t!("tutorial_reset", locale = locale).to_string();
+        let argument = options.input[1].to_lowercase();
+
+        if argument == start {
+            let mut connection = options.driver_manager.connect(TUTORIAL_URL).await?;
+            for statement in TUTORIAL_SQL.split(';') {
+                let statement = statement.trim();
+                if !statement.is_empty() {
+                    connection.execute(statement).await?;
+                }
+            }
+            options.state.read_only = false;
+            options.state.timeout = Duration::ZERO;
+            options.state.timeout_enforced = false;
+            options.state.connection = Some(connection);
+            options.state.tutorial_step = 1;
+        } else if argument == next {
+            if (1..=STEPS.len()).contains(&options.state.tutorial_step) {
+                options.state.tutorial_step += 1;
+            }
+        } else if argument == reset {
+            options.state.tutorial_step = 0;
+        } else {
+            return Err(InvalidOption {
+                command_name: self.name(locale).to_string(),
+                option: argument,
+            });
+        }
+
+        write_progress(configuration, options.state.tutorial_step, output)?;
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Write the tutorial progress and the current step
+fn write_progress(configuration: &Configuration, step: usize, output: &mut Output) -> Result<()> {
+    let locale = configuration.locale.as_str();
+    let command_identifier = &configuration.command_identifier;
+    let tutorial_command = t!("tutorial_command", locale = locale).to_string();
+
+    if step == 0 {
+        let start = t!("tutorial_start", locale = locale).to_string();
+        let command = format!("{command_identifier}{tutorial_command} {start}");
+        let not_started =
+            t!("tutorial_not_started", locale = locale, command = command).to_string();
+        writeln!(output, "{not_started}")?;
+        return Ok(());
+    }
+
+    if step > STEPS.len() {
+        let completed = t!("tutorial_completed", locale = locale).to_string();
+        writeln!(output, "{completed}")?;
+        return Ok(());
+    }
+
+    for (index, key) in STEPS.iter().enumerate() {
+        let marker = match (index + 1).cmp(&step) {
+            std::cmp::Ordering::Less => "[x]",
+            std::cmp::Ordering::Equal => "[>]",
+            std::cmp::Ordering::Greater => "[ ]",
+        };
+        let title_key = format!("{key}_title");
+        let title = t!(title_key.as_str(), locale = locale).to_string();
+        writeln!(output, "{marker} {title}")?;
+    }
+    writeln!(output)?;
+
+    let key = STEPS[step - 1];
+    let title_key = format!("{key}_title");
+    let title = t!(title_key.as_str(), locale = locale).to_string();
+    let step_title = t!(
+        "tutorial_step",
+        locale = locale,
+        step = step,
+        steps = STEPS.len(),
+        title = title
+    )
+    .to_string();
+    let body = t!(key, locale = locale).to_string();
+    writeln!(output, "{step_title}")?;
+    writeln!(output, "{body}")?;
+    for command in step_commands(configuration, step) {
+        writeln!(output, "  {command}")?;
+    }
+    writeln!(output)?;
+
+    let next = t!("tutorial_next", locale = locale).to_string();
+    let command = format!("{command_identifier}{tutorial_command} {next}");
+    let next_step = t!("tutorial_next_step", locale = locale, command = command).to_string();
+    writeln!(output, "{next_step}")?;
+    Ok(())
+}
+
+/// Get the example commands for a tutorial step
+fn step_commands(configuration: &Configuration, step: usize) -> Vec<String> {
+    let locale = configuration.locale.as_str();
+    let command_identifier = &configuration.command_identifier;
+    let command = |key: &str, argument: &str| {
+        let name = t!(key, locale = locale).to_string();
+        if argument.is_empty() {
+            format!("{command_identifier}{name}")
+        } else {
+            format!("{command_identifier}{name} {argument}")
+        }
+    };
+
+    match step {
+        1 => vec![r#"rsql --url "rusqlite://""#.to_string()],
+        2 => vec![TUTORIAL_QUERY.to_string()],
+        3 => vec![
+            command("format_command", "json"),
+            TUTORIAL_QUERY.to_string(),
+        ],
+        4 => vec![
+            command("format_command", "csv"),
+            command("output_command", "users.csv"),
+            TUTORIAL_QUERY.to_string(),
+        ],
+        5 => vec![command("tables_command", ""), command("help_command", "")],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::LoopCondition;
+    use crate::commands::{CommandManager, CommandOptions};
+    use crate::configuration::Configuration;
+    use crate::shell::ShellState;
+    use crate::writers::Output;
+    use rsql_drivers::{DriverManager, MockConnection};
+    use rsql_formatters::FormatterManager;
+    use rustyline::history::DefaultHistory;
+    use std::default;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "tutorial");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "start|next|reset");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(description, "Interactive tutorial for new users");
+    }
+
+    #[test]
+    fn test_tutorial_sql() {
+        let statements = TUTORIAL_SQL
+            .split(';')
+            .filter(|statement| !statement.trim().is_empty())
+            .count();
+        assert!(statements > 0);
+    }
+
+    async fn execute(state: &mut ShellState, input: Vec<String>) -> Result<String> {
+        let mut output = Output::default();
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut MockConnection::new(),
+            history: &DefaultHistory::new(),
+            state,
+            input,
+            output: &mut output,
+        };
+        let result = Command.execute(options).await?;
+        assert_eq!(result, LoopCondition::Continue);
+        Ok(output.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_execute_not_started() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+
+        let output = execute(state, vec![".tutorial".to_string()]).await?;
+
+        assert_eq!(output, "Run .tutorial start to start the tutorial\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_start() -> anyhow::Result<()> {
+        let state = &mut ShellState {
+            read_only: true,
+            ..default::Default::default()
+        };
+
+        let input = vec![".tutorial".to_string(), "start".to_string()];
+        let output = execute(state, input).await?;
+
+        assert_eq!(state.tutorial_step, 1);
+        assert!(!state.read_only);
+        assert!(output.contains("[>] Connect to a database"));
+        assert!(output.contains("[ ] Run a query"));
+        assert!(output.contains("Step 1/5: Connect to a database"));
+        assert!(output.contains(r#"  rsql --url "rusqlite://""#));
+        assert!(output.contains("Run .tutorial next to continue"));
+
+        let mut connection = state.connection.take().expect("connection");
+        assert_eq!(connection.url(), TUTORIAL_URL);
+        let mut query_result = connection.query(TUTORIAL_QUERY).await?;
+        assert!(query_result.next().await.is_some());
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_next() -> anyhow::Result<()> {
+        let state = &mut ShellState {
+            tutorial_step: 3,
+            ..default::Default::default()
+        };
+
+        let input = vec![".tutorial".to_string(), "next".to_string()];
+        let output = execute(state, input).await?;
+
+        assert_eq!(state.tutorial_step, 4);
+        assert!(output.contains("[x] Change the output format"));
+        assert!(output.contains("[>] Export the results"));
+        assert!(output.contains("  .format csv\n  .output users.csv\n"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_next_completed() -> anyhow::Result<()> {
+        let state = &mut ShellState {
+            tutorial_step: 5,
+            ..default::Default::default()
+        };
+
+        let input = vec![".tutorial".to_string(), "next".to_string()];
+        let output = execute(state, input).await?;
+
+        assert_eq!(state.tutorial_step, 6);
+        assert_eq!(
+            output,
+            "Congratulations, you have completed the tutorial!\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_next_not_started() -> anyhow::Result<()> {
+        let state = &mut ShellState::default();
+
+        let input = vec![".tutorial".to_string(), "next".to_string()];
+        let output = execute(state, input).await?;
+
+        assert_eq!(state.tutorial_step, 0);
+        assert_eq!(output, "Run .tutorial start to start the tutorial\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_reset() -> anyhow::Result<()> {
+        let state = &mut ShellState {
+            tutorial_step: 2,
+            ..default::Default::default()
+        };
+
+        let input = vec![".tutorial".to_string(), "reset".to_string()];
+        execute(state, input).await?;
+
+        assert_eq!(state.tutorial_step, 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let state = &mut ShellState::default();
+
+        let input = vec![".tutorial".to_string(), "foo".to_string()];
+        assert!(execute(state, input).await.is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::commands::Error::{InvalidOption, IoError, MissingArguments};
+use crate::commands::{
+    split_examples, CommandManager, CommandOptions, LoopCondition, Result, ShellCommand,
+};
+use crate::configuration::Configuration;
+use crate::executors::parse_commands;
+use async_trait::async_trait;
+use rsql_drivers::{Connection, MemoryQueryResult, Value};
+use rsql_formatters::writers::Output;
+use rsql_formatters::{FormatterManager, Results};
+use rust_i18n::t;
+use sqlparser::parser::Parser;
+use std::fs;
+use std::time::Instant;
+
+/// Parse the statements of a SQL file with the dialect of the connection, without executing them,
+/// and display the type and any syntax error of each statement
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("validate_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("validate_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        split_examples(&t!("validate_example_arguments", locale = locale))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("validate_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let Some(file) = options.input.get(1) else {
+            return Err(MissingArguments {
+                command_name: self.name(locale),
+                arguments: self.args(locale),
+            });
+        };
+        let contents = fs::read_to_string(file).map_err(|error| InvalidOption {
+            command_name: self.name(locale),
+            option: error.to_string(),
+        })?;
+
+        validate(
+            options.configuration,
+            options.command_manager,
+            options.formatter_manager,
+            options.connection,
+            &contents,
+            options.output,
+        )
+        .await?;
+        Ok(LoopCondition::Continue)
+    }
+}
+
+/// Parse the statements of the contents without executing them and write the line, type and any
+/// error of each statement with the results format; returns the number of invalid statements.
+///
+/// # Errors
+///
+/// Returns an error if the contents cannot be split into statements or the result cannot be
+/// written.
+pub(crate) async fn validate(
+    configuration: &Configuration,
+    command_manager: &CommandManager,
+    formatter_manager: &FormatterManager,
+    connection: &dyn Connection,
+    contents: &str,
+    output: &mut Output,
+) -> Result<usize> {
+    let start = Instant::now();
+    let locale = configuration.locale.as_str();
+    let command_identifier = configuration.command_identifier.as_str();
+    let statements =
+        parse_commands(command_identifier, contents).map_err(|error| IoError(error.into()))?;
+
+    let mut offset = 0;
+    let mut errors = 0;
+    let mut rows = Vec::new();
+    for statement in statements.iter().filter(|statement| !statement.is_empty()) {
+        let position = contents[offset..]
+            .find(statement.as_str())
+            .map_or(offset, |position| offset + position);
+        let line = contents[..position].matches('\n').count() + 1;
+        offset = position + statement.len();
+
+        let (statement_type, error) =
+            if let Some(command) = statement.strip_prefix(command_identifier) {
+                let name = command.split_whitespace().next().unwrap_or_default();
+                let error = command_manager
+                    .get_starts_with(locale, name)
+                    .is_none()
+                    .then(|| {
+                        crate::executors::Error::InvalidCommand {
+                            command_name: name.to_string(),
+                        }
+                        .to_string()
+                    });
+                ("Command".to_string(), error)
+            } else {
+                let error = Parser::parse_sql(connection.dialect().as_ref(), statement)
+                    .err()
+                    .map(|error| error.to_string());
+                (format!("{:?}", connection.parse_sql(statement)), error)
+            };
+
+        if error.is_some() {
+            errors += 1;
+        }
+        rows.push(vec![
+            Value::U64(line as u64),
+            Value::String(statement_type),
+            error.map_or(Value::Null, Value::String),
+        ]);
+    }
+
+    let columns = vec![
+        t!("validate_line", locale = locale).to_string(),
+        t!("validate_type", locale = locale).to_string(),
+        t!("validate_error", locale = locale).to_string(),
+    ];
+    let query_result = MemoryQueryResult::new(columns, rows);
+    let mut results = Results::Query(Box::new(query_result));
+    let formatter_options = &mut configuration.get_formatter_options();
+    let result_format = &configuration.results_format;
+    let formatter =
+        formatter_manager
+            .get(result_format)
+            .ok_or(rsql_formatters::Error::UnknownFormat {
+                format: result_format.to_string(),
+            })?;
+
+    formatter_options.elapsed = start.elapsed();
+    formatter
+        .format(formatter_options, &mut results, output)
+        .await?;
+
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::ShellState;
+    use indoc::indoc;
+    use rsql_drivers::{DriverManager, MockConnection, StatementMetadata};
+    use rustyline::history::DefaultHistory;
+    use sqlparser::dialect::GenericDialect;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "validate");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "<file>");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Check the syntax of the statements in a SQL file without executing them"
+        );
+    }
+
+    fn connection() -> MockConnection {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_dialect()
+            .returning(|| Box::new(GenericDialect));
+        connection.expect_parse_sql().returning(|sql| {
+            if sql.starts_with("SELECT") {
+                StatementMetadata::Query
+            } else {
+                StatementMetadata::Unknown
+            }
+        });
+        connection
+    }
+
+    #[tokio::test]
+    async fn test_validate() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let contents = indoc! {r"
+            SELECT 1;
+
+            SELECT *
+            FROM;
+            .foobar
+            .timer on
+        "};
+        let output = &mut Output::default();
+
+        let errors = validate(
+            &configuration,
+            &CommandManager::default(),
+            &FormatterManager::default(),
+            &connection(),
+            contents,
+            output,
+        )
+        .await?;
+
+        assert_eq!(errors, 2);
+        let output = output.to_string().replace("\r\n", "\n");
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "\"line\",\"type\",\"error\"");
+        assert_eq!(lines[1], "1,\"Query\",\"\"");
+        assert!(lines[2].starts_with("3,\"Query\",\"sql parser error:"));
+        assert_eq!(lines[3], "5,\"Command\",\"Invalid command foobar\"");
+        assert_eq!(lines[4], "6,\"Command\",\"\"");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_missing_file() {
+        let options = CommandOptions {
+            configuration: &mut Configuration::default(),
+            command_manager: &CommandManager::default(),
+            driver_manager: &DriverManager::default(),
+            formatter_manager: &FormatterManager::default(),
+            connection: &mut connection(),
+            history: &DefaultHistory::new(),
+            state: &mut ShellState::default(),
+            input: vec![".validate".to_string(), "does-not-exist.sql".to_string()],
+            output: &mut Output::default(),
+        };
+
+        let result = Command.execute(options).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::commands::last::write_no_result;
+use crate::commands::Error::InvalidOption;
+use crate::commands::{split_examples, CommandOptions, LoopCondition, Result, ShellCommand};
+use async_trait::async_trait;
+use rsql_drivers::MemoryQueryResult;
+use rust_i18n::t;
+
+/// Command to browse the result of the last query in an interactive viewer, which scrolls the
+/// rows and columns, searches the values and exports the selected rows; the viewer can be opened
+/// automatically when the rows do not fit in the terminal
+#[derive(Debug, Default)]
+pub struct Command;
+
+#[async_trait]
+impl ShellCommand for Command {
+    fn name(&self, locale: &str) -> String {
+        t!("view_command", locale = locale).to_string()
+    }
+
+    fn args(&self, locale: &str) -> String {
+        t!("view_argument", locale = locale).to_string()
+    }
+
+    fn examples(&self, locale: &str) -> Vec<String> {
+        let off = t!("off", locale = locale).to_string();
+        split_examples(&t!("view_example_arguments", locale = locale, off = off))
+    }
+
+    fn description(&self, locale: &str) -> String {
+        t!("view_description", locale = locale).to_string()
+    }
+
+    async fn execute<'a>(&self, options: CommandOptions<'a>) -> Result<LoopCondition> {
+        let locale = options.configuration.locale.as_str();
+        let auto = t!("view_auto", locale = locale).to_string();
+        let off = t!("off", locale = locale).to_string();
+
+        if let Some(value) = options.input.get(1) {
+            options.configuration.results_viewer_auto = if value.eq_ignore_ascii_case(&auto) {
+                true
+            } else if value.eq_ignore_ascii_case(&off) {
+                false
+            } else {
+                return Err(InvalidOption {
+                    command_name: self.name(locale).to_string(),
+                    option: value.to_string(),
+                });
+            };
+            return Ok(LoopCondition::Continue);
+        }
+
+        let Some(last_result) = options.state.last_result.clone() else {
+            return write_no_result(&options.configuration.locale, options.output);
+        };
+        view(options, last_result).await
+    }
+}
+
+#[cfg(feature = "viewer")]
+async fn view(
+    options: CommandOptions<'_>,
+    last_result: MemoryQueryResult,
+) -> Result<LoopCondition> {
+    crate::viewer::view(
+        options.configuration,
+        options.formatter_manager,
+        last_result,
+        options.output,
+    )
+    .await?;
+    Ok(LoopCondition::Continue)
+}
+
+#[cfg(not(feature = "viewer"))]
+#[expect(clippy::unused_async)]
+async fn view(
+    options: CommandOptions<'_>,
+    _last_result: MemoryQueryResult,
+) -> Result<LoopCondition> {
+    let locale = options.configuration.locale.as_str();
+    let view_unavailable = t!("view_unavailable", locale = locale).to_string();
+    writeln!(options.output, "{view_unavailable}")?;
+    Ok(LoopCondition::Continue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::execute;
+    use crate::configuration::Configuration;
+
+    #[test]
+    fn test_name() {
+        let name = Command.name("en");
+        assert_eq!(name, "view");
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Command.args("en");
+        assert_eq!(args, "[auto|off]");
+    }
+
+    #[test]
+    fn test_description() {
+        let description = Command.description("en");
+        assert_eq!(
+            description,
+            "Browse the result of the last query in an interactive viewer"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_no_result() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let output = execute(&Command, configuration, &[".view"]).await?;
+        assert_eq!(output, "No query result is available\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_auto_and_off() -> anyhow::Result<()> {
+        let configuration = &mut Configuration::default();
+        let _ = execute(&Command, configuration, &[".view", "auto"]).await?;
+        assert!(configuration.results_viewer_auto);
+        let _ = execute(&Command, configuration, &[".view", "off"]).await?;
+        assert!(!configuration.results_viewer_auto);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_invalid_option() {
+        let configuration = &mut Configuration::default();
+        let result = execute(&Command, configuration, &[".view", "foo"]).await;
+        assert!(matches!(result, Err(InvalidOption { .. })));
+    }
+}
@@ -1,15 +1,23 @@
+use crate::locales::resolve_locale;
+use crate::shell::{default_key_bindings, parse_key, KeyAction, KeyBinding};
 use anyhow::{bail, Result};
 use config::{Config, FileFormat};
 use dirs::home_dir;
 use indicatif::ProgressStyle;
-use rsql_formatters::FormatterOptions;
+use rsql_drivers::{DateFormat, DriverManager, HelperProvider, ReconnectOptions};
+use rsql_formatters::{BytesFormat, ColorRule, FormatterOptions};
 use rustyline::EditMode;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::env;
 use std::fs::{create_dir_all, OpenOptions};
 use std::io::Write;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Mutex;
 use std::time::Duration;
+use std::{fs, io};
+use toml_edit::DocumentMut;
 use tracing::level_filters::LevelFilter;
 use tracing::{debug, warn};
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
@@ -76,6 +84,34 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the key used to authenticate with the `.ask` provider.
+    #[must_use]
+    pub fn with_ask_key<S: Into<String>>(mut self, ask_key: S) -> Self {
+        self.configuration.ask_key = Some(ask_key.into());
+        self
+    }
+
+    /// Set the model used by the `.ask` provider.
+    #[must_use]
+    pub fn with_ask_model<S: Into<String>>(mut self, ask_model: S) -> Self {
+        self.configuration.ask_model = ask_model.into();
+        self
+    }
+
+    /// Set the URL of the `OpenAI` compatible API used by the `.ask` command.
+    #[must_use]
+    pub fn with_ask_url<S: Into<String>>(mut self, ask_url: S) -> Self {
+        self.configuration.ask_url = ask_url.into();
+        self
+    }
+
+    /// Set the display of history based autosuggestions.
+    #[must_use]
+    pub fn with_autosuggestions(mut self, autosuggestions: bool) -> Self {
+        self.configuration.autosuggestions = autosuggestions;
+        self
+    }
+
     /// Set the bail on error to use.
     #[must_use]
     pub fn with_bail_on_error(mut self, bail_on_error: bool) -> Self {
@@ -97,6 +133,13 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the credential helper command used to resolve passwords.
+    #[must_use]
+    pub fn with_credential_helper<S: Into<String>>(mut self, credential_helper: S) -> Self {
+        self.configuration.credential_helper = Some(credential_helper.into());
+        self
+    }
+
     /// Set the echo value.
     #[must_use]
     pub fn with_echo(mut self, echo: EchoMode) -> Self {
@@ -139,6 +182,27 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the key bindings for the shell actions.
+    #[must_use]
+    pub fn with_key_bindings(mut self, key_bindings: Vec<KeyBinding>) -> Self {
+        self.configuration.key_bindings = key_bindings;
+        self
+    }
+
+    /// Set if extensions specified in connection URLs are loaded.
+    #[must_use]
+    pub fn with_load_extensions(mut self, load_extensions: bool) -> Self {
+        self.configuration.load_extensions = load_extensions;
+        self
+    }
+
+    /// Add a named connection profile.
+    #[must_use]
+    pub fn with_profile<S: Into<String>>(mut self, name: S, profile: Profile) -> Self {
+        self.configuration.profiles.insert(name.into(), profile);
+        self
+    }
+
     /// Set the history to use.
     #[must_use]
     pub fn with_history(mut self, history: bool) -> Self {
@@ -167,6 +231,63 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set if statements can span multiple lines until a statement terminator is entered.
+    #[must_use]
+    pub fn with_multiline(mut self, multiline: bool) -> Self {
+        self.configuration.multiline = multiline;
+        self
+    }
+
+    /// Set the number of attempts to reconnect when the connection to the database is lost.
+    #[must_use]
+    pub fn with_reconnect_attempts(mut self, reconnect_attempts: usize) -> Self {
+        self.configuration.reconnect_attempts = reconnect_attempts;
+        self
+    }
+
+    /// Set if session settings are replayed after reconnecting.
+    #[must_use]
+    pub fn with_reconnect_replay_session(mut self, reconnect_replay_session: bool) -> Self {
+        self.configuration.reconnect_replay_session = reconnect_replay_session;
+        self
+    }
+
+    /// Set if statements that modify the database are refused.
+    #[must_use]
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.configuration.read_only = read_only;
+        self
+    }
+
+    /// Set if a JSON document is written for each statement executed instead of the formatted
+    /// results.
+    #[must_use]
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.configuration.json = json;
+        self
+    }
+
+    /// Set the number of connections used to execute consecutive queries in parallel.
+    #[must_use]
+    pub fn with_parallel(mut self, parallel: usize) -> Self {
+        self.configuration.parallel = parallel;
+        self
+    }
+
+    /// Set the prompt template to use.
+    #[must_use]
+    pub fn with_prompt<S: Into<String>>(mut self, prompt: S) -> Self {
+        self.configuration.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Set the color of the prompt.
+    #[must_use]
+    pub fn with_prompt_color<S: Into<String>>(mut self, prompt_color: S) -> Self {
+        self.configuration.prompt_color = Some(prompt_color.into());
+        self
+    }
+
     /// Set the theme to use.
     #[must_use]
     pub fn with_theme<S: Into<String>>(mut self, theme: S) -> Self {
@@ -174,6 +295,20 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set whether query results are cached.
+    #[must_use]
+    pub fn with_results_cache(mut self, results_cache: bool) -> Self {
+        self.configuration.results_cache = results_cache;
+        self
+    }
+
+    /// Set the time to live of cached query results; zero keeps results until they are evicted.
+    #[must_use]
+    pub fn with_results_cache_ttl(mut self, results_cache_ttl: Duration) -> Self {
+        self.configuration.results_cache_ttl = results_cache_ttl;
+        self
+    }
+
     /// Set the display of rows changed.
     #[must_use]
     pub fn with_results_changes(mut self, results_changes: bool) -> Self {
@@ -181,6 +316,20 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the maximum width of the bars displayed by the chart format.
+    #[must_use]
+    pub fn with_results_chart_width(mut self, results_chart_width: usize) -> Self {
+        self.configuration.results_chart_width = results_chart_width;
+        self
+    }
+
+    /// Set the conditional formatting rules applied to tables when color is enabled.
+    #[must_use]
+    pub fn with_results_color_rules(mut self, results_color_rules: Vec<ColorRule>) -> Self {
+        self.configuration.results_color_rules = results_color_rules;
+        self
+    }
+
     /// Set the display of the results' footer.
     #[must_use]
     pub fn with_results_footer(mut self, results_footer: bool) -> Self {
@@ -216,6 +365,119 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the format used to display binary values.
+    #[must_use]
+    pub fn with_results_bytes_format(mut self, results_bytes_format: BytesFormat) -> Self {
+        self.configuration.results_bytes_format = results_bytes_format;
+        self
+    }
+
+    /// Set the strftime pattern used to display date values.
+    #[must_use]
+    pub fn with_results_date_format<S: Into<String>>(mut self, results_date_format: S) -> Self {
+        self.configuration.results_date_format = Some(results_date_format.into());
+        self
+    }
+
+    /// Set the strftime pattern used to display date time values.
+    #[must_use]
+    pub fn with_results_datetime_format<S: Into<String>>(
+        mut self,
+        results_datetime_format: S,
+    ) -> Self {
+        self.configuration.results_datetime_format = Some(results_datetime_format.into());
+        self
+    }
+
+    /// Set the number of digits after the decimal point for floating point and decimal values.
+    #[must_use]
+    pub fn with_results_decimal_places(mut self, results_decimal_places: usize) -> Self {
+        self.configuration.results_decimal_places = Some(results_decimal_places);
+        self
+    }
+
+    /// Set the display of results that are wider than the terminal in the expanded format.
+    #[must_use]
+    pub fn with_results_expanded_auto(mut self, results_expanded_auto: bool) -> Self {
+        self.configuration.results_expanded_auto = results_expanded_auto;
+        self
+    }
+
+    /// Set the maximum width of values in tables; zero does not limit the width.
+    #[must_use]
+    pub fn with_results_max_column_width(mut self, results_max_column_width: usize) -> Self {
+        self.configuration.results_max_column_width = results_max_column_width;
+        self
+    }
+
+    /// Set the estimated size, in bytes, of the rows of a result that are kept in memory; rows
+    /// beyond the limit are written to a temporary file and tables are written as the rows are
+    /// fetched.  Zero does not limit the memory used.
+    #[must_use]
+    pub fn with_results_memory_limit(mut self, results_memory_limit: usize) -> Self {
+        self.configuration.results_memory_limit = results_memory_limit;
+        self
+    }
+
+    /// Set the exponent at which floating point and decimal values are displayed in scientific
+    /// notation.
+    #[must_use]
+    pub fn with_results_scientific_notation(mut self, results_scientific_notation: u32) -> Self {
+        self.configuration.results_scientific_notation = Some(results_scientific_notation);
+        self
+    }
+
+    /// Set the number of rows buffered to calculate the column widths of tables before rows are
+    /// written as they are fetched; zero writes the rows when all the rows have been fetched.
+    #[must_use]
+    pub fn with_results_stream_rows(mut self, results_stream_rows: usize) -> Self {
+        self.configuration.results_stream_rows = results_stream_rows;
+        self
+    }
+
+    /// Set the display of the thousands separator in numeric values.
+    #[must_use]
+    pub fn with_results_thousands_separator(mut self, results_thousands_separator: bool) -> Self {
+        self.configuration.results_thousands_separator = results_thousands_separator;
+        self
+    }
+
+    /// Set the strftime pattern used to display time values.
+    #[must_use]
+    pub fn with_results_time_format<S: Into<String>>(mut self, results_time_format: S) -> Self {
+        self.configuration.results_time_format = Some(results_time_format.into());
+        self
+    }
+
+    /// Set the string displayed for null values in tables.
+    #[must_use]
+    pub fn with_results_null_display<S: Into<String>>(mut self, results_null_display: S) -> Self {
+        self.configuration.results_null_display = results_null_display.into();
+        self
+    }
+
+    /// Set the display of a progress indicator while statements run and results are fetched.
+    #[must_use]
+    pub fn with_results_progress(mut self, results_progress: bool) -> Self {
+        self.configuration.results_progress = results_progress;
+        self
+    }
+
+    /// Set the maximum number of rows of a result that are recorded for the commands that use the
+    /// last result; zero disables recording.
+    #[must_use]
+    pub fn with_results_record_rows(mut self, results_record_rows: usize) -> Self {
+        self.configuration.results_record_rows = results_record_rows;
+        self
+    }
+
+    /// Set the maximum time that statements may run; zero disables the timeout.
+    #[must_use]
+    pub fn with_results_timeout(mut self, results_timeout: Duration) -> Self {
+        self.configuration.results_timeout = results_timeout;
+        self
+    }
+
     /// Set the display of the results' timer.
     #[must_use]
     pub fn with_results_timer(mut self, results_timer: bool) -> Self {
@@ -223,6 +485,21 @@ impl ConfigurationBuilder {
         self
     }
 
+    /// Set the display of the time spent in each phase of executing a statement.
+    #[must_use]
+    pub fn with_results_timer_verbose(mut self, results_timer_verbose: bool) -> Self {
+        self.configuration.results_timer_verbose = results_timer_verbose;
+        self
+    }
+
+    /// Set the opening of the results viewer when the rows of query results do not fit in the
+    /// terminal.
+    #[must_use]
+    pub fn with_results_viewer_auto(mut self, results_viewer_auto: bool) -> Self {
+        self.configuration.results_viewer_auto = results_viewer_auto;
+        self
+    }
+
     #[must_use]
     pub fn with_smart_completions(mut self, smart_completions: bool) -> Self {
         self.configuration.smart_completions = smart_completions;
@@ -293,6 +570,112 @@ impl FromStr for EchoMode {
     }
 }
 
+/// A named connection defined in the `connections` table of the configuration file; settings
+/// that are not specified are inherited from the configuration.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct Profile {
+    /// The url of the database
+    pub url: String,
+    /// The results format to use for the connection
+    #[serde(default, rename = "format")]
+    pub results_format: Option<String>,
+    /// The results limit to use for the connection
+    #[serde(default, rename = "limit")]
+    pub results_limit: Option<usize>,
+    /// Indicates if statements that modify the database are refused for the connection
+    #[serde(default)]
+    pub read_only: Option<bool>,
+    /// The prompt template to use for the connection
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// The color of the prompt for the connection, e.g. `red` for production databases
+    #[serde(default)]
+    pub prompt_color: Option<String>,
+}
+
+impl Profile {
+    /// Apply the settings of the profile to the configuration.
+    pub fn apply(&self, configuration: &mut Configuration) {
+        if let Some(results_format) = &self.results_format {
+            configuration.results_format.clone_from(results_format);
+        }
+        if let Some(results_limit) = self.results_limit {
+            configuration.results_limit = results_limit;
+        }
+        if let Some(read_only) = self.read_only {
+            configuration.read_only = read_only;
+        }
+        if let Some(prompt) = &self.prompt {
+            configuration.prompt = Some(prompt.clone());
+        }
+        if let Some(prompt_color) = &self.prompt_color {
+            configuration.prompt_color = Some(prompt_color.clone());
+        }
+    }
+}
+
+/// The target that results are written to.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputTarget {
+    #[default]
+    Stdout,
+    Clipboard,
+    File(PathBuf),
+    /// Files that are split after the number of rows; `{n}` in the path is the file number
+    Chunked {
+        path: PathBuf,
+        rows: usize,
+    },
+    /// Rows of query results are inserted into the table on the connection
+    Table(String),
+}
+
+/// The columns of query results that are displayed; columns are matched by name, ignoring case.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnSelection {
+    #[default]
+    All,
+    /// Only the columns are displayed, in the order of the columns
+    Show(Vec<String>),
+    /// All the columns except the columns are displayed
+    Hide(Vec<String>),
+}
+
+impl ColumnSelection {
+    /// Get the indexes of the selected columns of the results; returns `None` when all the columns
+    /// are selected, or none of the columns are selected.
+    #[must_use]
+    pub fn indexes(&self, columns: &[String]) -> Option<Vec<usize>> {
+        let position = |name: &String| {
+            columns
+                .iter()
+                .position(|column| column == name)
+                .or_else(|| {
+                    columns
+                        .iter()
+                        .position(|column| column.eq_ignore_ascii_case(name))
+                })
+        };
+        let indexes: Vec<usize> = match self {
+            ColumnSelection::All => return None,
+            ColumnSelection::Show(names) => names.iter().filter_map(position).collect(),
+            ColumnSelection::Hide(names) => {
+                let hidden: Vec<usize> = names.iter().filter_map(position).collect();
+                (0..columns.len())
+                    .filter(|index| !hidden.contains(index))
+                    .collect()
+            }
+        };
+        if indexes.is_empty() || (indexes.len() == columns.len() && indexes.is_sorted()) {
+            None
+        } else {
+            Some(indexes)
+        }
+    }
+}
+
 /// The configuration for the application.
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[expect(clippy::struct_excessive_bools)]
@@ -300,9 +683,14 @@ pub struct Configuration {
     pub program_name: String,
     pub version: String,
     pub config_dir: Option<PathBuf>,
+    pub ask_key: Option<String>,
+    pub ask_model: String,
+    pub ask_url: String,
+    pub autosuggestions: bool,
     pub bail_on_error: bool,
     pub color: bool,
     pub command_identifier: String,
+    pub credential_helper: Option<String>,
     pub echo: EchoMode,
     pub log_level: LevelFilter,
     pub log_dir: Option<PathBuf>,
@@ -313,14 +701,48 @@ pub struct Configuration {
     pub history_file: Option<PathBuf>,
     pub history_limit: usize,
     pub history_ignore_dups: bool,
+    pub json: bool,
+    pub key_bindings: Vec<KeyBinding>,
+    pub load_extensions: bool,
+    pub multiline: bool,
+    pub output: OutputTarget,
+    pub parallel: usize,
+    pub profiles: BTreeMap<String, Profile>,
+    pub prompt: Option<String>,
+    pub prompt_color: Option<String>,
+    pub read_only: bool,
+    pub reconnect_attempts: usize,
+    pub reconnect_replay_session: bool,
     pub theme: String,
+    pub results_bytes_format: BytesFormat,
+    pub results_cache: bool,
+    pub results_cache_ttl: Duration,
     pub results_changes: bool,
+    pub results_chart_width: usize,
+    pub results_color_rules: Vec<ColorRule>,
+    pub results_columns: ColumnSelection,
+    pub results_date_format: Option<String>,
+    pub results_datetime_format: Option<String>,
+    pub results_decimal_places: Option<usize>,
+    pub results_expanded_auto: bool,
     pub results_footer: bool,
     pub results_format: String,
     pub results_header: bool,
     pub results_limit: usize,
+    pub results_max_column_width: usize,
+    pub results_memory_limit: usize,
+    pub results_null_display: String,
+    pub results_progress: bool,
+    pub results_record_rows: usize,
     pub results_rows: bool,
+    pub results_scientific_notation: Option<u32>,
+    pub results_stream_rows: usize,
+    pub results_thousands_separator: bool,
+    pub results_time_format: Option<String>,
+    pub results_timeout: Duration,
     pub results_timer: bool,
+    pub results_timer_verbose: bool,
+    pub results_viewer_auto: bool,
     pub smart_completions: bool,
 }
 
@@ -330,9 +752,14 @@ impl Default for Configuration {
             program_name: String::new(),
             version: String::new(),
             config_dir: None,
+            ask_key: None,
+            ask_model: "gpt-4o-mini".to_string(),
+            ask_url: "https://api.openai.com/v1".to_string(),
+            autosuggestions: true,
             bail_on_error: false,
             color: true,
             command_identifier: ".".to_string(),
+            credential_helper: None,
             echo: EchoMode::Off,
             log_level: LevelFilter::OFF,
             log_dir: None,
@@ -343,33 +770,142 @@ impl Default for Configuration {
             history_file: None,
             history_limit: 1000,
             history_ignore_dups: true,
+            json: false,
+            key_bindings: default_key_bindings(),
+            load_extensions: false,
+            multiline: true,
+            output: OutputTarget::Stdout,
+            parallel: 1,
+            profiles: BTreeMap::new(),
+            prompt: None,
+            prompt_color: None,
+            read_only: false,
+            reconnect_attempts: 5,
+            reconnect_replay_session: true,
             theme: "Solarized (dark)".to_string(),
+            results_bytes_format: BytesFormat::Base64,
+            results_cache: false,
+            results_cache_ttl: Duration::from_secs(300),
             results_changes: true,
+            results_chart_width: 40,
+            results_color_rules: Vec::new(),
+            results_columns: ColumnSelection::All,
+            results_date_format: None,
+            results_datetime_format: None,
+            results_decimal_places: None,
+            results_expanded_auto: false,
             results_footer: true,
             results_format: "psql".to_string(),
             results_header: true,
             results_limit: 100,
+            results_max_column_width: 0,
+            results_memory_limit: 0,
+            results_null_display: "NULL".to_string(),
+            results_progress: true,
+            results_record_rows: 10_000,
             results_rows: true,
+            results_scientific_notation: None,
+            results_stream_rows: 0,
+            results_thousands_separator: true,
+            results_time_format: None,
+            results_timeout: Duration::ZERO,
             results_timer: true,
+            results_timer_verbose: false,
+            results_viewer_auto: false,
             smart_completions: true,
         }
     }
 }
 
 impl Configuration {
+    /// Configure the credential helper, reconnect options and loading of extensions of the driver
+    /// manager
+    pub fn configure_driver_manager(&self, driver_manager: &mut DriverManager) {
+        if let Some(credential_helper) = &self.credential_helper {
+            let provider = HelperProvider::new(credential_helper);
+            driver_manager.add_credential_provider(Box::new(provider));
+        }
+        driver_manager.set_reconnect_options(ReconnectOptions {
+            attempts: self.reconnect_attempts,
+            replay_session: self.reconnect_replay_session,
+            ..Default::default()
+        });
+        driver_manager.set_load_extensions(self.load_extensions);
+    }
+
     #[must_use]
     pub fn get_formatter_options(&self) -> FormatterOptions {
         FormatterOptions {
+            bytes_format: self.results_bytes_format,
             changes: self.results_changes,
+            chart_width: self.results_chart_width,
             color: self.color,
+            color_rules: self.results_color_rules.clone(),
+            date_format: DateFormat {
+                date: self.results_date_format.clone(),
+                time: self.results_time_format.clone(),
+                datetime: self.results_datetime_format.clone(),
+            },
+            decimal_places: self.results_decimal_places,
             elapsed: Duration::default(),
+            expanded_width: self.expanded_width(),
             footer: self.results_footer,
             header: self.results_header,
             locale: self.locale.clone(),
+            max_column_width: self.results_max_column_width,
+            memory_limit: self.results_memory_limit,
+            null_display: self.results_null_display.clone(),
             rows: self.results_rows,
+            scientific_notation: self.results_scientific_notation,
+            statistics: None,
+            stream_rows: self.results_stream_rows,
             theme: self.theme.clone(),
+            thousands_separator: self.results_thousands_separator,
             timer: self.results_timer,
+            warnings: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Save the setting to the configuration file, e.g. `shell.theme`, so that the setting is used
+    /// by later sessions; the formatting and comments of the file are preserved.  The setting is
+    /// not saved when the configuration directory is not set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration file cannot be read, parsed or written.
+    pub fn save_setting(&self, key: &str, value: &str) -> Result<()> {
+        let Some(config_dir) = &self.config_dir else {
+            return Ok(());
+        };
+        let configuration_file = config_dir.join(format!("{}.toml", self.program_name));
+        let contents = match fs::read_to_string(&configuration_file) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error.into()),
+        };
+        let mut document = contents.parse::<DocumentMut>()?;
+
+        let mut item = document.as_item_mut();
+        for part in key.split('.') {
+            item = &mut item[part];
+        }
+        *item = toml_edit::value(value);
+
+        create_dir_all(config_dir)?;
+        fs::write(&configuration_file, document.to_string())?;
+        Ok(())
+    }
+
+    /// Get the width of the terminal when results that are too wide for the terminal are
+    /// displayed in the expanded format; results written to files or the clipboard are not
+    /// affected.
+    fn expanded_width(&self) -> Option<usize> {
+        if !self.results_expanded_auto || self.output != OutputTarget::Stdout {
+            return None;
         }
+        console::Term::stdout()
+            .size_checked()
+            .map(|(_rows, columns)| usize::from(columns))
     }
 }
 
@@ -420,10 +956,20 @@ impl ConfigFile {
         })
     }
 
+    #[expect(clippy::too_many_lines)]
     fn load_configuration(&self, configuration: &mut Configuration) -> Result<()> {
         let config = &self.config;
         let config_dir = &self.config_dir;
 
+        if let Ok(ask_key) = config.get::<String>("ask.key") {
+            configuration.ask_key = Some(ask_key);
+        }
+        if let Ok(ask_model) = config.get::<String>("ask.model") {
+            configuration.ask_model = ask_model;
+        }
+        if let Ok(ask_url) = config.get::<String>("ask.url") {
+            configuration.ask_url = ask_url;
+        }
         if let Ok(bail_on_error) = config.get::<bool>("global.bail_on_error") {
             configuration.bail_on_error = bail_on_error;
         }
@@ -459,6 +1005,9 @@ impl ConfigFile {
             mode => bail!("Invalid shell.edit_mode: {mode}"),
         };
 
+        if let Ok(autosuggestions) = config.get("shell.autosuggestions") {
+            configuration.autosuggestions = autosuggestions;
+        }
         if let Ok(history) = config.get("shell.history.enabled") {
             configuration.history = history;
         }
@@ -470,15 +1019,64 @@ impl ConfigFile {
         if let Ok(history_ignore_dups) = config.get("shell.history.ignore_dups") {
             configuration.history_ignore_dups = history_ignore_dups;
         }
+        if let Ok(credential_helper) = config.get("shell.credential_helper") {
+            configuration.credential_helper = Some(credential_helper);
+        }
+        if let Ok(keys) = config.get::<BTreeMap<String, String>>("shell.keys") {
+            configuration.key_bindings = key_bindings(&keys)?;
+        }
+        if let Ok(load_extensions) = config.get("shell.load_extensions") {
+            configuration.load_extensions = load_extensions;
+        }
+        if let Ok(multiline) = config.get("shell.multiline") {
+            configuration.multiline = multiline;
+        }
+        if let Ok(prompt) = config.get("shell.prompt") {
+            configuration.prompt = Some(prompt);
+        }
+        if let Ok(prompt_color) = config.get("shell.prompt_color") {
+            configuration.prompt_color = Some(prompt_color);
+        }
+        if let Ok(read_only) = config.get("shell.read_only") {
+            configuration.read_only = read_only;
+        }
+        if let Ok(reconnect_attempts) = config.get("shell.reconnect.attempts") {
+            configuration.reconnect_attempts = reconnect_attempts;
+        }
+        if let Ok(reconnect_replay_session) = config.get("shell.reconnect.replay_session") {
+            configuration.reconnect_replay_session = reconnect_replay_session;
+        }
         if let Ok(smart_completions) = config.get("shell.smart.completions") {
             configuration.smart_completions = smart_completions;
         }
 
         configuration.theme = theme(config)?;
+        configuration.profiles = profiles(config)?;
 
+        if let Ok(results_cache) = config.get::<bool>("results.cache") {
+            configuration.results_cache = results_cache;
+        }
+        if let Ok(results_cache_ttl) = config.get::<String>("results.cache_ttl") {
+            configuration.results_cache_ttl = parse_timeout(&results_cache_ttl)?;
+        }
         if let Ok(results_changes) = config.get::<bool>("results.changes") {
             configuration.results_changes = results_changes;
         }
+        if let Ok(results_chart_width) = config.get::<usize>("results.chart_width") {
+            configuration.results_chart_width = results_chart_width;
+        }
+        if let Ok(results_color_rules) = config.get::<Vec<String>>("results.color_rules") {
+            configuration.results_color_rules = results_color_rules
+                .iter()
+                .filter_map(|rule| match ColorRule::from_str(rule) {
+                    Ok(rule) => Some(rule),
+                    Err(error) => {
+                        warn!("{error}");
+                        None
+                    }
+                })
+                .collect();
+        }
         if let Ok(results_footer) = config.get::<bool>("results.footer") {
             configuration.results_footer = results_footer;
         }
@@ -494,9 +1092,64 @@ impl ConfigFile {
         if let Ok(results_rows) = config.get::<bool>("results.rows") {
             configuration.results_rows = results_rows;
         }
+        if let Ok(results_bytes_format) = config.get::<String>("results.bytes_format") {
+            configuration.results_bytes_format =
+                BytesFormat::from_str(results_bytes_format.as_str()).unwrap_or_default();
+        }
+        if let Ok(results_date_format) = config.get::<String>("results.date_format") {
+            configuration.results_date_format = Some(results_date_format);
+        }
+        if let Ok(results_time_format) = config.get::<String>("results.time_format") {
+            configuration.results_time_format = Some(results_time_format);
+        }
+        if let Ok(results_datetime_format) = config.get::<String>("results.datetime_format") {
+            configuration.results_datetime_format = Some(results_datetime_format);
+        }
+        if let Ok(results_decimal_places) = config.get::<usize>("results.decimal_places") {
+            configuration.results_decimal_places = Some(results_decimal_places);
+        }
+        if let Ok(results_scientific_notation) = config.get::<u32>("results.scientific_notation") {
+            configuration.results_scientific_notation = Some(results_scientific_notation);
+        }
+        if let Ok(results_thousands_separator) = config.get::<bool>("results.thousands_separator") {
+            configuration.results_thousands_separator = results_thousands_separator;
+        }
+        if let Ok(results_expanded_auto) = config.get::<bool>("results.expanded_auto") {
+            configuration.results_expanded_auto = results_expanded_auto;
+        }
+        if let Ok(results_max_column_width) = config.get::<usize>("results.max_column_width") {
+            configuration.results_max_column_width = results_max_column_width;
+        }
+        if let Ok(results_memory_limit) = config.get::<String>("results.memory_limit") {
+            configuration.results_memory_limit = parse_memory_limit(&results_memory_limit)?;
+        }
+        if let Ok(results_null_display) = config.get::<String>("results.null_display") {
+            configuration.results_null_display = results_null_display;
+        }
+        if let Ok(results_stream_rows) = config.get::<usize>("results.stream_rows") {
+            configuration.results_stream_rows = results_stream_rows;
+        }
+        if let Ok(results_progress) = config.get::<bool>("results.progress") {
+            configuration.results_progress = results_progress;
+        }
+        if let Ok(results_record_rows) = config.get::<usize>("results.record_rows") {
+            configuration.results_record_rows = results_record_rows;
+        }
+        if let Ok(results_timeout) = config.get::<String>("results.timeout") {
+            configuration.results_timeout = parse_timeout(&results_timeout)?;
+        }
         if let Ok(results_timer) = config.get::<bool>("results.timer") {
             configuration.results_timer = results_timer;
         }
+        if let Ok(results_timer) = config.get::<String>("results.timer") {
+            if results_timer == "verbose" {
+                configuration.results_timer = true;
+                configuration.results_timer_verbose = true;
+            }
+        }
+        if let Ok(results_viewer_auto) = config.get::<bool>("results.viewer_auto") {
+            configuration.results_viewer_auto = results_viewer_auto;
+        }
 
         Ok(())
     }
@@ -504,23 +1157,97 @@ impl ConfigFile {
 
 fn get_locale(config: &Config) -> String {
     let default_locale = sys_locale::get_locale().unwrap_or_else(|| String::from("en"));
-    let locale = config.get("global.locale").unwrap_or(default_locale);
-    let parts: Vec<&str> = locale
-        .split(|c: char| !c.is_alphanumeric())
-        .filter(|&s| !s.is_empty())
-        .collect();
-
-    for i in (0..parts.len()).rev() {
-        let locale = parts[0..=i].join("-");
-        if available_locales!().contains(&locale.as_str()) {
-            return locale;
-        }
+    let locale: String = config.get("global.locale").unwrap_or(default_locale);
+    if let Some(locale) = resolve_locale(&locale) {
+        return locale;
     }
 
     warn!("Invalid locale: {locale}; defaulting to \"en\"");
     "en".to_string()
 }
 
+/// Parse a timeout such as `30s`, `500ms`, `5m` or `1h`; a number without a unit is a number of
+/// seconds, and `0` or `off` disables the timeout.
+///
+/// # Errors
+///
+/// Returns an error if the timeout is not a valid duration.
+pub fn parse_timeout(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("off") {
+        return Ok(Duration::ZERO);
+    }
+    let index = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(index);
+    let Ok(number) = number.parse::<f64>() else {
+        bail!("Invalid timeout: {value}");
+    };
+    let seconds = match unit.trim() {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => bail!("Invalid timeout: {value}"),
+    };
+    Ok(Duration::try_from_secs_f64(seconds)?)
+}
+
+/// Parse a memory limit such as `512MB`, `64KB` or `2GB`; a number without a unit is a number of
+/// bytes, and `0` or `off` disables the limit.
+///
+/// # Errors
+///
+/// Returns an error if the memory limit is not a valid size.
+pub fn parse_memory_limit(value: &str) -> Result<usize> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("off") {
+        return Ok(0);
+    }
+    let index = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(index);
+    let Ok(number) = number.parse::<usize>() else {
+        bail!("Invalid memory limit: {value}");
+    };
+    let multiplier: usize = match unit.trim().to_uppercase().as_str() {
+        "" | "B" => 1,
+        "KB" => 1024,
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        _ => bail!("Invalid memory limit: {value}"),
+    };
+    let Some(bytes) = number.checked_mul(multiplier) else {
+        bail!("Invalid memory limit: {value}");
+    };
+    Ok(bytes)
+}
+
+/// Get the key bindings from the configured keys and actions; the configured keys replace the
+/// default bindings for the same keys, and the `none` action removes the binding of a key.
+fn key_bindings(keys: &BTreeMap<String, String>) -> Result<Vec<KeyBinding>> {
+    let mut key_bindings = default_key_bindings();
+    for (key, action) in keys {
+        let key = key.trim().to_lowercase();
+        parse_key(&key)?;
+        key_bindings.retain(|key_binding| key_binding.key != key);
+        if !action.eq_ignore_ascii_case("none") {
+            key_bindings.push(KeyBinding::new(key, KeyAction::from_str(action)?)?);
+        }
+    }
+    Ok(key_bindings)
+}
+
+fn profiles(config: &Config) -> Result<BTreeMap<String, Profile>> {
+    match config.get::<BTreeMap<String, Profile>>("connections") {
+        Ok(profiles) => Ok(profiles),
+        Err(config::ConfigError::NotFound(_)) => Ok(BTreeMap::new()),
+        Err(error) => bail!("Invalid connections: {error}"),
+    }
+}
+
 fn theme(config: &Config) -> Result<String> {
     if let Ok(theme) = config.get("shell.theme") {
         return Ok(theme);
@@ -544,14 +1271,21 @@ fn theme(config: &Config) -> Result<String> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use indoc::indoc;
 
     #[test]
+    #[expect(clippy::too_many_lines)]
     fn test_configuration_builder() {
         let program_name = "test";
         let version = "1.2.3";
+        let ask_key = "key";
+        let ask_model = "llama3";
+        let ask_url = "http://localhost:11434/v1";
+        let autosuggestions = false;
         let bail_on_error = true;
         let color = true;
         let command_identifier = "\\";
+        let credential_helper = "pass-helper";
         let echo = EchoMode::On;
         let log_level = LevelFilter::OFF;
         let log_dir = ".rsql/logs";
@@ -562,20 +1296,60 @@ mod test {
         let history_file = ".rsql/history.txt";
         let history_limit = 42;
         let history_ignore_dups = false;
+        let json = true;
+        let load_extensions = true;
+        let multiline = false;
+        let parallel = 4;
+        let profile = Profile {
+            url: "rusqlite://".to_string(),
+            ..Default::default()
+        };
+        let prompt = "{user}@{host}> ";
+        let prompt_color = "red";
+        let read_only = true;
+        let reconnect_attempts = 3;
+        let reconnect_replay_session = false;
         let theme = "Solarized (light)";
+        let results_cache = true;
+        let results_cache_ttl = Duration::from_secs(60);
         let results_changes = false;
+        let results_chart_width = 42;
+        let results_color_rules =
+            vec![ColorRule::from_str("status = 'FAILED' => red row").expect("color rule")];
+        let results_bytes_format = BytesFormat::Hex;
+        let results_date_format = "%d/%m/%Y";
+        let results_datetime_format = "%d/%m/%Y %H:%M";
+        let results_decimal_places = 2;
+        let results_expanded_auto = true;
+        let results_scientific_notation = 6;
+        let results_thousands_separator = false;
+        let results_time_format = "%H:%M";
         let results_footer = false;
         let results_format = "psql".to_string();
         let results_header = false;
         let results_limit = 42;
+        let results_max_column_width = 40;
+        let results_memory_limit = 1024;
+        let results_null_display = "∅";
+        let results_stream_rows = 50;
+        let results_progress = false;
+        let results_record_rows = 500;
         let results_rows = false;
+        let results_timeout = Duration::from_secs(30);
         let results_timer = false;
+        let results_timer_verbose = true;
+        let results_viewer_auto = true;
         let smart_completions = true;
 
         let configuration = ConfigurationBuilder::new(program_name, version)
+            .with_ask_key(ask_key)
+            .with_ask_model(ask_model)
+            .with_ask_url(ask_url)
+            .with_autosuggestions(autosuggestions)
             .with_bail_on_error(bail_on_error)
             .with_color(color)
             .with_command_identifier(command_identifier)
+            .with_credential_helper(credential_helper)
             .with_echo(echo.clone())
             .with_log_level(log_level)
             .with_log_dir(log_dir)
@@ -586,22 +1360,61 @@ mod test {
             .with_history_file(history_file)
             .with_history_limit(history_limit)
             .with_history_ignore_dups(history_ignore_dups)
+            .with_json(json)
+            .with_load_extensions(load_extensions)
+            .with_multiline(multiline)
+            .with_parallel(parallel)
+            .with_profile("local", profile.clone())
+            .with_prompt(prompt)
+            .with_prompt_color(prompt_color)
+            .with_read_only(read_only)
+            .with_reconnect_attempts(reconnect_attempts)
+            .with_reconnect_replay_session(reconnect_replay_session)
             .with_theme(theme)
+            .with_results_cache(results_cache)
+            .with_results_cache_ttl(results_cache_ttl)
             .with_results_changes(results_changes)
+            .with_results_chart_width(results_chart_width)
+            .with_results_color_rules(results_color_rules.clone())
+            .with_results_bytes_format(results_bytes_format)
+            .with_results_date_format(results_date_format)
+            .with_results_datetime_format(results_datetime_format)
+            .with_results_decimal_places(results_decimal_places)
+            .with_results_expanded_auto(results_expanded_auto)
+            .with_results_scientific_notation(results_scientific_notation)
+            .with_results_thousands_separator(results_thousands_separator)
+            .with_results_time_format(results_time_format)
             .with_results_footer(results_footer)
             .with_results_format(results_format.clone())
             .with_results_header(results_header)
             .with_results_limit(results_limit)
+            .with_results_max_column_width(results_max_column_width)
+            .with_results_memory_limit(results_memory_limit)
+            .with_results_null_display(results_null_display)
+            .with_results_stream_rows(results_stream_rows)
+            .with_results_progress(results_progress)
+            .with_results_record_rows(results_record_rows)
             .with_results_rows(results_rows)
+            .with_results_timeout(results_timeout)
             .with_results_timer(results_timer)
+            .with_results_timer_verbose(results_timer_verbose)
+            .with_results_viewer_auto(results_viewer_auto)
             .with_smart_completions(smart_completions)
             .build();
 
         assert_eq!(configuration.program_name, program_name);
         assert_eq!(configuration.version, version);
+        assert_eq!(configuration.ask_key, Some(ask_key.to_string()));
+        assert_eq!(configuration.ask_model, ask_model);
+        assert_eq!(configuration.ask_url, ask_url);
+        assert_eq!(configuration.autosuggestions, autosuggestions);
         assert_eq!(configuration.bail_on_error, bail_on_error);
         assert_eq!(configuration.color, color);
         assert_eq!(configuration.command_identifier, command_identifier);
+        assert_eq!(
+            configuration.credential_helper,
+            Some(credential_helper.to_string())
+        );
         assert_eq!(configuration.echo, echo);
         assert_eq!(configuration.log_level, log_level);
         assert_eq!(
@@ -621,14 +1434,69 @@ mod test {
         );
         assert_eq!(configuration.history_limit, history_limit);
         assert_eq!(configuration.history_ignore_dups, history_ignore_dups);
+        assert_eq!(configuration.json, json);
+        assert_eq!(configuration.load_extensions, load_extensions);
+        assert_eq!(configuration.multiline, multiline);
+        assert_eq!(configuration.parallel, parallel);
+        assert_eq!(configuration.profiles.get("local"), Some(&profile));
+        assert_eq!(configuration.prompt, Some(prompt.to_string()));
+        assert_eq!(configuration.prompt_color, Some(prompt_color.to_string()));
+        assert_eq!(configuration.read_only, read_only);
+        assert_eq!(configuration.reconnect_attempts, reconnect_attempts);
+        assert_eq!(
+            configuration.reconnect_replay_session,
+            reconnect_replay_session
+        );
         assert_eq!(configuration.theme, theme);
+        assert_eq!(configuration.results_cache, results_cache);
+        assert_eq!(configuration.results_cache_ttl, results_cache_ttl);
         assert_eq!(configuration.results_changes, results_changes);
+        assert_eq!(configuration.results_chart_width, results_chart_width);
+        assert_eq!(configuration.results_color_rules, results_color_rules);
+        assert_eq!(configuration.results_bytes_format, results_bytes_format);
+        assert_eq!(
+            configuration.results_date_format,
+            Some(results_date_format.to_string())
+        );
+        assert_eq!(
+            configuration.results_datetime_format,
+            Some(results_datetime_format.to_string())
+        );
+        assert_eq!(
+            configuration.results_decimal_places,
+            Some(results_decimal_places)
+        );
+        assert_eq!(configuration.results_expanded_auto, results_expanded_auto);
+        assert_eq!(
+            configuration.results_scientific_notation,
+            Some(results_scientific_notation)
+        );
+        assert_eq!(
+            configuration.results_thousands_separator,
+            results_thousands_separator
+        );
+        assert_eq!(
+            configuration.results_time_format,
+            Some(results_time_format.to_string())
+        );
         assert_eq!(configuration.results_footer, results_footer);
         assert_eq!(configuration.results_format, results_format);
         assert_eq!(configuration.results_header, results_header);
         assert_eq!(configuration.results_limit, results_limit);
+        assert_eq!(
+            configuration.results_max_column_width,
+            results_max_column_width
+        );
+        assert_eq!(configuration.results_memory_limit, results_memory_limit);
+        assert_eq!(configuration.results_null_display, results_null_display);
+        assert_eq!(configuration.results_stream_rows, results_stream_rows);
+        assert_eq!(configuration.results_progress, results_progress);
+        assert_eq!(configuration.results_record_rows, results_record_rows);
         assert_eq!(configuration.results_rows, results_rows);
+        assert_eq!(configuration.results_timeout, results_timeout);
         assert_eq!(configuration.results_timer, results_timer);
+        assert_eq!(configuration.results_timer_verbose, results_timer_verbose);
+        assert_eq!(configuration.results_viewer_auto, results_viewer_auto);
     }
 
     #[test]
@@ -637,9 +1505,11 @@ mod test {
         assert!(configuration.program_name.is_empty());
         assert!(configuration.version.is_empty());
         assert_eq!(configuration.config_dir, None);
+        assert!(configuration.autosuggestions);
         assert!(!configuration.bail_on_error);
         assert!(configuration.color);
         assert_eq!(configuration.command_identifier, ".");
+        assert_eq!(configuration.credential_helper, None);
         assert_eq!(configuration.log_level, LevelFilter::OFF);
         assert_eq!(configuration.log_dir, None);
         assert_eq!(configuration.log_rotation, Rotation::DAILY);
@@ -649,14 +1519,57 @@ mod test {
         assert_eq!(configuration.history_file, None);
         assert_eq!(configuration.history_limit, 1000);
         assert!(configuration.history_ignore_dups);
+        assert!(!configuration.json);
+        assert_eq!(configuration.key_bindings, default_key_bindings());
+        assert!(!configuration.load_extensions);
+        assert!(configuration.multiline);
+        assert_eq!(configuration.output, OutputTarget::Stdout);
+        assert_eq!(configuration.parallel, 1);
+        assert!(configuration.profiles.is_empty());
+        assert_eq!(configuration.prompt, None);
+        assert_eq!(configuration.prompt_color, None);
+        assert!(!configuration.read_only);
+        assert_eq!(configuration.reconnect_attempts, 5);
+        assert!(configuration.reconnect_replay_session);
         assert_eq!(configuration.theme, "Solarized (dark)");
+        assert!(!configuration.results_cache);
+        assert_eq!(configuration.results_cache_ttl, Duration::from_secs(300));
         assert!(configuration.results_changes);
+        assert_eq!(configuration.results_chart_width, 40);
+        assert!(configuration.results_color_rules.is_empty());
+        assert_eq!(configuration.results_columns, ColumnSelection::All);
+        assert_eq!(configuration.results_bytes_format, BytesFormat::Base64);
+        assert_eq!(configuration.results_date_format, None);
+        assert_eq!(configuration.results_datetime_format, None);
+        assert_eq!(configuration.results_decimal_places, None);
+        assert!(!configuration.results_expanded_auto);
         assert!(configuration.results_footer);
         assert_eq!(configuration.results_format, "psql".to_string());
         assert!(configuration.results_header);
         assert_eq!(configuration.results_limit, 100);
+        assert_eq!(configuration.results_max_column_width, 0);
+        assert_eq!(configuration.results_memory_limit, 0);
+        assert_eq!(configuration.results_null_display, "NULL");
+        assert_eq!(configuration.results_stream_rows, 0);
+        assert!(configuration.results_progress);
+        assert_eq!(configuration.results_record_rows, 10_000);
         assert!(configuration.results_rows);
+        assert_eq!(configuration.results_scientific_notation, None);
+        assert!(configuration.results_thousands_separator);
+        assert_eq!(configuration.results_time_format, None);
+        assert_eq!(configuration.results_timeout, Duration::ZERO);
         assert!(configuration.results_timer);
+        assert!(!configuration.results_timer_verbose);
+    }
+
+    #[test]
+    fn test_expanded_width() {
+        let mut configuration = Configuration::default();
+        assert_eq!(configuration.expanded_width(), None);
+
+        configuration.results_expanded_auto = true;
+        configuration.output = OutputTarget::File(PathBuf::from("results.txt"));
+        assert_eq!(configuration.expanded_width(), None);
     }
 
     #[test]
@@ -683,6 +1596,59 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_save_setting() -> Result<()> {
+        let config_dir = tempfile::tempdir()?;
+        let configuration = Configuration {
+            program_name: "test".to_string(),
+            config_dir: Some(config_dir.path().to_path_buf()),
+            ..Default::default()
+        };
+        let configuration_file = config_dir.path().join("test.toml");
+        fs::write(
+            &configuration_file,
+            "# comment\n[shell]\ntheme.light = \"Solarized (light)\"\nmultiline = true\n",
+        )?;
+
+        configuration.save_setting("shell.theme", "base16-ocean.dark")?;
+
+        let contents = fs::read_to_string(&configuration_file)?;
+        assert!(contents.starts_with("# comment\n[shell]\n"));
+        assert!(contents.contains("theme = \"base16-ocean.dark\""));
+        assert!(contents.contains("multiline = true"));
+        assert!(!contents.contains("Solarized (light)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_setting_no_config_dir() -> Result<()> {
+        Configuration::default().save_setting("shell.theme", "base16-ocean.dark")
+    }
+
+    #[test]
+    fn test_key_bindings() -> Result<()> {
+        let keys = BTreeMap::from([
+            ("Ctrl-E".to_string(), "execute".to_string()),
+            ("alt-x".to_string(), "none".to_string()),
+        ]);
+        let bindings = key_bindings(&keys)?;
+        assert_eq!(
+            bindings,
+            vec![
+                KeyBinding::new("alt-enter", KeyAction::Execute)?,
+                KeyBinding::new("ctrl-l", KeyAction::ClearScreen)?,
+                KeyBinding::new("alt-e", KeyAction::Edit)?,
+                KeyBinding::new("ctrl-e", KeyAction::Execute)?,
+            ]
+        );
+
+        let keys = BTreeMap::from([("ctrl-e".to_string(), "foo".to_string())]);
+        assert!(key_bindings(&keys).is_err());
+        let keys = BTreeMap::from([("foo".to_string(), "execute".to_string())]);
+        assert!(key_bindings(&keys).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_get_locale_default() -> Result<()> {
         let prefix = "LOCALE_DEFAULT_TEST";
@@ -694,4 +1660,114 @@ mod test {
         assert_eq!(locale, "en".to_string());
         Ok(())
     }
+
+    #[test]
+    fn test_profiles() -> Result<()> {
+        let config = Config::builder()
+            .add_source(config::File::from_str(
+                indoc! {r#"
+                    [connections.local]
+                    url = "rusqlite://"
+
+                    [connections.prod]
+                    url = "postgresql://user@localhost/db"
+                    format = "json"
+                    limit = 10
+                    read_only = true
+                    prompt_color = "red"
+                "#},
+                FileFormat::Toml,
+            ))
+            .build()?;
+        let profiles = profiles(&config)?;
+        assert_eq!(profiles.len(), 2);
+
+        let mut configuration = Configuration::default();
+        profiles
+            .get("local")
+            .expect("local")
+            .apply(&mut configuration);
+        assert_eq!(configuration, Configuration::default());
+
+        let prod = profiles.get("prod").expect("prod");
+        assert_eq!(prod.url, "postgresql://user@localhost/db");
+        prod.apply(&mut configuration);
+        assert_eq!(configuration.results_format, "json");
+        assert_eq!(configuration.results_limit, 10);
+        assert!(configuration.read_only);
+        assert_eq!(configuration.prompt_color, Some("red".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_profiles_not_configured() -> Result<()> {
+        let config = Config::builder().build()?;
+        assert!(profiles(&config)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_profiles_invalid() -> Result<()> {
+        let config = Config::builder()
+            .add_source(config::File::from_str(
+                "[connections.prod]\nformat = \"json\"\n",
+                FileFormat::Toml,
+            ))
+            .build()?;
+        assert!(profiles(&config).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_timeout() -> Result<()> {
+        assert_eq!(parse_timeout("30")?, Duration::from_secs(30));
+        assert_eq!(parse_timeout("30s")?, Duration::from_secs(30));
+        assert_eq!(parse_timeout("1.5s")?, Duration::from_millis(1500));
+        assert_eq!(parse_timeout("500ms")?, Duration::from_millis(500));
+        assert_eq!(parse_timeout("2m")?, Duration::from_secs(120));
+        assert_eq!(parse_timeout("1h")?, Duration::from_secs(3600));
+        assert_eq!(parse_timeout("0")?, Duration::ZERO);
+        assert_eq!(parse_timeout("off")?, Duration::ZERO);
+        assert!(parse_timeout("").is_err());
+        assert!(parse_timeout("10x").is_err());
+        assert!(parse_timeout("s").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_selection_indexes() {
+        let columns = vec!["id".to_string(), "name".to_string(), "email".to_string()];
+        let names = |names: &[&str]| -> Vec<String> {
+            names.iter().map(ToString::to_string).collect()
+        };
+        assert_eq!(ColumnSelection::All.indexes(&columns), None);
+        assert_eq!(
+            ColumnSelection::Show(names(&["EMAIL", "id"])).indexes(&columns),
+            Some(vec![2, 0])
+        );
+        assert_eq!(
+            ColumnSelection::Hide(names(&["name"])).indexes(&columns),
+            Some(vec![0, 2])
+        );
+        assert_eq!(ColumnSelection::Show(names(&["foo"])).indexes(&columns), None);
+        assert_eq!(ColumnSelection::Hide(names(&["foo"])).indexes(&columns), None);
+        assert_eq!(
+            ColumnSelection::Hide(names(&["id", "name", "email"])).indexes(&columns),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_memory_limit() -> Result<()> {
+        assert_eq!(parse_memory_limit("1024")?, 1024);
+        assert_eq!(parse_memory_limit("64KB")?, 64 * 1024);
+        assert_eq!(parse_memory_limit("512 MB")?, 512 * 1024 * 1024);
+        assert_eq!(parse_memory_limit("2gb")?, 2 * 1024 * 1024 * 1024);
+        assert_eq!(parse_memory_limit("0")?, 0);
+        assert_eq!(parse_memory_limit("off")?, 0);
+        assert!(parse_memory_limit("").is_err());
+        assert!(parse_memory_limit("10TB").is_err());
+        assert!(parse_memory_limit("MB").is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,338 @@
+use crate::demo::{cents, date, text, Table, SEED};
+use chrono::{Days, NaiveDate};
+use fake::faker::address::en::{CityName, CountryName};
+use fake::faker::company::en::CompanyName;
+use fake::faker::internet::en::SafeEmail;
+use fake::faker::lorem::en::Words;
+use fake::faker::name::en::{FirstName, LastName, Name};
+use fake::Fake;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const GENRES: [&str; 10] = [
+    "Rock",
+    "Jazz",
+    "Metal",
+    "Alternative & Punk",
+    "Blues",
+    "Latin",
+    "Reggae",
+    "Pop",
+    "Classical",
+    "Electronica/Dance",
+];
+const MEDIA_TYPES: [&str; 5] = [
+    "MPEG audio file",
+    "Protected AAC audio file",
+    "Protected MPEG-4 video file",
+    "Purchased AAC audio file",
+    "AAC audio file",
+];
+/// The employees with their title and the key of their manager
+const EMPLOYEES: [(&str, Option<u64>); 8] = [
+    ("General Manager", None),
+    ("Sales Manager", Some(1)),
+    ("Sales Support Agent", Some(2)),
+    ("Sales Support Agent", Some(2)),
+    ("Sales Support Agent", Some(2)),
+    ("IT Manager", Some(1)),
+    ("IT Staff", Some(6)),
+    ("IT Staff", Some(6)),
+];
+/// The keys of the employees that support customers
+const SUPPORT_REPRESENTATIVES: [u64; 3] = [3, 4, 5];
+/// The price of a track in hundredths
+const TRACK_PRICE: u64 = 99;
+
+/// Generate the tables of the Chinook digital media store schema
+#[expect(clippy::too_many_lines)]
+pub(super) fn tables(scale: usize) -> Vec<Table> {
+    let scale = scale as u64;
+    let rng = &mut StdRng::seed_from_u64(SEED);
+    let artists = 25 * scale;
+    let albums = 35 * scale;
+    let customers = 60 * scale;
+    let invoices = 400 * scale;
+
+    let genre = GENRES
+        .iter()
+        .enumerate()
+        .map(|(index, name)| vec![(index + 1).to_string(), text(name)])
+        .collect();
+    let media_type = MEDIA_TYPES
+        .iter()
+        .enumerate()
+        .map(|(index, name)| vec![(index + 1).to_string(), text(name)])
+        .collect();
+    let artist = (1..=artists)
+        .map(|key| {
+            let name: String = Name().fake_with_rng(rng);
+            vec![key.to_string(), text(&name)]
+        })
+        .collect();
+
+    let mut album = Vec::new();
+    let mut track = Vec::new();
+    for key in 1..=albums {
+        album.push(vec![
+            key.to_string(),
+            text(&title(rng)),
+            rng.gen_range(1..=artists).to_string(),
+        ]);
+        let composer: String = Name().fake_with_rng(rng);
+        for _ in 0..rng.gen_range(8..=12) {
+            let milliseconds: u64 = rng.gen_range(120_000..=480_000);
+            track.push(vec![
+                (track.len() + 1).to_string(),
+                text(&title(rng)),
+                key.to_string(),
+                rng.gen_range(1..=MEDIA_TYPES.len()).to_string(),
+                rng.gen_range(1..=GENRES.len()).to_string(),
+                text(&composer),
+                milliseconds.to_string(),
+                (milliseconds * 32).to_string(),
+                cents(TRACK_PRICE),
+            ]);
+        }
+    }
+    let tracks = track.len();
+
+    let employee = EMPLOYEES
+        .iter()
+        .enumerate()
+        .map(|(index, (title, manager))| {
+            let first_name: String = FirstName().fake_with_rng(rng);
+            let last_name: String = LastName().fake_with_rng(rng);
+            let birth_date = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap_or_default()
+                + Days::new(rng.gen_range(0..=15_000));
+            let hire_date = NaiveDate::from_ymd_opt(2002, 1, 1).unwrap_or_default()
+                + Days::new(rng.gen_range(0..=700));
+            vec![
+                (index + 1).to_string(),
+                text(&last_name),
+                text(&first_name),
+                text(title),
+                manager.map_or_else(|| "NULL".to_string(), |manager| manager.to_string()),
+                date(birth_date),
+                date(hire_date),
+                text(&format!(
+                    "{}.{}@chinookcorp.com",
+                    first_name.to_lowercase(),
+                    last_name.to_lowercase()
+                )),
+            ]
+        })
+        .collect();
+
+    let mut locations = Vec::new();
+    let customer = (1..=customers)
+        .map(|key| {
+            let first_name: String = FirstName().fake_with_rng(rng);
+            let last_name: String = LastName().fake_with_rng(rng);
+            let company = if rng.gen_bool(0.2) {
+                let company: String = CompanyName().fake_with_rng(rng);
+                text(&company)
+            } else {
+                "NULL".to_string()
+            };
+            let city: String = CityName().fake_with_rng(rng);
+            let country: String = CountryName().fake_with_rng(rng);
+            let email: String = SafeEmail().fake_with_rng(rng);
+            locations.push((city.clone(), country.clone()));
+            vec![
+                key.to_string(),
+                text(&first_name),
+                text(&last_name),
+                company,
+                text(&city),
+                text(&country),
+                text(&email),
+                SUPPORT_REPRESENTATIVES[rng.gen_range(0..SUPPORT_REPRESENTATIVES.len())]
+                    .to_string(),
+            ]
+        })
+        .collect();
+
+    let start = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap_or_default();
+    let mut invoice = Vec::new();
+    let mut invoice_line = Vec::new();
+    for key in 1..=invoices {
+        let customer = rng.gen_range(1..=customers);
+        let mut total = 0;
+        for _ in 0..rng.gen_range(1..=5) {
+            let quantity: u64 = if rng.gen_bool(0.9) { 1 } else { 2 };
+            total += quantity * TRACK_PRICE;
+            invoice_line.push(vec![
+                (invoice_line.len() + 1).to_string(),
+                key.to_string(),
+                rng.gen_range(1..=tracks).to_string(),
+                cents(TRACK_PRICE),
+                quantity.to_string(),
+            ]);
+        }
+        let (city, country) = &locations[usize::try_from(customer - 1).unwrap_or_default()];
+        invoice.push(vec![
+            key.to_string(),
+            customer.to_string(),
+            date(start + Days::new(rng.gen_range(0..=1_825))),
+            text(city),
+            text(country),
+            cents(total),
+        ]);
+    }
+
+    vec![
+        Table::new(
+            "genre",
+            &["genre_id INTEGER NOT NULL", "name VARCHAR(120) NOT NULL"],
+            &["genre_id"],
+            genre,
+        ),
+        Table::new(
+            "media_type",
+            &[
+                "media_type_id INTEGER NOT NULL",
+                "name VARCHAR(120) NOT NULL",
+            ],
+            &["media_type_id"],
+            media_type,
+        ),
+        Table::new(
+            "artist",
+            &["artist_id INTEGER NOT NULL", "name VARCHAR(120) NOT NULL"],
+            &["artist_id"],
+            artist,
+        ),
+        Table::new(
+            "album",
+            &[
+                "album_id INTEGER NOT NULL",
+                "title VARCHAR(160) NOT NULL",
+                "artist_id INTEGER NOT NULL",
+            ],
+            &["album_id"],
+            album,
+        ),
+        Table::new(
+            "track",
+            &[
+                "track_id INTEGER NOT NULL",
+                "name VARCHAR(200) NOT NULL",
+                "album_id INTEGER NOT NULL",
+                "media_type_id INTEGER NOT NULL",
+                "genre_id INTEGER NOT NULL",
+                "composer VARCHAR(220)",
+                "milliseconds INTEGER NOT NULL",
+                "bytes INTEGER NOT NULL",
+                "unit_price DECIMAL(10,2) NOT NULL",
+            ],
+            &["track_id"],
+            track,
+        ),
+        Table::new(
+            "employee",
+            &[
+                "employee_id INTEGER NOT NULL",
+                "last_name VARCHAR(20) NOT NULL",
+                "first_name VARCHAR(20) NOT NULL",
+                "title VARCHAR(30) NOT NULL",
+                "reports_to INTEGER",
+                "birth_date DATE NOT NULL",
+                "hire_date DATE NOT NULL",
+                "email VARCHAR(60) NOT NULL",
+            ],
+            &["employee_id"],
+            employee,
+        ),
+        Table::new(
+            "customer",
+            &[
+                "customer_id INTEGER NOT NULL",
+                "first_name VARCHAR(40) NOT NULL",
+                "last_name VARCHAR(40) NOT NULL",
+                "company VARCHAR(80)",
+                "city VARCHAR(40) NOT NULL",
+                "country VARCHAR(60) NOT NULL",
+                "email VARCHAR(60) NOT NULL",
+                "support_rep_id INTEGER NOT NULL",
+            ],
+            &["customer_id"],
+            customer,
+        ),
+        Table::new(
+            "invoice",
+            &[
+                "invoice_id INTEGER NOT NULL",
+                "customer_id INTEGER NOT NULL",
+                "invoice_date DATE NOT NULL",
+                "billing_city VARCHAR(40) NOT NULL",
+                "billing_country VARCHAR(60) NOT NULL",
+                "total DECIMAL(10,2) NOT NULL",
+            ],
+            &["invoice_id"],
+            invoice,
+        ),
+        Table::new(
+            "invoice_line",
+            &[
+                "invoice_line_id INTEGER NOT NULL",
+                "invoice_id INTEGER NOT NULL",
+                "track_id INTEGER NOT NULL",
+                "unit_price DECIMAL(10,2) NOT NULL",
+                "quantity INTEGER NOT NULL",
+            ],
+            &["invoice_line_id"],
+            invoice_line,
+        ),
+    ]
+}
+
+/// Generate a title of one to four capitalized words
+fn title(rng: &mut StdRng) -> String {
+    let words: Vec<String> = Words(1..5).fake_with_rng(rng);
+    words
+        .iter()
+        .map(|word| {
+            let mut characters = word.chars();
+            characters.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(characters).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables() {
+        let tables = tables(1);
+        let rows: Vec<(&str, usize)> = tables
+            .iter()
+            .map(|table| (table.name(), table.rows()))
+            .collect();
+        assert_eq!(
+            rows[..3],
+            [("genre", 10), ("media_type", 5), ("artist", 25)]
+        );
+        assert_eq!(rows[3], ("album", 35));
+        assert!(rows[4].1 >= 35 * 8);
+        assert_eq!(
+            rows[5..8],
+            [("employee", 8), ("customer", 60), ("invoice", 400)]
+        );
+        assert!(rows[8].1 >= 400);
+    }
+
+    #[test]
+    fn test_title() {
+        let rng = &mut StdRng::seed_from_u64(SEED);
+        let title = title(rng);
+        assert!(!title.is_empty());
+        assert!(title
+            .split(' ')
+            .all(|word| word.starts_with(char::is_uppercase)));
+    }
+}
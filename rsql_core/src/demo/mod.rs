@@ -0,0 +1,252 @@
+//! Demo datasets that can be created on a connection, so that there is data to query for examples
+//! and tutorials.  The data is generated with a fixed seed, so a dataset contains the same rows
+//! every time it is created, and the number of rows is multiplied by the scale of the dataset.
+
+mod chinook;
+mod northwind;
+mod tpch;
+
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The number of rows inserted with each statement
+const BATCH_SIZE: usize = 100;
+/// The seed of the random number generator used to generate the data
+const SEED: u64 = 42;
+
+/// A demo dataset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dataset {
+    /// Digital media store with artists, albums, tracks, customers and invoices
+    Chinook,
+    /// Trading company with products, suppliers, customers, employees and orders
+    Northwind,
+    /// Wholesale supplier with the schema of the TPC-H decision support benchmark
+    Tpch,
+}
+
+impl Dataset {
+    /// All the demo datasets
+    pub const ALL: [Dataset; 3] = [Dataset::Chinook, Dataset::Northwind, Dataset::Tpch];
+
+    /// Get the name of the dataset
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dataset::Chinook => "chinook",
+            Dataset::Northwind => "northwind",
+            Dataset::Tpch => "tpch",
+        }
+    }
+
+    /// Generate the tables of the dataset; the number of rows of the tables that are not fixed
+    /// lookup tables is multiplied by the scale
+    #[must_use]
+    pub fn tables(&self, scale: usize) -> Vec<Table> {
+        let scale = scale.max(1);
+        match self {
+            Dataset::Chinook => chinook::tables(scale),
+            Dataset::Northwind => northwind::tables(scale),
+            Dataset::Tpch => tpch::tables(scale),
+        }
+    }
+}
+
+impl Display for Dataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Dataset {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Dataset::ALL
+            .into_iter()
+            .find(|dataset| dataset.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| name.to_string())
+    }
+}
+
+/// A table of a demo dataset
+#[derive(Clone, Debug)]
+pub struct Table {
+    name: &'static str,
+    columns: &'static [&'static str],
+    primary_key: &'static [&'static str],
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create a table; `columns` are the column definitions in the order of the values of the rows
+    /// and each row contains the SQL literals of the values
+    pub(crate) fn new(
+        name: &'static str,
+        columns: &'static [&'static str],
+        primary_key: &'static [&'static str],
+        rows: Vec<Vec<String>>,
+    ) -> Self {
+        Self {
+            name,
+            columns,
+            primary_key,
+            rows,
+        }
+    }
+
+    /// Get the name of the table
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// Get the number of rows of the table
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Get the statement that creates the table
+    #[must_use]
+    pub fn create_statement(&self) -> String {
+        let mut definitions: Vec<String> = self.columns.iter().map(ToString::to_string).collect();
+        if !self.primary_key.is_empty() {
+            definitions.push(format!("PRIMARY KEY ({})", self.primary_key.join(", ")));
+        }
+        format!("CREATE TABLE {} ({})", self.name, definitions.join(", "))
+    }
+
+    /// Get the statements that insert the rows of the table, in batches of rows
+    #[must_use]
+    pub fn insert_statements(&self) -> Vec<String> {
+        self.rows
+            .chunks(BATCH_SIZE)
+            .map(|rows| {
+                let values = rows
+                    .iter()
+                    .map(|row| format!("({})", row.join(", ")))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("INSERT INTO {} VALUES {values}", self.name)
+            })
+            .collect()
+    }
+}
+
+/// Get the SQL literal for a text value
+pub(crate) fn text(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Get the SQL literal for a date value
+pub(crate) fn date(value: NaiveDate) -> String {
+    format!("'{value}'")
+}
+
+/// Get the SQL literal for a decimal value with two decimal places from the value in hundredths,
+/// e.g. `1.50` for `150`
+pub(crate) fn cents(value: u64) -> String {
+    format!("{}.{:02}", value / 100, value % 100)
+}
+
+/// Choose a random value
+pub(crate) fn choose<'a>(rng: &mut StdRng, values: &[&'a str]) -> &'a str {
+    values.choose(rng).copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_from_str() {
+        assert_eq!(Dataset::from_str("tpch"), Ok(Dataset::Tpch));
+        assert_eq!(Dataset::from_str("Chinook"), Ok(Dataset::Chinook));
+        assert_eq!(Dataset::from_str("NORTHWIND"), Ok(Dataset::Northwind));
+        assert_eq!(Dataset::from_str("foo"), Err("foo".to_string()));
+        assert_eq!(Dataset::Tpch.to_string(), "tpch");
+    }
+
+    #[test]
+    fn test_table_statements() {
+        let rows = (0..150)
+            .map(|id| vec![id.to_string(), text("O'Brien")])
+            .collect();
+        let table = Table::new(
+            "users",
+            &["id INTEGER NOT NULL", "name VARCHAR(20) NOT NULL"],
+            &["id"],
+            rows,
+        );
+
+        assert_eq!(table.name(), "users");
+        assert_eq!(table.rows(), 150);
+        assert_eq!(
+            table.create_statement(),
+            "CREATE TABLE users (id INTEGER NOT NULL, name VARCHAR(20) NOT NULL, PRIMARY KEY (id))"
+        );
+        let statements = table.insert_statements();
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].starts_with("INSERT INTO users VALUES (0, 'O''Brien'), (1, "));
+        assert_eq!(statements[0].matches("), (").count(), 99);
+        assert_eq!(statements[1].matches("), (").count(), 49);
+    }
+
+    #[test]
+    fn test_literals() {
+        assert_eq!(text("it's"), "'it''s'");
+        assert_eq!(
+            date(NaiveDate::from_ymd_opt(2024, 1, 2).expect("valid date")),
+            "'2024-01-02'"
+        );
+        assert_eq!(cents(150), "1.50");
+        assert_eq!(cents(5), "0.05");
+    }
+
+    #[test]
+    fn test_datasets() {
+        for dataset in Dataset::ALL {
+            let tables = dataset.tables(1);
+            assert!(!tables.is_empty());
+            for table in &tables {
+                let columns = table.columns.len();
+                assert!(table.rows() > 0, "{dataset} {}", table.name());
+                assert!(
+                    table.rows.iter().all(|row| row.len() == columns),
+                    "{dataset} {}",
+                    table.name()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_datasets_are_reproducible() {
+        for dataset in Dataset::ALL {
+            let first: Vec<Vec<String>> = dataset
+                .tables(1)
+                .iter()
+                .map(Table::insert_statements)
+                .collect();
+            let second: Vec<Vec<String>> = dataset
+                .tables(1)
+                .iter()
+                .map(Table::insert_statements)
+                .collect();
+            assert_eq!(first, second, "{dataset}");
+        }
+    }
+
+    #[test]
+    fn test_datasets_scale() {
+        for dataset in Dataset::ALL {
+            let rows = |scale| -> usize { dataset.tables(scale).iter().map(Table::rows).sum() };
+            assert!(rows(2) > rows(1), "{dataset}");
+        }
+    }
+}
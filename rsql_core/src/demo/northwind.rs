@@ -0,0 +1,356 @@
+use crate::demo::{cents, choose, date, text, Table, SEED};
+use chrono::{Days, NaiveDate};
+use fake::faker::address::en::{CityName, CountryName, StreetName};
+use fake::faker::company::en::CompanyName;
+use fake::faker::lorem::en::Word;
+use fake::faker::name::en::{FirstName, LastName, Name};
+use fake::faker::phone_number::en::PhoneNumber;
+use fake::Fake;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// The categories of products with their description
+const CATEGORIES: [(&str, &str); 8] = [
+    ("Beverages", "Soft drinks, coffees, teas, beers, and ales"),
+    (
+        "Condiments",
+        "Sweet and savory sauces, relishes, spreads, and seasonings",
+    ),
+    ("Confections", "Desserts, candies, and sweet breads"),
+    ("Dairy Products", "Cheeses"),
+    ("Grains/Cereals", "Breads, crackers, pasta, and cereal"),
+    ("Meat/Poultry", "Prepared meats"),
+    ("Produce", "Dried fruit and bean curd"),
+    ("Seafood", "Seaweed and fish"),
+];
+const SHIPPERS: [&str; 3] = ["Speedy Express", "United Package", "Federal Shipping"];
+/// The employees with their title and the key of their manager
+const EMPLOYEES: [(&str, Option<u64>); 9] = [
+    ("Sales Representative", Some(2)),
+    ("Vice President, Sales", None),
+    ("Sales Representative", Some(2)),
+    ("Sales Representative", Some(2)),
+    ("Sales Manager", Some(2)),
+    ("Sales Representative", Some(5)),
+    ("Sales Representative", Some(5)),
+    ("Inside Sales Coordinator", Some(2)),
+    ("Sales Representative", Some(5)),
+];
+const CONTACT_TITLES: [&str; 5] = [
+    "Owner",
+    "Sales Representative",
+    "Marketing Manager",
+    "Order Administrator",
+    "Accounting Manager",
+];
+const UNITS: [&str; 5] = [
+    "10 boxes x 20 bags",
+    "24 - 12 oz bottles",
+    "12 - 550 ml bottles",
+    "48 - 6 oz jars",
+    "36 boxes",
+];
+
+/// Generate the tables of the Northwind trading company schema
+#[expect(clippy::too_many_lines)]
+pub(super) fn tables(scale: usize) -> Vec<Table> {
+    let scale = scale as u64;
+    let rng = &mut StdRng::seed_from_u64(SEED);
+    let suppliers = 10 * scale;
+    let products = 77 * scale;
+    let customers = 90 * scale;
+    let orders = 830 * scale;
+
+    let categories = CATEGORIES
+        .iter()
+        .enumerate()
+        .map(|(index, (name, description))| {
+            vec![(index + 1).to_string(), text(name), text(description)]
+        })
+        .collect();
+    let suppliers_rows = (1..=suppliers)
+        .map(|key| {
+            let mut row = vec![key.to_string()];
+            row.extend(company(rng));
+            row
+        })
+        .collect();
+
+    let mut prices = Vec::new();
+    let products_rows = (1..=products)
+        .map(|key| {
+            let word: String = Word().fake_with_rng(rng);
+            let price = rng.gen_range(250..=26_350);
+            prices.push(price);
+            vec![
+                key.to_string(),
+                text(&format!("{}{} {key}", word[..1].to_uppercase(), &word[1..])),
+                rng.gen_range(1..=suppliers).to_string(),
+                rng.gen_range(1..=CATEGORIES.len()).to_string(),
+                text(choose(rng, &UNITS)),
+                cents(price),
+                rng.gen_range(0..=125).to_string(),
+                i32::from(rng.gen_bool(0.1)).to_string(),
+            ]
+        })
+        .collect();
+
+    let customer_ids: Vec<String> = (1..=customers).map(|key| format!("C{key:04}")).collect();
+    let customers_rows = customer_ids
+        .iter()
+        .map(|id| {
+            let mut row = vec![text(id)];
+            row.extend(company(rng));
+            row
+        })
+        .collect();
+
+    let employees = EMPLOYEES
+        .iter()
+        .enumerate()
+        .map(|(index, (title, manager))| {
+            let first_name: String = FirstName().fake_with_rng(rng);
+            let last_name: String = LastName().fake_with_rng(rng);
+            let birth_date = NaiveDate::from_ymd_opt(1950, 1, 1).unwrap_or_default()
+                + Days::new(rng.gen_range(0..=12_000));
+            let hire_date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap_or_default()
+                + Days::new(rng.gen_range(0..=700));
+            let city: String = CityName().fake_with_rng(rng);
+            vec![
+                (index + 1).to_string(),
+                text(&last_name),
+                text(&first_name),
+                text(title),
+                date(birth_date),
+                date(hire_date),
+                text(&city),
+                manager.map_or_else(|| "NULL".to_string(), |manager| manager.to_string()),
+            ]
+        })
+        .collect();
+    let shippers = SHIPPERS
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let phone: String = PhoneNumber().fake_with_rng(rng);
+            vec![(index + 1).to_string(), text(name), text(&phone)]
+        })
+        .collect();
+
+    let start = NaiveDate::from_ymd_opt(2022, 7, 4).unwrap_or_default();
+    let mut orders_rows = Vec::new();
+    let mut order_details = Vec::new();
+    for key in 1..=orders {
+        let order_date = start + Days::new(rng.gen_range(0..=670));
+        let shipped_date = if rng.gen_bool(0.95) {
+            date(order_date + Days::new(rng.gen_range(1..=35)))
+        } else {
+            "NULL".to_string()
+        };
+        let city: String = CityName().fake_with_rng(rng);
+        let country: String = CountryName().fake_with_rng(rng);
+        orders_rows.push(vec![
+            key.to_string(),
+            text(&customer_ids[rng.gen_range(0..customer_ids.len())]),
+            rng.gen_range(1..=EMPLOYEES.len()).to_string(),
+            date(order_date),
+            date(order_date + Days::new(28)),
+            shipped_date,
+            rng.gen_range(1..=SHIPPERS.len()).to_string(),
+            cents(rng.gen_range(2..=100_000)),
+            text(&city),
+            text(&country),
+        ]);
+
+        let mut detail_products = Vec::new();
+        for _ in 0..rng.gen_range(1..=4) {
+            let product = rng.gen_range(1..=products);
+            if detail_products.contains(&product) {
+                continue;
+            }
+            detail_products.push(product);
+            let price = prices[usize::try_from(product - 1).unwrap_or_default()];
+            let discount = if rng.gen_bool(0.3) {
+                cents(5 * rng.gen_range(1..=5))
+            } else {
+                cents(0)
+            };
+            order_details.push(vec![
+                key.to_string(),
+                product.to_string(),
+                cents(price),
+                rng.gen_range(1..=120).to_string(),
+                discount,
+            ]);
+        }
+    }
+
+    vec![
+        Table::new(
+            "categories",
+            &[
+                "category_id INTEGER NOT NULL",
+                "category_name VARCHAR(15) NOT NULL",
+                "description VARCHAR(100)",
+            ],
+            &["category_id"],
+            categories,
+        ),
+        Table::new(
+            "suppliers",
+            &[
+                "supplier_id INTEGER NOT NULL",
+                "company_name VARCHAR(100) NOT NULL",
+                "contact_name VARCHAR(60)",
+                "contact_title VARCHAR(30)",
+                "address VARCHAR(60)",
+                "city VARCHAR(40)",
+                "country VARCHAR(60)",
+                "phone VARCHAR(30)",
+            ],
+            &["supplier_id"],
+            suppliers_rows,
+        ),
+        Table::new(
+            "products",
+            &[
+                "product_id INTEGER NOT NULL",
+                "product_name VARCHAR(40) NOT NULL",
+                "supplier_id INTEGER NOT NULL",
+                "category_id INTEGER NOT NULL",
+                "quantity_per_unit VARCHAR(20)",
+                "unit_price DECIMAL(10,2) NOT NULL",
+                "units_in_stock INTEGER NOT NULL",
+                "discontinued INTEGER NOT NULL",
+            ],
+            &["product_id"],
+            products_rows,
+        ),
+        Table::new(
+            "customers",
+            &[
+                "customer_id VARCHAR(5) NOT NULL",
+                "company_name VARCHAR(100) NOT NULL",
+                "contact_name VARCHAR(60)",
+                "contact_title VARCHAR(30)",
+                "address VARCHAR(60)",
+                "city VARCHAR(40)",
+                "country VARCHAR(60)",
+                "phone VARCHAR(30)",
+            ],
+            &["customer_id"],
+            customers_rows,
+        ),
+        Table::new(
+            "employees",
+            &[
+                "employee_id INTEGER NOT NULL",
+                "last_name VARCHAR(20) NOT NULL",
+                "first_name VARCHAR(20) NOT NULL",
+                "title VARCHAR(30) NOT NULL",
+                "birth_date DATE NOT NULL",
+                "hire_date DATE NOT NULL",
+                "city VARCHAR(40) NOT NULL",
+                "reports_to INTEGER",
+            ],
+            &["employee_id"],
+            employees,
+        ),
+        Table::new(
+            "shippers",
+            &[
+                "shipper_id INTEGER NOT NULL",
+                "company_name VARCHAR(40) NOT NULL",
+                "phone VARCHAR(30)",
+            ],
+            &["shipper_id"],
+            shippers,
+        ),
+        Table::new(
+            "orders",
+            &[
+                "order_id INTEGER NOT NULL",
+                "customer_id VARCHAR(5) NOT NULL",
+                "employee_id INTEGER NOT NULL",
+                "order_date DATE NOT NULL",
+                "required_date DATE NOT NULL",
+                "shipped_date DATE",
+                "ship_via INTEGER NOT NULL",
+                "freight DECIMAL(10,2) NOT NULL",
+                "ship_city VARCHAR(40) NOT NULL",
+                "ship_country VARCHAR(60) NOT NULL",
+            ],
+            &["order_id"],
+            orders_rows,
+        ),
+        Table::new(
+            "order_details",
+            &[
+                "order_id INTEGER NOT NULL",
+                "product_id INTEGER NOT NULL",
+                "unit_price DECIMAL(10,2) NOT NULL",
+                "quantity INTEGER NOT NULL",
+                "discount DECIMAL(4,2) NOT NULL",
+            ],
+            &["order_id", "product_id"],
+            order_details,
+        ),
+    ]
+}
+
+/// Generate the company, contact and location values shared by suppliers and customers
+fn company(rng: &mut StdRng) -> Vec<String> {
+    let name: String = CompanyName().fake_with_rng(rng);
+    let contact: String = Name().fake_with_rng(rng);
+    let street: String = StreetName().fake_with_rng(rng);
+    let city: String = CityName().fake_with_rng(rng);
+    let country: String = CountryName().fake_with_rng(rng);
+    let phone: String = PhoneNumber().fake_with_rng(rng);
+    vec![
+        text(&name),
+        text(&contact),
+        text(choose(rng, &CONTACT_TITLES)),
+        text(&format!("{} {street}", rng.gen_range(1..=999))),
+        text(&city),
+        text(&country),
+        text(&phone),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_tables() {
+        let tables = tables(1);
+        let rows: Vec<(&str, usize)> = tables
+            .iter()
+            .map(|table| (table.name(), table.rows()))
+            .collect();
+        assert_eq!(
+            rows[..7],
+            [
+                ("categories", 8),
+                ("suppliers", 10),
+                ("products", 77),
+                ("customers", 90),
+                ("employees", 9),
+                ("shippers", 3),
+                ("orders", 830),
+            ]
+        );
+        assert_eq!(rows[7].0, "order_details");
+        assert!(rows[7].1 >= 830);
+    }
+
+    #[test]
+    fn test_order_details_are_unique() {
+        let tables = tables(1);
+        let details = &tables[7];
+        let keys: HashSet<(&String, &String)> =
+            details.rows.iter().map(|row| (&row[0], &row[1])).collect();
+        assert_eq!(keys.len(), details.rows());
+    }
+}
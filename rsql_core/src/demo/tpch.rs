@@ -0,0 +1,450 @@
+use crate::demo::{cents, choose, date, text, Table, SEED};
+use chrono::{Days, NaiveDate};
+use fake::faker::address::en::StreetName;
+use fake::faker::lorem::en::Words;
+use fake::Fake;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// The nations of the TPC-H specification with the key of their region
+const NATIONS: [(&str, u64); 25] = [
+    ("ALGERIA", 0),
+    ("ARGENTINA", 1),
+    ("BRAZIL", 1),
+    ("CANADA", 1),
+    ("EGYPT", 4),
+    ("ETHIOPIA", 0),
+    ("FRANCE", 3),
+    ("GERMANY", 3),
+    ("INDIA", 2),
+    ("INDONESIA", 2),
+    ("IRAN", 4),
+    ("IRAQ", 4),
+    ("JAPAN", 2),
+    ("JORDAN", 4),
+    ("KENYA", 0),
+    ("MOROCCO", 0),
+    ("MOZAMBIQUE", 0),
+    ("PERU", 1),
+    ("CHINA", 2),
+    ("ROMANIA", 3),
+    ("SAUDI ARABIA", 4),
+    ("VIETNAM", 2),
+    ("RUSSIA", 3),
+    ("UNITED KINGDOM", 3),
+    ("UNITED STATES", 1),
+];
+const REGIONS: [&str; 5] = ["AFRICA", "AMERICA", "ASIA", "EUROPE", "MIDDLE EAST"];
+const COLORS: [&str; 16] = [
+    "almond",
+    "antique",
+    "azure",
+    "blue",
+    "chocolate",
+    "coral",
+    "forest",
+    "green",
+    "ivory",
+    "lavender",
+    "midnight",
+    "navy",
+    "olive",
+    "peach",
+    "rose",
+    "steel",
+];
+const TYPE_SIZES: [&str; 6] = ["STANDARD", "SMALL", "MEDIUM", "LARGE", "ECONOMY", "PROMO"];
+const TYPE_FINISHES: [&str; 5] = ["ANODIZED", "BURNISHED", "PLATED", "POLISHED", "BRUSHED"];
+const TYPE_MATERIALS: [&str; 5] = ["TIN", "NICKEL", "BRASS", "STEEL", "COPPER"];
+const CONTAINER_SIZES: [&str; 5] = ["SM", "LG", "MED", "JUMBO", "WRAP"];
+const CONTAINER_TYPES: [&str; 8] = ["CASE", "BOX", "BAG", "JAR", "PKG", "PACK", "CAN", "DRUM"];
+const SEGMENTS: [&str; 5] = [
+    "AUTOMOBILE",
+    "BUILDING",
+    "FURNITURE",
+    "MACHINERY",
+    "HOUSEHOLD",
+];
+const PRIORITIES: [&str; 5] = ["1-URGENT", "2-HIGH", "3-MEDIUM", "4-NOT SPECIFIED", "5-LOW"];
+const INSTRUCTIONS: [&str; 4] = [
+    "DELIVER IN PERSON",
+    "COLLECT COD",
+    "NONE",
+    "TAKE BACK RETURN",
+];
+const MODES: [&str; 7] = ["REG AIR", "AIR", "RAIL", "SHIP", "TRUCK", "MAIL", "FOB"];
+/// The number of suppliers of each part
+const PART_SUPPLIERS: u64 = 4;
+
+/// Generate the tables of the TPC-H schema; one scale is approximately 1/1000 of the TPC-H scale
+/// factor 1, e.g. 1,500 orders instead of 1,500,000
+#[expect(clippy::too_many_lines)]
+pub(super) fn tables(scale: usize) -> Vec<Table> {
+    let scale = scale as u64;
+    let rng = &mut StdRng::seed_from_u64(SEED);
+    let suppliers = 10 * scale;
+    let customers = 150 * scale;
+    let parts = 200 * scale;
+    let orders = 1_500 * scale;
+
+    let region = REGIONS
+        .iter()
+        .enumerate()
+        .map(|(key, name)| vec![key.to_string(), text(name), text(&comment(rng, 152))])
+        .collect();
+    let nation = NATIONS
+        .iter()
+        .enumerate()
+        .map(|(key, (name, region))| {
+            vec![
+                key.to_string(),
+                text(name),
+                region.to_string(),
+                text(&comment(rng, 152)),
+            ]
+        })
+        .collect();
+    let supplier = (1..=suppliers)
+        .map(|key| {
+            let nation = rng.gen_range(0..NATIONS.len() as u64);
+            vec![
+                key.to_string(),
+                text(&format!("Supplier#{key:09}")),
+                text(&address(rng)),
+                nation.to_string(),
+                text(&phone(rng, nation)),
+                account_balance(rng),
+                text(&comment(rng, 101)),
+            ]
+        })
+        .collect();
+    let customer = (1..=customers)
+        .map(|key| {
+            let nation = rng.gen_range(0..NATIONS.len() as u64);
+            vec![
+                key.to_string(),
+                text(&format!("Customer#{key:09}")),
+                text(&address(rng)),
+                nation.to_string(),
+                text(&phone(rng, nation)),
+                account_balance(rng),
+                text(choose(rng, &SEGMENTS)),
+                text(&comment(rng, 117)),
+            ]
+        })
+        .collect();
+
+    let mut part = Vec::new();
+    let mut partsupp = Vec::new();
+    for key in 1..=parts {
+        let mut colors = COLORS.to_vec();
+        colors.shuffle(rng);
+        let manufacturer = rng.gen_range(1..=5);
+        part.push(vec![
+            key.to_string(),
+            text(&colors[..5].join(" ")),
+            text(&format!("Manufacturer#{manufacturer}")),
+            text(&format!("Brand#{manufacturer}{}", rng.gen_range(1..=5))),
+            text(&format!(
+                "{} {} {}",
+                choose(rng, &TYPE_SIZES),
+                choose(rng, &TYPE_FINISHES),
+                choose(rng, &TYPE_MATERIALS)
+            )),
+            rng.gen_range(1..=50).to_string(),
+            text(&format!(
+                "{} {}",
+                choose(rng, &CONTAINER_SIZES),
+                choose(rng, &CONTAINER_TYPES)
+            )),
+            cents(retail_price(key)),
+            text(&comment(rng, 23)),
+        ]);
+        for index in 0..PART_SUPPLIERS {
+            partsupp.push(vec![
+                key.to_string(),
+                part_supplier(key, index, suppliers).to_string(),
+                rng.gen_range(1..=9_999).to_string(),
+                cents(rng.gen_range(100..=100_000)),
+                text(&comment(rng, 199)),
+            ]);
+        }
+    }
+
+    let start = NaiveDate::from_ymd_opt(1992, 1, 1).unwrap_or_default();
+    let current = NaiveDate::from_ymd_opt(1995, 6, 17).unwrap_or_default();
+    let mut order = Vec::new();
+    let mut lineitem = Vec::new();
+    for key in 1..=orders {
+        let order_date = start + Days::new(rng.gen_range(0..=2_405));
+        let mut total = 0;
+        let mut statuses = Vec::new();
+        for line in 1..=rng.gen_range(1..=7) {
+            let part = rng.gen_range(1..=parts);
+            let supplier = part_supplier(part, rng.gen_range(0..PART_SUPPLIERS), suppliers);
+            let quantity: u64 = rng.gen_range(1..=50);
+            let price = quantity * retail_price(part);
+            let discount: u64 = rng.gen_range(0..=10);
+            let tax: u64 = rng.gen_range(0..=8);
+            total += price * (100 + tax) * (100 - discount) / 10_000;
+            let ship_date = order_date + Days::new(rng.gen_range(1..=121));
+            let commit_date = order_date + Days::new(rng.gen_range(30..=90));
+            let receipt_date = ship_date + Days::new(rng.gen_range(1..=30));
+            let return_flag = if receipt_date > current {
+                "N"
+            } else if rng.gen_bool(0.5) {
+                "R"
+            } else {
+                "A"
+            };
+            let status = if ship_date > current { "O" } else { "F" };
+            statuses.push(status);
+            lineitem.push(vec![
+                key.to_string(),
+                part.to_string(),
+                supplier.to_string(),
+                line.to_string(),
+                cents(quantity * 100),
+                cents(price),
+                cents(discount),
+                cents(tax),
+                text(return_flag),
+                text(status),
+                date(ship_date),
+                date(commit_date),
+                date(receipt_date),
+                text(choose(rng, &INSTRUCTIONS)),
+                text(choose(rng, &MODES)),
+                text(&comment(rng, 44)),
+            ]);
+        }
+        let status = if statuses.iter().all(|status| *status == "F") {
+            "F"
+        } else if statuses.iter().all(|status| *status == "O") {
+            "O"
+        } else {
+            "P"
+        };
+        order.push(vec![
+            key.to_string(),
+            rng.gen_range(1..=customers).to_string(),
+            text(status),
+            cents(total),
+            date(order_date),
+            text(choose(rng, &PRIORITIES)),
+            text(&format!("Clerk#{:09}", rng.gen_range(1..=10 * scale))),
+            "0".to_string(),
+            text(&comment(rng, 79)),
+        ]);
+    }
+
+    vec![
+        Table::new(
+            "region",
+            &[
+                "r_regionkey INTEGER NOT NULL",
+                "r_name VARCHAR(25) NOT NULL",
+                "r_comment VARCHAR(152)",
+            ],
+            &["r_regionkey"],
+            region,
+        ),
+        Table::new(
+            "nation",
+            &[
+                "n_nationkey INTEGER NOT NULL",
+                "n_name VARCHAR(25) NOT NULL",
+                "n_regionkey INTEGER NOT NULL",
+                "n_comment VARCHAR(152)",
+            ],
+            &["n_nationkey"],
+            nation,
+        ),
+        Table::new(
+            "supplier",
+            &[
+                "s_suppkey INTEGER NOT NULL",
+                "s_name VARCHAR(25) NOT NULL",
+                "s_address VARCHAR(40) NOT NULL",
+                "s_nationkey INTEGER NOT NULL",
+                "s_phone VARCHAR(15) NOT NULL",
+                "s_acctbal DECIMAL(15,2) NOT NULL",
+                "s_comment VARCHAR(101) NOT NULL",
+            ],
+            &["s_suppkey"],
+            supplier,
+        ),
+        Table::new(
+            "customer",
+            &[
+                "c_custkey INTEGER NOT NULL",
+                "c_name VARCHAR(25) NOT NULL",
+                "c_address VARCHAR(40) NOT NULL",
+                "c_nationkey INTEGER NOT NULL",
+                "c_phone VARCHAR(15) NOT NULL",
+                "c_acctbal DECIMAL(15,2) NOT NULL",
+                "c_mktsegment VARCHAR(10) NOT NULL",
+                "c_comment VARCHAR(117) NOT NULL",
+            ],
+            &["c_custkey"],
+            customer,
+        ),
+        Table::new(
+            "part",
+            &[
+                "p_partkey INTEGER NOT NULL",
+                "p_name VARCHAR(55) NOT NULL",
+                "p_mfgr VARCHAR(25) NOT NULL",
+                "p_brand VARCHAR(10) NOT NULL",
+                "p_type VARCHAR(25) NOT NULL",
+                "p_size INTEGER NOT NULL",
+                "p_container VARCHAR(10) NOT NULL",
+                "p_retailprice DECIMAL(15,2) NOT NULL",
+                "p_comment VARCHAR(23) NOT NULL",
+            ],
+            &["p_partkey"],
+            part,
+        ),
+        Table::new(
+            "partsupp",
+            &[
+                "ps_partkey INTEGER NOT NULL",
+                "ps_suppkey INTEGER NOT NULL",
+                "ps_availqty INTEGER NOT NULL",
+                "ps_supplycost DECIMAL(15,2) NOT NULL",
+                "ps_comment VARCHAR(199) NOT NULL",
+            ],
+            &["ps_partkey", "ps_suppkey"],
+            partsupp,
+        ),
+        Table::new(
+            "orders",
+            &[
+                "o_orderkey INTEGER NOT NULL",
+                "o_custkey INTEGER NOT NULL",
+                "o_orderstatus VARCHAR(1) NOT NULL",
+                "o_totalprice DECIMAL(15,2) NOT NULL",
+                "o_orderdate DATE NOT NULL",
+                "o_orderpriority VARCHAR(15) NOT NULL",
+                "o_clerk VARCHAR(15) NOT NULL",
+                "o_shippriority INTEGER NOT NULL",
+                "o_comment VARCHAR(79) NOT NULL",
+            ],
+            &["o_orderkey"],
+            order,
+        ),
+        Table::new(
+            "lineitem",
+            &[
+                "l_orderkey INTEGER NOT NULL",
+                "l_partkey INTEGER NOT NULL",
+                "l_suppkey INTEGER NOT NULL",
+                "l_linenumber INTEGER NOT NULL",
+                "l_quantity DECIMAL(15,2) NOT NULL",
+                "l_extendedprice DECIMAL(15,2) NOT NULL",
+                "l_discount DECIMAL(15,2) NOT NULL",
+                "l_tax DECIMAL(15,2) NOT NULL",
+                "l_returnflag VARCHAR(1) NOT NULL",
+                "l_linestatus VARCHAR(1) NOT NULL",
+                "l_shipdate DATE NOT NULL",
+                "l_commitdate DATE NOT NULL",
+                "l_receiptdate DATE NOT NULL",
+                "l_shipinstruct VARCHAR(25) NOT NULL",
+                "l_shipmode VARCHAR(10) NOT NULL",
+                "l_comment VARCHAR(44) NOT NULL",
+            ],
+            &["l_orderkey", "l_linenumber"],
+            lineitem,
+        ),
+    ]
+}
+
+/// Generate a comment of random words with at most `length` characters
+fn comment(rng: &mut StdRng, length: usize) -> String {
+    let words: Vec<String> = Words(2..8).fake_with_rng(rng);
+    words.join(" ").chars().take(length).collect()
+}
+
+/// Generate a street address
+fn address(rng: &mut StdRng) -> String {
+    let street: String = StreetName().fake_with_rng(rng);
+    format!("{} {street}", rng.gen_range(1..=9_999))
+}
+
+/// Generate a phone number; the country code is derived from the nation key
+fn phone(rng: &mut StdRng, nation: u64) -> String {
+    format!(
+        "{}-{}-{}-{}",
+        nation + 10,
+        rng.gen_range(100..=999),
+        rng.gen_range(100..=999),
+        rng.gen_range(1_000..=9_999)
+    )
+}
+
+/// Generate an account balance between -999.99 and 9,999.99
+fn account_balance(rng: &mut StdRng) -> String {
+    let balance: i64 = rng.gen_range(-99_999..=999_999);
+    let sign = if balance < 0 { "-" } else { "" };
+    format!("{sign}{}", cents(balance.unsigned_abs()))
+}
+
+/// Get the retail price of a part in hundredths, using the formula of the TPC-H specification
+fn retail_price(part: u64) -> u64 {
+    90_000 + ((part / 10) % 20_001) + 100 * (part % 1_000)
+}
+
+/// Get the key of a supplier of a part, based on the formula of the TPC-H specification; the
+/// specification also adds `(part - 1) / suppliers` to the step, which only keeps the suppliers of
+/// a part distinct with thousands of suppliers
+fn part_supplier(part: u64, index: u64, suppliers: u64) -> u64 {
+    (part + index * (suppliers / PART_SUPPLIERS)) % suppliers + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_tables() {
+        let tables = tables(1);
+        let rows: Vec<(&str, usize)> = tables
+            .iter()
+            .map(|table| (table.name(), table.rows()))
+            .collect();
+        assert_eq!(
+            rows[..7],
+            [
+                ("region", 5),
+                ("nation", 25),
+                ("supplier", 10),
+                ("customer", 150),
+                ("part", 200),
+                ("partsupp", 800),
+                ("orders", 1_500),
+            ]
+        );
+        assert_eq!(rows[7].0, "lineitem");
+        assert!(rows[7].1 >= 1_500);
+    }
+
+    #[test]
+    fn test_part_suppliers_are_unique() {
+        let suppliers = 10;
+        for part in 1..=200 {
+            let keys: HashSet<u64> = (0..PART_SUPPLIERS)
+                .map(|index| part_supplier(part, index, suppliers))
+                .collect();
+            assert_eq!(keys.len(), 4);
+            assert!(keys.iter().all(|key| (1..=suppliers).contains(key)));
+        }
+    }
+
+    #[test]
+    fn test_retail_price() {
+        assert_eq!(retail_price(1), 90_100);
+        assert_eq!(retail_price(1_000), 90_100);
+    }
+}
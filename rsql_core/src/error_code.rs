@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+/// Stable codes identifying the category of an error surfaced by the shell. Codes are intended
+/// to be consumed by tooling and must not change once released.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// An error without a more specific category
+    Unknown,
+    /// An IO error
+    Io,
+    /// A generic driver error
+    Driver,
+    /// A driver was not found for the URL scheme
+    DriverNotFound,
+    /// The connection URL is invalid
+    InvalidUrl,
+    /// A value could not be converted
+    Conversion,
+    /// A column type is not supported by the driver
+    UnsupportedColumnType,
+    /// A generic formatter error
+    Formatter,
+    /// The requested format does not exist
+    UnknownFormat,
+    /// The command does not exist
+    InvalidCommand,
+    /// An invalid option was supplied to a command
+    InvalidOption,
+    /// A command is missing a required argument
+    MissingArguments,
+    /// A statement that modifies the database was refused in read-only mode
+    ReadOnly,
+    /// A statement was cancelled because it exceeded the timeout
+    Timeout,
+    /// A SQL template could not be rendered
+    Template,
+}
+
+impl ErrorCode {
+    /// Get the code identifier, e.g. `E0101`.
+    #[must_use]
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorCode::Unknown => "E0000",
+            ErrorCode::Io => "E0001",
+            ErrorCode::Driver => "E0100",
+            ErrorCode::DriverNotFound => "E0101",
+            ErrorCode::InvalidUrl => "E0102",
+            ErrorCode::Conversion => "E0103",
+            ErrorCode::UnsupportedColumnType => "E0104",
+            ErrorCode::Formatter => "E0200",
+            ErrorCode::UnknownFormat => "E0201",
+            ErrorCode::InvalidCommand => "E0300",
+            ErrorCode::InvalidOption => "E0301",
+            ErrorCode::MissingArguments => "E0302",
+            ErrorCode::ReadOnly => "E0400",
+            ErrorCode::Timeout => "E0401",
+            ErrorCode::Template => "E0402",
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Get the error code for a [`rsql_drivers::Error`]
+impl From<&rsql_drivers::Error> for ErrorCode {
+    fn from(error: &rsql_drivers::Error) -> Self {
+        match error {
+            rsql_drivers::Error::ConversionError(_) | rsql_drivers::Error::TryFromIntError(_) => {
+                ErrorCode::Conversion
+            }
+            rsql_drivers::Error::DriverNotFound(_) => ErrorCode::DriverNotFound,
+            rsql_drivers::Error::InvalidUrl(_) => ErrorCode::InvalidUrl,
+            rsql_drivers::Error::IoError(_) => ErrorCode::Driver,
+            rsql_drivers::Error::UnsupportedColumnType { .. } => ErrorCode::UnsupportedColumnType,
+        }
+    }
+}
+
+/// Get the error code for a [`rsql_formatters::Error`]
+impl From<&rsql_formatters::Error> for ErrorCode {
+    fn from(error: &rsql_formatters::Error) -> Self {
+        match error {
+            rsql_formatters::Error::UnknownFormat { .. } => ErrorCode::UnknownFormat,
+            rsql_formatters::Error::IoError(_) | rsql_formatters::Error::TryFromIntError(_) => {
+                ErrorCode::Formatter
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_display() {
+        assert_eq!(ErrorCode::Unknown.to_string(), "E0000");
+        assert_eq!(ErrorCode::InvalidCommand.to_string(), "E0300");
+        assert_eq!(ErrorCode::ReadOnly.to_string(), "E0400");
+        assert_eq!(ErrorCode::Timeout.to_string(), "E0401");
+        assert_eq!(ErrorCode::Template.to_string(), "E0402");
+    }
+
+    #[test]
+    fn test_driver_error_code() {
+        let error = rsql_drivers::Error::InvalidUrl("foo".to_string());
+        assert_eq!(ErrorCode::from(&error), ErrorCode::InvalidUrl);
+    }
+
+    #[test]
+    fn test_formatter_error_code() {
+        let error = rsql_formatters::Error::UnknownFormat {
+            format: "foo".to_string(),
+        };
+        assert_eq!(ErrorCode::from(&error), ErrorCode::UnknownFormat);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::commands::{CommandManager, CommandOptions, LoopCondition};
 use crate::configuration::Configuration;
 use crate::executors::{Error, Result};
+use crate::shell::ShellState;
 use regex::Regex;
 use rsql_drivers::{Connection, DriverManager};
 use rsql_formatters::writers::Output;
@@ -16,18 +17,21 @@ pub(crate) struct CommandExecutor<'a> {
     driver_manager: &'a DriverManager,
     formatter_manager: &'a FormatterManager,
     history: &'a DefaultHistory,
+    state: &'a mut ShellState,
     connection: &'a mut dyn Connection,
     output: &'a mut Output,
 }
 
 /// Implementation for [`CommandExecutor`].
 impl<'a> CommandExecutor<'a> {
+    #[expect(clippy::too_many_arguments)]
     pub(crate) fn new(
         configuration: &'a mut Configuration,
         command_manager: &'a CommandManager,
         driver_manager: &'a DriverManager,
         formatter_manager: &'a FormatterManager,
         history: &'a DefaultHistory,
+        state: &'a mut ShellState,
         connection: &'a mut dyn Connection,
         output: &'a mut Output,
     ) -> CommandExecutor<'a> {
@@ -37,6 +41,7 @@ impl<'a> CommandExecutor<'a> {
             driver_manager,
             formatter_manager,
             history,
+            state,
             connection,
             output,
         }
@@ -61,6 +66,7 @@ impl<'a> CommandExecutor<'a> {
                     formatter_manager: self.formatter_manager,
                     connection: self.connection,
                     history: self.history,
+                    state: self.state,
                     input,
                     output: self.output,
                 };
@@ -110,6 +116,7 @@ fn split_string(input: &str) -> Vec<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shell::ShellState;
     use rsql_drivers::MockConnection;
 
     #[tokio::test]
@@ -122,12 +129,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let output = &mut Output::default();
 
+        let mut state = ShellState::default();
         let executor = CommandExecutor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             output,
         );
@@ -150,12 +159,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let output = &mut Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = CommandExecutor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             output,
         );
@@ -177,12 +188,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let output = &mut Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = CommandExecutor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             output,
         );
@@ -1,3 +1,6 @@
+use crate::error_code::ErrorCode;
+use std::time::Duration;
+
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 #[derive(Debug, thiserror::Error)]
@@ -17,6 +20,32 @@ pub enum Error {
     /// IO error
     #[error(transparent)]
     IoError(anyhow::Error),
+    /// Error when a statement that modifies the database is executed in read-only mode
+    #[error("Statement not allowed in read-only mode")]
+    ReadOnly,
+    /// Error when a SQL template cannot be rendered
+    #[error("Template error on line {line}: {message}")]
+    TemplateError { line: usize, message: String },
+    /// Error when a statement is cancelled because it exceeded the timeout
+    #[error("Statement cancelled after exceeding the timeout of {0:?}")]
+    Timeout(Duration),
+}
+
+impl Error {
+    /// Get the error code for this error.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::CommandError(error) => error.code(),
+            Error::DriverError(error) => ErrorCode::from(error),
+            Error::FormatError(error) => ErrorCode::from(error),
+            Error::InvalidCommand { .. } => ErrorCode::InvalidCommand,
+            Error::IoError(_) => ErrorCode::Io,
+            Error::ReadOnly => ErrorCode::ReadOnly,
+            Error::TemplateError { .. } => ErrorCode::Template,
+            Error::Timeout(_) => ErrorCode::Timeout,
+        }
+    }
 }
 
 /// Converts a [`indicatif::style::TemplateError`] into an [`IoError`](Error::IoError)
@@ -40,6 +69,7 @@ impl From<std::io::Error> for Error {
     }
 }
 
+/// Converts a [`sqlparser::parser::ParserError`] into an [`IoError`](Error::IoError)
 impl From<sqlparser::parser::ParserError> for Error {
     fn from(error: sqlparser::parser::ParserError) -> Self {
         Error::IoError(error.into())
@@ -49,6 +79,42 @@ impl From<sqlparser::parser::ParserError> for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_invalid_command_code() {
+        let error = Error::InvalidCommand {
+            command_name: "foo".to_string(),
+        };
+        assert_eq!(error.code(), ErrorCode::InvalidCommand);
+    }
+
+    #[test]
+    fn test_read_only_code() {
+        let error = Error::ReadOnly;
+        assert_eq!(error.code(), ErrorCode::ReadOnly);
+        assert_eq!(error.to_string(), "Statement not allowed in read-only mode");
+    }
+
+    #[test]
+    fn test_timeout_code() {
+        let error = Error::Timeout(Duration::from_secs(30));
+        assert_eq!(error.code(), ErrorCode::Timeout);
+        assert_eq!(
+            error.to_string(),
+            "Statement cancelled after exceeding the timeout of 30s"
+        );
+    }
+
+    #[test]
+    fn test_sql_template_error() {
+        let error = Error::TemplateError {
+            line: 3,
+            message: "missing endif".to_string(),
+        };
+        assert_eq!(error.code(), ErrorCode::Template);
+        assert_eq!(error.to_string(), "Template error on line 3: missing endif");
+    }
+
     #[test]
     fn test_template_error() {
         let result = indicatif::ProgressStyle::with_template("{:^3");
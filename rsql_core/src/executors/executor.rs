@@ -1,15 +1,24 @@
 use crate::commands::{CommandManager, LoopCondition};
-use crate::configuration::{Configuration, EchoMode};
+use crate::configuration::{Configuration, EchoMode, OutputTarget};
 use crate::executors::command::CommandExecutor;
 use crate::executors::sql::SqlExecutor;
-use crate::executors::Result;
+use crate::executors::{Error, Result};
+use crate::shell::{CacheKey, ShellState};
+use futures_util::future::join_all;
 use regex::Regex;
-use rsql_drivers::{Connection, DriverManager};
+use rsql_drivers::{
+    is_session_statement, transaction_state, Connection, DriverManager, StatementMetadata,
+};
 use rsql_formatters::writers::Output;
 use rsql_formatters::{FormatterManager, FormatterOptions, Highlighter};
 use rustyline::history::DefaultHistory;
+use serde_json::Map;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use url::Url;
 
 pub struct Executor<'a> {
     configuration: &'a mut Configuration,
@@ -17,17 +26,21 @@ pub struct Executor<'a> {
     driver_manager: &'a DriverManager,
     formatter_manager: &'a FormatterManager,
     history: &'a DefaultHistory,
+    state: &'a mut ShellState,
     connection: &'a mut dyn Connection,
+    replacement: Option<Box<dyn Connection>>,
     output: &'a mut Output,
 }
 
 impl<'a> Executor<'a> {
+    #[expect(clippy::too_many_arguments)]
     pub(crate) fn new(
         configuration: &'a mut Configuration,
         command_manager: &'a CommandManager,
         driver_manager: &'a DriverManager,
         formatter_manager: &'a FormatterManager,
         history: &'a DefaultHistory,
+        state: &'a mut ShellState,
         connection: &'a mut dyn Connection,
         output: &'a mut Output,
     ) -> Executor<'a> {
@@ -37,20 +50,15 @@ impl<'a> Executor<'a> {
             driver_manager,
             formatter_manager,
             history,
+            state,
             connection,
+            replacement: None,
             output,
         }
     }
 
     fn parse_commands(&self, contents: &str) -> Result<Vec<String>> {
-        let command_identifier = regex::escape(&self.configuration.command_identifier);
-        let pattern = format!(r"(?ms)^\s*({command_identifier}.*?|.*?;|.*)\s*$");
-        let regex = Regex::new(pattern.as_str())?;
-        let commands: Vec<String> = regex
-            .find_iter(contents)
-            .map(|mat| mat.as_str().trim().to_string())
-            .collect();
-        Ok(commands)
+        parse_commands(&self.configuration.command_identifier, contents)
     }
 
     /// Execute the command and return the loop condition.
@@ -59,76 +67,568 @@ impl<'a> Executor<'a> {
     ///
     /// Returns an error if the command cannot be executed.
     pub async fn execute(&mut self, input: &str) -> Result<LoopCondition> {
+        let result = self.execute_commands(input).await;
+        if let Some(connection) = self.replacement.take() {
+            self.state.connection = Some(connection);
+        }
+        result
+    }
+
+    /// Execute the SQL statements and return the loop condition; commands are refused, so that
+    /// input received from other programs cannot run commands such as `.system`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input contains a command, or the statements cannot be executed.
+    pub async fn execute_sql(&mut self, input: &str) -> Result<LoopCondition> {
+        let command_identifier = &self.configuration.command_identifier;
+        if let Some(command) = self
+            .parse_commands(input.trim())?
+            .into_iter()
+            .find(|command| command.starts_with(command_identifier.as_str()))
+        {
+            let command_name = command.split_whitespace().next().unwrap_or_default();
+            return Err(Error::InvalidCommand {
+                command_name: command_name.to_string(),
+            });
+        }
+        self.execute(input).await
+    }
+
+    /// Execute the SQL statement and discard the results, for benchmarking the statement; returns
+    /// the time spent executing the statement and fetching the rows, and the number of rows
+    /// returned or affected.  The read-only mode and timeout of the configuration are enforced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the statement cannot be executed.
+    pub async fn bench(&mut self, sql: &str) -> Result<(Duration, u64)> {
+        self.enforce_read_only(sql).await?;
+        let timeout = self.enforce_timeout().await?;
+        let connection: &mut dyn Connection = match &mut self.replacement {
+            Some(connection) => connection.as_mut(),
+            None => &mut *self.connection,
+        };
+        let mut executor = SqlExecutor::new(
+            self.configuration,
+            self.formatter_manager,
+            connection,
+            self.output,
+        )
+        .with_timeout(timeout);
+        let start = Instant::now();
+        let rows = executor.execute_discard(sql).await?;
+        Ok((start.elapsed(), rows))
+    }
+
+    async fn execute_commands(&mut self, input: &str) -> Result<LoopCondition> {
         let input = input.trim();
-        let commands = self.parse_commands(input)?;
-        for command in commands {
-            if let LoopCondition::Exit(exit_code) = self.execute_command(command.as_str()).await? {
+        let commands: Vec<String> = self
+            .parse_commands(input)?
+            .into_iter()
+            .filter(|command| !command.is_empty())
+            .collect();
+        let mut index = 0;
+        while index < commands.len() {
+            let queries = self.parallel_queries(&commands[index..]);
+            if queries > 1 {
+                self.execute_parallel(&commands[index..index + queries])
+                    .await?;
+                index += queries;
+                continue;
+            }
+
+            let command = commands[index].as_str();
+            let loop_condition = self.execute_command(command).await?;
+            self.replace_connection().await?;
+            self.apply_read_only().await?;
+            if let LoopCondition::Exit(exit_code) = loop_condition {
                 return Ok(LoopCondition::Exit(exit_code));
             }
+            index += 1;
         }
 
         Ok(LoopCondition::Continue)
     }
 
+    /// Use the connection opened by a command, if any, for the remaining commands; the connection
+    /// replaces the connection of the shell once the input has been executed.
+    async fn replace_connection(&mut self) -> Result<()> {
+        if let Some(connection) = self.state.connection.take() {
+            self.state.in_transaction = false;
+            self.state.read_only = false;
+            self.state.session.clear();
+            if let Some(mut replaced) = self.replacement.replace(connection) {
+                replaced.close().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the connection used to execute commands.
+    fn connection(&mut self) -> &mut dyn Connection {
+        match &mut self.replacement {
+            Some(connection) => connection.as_mut(),
+            None => &mut *self.connection,
+        }
+    }
+
+    /// Record the SQL statement that succeeded if it begins or ends a transaction, or changes the
+    /// settings of the session.
+    fn record_statement(&mut self, sql: &str) {
+        if let Some(in_transaction) = transaction_state(sql) {
+            self.state.in_transaction = in_transaction;
+        }
+        if is_session_statement(sql) {
+            self.state.session.push(sql.to_string());
+        }
+    }
+
+    /// Get the number of consecutive read-only queries at the start of the commands that can be
+    /// executed in parallel; returns 0 when parallel execution is disabled.  Queries are not
+    /// executed in parallel while a transaction is open, since the other connections would not
+    /// see the changes of the transaction, or when the database only exists in the memory of the
+    /// connection.
+    fn parallel_queries(&self, commands: &[String]) -> usize {
+        if self.configuration.parallel <= 1 || self.configuration.json || self.state.in_transaction
+        {
+            return 0;
+        }
+
+        let command_identifier = &self.configuration.command_identifier;
+        let connection: &dyn Connection = match &self.replacement {
+            Some(connection) => connection.as_ref(),
+            None => &*self.connection,
+        };
+        if is_in_memory(connection.url()) {
+            return 0;
+        }
+        commands
+            .iter()
+            .take_while(|command| {
+                !command.starts_with(command_identifier)
+                    && !self.state.stash.references(command)
+                    && matches!(connection.parse_sql(command), StatementMetadata::Query)
+            })
+            .count()
+    }
+
+    /// Execute the queries concurrently on a pool of connections and write the results in the
+    /// order of the queries as soon as they are available.  The current connection is used as the
+    /// first connection of the pool and duplicates of it, with the same credentials, are opened
+    /// for the remaining workers; the session statements, read-only mode and historical reads of
+    /// the current connection are applied to the additional connections.
+    async fn execute_parallel(&mut self, queries: &[String]) -> Result<()> {
+        for query in queries {
+            self.enforce_read_only(query).await?;
+        }
+        let workers = self.configuration.parallel.min(queries.len());
+        let read_only = self.configuration.read_only;
+        let timeout = self.configuration.results_timeout;
+        let mut timeouts = vec![self.enforce_timeout().await?];
+        let mut connections = Vec::with_capacity(workers - 1);
+        for _ in 1..workers {
+            let mut connection = if let Some(connection) = self.connection().duplicate().await? {
+                connection
+            } else {
+                let url = self.connection().url().clone();
+                self.driver_manager.connect(url.as_str()).await?
+            };
+            for statement in &self.state.session {
+                connection.execute(statement).await?;
+            }
+            if read_only {
+                connection.set_read_only(true).await?;
+            }
+            if let (Some(_), Some(historical_reads)) =
+                (&self.state.as_of, connection.as_historical_reads())
+            {
+                historical_reads.set_as_of(self.state.as_of.clone()).await?;
+            }
+            if timeout.is_zero() || connection.set_timeout(timeout).await? {
+                timeouts.push(Duration::ZERO);
+            } else {
+                timeouts.push(timeout);
+            }
+            connections.push(connection);
+        }
+
+        let configuration = &*self.configuration;
+        let formatter_manager = self.formatter_manager;
+        let output = &mut *self.output;
+        let state = &mut *self.state;
+        let connection: &mut dyn Connection = match &mut self.replacement {
+            Some(connection) => connection.as_mut(),
+            None => &mut *self.connection,
+        };
+        let mut pool: Vec<&mut dyn Connection> = vec![connection];
+        for connection in &mut connections {
+            pool.push(connection.as_mut());
+        }
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let futures: Vec<_> = pool
+            .into_iter()
+            .zip(timeouts)
+            .enumerate()
+            .map(|(worker, (connection, timeout))| {
+                let sender = sender.clone();
+                async move {
+                    for index in (worker..queries.len()).step_by(workers) {
+                        let (sql, limit) = statement_limit(configuration, queries[index].as_str());
+                        let mut output = Output::default();
+                        let mut executor = SqlExecutor::new(
+                            configuration,
+                            formatter_manager,
+                            &mut *connection,
+                            &mut output,
+                        )
+                        .with_limit(limit)
+                        .with_timeout(timeout);
+                        let result = executor.execute(&sql).await;
+                        let last_result = executor.last_result();
+                        // Results received after a query has failed are discarded
+                        let _ = sender.send((index, result.map(|_| (output, last_result))));
+                    }
+                }
+            })
+            .collect();
+        drop(sender);
+
+        // Write the results of the queries that are next in order as each query completes
+        let write_results = async {
+            let mut completed = BTreeMap::new();
+            let mut next = 0;
+            while let Some((index, result)) = receiver.recv().await {
+                completed.insert(index, result);
+                while let Some(result) = completed.remove(&next) {
+                    echo(configuration, output, queries[next].as_str())?;
+                    let (results, last_result) = result?;
+                    write!(output, "{results}")?;
+                    if let Some(last_result) = last_result {
+                        state.set_last_result(last_result);
+                    }
+                    next += 1;
+                }
+            }
+            Ok(())
+        };
+        let (_, result) = tokio::join!(join_all(futures), write_results);
+
+        for connection in &mut connections {
+            connection.close().await?;
+        }
+        result
+    }
+
     async fn execute_command(&mut self, input: &str) -> Result<LoopCondition> {
         let input = input.trim();
 
         if input.is_empty() {
             return Ok(LoopCondition::Continue);
         }
-
-        let options = FormatterOptions {
-            color: self.configuration.color,
-            ..Default::default()
-        };
-        let helper = Highlighter::new(&options, "sql");
-        let locale = self.configuration.locale.as_str();
-        let echo = t!("echo_command", locale = locale);
-        let off = t!("off", locale = locale);
-        let echo_off = format!("{}{} {}", self.configuration.command_identifier, echo, off);
-
-        if input != echo_off {
-            if self.configuration.echo == EchoMode::On {
-                let input = helper.highlight(input)?;
-                writeln!(&mut self.output, "{input}")?;
-            } else if self.configuration.echo == EchoMode::Prompt {
-                let prompt = t!(
-                    "prompt",
-                    locale = locale,
-                    program_name = self.configuration.program_name,
-                );
-                let input = helper.highlight(input)?;
-                writeln!(&mut self.output, "{prompt}{input}")?;
-            }
+        if self.configuration.json {
+            return self.execute_json(input).await;
         }
 
+        self.echo(input)?;
+
         let command_identifier = &self.configuration.command_identifier;
         let loop_condition = if input.starts_with(command_identifier) {
+            let connection: &mut dyn Connection = match &mut self.replacement {
+                Some(connection) => connection.as_mut(),
+                None => &mut *self.connection,
+            };
             let mut executor = CommandExecutor::new(
                 self.configuration,
                 self.command_manager,
                 self.driver_manager,
                 self.formatter_manager,
                 self.history,
-                self.connection,
+                self.state,
+                connection,
                 self.output,
             );
 
             executor.execute(input).await?
         } else {
-            let mut executor = SqlExecutor::new(
+            self.state.last_statement = Some(input.to_string());
+            let (sql, limit) = statement_limit(self.configuration, input);
+            let cache_key = self.cache_key(&sql, limit);
+            let cached_result = cache_key.as_ref().and_then(|cache_key| {
+                let ttl = self.configuration.results_cache_ttl;
+                self.state.cache.get(cache_key, ttl)
+            });
+            let (executor, sql) = self.sql_executor(&sql).await?;
+            let mut executor = executor.with_limit(limit);
+            let (loop_condition, last_result) = if let Some(cached_result) = cached_result {
+                let loop_condition = executor.execute_cached(cached_result.clone()).await?;
+                (loop_condition, Some(cached_result))
+            } else {
+                let loop_condition = executor.execute(&sql).await?;
+                let last_result = executor.last_result();
+                if let (Some(cache_key), Some(last_result)) = (cache_key, &last_result) {
+                    self.state.cache.insert(cache_key, last_result.clone());
+                }
+                (loop_condition, last_result)
+            };
+            self.record_statement(input);
+            if let Some(last_result) = last_result {
+                #[cfg(feature = "viewer")]
+                if self.configuration.results_viewer_auto {
+                    crate::viewer::view_auto(
+                        self.configuration,
+                        self.formatter_manager,
+                        last_result.clone(),
+                        self.output,
+                    )
+                    .await?;
+                }
+                self.state.set_last_result(last_result);
+            }
+            loop_condition
+        };
+        Ok(loop_condition)
+    }
+
+    /// Execute the command or SQL statement and write a JSON document on a single line with the
+    /// statement, whether it succeeded, the error or the results, and the elapsed time, so that the
+    /// results can be processed by other programs.  The output of commands is captured and
+    /// included in the document.
+    async fn execute_json(&mut self, input: &str) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let mut details = Map::new();
+        let result = if input.starts_with(&self.configuration.command_identifier) {
+            let mut output = Output::default();
+            let connection: &mut dyn Connection = match &mut self.replacement {
+                Some(connection) => connection.as_mut(),
+                None => &mut *self.connection,
+            };
+            let mut executor = CommandExecutor::new(
                 self.configuration,
+                self.command_manager,
+                self.driver_manager,
                 self.formatter_manager,
-                self.connection,
-                self.output,
+                self.history,
+                self.state,
+                connection,
+                &mut output,
             );
+            let result = executor.execute(input).await;
+            details.insert("output".to_string(), output.to_string().into());
+            result
+        } else {
+            let (sql, limit) = statement_limit(self.configuration, input);
+            match self.sql_executor(&sql).await {
+                Ok((executor, sql)) => {
+                    let mut executor = executor.with_limit(limit);
+                    let result = executor.execute_json(&sql).await;
+                    if let Some(last_result) = executor.last_result() {
+                        self.state.set_last_result(last_result);
+                    }
+                    if result.is_ok() {
+                        self.record_statement(input);
+                    }
+                    result.map(|results| {
+                        details = results;
+                        LoopCondition::Continue
+                    })
+                }
+                Err(error) => Err(error),
+            }
+        };
 
-            executor.execute(input).await?
+        let mut document = Map::new();
+        document.insert("statement".to_string(), input.into());
+        document.insert("success".to_string(), result.is_ok().into());
+        if let Err(error) = &result {
+            document.insert("error".to_string(), error.to_string().into());
+        }
+        document.extend(details);
+        document.insert(
+            "elapsed_seconds".to_string(),
+            start.elapsed().as_secs_f64().into(),
+        );
+        writeln!(self.output, "{}", serde_json::Value::Object(document))?;
+        result
+    }
+
+    /// Create the executor for the SQL statement and get the SQL to execute; the read-only mode
+    /// and timeout of the configuration are applied to the connection, and results stashed in the
+    /// local database that are referenced by the statement are prepared.
+    async fn sql_executor(&mut self, input: &str) -> Result<(SqlExecutor<'_>, String)> {
+        self.enforce_read_only(input).await?;
+        let mut timeout = self.enforce_timeout().await?;
+        let mut sql = input.to_string();
+        let stash_connection = if self.state.stash.references(input) {
+            let memory_limit = self.configuration.results_memory_limit;
+            sql = self.state.stash.prepare(input, memory_limit).await?;
+            timeout = self.configuration.results_timeout;
+            self.state.stash.connection()
+        } else {
+            None
         };
-        Ok(loop_condition)
+        let connection: &mut dyn Connection = match (stash_connection, &mut self.replacement) {
+            (Some(connection), _) => connection,
+            (None, Some(connection)) => connection.as_mut(),
+            (None, None) => &mut *self.connection,
+        };
+        let executor = SqlExecutor::new(
+            self.configuration,
+            self.formatter_manager,
+            connection,
+            self.output,
+        )
+        .with_timeout(timeout);
+        Ok((executor, sql))
+    }
+
+    /// Get the key of the query in the result cache; returns `None` when the cache is disabled or
+    /// the statement is not a query that can be cached.  Statements that modify the database
+    /// evict the results cached for the connection.
+    fn cache_key(&mut self, sql: &str, limit: usize) -> Option<CacheKey> {
+        if !self.configuration.results_cache
+            || matches!(self.configuration.output, OutputTarget::Table(_))
+            || self.state.stash.references(sql)
+        {
+            return None;
+        }
+        let connection = self.connection();
+        let url = connection.url().clone();
+        match connection.parse_sql(sql) {
+            StatementMetadata::Query => Some(CacheKey::new(
+                &url,
+                sql,
+                limit,
+                &self.configuration.results_columns,
+            )),
+            StatementMetadata::DDL | StatementMetadata::DML => {
+                self.state.cache.evict_url(&url);
+                None
+            }
+            StatementMetadata::Unknown => None,
+        }
+    }
+
+    /// Only allow statements that read from the database in read-only mode; statements that are
+    /// not known to be read-only (including statements that change the read-only setting of the
+    /// session) are refused.
+    async fn enforce_read_only(&mut self, input: &str) -> Result<()> {
+        self.apply_read_only().await?;
+        if self.configuration.read_only && !self.connection().is_read_only_sql(input) {
+            return Err(Error::ReadOnly);
+        }
+        Ok(())
+    }
+
+    /// Make the session of the connection match the read-only setting, for databases that support
+    /// read-only sessions.  The setting is applied as soon as it changes or a new connection is
+    /// opened, so that the session is read-only before any statement is executed.
+    async fn apply_read_only(&mut self) -> Result<()> {
+        let read_only = self.configuration.read_only;
+        if read_only != self.state.read_only {
+            self.connection().set_read_only(read_only).await?;
+            self.state.read_only = read_only;
+        }
+        Ok(())
+    }
+
+    /// Apply the statement timeout of the configuration to the session of the connection.  Returns
+    /// the timeout that the executor must enforce, which is zero when no timeout is configured or
+    /// the database enforces the timeout.
+    async fn enforce_timeout(&mut self) -> Result<Duration> {
+        let timeout = self.configuration.results_timeout;
+        if timeout != self.state.timeout {
+            self.state.timeout_enforced = self.connection().set_timeout(timeout).await?;
+            self.state.timeout = timeout;
+        }
+
+        if self.state.timeout_enforced {
+            Ok(Duration::ZERO)
+        } else {
+            Ok(timeout)
+        }
+    }
+
+    /// Echo the input based on the echo mode of the configuration.
+    fn echo(&mut self, input: &str) -> Result<()> {
+        echo(self.configuration, self.output, input)
     }
 }
 
+/// Write the input to the output when echo is enabled
+fn echo(configuration: &Configuration, output: &mut Output, input: &str) -> Result<()> {
+    let options = FormatterOptions {
+        color: configuration.color,
+        ..Default::default()
+    };
+    let helper = Highlighter::new(&options, "sql");
+    let locale = configuration.locale.as_str();
+    let echo = t!("echo_command", locale = locale);
+    let off = t!("off", locale = locale);
+    let echo_off = format!("{}{} {}", configuration.command_identifier, echo, off);
+
+    if input != echo_off {
+        if configuration.echo == EchoMode::On {
+            let input = helper.highlight(input)?;
+            writeln!(output, "{input}")?;
+        } else if configuration.echo == EchoMode::Prompt {
+            let prompt = t!(
+                "prompt",
+                locale = locale,
+                program_name = configuration.program_name,
+            );
+            let input = helper.highlight(input)?;
+            writeln!(output, "{prompt}{input}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The suffix of a statement that returns all the rows of a query, ignoring the results limit;
+/// e.g. `SELECT * FROM users \all;`
+const FETCH_ALL_SUFFIX: &str = "\\all";
+
+/// Get the statement to execute and the maximum number of rows returned; the fetch all suffix is
+/// removed from the statement, and the limit is zero when the suffix is present.
+fn statement_limit(configuration: &Configuration, input: &str) -> (String, usize) {
+    let trimmed = input.trim_end();
+    let (statement, terminator) = match trimmed.strip_suffix(';') {
+        Some(statement) => (statement.trim_end(), ";"),
+        None => (trimmed, ""),
+    };
+    match statement.strip_suffix(FETCH_ALL_SUFFIX) {
+        Some(statement) => (format!("{}{terminator}", statement.trim_end()), 0),
+        None => (input.to_string(), configuration.results_limit),
+    }
+}
+
+/// Returns true if the database of the URL only exists in the memory of the connection, e.g.
+/// `rusqlite://`, so that other connections to the URL would open a different database.
+fn is_in_memory(url: &str) -> bool {
+    url.contains(":memory:")
+        || Url::parse(url).is_ok_and(|url| {
+            url.host_str().is_none_or(str::is_empty) && matches!(url.path(), "" | "/")
+        })
+}
+
+/// Split the contents into individual commands and SQL statements.  Commands start with the
+/// command identifier and end at the end of the line; SQL statements end with a semicolon.
+///
+/// # Errors
+///
+/// Returns an error if the pattern used to split the contents cannot be created.
+pub fn parse_commands(command_identifier: &str, contents: &str) -> Result<Vec<String>> {
+    let command_identifier = regex::escape(command_identifier);
+    let pattern = format!(r"(?ms)^\s*({command_identifier}.*?|.*?;|.*)\s*$");
+    let regex = Regex::new(pattern.as_str())?;
+    let commands: Vec<String> = regex
+        .find_iter(contents)
+        .map(|mat| mat.as_str().trim().to_string())
+        .collect();
+    Ok(commands)
+}
+
 impl Debug for Executor<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Executor")
@@ -144,9 +644,13 @@ impl Debug for Executor<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::shell::ShellState;
+    use anyhow::anyhow;
     use indoc::indoc;
     use mockall::predicate::eq;
-    use rsql_drivers::MockConnection;
+    use rsql_drivers::Error::IoError;
+    use rsql_drivers::{MemoryQueryResult, MockConnection, QueryResult, Value};
+    use sqlparser::dialect::GenericDialect;
 
     #[tokio::test]
     async fn test_debug() {
@@ -158,12 +662,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let output = &mut Output::default();
 
+        let mut state = ShellState::default();
         let executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             output,
         );
@@ -186,12 +692,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -228,12 +736,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -257,6 +767,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_commands_multibyte_command_identifier() -> anyhow::Result<()> {
+        let contents = "\u{e9}bail on\nSELECT '\u{e9}';\n\u{e9}";
+        let commands = parse_commands("\u{e9}", contents)?;
+
+        assert_eq!(commands.len(), 3);
+        assert_eq!(commands[0], "\u{e9}bail on");
+        assert_eq!(commands[1], "SELECT '\u{e9}';");
+        assert_eq!(commands[2], "\u{e9}");
+        Ok(())
+    }
+
+    #[test]
+    fn test_statement_limit() {
+        let configuration = Configuration {
+            results_limit: 10,
+            ..Default::default()
+        };
+        assert_eq!(
+            statement_limit(&configuration, "SELECT * FROM users;"),
+            ("SELECT * FROM users;".to_string(), 10)
+        );
+        assert_eq!(
+            statement_limit(&configuration, "SELECT * FROM users \\all;"),
+            ("SELECT * FROM users;".to_string(), 0)
+        );
+        assert_eq!(
+            statement_limit(&configuration, "SELECT * FROM users\\all"),
+            ("SELECT * FROM users".to_string(), 0)
+        );
+    }
+
     #[tokio::test]
     async fn test_execute() -> anyhow::Result<()> {
         let mut configuration = Configuration {
@@ -271,12 +813,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -301,12 +845,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -316,6 +862,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_sql_refuses_commands() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            bail_on_error: false,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        let mut output = Output::default();
+
+        let mut state = ShellState::default();
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            &mut connection,
+            &mut output,
+        );
+
+        let result = executor.execute_sql("SELECT 1;\n.bail on").await;
+        assert!(
+            matches!(result, Err(Error::InvalidCommand { command_name }) if command_name == ".bail")
+        );
+        assert!(!configuration.bail_on_error);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_loop_exit() -> anyhow::Result<()> {
         let mut configuration = Configuration::default();
@@ -327,12 +906,14 @@ mod tests {
         connection.expect_close().returning(|| Ok(()));
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -357,12 +938,14 @@ mod tests {
         let mut connection = MockConnection::new();
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -424,14 +1007,19 @@ mod tests {
             .expect_parse_sql()
             .with(eq(input))
             .returning(|_| rsql_drivers::StatementMetadata::DML);
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
         let mut output = Output::default();
 
+        let mut state = ShellState::default();
         let mut executor = Executor::new(
             &mut configuration,
             &command_manager,
             &driver_manager,
             &formatter_manager,
             &history,
+            &mut state,
             &mut connection,
             &mut output,
         );
@@ -457,6 +1045,265 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_command_cached() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            results_cache: true,
+            results_format: "csv".to_string(),
+            results_footer: false,
+            results_limit: 0,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        let query = "SELECT 'foo'";
+        let insert = "INSERT INTO foo";
+        connection
+            .expect_url()
+            .return_const("mock://".to_string());
+        connection
+            .expect_parse_sql()
+            .with(eq(query))
+            .returning(|_| StatementMetadata::Query);
+        connection
+            .expect_parse_sql()
+            .with(eq(insert))
+            .returning(|_| StatementMetadata::DML);
+        connection
+            .expect_query()
+            .with(eq(query))
+            .times(1)
+            .returning(|sql| Ok(sql_query_result(sql)));
+        connection
+            .expect_execute()
+            .with(eq(insert))
+            .returning(|_| Ok(1));
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let mut state = ShellState::default();
+        let mut output = Output::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            &mut connection,
+            &mut output,
+        );
+        executor.execute_command(query).await?;
+        executor.execute_command(query).await?;
+        assert_eq!(executor.state.cache.entries().len(), 1);
+        assert_eq!(executor.state.cache.entries()[0].hits(), 1);
+        assert!(executor.state.last_result.is_some());
+
+        executor.execute_command(insert).await?;
+        assert!(executor.state.cache.entries().is_empty());
+        let output = output.to_string().replace("\r\n", "\n");
+        assert_eq!(output.matches("\"SELECT 'foo'\"").count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_read_only() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            read_only: true,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        let input = "TRUNCATE foo";
+        connection
+            .expect_set_read_only()
+            .with(eq(true))
+            .times(1)
+            .returning(|_| Ok(()));
+        connection
+            .expect_is_read_only_sql()
+            .with(eq(input))
+            .returning(|_| false);
+        let state = &mut ShellState::default();
+        let mut output = Output::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            state,
+            &mut connection,
+            &mut output,
+        );
+
+        let result = executor.execute_command(input).await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+        let result = executor.execute_command(input).await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+        assert!(state.read_only);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_read_only_command() -> anyhow::Result<()> {
+        let mut configuration = Configuration::default();
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_set_read_only()
+            .with(eq(true))
+            .times(1)
+            .returning(|_| Ok(()));
+        let state = &mut ShellState::default();
+        let mut output = Output::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            state,
+            &mut connection,
+            &mut output,
+        );
+
+        let _ = executor.execute(".readonly on").await?;
+        assert!(state.read_only);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_json() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            json: true,
+            read_only: true,
+            echo: EchoMode::On,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_set_read_only()
+            .with(eq(true))
+            .returning(|_| Ok(()));
+        connection
+            .expect_is_read_only_sql()
+            .with(eq("SELECT * FROM foo;"))
+            .returning(|_| true);
+        connection
+            .expect_is_read_only_sql()
+            .with(eq("DELETE FROM foo;"))
+            .returning(|_| false);
+        connection
+            .expect_parse_sql()
+            .with(eq("SELECT * FROM foo;"))
+            .returning(|_| StatementMetadata::Query);
+        connection
+            .expect_parse_sql()
+            .with(eq("DELETE FROM foo;"))
+            .returning(|_| StatementMetadata::DML);
+        connection.expect_query_with_limit().returning(|_, _| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![vec![Value::I64(1)]],
+            )))
+        });
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let state = &mut ShellState::default();
+        let mut output = Output::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            state,
+            &mut connection,
+            &mut output,
+        );
+        executor.execute("SELECT * FROM foo;").await?;
+        let result = executor.execute("DELETE FROM foo;").await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+
+        let documents: Vec<serde_json::Value> = output
+            .to_string()
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0]["statement"], "SELECT * FROM foo;");
+        assert_eq!(documents[0]["success"], true);
+        assert_eq!(
+            documents[0]["results"],
+            serde_json::json!([{"columns": ["id"], "rows": [[1]]}])
+        );
+        assert!(documents[0]["elapsed_seconds"].is_f64());
+        assert_eq!(documents[1]["statement"], "DELETE FROM foo;");
+        assert_eq!(documents[1]["success"], false);
+        assert_eq!(
+            documents[1]["error"],
+            "Statement not allowed in read-only mode"
+        );
+        assert!(state.last_result.is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_enforce_timeout() -> anyhow::Result<()> {
+        let timeout = Duration::from_secs(30);
+        let mut configuration = Configuration {
+            results_timeout: timeout,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_set_timeout()
+            .with(eq(timeout))
+            .times(1)
+            .returning(|_| Ok(false));
+        let state = &mut ShellState::default();
+        let mut output = Output::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            state,
+            &mut connection,
+            &mut output,
+        );
+
+        assert_eq!(executor.enforce_timeout().await?, timeout);
+        assert_eq!(executor.enforce_timeout().await?, timeout);
+        assert_eq!(state.timeout, timeout);
+        assert!(!state.timeout_enforced);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_sql_echo_on() -> anyhow::Result<()> {
         test_execute_command_sql(EchoMode::On).await
@@ -471,4 +1318,306 @@ mod tests {
     async fn test_execute_sql_echo_off() -> anyhow::Result<()> {
         test_execute_command_sql(EchoMode::Off).await
     }
+
+    fn sql_query_result(sql: &str) -> Box<dyn QueryResult> {
+        Box::new(MemoryQueryResult::new(
+            vec!["sql".to_string()],
+            vec![vec![Value::String(sql.to_string())]],
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_execute_parallel() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            color: false,
+            parallel: 2,
+            read_only: true,
+            results_timer: false,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::new();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection.expect_duplicate().times(1).returning(|| {
+            let mut duplicate = MockConnection::new();
+            duplicate
+                .expect_dialect()
+                .returning(|| Box::new(GenericDialect {}));
+            duplicate
+                .expect_match_statement()
+                .returning(|_| StatementMetadata::Query);
+            duplicate
+                .expect_parse_sql()
+                .returning(|_| StatementMetadata::Query);
+            duplicate
+                .expect_execute()
+                .withf(|sql| sql == "SET search_path = app")
+                .times(1)
+                .returning(|_| Ok(0));
+            duplicate
+                .expect_set_read_only()
+                .withf(|read_only| *read_only)
+                .times(1)
+                .returning(|_| Ok(()));
+            duplicate
+                .expect_query_with_limit()
+                .returning(|sql, _| Ok(sql_query_result(sql)));
+            duplicate.expect_statistics().returning(|| None);
+            duplicate.expect_messages().returning(Vec::new);
+            duplicate.expect_reconnected().returning(|| false);
+            duplicate.expect_close().returning(|| Ok(()));
+            Ok(Some(Box::new(duplicate)))
+        });
+        connection
+            .expect_url()
+            .return_const("mock://localhost".to_string());
+        connection
+            .expect_parse_sql()
+            .returning(|_| StatementMetadata::Query);
+        connection.expect_set_read_only().returning(|_| Ok(()));
+        connection.expect_is_read_only_sql().returning(|_| true);
+        connection
+            .expect_query_with_limit()
+            .returning(|sql, _| Ok(sql_query_result(sql)));
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let mut output = Output::default();
+        let mut state = ShellState {
+            session: vec!["SET search_path = app".to_string()],
+            ..Default::default()
+        };
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            &mut connection,
+            &mut output,
+        );
+
+        let result = executor
+            .execute(indoc! {r"
+                SELECT 'a';
+                SELECT 'b';
+                SELECT 'c';
+            "})
+            .await?;
+        assert_eq!(result, LoopCondition::Continue);
+        let execute_output = output.to_string();
+        let a = execute_output.find("SELECT 'a'").expect("a");
+        let b = execute_output.find("SELECT 'b'").expect("b");
+        let c = execute_output.find("SELECT 'c'").expect("c");
+        assert!(a < b && b < c);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_parallel_error() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            color: false,
+            parallel: 2,
+            results_timer: false,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::new();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection.expect_duplicate().returning(|| {
+            let mut duplicate = MockConnection::new();
+            duplicate
+                .expect_parse_sql()
+                .returning(|_| StatementMetadata::Query);
+            duplicate
+                .expect_query_with_limit()
+                .returning(|_, _| Err(IoError(anyhow!("query failed"))));
+            duplicate.expect_close().returning(|| Ok(()));
+            Ok(Some(Box::new(duplicate)))
+        });
+        connection
+            .expect_url()
+            .return_const("mock://localhost".to_string());
+        connection
+            .expect_parse_sql()
+            .returning(|_| StatementMetadata::Query);
+        connection
+            .expect_query_with_limit()
+            .returning(|sql, _| Ok(sql_query_result(sql)));
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let mut output = Output::default();
+        let mut state = ShellState::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            &mut connection,
+            &mut output,
+        );
+
+        let result = executor
+            .execute(indoc! {r"
+                SELECT 'a';
+                SELECT 'b';
+                SELECT 'c';
+            "})
+            .await;
+        assert!(result.is_err());
+        let execute_output = output.to_string();
+        assert!(execute_output.contains("SELECT 'a'"));
+        assert!(!execute_output.contains("SELECT 'c'"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_parallel_in_memory() -> anyhow::Result<()> {
+        let mut configuration = Configuration {
+            color: false,
+            parallel: 2,
+            results_timer: false,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let mut output = Output::default();
+
+        let mut state = ShellState::default();
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            connection.as_mut(),
+            &mut output,
+        );
+
+        let result = executor
+            .execute(indoc! {r"
+                CREATE TABLE t (id INTEGER);
+                INSERT INTO t VALUES (42);
+                SELECT id FROM t;
+                SELECT id + 1 FROM t;
+            "})
+            .await?;
+        assert_eq!(result, LoopCondition::Continue);
+        let execute_output = output.to_string();
+        assert!(execute_output.contains("42"));
+        assert!(execute_output.contains("43"));
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_parallel_queries() {
+        let mut configuration = Configuration {
+            parallel: 4,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_url()
+            .return_const("mock://localhost".to_string());
+        connection.expect_parse_sql().returning(|sql| {
+            if sql.starts_with("SELECT") {
+                StatementMetadata::Query
+            } else {
+                StatementMetadata::DML
+            }
+        });
+        let mut output = Output::default();
+
+        let mut state = ShellState::default();
+        let executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            &mut connection,
+            &mut output,
+        );
+
+        let commands = vec![
+            "SELECT 1;".to_string(),
+            "SELECT 2;".to_string(),
+            ".timer on".to_string(),
+            "SELECT 3;".to_string(),
+        ];
+        assert_eq!(executor.parallel_queries(&commands), 2);
+        assert_eq!(executor.parallel_queries(&commands[2..]), 0);
+        let commands = vec!["INSERT INTO foo".to_string(), "SELECT 1;".to_string()];
+        assert_eq!(executor.parallel_queries(&commands), 0);
+    }
+
+    #[tokio::test]
+    async fn test_parallel_queries_transaction() {
+        let mut configuration = Configuration {
+            parallel: 4,
+            ..Default::default()
+        };
+        let command_manager = CommandManager::default();
+        let driver_manager = DriverManager::default();
+        let formatter_manager = FormatterManager::default();
+        let history = DefaultHistory::new();
+        let mut connection = MockConnection::new();
+        connection
+            .expect_url()
+            .return_const("mock://localhost".to_string());
+        connection
+            .expect_parse_sql()
+            .returning(|_| StatementMetadata::Query);
+        let mut output = Output::default();
+        let mut state = ShellState::default();
+
+        let mut executor = Executor::new(
+            &mut configuration,
+            &command_manager,
+            &driver_manager,
+            &formatter_manager,
+            &history,
+            &mut state,
+            &mut connection,
+            &mut output,
+        );
+
+        let commands = vec!["SELECT 1;".to_string(), "SELECT 2;".to_string()];
+        executor.record_statement("BEGIN;");
+        assert_eq!(executor.parallel_queries(&commands), 0);
+        executor.record_statement("COMMIT;");
+        assert_eq!(executor.parallel_queries(&commands), 2);
+        executor.record_statement("SET search_path = app;");
+        assert_eq!(executor.state.session, vec!["SET search_path = app;"]);
+    }
+
+    #[test]
+    fn test_is_in_memory() {
+        assert!(is_in_memory("rusqlite://"));
+        assert!(is_in_memory("duckdb://"));
+        assert!(is_in_memory("sqlite://:memory:"));
+        assert!(!is_in_memory("rusqlite://test.db"));
+        assert!(!is_in_memory("rusqlite:///tmp/test.db"));
+        assert!(!is_in_memory("postgresql://localhost/db"));
+    }
 }
@@ -2,6 +2,9 @@ mod command;
 mod error;
 mod executor;
 mod sql;
+mod table;
+mod template;
 
 pub use error::{Error, Result};
-pub use executor::Executor;
+pub use executor::{parse_commands, Executor};
+pub use template::{is_template, render_template};
@@ -1,22 +1,209 @@
 use crate::commands::LoopCondition;
-use crate::configuration::Configuration;
-use crate::executors::Result;
+use crate::configuration::{Configuration, OutputTarget};
+use crate::executors::{table, Error, Result};
+use async_trait::async_trait;
+use colored::Colorize;
 use indicatif::ProgressStyle;
-use rsql_drivers::{Connection, LimitQueryResult, StatementMetadata};
+use rsql_drivers::{
+    ColumnMetadata, Connection, LimitQueryResult, MemoryQueryResult, QueryResult, Row,
+    RowBuffer, StatementMetadata, Value,
+};
 use rsql_formatters;
 use rsql_formatters::writers::Output;
-use rsql_formatters::{FormatterManager, Results};
+use rsql_formatters::{Formatter, FormatterManager, FormatterOptions, Results};
+use rust_i18n::t;
+use serde_json::{json, Map};
 use std::fmt;
 use std::fmt::Debug;
-use tracing::{instrument, Span};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info_span, warn, Instrument, Span};
 use tracing_indicatif::span_ext::IndicatifSpanExt;
 
+/// Time spent in each phase of executing a statement; displayed when the verbose timer is enabled.
+#[derive(Debug, Default)]
+struct PhaseTimings {
+    parse: Duration,
+    execute: Duration,
+    fetch: Arc<AtomicU64>,
+}
+
+impl PhaseTimings {
+    /// Get the time spent fetching rows from the driver.
+    fn fetch(&self) -> Duration {
+        Duration::from_nanos(self.fetch.load(Ordering::Relaxed))
+    }
+}
+
+/// Query result that records the time spent fetching rows.
+#[derive(Debug)]
+struct TimedQueryResult {
+    inner: Box<dyn QueryResult>,
+    fetch: Arc<AtomicU64>,
+}
+
+#[async_trait]
+impl QueryResult for TimedQueryResult {
+    async fn columns(&self) -> Vec<String> {
+        self.inner.columns().await
+    }
+
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.inner.columns_metadata().await
+    }
+
+    async fn next(&mut self) -> Option<Row> {
+        let start = Instant::now();
+        let row = self.inner.next().await;
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.fetch.fetch_add(nanos, Ordering::Relaxed);
+        row
+    }
+
+    fn take_error(&mut self) -> Option<rsql_drivers::Error> {
+        self.inner.take_error()
+    }
+}
+
+/// Query result that records the rows as they are fetched so that the result can be displayed
+/// again, or post-processed by commands, without re-running the query.  Rows that exceed the
+/// memory limit are written to a temporary file.  Recording stops, and the recorded rows are
+/// discarded, when the result has more rows than the maximum, so that the rows of large results
+/// are not copied.
+#[derive(Debug)]
+struct RecordingQueryResult {
+    inner: Box<dyn QueryResult>,
+    rows: Arc<Mutex<Option<RowBuffer>>>,
+    remaining: Option<usize>,
+}
+
+#[async_trait]
+impl QueryResult for RecordingQueryResult {
+    async fn columns(&self) -> Vec<String> {
+        self.inner.columns().await
+    }
+
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.inner.columns_metadata().await
+    }
+
+    async fn next(&mut self) -> Option<Row> {
+        let row = self.inner.next().await;
+        if let (Some(row), Some(remaining)) = (&row, self.remaining) {
+            if let Ok(mut rows) = self.rows.lock() {
+                if remaining == 0 {
+                    *rows = None;
+                    self.remaining = None;
+                } else if let Some(rows) = rows.as_mut() {
+                    if let Err(error) = rows.push(row.clone()) {
+                        warn!("Unable to record row: {error}");
+                    }
+                    self.remaining = Some(remaining - 1);
+                }
+            }
+        }
+        row
+    }
+
+    fn take_error(&mut self) -> Option<rsql_drivers::Error> {
+        let error = self.inner.take_error()?;
+        if let Ok(mut rows) = self.rows.lock() {
+            *rows = None;
+        }
+        Some(error)
+    }
+}
+
+/// Query result that reports the number of rows fetched to the progress indicator.
+#[derive(Debug)]
+struct ProgressQueryResult {
+    inner: Box<dyn QueryResult>,
+    span: Span,
+}
+
+#[async_trait]
+impl QueryResult for ProgressQueryResult {
+    async fn columns(&self) -> Vec<String> {
+        self.inner.columns().await
+    }
+
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.inner.columns_metadata().await
+    }
+
+    async fn next(&mut self) -> Option<Row> {
+        let row = self.inner.next().await;
+        if row.is_some() {
+            self.span.pb_inc(1);
+        }
+        row
+    }
+
+    fn take_error(&mut self) -> Option<rsql_drivers::Error> {
+        self.inner.take_error()
+    }
+}
+
+/// Query result that returns only the columns selected with the columns command.
+#[derive(Debug)]
+struct ColumnsQueryResult {
+    inner: Box<dyn QueryResult>,
+    indexes: Vec<usize>,
+}
+
+#[async_trait]
+impl QueryResult for ColumnsQueryResult {
+    async fn columns(&self) -> Vec<String> {
+        let columns = self.inner.columns().await;
+        self.indexes
+            .iter()
+            .filter_map(|index| columns.get(*index).cloned())
+            .collect()
+    }
+
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        let columns_metadata = self.inner.columns_metadata().await;
+        self.indexes
+            .iter()
+            .filter_map(|index| columns_metadata.get(*index).cloned())
+            .collect()
+    }
+
+    async fn next(&mut self) -> Option<Row> {
+        let row = self.inner.next().await?;
+        Some(
+            self.indexes
+                .iter()
+                .filter_map(|index| row.get(*index).cloned())
+                .collect(),
+        )
+    }
+
+    fn take_error(&mut self) -> Option<rsql_drivers::Error> {
+        self.inner.take_error()
+    }
+}
+
+/// Columns and rows recorded for the last query.
+#[derive(Debug)]
+struct Recording {
+    columns: Vec<String>,
+    columns_metadata: Vec<ColumnMetadata>,
+    rows: Arc<Mutex<Option<RowBuffer>>>,
+}
+
 /// A SQL executor for interacting with a database.
 pub(crate) struct SqlExecutor<'a> {
     configuration: &'a Configuration,
     formatter_manager: &'a FormatterManager,
     connection: &'a mut dyn Connection,
     output: &'a mut Output,
+    timings: PhaseTimings,
+    recording: Option<Recording>,
+    limit: usize,
+    timeout: Duration,
 }
 
 /// Implementation for [`SqlExecutor`].
@@ -32,12 +219,247 @@ impl<'a> SqlExecutor<'a> {
             formatter_manager,
             connection,
             output,
+            timings: PhaseTimings::default(),
+            recording: None,
+            limit: configuration.results_limit,
+            timeout: Duration::ZERO,
+        }
+    }
+
+    /// Set the maximum number of rows returned by queries, instead of the limit of the
+    /// configuration; zero returns all the rows.
+    pub(crate) fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Cancel statements that run longer than the timeout, including the time spent fetching and
+    /// formatting the results; zero disables the timeout.
+    pub(crate) fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Take the result of the last query executed; the result contains the rows that were
+    /// displayed.  Returns `None` when the last statement was not a query, or the result had more
+    /// rows than are recorded.
+    pub(crate) fn last_result(&mut self) -> Option<MemoryQueryResult> {
+        let recording = self.recording.take()?;
+        let rows = recording.rows.lock().ok()?.take()?;
+        Some(
+            rows.into_query_result(recording.columns)
+                .with_columns_metadata(recording.columns_metadata),
+        )
+    }
+
+    /// Execute SQL and discard the results; returns the number of rows returned or affected.  The
+    /// statement is cancelled if it runs longer than the timeout, including the time spent
+    /// fetching the rows.
+    pub(crate) async fn execute_discard(&mut self, sql: &str) -> Result<u64> {
+        let deadline = Instant::now() + self.timeout;
+        let timeout = self.timeout;
+        let rows = cancel_after(timeout, deadline, async {
+            let rows = match self.execute_sql(sql, 0).await? {
+                Results::Query(mut query_results) => {
+                    let mut rows = 0;
+                    while query_results.next().await.is_some() {
+                        rows += 1;
+                    }
+                    if let Some(error) = query_results.take_error() {
+                        return Err(error.into());
+                    }
+                    rows
+                }
+                Results::Execute(rows) => rows,
+            };
+            Ok::<u64, Error>(rows)
+        })
+        .await??;
+        self.recording = None;
+        Ok(rows)
+    }
+
+    /// Execute SQL and return the details of the results for the JSON output mode: the columns
+    /// and rows of each result set returned by a query, or the number of rows affected by other
+    /// statements, and the messages reported by the server.  The limit and timeout of the
+    /// configuration apply as when the results are displayed.
+    pub(crate) async fn execute_json(
+        &mut self,
+        sql: &str,
+    ) -> Result<Map<String, serde_json::Value>> {
+        let deadline = Instant::now() + self.timeout;
+        let timeout = self.timeout;
+        let limit = self.limit;
+        cancel_after(timeout, deadline, async {
+            let mut details = Map::new();
+            match self.execute_sql(sql, limit).await? {
+                Results::Query(query_results) => {
+                    let mut results = vec![self.json_result(query_results).await?];
+                    while let Some(mut query_results) = next_result(self.connection).await? {
+                        if limit > 0 {
+                            query_results = Box::new(LimitQueryResult::new(query_results, limit));
+                        }
+                        let query_results = self.select_columns(query_results).await;
+                        results.push(self.json_result(query_results).await?);
+                    }
+                    details.insert("results".to_string(), results.into());
+                }
+                Results::Execute(rows) => {
+                    details.insert("rows_affected".to_string(), rows.into());
+                }
+            }
+            details.insert("messages".to_string(), self.connection.messages().into());
+            Ok::<_, Error>(details)
+        })
+        .await?
+    }
+
+    /// Get the columns and rows of the query results as JSON; binary values are formatted with the
+    /// bytes format of the configuration.
+    async fn json_result(
+        &self,
+        mut query_results: Box<dyn QueryResult>,
+    ) -> Result<serde_json::Value> {
+        let locale = self.configuration.locale.as_str();
+        let bytes_format = self.configuration.results_bytes_format;
+        let columns = query_results.columns().await;
+        let mut rows = Vec::new();
+        while let Some(row) = query_results.next().await {
+            let row: Vec<serde_json::Value> = row
+                .into_iter()
+                .map(|value| match value {
+                    Value::Bytes(bytes) => bytes_format.format(&bytes, locale).into(),
+                    value => serde_json::to_value(value).unwrap_or_default(),
+                })
+                .collect();
+            rows.push(row);
+        }
+        if let Some(error) = query_results.take_error() {
+            return Err(error.into());
         }
+        Ok(json!({ "columns": columns, "rows": rows }))
+    }
+
+    /// Display the cached result of a query instead of executing the query.
+    pub(crate) async fn execute_cached(
+        &mut self,
+        result: MemoryQueryResult,
+    ) -> Result<LoopCondition> {
+        let start = Instant::now();
+        let formatter = self.formatter()?;
+        let mut options = self.configuration.get_formatter_options();
+        let mut results = Results::Query(Box::new(result));
+        options.elapsed = start.elapsed();
+        formatter.format(&options, &mut results, self.output).await?;
+        self.write_warnings(&options);
+        query_error(&mut results)?;
+        Ok(LoopCondition::Continue)
     }
 
     /// Execute SQL.
     pub(crate) async fn execute(&mut self, sql: &str) -> Result<LoopCondition> {
-        let start = std::time::Instant::now();
+        let start = Instant::now();
+        let formatter = self.formatter()?;
+
+        let mut options = self.configuration.get_formatter_options();
+
+        // Table output writes to the database and must receive every row
+        let to_table = matches!(self.configuration.output, OutputTarget::Table(_));
+        if to_table && self.configuration.read_only {
+            return Err(Error::ReadOnly);
+        }
+        let limit = if to_table { 0 } else { self.limit };
+        let timeout = self.timeout;
+        let deadline = start + timeout;
+        let progress = self.configuration.results_progress;
+        let execute_span = if progress {
+            info_span!("execute_sql")
+        } else {
+            Span::none()
+        };
+        let mut results = cancel_after(
+            timeout,
+            deadline,
+            self.execute_sql(sql, limit).instrument(execute_span),
+        )
+        .await??;
+        options.elapsed = start.elapsed();
+        options.statistics = self.connection.statistics();
+        self.write_messages();
+
+        let configuration = self.configuration;
+        if let (OutputTarget::Table(table), Results::Query(query_results)) =
+            (&configuration.output, &mut results)
+        {
+            let rows = cancel_after(
+                timeout,
+                deadline,
+                table::insert_results(self.connection, table, query_results.as_mut()),
+            )
+            .await??;
+            let locale = configuration.locale.as_str();
+            let message = t!("output_table_rows", locale = locale, rows = rows, table = table);
+            writeln!(self.output, "{message}")?;
+            return Ok(LoopCondition::Continue);
+        }
+
+        let fetch_span = if progress {
+            let fetch_span = info_span!("fetch");
+            let rows = t!("progress_rows", locale = self.configuration.locale.as_str());
+            fetch_span.pb_set_style(&ProgressStyle::with_template(&format!(
+                "{{span_child_prefix}}{{spinner}} {{human_pos}} {rows} [{{elapsed}}]"
+            ))?);
+            results = match results {
+                Results::Query(query_results) => Results::Query(Box::new(ProgressQueryResult {
+                    inner: query_results,
+                    span: fetch_span.clone(),
+                })),
+                results @ Results::Execute(_) => results,
+            };
+            fetch_span
+        } else {
+            Span::none()
+        };
+        let format_start = Instant::now();
+        cancel_after(
+            timeout,
+            deadline,
+            formatter
+                .format(&options, &mut results, self.output)
+                .instrument(fetch_span),
+        )
+        .await??;
+        self.write_warnings(&options);
+        query_error(&mut results)?;
+
+        // Statements such as stored procedures may return additional result sets
+        while let Some(mut query_results) =
+            cancel_after(timeout, deadline, next_result(self.connection)).await??
+        {
+            if limit > 0 {
+                query_results = Box::new(LimitQueryResult::new(query_results, limit));
+            }
+            let query_results = self.select_columns(query_results).await;
+            let mut results = Results::Query(query_results);
+            cancel_after(
+                timeout,
+                deadline,
+                formatter.format(&options, &mut results, self.output),
+            )
+            .await??;
+            self.write_warnings(&options);
+            query_error(&mut results)?;
+        }
+
+        if self.configuration.results_timer_verbose {
+            let format = format_start.elapsed().saturating_sub(self.timings.fetch());
+            self.write_timings(format)?;
+        }
+        Ok(LoopCondition::Continue)
+    }
+
+    /// Get the formatter for the results format of the configuration.
+    fn formatter(&self) -> Result<&'a dyn Formatter> {
         let result_format = &self.configuration.results_format;
         let Some(formatter) = self.formatter_manager.get(result_format) else {
             return Err(rsql_formatters::Error::UnknownFormat {
@@ -45,47 +467,169 @@ impl<'a> SqlExecutor<'a> {
             }
             .into());
         };
+        Ok(formatter)
+    }
 
-        let mut options = self.configuration.get_formatter_options();
+    /// Return only the columns of the query results selected with the columns command.
+    async fn select_columns(&self, query_results: Box<dyn QueryResult>) -> Box<dyn QueryResult> {
+        let columns = query_results.columns().await;
+        match self.configuration.results_columns.indexes(&columns) {
+            Some(indexes) => Box::new(ColumnsQueryResult {
+                inner: query_results,
+                indexes,
+            }),
+            None => query_results,
+        }
+    }
 
-        let limit = self.configuration.results_limit;
-        let mut results = self.execute_sql(sql, limit).await?;
-        options.elapsed = start.elapsed();
-        formatter
-            .format(&options, &mut results, self.output)
-            .await?;
-        Ok(LoopCondition::Continue)
+    /// Write the messages reported by the server for the statement to stderr, e.g. `PostgreSQL`
+    /// notices, `MySQL` warnings or SQL Server `PRINT` messages, so they are not part of the
+    /// results; a warning is written when the connection was lost and re-established.
+    fn write_messages(&mut self) {
+        let mut messages = self.connection.messages();
+        if self.connection.reconnected() {
+            let locale = self.configuration.locale.as_str();
+            messages.push(t!("reconnected", locale = locale).to_string());
+        }
+        for message in messages {
+            if self.configuration.color {
+                eprintln!("{}", message.yellow());
+            } else {
+                eprintln!("{message}");
+            }
+        }
+    }
+
+    /// Write the warnings raised by the formatter to stderr, like the messages of the connection.
+    fn write_warnings(&self, options: &FormatterOptions) {
+        for warning in options.take_warnings() {
+            if self.configuration.color {
+                eprintln!("{}", warning.yellow());
+            } else {
+                eprintln!("{warning}");
+            }
+        }
+    }
+
+    /// Write the time spent in each phase of executing the statement.
+    fn write_timings(&mut self, format: Duration) -> Result<()> {
+        let locale = self.configuration.locale.as_str();
+        let timings = t!(
+            "timer_phases",
+            locale = locale,
+            parse = format!("{:?}", self.timings.parse),
+            execute = format!("{:?}", self.timings.execute),
+            fetch = format!("{:?}", self.timings.fetch()),
+            format = format!("{format:?}"),
+        )
+        .to_string();
+
+        if self.configuration.color {
+            writeln!(self.output, "{}", timings.dimmed())?;
+        } else {
+            writeln!(self.output, "{timings}")?;
+        }
+        Ok(())
     }
 
     /// Execute the SQL and return the results.
     ///
     /// This function is split out so that it can be instrumented and a visual progress indicator
-    /// can be shown without leaving artifacts in the output when the results are formatted.
-    #[instrument(skip(self, sql, limit))]
+    /// showing the elapsed time can be shown without leaving artifacts in the output when the
+    /// results are formatted.
     async fn execute_sql(&mut self, sql: &str, limit: usize) -> Result<Results> {
         Span::current().pb_set_style(&ProgressStyle::with_template(
-            "{span_child_prefix}{spinner}",
+            "{span_child_prefix}{spinner} [{elapsed}]",
         )?);
+        self.timings = PhaseTimings::default();
+        self.recording = None;
+        let parse_start = Instant::now();
         let statement_metadata = self.connection.parse_sql(sql);
         let is_select = matches!(statement_metadata, StatementMetadata::Query);
+        self.timings.parse = parse_start.elapsed();
 
+        let execute_start = Instant::now();
         let results = if is_select {
-            let query_results = self.connection.query(sql).await?;
+            // Limit the rows returned by the database, rather than fetching all the rows
+            let mut query_results = match limit {
+                0 => self.connection.query(sql).await?,
+                limit => self.connection.query_with_limit(sql, limit).await?,
+            };
+            self.timings.execute = execute_start.elapsed();
+            if self.configuration.results_timer_verbose {
+                query_results = Box::new(TimedQueryResult {
+                    inner: query_results,
+                    fetch: Arc::clone(&self.timings.fetch),
+                });
+            }
 
-            if limit == 0 {
-                Results::Query(query_results)
-            } else {
-                let limit_query_result = LimitQueryResult::new(query_results, limit);
-                Results::Query(Box::new(limit_query_result))
+            if limit > 0 {
+                query_results = Box::new(LimitQueryResult::new(query_results, limit));
             }
+            query_results = self.select_columns(query_results).await;
+
+            let record_rows = self.configuration.results_record_rows;
+            if record_rows > 0 {
+                let recording = Recording {
+                    columns: query_results.columns().await,
+                    columns_metadata: query_results.columns_metadata().await,
+                    rows: Arc::new(Mutex::new(Some(RowBuffer::new(
+                        self.configuration.results_memory_limit,
+                    )))),
+                };
+                query_results = Box::new(RecordingQueryResult {
+                    inner: query_results,
+                    rows: Arc::clone(&recording.rows),
+                    remaining: Some(record_rows),
+                });
+                self.recording = Some(recording);
+            }
+            Results::Query(query_results)
         } else {
-            Results::Execute(self.connection.execute(sql).await?)
+            let rows = self.connection.execute(sql).await?;
+            self.timings.execute = execute_start.elapsed();
+            Results::Execute(rows)
         };
 
         Ok(results)
     }
 }
 
+/// Get the next result set of the last query; connections that do not return multiple result sets
+/// have no further result sets.
+async fn next_result(connection: &mut dyn Connection) -> Result<Option<Box<dyn QueryResult>>> {
+    match connection.as_multiple_results() {
+        Some(multiple_results) => Ok(multiple_results.next_result().await?),
+        None => Ok(None),
+    }
+}
+
+/// Return the error that ended the rows of the query results early, if any, so that a truncated
+/// result fails the statement instead of being reported as complete.
+fn query_error(results: &mut Results) -> Result<()> {
+    if let Results::Query(query_results) = results {
+        if let Some(error) = query_results.take_error() {
+            return Err(error.into());
+        }
+    }
+    Ok(())
+}
+
+/// Wait for the future to complete; the future is cancelled if it has not completed by the
+/// deadline, unless the timeout is zero.
+async fn cancel_after<F: Future>(
+    timeout: Duration,
+    deadline: Instant,
+    future: F,
+) -> Result<F::Output> {
+    if timeout.is_zero() {
+        return Ok(future.await);
+    }
+    tokio::time::timeout_at(deadline.into(), future)
+        .await
+        .map_err(|_| Error::Timeout(timeout))
+}
+
 impl Debug for SqlExecutor<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("SqlExecutor")
@@ -99,9 +643,11 @@ impl Debug for SqlExecutor<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::configuration::Configuration;
+    use crate::configuration::{ColumnSelection, Configuration};
     use mockall::predicate::eq;
-    use rsql_drivers::{MemoryQueryResult, MockConnection};
+    use rsql_drivers::{
+        MemoryQueryResult, MockCapableConnection, MockConnection, MockMultipleResults,
+    };
 
     #[tokio::test]
     async fn test_debug() {
@@ -153,6 +699,9 @@ mod tests {
             .expect_parse_sql()
             .with(eq(sql))
             .returning(|_| rsql_drivers::StatementMetadata::Unknown);
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
         let connection = &mut connection as &mut dyn Connection;
         let mut output = Output::default();
 
@@ -167,6 +716,482 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_discard() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM users";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection.expect_query().returning(|_| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![
+                    vec![rsql_drivers::Value::I64(1)],
+                    vec![rsql_drivers::Value::I64(2)],
+                ],
+            )))
+        });
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let rows = executor.execute_discard(sql).await?;
+
+        assert_eq!(rows, 2);
+        assert!(executor.last_result().is_none());
+        assert!(output.to_string().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_json() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM users";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection.expect_query_with_limit().returning(|_, _| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string(), "data".to_string()],
+                vec![vec![Value::I64(1), Value::Bytes(vec![1, 2, 3])]],
+            )))
+        });
+        connection
+            .expect_messages()
+            .returning(|| vec!["NOTICE: done".to_string()]);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let details = executor.execute_json(sql).await?;
+
+        assert_eq!(
+            serde_json::Value::Object(details),
+            json!({
+                "results": [{"columns": ["id", "data"], "rows": [[1, "AQID"]]}],
+                "messages": ["NOTICE: done"],
+            })
+        );
+        assert!(executor.last_result().is_some());
+        assert!(output.to_string().is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_json_rows_affected() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "DELETE FROM users";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::DML);
+        connection
+            .expect_execute()
+            .with(eq(sql))
+            .returning(|_| Ok(3));
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let details = executor.execute_json(sql).await?;
+
+        assert_eq!(
+            serde_json::Value::Object(details),
+            json!({"rows_affected": 3, "messages": []})
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_multiple_results() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            color: false,
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockCapableConnection::default();
+        let sql = "EXEC report";
+        connection
+            .connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection
+            .connection
+            .expect_query_with_limit()
+            .returning(|_, _| {
+                Ok(Box::new(MemoryQueryResult::new(
+                    vec!["a".to_string()],
+                    vec![vec![rsql_drivers::Value::I64(1)]],
+                )))
+            });
+        connection.connection.expect_statistics().returning(|| None);
+        connection
+            .connection
+            .expect_messages()
+            .returning(|| vec!["Report started".to_string()]);
+        connection
+            .connection
+            .expect_reconnected()
+            .returning(|| false);
+        let mut results = vec![MemoryQueryResult::new(
+            vec!["b".to_string()],
+            vec![vec![rsql_drivers::Value::I64(2)]],
+        )];
+        let mut multiple_results = MockMultipleResults::new();
+        multiple_results.expect_next_result().returning(move || {
+            Ok(results
+                .pop()
+                .map(|result| Box::new(result) as Box<dyn QueryResult>))
+        });
+        connection.multiple_results = Some(multiple_results);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let result = executor.execute(sql).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert_eq!(
+            output.to_string().replace("\r\n", "\n"),
+            "\"a\"\n1\n\"b\"\n2\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_progress_disabled() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            color: false,
+            results_progress: false,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM foo";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection.expect_query_with_limit().returning(|_, _| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![vec![rsql_drivers::Value::I64(1)]],
+            )))
+        });
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let result = executor.execute(sql).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        assert!(output.to_string().contains("1 row"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_progress_query_result() {
+        let mut query_result = ProgressQueryResult {
+            inner: Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![
+                    vec![rsql_drivers::Value::I64(1)],
+                    vec![rsql_drivers::Value::I64(2)],
+                ],
+            )),
+            span: info_span!("fetch"),
+        };
+
+        assert_eq!(query_result.columns().await, vec!["id".to_string()]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![rsql_drivers::Value::I64(1)])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![rsql_drivers::Value::I64(2)])
+        );
+        assert_eq!(query_result.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_execute_timer_verbose() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            color: false,
+            results_timer_verbose: true,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM foo";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection.expect_query_with_limit().returning(|_, _| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![vec![rsql_drivers::Value::I64(1)]],
+            )))
+        });
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let result = executor.execute(sql).await?;
+
+        assert_eq!(result, LoopCondition::Continue);
+        let execute_output = output.to_string();
+        assert!(execute_output.contains("parse "));
+        assert!(execute_output.contains(", execute "));
+        assert!(execute_output.contains(", fetch "));
+        assert!(execute_output.contains(", format "));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_last_result() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM foo";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection.expect_query_with_limit().returning(|_, _| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![
+                    vec![rsql_drivers::Value::I64(1)],
+                    vec![rsql_drivers::Value::I64(2)],
+                ],
+            )))
+        });
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let _ = executor.execute(sql).await?;
+
+        let mut last_result = executor.last_result().expect("last result");
+        assert_eq!(last_result.columns().await, vec!["id".to_string()]);
+        assert!(last_result.columns_metadata().await[0].is_numeric());
+        assert_eq!(
+            last_result.next().await,
+            Some(vec![rsql_drivers::Value::I64(1)])
+        );
+        assert_eq!(
+            last_result.next().await,
+            Some(vec![rsql_drivers::Value::I64(2)])
+        );
+        assert!(last_result.next().await.is_none());
+        assert!(executor.last_result().is_none());
+
+        Ok(())
+    }
+
+    async fn test_execute_record_rows(record_rows: usize) -> anyhow::Result<bool> {
+        let configuration = Configuration {
+            results_record_rows: record_rows,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM foo";
+        connection
+            .expect_parse_sql()
+            .with(eq(sql))
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection.expect_query_with_limit().returning(|_, _| {
+            Ok(Box::new(MemoryQueryResult::new(
+                vec!["id".to_string()],
+                vec![
+                    vec![rsql_drivers::Value::I64(1)],
+                    vec![rsql_drivers::Value::I64(2)],
+                ],
+            )))
+        });
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let mut output = Output::default();
+
+        let mut executor =
+            SqlExecutor::new(&configuration, &formatter_manager, connection, &mut output);
+        let _ = executor.execute(sql).await?;
+        let recorded = executor.last_result().is_some();
+        assert!(output.to_string().contains("2 rows"));
+        Ok(recorded)
+    }
+
+    #[tokio::test]
+    async fn test_execute_record_rows_limit() -> anyhow::Result<()> {
+        assert!(test_execute_record_rows(2).await?);
+        assert!(!test_execute_record_rows(1).await?);
+        assert!(!test_execute_record_rows(0).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_table() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            output: OutputTarget::Table("results".to_string()),
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let driver_manager = rsql_drivers::DriverManager::default();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let mut output = Output::default();
+
+        let mut executor = SqlExecutor::new(
+            &configuration,
+            &formatter_manager,
+            connection.as_mut(),
+            &mut output,
+        );
+        let _ = executor.execute("SELECT 1 AS id, 'foo' AS name").await?;
+        assert!(executor.last_result().is_some());
+        assert_eq!(output.to_string(), "1 rows inserted into results\n");
+
+        let mut query_result = connection.query("SELECT id, name FROM results").await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![
+                rsql_drivers::Value::I64(1),
+                rsql_drivers::Value::String("foo".to_string())
+            ])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_table_ignores_limit() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            output: OutputTarget::Table("results".to_string()),
+            results_limit: 2,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let driver_manager = rsql_drivers::DriverManager::default();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let mut output = Output::default();
+
+        let mut executor = SqlExecutor::new(
+            &configuration,
+            &formatter_manager,
+            connection.as_mut(),
+            &mut output,
+        );
+        let _ = executor
+            .execute("SELECT 1 AS id UNION ALL SELECT 2 UNION ALL SELECT 3")
+            .await?;
+        assert_eq!(output.to_string(), "3 rows inserted into results\n");
+
+        let mut query_result = connection.query("SELECT COUNT(*) FROM results").await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![rsql_drivers::Value::I64(3)])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_output_table_read_only() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            output: OutputTarget::Table("results".to_string()),
+            read_only: true,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let driver_manager = rsql_drivers::DriverManager::default();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let mut output = Output::default();
+
+        let mut executor = SqlExecutor::new(
+            &configuration,
+            &formatter_manager,
+            connection.as_mut(),
+            &mut output,
+        );
+        let result = executor.execute("SELECT 1 AS id").await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+
+        let mut query_result = connection
+            .query("SELECT COUNT(*) FROM sqlite_master WHERE name = 'results'")
+            .await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![rsql_drivers::Value::I64(0)])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_columns() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            results_columns: ColumnSelection::Hide(vec!["NAME".to_string()]),
+            results_format: "csv".to_string(),
+            results_footer: false,
+            ..Default::default()
+        };
+        let formatter_manager = FormatterManager::default();
+        let driver_manager = rsql_drivers::DriverManager::default();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let mut output = Output::default();
+
+        let mut executor = SqlExecutor::new(
+            &configuration,
+            &formatter_manager,
+            connection.as_mut(),
+            &mut output,
+        );
+        let _ = executor
+            .execute("SELECT 1 AS id, 'foo' AS name, 'a@b.c' AS email")
+            .await?;
+        let last_result = executor.last_result().expect("last result");
+        assert_eq!(last_result.columns().await, vec!["id", "email"]);
+        assert_eq!(output.to_string(), "\"id\",\"email\"\n1,\"a@b.c\"\n");
+        connection.close().await?;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_results_query() -> anyhow::Result<()> {
         let configuration = Configuration::default();
@@ -179,8 +1204,9 @@ mod tests {
             .with(eq(sql))
             .returning(|_| rsql_drivers::StatementMetadata::Query);
         connection
-            .expect_query()
-            .returning(|_| Ok(Box::<MemoryQueryResult>::default()));
+            .expect_query_with_limit()
+            .with(eq(sql), eq(limit))
+            .returning(|_, _| Ok(Box::<MemoryQueryResult>::default()));
         let connection = &mut connection as &mut dyn Connection;
         let output = &mut Output::default();
 
@@ -192,6 +1218,49 @@ mod tests {
         Ok(())
     }
 
+    /// Query result that waits before returning each row
+    #[derive(Debug)]
+    struct SlowQueryResult;
+
+    #[async_trait]
+    impl QueryResult for SlowQueryResult {
+        async fn columns(&self) -> Vec<String> {
+            vec!["id".to_string()]
+        }
+
+        async fn next(&mut self) -> Option<Row> {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_timeout() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let formatter_manager = FormatterManager::default();
+        let mut connection = MockConnection::new();
+        let sql = "SELECT * FROM foo";
+        connection
+            .expect_parse_sql()
+            .returning(|_| rsql_drivers::StatementMetadata::Query);
+        connection
+            .expect_query_with_limit()
+            .returning(|_, _| Ok(Box::new(SlowQueryResult)));
+        connection.expect_statistics().returning(|| None);
+        connection.expect_messages().returning(Vec::new);
+        connection.expect_reconnected().returning(|| false);
+        let connection = &mut connection as &mut dyn Connection;
+        let output = &mut Output::default();
+        let timeout = Duration::from_millis(10);
+
+        let mut executor = SqlExecutor::new(&configuration, &formatter_manager, connection, output)
+            .with_timeout(timeout);
+        let result = executor.execute(sql).await;
+
+        assert!(matches!(result, Err(Error::Timeout(value)) if value == timeout));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_execute_results_execute() -> anyhow::Result<()> {
         let configuration = Configuration::default();
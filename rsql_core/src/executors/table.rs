@@ -0,0 +1,432 @@
+use crate::executors::Result;
+use crate::shell::quote_identifier;
+use rsql_drivers::{Connection, QueryResult, Row, Value};
+use sqlparser::dialect::{
+    Dialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
+use std::fmt::Write;
+
+/// The number of rows inserted by each statement when results are written to a table
+const INSERT_BATCH_SIZE: usize = 100;
+
+/// Insert the rows of the query result into the table on the connection as they are fetched, in
+/// batches of `INSERT_BATCH_SIZE` rows; the table is created, with column types inferred from the
+/// values of the first batch, when it does not exist.  Returns the number of rows inserted.
+pub(crate) async fn insert_results(
+    connection: &mut dyn Connection,
+    table: &str,
+    query_result: &mut dyn QueryResult,
+) -> Result<u64> {
+    let columns = query_result.columns().await;
+    let column_names: Vec<String> = {
+        let dialect = connection.dialect();
+        columns
+            .iter()
+            .map(|column| quote_identifier(dialect.as_ref(), column))
+            .collect()
+    };
+    let mut batch = next_batch(query_result).await?;
+
+    if !table_exists(connection, table).await? {
+        let definitions = {
+            let dialect = connection.dialect();
+            column_names
+                .iter()
+                .enumerate()
+                .map(|(index, column)| {
+                    let kind = column_kind(&batch, index);
+                    format!("{column} {}", column_type(dialect.as_ref(), &kind))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        connection
+            .execute(&format!("CREATE TABLE {table} ({definitions})"))
+            .await?;
+    }
+
+    let column_names = column_names.join(", ");
+    let mut inserted = 0;
+    while !batch.is_empty() {
+        let values = {
+            let dialect = connection.dialect();
+            batch
+                .iter()
+                .map(|row| {
+                    let values = row
+                        .iter()
+                        .map(|value| literal(dialect.as_ref(), value))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("({values})")
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        connection
+            .execute(&format!(
+                "INSERT INTO {table} ({column_names}) VALUES {values}"
+            ))
+            .await?;
+        inserted += batch.len() as u64;
+        batch = next_batch(query_result).await?;
+    }
+    Ok(inserted)
+}
+
+/// Fetch the next batch of rows of the query result; the batch is empty once every row has been
+/// fetched.
+async fn next_batch(query_result: &mut dyn QueryResult) -> Result<Vec<Row>> {
+    let mut batch = Vec::with_capacity(INSERT_BATCH_SIZE);
+    while batch.len() < INSERT_BATCH_SIZE {
+        let Some(row) = query_result.next().await else {
+            break;
+        };
+        batch.push(row);
+    }
+    if let Some(error) = query_result.take_error() {
+        return Err(error.into());
+    }
+    Ok(batch)
+}
+
+/// Returns true if the table exists; the table name may be qualified with the schema name.
+async fn table_exists(connection: &mut dyn Connection, table: &str) -> Result<bool> {
+    let metadata = connection.metadata().await?;
+    let unquote = |name: &str| name.trim_matches(['"', '`', '[', ']']).to_string();
+    let (schema, table) = match table.rsplit_once('.') {
+        Some((schema, table)) => (metadata.get(unquote(schema)), unquote(table)),
+        None => (metadata.current_schema(), unquote(table)),
+    };
+    let exists = schema.is_some_and(|schema| {
+        schema
+            .tables()
+            .iter()
+            .any(|schema_table| schema_table.name().eq_ignore_ascii_case(&table))
+    });
+    Ok(exists)
+}
+
+/// The kind of values of a column, used to choose the SQL type of the column
+#[derive(Debug, PartialEq)]
+enum ColumnKind {
+    Boolean,
+    Integer,
+    Double,
+    Decimal { precision: usize, scale: usize },
+    Date,
+    Time,
+    DateTime,
+    Bytes,
+    Text,
+}
+
+/// Get the kind of the values of the column; columns without values are text.  Integers that do
+/// not fit in a `BIGINT` and decimal values are exact decimals, with the precision and scale
+/// needed for the values.
+fn column_kind(rows: &[Row], index: usize) -> ColumnKind {
+    let values: Vec<&Value> = rows
+        .iter()
+        .filter_map(|row| row.get(index))
+        .filter(|value| !value.is_null())
+        .collect();
+    let all = |matches: fn(&Value) -> bool| {
+        !values.is_empty() && values.iter().all(|value| matches(value))
+    };
+
+    if all(|value| matches!(value, Value::Bool(_))) {
+        ColumnKind::Boolean
+    } else if all(|value| {
+        matches!(
+            value,
+            Value::I8(_)
+                | Value::I16(_)
+                | Value::I32(_)
+                | Value::I64(_)
+                | Value::U8(_)
+                | Value::U16(_)
+                | Value::U32(_)
+        )
+    }) {
+        ColumnKind::Integer
+    } else if all(|value| value.is_numeric() && !matches!(value, Value::F32(_) | Value::F64(_))) {
+        let (integer_digits, scale) =
+            values
+                .iter()
+                .fold((1, 0), |(integer_digits, scale), value| {
+                    let value = value.to_string();
+                    let (integer, fraction) = value
+                        .trim_start_matches('-')
+                        .split_once('.')
+                        .unwrap_or((value.trim_start_matches('-'), ""));
+                    let digits = integer.trim_start_matches('0').len();
+                    (integer_digits.max(digits), scale.max(fraction.len()))
+                });
+        ColumnKind::Decimal {
+            precision: integer_digits + scale,
+            scale,
+        }
+    } else if all(Value::is_numeric) {
+        ColumnKind::Double
+    } else if all(|value| matches!(value, Value::Date(_))) {
+        ColumnKind::Date
+    } else if all(|value| matches!(value, Value::Time(_))) {
+        ColumnKind::Time
+    } else if all(|value| matches!(value, Value::DateTime(_))) {
+        ColumnKind::DateTime
+    } else if all(|value| matches!(value, Value::Bytes(_))) {
+        ColumnKind::Bytes
+    } else {
+        ColumnKind::Text
+    }
+}
+
+/// Get the SQL column type of the dialect for the kind of values; exact decimals that do not fit
+/// in the decimal type of the database are stored as text so that no precision is lost.
+fn column_type(dialect: &dyn Dialect, kind: &ColumnKind) -> String {
+    let sql_server = dialect.is::<MsSqlDialect>();
+    let mysql = dialect.is::<MySqlDialect>();
+    let postgresql = dialect.is::<PostgreSqlDialect>();
+    let column_type = match kind {
+        ColumnKind::Boolean if sql_server => "BIT",
+        ColumnKind::Boolean => "BOOLEAN",
+        ColumnKind::Integer => "BIGINT",
+        ColumnKind::Double => "DOUBLE PRECISION",
+        ColumnKind::Decimal { .. } if postgresql => "NUMERIC",
+        ColumnKind::Decimal { precision, scale } => {
+            let max_precision = if mysql { 65 } else { 38 };
+            let max_scale = if mysql { 30 } else { max_precision };
+            let decimal = !dialect.is::<SQLiteDialect>() && !dialect.is::<GenericDialect>();
+            if decimal && *precision <= max_precision && *scale <= max_scale {
+                return format!("DECIMAL({precision}, {scale})");
+            }
+            return column_type(dialect, &ColumnKind::Text);
+        }
+        ColumnKind::Date => "DATE",
+        ColumnKind::Time => "TIME",
+        ColumnKind::DateTime if sql_server => "DATETIME2",
+        ColumnKind::DateTime if mysql => "DATETIME(6)",
+        ColumnKind::DateTime => "TIMESTAMP",
+        ColumnKind::Bytes if sql_server => "VARBINARY(MAX)",
+        ColumnKind::Bytes if mysql => "LONGBLOB",
+        ColumnKind::Bytes if postgresql => "BYTEA",
+        ColumnKind::Bytes => "BLOB",
+        ColumnKind::Text if sql_server => "NVARCHAR(MAX)",
+        ColumnKind::Text if mysql => "LONGTEXT",
+        ColumnKind::Text => "TEXT",
+    };
+    column_type.to_string()
+}
+
+/// Convert the value to a SQL literal of the dialect; quotes are doubled in strings, and
+/// backslashes are also escaped for databases that treat them as escape characters.  Wide
+/// integers and decimals are quoted so that the database does not read them as floating point
+/// numbers.
+fn literal(dialect: &dyn Dialect, value: &Value) -> String {
+    let sql_server = dialect.is::<MsSqlDialect>();
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Bool(value) if sql_server => u8::from(*value).to_string(),
+        Value::Bool(value) => value.to_string().to_uppercase(),
+        Value::Bytes(bytes) => {
+            let hex = bytes.iter().fold(String::new(), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02X}");
+                hex
+            });
+            if sql_server {
+                format!("0x{hex}")
+            } else if dialect.is::<PostgreSqlDialect>() {
+                format!("DECODE('{hex}', 'hex')")
+            } else {
+                format!("X'{hex}'")
+            }
+        }
+        Value::F32(_) | Value::F64(_) => match value.to_f64() {
+            Some(number) if number.is_finite() => value.to_string(),
+            _ => "NULL".to_string(),
+        },
+        Value::I128(_) | Value::U64(_) | Value::U128(_) | Value::Decimal(_) => {
+            format!("'{value}'")
+        }
+        value if value.is_numeric() => value.to_string(),
+        value => {
+            let mut string = value.to_string();
+            if dialect.supports_string_literal_backslash_escape() {
+                string = string.replace('\\', "\\\\");
+            }
+            let string = string.replace('\'', "''");
+            if sql_server {
+                format!("N'{string}'")
+            } else {
+                format!("'{string}'")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsql_drivers::{DriverManager, MemoryQueryResult};
+
+    #[test]
+    fn test_column_kind() {
+        let rows = vec![
+            vec![
+                Value::I32(1),
+                Value::F64(1.5),
+                Value::Bool(true),
+                Value::String("foo".to_string()),
+                Value::Null,
+                Value::U64(u64::MAX),
+                Value::I128(-12_345),
+            ],
+            vec![
+                Value::I64(2),
+                Value::I32(2),
+                Value::Null,
+                Value::I32(3),
+                Value::Null,
+                Value::U8(1),
+                Value::I64(123_456),
+            ],
+        ];
+        assert_eq!(column_kind(&rows, 0), ColumnKind::Integer);
+        assert_eq!(column_kind(&rows, 1), ColumnKind::Double);
+        assert_eq!(column_kind(&rows, 2), ColumnKind::Boolean);
+        assert_eq!(column_kind(&rows, 3), ColumnKind::Text);
+        assert_eq!(column_kind(&rows, 4), ColumnKind::Text);
+        assert_eq!(
+            column_kind(&rows, 5),
+            ColumnKind::Decimal {
+                precision: 20,
+                scale: 0
+            }
+        );
+        assert_eq!(
+            column_kind(&rows, 6),
+            ColumnKind::Decimal {
+                precision: 6,
+                scale: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_column_type() {
+        let decimal = ColumnKind::Decimal {
+            precision: 20,
+            scale: 2,
+        };
+        let wide_decimal = ColumnKind::Decimal {
+            precision: 39,
+            scale: 0,
+        };
+        let postgresql = PostgreSqlDialect {};
+        assert_eq!(column_type(&postgresql, &ColumnKind::Boolean), "BOOLEAN");
+        assert_eq!(column_type(&postgresql, &decimal), "NUMERIC");
+        assert_eq!(column_type(&postgresql, &ColumnKind::DateTime), "TIMESTAMP");
+        assert_eq!(column_type(&postgresql, &ColumnKind::Bytes), "BYTEA");
+        let sql_server = MsSqlDialect {};
+        assert_eq!(column_type(&sql_server, &ColumnKind::Boolean), "BIT");
+        assert_eq!(column_type(&sql_server, &decimal), "DECIMAL(20, 2)");
+        assert_eq!(column_type(&sql_server, &wide_decimal), "NVARCHAR(MAX)");
+        assert_eq!(column_type(&sql_server, &ColumnKind::DateTime), "DATETIME2");
+        assert_eq!(
+            column_type(&sql_server, &ColumnKind::Bytes),
+            "VARBINARY(MAX)"
+        );
+        let mysql = MySqlDialect {};
+        assert_eq!(column_type(&mysql, &wide_decimal), "DECIMAL(39, 0)");
+        assert_eq!(column_type(&mysql, &ColumnKind::DateTime), "DATETIME(6)");
+        assert_eq!(column_type(&mysql, &ColumnKind::Bytes), "LONGBLOB");
+        let sqlite = SQLiteDialect {};
+        assert_eq!(column_type(&sqlite, &ColumnKind::Integer), "BIGINT");
+        assert_eq!(column_type(&sqlite, &decimal), "TEXT");
+        assert_eq!(column_type(&sqlite, &ColumnKind::Bytes), "BLOB");
+    }
+
+    #[test]
+    fn test_literal() {
+        let sqlite = SQLiteDialect {};
+        assert_eq!(literal(&sqlite, &Value::Null), "NULL");
+        assert_eq!(literal(&sqlite, &Value::Bool(false)), "FALSE");
+        assert_eq!(literal(&sqlite, &Value::Bytes(vec![1, 171])), "X'01AB'");
+        assert_eq!(literal(&sqlite, &Value::F64(f64::NAN)), "NULL");
+        assert_eq!(literal(&sqlite, &Value::I64(42)), "42");
+        assert_eq!(
+            literal(&sqlite, &Value::U64(u64::MAX)),
+            "'18446744073709551615'"
+        );
+        assert_eq!(
+            literal(&sqlite, &Value::String("it's \\".to_string())),
+            "'it''s \\'"
+        );
+    }
+
+    #[test]
+    fn test_literal_dialect() {
+        let value = Value::String("it\\'s".to_string());
+        assert_eq!(literal(&MySqlDialect {}, &value), "'it\\\\''s'");
+        assert_eq!(literal(&PostgreSqlDialect {}, &value), "'it\\''s'");
+        assert_eq!(literal(&MsSqlDialect {}, &value), "N'it\\''s'");
+        assert_eq!(literal(&MsSqlDialect {}, &Value::Bool(true)), "1");
+        assert_eq!(
+            literal(&MsSqlDialect {}, &Value::Bytes(vec![1, 171])),
+            "0x01AB"
+        );
+        assert_eq!(
+            literal(&PostgreSqlDialect {}, &Value::Bytes(vec![1, 171])),
+            "DECODE('01AB', 'hex')"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_insert_results() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let columns = vec!["id".to_string(), "Name".to_string()];
+        let rows = vec![
+            vec![Value::I64(1), Value::String("foo".to_string())],
+            vec![Value::I64(2), Value::Null],
+        ];
+
+        let mut query_result = MemoryQueryResult::new(columns.clone(), rows.clone());
+        let inserted = insert_results(connection.as_mut(), "users", &mut query_result).await?;
+        assert_eq!(inserted, 2);
+        let mut query_result = MemoryQueryResult::new(columns, rows[..1].to_vec());
+        let inserted = insert_results(connection.as_mut(), "users", &mut query_result).await?;
+        assert_eq!(inserted, 1);
+
+        let mut query_result = connection
+            .query("SELECT COUNT(*), COUNT(\"Name\") FROM users")
+            .await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(3), Value::I64(2)])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_insert_results_batches() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect("rusqlite://").await?;
+        let rows = (0..250).map(|id| vec![Value::I64(id)]).collect();
+        let mut query_result = MemoryQueryResult::new(vec!["id".to_string()], rows);
+
+        let inserted = insert_results(connection.as_mut(), "ids", &mut query_result).await?;
+        assert_eq!(inserted, 250);
+
+        let mut query_result = connection
+            .query("SELECT COUNT(*), MAX(id) FROM ids")
+            .await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(250), Value::I64(249)])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+}
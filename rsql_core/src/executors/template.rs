@@ -0,0 +1,538 @@
+use crate::executors::{Error, Result};
+use std::collections::BTreeMap;
+
+/// The file extensions of SQL files that are rendered as templates before they are executed
+const TEMPLATE_EXTENSIONS: [&str; 2] = [".j2", ".jinja"];
+
+/// Returns true if the file is a SQL template, e.g. `report.sql.j2`
+#[must_use]
+pub fn is_template(file: &str) -> bool {
+    let file = file.to_lowercase();
+    TEMPLATE_EXTENSIONS
+        .iter()
+        .any(|extension| file.ends_with(extension))
+}
+
+/// Render a SQL template with the variables.  Templates use a minimal subset of the Jinja syntax:
+///
+/// - `{{ name }}` is replaced with the value of a variable; the `quote`, `upper` and `lower`
+///   filters can be applied, e.g. `{{ region | quote }}`
+/// - `{% if condition %}`, `{% elif condition %}`, `{% else %}` and `{% endif %}` include text
+///   conditionally; conditions compare values with `==` and `!=` and are combined with `and`, `or`
+///   and `not`
+/// - `{% for item in items %}` and `{% endfor %}` repeat text for each of the comma separated
+///   values of a variable; `loop.index`, `loop.first` and `loop.last` are defined in the loop
+/// - `{# comment #}` is removed
+///
+/// The line of a block tag is removed when the tag is the only text on the line.
+///
+/// # Errors
+///
+/// Returns an error if the template is invalid or uses an undefined variable.
+pub fn render_template(template: &str, variables: &BTreeMap<String, String>) -> Result<String> {
+    let mut tokens = tokenize(template)?.into_iter().peekable();
+    let nodes = parse(&mut tokens, &[])?;
+    let mut scopes = vec![variables.clone()];
+    let mut output = String::new();
+    render(&nodes, &mut scopes, &mut output)?;
+    Ok(output)
+}
+
+fn template_error<S: Into<String>>(line: usize, message: S) -> Error {
+    Error::TemplateError {
+        line,
+        message: message.into(),
+    }
+}
+
+#[derive(Debug)]
+enum TokenKind {
+    Text(String),
+    Expression(String),
+    Tag(String),
+}
+
+#[derive(Debug)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+}
+
+/// Split the template into text, expressions and tags
+fn tokenize(template: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut remaining = template;
+    let mut line = 1;
+    // Indicates if the remaining template starts at the start of a line
+    let mut line_start = true;
+    while !remaining.is_empty() {
+        let start = ["{{", "{%", "{#"]
+            .iter()
+            .filter_map(|delimiter| remaining.find(delimiter))
+            .min();
+        let Some(start) = start else {
+            tokens.push(Token {
+                kind: TokenKind::Text(remaining.to_string()),
+                line,
+            });
+            break;
+        };
+
+        let delimiter = &remaining[start..start + 2];
+        let end_delimiter = match delimiter {
+            "{{" => "}}",
+            "{%" => "%}",
+            _ => "#}",
+        };
+        let content_start = start + 2;
+        let Some(end) = remaining[content_start..].find(end_delimiter) else {
+            return Err(template_error(line, format!("missing {end_delimiter}")));
+        };
+        let content = &remaining[content_start..content_start + end];
+        let mut text = &remaining[..start];
+        let mut rest = &remaining[content_start + end + 2..];
+        let text_line = line;
+        line += text.matches('\n').count();
+        let content_line = line;
+        line += content.matches('\n').count();
+
+        // Remove the line of block tags and comments that are the only text on the line
+        let mut removed_line = false;
+        if delimiter != "{{" {
+            let text_line_start = text.rfind('\n').map(|index| index + 1);
+            let at_line_start = match text_line_start {
+                Some(index) => text[index..].trim().is_empty(),
+                None => line_start && text.trim().is_empty(),
+            };
+            let rest_trimmed = rest.trim_start_matches([' ', '\t', '\r']);
+            if at_line_start && (rest_trimmed.is_empty() || rest_trimmed.starts_with('\n')) {
+                text = &text[..text_line_start.unwrap_or(0)];
+                removed_line = rest_trimmed.starts_with('\n');
+                rest = rest_trimmed.strip_prefix('\n').unwrap_or(rest_trimmed);
+            }
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token {
+                kind: TokenKind::Text(text.to_string()),
+                line: text_line,
+            });
+        }
+        let kind = match delimiter {
+            "{{" => Some(TokenKind::Expression(content.trim().to_string())),
+            "{%" => Some(TokenKind::Tag(content.trim().to_string())),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            tokens.push(Token {
+                kind,
+                line: content_line,
+            });
+        }
+        if removed_line {
+            line += 1;
+        }
+        line_start = removed_line;
+        remaining = rest;
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Expression {
+        expression: String,
+        line: usize,
+    },
+    If {
+        branches: Vec<(String, Vec<Node>)>,
+        otherwise: Vec<Node>,
+        line: usize,
+    },
+    For {
+        variable: String,
+        items: String,
+        body: Vec<Node>,
+        line: usize,
+    },
+}
+
+/// Split a tag into its keyword and arguments, e.g. `if` and `region == 'EU'`
+fn split_tag(tag: &str) -> (&str, &str) {
+    match tag.split_once(char::is_whitespace) {
+        Some((keyword, arguments)) => (keyword, arguments.trim()),
+        None => (tag, ""),
+    }
+}
+
+/// Parse the tokens into nodes until one of the end tags is found; the end tag is not consumed.
+fn parse<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    end_tags: &[&str],
+) -> Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.peek() {
+        if let TokenKind::Tag(tag) = &token.kind {
+            let (keyword, _) = split_tag(tag);
+            if end_tags.contains(&keyword) {
+                return Ok(nodes);
+            }
+        }
+        let Some(token) = tokens.next() else {
+            break;
+        };
+        let line = token.line;
+        match token.kind {
+            TokenKind::Text(text) => nodes.push(Node::Text(text)),
+            TokenKind::Expression(expression) => nodes.push(Node::Expression { expression, line }),
+            TokenKind::Tag(tag) => {
+                let (keyword, arguments) = split_tag(&tag);
+                match keyword {
+                    "if" => nodes.push(parse_if(tokens, arguments, line)?),
+                    "for" => nodes.push(parse_for(tokens, arguments, line)?),
+                    _ => return Err(template_error(line, format!("unexpected tag: {tag}"))),
+                }
+            }
+        }
+    }
+    if end_tags.is_empty() {
+        Ok(nodes)
+    } else {
+        Err(template_error(0, format!("missing {}", end_tags.join(" or "))))
+    }
+}
+
+fn parse_if<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    condition: &str,
+    line: usize,
+) -> Result<Node> {
+    let missing_endif = |error: Error| match error {
+        Error::TemplateError { line: 0, .. } => template_error(line, "missing endif"),
+        error => error,
+    };
+    let mut branches = Vec::new();
+    let mut otherwise = Vec::new();
+    let mut condition = condition.to_string();
+    loop {
+        let body = parse(tokens, &["elif", "else", "endif"]).map_err(missing_endif)?;
+        let Some(Token {
+            kind: TokenKind::Tag(tag),
+            ..
+        }) = tokens.next()
+        else {
+            return Err(template_error(line, "missing endif"));
+        };
+        branches.push((condition, body));
+        match split_tag(&tag) {
+            ("elif", next_condition) => condition = next_condition.to_string(),
+            ("else", _) => {
+                otherwise = parse(tokens, &["endif"]).map_err(missing_endif)?;
+                tokens.next();
+                break;
+            }
+            _ => break,
+        }
+    }
+    Ok(Node::If {
+        branches,
+        otherwise,
+        line,
+    })
+}
+
+fn parse_for<I: Iterator<Item = Token>>(
+    tokens: &mut std::iter::Peekable<I>,
+    arguments: &str,
+    line: usize,
+) -> Result<Node> {
+    let parts: Vec<&str> = arguments.split_whitespace().collect();
+    let [variable, "in", items] = parts.as_slice() else {
+        return Err(template_error(line, format!("invalid for loop: {arguments}")));
+    };
+    let body = parse(tokens, &["endfor"]).map_err(|error| match error {
+        Error::TemplateError { line: 0, .. } => template_error(line, "missing endfor"),
+        error => error,
+    })?;
+    tokens.next();
+    Ok(Node::For {
+        variable: (*variable).to_string(),
+        items: (*items).to_string(),
+        body,
+        line,
+    })
+}
+
+/// Get the value of a variable from the innermost scope that defines it
+fn lookup<'a>(scopes: &'a [BTreeMap<String, String>], name: &str) -> Option<&'a String> {
+    scopes.iter().rev().find_map(|scope| scope.get(name))
+}
+
+/// Evaluate an operand of an expression, which is a quoted string, a number or a variable;
+/// undefined variables are `None`.
+fn operand(scopes: &[BTreeMap<String, String>], operand: &str) -> Option<String> {
+    let operand = operand.trim();
+    for quote in ['\'', '"'] {
+        if let Some(value) = operand
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return Some(value.to_string());
+        }
+    }
+    if operand.parse::<f64>().is_ok() {
+        return Some(operand.to_string());
+    }
+    lookup(scopes, operand).cloned()
+}
+
+/// Returns true if the value is defined and is not empty, `false` or `0`
+fn is_truthy(value: Option<&str>) -> bool {
+    value.is_some_and(|value| {
+        !value.is_empty() && !value.eq_ignore_ascii_case("false") && value != "0"
+    })
+}
+
+/// Split a condition into operands, operators and quoted strings
+fn condition_tokens(condition: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut quote = None;
+    let mut characters = condition.chars().peekable();
+    while let Some(character) = characters.next() {
+        match quote {
+            Some(end) => {
+                token.push(character);
+                if character == end {
+                    quote = None;
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            None if character == '\'' || character == '"' => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+                token.push(character);
+                quote = Some(character);
+            }
+            None if character.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            None if (character == '=' || character == '!') && characters.peek() == Some(&'=') => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+                characters.next();
+                tokens.push(format!("{character}="));
+            }
+            None => token.push(character),
+        }
+    }
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Evaluate a condition; `and` has a higher precedence than `or`, and `not` negates the
+/// comparison or operand that follows it.
+fn evaluate(scopes: &[BTreeMap<String, String>], condition: &str, line: usize) -> Result<bool> {
+    let tokens = condition_tokens(condition);
+    if tokens.is_empty() {
+        return Err(template_error(line, "missing condition"));
+    }
+    let mut result = false;
+    for disjunction in tokens.split(|token| token == "or") {
+        let mut conjunction_result = true;
+        for conjunction in disjunction.split(|token| token == "and") {
+            let negations = conjunction.iter().take_while(|token| *token == "not").count();
+            let value = match &conjunction[negations..] {
+                [value] => is_truthy(operand(scopes, value).as_deref()),
+                [left, operator, right] if operator == "==" || operator == "!=" => {
+                    let equal = operand(scopes, left) == operand(scopes, right);
+                    equal == (operator == "==")
+                }
+                _ => return Err(template_error(line, format!("invalid condition: {condition}"))),
+            };
+            conjunction_result &= value ^ (negations % 2 == 1);
+        }
+        result |= conjunction_result;
+    }
+    Ok(result)
+}
+
+/// Apply a filter to a value
+fn filter(value: &str, filter: &str, line: usize) -> Result<String> {
+    match filter {
+        "quote" => Ok(format!("'{}'", value.replace('\'', "''"))),
+        "upper" => Ok(value.to_uppercase()),
+        "lower" => Ok(value.to_lowercase()),
+        _ => Err(template_error(line, format!("unknown filter: {filter}"))),
+    }
+}
+
+fn render(
+    nodes: &[Node],
+    scopes: &mut Vec<BTreeMap<String, String>>,
+    output: &mut String,
+) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Expression { expression, line } => {
+                let mut parts = expression.split('|');
+                let name = parts.next().unwrap_or_default().trim();
+                let Some(mut value) = operand(scopes, name) else {
+                    return Err(template_error(*line, format!("undefined variable: {name}")));
+                };
+                for name in parts {
+                    value = filter(&value, name.trim(), *line)?;
+                }
+                output.push_str(&value);
+            }
+            Node::If {
+                branches,
+                otherwise,
+                line,
+            } => {
+                let mut body = otherwise;
+                for (condition, branch) in branches {
+                    if evaluate(scopes, condition, *line)? {
+                        body = branch;
+                        break;
+                    }
+                }
+                render(body, scopes, output)?;
+            }
+            Node::For {
+                variable,
+                items,
+                body,
+                line,
+            } => {
+                let Some(items) = lookup(scopes, items).cloned() else {
+                    return Err(template_error(*line, format!("undefined variable: {items}")));
+                };
+                let items: Vec<&str> = items
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .collect();
+                for (index, item) in items.iter().enumerate() {
+                    let scope = BTreeMap::from([
+                        (variable.clone(), (*item).to_string()),
+                        ("loop.index".to_string(), (index + 1).to_string()),
+                        ("loop.first".to_string(), (index == 0).to_string()),
+                        ("loop.last".to_string(), (index + 1 == items.len()).to_string()),
+                    ]);
+                    scopes.push(scope);
+                    let result = render(body, scopes, output);
+                    scopes.pop();
+                    result?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn variables(variables: &[(&str, &str)]) -> BTreeMap<String, String> {
+        variables
+            .iter()
+            .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_is_template() {
+        assert!(is_template("report.sql.j2"));
+        assert!(is_template("REPORT.SQL.JINJA"));
+        assert!(!is_template("report.sql"));
+    }
+
+    #[test]
+    fn test_render_variables() -> anyhow::Result<()> {
+        let variables = variables(&[("region", "EU"), ("name", "O'Brien")]);
+        let sql = render_template(
+            "SELECT * FROM sales WHERE region = '{{ region }}' AND name = {{name|quote}}; {# x #}",
+            &variables,
+        )?;
+        assert_eq!(
+            sql,
+            "SELECT * FROM sales WHERE region = 'EU' AND name = 'O''Brien'; "
+        );
+        assert_eq!(
+            render_template("{{ region | lower }}{{ 'x' | upper }}", &variables)?,
+            "euX"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_conditionals() -> anyhow::Result<()> {
+        let template = indoc! {"
+            SELECT *
+            FROM sales
+            {% if region == 'EU' and not archived %}
+            WHERE region = 'EU'
+            {% elif region %}
+            WHERE region = '{{ region }}'
+            {% else %}
+            WHERE region IS NULL
+            {% endif %}
+        "};
+        let sql = render_template(template, &variables(&[("region", "EU")]))?;
+        assert_eq!(sql, "SELECT *\nFROM sales\nWHERE region = 'EU'\n");
+        let sql = render_template(template, &variables(&[("region", "US")]))?;
+        assert_eq!(sql, "SELECT *\nFROM sales\nWHERE region = 'US'\n");
+        let sql = render_template(template, &variables(&[("archived", "true")]))?;
+        assert_eq!(sql, "SELECT *\nFROM sales\nWHERE region IS NULL\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_loops() -> anyhow::Result<()> {
+        let template = indoc! {"
+            SELECT
+            {% for column in columns %}
+              sum({{ column }}) AS {{ column }}{% if not loop.last %},{% endif %}
+            {% endfor %}
+            FROM sales
+        "};
+        let sql = render_template(template, &variables(&[("columns", "a, b")]))?;
+        assert_eq!(sql, "SELECT\n  sum(a) AS a,\n  sum(b) AS b\nFROM sales\n");
+        let sql = render_template(
+            "{% for x in items %}{{ loop.index }}={{ x }} {% endfor %}",
+            &variables(&[("items", "a,b,c")]),
+        )?;
+        assert_eq!(sql, "1=a 2=b 3=c ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let variables = variables(&[("items", "a")]);
+        let error = render_template("SELECT 1;\nSELECT {{ foo }};", &variables)
+            .expect_err("undefined variable");
+        assert_eq!(
+            error.to_string(),
+            "Template error on line 2: undefined variable: foo"
+        );
+        assert!(render_template("{{ items", &variables).is_err());
+        assert!(render_template("{% if items %}", &variables).is_err());
+        assert!(render_template("{% for x in items %}", &variables).is_err());
+        assert!(render_template("{% for x items %}{% endfor %}", &variables).is_err());
+        assert!(render_template("{% endif %}", &variables).is_err());
+        assert!(render_template("{% foo %}", &variables).is_err());
+        assert!(render_template("{{ items | foo }}", &variables).is_err());
+        assert!(render_template("{% if items == %}{% endif %}", &variables).is_err());
+    }
+}
@@ -5,10 +5,18 @@
 #[macro_use]
 extern crate rust_i18n;
 
+pub mod assistant;
 pub mod commands;
 pub mod configuration;
+pub mod demo;
+pub mod error_code;
 pub mod executors;
+pub mod locales;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod shell;
+#[cfg(feature = "viewer")]
+mod viewer;
 
 pub use rsql_formatters::writers;
 
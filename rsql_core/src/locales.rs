@@ -0,0 +1,134 @@
+//! Locale resolution for the translations; translations that are missing for a regional locale
+//! (e.g. `pt-BR`) fall back to the base language (e.g. `pt`) before the default `en` locale.
+
+/// The locale used when a translation is not available in the requested locale
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Get the sorted list of locales with translations.
+#[must_use]
+pub fn available_locales() -> Vec<String> {
+    let mut locales: Vec<String> = available_locales!()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect();
+    locales.sort();
+    locales
+}
+
+/// Get the locales that are searched for a translation, from the most to the least specific;
+/// e.g. `pt-BR` falls back to `pt` and then `en`.
+#[must_use]
+pub fn fallback_chain(locale: &str) -> Vec<String> {
+    let parts = locale_parts(locale);
+    let mut chain: Vec<String> = (1..=parts.len())
+        .rev()
+        .map(|length| parts[..length].join("-"))
+        .collect();
+    if !chain.iter().any(|locale| locale == DEFAULT_LOCALE) {
+        chain.push(DEFAULT_LOCALE.to_string());
+    }
+    chain
+}
+
+/// Resolve the locale to the most specific available locale, ignoring case and accepting `_` as a
+/// separator; e.g. `pt_br` resolves to `pt-BR` and `de-AT` resolves to `de`.  Returns `None` when
+/// neither the locale nor its base language are available.
+#[must_use]
+pub fn resolve_locale(locale: &str) -> Option<String> {
+    let available_locales = available_locales();
+    let parts = locale_parts(locale);
+    (1..=parts.len()).rev().find_map(|length| {
+        let locale = parts[..length].join("-");
+        available_locales
+            .iter()
+            .find(|available| available.eq_ignore_ascii_case(&locale))
+            .cloned()
+    })
+}
+
+/// Get the available locales that are similar to an invalid locale; locales with the same language
+/// are suggested first, otherwise locales with a language that starts with the same letter.
+#[must_use]
+pub fn suggest_locales(locale: &str) -> Vec<String> {
+    let parts = locale_parts(locale);
+    let Some(language) = parts.first().map(|language| language.to_lowercase()) else {
+        return Vec::new();
+    };
+    let available_locales = available_locales();
+    let same_language: Vec<String> = available_locales
+        .iter()
+        .filter(|available| available.split('-').next() == Some(language.as_str()))
+        .cloned()
+        .collect();
+    if !same_language.is_empty() {
+        return same_language;
+    }
+    let first_letter = &language[..language.chars().next().map_or(0, char::len_utf8)];
+    available_locales
+        .into_iter()
+        .filter(|available| !available.contains('-') && available.starts_with(first_letter))
+        .collect()
+}
+
+/// Split a locale such as `pt_BR.UTF-8` into its language and region parts, e.g. `["pt", "BR"]`;
+/// encoding and modifier suffixes are ignored.
+fn locale_parts(locale: &str) -> Vec<&str> {
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    locale
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_locales() {
+        let locales = available_locales();
+        assert!(locales.contains(&"en".to_string()));
+        assert!(locales.contains(&"en-GB".to_string()));
+        assert!(locales.contains(&"pt-BR".to_string()));
+        assert!(locales.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn test_fallback_chain() {
+        assert_eq!(fallback_chain("pt-BR"), vec!["pt-BR", "pt", "en"]);
+        assert_eq!(fallback_chain("de_DE.UTF-8"), vec!["de-DE", "de", "en"]);
+        assert_eq!(fallback_chain("en-GB"), vec!["en-GB", "en"]);
+        assert_eq!(fallback_chain(""), vec!["en"]);
+    }
+
+    #[test]
+    fn test_resolve_locale() {
+        assert_eq!(resolve_locale("pt_br"), Some("pt-BR".to_string()));
+        assert_eq!(resolve_locale("pt-PT"), Some("pt".to_string()));
+        assert_eq!(resolve_locale("de-AT"), Some("de".to_string()));
+        assert_eq!(resolve_locale("EN"), Some("en".to_string()));
+        assert_eq!(resolve_locale("foo"), None);
+        assert_eq!(resolve_locale(""), None);
+    }
+
+    #[test]
+    fn test_suggest_locales() {
+        assert_eq!(suggest_locales("en-US"), vec!["en", "en-GB"]);
+        let suggestions = suggest_locales("zz");
+        assert!(suggestions.contains(&"zh".to_string()));
+        assert!(suggestions.iter().all(|locale| locale.starts_with('z')));
+        assert!(suggest_locales("").is_empty());
+    }
+
+    #[test]
+    fn test_fallback_translation() {
+        assert_eq!(
+            t!("locale_setting", locale = "pt-BR", current_locale = "pt-BR"),
+            "Localidade: pt-BR"
+        );
+        assert_eq!(
+            t!("locale_command", locale = "pt-BR"),
+            t!("locale_command", locale = "pt")
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::configuration::parse_timeout;
+use crate::shell::DEFAULT_URL;
+use clap::{Args, ValueHint};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// The address the server listens on by default; only local programs can connect to it.
+const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+
+#[derive(Clone, Debug, Args)]
+pub struct ServeArgs {
+    /// The url of the database
+    #[arg(short, long, default_value = DEFAULT_URL, env = "DATABASE_URL", value_hint = ValueHint::Url)]
+    pub url: String,
+
+    /// The address and port to listen on
+    #[arg(long, default_value = DEFAULT_LISTEN)]
+    pub listen: SocketAddr,
+
+    /// The token that requests must send in the `Authorization: Bearer <token>` header
+    #[arg(long, env = "RSQL_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+
+    /// Allow statements that modify the database; statements are refused in read-only mode by
+    /// default
+    #[arg(long)]
+    pub allow_writes: bool,
+
+    /// Cancel statements that run longer than the timeout, e.g. 30s, 500ms or 5m
+    #[arg(long, value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+}
+
+impl Default for ServeArgs {
+    fn default() -> Self {
+        ServeArgs {
+            url: DEFAULT_URL.to_string(),
+            listen: DEFAULT_LISTEN.parse().expect("valid address"),
+            token: None,
+            allow_writes: false,
+            timeout: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct McpArgs {
+    /// The url of the database
+    #[arg(short, long, default_value = DEFAULT_URL, env = "DATABASE_URL", value_hint = ValueHint::Url)]
+    pub url: String,
+
+    /// Allow statements that modify the database; statements are refused in read-only mode by
+    /// default
+    #[arg(long)]
+    pub allow_writes: bool,
+
+    /// Cancel statements that run longer than the timeout, e.g. 30s, 500ms or 5m
+    #[arg(long, value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+}
+
+impl Default for McpArgs {
+    fn default() -> Self {
+        McpArgs {
+            url: DEFAULT_URL.to_string(),
+            allow_writes: false,
+            timeout: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[command(flatten)]
+        serve: ServeArgs,
+    }
+
+    #[test]
+    fn test_default() {
+        let args = ServeArgs::default();
+        assert_eq!(args.url, DEFAULT_URL);
+        assert_eq!(args.listen.to_string(), DEFAULT_LISTEN);
+        assert!(!args.allow_writes);
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        let cli = Cli::try_parse_from([
+            "rsql",
+            "--url",
+            "rusqlite://",
+            "--listen",
+            "0.0.0.0:9000",
+            "--token",
+            "secret",
+            "--allow-writes",
+            "--timeout",
+            "30s",
+        ])?;
+        let args = cli.serve;
+        assert_eq!(args.url, "rusqlite://");
+        assert_eq!(args.listen.port(), 9000);
+        assert_eq!(args.token, Some("secret".to_string()));
+        assert!(args.allow_writes);
+        assert_eq!(args.timeout, Some(Duration::from_secs(30)));
+        Ok(())
+    }
+}
@@ -0,0 +1,307 @@
+use crate::configuration::Configuration;
+use crate::server::query::{QueryRequest, QueryServer};
+use crate::server::{connect, McpArgs};
+use crate::shell::Result;
+use crate::writers::Output;
+use rsql_drivers::Table;
+use serde_json::{json, Value};
+use std::io::Write;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
+
+/// The version of the Model Context Protocol implemented by the server
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Serve the Model Context Protocol over stdin and stdout until stdin is closed, so that AI
+/// assistants can use the database with the `list_tables`, `describe_table` and `run_query`
+/// tools.  Statements that modify the database are refused unless writes are allowed.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be connected to, or the messages cannot be read or
+/// written.
+pub async fn mcp(configuration: Configuration, args: &McpArgs, output: &mut Output) -> Result<()> {
+    let version = configuration.version.clone();
+    let server = connect(configuration, &args.url, args.allow_writes, args.timeout).await?;
+    let mut mcp_server = McpServer { server, version };
+    let result = mcp_server
+        .run(BufReader::new(tokio::io::stdin()), output)
+        .await;
+    mcp_server.server.close().await?;
+    result
+}
+
+struct McpServer {
+    server: QueryServer,
+    version: String,
+}
+
+impl McpServer {
+    /// Handle the messages read from the reader, one JSON-RPC message per line, and write the
+    /// responses to the output.
+    async fn run<R: AsyncBufRead + Unpin>(&mut self, reader: R, output: &mut Output) -> Result<()> {
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle(&line).await {
+                writeln!(output, "{response}")?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a message; returns the response, or `None` for notifications, which have no id and
+    /// are not answered.
+    async fn handle(&mut self, message: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(message) {
+            Ok(message) => message,
+            Err(error) => return Some(error_response(&Value::Null, PARSE_ERROR, &error)),
+        };
+        let id = message.get("id")?;
+        let method = message["method"].as_str().unwrap_or_default();
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "rsql", "version": self.version },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools() })),
+            "tools/call" => self.call_tool(&message["params"]).await,
+            _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+        };
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        };
+        Some(response)
+    }
+
+    /// Call the tool; errors of the tool, such as a statement that failed, are returned in the
+    /// result so that the assistant can correct the call.
+    async fn call_tool(&mut self, params: &Value) -> std::result::Result<Value, (i64, String)> {
+        let name = params["name"].as_str().unwrap_or_default();
+        let arguments = &params["arguments"];
+        let result = match name {
+            "list_tables" => self.list_tables().await,
+            "describe_table" => match arguments["table"].as_str() {
+                Some(table) => self.describe_table(table).await,
+                None => return Err((INVALID_PARAMS, "Missing argument: table".to_string())),
+            },
+            "run_query" => match arguments["sql"].as_str() {
+                Some(sql) => self.run_query(sql).await,
+                None => return Err((INVALID_PARAMS, "Missing argument: sql".to_string())),
+            },
+            _ => return Err((INVALID_PARAMS, format!("Unknown tool: {name}"))),
+        };
+        let (text, is_error) = match result {
+            Ok(text) => (text, false),
+            Err(error) => (error, true),
+        };
+        Ok(json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        }))
+    }
+
+    /// List the tables of the current schema as a JSON array of names
+    async fn list_tables(&mut self) -> std::result::Result<String, String> {
+        let metadata = self.server.metadata().await.map_err(|error| error.to_string())?;
+        let tables: Vec<&str> = metadata
+            .current_schema()
+            .map(|schema| schema.tables().into_iter().map(Table::name).collect())
+            .unwrap_or_default();
+        Ok(json!(tables).to_string())
+    }
+
+    /// Describe the columns and indexes of the table as a JSON document
+    async fn describe_table(&mut self, name: &str) -> std::result::Result<String, String> {
+        let metadata = self.server.metadata().await.map_err(|error| error.to_string())?;
+        let Some(table) = metadata.current_schema().and_then(|schema| schema.get(name)) else {
+            return Err(format!("Table not found: {name}"));
+        };
+        let columns: Vec<Value> = table
+            .columns()
+            .iter()
+            .map(|column| {
+                json!({
+                    "name": column.name(),
+                    "type": column.data_type(),
+                    "not_null": column.not_null(),
+                    "default": column.default(),
+                })
+            })
+            .collect();
+        let indexes: Vec<Value> = table
+            .indexes()
+            .iter()
+            .map(|index| {
+                json!({
+                    "name": index.name(),
+                    "columns": index.columns(),
+                    "unique": index.unique(),
+                })
+            })
+            .collect();
+        let document = json!({ "name": table.name(), "columns": columns, "indexes": indexes });
+        Ok(document.to_string())
+    }
+
+    /// Execute the SQL statements and return the JSON documents of the statements
+    async fn run_query(&mut self, sql: &str) -> std::result::Result<String, String> {
+        let request = QueryRequest {
+            sql: sql.to_string(),
+            format: "json".to_string(),
+        };
+        let response = self
+            .server
+            .query(&request)
+            .await
+            .map_err(|error| error.to_string())?;
+        Ok(String::from_utf8_lossy(&response.body).into_owned())
+    }
+}
+
+/// Get the definitions of the tools provided by the server
+fn tools() -> Value {
+    json!([
+        {
+            "name": "list_tables",
+            "description": "List the tables of the current schema of the database",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+        {
+            "name": "describe_table",
+            "description": "Describe the columns and indexes of a table",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "table": { "type": "string", "description": "The name of the table" },
+                },
+                "required": ["table"],
+            },
+        },
+        {
+            "name": "run_query",
+            "description": "Execute SQL statements and return the results of each statement as \
+                JSON; in read-only mode only queries and statements that display information \
+                (SHOW and EXPLAIN) are executed, and all other statements are refused",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "sql": { "type": "string", "description": "The SQL statements to execute" },
+                },
+                "required": ["sql"],
+            },
+        },
+    ])
+}
+
+fn error_response(id: &Value, code: i64, message: &impl ToString) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.to_string() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn run(input: &str) -> anyhow::Result<Vec<Value>> {
+        let server = connect(Configuration::default(), "rusqlite://", false, None).await?;
+        let mut mcp_server = McpServer {
+            server,
+            version: "0.0.0".to_string(),
+        };
+        let mut output = Output::default();
+        mcp_server.run(input.as_bytes(), &mut output).await?;
+        mcp_server.server.close().await?;
+        let responses = output
+            .to_string()
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<std::result::Result<_, _>>()?;
+        Ok(responses)
+    }
+
+    fn call(id: u64, name: &str, arguments: &Value) -> String {
+        let params = json!({ "name": name, "arguments": arguments });
+        json!({ "jsonrpc": "2.0", "id": id, "method": "tools/call", "params": params }).to_string()
+    }
+
+    #[tokio::test]
+    async fn test_initialize() -> anyhow::Result<()> {
+        let input = [
+            r#"{"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}"#,
+            r#"{"jsonrpc": "2.0", "method": "notifications/initialized"}"#,
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "tools/list"}"#,
+            r#"{"jsonrpc": "2.0", "id": 3, "method": "foo"}"#,
+            "not json",
+        ]
+        .join("\n");
+        let responses = run(&input).await?;
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert_eq!(responses[0]["result"]["serverInfo"]["version"], "0.0.0");
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["result"]["tools"][2]["name"], "run_query");
+        assert_eq!(responses[2]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(responses[3]["error"]["code"], PARSE_ERROR);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tools() -> anyhow::Result<()> {
+        let input = [
+            call(1, "list_tables", &json!({})),
+            call(2, "describe_table", &json!({ "table": "foo" })),
+            call(3, "run_query", &json!({ "sql": "SELECT 42 AS answer;" })),
+            call(4, "run_query", &json!({ "sql": "CREATE TABLE users (id INTEGER);" })),
+            call(5, "run_query", &json!({})),
+            call(6, "foo", &json!({})),
+        ]
+        .join("\n");
+        let responses = run(&input).await?;
+        assert_eq!(responses[0]["result"]["content"][0]["text"], "[]");
+        assert_eq!(responses[1]["result"]["isError"], true);
+        assert_eq!(
+            responses[1]["result"]["content"][0]["text"],
+            "Table not found: foo"
+        );
+        let text = responses[2]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap_or_default();
+        assert!(text.contains("answer"));
+        assert_eq!(responses[2]["result"]["isError"], false);
+        assert_eq!(responses[3]["result"]["isError"], true);
+        assert_eq!(responses[4]["error"]["code"], INVALID_PARAMS);
+        assert_eq!(responses[5]["error"]["code"], INVALID_PARAMS);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_run_query_read_only() -> anyhow::Result<()> {
+        let input = [
+            call(1, "run_query", &json!({ "sql": "TRUNCATE users;" })),
+            call(2, "run_query", &json!({ "sql": "PRAGMA query_only = OFF;" })),
+            call(3, "run_query", &json!({ "sql": "CREATE TABLE users (id INTEGER);" })),
+            call(4, "run_query", &json!({ "sql": "SELECT 1;" })),
+        ]
+        .join("\n");
+        let responses = run(&input).await?;
+        for response in &responses[..3] {
+            assert_eq!(response["result"]["isError"], true);
+        }
+        assert_eq!(responses[3]["result"]["isError"], false);
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+mod args;
+mod mcp;
+mod query;
+
+pub use args::{McpArgs, ServeArgs};
+pub use mcp::mcp;
+
+use crate::configuration::Configuration;
+use crate::shell::Result;
+use crate::writers::Output;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use query::{QueryRequest, QueryServer};
+use rsql_drivers::DriverManager;
+use rust_i18n::t;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// The state shared by the requests; statements are executed one request at a time with the
+/// connection of the server.
+#[derive(Clone)]
+struct ServerState {
+    token: Option<Arc<str>>,
+    server: Arc<Mutex<QueryServer>>,
+}
+
+/// Serve an HTTP API for the database until the process is interrupted.  `POST /query` executes
+/// the SQL statements of a JSON request, e.g. `{"sql": "SELECT * FROM users", "format": "csv"}`,
+/// and returns the results as JSON, CSV or Arrow; `GET /health` returns the status of the server.
+/// Statements that modify the database are refused unless writes are allowed, and requests must
+/// send the token in the `Authorization` header when a token is configured.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be connected to, or the address cannot be listened on.
+pub async fn serve(
+    configuration: Configuration,
+    args: &ServeArgs,
+    output: &mut Output,
+) -> Result<()> {
+    let locale = configuration.locale.clone();
+    let server = connect(configuration, &args.url, args.allow_writes, args.timeout).await?;
+    let server = Arc::new(Mutex::new(server));
+    let state = ServerState {
+        token: args.token.as_deref().map(Arc::from),
+        server: Arc::clone(&server),
+    };
+
+    let listener = TcpListener::bind(args.listen).await?;
+    let address = listener.local_addr()?;
+    if state.token.is_none() && !address.ip().is_loopback() {
+        warn!("Listening on {address} without a token; any client can execute statements");
+    }
+    let server_listening = t!("server_listening", locale = locale, address = address);
+    writeln!(output, "{server_listening}")?;
+    output.flush()?;
+    info!("Listening on {address}");
+
+    axum::serve(listener, router(state))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    server.lock().await.close().await?;
+    Ok(())
+}
+
+/// Connect to the database for a server; unless writes are allowed, the connection is made
+/// read-only and only statements that read from the database are executed.
+async fn connect(
+    mut configuration: Configuration,
+    url: &str,
+    allow_writes: bool,
+    timeout: Option<Duration>,
+) -> Result<QueryServer> {
+    configuration.read_only = !allow_writes;
+    if let Some(timeout) = timeout {
+        configuration.results_timeout = timeout;
+    }
+    let mut driver_manager = DriverManager::default();
+    configuration.configure_driver_manager(&mut driver_manager);
+    let connection = driver_manager.connect(url).await?;
+    let server = QueryServer::new(configuration, driver_manager, connection).await?;
+    Ok(server)
+}
+
+fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/query", post(query))
+        .with_state(state)
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Execute the statements of the request; the request is authorized before the body is parsed.
+async fn query(State(state): State<ServerState>, headers: HeaderMap, body: Bytes) -> Response {
+    if !authorized(state.token.as_deref(), &headers) {
+        return error_response(StatusCode::UNAUTHORIZED, "Unauthorized");
+    }
+    let request: QueryRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(error) => return error_response(StatusCode::BAD_REQUEST, &error.to_string()),
+    };
+
+    let mut server = state.server.lock().await;
+    match server.query(&request).await {
+        Ok(response) => {
+            ([(header::CONTENT_TYPE, response.content_type)], response.body).into_response()
+        }
+        Err(error) => error_response(StatusCode::BAD_REQUEST, &error.to_string()),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Returns true if no token is configured, or the request sends the token in the `Authorization:
+/// Bearer <token>` header.  The digests of the tokens are compared, so that the time taken by the
+/// comparison does not reveal the token.
+fn authorized(token: Option<&str>, headers: &HeaderMap) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|value| Sha256::digest(value) == Sha256::digest(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(authorization: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static(authorization),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_authorized() {
+        assert!(authorized(None, &HeaderMap::new()));
+        assert!(authorized(Some("secret"), &headers("Bearer secret")));
+        assert!(!authorized(Some("secret"), &HeaderMap::new()));
+        assert!(!authorized(Some("secret"), &headers("Bearer foo")));
+        assert!(!authorized(Some("secret"), &headers("Basic secret")));
+    }
+
+    #[tokio::test]
+    async fn test_query() -> anyhow::Result<()> {
+        let server = connect(Configuration::default(), "rusqlite://", false, None).await?;
+        let state = ServerState {
+            token: Some(Arc::from("secret")),
+            server: Arc::new(Mutex::new(server)),
+        };
+        let body = Bytes::from_static(br#"{"sql": "SELECT 1 AS id;", "format": "json"}"#);
+
+        let response = query(State(state.clone()), HeaderMap::new(), body.clone()).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = query(State(state.clone()), headers("Bearer secret"), body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = Bytes::from_static(br#"{"sql": "CREATE TABLE users (id INTEGER);"}"#);
+        let response = query(State(state.clone()), headers("Bearer secret"), body).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = Bytes::from_static(br#"{"sql": "TRUNCATE users;"}"#);
+        let response = query(State(state.clone()), headers("Bearer secret"), body).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = Bytes::from_static(br#"{"sql": "PRAGMA query_only = OFF;"}"#);
+        let response = query(State(state.clone()), headers("Bearer secret"), body).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = Bytes::from_static(b"SELECT 1;");
+        let response = query(State(state.clone()), headers("Bearer secret"), body).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        state.server.lock().await.close().await?;
+        Ok(())
+    }
+}
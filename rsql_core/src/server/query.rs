@@ -0,0 +1,360 @@
+use crate::commands::CommandManager;
+use crate::configuration::{Configuration, EchoMode};
+use crate::executors::{parse_commands, Executor, Result};
+use crate::shell::ShellState;
+use crate::writers::{Output, Writer};
+use rsql_drivers::{Connection, DriverManager, Metadata};
+use rsql_formatters::FormatterManager;
+use rustyline::history::DefaultHistory;
+use serde::Deserialize;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// The format of the results when the request does not specify a format
+const DEFAULT_FORMAT: &str = "json";
+
+/// A request to execute SQL statements; commands are not allowed.
+#[derive(Debug, Deserialize)]
+pub(crate) struct QueryRequest {
+    pub(crate) sql: String,
+    /// The format of the results: json, csv or arrow
+    #[serde(default = "default_format")]
+    pub(crate) format: String,
+}
+
+fn default_format() -> String {
+    DEFAULT_FORMAT.to_string()
+}
+
+/// The results of a request and their content type
+#[derive(Debug)]
+pub(crate) struct QueryResponse {
+    pub(crate) content_type: &'static str,
+    pub(crate) body: Vec<u8>,
+}
+
+/// Executes the statements of requests with the connection of the server; statements are executed
+/// with the read-only mode, timeout and results limit of the configuration.
+///
+/// In read-only mode, statements are only executed if the parser recognizes them as read-only,
+/// but this check is not a security boundary: a query can have side effects, e.g. by calling a
+/// function such as `set_config` that makes the session writable again.  The read-only setting of
+/// the session is therefore applied again before each statement, so that databases that support
+/// read-only sessions refuse writes regardless of the statements executed before.
+pub(crate) struct QueryServer {
+    configuration: Configuration,
+    command_manager: CommandManager,
+    driver_manager: DriverManager,
+    formatter_manager: FormatterManager,
+    history: DefaultHistory,
+    state: ShellState,
+    connection: Box<dyn Connection>,
+}
+
+impl QueryServer {
+    /// Create the server for the connection; in read-only mode the connection is made read-only
+    /// before any request is executed, so that databases that support read-only sessions also
+    /// refuse statements that modify the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection cannot be made read-only.
+    pub(crate) async fn new(
+        configuration: Configuration,
+        driver_manager: DriverManager,
+        mut connection: Box<dyn Connection>,
+    ) -> rsql_drivers::Result<Self> {
+        let mut state = ShellState::default();
+        if configuration.read_only {
+            connection.set_read_only(true).await?;
+            state.read_only = true;
+        }
+        Ok(Self {
+            configuration,
+            command_manager: CommandManager::default(),
+            driver_manager,
+            formatter_manager: FormatterManager::default(),
+            history: DefaultHistory::new(),
+            state,
+            connection,
+        })
+    }
+
+    /// Execute the statements of the request and return the results in the requested format.
+    /// JSON results are an array with a document for each statement, as written in JSON mode;
+    /// CSV and Arrow results are the results of each statement, one after the other.  In read-only
+    /// mode the connection is made read-only again before each statement is executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the format is not supported, the request contains a command, or a
+    /// statement cannot be executed.
+    pub(crate) async fn query(&mut self, request: &QueryRequest) -> Result<QueryResponse> {
+        let format = request.format.as_str();
+        let Some(content_type) = self.content_type(format) else {
+            let error = rsql_formatters::Error::UnknownFormat {
+                format: format.to_string(),
+            };
+            return Err(error.into());
+        };
+
+        let mut configuration = self.configuration.clone();
+        configuration.color = false;
+        configuration.echo = EchoMode::Off;
+        configuration.json = format == "json";
+        configuration.results_footer = false;
+        configuration.results_timer = false;
+        configuration.results_format = format.to_string();
+
+        let read_only = self.configuration.read_only;
+        let statements = if read_only {
+            parse_commands(&configuration.command_identifier, &request.sql)?
+                .into_iter()
+                .filter(|statement| !statement.is_empty())
+                .collect()
+        } else {
+            vec![request.sql.clone()]
+        };
+
+        let writer = BodyWriter::default();
+        let mut output = Output::new(Box::new(writer.clone()));
+        for statement in &statements {
+            if read_only {
+                self.connection.set_read_only(true).await?;
+            }
+            let mut executor = Executor::new(
+                &mut configuration,
+                &self.command_manager,
+                &self.driver_manager,
+                &self.formatter_manager,
+                &self.history,
+                &mut self.state,
+                self.connection.as_mut(),
+                &mut output,
+            );
+            executor.execute_sql(statement).await?;
+        }
+
+        let mut body = writer.take();
+        if configuration.json {
+            let documents: Vec<&str> = std::str::from_utf8(&body)
+                .unwrap_or_default()
+                .lines()
+                .collect();
+            body = format!("[{}]", documents.join(",")).into_bytes();
+        }
+        Ok(QueryResponse { content_type, body })
+    }
+
+    /// Get the content type of the results for the format; `None` when the format is not
+    /// supported or the formatter is not available.
+    fn content_type(&self, format: &str) -> Option<&'static str> {
+        let content_type = match format {
+            "json" => return Some("application/json"),
+            "csv" => "text/csv; charset=utf-8",
+            "arrow" => "application/vnd.apache.arrow.stream",
+            _ => return None,
+        };
+        self.formatter_manager.get(format).map(|_| content_type)
+    }
+
+    /// Get the metadata of the database
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metadata cannot be retrieved.
+    pub(crate) async fn metadata(&mut self) -> rsql_drivers::Result<Metadata> {
+        self.connection.metadata().await
+    }
+
+    /// Close the connection of the server
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection cannot be closed.
+    pub(crate) async fn close(&mut self) -> rsql_drivers::Result<()> {
+        self.connection.close().await
+    }
+}
+
+/// A writer that collects the body of a response; the buffer is shared with the writer given to
+/// the executor, so that the body can be taken once the statements have been executed.
+#[derive(Clone, Debug, Default)]
+struct BodyWriter {
+    buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+impl BodyWriter {
+    fn take(&self) -> Vec<u8> {
+        self.buffer
+            .lock()
+            .map(|mut buffer| mem::take(&mut *buffer))
+            .unwrap_or_default()
+    }
+}
+
+impl Display for BodyWriter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let buffer = self.buffer.lock().map_err(|_| fmt::Error)?;
+        write!(f, "{}", String::from_utf8_lossy(&buffer))
+    }
+}
+
+impl Write for BodyWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut buffer = self
+            .buffer
+            .lock()
+            .map_err(|_| io::Error::other("response body is unavailable"))?;
+        buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Writer for BodyWriter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executors::Error;
+
+    async fn server(read_only: bool) -> anyhow::Result<QueryServer> {
+        let configuration = Configuration {
+            read_only,
+            ..Default::default()
+        };
+        let driver_manager = DriverManager::default();
+        let connection = driver_manager.connect("rusqlite://").await?;
+        let server = QueryServer::new(configuration, driver_manager, connection).await?;
+        Ok(server)
+    }
+
+    fn request(sql: &str, format: &str) -> QueryRequest {
+        QueryRequest {
+            sql: sql.to_string(),
+            format: format.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_default_format() -> anyhow::Result<()> {
+        let request: QueryRequest = serde_json::from_str(r#"{"sql": "SELECT 1"}"#)?;
+        assert_eq!(request.format, "json");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_json() -> anyhow::Result<()> {
+        let mut server = server(false).await?;
+        let response = server
+            .query(&request("SELECT 1 AS id;\nSELECT 'foo' AS name;", "json"))
+            .await?;
+        assert_eq!(response.content_type, "application/json");
+        let documents: serde_json::Value = serde_json::from_slice(&response.body)?;
+        let documents = documents.as_array().expect("array");
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0]["success"], true);
+        assert_eq!(documents[1]["statement"], "SELECT 'foo' AS name;");
+        server.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_csv() -> anyhow::Result<()> {
+        let mut server = server(false).await?;
+        let sql = "CREATE TABLE users (id INTEGER);\nINSERT INTO users VALUES (1), (2);\n\
+            SELECT id FROM users;";
+        let response = server.query(&request(sql, "csv")).await?;
+        assert_eq!(response.content_type, "text/csv; charset=utf-8");
+        let body = String::from_utf8(response.body)?.replace("\r\n", "\n");
+        assert!(body.ends_with("\"id\"\n1\n2\n"));
+        server.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_read_only() -> anyhow::Result<()> {
+        let mut server = server(true).await?;
+        let result = server
+            .query(&request("CREATE TABLE users (id INTEGER);", "json"))
+            .await;
+        assert!(matches!(result, Err(Error::ReadOnly)));
+        server.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_read_only_writes() -> anyhow::Result<()> {
+        let mut server = server(true).await?;
+        for sql in [
+            "TRUNCATE users;",
+            "PRAGMA query_only = OFF;",
+            "ATTACH DATABASE ':memory:' AS other;",
+            "SELECT 1; DROP TABLE users;",
+        ] {
+            let result = server.query(&request(sql, "json")).await;
+            assert!(matches!(result, Err(Error::ReadOnly)), "{sql}");
+        }
+        // The connection is also read-only, for statements that are not refused by the executor
+        let result = server
+            .connection
+            .execute("CREATE TABLE users (id INTEGER)")
+            .await;
+        assert!(result.is_err());
+        server.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_read_only_session() -> anyhow::Result<()> {
+        let mut server = server(true).await?;
+        // A statement with side effects may make the session writable; the next request makes the
+        // session read-only again before its statements are executed
+        server.connection.execute("PRAGMA query_only = OFF").await?;
+        let response = server.query(&request("SELECT 1;", "json")).await?;
+        let documents: serde_json::Value = serde_json::from_slice(&response.body)?;
+        assert_eq!(documents[0]["success"], true);
+        let result = server
+            .connection
+            .execute("CREATE TABLE users (id INTEGER)")
+            .await;
+        assert!(result.is_err());
+        server.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_command() -> anyhow::Result<()> {
+        let mut server = server(false).await?;
+        let result = server.query(&request(".tables", "json")).await;
+        assert!(matches!(result, Err(Error::InvalidCommand { .. })));
+        server.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_unknown_format() -> anyhow::Result<()> {
+        let mut server = server(false).await?;
+        let result = server.query(&request("SELECT 1;", "psql")).await;
+        assert!(matches!(result, Err(Error::FormatError(_))));
+        server.close().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_body_writer() -> anyhow::Result<()> {
+        let writer = BodyWriter::default();
+        let mut output = Output::new(Box::new(writer.clone()));
+        output.write_all(b"foo")?;
+        assert_eq!(output.to_string(), "foo");
+        assert_eq!(writer.take(), b"foo");
+        assert_eq!(writer.take(), b"");
+        Ok(())
+    }
+}
@@ -1,22 +1,80 @@
-use clap::Parser;
+use crate::configuration::parse_timeout;
+use crate::shell::report::REPORT_FORMATS;
+use clap::{Parser, ValueHint};
 use clap_stdin::FileOrStdin;
+use std::time::Duration;
 
 #[cfg(feature = "driver-rusqlite")]
-const DEFAULT_URL: &str = "rusqlite://";
+pub(crate) const DEFAULT_URL: &str = "rusqlite://";
 
 #[cfg(not(feature = "driver-rusqlite"))]
-const DEFAULT_URL: &str = "";
+pub(crate) const DEFAULT_URL: &str = "";
 
 #[derive(Debug, Parser)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct ShellArgs {
     /// The url of the database
-    #[arg(short, long, default_value = DEFAULT_URL, env = "DATABASE_URL")]
+    #[arg(short, long, default_value = DEFAULT_URL, env = "DATABASE_URL", value_hint = ValueHint::Url)]
     pub url: String,
 
+    /// The name of a connection profile defined in the configuration file
+    #[arg(long)]
+    pub profile: Option<String>,
+
     /// The input file to execute
-    #[arg(short, long)]
+    #[arg(short, long, value_hint = ValueHint::FilePath)]
     pub file: Option<FileOrStdin>,
 
+    /// The format of the results, e.g. psql, csv or json
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// Check the syntax of the input file or commands without executing them
+    #[arg(long)]
+    pub check: bool,
+
+    /// Refuse statements that modify the database
+    #[arg(long)]
+    pub read_only: bool,
+
+    /// Load the extensions specified in the URL, e.g. the extensions parameter of sqlite URLs;
+    /// extensions are native libraries that are loaded into the process
+    #[arg(long)]
+    pub load_extensions: bool,
+
+    /// Store the password of the URL with the credential helper or keyring after connecting, so
+    /// that later connections can omit it from the URL
+    #[arg(long)]
+    pub store_password: bool,
+
+    /// Cancel statements that run longer than the timeout, e.g. 30s, 500ms or 5m
+    #[arg(long, value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+
+    /// Write a JSON document for each statement executed, with the statement, whether it
+    /// succeeded, the results or error, and the elapsed time, instead of the formatted results
+    #[arg(long)]
+    pub json: bool,
+
+    /// Apply the pending migrations in the directory, recording the applied migrations in the
+    /// `_rsql_migrations` table, and display the status of the migrations
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub migrate: Option<String>,
+
+    /// Execute the fenced sql code blocks of a Markdown file and write a report of the file with
+    /// the results inserted below each block
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub report: Option<FileOrStdin>,
+
+    /// The format of the report: markdown or html
+    #[arg(long, default_value = "markdown", value_parser = REPORT_FORMATS)]
+    pub report_format: String,
+
+    /// Set a variable used to render SQL templates (files ending in `.j2` or `.jinja`); may be
+    /// specified multiple times
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_variable)]
+    pub variables: Vec<(String, String)>,
+
     /// Sequential list of commands to execute
     #[arg(last = true)]
     pub commands: Vec<String>,
@@ -26,12 +84,34 @@ impl Default for ShellArgs {
     fn default() -> Self {
         ShellArgs {
             url: DEFAULT_URL.to_string(),
+            profile: None,
             file: None,
+            format: None,
+            check: false,
+            read_only: false,
+            load_extensions: false,
+            store_password: false,
+            timeout: None,
+            json: false,
+            migrate: None,
+            report: None,
+            report_format: "markdown".to_string(),
+            variables: vec![],
             commands: vec![],
         }
     }
 }
 
+/// Parse a template variable in the form `name=value`
+fn parse_variable(variable: &str) -> Result<(String, String), String> {
+    match variable.split_once('=') {
+        Some((name, value)) if !name.trim().is_empty() => {
+            Ok((name.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("Invalid variable: {variable}; expected NAME=VALUE")),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -40,8 +120,34 @@ mod test {
     fn test_default() {
         let args = ShellArgs::default();
         assert_eq!(args.url, DEFAULT_URL);
+        assert!(args.profile.is_none());
         assert!(args.file.is_none());
+        assert!(args.format.is_none());
+        assert!(!args.check);
+        assert!(!args.read_only);
+        assert!(!args.load_extensions);
+        assert!(!args.store_password);
+        assert!(args.timeout.is_none());
+        assert!(!args.json);
+        assert!(args.migrate.is_none());
+        assert!(args.report.is_none());
+        assert_eq!(args.report_format, "markdown");
+        assert!(args.variables.is_empty());
         let empty_commands: Vec<String> = Vec::new();
         assert_eq!(args.commands, empty_commands);
     }
+
+    #[test]
+    fn test_parse_variable() {
+        assert_eq!(
+            parse_variable("region=EU"),
+            Ok(("region".to_string(), "EU".to_string()))
+        );
+        assert_eq!(
+            parse_variable("filter=a=b"),
+            Ok(("filter".to_string(), "a=b".to_string()))
+        );
+        assert!(parse_variable("region").is_err());
+        assert!(parse_variable("=EU").is_err());
+    }
 }
@@ -0,0 +1,169 @@
+use crate::configuration::ColumnSelection;
+use rsql_drivers::MemoryQueryResult;
+use std::time::{Duration, Instant};
+
+/// The connection, statement and settings that identify the cached result of a query; results
+/// are only reused for the same connection URL, statement, results limit and column selection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CacheKey {
+    url: String,
+    statement: String,
+    limit: usize,
+    columns: ColumnSelection,
+}
+
+impl CacheKey {
+    #[must_use]
+    pub fn new(url: &str, statement: &str, limit: usize, columns: &ColumnSelection) -> Self {
+        Self {
+            url: url.to_string(),
+            statement: statement.trim().to_string(),
+            limit,
+            columns: columns.clone(),
+        }
+    }
+}
+
+/// The result of a query stored in the cache
+#[derive(Debug)]
+pub struct CacheEntry {
+    key: CacheKey,
+    result: MemoryQueryResult,
+    created: Instant,
+    hits: u64,
+}
+
+impl CacheEntry {
+    /// Get the statement of the cached query
+    #[must_use]
+    pub fn statement(&self) -> &str {
+        &self.key.statement
+    }
+
+    /// Get the number of times the cached result has been served
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Get the time elapsed since the result was cached
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+}
+
+/// Results of queries cached per connection URL, so that repeated queries are displayed without
+/// executing them again.  Entries expire after the time to live, and the entries of a connection
+/// are evicted when a statement modifies its database.
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    entries: Vec<CacheEntry>,
+}
+
+impl ResultCache {
+    /// Get the cached entries, oldest first
+    #[must_use]
+    pub fn entries(&self) -> &[CacheEntry] {
+        &self.entries
+    }
+
+    /// Get the cached result for the key; expired entries are evicted first.  A time to live of
+    /// zero keeps entries until they are evicted.
+    pub fn get(&mut self, key: &CacheKey, ttl: Duration) -> Option<MemoryQueryResult> {
+        self.evict_expired(ttl);
+        let entry = self.entries.iter_mut().find(|entry| &entry.key == key)?;
+        entry.hits += 1;
+        Some(entry.result.clone())
+    }
+
+    /// Store the result for the key, replacing any result cached for the key
+    pub fn insert(&mut self, key: CacheKey, result: MemoryQueryResult) {
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.push(CacheEntry {
+            key,
+            result,
+            created: Instant::now(),
+            hits: 0,
+        });
+    }
+
+    /// Evict the results cached for the connection URL; returns the number of entries evicted
+    pub fn evict_url(&mut self, url: &str) -> usize {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.key.url != url);
+        len - self.entries.len()
+    }
+
+    /// Evict the entries that are older than the time to live; a time to live of zero keeps all
+    /// the entries
+    pub fn evict_expired(&mut self, ttl: Duration) {
+        if !ttl.is_zero() {
+            self.entries.retain(|entry| entry.age() < ttl);
+        }
+    }
+
+    /// Evict all the entries; returns the number of entries evicted
+    pub fn clear(&mut self) -> usize {
+        let len = self.entries.len();
+        self.entries.clear();
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsql_drivers::{QueryResult, Value};
+
+    fn key(url: &str, statement: &str) -> CacheKey {
+        CacheKey::new(url, statement, 100, &ColumnSelection::All)
+    }
+
+    fn result(value: i64) -> MemoryQueryResult {
+        MemoryQueryResult::new(vec!["id".to_string()], vec![vec![Value::I64(value)]])
+    }
+
+    #[tokio::test]
+    async fn test_get() {
+        let mut cache = ResultCache::default();
+        let ttl = Duration::from_secs(60);
+        assert!(cache.get(&key("rusqlite://", "SELECT 1"), ttl).is_none());
+
+        cache.insert(key("rusqlite://", "SELECT 1"), result(1));
+        cache.insert(key("rusqlite://", "SELECT 1 "), result(2));
+        let mut cached = cache
+            .get(&key("rusqlite://", "SELECT 1"), ttl)
+            .expect("cached result");
+        assert_eq!(cached.next().await, Some(vec![Value::I64(2)]));
+        assert_eq!(cache.entries().len(), 1);
+        assert_eq!(cache.entries()[0].statement(), "SELECT 1");
+        assert_eq!(cache.entries()[0].hits(), 1);
+
+        assert!(cache.get(&key("duckdb://", "SELECT 1"), ttl).is_none());
+        let limit_key = CacheKey::new("rusqlite://", "SELECT 1", 0, &ColumnSelection::All);
+        assert!(cache.get(&limit_key, ttl).is_none());
+    }
+
+    #[test]
+    fn test_evict_expired() {
+        let mut cache = ResultCache::default();
+        cache.insert(key("rusqlite://", "SELECT 1"), result(1));
+        cache.evict_expired(Duration::ZERO);
+        assert_eq!(cache.entries().len(), 1);
+        cache.evict_expired(Duration::from_nanos(1));
+        assert!(cache.entries().is_empty());
+    }
+
+    #[test]
+    fn test_evict_url() {
+        let mut cache = ResultCache::default();
+        cache.insert(key("rusqlite://", "SELECT 1"), result(1));
+        cache.insert(key("rusqlite://", "SELECT 2"), result(2));
+        cache.insert(key("duckdb://", "SELECT 1"), result(1));
+        assert_eq!(cache.evict_url("rusqlite://"), 2);
+        assert_eq!(cache.entries().len(), 1);
+        assert_eq!(cache.clear(), 1);
+        assert!(cache.entries().is_empty());
+    }
+}
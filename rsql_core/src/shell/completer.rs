@@ -1,6 +1,7 @@
-use rsql_drivers::{Metadata, Table};
-use rustyline::completion::{Candidate, Completer, Pair};
+use rsql_drivers::{Metadata, Schema, Table};
+use rustyline::completion::{Candidate, Completer, FilenameCompleter, Pair};
 use rustyline::Context;
+use sqlparser::dialect::Dialect;
 use sqlparser::keywords::Keyword;
 use sqlparser::tokenizer::{Token, TokenWithLocation, Tokenizer};
 use std::matches;
@@ -11,6 +12,27 @@ use crate::configuration::Configuration;
 
 static CANDIDATES: LazyLock<Vec<Pair>> = LazyLock::new(init_candidates);
 
+/// The URL schemes of the drivers that read files, e.g. `csv://data/users.csv`
+const FILE_SCHEMES: [&str; 17] = [
+    "arrow",
+    "avro",
+    "csv",
+    "delimited",
+    "dir",
+    "duckdb",
+    "excel",
+    "file",
+    "json",
+    "jsonl",
+    "ods",
+    "parquet",
+    "rusqlite",
+    "sqlite",
+    "tsv",
+    "xml",
+    "yaml",
+];
+
 #[expect(clippy::too_many_lines)]
 fn init_candidates() -> Vec<Pair> {
     vec![
@@ -192,6 +214,8 @@ impl Default for Suggestion {
 pub struct ReplCompleter {
     metadata: Metadata,
     smart_completions: bool,
+    command_identifier: String,
+    commands: Vec<(String, Vec<String>)>,
 }
 
 impl ReplCompleter {
@@ -199,13 +223,73 @@ impl ReplCompleter {
         let mut completer = Self::new(metadata);
         completer.smart_completions = configuration.smart_completions;
         completer
+            .command_identifier
+            .clone_from(&configuration.command_identifier);
+        completer
     }
 
     pub fn new(metadata: Metadata) -> Self {
         Self {
             metadata,
             smart_completions: true,
+            command_identifier: ".".to_string(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Set the command names and the values used to complete the first argument of each command
+    #[must_use]
+    pub fn with_commands(mut self, commands: Vec<(String, Vec<String>)>) -> Self {
+        self.commands = commands;
+        self
+    }
+
+    /// Completes the command name, or the first argument of the command, when the input starts
+    /// with the command identifier.  Returns `None` when the input is not a command.
+    fn command_completions(&self, input: &str) -> Option<(usize, Vec<Pair>)> {
+        let command = input
+            .trim_start()
+            .strip_prefix(self.command_identifier.as_str())?;
+        let to_pair = |value: &String| Pair {
+            display: value.clone(),
+            replacement: value.clone(),
+        };
+
+        let Some((command_name, argument)) = command.split_once(char::is_whitespace) else {
+            let candidates = self
+                .commands
+                .iter()
+                .map(|(name, _)| name)
+                .filter(|name| name.starts_with(command))
+                .map(to_pair)
+                .collect();
+            return Some((input.len() - command.len(), candidates));
+        };
+
+        let argument = argument.trim_start();
+        if argument.contains(char::is_whitespace) {
+            return Some((input.len(), Vec::new()));
         }
+        if let Some(path) = file_url_path(argument) {
+            let offset = input.len() - path.len();
+            return match FilenameCompleter::new().complete_path(path, path.len()) {
+                Ok((start, candidates)) => Some((offset + start, candidates)),
+                Err(_) => Some((input.len(), Vec::new())),
+            };
+        }
+        let candidates = self
+            .commands
+            .iter()
+            .find(|(name, _)| name == command_name)
+            .map(|(_, values)| {
+                values
+                    .iter()
+                    .filter(|value| value.starts_with(argument))
+                    .map(to_pair)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some((input.len() - argument.len(), candidates))
     }
 
     /// identifies table names and aliases in `tokens`
@@ -241,20 +325,25 @@ impl ReplCompleter {
 
         debug!("Table aliases found: {:?}", table_aliases);
 
-        let tables = if let Some(schema) = self.metadata.current_schema() {
-            tokens_no_location
-                .iter()
-                .filter_map(|token| {
-                    if let Token::Word(word) = token {
-                        schema.get(&word.value)
-                    } else {
-                        None
+        let tables: Vec<&Table> = tokens_no_location
+            .iter()
+            .enumerate()
+            .filter_map(|(index, token)| {
+                let Token::Word(word) = token else {
+                    return None;
+                };
+                let qualifier = index
+                    .checked_sub(2)
+                    .map(|qualifier_index| &tokens_no_location[qualifier_index..index]);
+                let schema = match qualifier {
+                    Some([Token::Word(schema), Token::Period]) => {
+                        self.metadata.get(schema.value.as_str())
                     }
-                })
-                .collect()
-        } else {
-            vec![]
-        };
+                    _ => self.metadata.current_schema(),
+                };
+                schema?.get(&word.value)
+            })
+            .collect();
 
         tables
             .into_iter()
@@ -385,6 +474,8 @@ impl ReplCompleter {
         suggestion: Suggestion,
     ) -> Vec<Pair> {
         debug!("Suggestion: {suggestion:?}");
+        let dialect = self.metadata.dialect();
+        let dialect = dialect.as_ref();
         match suggestion {
             Suggestion::Keyword(starts_with) => CANDIDATES
                 .iter()
@@ -392,19 +483,25 @@ impl ReplCompleter {
                 .cloned()
                 .collect(),
             Suggestion::Table(starts_with) => {
-                if let Some(schema) = self.metadata.current_schema() {
-                    schema
-                        .tables()
-                        .iter()
-                        .filter(|table| table.name().starts_with(starts_with.trim()))
-                        .map(|table| Pair {
-                            display: format!("Table: {}", table.name()),
-                            replacement: table.name().to_string(),
-                        })
-                        .collect()
+                let starts_with = starts_with.trim();
+                let mut candidates = if let Some(schema) = self.metadata.current_schema() {
+                    table_candidates(dialect, schema, starts_with)
                 } else {
                     vec![]
-                }
+                };
+                candidates.extend(
+                    self.metadata
+                        .schemas()
+                        .iter()
+                        .filter(|schema| {
+                            !schema.current() && schema.name().starts_with(starts_with)
+                        })
+                        .map(|schema| Pair {
+                            display: format!("Schema: {}", schema.name()),
+                            replacement: format!("{}.", quote_identifier(dialect, schema.name())),
+                        }),
+                );
+                candidates
             }
             Suggestion::TableColumn(table_name) => tables
                 .iter()
@@ -414,15 +511,7 @@ impl ReplCompleter {
                         Token::Period => String::new(),
                         token => token.to_string().trim().to_string(),
                     };
-                    table
-                        .columns()
-                        .into_iter()
-                        .filter(|column| column.name().starts_with(&prefix))
-                        .map(|column| Pair {
-                            display: format!("Column: {}", column.name()),
-                            replacement: column.name().to_string(),
-                        })
-                        .collect::<Vec<Pair>>()
+                    column_candidates(dialect, table, &prefix)
                 })
                 .unwrap_or_default(),
             Suggestion::TableInQuery(prefix) => tables
@@ -446,12 +535,52 @@ impl ReplCompleter {
                 .iter()
                 .map(|schema| Pair {
                     display: format!("Schema: {}", schema.name()),
-                    replacement: schema.name().to_string(),
+                    replacement: quote_identifier(dialect, schema.name()),
                 })
                 .collect(),
         }
     }
 
+    /// Complete a qualified identifier at the end of the input; `schema.` completes the tables of
+    /// the schema and `schema.table.` completes the columns of the table.  Any schema in the
+    /// metadata can be used, not only the current schema.  Returns `None` when the input does not
+    /// end with an identifier qualified by a known schema.
+    fn qualified_completions(&self, input: &str) -> Option<(usize, Vec<Pair>)> {
+        let dialect = self.metadata.dialect();
+        let dialect = dialect.as_ref();
+        let tokens = Tokenizer::new(dialect, input).tokenize().ok()?;
+
+        let mut index = tokens.len();
+        let prefix = match tokens.last()? {
+            Token::Word(word) if word.quote_style.is_none() => {
+                index -= 1;
+                word.value.clone()
+            }
+            Token::Period => String::new(),
+            _ => return None,
+        };
+
+        let mut qualifiers = Vec::new();
+        while index >= 2 && tokens[index - 1] == Token::Period {
+            let Token::Word(word) = &tokens[index - 2] else {
+                break;
+            };
+            qualifiers.insert(0, word.value.clone());
+            index -= 2;
+        }
+
+        let candidates = match qualifiers.as_slice() {
+            [schema] => table_candidates(dialect, self.metadata.get(schema.as_str())?, &prefix),
+            [schema, table] => {
+                let table = self.metadata.get(schema.as_str())?.get(table.as_str())?;
+                column_candidates(dialect, table, &prefix)
+            }
+            _ => return None,
+        };
+        let start = input.len().saturating_sub(prefix.len());
+        Some((start, candidates))
+    }
+
     fn get_completions(
         &self,
         token_at_cursor: &TokenWithLocation,
@@ -472,6 +601,13 @@ impl Completer for ReplCompleter {
         pos: usize,
         _ctx: &Context,
     ) -> Result<(usize, Vec<Self::Candidate>), rustyline::error::ReadlineError> {
+        if let Some(completions) = line
+            .get(..pos)
+            .and_then(|input| self.command_completions(input))
+        {
+            return Ok(completions);
+        }
+
         if !self.smart_completions {
             let start = line[..pos]
                 .rfind(|c: char| c.is_whitespace())
@@ -485,6 +621,13 @@ impl Completer for ReplCompleter {
             return Ok((start, candidates));
         }
 
+        if let Some(completions) = line
+            .get(..pos)
+            .and_then(|input| self.qualified_completions(input))
+        {
+            return Ok(completions);
+        }
+
         let cursor_location = u64::try_from(pos).unwrap_or(0);
         debug!("looking for completions line: {line}, pos: {cursor_location}");
         let dialect = self.metadata.dialect();
@@ -514,6 +657,50 @@ impl Completer for ReplCompleter {
     }
 }
 
+/// Create the candidates for the tables of the schema that start with the prefix
+fn table_candidates(dialect: &dyn Dialect, schema: &Schema, prefix: &str) -> Vec<Pair> {
+    schema
+        .tables()
+        .iter()
+        .filter(|table| table.name().starts_with(prefix))
+        .map(|table| Pair {
+            display: format!("Table: {}", table.name()),
+            replacement: quote_identifier(dialect, table.name()),
+        })
+        .collect()
+}
+
+/// Create the candidates for the columns of the table that start with the prefix
+fn column_candidates(dialect: &dyn Dialect, table: &Table, prefix: &str) -> Vec<Pair> {
+    table
+        .columns()
+        .into_iter()
+        .filter(|column| column.name().starts_with(prefix))
+        .map(|column| Pair {
+            display: format!("Column: {}", column.name()),
+            replacement: quote_identifier(dialect, column.name()),
+        })
+        .collect()
+}
+
+/// Quote the identifier with the quote style of the dialect unless it only contains lowercase
+/// letters, digits and underscores and does not start with a digit.
+pub(crate) fn quote_identifier(dialect: &dyn Dialect, identifier: &str) -> String {
+    let mut chars = identifier.chars();
+    let unquoted = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if unquoted {
+        return identifier.to_string();
+    }
+
+    let quote = dialect.identifier_quote_style(identifier).unwrap_or('"');
+    let end_quote = if quote == '[' { ']' } else { quote };
+    let escaped = identifier.replace(end_quote, &format!("{end_quote}{end_quote}"));
+    format!("{quote}{escaped}{end_quote}")
+}
+
 fn find_previous_keyword(
     tokens: &[TokenWithLocation],
     index: usize,
@@ -527,6 +714,17 @@ fn find_previous_keyword(
     })
 }
 
+/// Get the path of a URL with the scheme of a driver that reads files, e.g. `data/` for
+/// `csv://data/`.  Returns `None` for the URLs of other drivers, and once the query parameters of
+/// the URL are being entered.
+fn file_url_path(argument: &str) -> Option<&str> {
+    let (scheme, path) = argument.split_once("://")?;
+    if !FILE_SCHEMES.contains(&scheme) || path.contains('?') {
+        return None;
+    }
+    Some(path)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -549,6 +747,116 @@ mod test {
         Ok(())
     }
 
+    fn command_completer() -> ReplCompleter {
+        ReplCompleter::new(create_mock_metadata()).with_commands(vec![
+            ("exit".to_string(), Vec::new()),
+            (
+                "format".to_string(),
+                vec!["ascii".to_string(), "unicode".to_string()],
+            ),
+            (
+                "footer".to_string(),
+                vec!["on".to_string(), "off".to_string()],
+            ),
+            (
+                "timer".to_string(),
+                vec!["on".to_string(), "verbose".to_string(), "off".to_string()],
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_command_name_completion() -> anyhow::Result<()> {
+        let completer = command_completer();
+        let history = DefaultHistory::new();
+        let context = Context::new(&history);
+
+        let (start, candidates) = completer.complete(".for", 4, &context)?;
+        assert_eq!(start, 1);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].replacement(), "format");
+
+        let (start, candidates) = completer.complete(".f", 2, &context)?;
+        assert_eq!(start, 1);
+        let replacements: Vec<_> = candidates.iter().map(Pair::replacement).collect();
+        assert_eq!(replacements, vec!["format", "footer"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_argument_completion() -> anyhow::Result<()> {
+        let completer = command_completer();
+        let history = DefaultHistory::new();
+        let context = Context::new(&history);
+
+        let (start, candidates) = completer.complete(".format a", 9, &context)?;
+        assert_eq!(start, 8);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].replacement(), "ascii");
+
+        let (start, candidates) = completer.complete(".timer ", 7, &context)?;
+        assert_eq!(start, 7);
+        assert_eq!(candidates.len(), 3);
+
+        let (_, candidates) = completer.complete(".timer v", 8, &context)?;
+        assert_eq!(candidates[0].replacement(), "verbose");
+
+        let (_, candidates) = completer.complete(".exit o", 7, &context)?;
+        assert!(candidates.is_empty());
+
+        let (_, candidates) = completer.complete(".timer on o", 11, &context)?;
+        assert!(candidates.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_url_path() {
+        assert_eq!(file_url_path("csv://data/"), Some("data/"));
+        assert_eq!(file_url_path("parquet://"), Some(""));
+        assert_eq!(file_url_path("csv://users.csv?has_header=false"), None);
+        assert_eq!(file_url_path("postgresql://localhost/db"), None);
+        assert_eq!(file_url_path("prod"), None);
+    }
+
+    #[test]
+    fn test_command_file_url_completion() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        std::fs::write(directory.path().join("users.csv"), "id\n1\n")?;
+        std::fs::create_dir(directory.path().join("data"))?;
+        let path = directory.path().to_string_lossy().replace('\\', "/");
+        let completer = command_completer();
+        let history = DefaultHistory::new();
+        let context = Context::new(&history);
+
+        let input = format!(".connect csv://{path}/us");
+        let (start, candidates) = completer.complete(&input, input.len(), &context)?;
+        assert_eq!(start, ".connect csv://".len());
+        assert_eq!(candidates.len(), 1);
+        assert!(candidates[0].replacement().ends_with("users.csv"));
+
+        let input = format!(".connect csv://{path}/users.csv?has");
+        let (_, candidates) = completer.complete(&input, input.len(), &context)?;
+        assert!(candidates.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_command_completion_custom_identifier() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            command_identifier: "\\".to_string(),
+            ..Default::default()
+        };
+        let completer = ReplCompleter::with_config(&configuration, create_mock_metadata())
+            .with_commands(vec![("timer".to_string(), Vec::new())]);
+        let history = DefaultHistory::new();
+        let context = Context::new(&history);
+
+        let (start, candidates) = completer.complete("\\ti", 3, &context)?;
+        assert_eq!(start, 1);
+        assert_eq!(candidates[0].replacement(), "timer");
+        Ok(())
+    }
+
     #[test]
     fn test_keyword_completion() {
         let metadata = create_mock_metadata();
@@ -784,6 +1092,104 @@ mod test {
         metadata
     }
 
+    fn create_multi_schema_metadata() -> Metadata {
+        let mut metadata = create_mock_metadata();
+        let mut schema = Schema::new("sales", false);
+        let mut invoices_table = Table::new("invoices");
+        invoices_table.add_column(Column::new("id", "INT", false, None));
+        invoices_table.add_column(Column::new("Amount Due", "DECIMAL", true, None));
+        schema.add(invoices_table);
+        schema.add(Table::new("Line Items"));
+        metadata.add(schema);
+        metadata
+    }
+
+    #[test]
+    fn test_schema_table_completion() {
+        let completer = ReplCompleter::new(create_multi_schema_metadata());
+        let (start, candidates) = completer
+            .complete(
+                "SELECT * FROM sales.",
+                20,
+                &Context::new(&DefaultHistory::new()),
+            )
+            .expect("valid completion call");
+
+        assert_eq!(start, 20);
+        assert!(candidates.iter().any(|c| c.replacement() == "invoices"));
+        assert!(candidates
+            .iter()
+            .any(|c| c.replacement() == "\"Line Items\""));
+        assert!(!candidates.iter().any(|c| c.replacement() == "users"));
+
+        let (start, candidates) = completer
+            .complete(
+                "SELECT * FROM sales.inv",
+                23,
+                &Context::new(&DefaultHistory::new()),
+            )
+            .expect("valid completion call");
+        assert_eq!(start, 20);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].replacement(), "invoices");
+    }
+
+    #[test]
+    fn test_schema_table_column_completion() {
+        let completer = ReplCompleter::new(create_multi_schema_metadata());
+        let (start, candidates) = completer
+            .complete(
+                "SELECT sales.invoices.",
+                22,
+                &Context::new(&DefaultHistory::new()),
+            )
+            .expect("valid completion call");
+
+        assert_eq!(start, 22);
+        assert!(candidates.iter().any(|c| c.replacement() == "id"));
+        assert!(candidates
+            .iter()
+            .any(|c| c.replacement() == "\"Amount Due\""));
+    }
+
+    #[test]
+    fn test_schema_table_alias_completion() {
+        let completer = ReplCompleter::new(create_multi_schema_metadata());
+        let (start, candidates) = completer
+            .complete(
+                "SELECT i. FROM sales.invoices i",
+                9,
+                &Context::new(&DefaultHistory::new()),
+            )
+            .expect("valid completion call");
+
+        assert_eq!(start, 9);
+        assert!(candidates.iter().any(|c| c.replacement() == "id"));
+    }
+
+    #[test]
+    fn test_completion_after_from_includes_schemas() {
+        let completer = ReplCompleter::new(create_multi_schema_metadata());
+        let (_, candidates) = completer
+            .complete("SELECT * FROM ", 14, &Context::new(&DefaultHistory::new()))
+            .expect("valid completion call");
+
+        assert!(candidates.iter().any(|c| c.replacement() == "users"));
+        assert!(candidates.iter().any(|c| c.replacement() == "sales."));
+        assert!(!candidates.iter().any(|c| c.replacement() == "public."));
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        let dialect = GenericDialect;
+        assert_eq!(quote_identifier(&dialect, "users"), "users");
+        assert_eq!(quote_identifier(&dialect, "_user_2"), "_user_2");
+        assert_eq!(quote_identifier(&dialect, "Users"), "\"Users\"");
+        assert_eq!(quote_identifier(&dialect, "line items"), "\"line items\"");
+        assert_eq!(quote_identifier(&dialect, "2fa"), "\"2fa\"");
+        assert_eq!(quote_identifier(&dialect, "a\"b"), "\"a\"\"b\"");
+    }
+
     #[test]
     fn test_find_previous_keyword() {
         let sql = "SELECT * FROM orders o JOIN users u ON o.user_id = u.id WHERE u.id = 7 AND o.total > 10.0";
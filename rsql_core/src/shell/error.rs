@@ -1,3 +1,5 @@
+use crate::error_code::ErrorCode;
+
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 #[derive(Debug, thiserror::Error)]
@@ -16,6 +18,49 @@ pub enum Error {
     UnknownError(anyhow::Error),
 }
 
+impl Error {
+    /// Get the error code for this error.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::DriverError(error) => ErrorCode::from(error),
+            Error::ExecutorError(error) => error.code(),
+            Error::IoError(_) => ErrorCode::Io,
+            Error::UnknownError(_) => ErrorCode::Unknown,
+        }
+    }
+
+    /// Get the message that should be displayed to the user.
+    #[must_use]
+    pub fn user_message(&self) -> String {
+        self.to_string()
+    }
+
+    /// Get the detailed message, including the error code and all of the underlying causes,
+    /// that is intended for logs and bug reports rather than end users.
+    #[must_use]
+    pub fn internal_message(&self) -> String {
+        let code = self.code();
+        let sources = self.sources().join(": ");
+        format!("[{code}] {sources} ({self:?})")
+    }
+
+    /// Get the messages for this error followed by the messages of each of its causes.
+    #[must_use]
+    pub fn sources(&self) -> Vec<String> {
+        let mut sources = vec![self.to_string()];
+        let mut source = std::error::Error::source(self);
+        while let Some(error) = source {
+            let message = error.to_string();
+            if sources.last() != Some(&message) {
+                sources.push(message);
+            }
+            source = error.source();
+        }
+        sources
+    }
+}
+
 /// Converts a [`clap_stdin::StdinError`] into an [`IoError`](Error::IoError)
 impl From<clap_stdin::StdinError> for Error {
     fn from(error: clap_stdin::StdinError) -> Self {
@@ -50,6 +95,55 @@ mod tests {
     use indicatif::ProgressStyle;
     use test_log::test;
 
+    #[test]
+    fn test_driver_error_code() {
+        let error = Error::from(rsql_drivers::Error::DriverNotFound("foo".to_string()));
+        assert_eq!(error.code(), ErrorCode::DriverNotFound);
+        assert_eq!(error.user_message(), "driver not found for: foo");
+    }
+
+    #[test]
+    fn test_executor_error_code() {
+        let error = Error::from(crate::executors::Error::InvalidCommand {
+            command_name: "foo".to_string(),
+        });
+        assert_eq!(error.code(), ErrorCode::InvalidCommand);
+        assert_eq!(error.user_message(), "Invalid command foo");
+        assert!(error
+            .internal_message()
+            .starts_with("[E0300] Invalid command foo"));
+    }
+
+    #[test]
+    fn test_command_error_code() {
+        let error = Error::from(crate::executors::Error::from(
+            crate::commands::Error::MissingArguments {
+                command_name: "sleep".to_string(),
+                arguments: "seconds".to_string(),
+            },
+        ));
+        assert_eq!(error.code(), ErrorCode::MissingArguments);
+    }
+
+    #[test]
+    fn test_formatter_error_code() {
+        let error = Error::from(crate::executors::Error::from(
+            rsql_formatters::Error::UnknownFormat {
+                format: "foo".to_string(),
+            },
+        ));
+        assert_eq!(error.code(), ErrorCode::UnknownFormat);
+    }
+
+    #[test]
+    fn test_sources() {
+        let error = anyhow::Error::from(std::io::Error::new(std::io::ErrorKind::Other, "cause"))
+            .context("context");
+        let error = Error::UnknownError(error);
+        assert_eq!(error.code(), ErrorCode::Unknown);
+        assert_eq!(error.sources(), vec!["context", "cause"]);
+    }
+
     #[test]
     fn test_stdin_error() {
         let error = clap_stdin::StdinError::FromStr("test".to_string());
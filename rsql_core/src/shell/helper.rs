@@ -1,8 +1,9 @@
+use crate::commands::{CommandManager, CompletionOptions};
 use crate::configuration::Configuration;
 use crate::shell::completer::ReplCompleter;
 use crate::shell::Result;
 use rsql_drivers::{Connection, Metadata};
-use rsql_formatters::Highlighter;
+use rsql_formatters::{FormatterManager, Highlighter};
 use rustyline::completion::Completer;
 use rustyline::hint::{Hinter, HistoryHinter};
 use rustyline::validate::{ValidationContext, ValidationResult, Validator};
@@ -11,32 +12,55 @@ use rustyline::{Context, Helper};
 pub(crate) struct ReplHelper {
     pub(crate) highlighter: Highlighter,
     pub(crate) completer: ReplCompleter,
+    pub(crate) prompt_color: Option<String>,
+    pub(crate) autosuggestions: bool,
+    pub(crate) color: bool,
 }
 
 impl ReplHelper {
     #[cfg(test)]
     pub(crate) fn new(configuration: &Configuration) -> Self {
-        Self::new_with_metadata(configuration, Metadata::default())
+        Self::new_with_metadata(configuration, Metadata::default(), Vec::new())
     }
 
     pub(crate) async fn with_connection(
         configuration: &Configuration,
+        command_manager: &CommandManager,
+        formatter_manager: &FormatterManager,
         connection: &mut dyn Connection,
     ) -> Result<Self> {
-        Ok(Self::new_with_metadata(
-            configuration,
-            connection.metadata().await?,
-        ))
+        let metadata = connection.metadata().await?;
+        let locale = configuration.locale.as_str();
+        let options = CompletionOptions {
+            locale,
+            command_manager,
+            formatter_manager,
+            metadata: &metadata,
+        };
+        let commands = command_manager
+            .iter()
+            .map(|command| (command.name(locale), command.completions(&options)))
+            .collect();
+        Ok(Self::new_with_metadata(configuration, metadata, commands))
     }
 
-    pub(crate) fn new_with_metadata(configuration: &Configuration, metadata: Metadata) -> Self {
+    pub(crate) fn new_with_metadata(
+        configuration: &Configuration,
+        metadata: Metadata,
+        commands: Vec<(String, Vec<String>)>,
+    ) -> Self {
         let options = configuration.get_formatter_options();
         let highlighter = Highlighter::new(&options, "sql");
-        let completer = ReplCompleter::with_config(configuration, metadata);
+        let completer = ReplCompleter::with_config(configuration, metadata).with_commands(commands);
+
+        let prompt_color = configuration.prompt_color.clone();
 
         Self {
             highlighter,
             completer,
+            prompt_color,
+            autosuggestions: configuration.autosuggestions,
+            color: configuration.color,
         }
     }
 }
@@ -59,7 +83,12 @@ impl Completer for ReplHelper {
 impl Hinter for ReplHelper {
     type Hint = String;
 
+    /// Suggest the remainder of the most recent history entry that starts with the line when the
+    /// cursor is at the end of the line.
     fn hint(&self, line: &str, pos: usize, ctx: &Context<'_>) -> Option<Self::Hint> {
+        if !self.autosuggestions {
+            return None;
+        }
         let hinter = HistoryHinter {};
         hinter.hint(line, pos, ctx)
     }
@@ -74,7 +103,7 @@ impl Validator for ReplHelper {
 #[cfg(test)]
 mod test {
     use super::*;
-    use rustyline::history::DefaultHistory;
+    use rustyline::history::{DefaultHistory, History};
 
     #[test]
     fn test_new() {
@@ -91,4 +120,38 @@ mod test {
         let hint = helper.hint("SELECT", 0, &ctx);
         assert!(hint.is_none());
     }
+
+    #[test]
+    fn test_hinter_history() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let helper = ReplHelper::new(&configuration);
+        let mut history = DefaultHistory::new();
+        history.add("SELECT * FROM users")?;
+        history.add("SELECT * FROM orders")?;
+        let ctx = Context::new(&history);
+        let line = "SELECT * FROM u";
+        assert_eq!(helper.hint(line, line.len(), &ctx), Some("sers".to_string()));
+        let line = "SELECT";
+        assert_eq!(
+            helper.hint(line, line.len(), &ctx),
+            Some(" * FROM orders".to_string())
+        );
+        assert!(helper.hint(line, 0, &ctx).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_hinter_disabled() -> anyhow::Result<()> {
+        let configuration = Configuration {
+            autosuggestions: false,
+            ..Default::default()
+        };
+        let helper = ReplHelper::new(&configuration);
+        let mut history = DefaultHistory::new();
+        history.add("SELECT * FROM users")?;
+        let ctx = Context::new(&history);
+        let line = "SELECT";
+        assert!(helper.hint(line, line.len(), &ctx).is_none());
+        Ok(())
+    }
 }
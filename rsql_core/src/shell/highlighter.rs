@@ -1,4 +1,5 @@
 use crate::shell::helper::ReplHelper;
+use colored::Colorize;
 use rustyline::highlight::{CmdKind, Highlighter};
 use std::borrow::Cow;
 
@@ -7,6 +8,25 @@ impl Highlighter for ReplHelper {
         self.highlighter.highlight(line).expect("highlight")
     }
 
+    fn highlight_prompt<'b, 's: 'b, 'p: 'b>(
+        &'s self,
+        prompt: &'p str,
+        _default: bool,
+    ) -> Cow<'b, str> {
+        match &self.prompt_color {
+            Some(color) => Cow::Owned(prompt.color(color.as_str()).to_string()),
+            None => Cow::Borrowed(prompt),
+        }
+    }
+
+    fn highlight_hint<'h>(&self, hint: &'h str) -> Cow<'h, str> {
+        if self.color {
+            Cow::Owned(hint.dimmed().to_string())
+        } else {
+            Cow::Borrowed(hint)
+        }
+    }
+
     fn highlight_char(&self, line: &str, pos: usize, _kind: CmdKind) -> bool {
         let _ = (line, pos);
         true
@@ -42,6 +62,27 @@ mod test {
         assert!(highlighted.contains(line));
     }
 
+    #[test]
+    fn test_highlight_prompt() {
+        let configuration = Configuration::default();
+        let helper = ReplHelper::new(&configuration);
+        let prompt = "rsql> ";
+        let highlighted = helper.highlight_prompt(prompt, true);
+        assert_eq!(highlighted, prompt);
+    }
+
+    #[test]
+    fn test_highlight_prompt_color() {
+        let configuration = Configuration {
+            prompt_color: Some("red".to_string()),
+            ..Default::default()
+        };
+        let helper = ReplHelper::new(&configuration);
+        let prompt = "rsql> ";
+        let highlighted = helper.highlight_prompt(prompt, true);
+        assert!(highlighted.contains(prompt));
+    }
+
     #[test]
     fn test_highlight_char() {
         let configuration = Configuration::default();
@@ -50,4 +91,24 @@ mod test {
         let highlighted = helper.highlight_char(line, 0, CmdKind::ForcedRefresh);
         assert!(highlighted);
     }
+
+    #[test]
+    fn test_highlight_hint() {
+        let configuration = Configuration {
+            color: false,
+            ..Default::default()
+        };
+        let helper = ReplHelper::new(&configuration);
+        let hint = " FROM users";
+        assert_eq!(helper.highlight_hint(hint), hint);
+    }
+
+    #[test]
+    fn test_highlight_hint_color() {
+        let configuration = Configuration::default();
+        let helper = ReplHelper::new(&configuration);
+        let hint = " FROM users";
+        let highlighted = helper.highlight_hint(hint);
+        assert!(highlighted.contains(hint));
+    }
 }
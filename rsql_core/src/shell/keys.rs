@@ -0,0 +1,316 @@
+use anyhow::{bail, Result};
+use rustyline::{
+    Cmd, ConditionalEventHandler, Event, EventContext, EventHandler, KeyCode, KeyEvent, Modifiers,
+    RepeatCount,
+};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+
+/// The shell actions that can be bound to a key
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyAction {
+    /// Execute the statement being entered, even when it is not complete
+    Execute,
+    /// Clear the screen
+    ClearScreen,
+    /// Toggle the display of wide results in the expanded format
+    ToggleExpanded,
+    /// Edit the statement being entered, or the last statement, in an external editor
+    Edit,
+}
+
+impl KeyAction {
+    /// Get the name of the action used in the configuration
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyAction::Execute => "execute",
+            KeyAction::ClearScreen => "clear_screen",
+            KeyAction::ToggleExpanded => "toggle_expanded",
+            KeyAction::Edit => "edit",
+        }
+    }
+}
+
+impl FromStr for KeyAction {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().replace('-', "_").as_str() {
+            "execute" => Ok(KeyAction::Execute),
+            "clear_screen" => Ok(KeyAction::ClearScreen),
+            "toggle_expanded" => Ok(KeyAction::ToggleExpanded),
+            "edit" => Ok(KeyAction::Edit),
+            _ => bail!("Invalid key action: {value}"),
+        }
+    }
+}
+
+impl Display for KeyAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A key, such as `ctrl-e`, `alt-enter` or `f5`, bound to a shell action
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyBinding {
+    pub key: String,
+    pub action: KeyAction,
+}
+
+impl KeyBinding {
+    /// Create a new key binding; the key is validated and normalized to lower case.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not valid.
+    pub fn new<S: AsRef<str>>(key: S, action: KeyAction) -> Result<Self> {
+        let key = key.as_ref().trim().to_lowercase();
+        parse_key(&key)?;
+        Ok(Self { key, action })
+    }
+
+    /// Get the rustyline key event for the key
+    fn key_event(&self) -> Result<KeyEvent> {
+        parse_key(&self.key)
+    }
+}
+
+/// Get the default key bindings
+#[must_use]
+pub fn default_key_bindings() -> Vec<KeyBinding> {
+    vec![
+        KeyBinding {
+            key: "alt-enter".to_string(),
+            action: KeyAction::Execute,
+        },
+        KeyBinding {
+            key: "ctrl-l".to_string(),
+            action: KeyAction::ClearScreen,
+        },
+        KeyBinding {
+            key: "alt-x".to_string(),
+            action: KeyAction::ToggleExpanded,
+        },
+        KeyBinding {
+            key: "alt-e".to_string(),
+            action: KeyAction::Edit,
+        },
+    ]
+}
+
+/// Parse a key such as `ctrl-e`, `alt-enter`, `ctrl-alt-x` or `f5` into a rustyline key event;
+/// the modifiers (`ctrl`, `alt` and `shift`) are separated from the key by `-` or `+`.
+///
+/// # Errors
+///
+/// Returns an error if the key or a modifier is not valid.
+pub fn parse_key(key: &str) -> Result<KeyEvent> {
+    let parts: Vec<String> = key
+        .split(['-', '+'])
+        .map(|part| part.trim().to_lowercase())
+        .collect();
+    let Some((key_name, modifier_names)) = parts.split_last() else {
+        bail!("Invalid key: {key}");
+    };
+
+    let mut modifiers = Modifiers::NONE;
+    for modifier in modifier_names {
+        modifiers |= match modifier.as_str() {
+            "ctrl" | "control" => Modifiers::CTRL,
+            "alt" | "meta" => Modifiers::ALT,
+            "shift" => Modifiers::SHIFT,
+            _ => bail!("Invalid key: {key}"),
+        };
+    }
+
+    let key_code = match key_name.as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "esc" | "escape" => KeyCode::Esc,
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "insert" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "space" => KeyCode::Char(' '),
+        name => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(char), None) if !char.is_control() => {
+                    // Control characters are reported with upper case letters
+                    let char = if modifiers.contains(Modifiers::CTRL) {
+                        char.to_ascii_uppercase()
+                    } else {
+                        char
+                    };
+                    return Ok(KeyEvent::new(char, modifiers));
+                }
+                (Some('f'), Some(_)) => match name[1..].parse::<u8>() {
+                    Ok(number) if (1..=24).contains(&number) => KeyCode::F(number),
+                    _ => bail!("Invalid key: {key}"),
+                },
+                _ => bail!("Invalid key: {key}"),
+            }
+        }
+    };
+    Ok(KeyEvent(key_code, modifiers))
+}
+
+/// Rustyline event handler that records the action of a key binding for the shell and accepts
+/// the line, so that the shell can perform the action when the line is returned; the clear screen
+/// action is performed by rustyline.
+#[derive(Debug)]
+pub(crate) struct KeyActionHandler {
+    action: KeyAction,
+    pending_action: Arc<Mutex<Option<KeyAction>>>,
+}
+
+impl KeyActionHandler {
+    /// Create the event handlers for the key bindings; the action of the key that is pressed is
+    /// stored in the pending action.
+    pub(crate) fn event_handlers(
+        key_bindings: &[KeyBinding],
+        pending_action: &Arc<Mutex<Option<KeyAction>>>,
+    ) -> Result<Vec<(Event, EventHandler)>> {
+        key_bindings
+            .iter()
+            .map(|key_binding| {
+                let handler = KeyActionHandler {
+                    action: key_binding.action,
+                    pending_action: Arc::clone(pending_action),
+                };
+                let event = Event::from(key_binding.key_event()?);
+                Ok((event, EventHandler::Conditional(Box::new(handler))))
+            })
+            .collect()
+    }
+}
+
+impl ConditionalEventHandler for KeyActionHandler {
+    fn handle(
+        &self,
+        _event: &Event,
+        _count: RepeatCount,
+        _positive: bool,
+        _context: &EventContext,
+    ) -> Option<Cmd> {
+        if self.action == KeyAction::ClearScreen {
+            return Some(Cmd::ClearScreen);
+        }
+        if let Ok(mut pending_action) = self.pending_action.lock() {
+            *pending_action = Some(self.action);
+        }
+        Some(Cmd::AcceptLine)
+    }
+}
+
+/// Rustyline event handler that accepts the autosuggestion when the end key is pressed at the end
+/// of the line; otherwise the default end of line movement is used.  The right arrow accepts the
+/// autosuggestion by default.
+#[derive(Debug)]
+pub(crate) struct AcceptHintHandler;
+
+impl AcceptHintHandler {
+    /// Create the event handler for accepting the autosuggestion
+    pub(crate) fn event_handler() -> (Event, EventHandler) {
+        let event = Event::from(KeyEvent(KeyCode::End, Modifiers::NONE));
+        (event, EventHandler::Conditional(Box::new(AcceptHintHandler)))
+    }
+}
+
+impl ConditionalEventHandler for AcceptHintHandler {
+    fn handle(
+        &self,
+        _event: &Event,
+        _count: RepeatCount,
+        _positive: bool,
+        context: &EventContext,
+    ) -> Option<Cmd> {
+        if context.has_hint() && context.pos() == context.line().len() {
+            Some(Cmd::CompleteHint)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_action_from_str() -> Result<()> {
+        assert_eq!(KeyAction::from_str("execute")?, KeyAction::Execute);
+        assert_eq!(KeyAction::from_str("clear-screen")?, KeyAction::ClearScreen);
+        assert_eq!(
+            KeyAction::from_str("TOGGLE_EXPANDED")?,
+            KeyAction::ToggleExpanded
+        );
+        assert_eq!(KeyAction::from_str("edit")?, KeyAction::Edit);
+        assert!(KeyAction::from_str("foo").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_action_display() {
+        assert_eq!(KeyAction::ToggleExpanded.to_string(), "toggle_expanded");
+    }
+
+    #[test]
+    fn test_key_binding_new() -> Result<()> {
+        let key_binding = KeyBinding::new("Ctrl-E", KeyAction::Execute)?;
+        assert_eq!(key_binding.key, "ctrl-e");
+        assert!(KeyBinding::new("hyper-e", KeyAction::Execute).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_key() -> Result<()> {
+        assert_eq!(parse_key("ctrl-e")?, KeyEvent::ctrl('E'));
+        assert_eq!(parse_key("alt+x")?, KeyEvent::alt('x'));
+        assert_eq!(
+            parse_key("alt-enter")?,
+            KeyEvent(KeyCode::Enter, Modifiers::ALT)
+        );
+        assert_eq!(
+            parse_key("ctrl-alt-x")?,
+            KeyEvent(KeyCode::Char('X'), Modifiers::CTRL_ALT)
+        );
+        assert_eq!(parse_key("f5")?, KeyEvent(KeyCode::F(5), Modifiers::NONE));
+        assert!(parse_key("").is_err());
+        assert!(parse_key("f25").is_err());
+        assert!(parse_key("foo").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_key_bindings() {
+        for key_binding in default_key_bindings() {
+            assert!(key_binding.key_event().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_event_handlers() -> Result<()> {
+        let pending_action = Arc::new(Mutex::new(None));
+        let event_handlers =
+            KeyActionHandler::event_handlers(&default_key_bindings(), &pending_action)?;
+        assert_eq!(event_handlers.len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_accept_hint_event_handler() {
+        let (event, _handler) = AcceptHintHandler::event_handler();
+        assert_eq!(event, Event::from(KeyEvent(KeyCode::End, Modifiers::NONE)));
+    }
+}
@@ -1,12 +1,25 @@
 extern crate colored;
 
 mod args;
+mod cache;
 mod completer;
 mod error;
 mod helper;
 mod highlighter;
+mod keys;
+mod prompt;
 mod repl;
+mod report;
+mod stash;
+mod state;
 
+pub use crate::error_code::ErrorCode;
 pub use args::ShellArgs;
+pub(crate) use args::DEFAULT_URL;
+pub use cache::{CacheEntry, CacheKey, ResultCache};
+pub(crate) use completer::quote_identifier;
 pub use error::{Error, Result};
+pub use keys::{default_key_bindings, parse_key, KeyAction, KeyBinding};
 pub use repl::{Shell, ShellBuilder};
+pub use stash::Stash;
+pub use state::ShellState;
@@ -0,0 +1,111 @@
+use url::Url;
+
+/// Render a prompt template for the connection url.  Variables are enclosed in braces:
+/// `{program_name}`, `{driver}`, `{user}`, `{host}`, `{port}` and `{database}`; unknown variables
+/// are displayed unchanged.
+pub(crate) fn render_prompt(template: &str, program_name: &str, url: &str) -> String {
+    let url = Url::parse(url).ok();
+    let value = |name: &str| -> Option<String> {
+        let value = match name {
+            "program_name" => program_name.to_string(),
+            "driver" => url
+                .as_ref()
+                .map(Url::scheme)
+                .unwrap_or_default()
+                .to_string(),
+            "user" => url
+                .as_ref()
+                .map(Url::username)
+                .unwrap_or_default()
+                .to_string(),
+            "host" => url
+                .as_ref()
+                .and_then(Url::host_str)
+                .unwrap_or_default()
+                .to_string(),
+            "port" => url
+                .as_ref()
+                .and_then(Url::port)
+                .map(|port| port.to_string())
+                .unwrap_or_default(),
+            "database" => url
+                .as_ref()
+                .map(|url| url.path().trim_start_matches('/'))
+                .unwrap_or_default()
+                .to_string(),
+            _ => return None,
+        };
+        Some(value)
+    };
+
+    let mut prompt = String::with_capacity(template.len());
+    let mut remaining = template;
+    while let Some(start) = remaining.find('{') {
+        prompt.push_str(&remaining[..start]);
+        let variable = &remaining[start + 1..];
+        let Some(end) = variable.find('}') else {
+            prompt.push_str(&remaining[start..]);
+            return prompt;
+        };
+        let name = &variable[..end];
+        match value(name) {
+            Some(value) => prompt.push_str(&value),
+            None => prompt.push_str(&remaining[start..start + end + 2]),
+        }
+        remaining = &variable[end + 1..];
+    }
+    prompt.push_str(remaining);
+    prompt
+}
+
+/// Get the continuation prompt for a prompt; the continuation prompt is aligned with the end of
+/// the prompt.
+pub(crate) fn continuation_prompt(prompt: &str) -> String {
+    let width = prompt.chars().count();
+    format!("{:>width$}", "-> ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_render_prompt() {
+        let prompt = render_prompt(
+            "{user}@{host}:{port}/{database} [{driver}] > ",
+            "rsql",
+            "postgresql://postgres@localhost:5432/db",
+        );
+        assert_eq!(prompt, "postgres@localhost:5432/db [postgresql] > ");
+    }
+
+    #[test]
+    fn test_render_prompt_program_name() {
+        let prompt = render_prompt("{program_name}> ", "rsql", "rusqlite://");
+        assert_eq!(prompt, "rsql> ");
+    }
+
+    #[test]
+    fn test_render_prompt_missing_values() {
+        let prompt = render_prompt("{user}@{host}:{port}/{database}> ", "rsql", "rusqlite://");
+        assert_eq!(prompt, "@:/> ");
+    }
+
+    #[test]
+    fn test_render_prompt_unknown_variable() {
+        let prompt = render_prompt("{foo} {driver} {bar", "rsql", "rusqlite://");
+        assert_eq!(prompt, "{foo} rusqlite {bar");
+    }
+
+    #[test]
+    fn test_render_prompt_invalid_url() {
+        let prompt = render_prompt("{driver}{user}> ", "rsql", "foo");
+        assert_eq!(prompt, "> ");
+    }
+
+    #[test]
+    fn test_continuation_prompt() {
+        assert_eq!(continuation_prompt("rsql> "), "   -> ");
+        assert_eq!(continuation_prompt(">"), "-> ");
+    }
+}
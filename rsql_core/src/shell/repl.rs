@@ -1,20 +1,29 @@
-use crate::commands::{help, CommandManager, LoopCondition, ShellCommand};
+use crate::commands::validate::validate;
+use crate::commands::{edit, help, migrate, send, CommandManager, LoopCondition, ShellCommand};
 use crate::configuration::Configuration;
 use crate::executors;
 use crate::executors::Executor;
 use crate::shell::helper::ReplHelper;
-use crate::shell::Result;
-use crate::shell::ShellArgs;
+use crate::shell::keys::{AcceptHintHandler, KeyAction, KeyActionHandler};
+use crate::shell::prompt::{continuation_prompt, render_prompt};
+use crate::shell::{Error, Result};
+use crate::shell::{ShellArgs, ShellState};
+use anyhow::anyhow;
 use colored::Colorize;
-use rsql_drivers::{Connection, DriverManager};
+use rsql_drivers::{AuthenticationPrompt, Connection, DriverManager};
 use rsql_formatters::writers::Output;
 use rsql_formatters::FormatterManager;
 use rustyline::config::Configurer;
 use rustyline::error::ReadlineError;
 use rustyline::history::{DefaultHistory, FileHistory};
 use rustyline::{ColorMode, CompletionType, Editor};
+use sqlparser::dialect::Dialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
 use std::fmt::Debug;
+use std::mem;
+use std::sync::{Arc, Mutex};
 use tracing::error;
+use url::Url;
 
 /// A builder for creating a [Shell].
 #[derive(Debug, Default)]
@@ -61,8 +70,25 @@ impl ShellBuilder {
 
     /// Build the shell.
     #[must_use]
-    pub fn build(self) -> Shell {
+    pub fn build(mut self) -> Shell {
         self.shell
+            .configuration
+            .configure_driver_manager(&mut self.shell.driver_manager);
+        self.shell
+            .driver_manager
+            .set_authentication_prompt(Box::new(StderrPrompt));
+        self.shell
+    }
+}
+
+/// Displays the sign in instructions of the drivers on stderr, so that they are not written to
+/// the output of the results
+#[derive(Debug)]
+struct StderrPrompt;
+
+impl AuthenticationPrompt for StderrPrompt {
+    fn display(&self, message: &str) {
+        eprintln!("{message}");
     }
 }
 
@@ -74,6 +100,7 @@ pub struct Shell {
     pub command_manager: CommandManager,
     pub formatter_manager: FormatterManager,
     pub output: Output,
+    pub state: ShellState,
 }
 
 /// Shell implementation.
@@ -84,38 +111,157 @@ impl Shell {
     ///
     /// Returns an error if the shell fails to execute.
     pub async fn execute(&mut self, args: &ShellArgs) -> Result<i32> {
-        let mut binding = self.driver_manager.connect(args.url.as_str()).await?;
-        let connection = binding.as_mut();
-        let input = if let Some(file) = &args.file {
-            Some(file.clone().contents()?)
+        let url = match &args.profile {
+            Some(name) => {
+                let profile = self
+                    .configuration
+                    .profiles
+                    .get(name)
+                    .cloned()
+                    .ok_or_else(|| Error::IoError(anyhow!("Unknown profile: {name}")))?;
+                profile.apply(&mut self.configuration);
+                profile.url
+            }
+            None => args.url.clone(),
+        };
+        if args.read_only {
+            self.configuration.read_only = true;
+        }
+        if let Some(timeout) = args.timeout {
+            self.configuration.results_timeout = timeout;
+        }
+        if args.load_extensions {
+            self.configuration.load_extensions = true;
+            self.driver_manager.set_load_extensions(true);
+        }
+        if let Some(format) = &args.format {
+            if self.formatter_manager.get(format).is_none() {
+                let error = rsql_formatters::Error::UnknownFormat {
+                    format: format.to_string(),
+                };
+                return Err(executors::Error::from(error).into());
+            }
+            self.configuration.results_format.clone_from(format);
+        }
+        if args.json {
+            self.configuration.json = true;
+            self.configuration.color = false;
+        }
+        self.state.variables.extend(args.variables.iter().cloned());
+        let mut connection = self.connect(&url).await?;
+        if args.store_password {
+            self.store_password(&url).await?;
+        }
+        let input = if args.report.is_some() {
+            None
+        } else if let Some(directory) = &args.migrate {
+            // Stop at the first failed migration and exit with an error
+            self.configuration.bail_on_error = true;
+            let command_name = migrate::Command.name(&self.configuration.locale);
+            let command_identifier = &self.configuration.command_identifier;
+            Some(format!("{command_identifier}{command_name} \"{directory}\""))
+        } else if let Some(file) = &args.file {
+            let contents = file.clone().contents()?;
+            if executors::is_template(file.filename()) {
+                Some(executors::render_template(&contents, &self.state.variables)?)
+            } else {
+                Some(contents)
+            }
         } else if !args.commands.is_empty() {
             Some(args.commands.join("\n"))
         } else {
             None
         };
 
-        let exit_code = if let Some(input) = input {
-            match &self
-                .evaluate(connection, &DefaultHistory::new(), input.to_string())
-                .await?
-            {
+        let exit_code = if let Some(report) = &args.report {
+            let contents = report.clone().contents()?;
+            self.report(&mut connection, &contents, &args.report_format).await?
+        } else if args.check {
+            let errors = validate(
+                &self.configuration,
+                &self.command_manager,
+                &self.formatter_manager,
+                connection.as_mut(),
+                input.unwrap_or_default().as_str(),
+                &mut self.output,
+            )
+            .await
+            .map_err(executors::Error::from)?;
+            i32::from(errors > 0)
+        } else if let Some(input) = input {
+            let loop_condition = self
+                .evaluate(
+                    connection.as_mut(),
+                    &DefaultHistory::new(),
+                    input.to_string(),
+                )
+                .await?;
+            self.replace_connection(&mut connection).await?;
+            match loop_condition {
                 LoopCondition::Continue => 0,
-                LoopCondition::Exit(exit_code) => *exit_code,
+                LoopCondition::Exit(exit_code) => exit_code,
             }
         } else {
-            self.repl(connection).await?
+            self.repl(&mut connection).await?
         };
 
+        self.state.stash.close().await?;
         connection.close().await?;
         Ok(exit_code)
     }
 
+    /// Connect to the database; the session is made read-only before any statement is executed
+    /// in read-only mode.
+    async fn connect(&mut self, url: &str) -> Result<Box<dyn Connection>> {
+        let mut connection = self.driver_manager.connect(url).await?;
+        if self.configuration.read_only {
+            connection.set_read_only(true).await?;
+            self.state.read_only = true;
+        }
+        Ok(connection)
+    }
+
+    /// Store the password of the URL with the credential provider of the driver manager
+    async fn store_password(&self, url: &str) -> Result<()> {
+        let url = Url::parse(url).map_err(rsql_drivers::Error::from)?;
+        let Some(password) = url.password() else {
+            return Err(Error::IoError(anyhow!(
+                "The URL does not contain a password to store"
+            )));
+        };
+        self.driver_manager
+            .store_credential_password(&url, password)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the connection with the connection opened by a command, if any, and close the
+    /// replaced connection.  Historical reads are not applied to the new connection.
+    pub(crate) async fn replace_connection(
+        &mut self,
+        connection: &mut Box<dyn Connection>,
+    ) -> Result<()> {
+        if let Some(mut replacement) = self.state.connection.take() {
+            mem::swap(connection, &mut replacement);
+            replacement.close().await?;
+            self.state.as_of = None;
+        }
+        Ok(())
+    }
+
     async fn editor(
         &self,
         history_file: &str,
         connection: &mut dyn Connection,
+        pending_action: &Arc<Mutex<Option<KeyAction>>>,
     ) -> Result<Editor<ReplHelper, FileHistory>> {
-        let helper = ReplHelper::with_connection(&self.configuration, connection).await?;
+        let helper = ReplHelper::with_connection(
+            &self.configuration,
+            &self.command_manager,
+            &self.formatter_manager,
+            connection,
+        )
+        .await?;
         let mut editor = Editor::<ReplHelper, FileHistory>::new()?;
         if self.configuration.color {
             editor.set_color_mode(ColorMode::Forced);
@@ -125,6 +271,16 @@ impl Shell {
         editor.set_edit_mode(self.configuration.edit_mode);
         editor.set_completion_type(CompletionType::Circular);
         editor.set_helper(Some(helper));
+        let event_handlers =
+            KeyActionHandler::event_handlers(&self.configuration.key_bindings, pending_action)
+                .map_err(Error::IoError)?;
+        for (event, handler) in event_handlers {
+            editor.bind_sequence(event, handler);
+        }
+        if self.configuration.autosuggestions {
+            let (event, handler) = AcceptHintHandler::event_handler();
+            editor.bind_sequence(event, handler);
+        }
 
         if self.configuration.history {
             let _ = editor.load_history(history_file);
@@ -139,56 +295,77 @@ impl Shell {
     }
 
     /// Run the Read-Eval-Print Loop (REPL) for the shell.
-    async fn repl(&mut self, connection: &mut dyn Connection) -> Result<i32> {
+    async fn repl(&mut self, connection: &mut Box<dyn Connection>) -> Result<i32> {
         let history_file = match self.configuration.history_file {
             Some(ref file) => String::from(file.to_string_lossy()),
             None => String::new(),
         };
+        let mut statement = String::new();
+        // The line being edited when a key action is performed, which is restored after the action
+        let mut initial = String::new();
+        let pending_action = Arc::new(Mutex::new(None));
         loop {
             // Create a new editor for each iteration in order to read any changes to the configuration.
-            let mut editor = self.editor(history_file.as_str(), connection).await?;
-            let locale = self.configuration.locale.as_str();
-            let prompt = t!(
-                "prompt",
-                locale = locale,
-                program_name = self.configuration.program_name,
-            );
+            let mut editor = self
+                .editor(history_file.as_str(), connection.as_mut(), &pending_action)
+                .await?;
+            let prompt = self.prompt(connection.url(), statement.is_empty());
+            let line = editor.readline_with_initial(&prompt, (&mem::take(&mut initial), ""));
+            let key_action = pending_action
+                .lock()
+                .map_err(|error| Error::IoError(anyhow!("{error}")))?
+                .take();
 
-            let loop_condition = match editor.readline(&prompt) {
+            let loop_condition = match line {
                 Ok(line) => {
-                    let loop_condition = match &self
-                        .evaluate(connection, editor.history(), line.clone())
-                        .await
-                    {
+                    let input = match key_action {
+                        Some(KeyAction::Execute) => Some(execute_input(&mut statement, &line)),
+                        Some(KeyAction::ToggleExpanded) => {
+                            self.toggle_expanded()?;
+                            initial = line;
+                            None
+                        }
+                        Some(KeyAction::Edit) => {
+                            initial = self.edit_input(&mut statement, line).await;
+                            None
+                        }
+                        Some(KeyAction::ClearScreen) | None => {
+                            let dialect = connection.dialect();
+                            self.statement_input(&mut statement, &line, dialect.as_ref())
+                        }
+                    };
+                    let Some(input) = input else {
+                        continue;
+                    };
+                    self.state.statement.clone_from(&statement);
+                    let result = self
+                        .evaluate(connection.as_mut(), editor.history(), input.clone())
+                        .await;
+                    self.replace_connection(connection).await?;
+                    // Load the statement edited by the edit command into the prompt
+                    if let Some(edited_statement) = self.state.edited_statement.take() {
+                        statement.clear();
+                        initial = edited_statement;
+                    }
+                    let loop_condition = match result {
                         Ok(LoopCondition::Continue) => LoopCondition::Continue,
-                        Ok(LoopCondition::Exit(exit_code)) => LoopCondition::Exit(*exit_code),
+                        Ok(LoopCondition::Exit(exit_code)) => LoopCondition::Exit(exit_code),
                         Err(_error) => LoopCondition::Exit(1),
                     };
 
                     if self.configuration.history {
-                        let _ = editor.add_history_entry(line.as_str());
+                        let _ = editor.add_history_entry(input.as_str());
                         editor.save_history(history_file.as_str())?;
                     }
 
                     loop_condition
                 }
-                Err(ReadlineError::Interrupted) => LoopCondition::Continue,
+                Err(ReadlineError::Interrupted) => {
+                    statement.clear();
+                    LoopCondition::Continue
+                }
                 Err(error) => {
-                    let mut error_string = t!("error", locale = locale).to_string();
-                    let error_message = format!("{error:?}");
-                    if self.configuration.color {
-                        error_string = error_string.red().to_string();
-                    }
-                    eprintln!(
-                        "{}",
-                        t!(
-                            "error_format",
-                            locale = locale,
-                            error = error_string.red(),
-                            message = error_message,
-                        )
-                    );
-                    error!(error_message);
+                    self.write_error(&Error::from(error));
                     LoopCondition::Exit(1)
                 }
             };
@@ -199,6 +376,106 @@ impl Shell {
         }
     }
 
+    /// Edit the statement being entered, or the last statement executed when nothing has been
+    /// entered, in the external editor and return the edited statement to load into the prompt;
+    /// the line is returned unchanged when the editor fails.
+    async fn edit_input(&mut self, statement: &mut String, line: String) -> String {
+        let text = if statement.is_empty() && line.trim().is_empty() {
+            self.state.last_statement.clone().unwrap_or_default()
+        } else {
+            let mut text = statement.clone();
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(&line);
+            text
+        };
+
+        match edit::edit(&text).await {
+            Ok(edited_statement) => {
+                statement.clear();
+                edited_statement
+            }
+            Err(error) => {
+                self.write_error(&Error::from(executors::Error::from(error)));
+                line
+            }
+        }
+    }
+
+    /// Toggle displaying wide results in the expanded format and display the new setting.
+    fn toggle_expanded(&mut self) -> Result<()> {
+        let configuration = &mut self.configuration;
+        configuration.results_expanded_auto = !configuration.results_expanded_auto;
+        let locale = configuration.locale.as_str();
+        let mode = if configuration.results_expanded_auto {
+            t!("expanded_auto", locale = locale)
+        } else {
+            t!("off", locale = locale)
+        };
+        let expanded_setting = t!("expanded_setting", locale = locale, mode = mode);
+        writeln!(self.output, "{expanded_setting}")?;
+        Ok(())
+    }
+
+    /// Get the prompt for the connection url; the configured prompt template is used when one is
+    /// specified, otherwise the prompt of the locale is used.
+    fn prompt(&self, url: &str, new_statement: bool) -> String {
+        let program_name = self.configuration.program_name.as_str();
+        if let Some(template) = &self.configuration.prompt {
+            let prompt = render_prompt(template, program_name, url);
+            return if new_statement {
+                prompt
+            } else {
+                continuation_prompt(&prompt)
+            };
+        }
+
+        let locale = self.configuration.locale.as_str();
+        let prompt_key = if new_statement {
+            "prompt"
+        } else {
+            "prompt_continuation"
+        };
+        t!(prompt_key, locale = locale, program_name = program_name).to_string()
+    }
+
+    /// Append the line to the statement being entered and return the input to evaluate, or `None`
+    /// when the statement is not complete and more lines should be read.  Commands are evaluated
+    /// immediately without changing the statement being entered; the send command submits the
+    /// statement even when it is not complete.
+    fn statement_input(
+        &self,
+        statement: &mut String,
+        line: &str,
+        dialect: &dyn Dialect,
+    ) -> Option<String> {
+        let command_identifier = self.configuration.command_identifier.as_str();
+        let locale = self.configuration.locale.as_str();
+        let send_command = format!("{command_identifier}{}", send::Command.name(locale));
+        let input = line.trim();
+
+        if !statement.is_empty() && input == send_command {
+            return Some(mem::take(statement));
+        }
+        if !self.configuration.multiline
+            || input.starts_with(command_identifier)
+            || (statement.is_empty() && input.is_empty())
+        {
+            return Some(line.to_string());
+        }
+
+        if !statement.is_empty() {
+            statement.push('\n');
+        }
+        statement.push_str(line);
+        if statement_complete(dialect, statement) {
+            Some(mem::take(statement))
+        } else {
+            None
+        }
+    }
+
     /// Evaluate the input line and return the loop condition.
     async fn evaluate(
         &mut self,
@@ -212,6 +489,7 @@ impl Shell {
             &self.driver_manager,
             &self.formatter_manager,
             history,
+            &mut self.state,
             connection,
             &mut self.output,
         );
@@ -226,24 +504,11 @@ impl Shell {
         match result {
             Ok(loop_condition) => Ok(loop_condition),
             Err(error) => {
-                let locale = self.configuration.locale.as_str();
-                let mut error_string = t!("error", locale = locale).to_string();
-                let error_message = format!("{error:?}");
-                if self.configuration.color {
-                    error_string = error_string.red().to_string();
-                }
-                eprintln!(
-                    "{}",
-                    t!(
-                        "error_format",
-                        locale = locale,
-                        error = error_string.red(),
-                        message = error_message,
-                    )
-                );
+                let error = Error::from(error);
+                self.write_error(&error);
 
                 if self.configuration.bail_on_error {
-                    Err(error.into())
+                    Err(error)
                 } else {
                     Ok(LoopCondition::Continue)
                 }
@@ -251,6 +516,25 @@ impl Shell {
         }
     }
 
+    /// Display the user facing message for an error and log the internal details.
+    pub(crate) fn write_error(&self, error: &Error) {
+        let locale = self.configuration.locale.as_str();
+        let mut error_string = t!("error", locale = locale).to_string();
+        if self.configuration.color {
+            error_string = error_string.red().to_string();
+        }
+        eprintln!(
+            "{}",
+            t!(
+                "error_format",
+                locale = locale,
+                error = error_string,
+                message = error.user_message(),
+            )
+        );
+        error!(code = error.code().code(), "{}", error.internal_message());
+    }
+
     fn invalid_command_help_available(&mut self, mut invalid_command: String) -> Result<bool> {
         let locale = self.configuration.locale.as_str();
         let mut help_command = help::Command.name(locale);
@@ -298,11 +582,38 @@ impl Shell {
     }
 }
 
+/// Append the line to the statement being entered and return the statement to execute; used by
+/// the execute key binding to submit the statement even when it is not complete.
+fn execute_input(statement: &mut String, line: &str) -> String {
+    if !statement.is_empty() {
+        statement.push('\n');
+    }
+    statement.push_str(line);
+    mem::take(statement)
+}
+
+/// Returns true when the SQL ends with a statement terminator that is not part of a string, quoted
+/// identifier or comment.
+fn statement_complete(dialect: &dyn Dialect, sql: &str) -> bool {
+    let Ok(tokens) = Tokenizer::new(dialect, sql).tokenize() else {
+        return false;
+    };
+    tokens
+        .iter()
+        .rev()
+        .find(|token| !matches!(token, Token::Whitespace(_)))
+        .is_none_or(|token| *token == Token::SemiColon)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use rsql_drivers::{Metadata, MockConnection, MockDriver};
+    use crate::configuration::{ConfigurationBuilder, Profile};
+    use mockall::predicate::eq;
+    use rsql_drivers::{Metadata, MockConnection, MockDriver, StatementMetadata};
     use rustyline::history::DefaultHistory;
+    use sqlparser::dialect::GenericDialect;
+    use std::str::FromStr;
 
     #[test]
     fn test_shell_builder() {
@@ -374,6 +685,282 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_execute_read_only() -> anyhow::Result<()> {
+        let driver_identifier = "test-driver";
+        let mut mock_driver = MockDriver::new();
+        mock_driver
+            .expect_identifier()
+            .returning(|| driver_identifier);
+        mock_driver.expect_supports_file_type().returning(|_| false);
+        mock_driver.expect_connect().returning(|_, _| {
+            let mut mock_connection = MockConnection::new();
+            mock_connection
+                .expect_set_read_only()
+                .with(eq(true))
+                .times(1)
+                .returning(|_| Ok(()));
+            mock_connection.expect_close().returning(|| Ok(()));
+            Ok(Box::new(mock_connection))
+        });
+        let mut driver_manager = DriverManager::new();
+        driver_manager.add(Box::new(mock_driver));
+        let mut shell = ShellBuilder::default()
+            .with_driver_manager(driver_manager)
+            .build();
+        let args = ShellArgs {
+            url: format!("{driver_identifier}://"),
+            commands: vec![".bail on".to_string()],
+            read_only: true,
+            ..Default::default()
+        };
+
+        assert_eq!(0, shell.execute(&args).await?);
+
+        assert!(shell.state.read_only);
+        Ok(())
+    }
+
+    #[test]
+    fn test_prompt() {
+        let configuration = Configuration {
+            program_name: "rsql".to_string(),
+            ..Default::default()
+        };
+        let shell = ShellBuilder::default()
+            .with_configuration(configuration)
+            .build();
+        assert_eq!(shell.prompt("rusqlite://", true), "rsql> ");
+        assert_eq!(shell.prompt("rusqlite://", false), "rsql-> ");
+    }
+
+    #[test]
+    fn test_prompt_template() {
+        let configuration = Configuration {
+            program_name: "rsql".to_string(),
+            prompt: Some("{user}@{host} [{driver}] > ".to_string()),
+            ..Default::default()
+        };
+        let shell = ShellBuilder::default()
+            .with_configuration(configuration)
+            .build();
+        let url = "postgresql://postgres@localhost/db";
+        assert_eq!(
+            shell.prompt(url, true),
+            "postgres@localhost [postgresql] > "
+        );
+        assert_eq!(shell.prompt(url, false), format!("{:>34}", "-> "));
+    }
+
+    #[tokio::test]
+    async fn test_execute_profile() -> anyhow::Result<()> {
+        let driver_identifier = "test-driver";
+        let mut mock_driver = MockDriver::new();
+        mock_driver
+            .expect_identifier()
+            .returning(|| driver_identifier);
+        mock_driver.expect_supports_file_type().returning(|_| false);
+        mock_driver
+            .expect_connect()
+            .withf(|url, _| url == "test-driver://profile" || url == "test-driver://other")
+            .times(2)
+            .returning(|_, _| {
+                let mut mock_connection = MockConnection::new();
+                mock_connection.expect_close().times(1).returning(|| Ok(()));
+                Ok(Box::new(mock_connection))
+            });
+        let mut driver_manager = DriverManager::new();
+        driver_manager.add(Box::new(mock_driver));
+        let configuration = ConfigurationBuilder::default()
+            .with_profile(
+                "test",
+                Profile {
+                    url: format!("{driver_identifier}://profile"),
+                    results_limit: Some(10),
+                    ..Default::default()
+                },
+            )
+            .build();
+        let mut shell = ShellBuilder::default()
+            .with_configuration(configuration)
+            .with_driver_manager(driver_manager)
+            .build();
+        let args = ShellArgs {
+            profile: Some("test".to_string()),
+            commands: vec![format!(".connect {driver_identifier}://other")],
+            ..Default::default()
+        };
+
+        assert_eq!(0, shell.execute(&args).await?);
+
+        assert_eq!(shell.configuration.results_limit, 10);
+        assert!(shell.state.connection.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_unknown_profile() {
+        let mut shell = ShellBuilder::default().build();
+        let args = ShellArgs {
+            profile: Some("foo".to_string()),
+            ..Default::default()
+        };
+
+        assert!(shell.execute(&args).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_format() -> anyhow::Result<()> {
+        let driver_identifier = "test-driver";
+        let mut mock_driver = MockDriver::new();
+        mock_driver
+            .expect_identifier()
+            .returning(|| driver_identifier);
+        mock_driver.expect_supports_file_type().returning(|_| false);
+        mock_driver.expect_connect().returning(|_, _| {
+            let mut mock_connection = MockConnection::new();
+            mock_connection.expect_close().returning(|| Ok(()));
+            Ok(Box::new(mock_connection))
+        });
+        let mut driver_manager = DriverManager::new();
+        driver_manager.add(Box::new(mock_driver));
+        let mut shell = ShellBuilder::default()
+            .with_driver_manager(driver_manager)
+            .build();
+        let args = ShellArgs {
+            url: format!("{driver_identifier}://"),
+            format: Some("csv".to_string()),
+            commands: vec![".bail on".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(0, shell.execute(&args).await?);
+        assert_eq!(shell.configuration.results_format, "csv");
+
+        let args = ShellArgs {
+            format: Some("foo".to_string()),
+            ..args
+        };
+        assert!(shell.execute(&args).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_check() -> anyhow::Result<()> {
+        let driver_identifier = "test-driver";
+        let mut mock_driver = MockDriver::new();
+        mock_driver
+            .expect_identifier()
+            .returning(|| driver_identifier);
+        mock_driver.expect_supports_file_type().returning(|_| false);
+        mock_driver.expect_connect().returning(|_, _| {
+            let mut mock_connection = MockConnection::new();
+            mock_connection
+                .expect_dialect()
+                .returning(|| Box::new(GenericDialect));
+            mock_connection
+                .expect_match_statement()
+                .returning(|_| StatementMetadata::DDL);
+            mock_connection.expect_close().returning(|| Ok(()));
+            Ok(Box::new(mock_connection))
+        });
+        let mut driver_manager = DriverManager::new();
+        driver_manager.add(Box::new(mock_driver));
+        let mut shell = ShellBuilder::default()
+            .with_driver_manager(driver_manager)
+            .build();
+        let args = ShellArgs {
+            url: format!("{driver_identifier}://"),
+            check: true,
+            commands: vec!["CREATE TABLE foo (id INT);".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(0, shell.execute(&args).await?);
+
+        let args = ShellArgs {
+            commands: vec!["CREATE TABLE;".to_string()],
+            ..args
+        };
+        assert_eq!(1, shell.execute(&args).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_migrate() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        std::fs::write(
+            directory.path().join("1_create_users.sql"),
+            "CREATE TABLE users (id INTEGER);",
+        )?;
+        let mut shell = ShellBuilder::default()
+            .with_configuration(Configuration {
+                bail_on_error: false,
+                ..Default::default()
+            })
+            .build();
+        let args = ShellArgs {
+            url: "rusqlite://".to_string(),
+            migrate: Some(directory.path().to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        assert_eq!(0, shell.execute(&args).await?);
+        assert!(shell.configuration.bail_on_error);
+
+        std::fs::write(directory.path().join("2_invalid.sql"), "CREATE TABLE;")?;
+        assert!(shell.execute(&args).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_store_password_without_password() {
+        let mut shell = ShellBuilder::default().build();
+        let args = ShellArgs {
+            url: "rusqlite://".to_string(),
+            store_password: true,
+            ..Default::default()
+        };
+        assert!(shell.execute(&args).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_template_file() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("locale.sql.j2");
+        std::fs::write(&path, ".locale {{ locale }}")?;
+        let mut shell = ShellBuilder::default().build();
+        let args = ShellArgs {
+            url: "rusqlite://".to_string(),
+            file: Some(clap_stdin::FileOrStdin::from_str(&path.to_string_lossy())?),
+            variables: vec![("locale".to_string(), "en-GB".to_string())],
+            ..Default::default()
+        };
+        assert_eq!(0, shell.execute(&args).await?);
+        assert_eq!(shell.configuration.locale, "en-GB");
+        assert_eq!(
+            shell.state.variables.get("locale"),
+            Some(&"en-GB".to_string())
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_report() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("report.md");
+        std::fs::write(&path, "# Report\n\n```sql\nSELECT 42 AS answer;\n```\n")?;
+        let mut shell = ShellBuilder::default().build();
+        let args = ShellArgs {
+            url: "rusqlite://".to_string(),
+            report: Some(clap_stdin::FileOrStdin::from_str(&path.to_string_lossy())?),
+            report_format: "html".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(0, shell.execute(&args).await?);
+        let output = shell.output.to_string();
+        assert!(output.contains("<h1>Report</h1>"));
+        assert!(output.contains("<th style=\"text-align: right\">answer</th>"));
+        Ok(())
+    }
+
     async fn test_editor(color: bool) -> anyhow::Result<()> {
         let configuration = Configuration {
             bail_on_error: false,
@@ -389,7 +976,10 @@ mod test {
             .expect_metadata()
             .with()
             .returning(|| Ok(Metadata::default()));
-        let _ = shell.editor("history.txt", &mut connection).await?;
+        let pending_action = Arc::new(Mutex::new(None));
+        let _ = shell
+            .editor("history.txt", &mut connection, &pending_action)
+            .await?;
         Ok(())
     }
 
@@ -496,4 +1086,107 @@ mod test {
         assert!(!shell.invalid_command_help_available(invalid_command)?);
         Ok(())
     }
+
+    #[test]
+    fn test_statement_input() {
+        let shell = Shell::default();
+        let dialect = GenericDialect {};
+        let mut statement = String::new();
+
+        assert_eq!(
+            shell.statement_input(&mut statement, "SELECT 1;", &dialect),
+            Some("SELECT 1;".to_string())
+        );
+        assert_eq!(
+            shell.statement_input(&mut statement, "SELECT *", &dialect),
+            None
+        );
+        assert_eq!(
+            shell.statement_input(&mut statement, ".timer on", &dialect),
+            Some(".timer on".to_string())
+        );
+        assert_eq!(
+            shell.statement_input(&mut statement, "FROM users", &dialect),
+            None
+        );
+        assert_eq!(
+            shell.statement_input(&mut statement, "WHERE id = 1;", &dialect),
+            Some("SELECT *\nFROM users\nWHERE id = 1;".to_string())
+        );
+        assert!(statement.is_empty());
+    }
+
+    #[test]
+    fn test_statement_input_send() {
+        let shell = Shell::default();
+        let dialect = GenericDialect {};
+        let mut statement = String::new();
+
+        assert_eq!(
+            shell.statement_input(&mut statement, ".send", &dialect),
+            Some(".send".to_string())
+        );
+        assert_eq!(
+            shell.statement_input(&mut statement, "SELECT 1", &dialect),
+            None
+        );
+        assert_eq!(
+            shell.statement_input(&mut statement, ".send", &dialect),
+            Some("SELECT 1".to_string())
+        );
+        assert!(statement.is_empty());
+    }
+
+    #[test]
+    fn test_statement_input_multiline_disabled() {
+        let shell = ShellBuilder::default()
+            .with_configuration(Configuration {
+                multiline: false,
+                ..Default::default()
+            })
+            .build();
+        let dialect = GenericDialect {};
+        let mut statement = String::new();
+
+        assert_eq!(
+            shell.statement_input(&mut statement, "SELECT 1", &dialect),
+            Some("SELECT 1".to_string())
+        );
+        assert!(statement.is_empty());
+    }
+
+    #[test]
+    fn test_execute_input() {
+        let mut statement = "SELECT *".to_string();
+        assert_eq!(
+            execute_input(&mut statement, "FROM users"),
+            "SELECT *\nFROM users".to_string()
+        );
+        assert!(statement.is_empty());
+        assert_eq!(execute_input(&mut statement, "SELECT 1"), "SELECT 1");
+    }
+
+    #[test]
+    fn test_toggle_expanded() -> anyhow::Result<()> {
+        let mut shell = ShellBuilder::default().build();
+
+        shell.toggle_expanded()?;
+        assert!(shell.configuration.results_expanded_auto);
+        shell.toggle_expanded()?;
+        assert!(!shell.configuration.results_expanded_auto);
+        assert_eq!(shell.output.to_string(), "Expanded: auto\nExpanded: off\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_statement_complete() {
+        let dialect = GenericDialect {};
+        assert!(statement_complete(&dialect, ""));
+        assert!(statement_complete(&dialect, "SELECT 1;"));
+        assert!(statement_complete(&dialect, "SELECT 1; -- comment"));
+        assert!(!statement_complete(&dialect, "SELECT 1"));
+        assert!(!statement_complete(&dialect, "SELECT ';"));
+        assert!(!statement_complete(&dialect, "SELECT 1 /* ; */"));
+        assert!(!statement_complete(&dialect, "SELECT 1; SELECT 2"));
+    }
 }
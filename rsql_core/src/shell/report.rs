@@ -0,0 +1,297 @@
+use crate::commands::LoopCondition;
+use crate::executors::Executor;
+use crate::shell::{Error, Result, Shell};
+use indoc::formatdoc;
+use pulldown_cmark::{html, Options, Parser};
+use rsql_drivers::Connection;
+use rsql_formatters::writers::Output;
+use rustyline::history::DefaultHistory;
+use std::io::Write;
+use std::mem;
+
+/// The formats of the reports written for Markdown files
+pub(crate) const REPORT_FORMATS: [&str; 2] = ["markdown", "html"];
+
+/// A part of a Markdown file; the text of the part ends with a fenced `sql` code block when the
+/// part has SQL to execute.
+#[derive(Debug, PartialEq, Eq)]
+struct Part {
+    text: String,
+    sql: Option<String>,
+}
+
+impl Shell {
+    /// Execute the fenced `sql` code blocks of the Markdown file and write a report of the file
+    /// with the results of each block inserted below the block, as Markdown tables, or as an HTML
+    /// document when the format is `html`.  Errors are inserted in the report instead of the
+    /// results; returns the exit code, which is 1 when a block failed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be written.
+    pub(crate) async fn report(
+        &mut self,
+        connection: &mut Box<dyn Connection>,
+        contents: &str,
+        format: &str,
+    ) -> Result<i32> {
+        // Results are inserted as Markdown tables; the footer is omitted as it would be displayed
+        // as a row of the table, and the timer would change the report each time it is written.
+        let configuration = self.configuration.clone();
+        self.configuration.color = false;
+        self.configuration.results_format = "markdown".to_string();
+        self.configuration.results_footer = false;
+        self.configuration.results_timer = false;
+
+        let history = DefaultHistory::new();
+        let mut report = String::new();
+        let mut failed = false;
+        for part in split_parts(contents) {
+            report.push_str(&part.text);
+            let Some(sql) = part.sql else {
+                continue;
+            };
+
+            let mut output = Output::default();
+            let mut executor = Executor::new(
+                &mut self.configuration,
+                &self.command_manager,
+                &self.driver_manager,
+                &self.formatter_manager,
+                &history,
+                &mut self.state,
+                connection.as_mut(),
+                &mut output,
+            );
+            let result = executor.execute(&sql).await;
+            self.replace_connection(connection).await?;
+
+            let results = output.to_string();
+            if !results.trim().is_empty() {
+                report.push('\n');
+                report.push_str(results.trim_end());
+                report.push('\n');
+            }
+            match result {
+                Ok(LoopCondition::Continue) => {}
+                Ok(LoopCondition::Exit(_)) => break,
+                Err(error) => {
+                    let error = Error::from(error);
+                    self.write_error(&error);
+                    let locale = self.configuration.locale.as_str();
+                    let error_label = t!("error", locale = locale);
+                    report.push_str(&format!("\n> **{error_label}:** {}\n", error.user_message()));
+                    failed = true;
+                    if self.configuration.bail_on_error {
+                        break;
+                    }
+                }
+            }
+        }
+        self.configuration = configuration;
+
+        if format == "html" {
+            report = markdown_to_html(&report);
+        }
+        write!(self.output, "{report}")?;
+        self.output.flush()?;
+        Ok(i32::from(failed))
+    }
+}
+
+/// Split the Markdown into parts that end with a fenced `sql` code block; the info string of the
+/// block may include other words after `sql`, e.g. ```` ```sql title="users" ````.  Other code
+/// blocks are not executed.
+fn split_parts(contents: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    // The fence of the code block being read, and the SQL of the block when it is a sql block
+    let mut fence: Option<(String, Option<String>)> = None;
+
+    for line in contents.split_inclusive('\n') {
+        text.push_str(line);
+        let trimmed = line.trim_start_matches(' ');
+        let indent = line.len() - trimmed.len();
+
+        match &mut fence {
+            Some((opening, sql)) => {
+                let closing = trimmed.trim_end();
+                if indent < 4
+                    && closing.starts_with(opening.as_str())
+                    && closing.chars().all(|character| opening.starts_with(character))
+                {
+                    if let Some(sql) = sql.take() {
+                        parts.push(Part {
+                            text: mem::take(&mut text),
+                            sql: Some(sql),
+                        });
+                    }
+                    fence = None;
+                } else if let Some(sql) = sql {
+                    sql.push_str(line);
+                }
+            }
+            None if indent < 4 => {
+                let Some(character) = trimmed.chars().next() else {
+                    continue;
+                };
+                if character != '`' && character != '~' {
+                    continue;
+                }
+                let length = trimmed.chars().take_while(|c| *c == character).count();
+                if length < 3 {
+                    continue;
+                }
+                let info = trimmed[length..].trim();
+                let language = info.split_whitespace().next().unwrap_or_default();
+                let sql = language.eq_ignore_ascii_case("sql").then(String::new);
+                fence = Some((character.to_string().repeat(length), sql));
+            }
+            None => {}
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(Part { text, sql: None });
+    }
+    parts
+}
+
+/// Render the Markdown report as an HTML document
+fn markdown_to_html(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES);
+    let mut body = String::new();
+    html::push_html(&mut body, parser);
+    formatdoc! {r#"
+        <!DOCTYPE html>
+        <html>
+        <head>
+        <meta charset="utf-8">
+        </head>
+        <body>
+        {body}</body>
+        </html>
+    "#}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shell::ShellBuilder;
+    use indoc::indoc;
+    use rsql_drivers::DriverManager;
+
+    #[test]
+    fn test_split_parts() {
+        let contents = indoc! {r#"
+            # Users
+
+            ```sql title="users"
+            SELECT 1;
+            ```
+
+            ```python
+            print("not executed")
+            ```
+
+            ````SQL
+            ```
+            SELECT 2;
+            ````
+            Done
+        "#};
+        let parts = split_parts(contents);
+        assert_eq!(parts.len(), 3);
+        assert_eq!(
+            parts[0],
+            Part {
+                text: "# Users\n\n```sql title=\"users\"\nSELECT 1;\n```\n".to_string(),
+                sql: Some("SELECT 1;\n".to_string()),
+            }
+        );
+        assert_eq!(parts[1].sql, Some("```\nSELECT 2;\n".to_string()));
+        assert!(parts[1].text.contains("print(\"not executed\")"));
+        assert_eq!(
+            parts[2],
+            Part {
+                text: "Done\n".to_string(),
+                sql: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_parts_unclosed_block() {
+        let parts = split_parts("```sql\nSELECT 1;\n");
+        assert_eq!(
+            parts,
+            vec![Part {
+                text: "```sql\nSELECT 1;\n".to_string(),
+                sql: None
+            }]
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_html() {
+        let html = markdown_to_html("# Report\n\n| id |\n|---:|\n| 1 |\n");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<h1>Report</h1>"));
+        assert!(html.contains("<td style=\"text-align: right\">1</td>"));
+    }
+
+    async fn report(contents: &str, format: &str) -> anyhow::Result<(i32, String)> {
+        let mut shell = ShellBuilder::default().build();
+        let mut connection = DriverManager::default().connect("rusqlite://").await?;
+        let exit_code = shell.report(&mut connection, contents, format).await?;
+        connection.close().await?;
+        assert_eq!(shell.configuration.results_format, "psql");
+        Ok((exit_code, shell.output.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_report() -> anyhow::Result<()> {
+        let contents = indoc! {"
+            # Report
+
+            ```sql
+            CREATE TABLE users (id INTEGER, name TEXT);
+            INSERT INTO users VALUES (1, 'foo');
+            ```
+
+            ```sql
+            SELECT id, name FROM users;
+            ```
+        "};
+        let (exit_code, output) = report(contents, "markdown").await?;
+        assert_eq!(exit_code, 0);
+        let expected = indoc! {"
+            # Report
+
+            ```sql
+            CREATE TABLE users (id INTEGER, name TEXT);
+            INSERT INTO users VALUES (1, 'foo');
+            ```
+
+            ```sql
+            SELECT id, name FROM users;
+            ```
+
+            | id | name |
+            |---:|------|
+            |  1 | foo  |
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_report_error() -> anyhow::Result<()> {
+        let contents = "```sql\nSELECT * FROM foo;\n```\n";
+        let (exit_code, output) = report(contents, "html").await?;
+        assert_eq!(exit_code, 1);
+        assert!(output.contains("<code class=\"language-sql\">SELECT * FROM foo;"));
+        assert!(output.contains("<strong>Error:</strong>"));
+        Ok(())
+    }
+}
@@ -0,0 +1,665 @@
+use rsql_drivers::{
+    Connection, DriverManager, MemoryQueryResult, QueryResult, Result, Row, RowBuffer, Value,
+};
+use sqlparser::ast::Ident;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// The URL of the local database used to store stashed query results
+const STASH_URL: &str = "rusqlite://";
+/// The number of rows inserted by each statement when a query result is stashed
+const INSERT_BATCH_SIZE: usize = 500;
+
+/// Query results stored as tables in a local in-memory `SQLite` database, so that the results of
+/// queries from different connections can be queried, and joined, with SQL.  Databases attached
+/// to the stash are available as schemas of the local database; the tables of an attached
+/// database that are referenced by a query are copied to the local database before the query is
+/// executed, and the copies are reused until the database is attached again or the metadata is
+/// refreshed.
+#[derive(Debug, Default)]
+pub struct Stash {
+    connection: Option<Box<dyn Connection>>,
+    tables: Vec<String>,
+    attachments: Vec<Attachment>,
+}
+
+/// A database attached to the stash with a name that is used as the schema for its tables
+#[derive(Debug)]
+struct Attachment {
+    name: String,
+    connection: Box<dyn Connection>,
+    /// The names of the tables that have been copied to the local database; each name is the
+    /// list of the parts of the name in the attached database, e.g. `public.users`
+    copied: Vec<Vec<Ident>>,
+}
+
+/// A relation read by the SQL with the range of the tokens of its name
+#[derive(Debug)]
+struct Relation {
+    /// The parts of the qualified name, e.g. `pg` and `users` for `FROM pg.users`
+    parts: Vec<Ident>,
+    tokens: Range<usize>,
+}
+
+impl Stash {
+    /// Get the names of the stashed tables
+    #[must_use]
+    pub fn tables(&self) -> &[String] {
+        &self.tables
+    }
+
+    /// Get the names and URLs of the attached databases
+    #[must_use]
+    pub fn attachments(&self) -> Vec<(&str, &str)> {
+        self.attachments
+            .iter()
+            .map(|attachment| {
+                (
+                    attachment.name.as_str(),
+                    attachment.connection.url().as_str(),
+                )
+            })
+            .collect()
+    }
+
+    /// Get the connection to the local database; returns `None` if no results have been stashed
+    pub fn connection(&mut self) -> Option<&mut dyn Connection> {
+        self.connection
+            .as_mut()
+            .map(|connection| connection.as_mut() as &mut dyn Connection)
+    }
+
+    /// Get the connection to the local database, creating the database if required
+    async fn local_connection(
+        &mut self,
+        driver_manager: &DriverManager,
+    ) -> Result<&mut Box<dyn Connection>> {
+        let connection = match &mut self.connection {
+            Some(connection) => connection,
+            connection @ None => connection.insert(driver_manager.connect(STASH_URL).await?),
+        };
+        Ok(connection)
+    }
+
+    /// Store the query result as a table with the name, replacing any stashed table with the same
+    /// name.  The local database is created when the first result is stashed; rows exceeding the
+    /// memory limit in bytes are buffered in a temporary file while the table is stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the local database cannot be created or the table cannot be stored.
+    pub async fn add(
+        &mut self,
+        driver_manager: &DriverManager,
+        name: &str,
+        mut result: MemoryQueryResult,
+        memory_limit: usize,
+    ) -> Result<()> {
+        let connection = self.local_connection(driver_manager).await?;
+        store(
+            connection.as_mut(),
+            &quote_identifier(name),
+            &mut result,
+            memory_limit,
+        )
+        .await?;
+
+        if !self
+            .tables
+            .iter()
+            .any(|table| table.eq_ignore_ascii_case(name))
+        {
+            self.tables.push(name.to_string());
+        }
+        Ok(())
+    }
+
+    /// Connect to the database with the URL and attach it with the name, replacing any database
+    /// attached with the same name; the tables copied from the replaced database are copied again
+    /// when they are next referenced.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be connected to or attached.
+    pub async fn attach(
+        &mut self,
+        driver_manager: &DriverManager,
+        name: &str,
+        url: &str,
+    ) -> Result<()> {
+        let connection = driver_manager.connect(url).await?;
+        if let Some(attachment) = self
+            .attachments
+            .iter_mut()
+            .find(|attachment| attachment.name.eq_ignore_ascii_case(name))
+        {
+            let mut previous = std::mem::replace(&mut attachment.connection, connection);
+            attachment.copied.clear();
+            return previous.close().await;
+        }
+
+        let local_connection = self.local_connection(driver_manager).await?;
+        local_connection
+            .execute(&format!(
+                "ATTACH DATABASE ':memory:' AS {}",
+                quote_identifier(name)
+            ))
+            .await?;
+        self.attachments.push(Attachment {
+            name: name.to_string(),
+            connection,
+            copied: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Discard the copies of the tables of the attached databases, so that the tables are copied
+    /// again when they are next referenced.
+    pub fn refresh(&mut self) {
+        for attachment in &mut self.attachments {
+            attachment.copied.clear();
+        }
+    }
+
+    /// Returns true if the SQL reads from a stashed table or from a table of an attached
+    /// database; tables are identified by the names following the `FROM` and `JOIN` keywords.
+    #[must_use]
+    pub fn references(&self, sql: &str) -> bool {
+        if self.tables.is_empty() && self.attachments.is_empty() {
+            return false;
+        }
+
+        relations(&tokenize(sql))
+            .iter()
+            .any(|relation| match relation.parts.as_slice() {
+                [table] => self
+                    .tables
+                    .iter()
+                    .any(|stashed| stashed.eq_ignore_ascii_case(&table.value)),
+                [name, _] | [name, _, _] => self.attachment_index(&name.value).is_some(),
+                _ => false,
+            })
+    }
+
+    /// Copy the tables of attached databases that are referenced by the SQL to the local
+    /// database, unless they have already been copied, and get the SQL to execute with the local
+    /// connection.  Quoted parts of the table names are quoted for the attached database; rows
+    /// exceeding the memory limit in bytes are buffered in a temporary file while each table is
+    /// copied.
+    ///
+    /// `SQLite` only supports names qualified with the database, so the names of tables that are
+    /// also qualified with a schema, e.g. `pg.public.users`, are replaced with the name of the
+    /// local copy in the SQL that is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a table cannot be read from an attached database or stored.
+    pub async fn prepare(&mut self, sql: &str, memory_limit: usize) -> Result<String> {
+        let Some(local_connection) = self.connection.as_deref_mut() else {
+            return Ok(sql.to_string());
+        };
+
+        let tokens = tokenize(sql);
+        let relations = relations(&tokens);
+        let mut local_sql = String::new();
+        let mut token_index = 0;
+        for relation in &relations {
+            let ([name, _] | [name, _, _]) = relation.parts.as_slice() else {
+                continue;
+            };
+            let Some(attachment) = self
+                .attachments
+                .iter_mut()
+                .find(|attachment| attachment.name.eq_ignore_ascii_case(&name.value))
+            else {
+                continue;
+            };
+
+            let table = &relation.parts[1..];
+            let local_table = local_table(&attachment.name, table);
+            if !attachment.copied.iter().any(|copied| copied == table) {
+                let remote_table = remote_table(attachment.connection.as_ref(), table);
+                let mut result = attachment
+                    .connection
+                    .query(&format!("SELECT * FROM {remote_table}"))
+                    .await?;
+                store(
+                    &mut *local_connection,
+                    &local_table,
+                    result.as_mut(),
+                    memory_limit,
+                )
+                .await?;
+                attachment.copied.push(table.to_vec());
+            }
+
+            if table.len() > 1 {
+                for token in &tokens[token_index..relation.tokens.start] {
+                    let _ = write!(local_sql, "{token}");
+                }
+                local_sql.push_str(&local_table);
+                token_index = relation.tokens.end;
+            }
+        }
+
+        if token_index == 0 {
+            return Ok(sql.to_string());
+        }
+        for token in &tokens[token_index..] {
+            let _ = write!(local_sql, "{token}");
+        }
+        Ok(local_sql)
+    }
+
+    /// Get the index of the attachment with the name
+    fn attachment_index(&self, name: &str) -> Option<usize> {
+        self.attachments
+            .iter()
+            .position(|attachment| attachment.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Close the local database and the attached databases; all stashed tables are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database cannot be closed.
+    pub async fn close(&mut self) -> Result<()> {
+        self.tables.clear();
+        for mut attachment in self.attachments.drain(..) {
+            attachment.connection.close().await?;
+        }
+        if let Some(mut connection) = self.connection.take() {
+            connection.close().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Get the quoted name of the local copy of the table of the attached database; the parts of the
+/// table name are joined into a single name, e.g. `"pg"."public.users"`.
+fn local_table(attachment: &str, table: &[Ident]) -> String {
+    let table = table
+        .iter()
+        .map(|part| part.value.as_str())
+        .collect::<Vec<_>>()
+        .join(".");
+    format!(
+        "{}.{}",
+        quote_identifier(attachment),
+        quote_identifier(&table)
+    )
+}
+
+/// Get the name of the table in the attached database; parts that were quoted in the query are
+/// quoted for the dialect of the attached database.
+fn remote_table(connection: &dyn Connection, table: &[Ident]) -> String {
+    let dialect = connection.dialect();
+    table
+        .iter()
+        .map(|part| match part.quote_style {
+            Some(_) => super::quote_identifier(dialect.as_ref(), &part.value),
+            None => part.value.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Store the query result as the table, replacing any existing table; the table name must be
+/// quoted.  The rows are read into a buffer that keeps up to `memory_limit` bytes of rows in
+/// memory, so that the column types can be determined before the rows are inserted.
+async fn store(
+    connection: &mut dyn Connection,
+    table: &str,
+    result: &mut dyn QueryResult,
+    memory_limit: usize,
+) -> Result<()> {
+    let columns = result.columns().await;
+    let mut column_types = vec![None; columns.len()];
+    let mut buffer = RowBuffer::new(memory_limit);
+    while let Some(row) = result.next().await {
+        update_column_types(&mut column_types, &row);
+        buffer.push(row)?;
+    }
+    if let Some(error) = result.take_error() {
+        return Err(error);
+    }
+
+    let definitions = columns
+        .iter()
+        .zip(&column_types)
+        .map(|(column, column_type)| {
+            format!(
+                "{} {}",
+                quote_identifier(column),
+                column_type.unwrap_or("TEXT")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    connection
+        .execute(&format!("DROP TABLE IF EXISTS {table}"))
+        .await?;
+    connection
+        .execute(&format!("CREATE TABLE {table} ({definitions})"))
+        .await?;
+    let mut rows = buffer.into_query_result(columns);
+    let mut values = Vec::with_capacity(INSERT_BATCH_SIZE);
+    while let Some(row) = rows.next().await {
+        let row_values = row.iter().map(literal).collect::<Vec<_>>().join(", ");
+        values.push(format!("({row_values})"));
+        if values.len() == INSERT_BATCH_SIZE {
+            insert(connection, table, &mut values).await?;
+        }
+    }
+    if let Some(error) = rows.take_error() {
+        return Err(error);
+    }
+    if !values.is_empty() {
+        insert(connection, table, &mut values).await?;
+    }
+    Ok(())
+}
+
+/// Insert the values of the rows into the table and clear the values
+async fn insert(
+    connection: &mut dyn Connection,
+    table: &str,
+    values: &mut Vec<String>,
+) -> Result<()> {
+    let sql = format!("INSERT INTO {table} VALUES {}", values.join(", "));
+    connection.execute(&sql).await?;
+    values.clear();
+    Ok(())
+}
+
+/// Get the tokens of the SQL; returns no tokens if the SQL cannot be tokenized
+fn tokenize(sql: &str) -> Vec<Token> {
+    Tokenizer::new(&GenericDialect {}, sql)
+        .tokenize()
+        .unwrap_or_default()
+}
+
+/// Get the relations read by the SQL, identified by the names following the `FROM` and `JOIN`
+/// keywords and the commas of the `FROM` clause.
+fn relations(tokens: &[Token]) -> Vec<Relation> {
+    let mut relations = Vec::new();
+    let mut parts: Vec<Ident> = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+    let mut expect_name = false;
+    let mut from_clause = false;
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Whitespace(_) => {}
+            Token::Word(word) if expect_name => {
+                if parts.is_empty() {
+                    start = index;
+                }
+                parts.push(match word.quote_style {
+                    Some(quote) => Ident::with_quote(quote, word.value.clone()),
+                    None => Ident::new(word.value.clone()),
+                });
+                end = index + 1;
+                expect_name = false;
+            }
+            Token::Period if !parts.is_empty() && !expect_name => expect_name = true,
+            _ => {
+                if !parts.is_empty() {
+                    relations.push(Relation {
+                        parts: std::mem::take(&mut parts),
+                        tokens: start..end,
+                    });
+                }
+                expect_name = false;
+                match token {
+                    Token::Word(word) if matches!(word.keyword, Keyword::FROM | Keyword::JOIN) => {
+                        from_clause = true;
+                        expect_name = true;
+                    }
+                    Token::Word(word)
+                        if !matches!(word.keyword, Keyword::NoKeyword | Keyword::AS) =>
+                    {
+                        from_clause = false;
+                    }
+                    Token::Comma if from_clause => expect_name = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+    if !parts.is_empty() {
+        relations.push(Relation {
+            parts,
+            tokens: start..end,
+        });
+    }
+    relations
+}
+
+/// Quote the identifier so that any name can be used for tables and columns
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Update the `SQLite` column types with the values of the row; the type of a column is `None`
+/// until a value that is not null is read.  Integer columns with a floating point value are
+/// `REAL` and columns with values of different kinds are `TEXT`.
+fn update_column_types(column_types: &mut [Option<&'static str>], row: &Row) {
+    for (column_type, value) in column_types.iter_mut().zip(row) {
+        let value_type = match value {
+            Value::Null => continue,
+            Value::Bool(_)
+            | Value::I8(_)
+            | Value::I16(_)
+            | Value::I32(_)
+            | Value::I64(_)
+            | Value::U8(_)
+            | Value::U16(_)
+            | Value::U32(_) => "INTEGER",
+            value if value.is_numeric() => "REAL",
+            Value::Bytes(_) => "BLOB",
+            _ => "TEXT",
+        };
+        *column_type = Some(match (*column_type, value_type) {
+            (None, value_type) => value_type,
+            (Some(current), value_type) if current == value_type => current,
+            (Some("INTEGER" | "REAL"), "INTEGER" | "REAL") => "REAL",
+            _ => "TEXT",
+        });
+    }
+}
+
+/// Convert the value to a `SQLite` literal
+fn literal(value: &Value) -> String {
+    match value {
+        Value::Null => "NULL".to_string(),
+        Value::Bool(value) => i32::from(*value).to_string(),
+        Value::Bytes(bytes) => {
+            let hex = bytes.iter().fold(String::new(), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02X}");
+                hex
+            });
+            format!("X'{hex}'")
+        }
+        Value::F32(_) | Value::F64(_) => match value.to_f64() {
+            Some(number) if number.is_finite() => value.to_string(),
+            _ => "NULL".to_string(),
+        },
+        value if value.is_numeric() => value.to_string(),
+        value => format!("'{}'", value.to_string().replace('\'', "''")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rsql_drivers::MockConnection;
+    use sqlparser::dialect::MySqlDialect;
+
+    fn stash(tables: &[&str]) -> Stash {
+        Stash {
+            connection: None,
+            tables: tables.iter().map(ToString::to_string).collect(),
+            attachments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_references() {
+        assert!(!stash(&[]).references("SELECT * FROM r1"));
+        let stash = stash(&["r1", "r2"]);
+        assert!(stash.references("SELECT * FROM r1"));
+        assert!(stash.references("SELECT * FROM R1 WHERE id = 1"));
+        assert!(stash.references("SELECT * FROM users u JOIN r2 ON u.id = r2.id"));
+        assert!(stash.references("SELECT * FROM users u, r2 WHERE u.id = r2.id"));
+        assert!(stash.references("SELECT * FROM users AS u, r2"));
+        assert!(!stash.references("SELECT r1 FROM users"));
+        assert!(!stash.references("SELECT * FROM users WHERE name = 'r1'"));
+        assert!(!stash.references("SELECT * FROM users WHERE id IN (1, r1)"));
+    }
+
+    #[test]
+    fn test_references_attachment() {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_url()
+            .return_const("postgresql://localhost".to_string());
+        let mut stash = stash(&[]);
+        stash.attachments.push(Attachment {
+            name: "pg".to_string(),
+            connection: Box::new(connection),
+            copied: Vec::new(),
+        });
+        assert!(stash.references("SELECT * FROM pg.users"));
+        assert!(stash.references("SELECT * FROM pg.public.users"));
+        assert!(stash.references("SELECT * FROM PG.users u JOIN events e ON u.id = e.user_id"));
+        assert!(!stash.references("SELECT * FROM public.users"));
+        assert!(!stash.references("SELECT pg.id FROM users"));
+        assert_eq!(stash.attachments(), vec![("pg", "postgresql://localhost")]);
+    }
+
+    #[test]
+    fn test_relations() {
+        assert!(relations(&tokenize("SELECT 1")).is_empty());
+        let tokens = tokenize(
+            "SELECT u.id FROM pg.users AS u, events JOIN a.\"b\".c ON x = y WHERE z IN (1, w)",
+        );
+        let relations = relations(&tokens);
+        let names: Vec<String> = relations
+            .iter()
+            .map(|relation| {
+                relation
+                    .parts
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(".")
+            })
+            .collect();
+        assert_eq!(names, vec!["pg.users", "events", "a.\"b\".c"]);
+        let tokens: Vec<String> = tokens[relations[2].tokens.clone()]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(tokens.concat(), "a.\"b\".c");
+    }
+
+    #[tokio::test]
+    async fn test_prepare() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_dialect()
+            .returning(|| Box::new(MySqlDialect {}));
+        connection
+            .expect_query()
+            .withf(|sql| sql == "SELECT * FROM public.`Users`")
+            .times(2)
+            .returning(|_| {
+                Ok(Box::new(MemoryQueryResult::new(
+                    vec!["id".to_string()],
+                    vec![vec![Value::I64(1)], vec![Value::I64(2)]],
+                )))
+            });
+        connection.expect_close().returning(|| Ok(()));
+        let driver_manager = DriverManager::default();
+        let mut stash = stash(&[]);
+        stash
+            .local_connection(&driver_manager)
+            .await?
+            .execute("ATTACH DATABASE ':memory:' AS \"pg\"")
+            .await?;
+        stash.attachments.push(Attachment {
+            name: "pg".to_string(),
+            connection: Box::new(connection),
+            copied: Vec::new(),
+        });
+
+        let sql = "SELECT COUNT(*) FROM pg.public.\"Users\"";
+        let local_sql = stash.prepare(sql, 1).await?;
+        assert_eq!(local_sql, "SELECT COUNT(*) FROM \"pg\".\"public.Users\"");
+        let local_connection = stash.connection().expect("connection");
+        let mut query_result = local_connection.query(&local_sql).await?;
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(2)]));
+
+        // The copied table is reused until the stash is refreshed
+        assert_eq!(stash.prepare(sql, 0).await?, local_sql);
+        stash.refresh();
+        assert_eq!(stash.prepare(sql, 0).await?, local_sql);
+        stash.close().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier("r1"), "\"r1\"");
+        assert_eq!(quote_identifier("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[test]
+    fn test_update_column_types() {
+        let rows = vec![
+            vec![
+                Value::I64(1),
+                Value::F64(1.5),
+                Value::Bytes(vec![1]),
+                Value::String("a".to_string()),
+                Value::Null,
+            ],
+            vec![
+                Value::Null,
+                Value::I32(2),
+                Value::Bytes(vec![2]),
+                Value::I64(1),
+                Value::Null,
+            ],
+        ];
+        let mut column_types = vec![None; 5];
+        for row in &rows {
+            update_column_types(&mut column_types, row);
+        }
+        assert_eq!(
+            column_types,
+            vec![
+                Some("INTEGER"),
+                Some("REAL"),
+                Some("BLOB"),
+                Some("TEXT"),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(literal(&Value::Null), "NULL");
+        assert_eq!(literal(&Value::Bool(true)), "1");
+        assert_eq!(literal(&Value::Bytes(vec![0x0a, 0xff])), "X'0AFF'");
+        assert_eq!(literal(&Value::I64(-42)), "-42");
+        assert_eq!(literal(&Value::F64(1.5)), "1.5");
+        assert_eq!(literal(&Value::F64(f64::NAN)), "NULL");
+        assert_eq!(literal(&Value::String("it's".to_string())), "'it''s'");
+    }
+}
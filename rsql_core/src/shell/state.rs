@@ -0,0 +1,49 @@
+use crate::shell::{ResultCache, Stash};
+use rsql_drivers::{Connection, MemoryQueryResult};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// State retained by the shell between statements and commands
+#[derive(Debug, Default)]
+pub struct ShellState {
+    /// The last SQL statement executed
+    pub last_statement: Option<String>,
+    /// The statement being entered in the shell that is not complete
+    pub statement: String,
+    /// The text edited in the external editor that is loaded into the prompt
+    pub edited_statement: Option<String>,
+    /// The rows displayed for the last query
+    pub last_result: Option<MemoryQueryResult>,
+    /// The rows displayed for the query before the last query
+    pub previous_result: Option<MemoryQueryResult>,
+    /// Query results stored as tables in a local database
+    pub stash: Stash,
+    /// Query results cached per connection when the result cache is enabled
+    pub cache: ResultCache,
+    /// Indicates if the session of the connection has been made read-only
+    pub read_only: bool,
+    /// The statement timeout applied to the session of the connection
+    pub timeout: Duration,
+    /// Indicates if the database enforces the statement timeout of the session
+    pub timeout_enforced: bool,
+    /// The timestamp of the historical reads of the session of the connection
+    pub as_of: Option<String>,
+    /// Indicates if an explicit transaction is open on the connection
+    pub in_transaction: bool,
+    /// The statements that changed the settings of the session of the connection, e.g. `SET` and
+    /// `USE`, which are applied to the additional connections used to execute queries in parallel
+    pub session: Vec<String>,
+    /// A connection opened by a command that replaces the connection of the shell
+    pub connection: Option<Box<dyn Connection>>,
+    /// The variables used to render SQL templates
+    pub variables: BTreeMap<String, String>,
+    /// The current step of the tutorial; 0 when the tutorial has not been started
+    pub tutorial_step: usize,
+}
+
+impl ShellState {
+    /// Set the result of the last query; the result it replaces becomes the previous result.
+    pub fn set_last_result(&mut self, last_result: MemoryQueryResult) {
+        self.previous_result = self.last_result.replace(last_result);
+    }
+}
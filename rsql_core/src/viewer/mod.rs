@@ -0,0 +1,164 @@
+mod state;
+mod ui;
+
+use crate::commands::last::columns_and_rows;
+use crate::configuration::{Configuration, OutputTarget};
+use crate::writers::{ClipboardWriter, Output};
+use rsql_drivers::{MemoryQueryResult, Row, Value};
+use rsql_formatters::Error::IoError;
+use rsql_formatters::{FormatterManager, Result, Results};
+use rust_i18n::t;
+use state::ViewerState;
+use std::io::{self, IsTerminal, Write};
+
+/// The lines displayed with the rows of a table: the borders, the header and the footer
+const TABLE_LINES: usize = 5;
+
+/// Browse the result in the interactive results viewer; rows selected in the viewer are copied to
+/// the clipboard, formatted with the results format.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be used by the viewer, or the rows cannot be exported.
+pub(crate) async fn view(
+    configuration: &Configuration,
+    formatter_manager: &FormatterManager,
+    result: MemoryQueryResult,
+    output: &mut Output,
+) -> Result<()> {
+    let (columns, rows) = columns_and_rows(result)
+        .await
+        .map_err(|error| IoError(error.into()))?;
+    view_rows(configuration, formatter_manager, columns, rows, output).await
+}
+
+/// Browse the result in the results viewer when the viewer is opened automatically and the rows do
+/// not fit in the terminal; results written to files, or displayed when the shell is not attached
+/// to a terminal, are not viewed.
+///
+/// # Errors
+///
+/// Returns an error if the terminal cannot be used by the viewer, or the rows cannot be exported.
+pub(crate) async fn view_auto(
+    configuration: &Configuration,
+    formatter_manager: &FormatterManager,
+    result: MemoryQueryResult,
+    output: &mut Output,
+) -> Result<()> {
+    if !configuration.results_viewer_auto
+        || configuration.output != OutputTarget::Stdout
+        || !io::stdin().is_terminal()
+        || !io::stdout().is_terminal()
+    {
+        return Ok(());
+    }
+    let Some((height, _width)) = console::Term::stdout().size_checked() else {
+        return Ok(());
+    };
+
+    let (columns, rows) = columns_and_rows(result)
+        .await
+        .map_err(|error| IoError(error.into()))?;
+    if rows.len() + TABLE_LINES <= usize::from(height) {
+        return Ok(());
+    }
+    view_rows(configuration, formatter_manager, columns, rows, output).await
+}
+
+async fn view_rows(
+    configuration: &Configuration,
+    formatter_manager: &FormatterManager,
+    columns: Vec<String>,
+    rows: Vec<Row>,
+    output: &mut Output,
+) -> Result<()> {
+    let locale = configuration.locale.as_str();
+    let values = rows
+        .iter()
+        .map(|row| row.iter().map(|value| display(configuration, value)).collect())
+        .collect();
+    let mut state = ViewerState::new(
+        columns.clone(),
+        values,
+        configuration.results_max_column_width,
+    );
+    let Some(selection) = ui::run(&mut state, locale)? else {
+        return Ok(());
+    };
+
+    let rows = rows[selection].to_vec();
+    let exported = rows.len();
+    export(configuration, formatter_manager, columns, rows).await?;
+    let view_exported = t!("view_exported", locale = locale, rows = exported).to_string();
+    writeln!(output, "{view_exported}")?;
+    Ok(())
+}
+
+/// Copy the rows to the clipboard, formatted with the results format
+async fn export(
+    configuration: &Configuration,
+    formatter_manager: &FormatterManager,
+    columns: Vec<String>,
+    rows: Vec<Row>,
+) -> Result<()> {
+    let result_format = &configuration.results_format;
+    let formatter = formatter_manager.get(result_format).ok_or(
+        rsql_formatters::Error::UnknownFormat {
+            format: result_format.to_string(),
+        },
+    )?;
+
+    // Colors, the terminal width and the timer are not useful when the rows are pasted into
+    // other documents
+    let mut formatter_options = configuration.get_formatter_options();
+    formatter_options.color = false;
+    formatter_options.expanded_width = None;
+    formatter_options.timer = false;
+    let mut results = Results::Query(Box::new(MemoryQueryResult::new(columns, rows)));
+    let mut clipboard = Output::new(Box::<ClipboardWriter>::default());
+    formatter
+        .format(&formatter_options, &mut results, &mut clipboard)
+        .await?;
+    clipboard.flush()?;
+    Ok(())
+}
+
+/// Format the value for the viewer; null values are displayed with the null display and binary
+/// values with the bytes format of the configuration.
+fn display(configuration: &Configuration, value: &Value) -> String {
+    match value {
+        Value::Null => configuration.results_null_display.clone(),
+        Value::Bytes(bytes) => configuration
+            .results_bytes_format
+            .format(bytes, &configuration.locale),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsql_formatters::BytesFormat;
+
+    #[test]
+    fn test_display() {
+        let configuration = Configuration {
+            results_bytes_format: BytesFormat::Hex,
+            results_null_display: "∅".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(display(&configuration, &Value::Null), "∅");
+        assert_eq!(display(&configuration, &Value::Bytes(vec![1, 171])), "\\x01ab");
+        assert_eq!(display(&configuration, &Value::I64(42)), "42");
+    }
+
+    #[tokio::test]
+    async fn test_view_auto_disabled() -> anyhow::Result<()> {
+        let configuration = Configuration::default();
+        let result = MemoryQueryResult::new(vec!["id".to_string()], vec![vec![Value::I64(1)]]);
+        let mut output = Output::default();
+        view_auto(&configuration, &FormatterManager::default(), result, &mut output).await?;
+        assert_eq!(output.to_string(), "");
+        Ok(())
+    }
+}
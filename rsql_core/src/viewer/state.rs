@@ -0,0 +1,315 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::ops::RangeInclusive;
+use unicode_width::UnicodeWidthStr;
+
+/// The maximum width of the columns of the table; wider values are truncated and can be read in
+/// the expanded row view.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// The action to take after a key has been handled
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Action {
+    Continue,
+    Export,
+    Quit,
+}
+
+/// The state of the results viewer: the formatted values of the result, the selected row, the
+/// rows and columns scrolled past, and the search being entered.
+#[derive(Debug, Default)]
+pub(crate) struct ViewerState {
+    pub(crate) columns: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+    pub(crate) widths: Vec<usize>,
+    /// The selected row
+    pub(crate) row: usize,
+    /// The first row displayed
+    pub(crate) offset: usize,
+    /// The first column displayed
+    pub(crate) column: usize,
+    /// The row where the selection of rows to export started
+    pub(crate) anchor: Option<usize>,
+    /// Indicates if the selected row is displayed with one line per column
+    pub(crate) expanded: bool,
+    /// The text of the search being entered
+    pub(crate) search: Option<String>,
+    /// The text of the last search, used to find the next match
+    pub(crate) last_search: String,
+    /// Indicates if the last search did not match any row
+    pub(crate) not_found: bool,
+}
+
+impl ViewerState {
+    /// Create the state for the columns and the formatted values of the rows; the width of each
+    /// column is the width of the widest value, up to `max_column_width`, or a default maximum
+    /// when it is zero.
+    pub(crate) fn new(
+        columns: Vec<String>,
+        rows: Vec<Vec<String>>,
+        max_column_width: usize,
+    ) -> Self {
+        let max_column_width = if max_column_width == 0 {
+            MAX_COLUMN_WIDTH
+        } else {
+            max_column_width
+        };
+        let widths = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                rows.iter()
+                    .filter_map(|row| row.get(index))
+                    .map(|value| value.width())
+                    .fold(column.width(), usize::max)
+                    .min(max_column_width)
+            })
+            .collect();
+        Self {
+            columns,
+            rows,
+            widths,
+            ..Default::default()
+        }
+    }
+
+    /// Get the range of rows selected for export; the selected row when no range was started.
+    pub(crate) fn selection(&self) -> RangeInclusive<usize> {
+        let anchor = self.anchor.unwrap_or(self.row);
+        anchor.min(self.row)..=anchor.max(self.row)
+    }
+
+    /// Returns true if the row is part of a range of rows selected for export
+    pub(crate) fn is_selected(&self, row: usize) -> bool {
+        self.anchor.is_some() && self.selection().contains(&row)
+    }
+
+    /// Scroll the rows so that the selected row is one of the `height` rows displayed
+    pub(crate) fn scroll_into_view(&mut self, height: usize) {
+        let height = height.max(1);
+        if self.row < self.offset {
+            self.offset = self.row;
+        } else if self.row >= self.offset + height {
+            self.offset = self.row + 1 - height;
+        }
+    }
+
+    /// Handle the key; `height` is the number of rows displayed, which is scrolled by the page
+    /// keys.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent, height: usize) -> Action {
+        if self.search.is_some() {
+            self.handle_search_key(key);
+            return Action::Continue;
+        }
+
+        self.not_found = false;
+        let last_row = self.rows.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Esc => {
+                if self.anchor.take().is_none() && !self.expanded {
+                    return Action::Quit;
+                }
+                self.expanded = false;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.row = (self.row + 1).min(last_row),
+            KeyCode::PageUp => self.row = self.row.saturating_sub(height.max(1)),
+            KeyCode::PageDown => self.row = (self.row + height.max(1)).min(last_row),
+            KeyCode::Home | KeyCode::Char('g') => self.row = 0,
+            KeyCode::End | KeyCode::Char('G') => self.row = last_row,
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.column = (self.column + 1).min(self.columns.len().saturating_sub(1));
+            }
+            KeyCode::Enter => self.expanded = !self.expanded,
+            KeyCode::Char(' ' | 'v') => {
+                self.anchor = match self.anchor {
+                    Some(_) => None,
+                    None => Some(self.row),
+                };
+            }
+            KeyCode::Char('y') if !self.rows.is_empty() => return Action::Export,
+            KeyCode::Char('/') => self.search = Some(String::new()),
+            KeyCode::Char('n') => self.find(true),
+            KeyCode::Char('N') => self.find(false),
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    /// Handle a key while the search is entered; the search starts when enter is pressed.
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.search = None,
+            KeyCode::Backspace => {
+                search.pop();
+            }
+            KeyCode::Enter => {
+                if let Some(search) = self.search.take() {
+                    if !search.is_empty() {
+                        self.last_search = search;
+                    }
+                }
+                self.find(true);
+            }
+            KeyCode::Char(character) => search.push(character),
+            _ => {}
+        }
+    }
+
+    /// Select the next row, or the previous row, with a value that contains the last search,
+    /// ignoring case; the search wraps around at the end of the rows.
+    fn find(&mut self, forward: bool) {
+        if self.last_search.is_empty() || self.rows.is_empty() {
+            return;
+        }
+        let search = self.last_search.to_lowercase();
+        let count = self.rows.len();
+        let found = (1..=count)
+            .map(|step| {
+                if forward {
+                    (self.row + step) % count
+                } else {
+                    (self.row + count - step % count) % count
+                }
+            })
+            .find(|&index| {
+                self.rows[index]
+                    .iter()
+                    .any(|value| value.to_lowercase().contains(&search))
+            });
+        match found {
+            Some(index) => self.row = index,
+            None => self.not_found = true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> ViewerState {
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let rows = (1..=10)
+            .map(|id| vec![id.to_string(), format!("name {id}")])
+            .collect();
+        ViewerState::new(columns, rows, 0)
+    }
+
+    fn press(state: &mut ViewerState, codes: &[KeyCode]) -> Action {
+        let mut action = Action::Continue;
+        for code in codes {
+            action = state.handle_key(KeyEvent::from(*code), 3);
+        }
+        action
+    }
+
+    #[test]
+    fn test_widths() {
+        let columns = vec!["id".to_string(), "description".to_string()];
+        let rows = vec![vec!["12345".to_string(), "x".repeat(100)]];
+        let state = ViewerState::new(columns.clone(), rows.clone(), 0);
+        assert_eq!(state.widths, vec![5, MAX_COLUMN_WIDTH]);
+        let state = ViewerState::new(columns, rows, 8);
+        assert_eq!(state.widths, vec![5, 8]);
+    }
+
+    #[test]
+    fn test_scroll_rows() {
+        let mut state = state();
+        press(&mut state, &[KeyCode::Down, KeyCode::Char('j')]);
+        assert_eq!(state.row, 2);
+        press(&mut state, &[KeyCode::Up]);
+        assert_eq!(state.row, 1);
+        press(&mut state, &[KeyCode::PageDown]);
+        assert_eq!(state.row, 4);
+        press(&mut state, &[KeyCode::End, KeyCode::Down]);
+        assert_eq!(state.row, 9);
+        state.scroll_into_view(3);
+        assert_eq!(state.offset, 7);
+        press(&mut state, &[KeyCode::Home, KeyCode::Up]);
+        assert_eq!(state.row, 0);
+        state.scroll_into_view(3);
+        assert_eq!(state.offset, 0);
+    }
+
+    #[test]
+    fn test_scroll_columns() {
+        let mut state = state();
+        press(&mut state, &[KeyCode::Right, KeyCode::Right]);
+        assert_eq!(state.column, 1);
+        press(&mut state, &[KeyCode::Left, KeyCode::Left]);
+        assert_eq!(state.column, 0);
+    }
+
+    #[test]
+    fn test_expanded() {
+        let mut state = state();
+        press(&mut state, &[KeyCode::Enter]);
+        assert!(state.expanded);
+        assert_eq!(press(&mut state, &[KeyCode::Esc]), Action::Continue);
+        assert!(!state.expanded);
+        assert_eq!(press(&mut state, &[KeyCode::Esc]), Action::Quit);
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut state = state();
+        press(&mut state, &[KeyCode::Down]);
+        assert_eq!(state.selection(), 1..=1);
+        assert!(!state.is_selected(1));
+        press(&mut state, &[KeyCode::Char(' '), KeyCode::Down, KeyCode::Down]);
+        assert_eq!(state.selection(), 1..=3);
+        assert!(state.is_selected(2));
+        press(&mut state, &[KeyCode::Home]);
+        assert_eq!(state.selection(), 0..=1);
+        assert_eq!(press(&mut state, &[KeyCode::Char('y')]), Action::Export);
+        press(&mut state, &[KeyCode::Esc]);
+        assert_eq!(state.anchor, None);
+    }
+
+    #[test]
+    fn test_search() {
+        let mut state = state();
+        let mut codes = vec![KeyCode::Char('/')];
+        codes.extend("NAME 1x".chars().map(KeyCode::Char));
+        codes.extend([KeyCode::Backspace, KeyCode::Enter]);
+        press(&mut state, &codes);
+        assert_eq!(state.search, None);
+        assert_eq!(state.last_search, "NAME 1");
+        assert_eq!(state.row, 9);
+        press(&mut state, &[KeyCode::Char('n')]);
+        assert_eq!(state.row, 0);
+        press(&mut state, &[KeyCode::Char('N')]);
+        assert_eq!(state.row, 9);
+        assert!(!state.not_found);
+
+        let mut codes = vec![KeyCode::Char('/')];
+        codes.extend("foo".chars().map(KeyCode::Char));
+        codes.push(KeyCode::Enter);
+        press(&mut state, &codes);
+        assert!(state.not_found);
+        assert_eq!(state.row, 9);
+    }
+
+    #[test]
+    fn test_quit() {
+        let mut state = state();
+        assert_eq!(press(&mut state, &[KeyCode::Char('q')]), Action::Quit);
+        let key = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(state.handle_key(key, 3), Action::Quit);
+    }
+
+    #[test]
+    fn test_export_without_rows() {
+        let mut state = ViewerState::new(vec!["id".to_string()], Vec::new(), 0);
+        assert_eq!(press(&mut state, &[KeyCode::Char('y')]), Action::Continue);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::viewer::state::{Action, ViewerState};
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Cell, Paragraph, Row, Table, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use rust_i18n::t;
+use std::io;
+use std::ops::RangeInclusive;
+use unicode_width::UnicodeWidthStr;
+
+/// The space between the columns of the table
+const COLUMN_SPACING: u16 = 2;
+
+/// Display the viewer in the alternate screen until it is closed; returns the range of rows
+/// selected for export, or `None` when the viewer was closed without exporting rows.
+pub(crate) fn run(
+    state: &mut ViewerState,
+    locale: &str,
+) -> io::Result<Option<RangeInclusive<usize>>> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, state, locale);
+    ratatui::try_restore()?;
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    state: &mut ViewerState,
+    locale: &str,
+) -> io::Result<Option<RangeInclusive<usize>>> {
+    loop {
+        let mut height = 0;
+        terminal.draw(|frame| height = draw(frame, state, locale))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match state.handle_key(key, height) {
+            Action::Continue => {}
+            Action::Export => return Ok(Some(state.selection())),
+            Action::Quit => return Ok(None),
+        }
+    }
+}
+
+/// Draw the viewer; returns the number of rows displayed.
+fn draw(frame: &mut Frame, state: &mut ViewerState, locale: &str) -> usize {
+    let [body, status] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let height = usize::from(body.height.saturating_sub(1));
+    state.scroll_into_view(height);
+
+    if state.expanded {
+        draw_row(frame, body, state);
+    } else {
+        draw_table(frame, body, state);
+    }
+    let status_line = Paragraph::new(status_line(state, locale))
+        .style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_widget(status_line, status);
+    height
+}
+
+/// Draw the visible rows and columns as a table; the selected row is highlighted, and rows
+/// selected for export are underlined.
+fn draw_table(frame: &mut Frame, area: Rect, state: &ViewerState) {
+    let columns = state.column..state.columns.len();
+    let header = Row::new(
+        columns
+            .clone()
+            .map(|index| Cell::from(state.columns[index].as_str())),
+    )
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let end = (state.offset + usize::from(area.height)).min(state.rows.len());
+    let rows = state.rows[state.offset..end]
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let index = state.offset + index;
+            let style = if index == state.row {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else if state.is_selected(index) {
+                Style::default().add_modifier(Modifier::UNDERLINED)
+            } else {
+                Style::default()
+            };
+            let cells = columns
+                .clone()
+                .map(|column| Cell::from(row.get(column).map_or("", String::as_str)));
+            Row::new(cells).style(style)
+        });
+    let widths = columns.clone().map(|index| {
+        Constraint::Length(u16::try_from(state.widths[index]).unwrap_or(u16::MAX))
+    });
+
+    let table = Table::new(rows, widths)
+        .header(header)
+        .column_spacing(COLUMN_SPACING);
+    frame.render_widget(table, area);
+}
+
+/// Draw the selected row with one line per column, so that long values can be read.
+fn draw_row(frame: &mut Frame, area: Rect, state: &ViewerState) {
+    let width = state
+        .columns
+        .iter()
+        .map(|column| column.width())
+        .max()
+        .unwrap_or_default();
+    let row = state.rows.get(state.row);
+    let lines: Vec<Line> = state
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let padding = " ".repeat(width - column.width());
+            let value = row
+                .and_then(|row| row.get(index))
+                .map_or("", String::as_str);
+            Line::from(vec![
+                Span::styled(
+                    format!("{column}{padding} │ "),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(value),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
+/// Get the status line with the position of the selected row and column and the keys of the
+/// viewer, or the search being entered.
+fn status_line(state: &ViewerState, locale: &str) -> String {
+    if let Some(search) = &state.search {
+        return format!("/{search}");
+    }
+    if state.not_found {
+        return t!("view_not_found", locale = locale, search = state.last_search).to_string();
+    }
+    let status = t!(
+        "view_status",
+        locale = locale,
+        row = state.row + 1,
+        rows = state.rows.len(),
+        column = state.column + 1,
+        columns = state.columns.len()
+    );
+    let help = t!("view_help", locale = locale);
+    format!(" {status}  {help}")
+}
@@ -0,0 +1,600 @@
+use crate::athena::metadata;
+use crate::error::Result;
+use crate::Error::{ConversionError, InvalidUrl, IoError};
+use crate::{MemoryQueryResult, Metadata, QueryResult, QueryStatistics, Row, Value};
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_athena::config::Credentials;
+use aws_sdk_athena::types::{
+    ColumnInfo, QueryExecution, QueryExecutionContext, QueryExecutionState,
+    QueryExecutionStatistics, ResultConfiguration, StatementType,
+};
+use aws_sdk_athena::Client;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use file_type::FileType;
+use sqlparser::dialect::{Dialect, GenericDialect};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::info;
+use url::Url;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.f";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+#[derive(Debug)]
+pub struct Driver;
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        "athena"
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let connection = Connection::new(url, password).await?;
+        Ok(Box::new(connection))
+    }
+
+    fn supports_file_type(&self, _file_type: &FileType) -> bool {
+        false
+    }
+}
+
+/// Options parsed from an Athena URL
+#[derive(Clone, Debug, PartialEq)]
+struct Options {
+    region: String,
+    access_key_id: Option<String>,
+    catalog: String,
+    database: String,
+    workgroup: String,
+    output_location: Option<String>,
+    read_from_s3: bool,
+    poll_interval: Duration,
+}
+
+impl Options {
+    /// Parse the options from an `athena://[<access_key_id>:<secret_access_key>@]<region>[/<database>]`
+    /// URL.
+    fn parse(url: &str) -> Result<Self> {
+        let parsed_url = Url::parse(url)?;
+        let query_parameters: HashMap<String, String> =
+            parsed_url.query_pairs().into_owned().collect();
+        let region = parsed_url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| InvalidUrl("Athena URL requires a region".to_string()))?
+            .to_string();
+        let access_key_id = Some(parsed_url.username().to_string()).filter(|user| !user.is_empty());
+        let database = parsed_url.path().trim_start_matches('/');
+        let database = if database.is_empty() {
+            "default".to_string()
+        } else {
+            database.to_string()
+        };
+        let poll_interval = match query_parameters.get("poll_interval") {
+            Some(poll_interval) => poll_interval
+                .parse::<u64>()
+                .map_err(|error| ConversionError(error.to_string()))?,
+            None => 500,
+        };
+
+        Ok(Self {
+            region,
+            access_key_id,
+            catalog: query_parameters
+                .get("catalog")
+                .map_or("AwsDataCatalog", String::as_str)
+                .to_string(),
+            database,
+            workgroup: query_parameters
+                .get("workgroup")
+                .map_or("primary", String::as_str)
+                .to_string(),
+            output_location: query_parameters.get("output_location").cloned(),
+            read_from_s3: query_parameters
+                .get("read_from_s3")
+                .is_some_and(|value| value == "true"),
+            poll_interval: Duration::from_millis(poll_interval),
+        })
+    }
+}
+
+/// Convert the statistics for an Athena query execution; the execution time is the time the
+/// query engine spent executing the query, excluding queueing and planning.
+fn query_statistics(statistics: &QueryExecutionStatistics) -> QueryStatistics {
+    QueryStatistics {
+        bytes_scanned: statistics
+            .data_scanned_in_bytes()
+            .and_then(|bytes| u64::try_from(bytes).ok()),
+        execution_time: statistics
+            .engine_execution_time_in_millis()
+            .and_then(|millis| u64::try_from(millis).ok())
+            .map(Duration::from_millis),
+        rows_affected: None,
+        rows_scanned: None,
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Connection {
+    url: String,
+    options: Options,
+    client: Client,
+    s3_client: aws_sdk_s3::Client,
+    statistics: Option<QueryStatistics>,
+}
+
+impl Connection {
+    /// Create a new Athena connection; credentials are read from the URL when specified, otherwise
+    /// the default AWS credential chain (environment, shared config files, instance metadata) is
+    /// used.
+    pub(crate) async fn new(url: String, password: Option<String>) -> Result<Connection> {
+        let options = Options::parse(url.as_str())?;
+        let mut loader = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(options.region.clone()));
+        if let (Some(access_key_id), Some(secret_access_key)) = (&options.access_key_id, password) {
+            let credentials =
+                Credentials::new(access_key_id, secret_access_key, None, None, "rsql");
+            loader = loader.credentials_provider(credentials);
+        }
+        let config = loader.load().await;
+
+        Ok(Connection {
+            url,
+            options,
+            client: Client::new(&config),
+            s3_client: aws_sdk_s3::Client::new(&config),
+            statistics: None,
+        })
+    }
+
+    /// Start the query and wait for it to complete
+    async fn execute_query(&mut self, sql: &str) -> Result<QueryExecution> {
+        let context = QueryExecutionContext::builder()
+            .catalog(&self.options.catalog)
+            .database(&self.options.database)
+            .build();
+        let mut request = self
+            .client
+            .start_query_execution()
+            .query_string(sql)
+            .work_group(&self.options.workgroup)
+            .query_execution_context(context);
+        if let Some(output_location) = &self.options.output_location {
+            let configuration = ResultConfiguration::builder()
+                .output_location(output_location)
+                .build();
+            request = request.result_configuration(configuration);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        let query_execution_id = response
+            .query_execution_id()
+            .ok_or_else(|| {
+                IoError(anyhow::anyhow!(
+                    "Athena did not return a query execution id"
+                ))
+            })?
+            .to_string();
+
+        loop {
+            let response = self
+                .client
+                .get_query_execution()
+                .query_execution_id(&query_execution_id)
+                .send()
+                .await
+                .map_err(|error| IoError(error.into()))?;
+            let query_execution = response
+                .query_execution()
+                .ok_or_else(|| IoError(anyhow::anyhow!("Athena query execution not found")))?
+                .clone();
+            let status = query_execution.status();
+            match status.and_then(|status| status.state()) {
+                Some(QueryExecutionState::Succeeded) => {
+                    self.statistics = query_execution.statistics().map(query_statistics);
+                    info!(%query_execution_id, statistics = ?self.statistics, "Athena query succeeded");
+                    return Ok(query_execution);
+                }
+                Some(QueryExecutionState::Failed | QueryExecutionState::Cancelled) => {
+                    let reason = status
+                        .and_then(|status| status.state_change_reason())
+                        .unwrap_or("unknown reason");
+                    return Err(IoError(anyhow::anyhow!(
+                        "Athena query {query_execution_id} failed: {reason}"
+                    )));
+                }
+                _ => tokio::time::sleep(self.options.poll_interval).await,
+            }
+        }
+    }
+
+    /// Read the results of a query execution using the Athena API
+    async fn read_results(
+        &self,
+        query_execution: &QueryExecution,
+    ) -> Result<(Vec<String>, Vec<Row>)> {
+        let query_execution_id = query_execution.query_execution_id().unwrap_or_default();
+        // The first row of the results for a SELECT statement contains the column names
+        let mut skip_header = query_execution.statement_type() == Some(&StatementType::Dml);
+        let mut columns = Vec::new();
+        let mut rows = Vec::new();
+        let mut next_token = None;
+
+        loop {
+            let response = self
+                .client
+                .get_query_results()
+                .query_execution_id(query_execution_id)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(|error| IoError(error.into()))?;
+            let Some(result_set) = response.result_set() else {
+                break;
+            };
+            if columns.is_empty() {
+                columns = result_set
+                    .result_set_metadata()
+                    .map(|metadata| metadata.column_info().to_vec())
+                    .unwrap_or_default();
+            }
+            for row in result_set.rows() {
+                if skip_header {
+                    skip_header = false;
+                    continue;
+                }
+                let values = row
+                    .data()
+                    .iter()
+                    .map(|datum| datum.var_char_value())
+                    .collect::<Vec<_>>();
+                rows.push(convert_row(&columns, &values)?);
+            }
+
+            next_token = response.next_token().map(ToString::to_string);
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        let column_names = columns
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        Ok((column_names, rows))
+    }
+
+    /// Read the results of a query execution directly from the CSV file in the S3 output location;
+    /// this is faster than paging through the results with the Athena API for large result sets.
+    async fn read_results_from_s3(
+        &self,
+        query_execution: &QueryExecution,
+    ) -> Result<(Vec<String>, Vec<Row>)> {
+        let query_execution_id = query_execution.query_execution_id().unwrap_or_default();
+        let output_location = query_execution
+            .result_configuration()
+            .and_then(|configuration| configuration.output_location())
+            .ok_or_else(|| IoError(anyhow::anyhow!("Athena query has no output location")))?;
+        let output_url = Url::parse(output_location)?;
+        let bucket = output_url.host_str().unwrap_or_default();
+        let key = output_url.path().trim_start_matches('/');
+
+        let response = self
+            .client
+            .get_query_results()
+            .query_execution_id(query_execution_id)
+            .max_results(1)
+            .send()
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        let columns = response
+            .result_set()
+            .and_then(|result_set| result_set.result_set_metadata())
+            .map(|metadata| metadata.column_info().to_vec())
+            .unwrap_or_default();
+
+        let object = self
+            .s3_client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        let bytes = object
+            .body
+            .collect()
+            .await
+            .map_err(|error| IoError(error.into()))?
+            .into_bytes();
+
+        let rows = read_csv(&columns, &bytes)?;
+        let column_names = columns
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect();
+        Ok((column_names, rows))
+    }
+}
+
+/// Read the rows from an Athena CSV result file; empty unquoted fields are null values.
+fn read_csv(columns: &[ColumnInfo], bytes: &[u8]) -> Result<Vec<Row>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_reader(bytes);
+    let mut rows = Vec::new();
+    for record in reader.byte_records() {
+        let record = record.map_err(|error| IoError(error.into()))?;
+        let values = record
+            .iter()
+            .map(|value| {
+                if value.is_empty() {
+                    None
+                } else {
+                    std::str::from_utf8(value).ok()
+                }
+            })
+            .collect::<Vec<_>>();
+        rows.push(convert_row(columns, &values)?);
+    }
+    Ok(rows)
+}
+
+/// Convert the string values of a row to values using the column types
+fn convert_row(columns: &[ColumnInfo], values: &[Option<&str>]) -> Result<Row> {
+    columns
+        .iter()
+        .zip(values)
+        .map(|(column, value)| convert_to_value(column.r#type(), *value))
+        .collect()
+}
+
+/// Convert an Athena string value to a value for the Athena data type
+fn convert_to_value(data_type: &str, value: Option<&str>) -> Result<Value> {
+    let Some(value) = value else {
+        return Ok(Value::Null);
+    };
+    let data_type = data_type.to_lowercase();
+    let data_type = data_type.split('(').next().unwrap_or_default().trim();
+    let error = |error: &dyn std::fmt::Display| {
+        ConversionError(format!(
+            "could not parse {data_type} value [{value}]: {error}"
+        ))
+    };
+
+    let value = match data_type {
+        "boolean" => Value::Bool(value.parse().map_err(|e| error(&e))?),
+        "tinyint" => Value::I8(value.parse().map_err(|e| error(&e))?),
+        "smallint" => Value::I16(value.parse().map_err(|e| error(&e))?),
+        "int" | "integer" => Value::I32(value.parse().map_err(|e| error(&e))?),
+        "bigint" => Value::I64(value.parse().map_err(|e| error(&e))?),
+        "float" | "real" => Value::F32(value.parse().map_err(|e| error(&e))?),
+        "double" => Value::F64(value.parse().map_err(|e| error(&e))?),
+        "decimal" => Value::Decimal(value.parse().map_err(|e| error(&e))?),
+        "date" => {
+            Value::Date(NaiveDate::parse_from_str(value, DATE_FORMAT).map_err(|e| error(&e))?)
+        }
+        "time" => {
+            Value::Time(NaiveTime::parse_from_str(value, TIME_FORMAT).map_err(|e| error(&e))?)
+        }
+        "timestamp" => Value::DateTime(
+            NaiveDateTime::parse_from_str(value, DATETIME_FORMAT).map_err(|e| error(&e))?,
+        ),
+        "json" => Value::Json(serde_json::from_str(value).map_err(|e| error(&e))?),
+        "varbinary" => {
+            let hex: String = value.split_whitespace().collect();
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|index| u8::from_str_radix(hex.get(index..index + 2).unwrap_or_default(), 16))
+                .collect::<std::result::Result<Vec<u8>, _>>()
+                .map_err(|e| error(&e))?;
+            Value::Bytes(bytes)
+        }
+        // includes char, varchar, string, array, map and row values
+        _ => Value::String(value.to_string()),
+    };
+    Ok(value)
+}
+
+#[async_trait]
+impl crate::Connection for Connection {
+    fn url(&self) -> &String {
+        &self.url
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<u64> {
+        let query_execution = self.execute_query(sql).await?;
+        let rows = match query_execution.statement_type() {
+            Some(StatementType::Dml) => {
+                let (_, rows) = self.read_results(&query_execution).await?;
+                u64::try_from(rows.len())?
+            }
+            _ => 0,
+        };
+        Ok(rows)
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        let query_execution = self.execute_query(sql).await?;
+        let (columns, rows) = if self.options.read_from_s3 {
+            self.read_results_from_s3(&query_execution).await?
+        } else {
+            self.read_results(&query_execution).await?
+        };
+        Ok(Box::new(MemoryQueryResult::new(columns, rows)))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn statistics(&self) -> Option<QueryStatistics> {
+        self.statistics.clone()
+    }
+
+    async fn metadata(&mut self) -> Result<Metadata> {
+        let database = self.options.database.clone();
+        metadata::get_metadata(self, database.as_str()).await
+    }
+
+    fn dialect(&self) -> Box<dyn Dialect> {
+        Box::new(GenericDialect)
+    }
+}
+
+impl crate::Capabilities for Connection {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_options() -> Result<()> {
+        let options = Options::parse(
+            "athena://AKID@us-east-1/sales?workgroup=analytics&output_location=s3://bucket/results/&read_from_s3=true&poll_interval=100",
+        )?;
+        assert_eq!(
+            options,
+            Options {
+                region: "us-east-1".to_string(),
+                access_key_id: Some("AKID".to_string()),
+                catalog: "AwsDataCatalog".to_string(),
+                database: "sales".to_string(),
+                workgroup: "analytics".to_string(),
+                output_location: Some("s3://bucket/results/".to_string()),
+                read_from_s3: true,
+                poll_interval: Duration::from_millis(100),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_defaults() -> Result<()> {
+        let options = Options::parse("athena://eu-west-1")?;
+        assert_eq!(options.region, "eu-west-1");
+        assert_eq!(options.access_key_id, None);
+        assert_eq!(options.catalog, "AwsDataCatalog");
+        assert_eq!(options.database, "default");
+        assert_eq!(options.workgroup, "primary");
+        assert_eq!(options.output_location, None);
+        assert!(!options.read_from_s3);
+        assert_eq!(options.poll_interval, Duration::from_millis(500));
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_errors() {
+        assert!(Options::parse("athena://").is_err());
+        assert!(Options::parse("athena://us-east-1?poll_interval=x").is_err());
+    }
+
+    #[test]
+    fn test_convert_to_value() -> Result<()> {
+        assert_eq!(convert_to_value("varchar", None)?, Value::Null);
+        assert_eq!(
+            convert_to_value("boolean", Some("true"))?,
+            Value::Bool(true)
+        );
+        assert_eq!(convert_to_value("tinyint", Some("8"))?, Value::I8(8));
+        assert_eq!(convert_to_value("smallint", Some("16"))?, Value::I16(16));
+        assert_eq!(convert_to_value("integer", Some("32"))?, Value::I32(32));
+        assert_eq!(convert_to_value("bigint", Some("64"))?, Value::I64(64));
+        assert_eq!(convert_to_value("real", Some("1.5"))?, Value::F32(1.5));
+        assert_eq!(convert_to_value("double", Some("2.5"))?, Value::F64(2.5));
+        assert_eq!(
+            convert_to_value("decimal(10,2)", Some("12.34"))?,
+            Value::Decimal(rust_decimal::Decimal::new(1234, 2))
+        );
+        assert_eq!(
+            convert_to_value("varchar", Some("foo"))?,
+            Value::String("foo".to_string())
+        );
+        assert_eq!(
+            convert_to_value("date", Some("2024-01-02"))?,
+            Value::Date(NaiveDate::from_ymd_opt(2024, 1, 2).expect("invalid date"))
+        );
+        assert_eq!(
+            convert_to_value("time", Some("12:34:56.789"))?,
+            Value::Time(NaiveTime::from_hms_milli_opt(12, 34, 56, 789).expect("invalid time"))
+        );
+        assert_eq!(
+            convert_to_value("timestamp", Some("2024-01-02 12:34:56.789"))?,
+            Value::DateTime(
+                NaiveDate::from_ymd_opt(2024, 1, 2)
+                    .and_then(|date| date.and_hms_milli_opt(12, 34, 56, 789))
+                    .expect("invalid datetime")
+            )
+        );
+        assert_eq!(
+            convert_to_value("json", Some(r#"{"a":1}"#))?,
+            Value::Json(serde_json::json!({"a": 1}))
+        );
+        assert_eq!(
+            convert_to_value("varbinary", Some("68 65 6c"))?,
+            Value::Bytes(b"hel".to_vec())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_value_error() {
+        assert!(convert_to_value("integer", Some("foo")).is_err());
+        assert!(convert_to_value("date", Some("foo")).is_err());
+    }
+
+    #[test]
+    fn test_read_csv() -> Result<()> {
+        let columns = vec![
+            ColumnInfo::builder()
+                .name("id")
+                .r#type("integer")
+                .build()
+                .map_err(|error| IoError(error.into()))?,
+            ColumnInfo::builder()
+                .name("name")
+                .r#type("varchar")
+                .build()
+                .map_err(|error| IoError(error.into()))?,
+        ];
+        let csv = "\"id\",\"name\"\n\"1\",\"John Doe\"\n\"2\",\n";
+
+        let rows = read_csv(&columns, csv.as_bytes())?;
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![Value::I32(1), Value::String("John Doe".to_string())],
+                vec![Value::I32(2), Value::Null],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_statistics() {
+        let statistics = QueryExecutionStatistics::builder()
+            .data_scanned_in_bytes(1024)
+            .engine_execution_time_in_millis(1500)
+            .build();
+        let statistics = query_statistics(&statistics);
+        assert_eq!(
+            statistics,
+            QueryStatistics {
+                bytes_scanned: Some(1024),
+                execution_time: Some(Duration::from_millis(1500)),
+                rows_affected: None,
+                rows_scanned: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{Column, Connection, Metadata, Result, Schema, Table};
+use indoc::formatdoc;
+
+pub(crate) async fn get_metadata(
+    connection: &mut dyn Connection,
+    database: &str,
+) -> Result<Metadata> {
+    let mut metadata = Metadata::with_dialect(connection.dialect());
+    let mut schema = Schema::new(database, true);
+
+    retrieve_tables(connection, &mut schema).await?;
+    metadata.add(schema);
+
+    Ok(metadata)
+}
+
+async fn retrieve_tables(connection: &mut dyn Connection, schema: &mut Schema) -> Result<()> {
+    let schema_name = schema.name().replace('\'', "''");
+    let sql = formatdoc! { r"
+            SELECT
+                table_name,
+                column_name,
+                data_type,
+                is_nullable,
+                column_default
+            FROM
+                information_schema.columns
+            WHERE
+                table_schema = '{schema_name}'
+            ORDER BY
+                table_name,
+                ordinal_position
+        "};
+    let mut query_result = connection.query(sql.as_str()).await?;
+
+    while let Some(row) = query_result.next().await {
+        let table_name = match row.first() {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let column_name = match row.get(1) {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let column_type = match row.get(2) {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let not_null = match row.get(3) {
+            Some(value) => value.to_string() == "NO",
+            None => continue,
+        };
+        let default_value = match row.get(4) {
+            Some(value) if !value.is_null() => Some(value.to_string()),
+            _ => None,
+        };
+
+        let column = Column::new(column_name, column_type, not_null, default_value);
+        if let Some(table) = schema.get_mut(&table_name) {
+            table.add_column(column);
+        } else {
+            let mut table = Table::new(table_name);
+            table.add_column(column);
+            schema.add(table);
+        }
+    }
+
+    Ok(())
+}
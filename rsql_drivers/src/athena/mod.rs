@@ -0,0 +1,4 @@
+pub mod driver;
+mod metadata;
+
+pub use driver::Driver;
@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use async_trait::async_trait;
 use file_type::FileType;
 use polars::io::avro::AvroReader;
@@ -8,7 +8,6 @@ use polars::io::SerReader;
 use polars::prelude::IntoLazy;
 use polars_sql::SQLContext;
 use std::fs::File;
-use url::Url;
 
 #[derive(Debug)]
 pub struct Driver;
@@ -24,8 +23,8 @@ impl crate::Driver for Driver {
         url: String,
         _password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let parsed_url = Url::parse(url.as_str())?;
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let file = File::open(&file_name)?;
 
         let data_frame = AvroReader::new(file).set_rechunk(true).finish()?;
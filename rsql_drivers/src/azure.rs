@@ -0,0 +1,387 @@
+use crate::error::Result;
+use crate::AuthenticationPrompt;
+use crate::Error::{InvalidUrl, IoError};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use file_type::FileType;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::warn;
+use url::Url;
+
+/// The Microsoft identity platform authority
+const AUTHORITY: &str = "https://login.microsoftonline.com";
+/// The tenant used when the URL does not specify a tenant
+const DEFAULT_TENANT: &str = "organizations";
+/// The public client used for the device code flow when the URL does not specify a client; this is
+/// the well known client id of the Azure CLI
+const DEFAULT_CLIENT_ID: &str = "04b07795-8ddb-461a-bbee-02f9e1bf7b46";
+/// The scope of access tokens for Azure SQL
+const SQL_SERVER_SCOPE: &str = "https://database.windows.net/.default";
+/// Cached tokens are refreshed when they expire within this duration
+const REFRESH_MARGIN: Duration = Duration::from_secs(300);
+
+/// Access tokens cached by tenant, client and scope so that connections to the same database do
+/// not authenticate again until the token is about to expire
+static TOKENS: LazyLock<Mutex<HashMap<String, CachedToken>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns true if the URL requests Azure AD (Entra ID) authentication with the `auth=azure` query
+/// parameter
+pub(crate) fn azure_enabled(url: &Url) -> bool {
+    url.query_pairs()
+        .any(|(key, value)| key == "auth" && value.eq_ignore_ascii_case("azure"))
+}
+
+/// Driver that acquires an Azure AD (Entra ID) access token and passes it to the wrapped driver as
+/// the password; `sqlserver` uses the token for Azure SQL authentication and `snowflake` for OAuth.
+/// A client secret, from the `client_secret` parameter or the password, selects the client
+/// credentials flow; otherwise the device code flow is used.
+#[derive(Debug)]
+pub(crate) struct Driver {
+    inner: Arc<dyn crate::Driver>,
+    prompt: Option<Arc<dyn AuthenticationPrompt>>,
+}
+
+impl Driver {
+    /// Create a driver that displays the device code sign in instructions with the prompt
+    pub(crate) fn new(
+        inner: Arc<dyn crate::Driver>,
+        prompt: Option<Arc<dyn AuthenticationPrompt>>,
+    ) -> Self {
+        Self { inner, prompt }
+    }
+}
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        self.inner.identifier()
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let options = Options::parse(&Url::parse(url.as_str())?, password)?;
+        let token = access_token(&options, self.prompt.as_deref()).await?;
+        self.inner.connect(url, Some(token)).await
+    }
+
+    fn supports_file_type(&self, file_type: &FileType) -> bool {
+        self.inner.supports_file_type(file_type)
+    }
+}
+
+/// Options parsed from the URL parameters: `tenant_id`, `client_id`, `client_secret` and `scope`
+#[derive(Clone, Debug, PartialEq)]
+struct Options {
+    tenant_id: String,
+    client_id: String,
+    client_secret: Option<String>,
+    scope: String,
+}
+
+impl Options {
+    fn parse(url: &Url, password: Option<String>) -> Result<Self> {
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let tenant_id = params
+            .get("tenant_id")
+            .map_or(DEFAULT_TENANT, String::as_str)
+            .to_string();
+        let client_id = params
+            .get("client_id")
+            .map_or(DEFAULT_CLIENT_ID, String::as_str)
+            .to_string();
+        let client_secret = params.get("client_secret").cloned().or(password);
+        let scope = match (params.get("scope"), url.scheme()) {
+            (Some(scope), _) => scope.to_string(),
+            (None, "sqlserver") => SQL_SERVER_SCOPE.to_string(),
+            (None, _) => {
+                return Err(InvalidUrl(
+                    "Azure AD authentication requires a scope".to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            tenant_id,
+            client_id,
+            client_secret,
+            scope,
+        })
+    }
+
+    fn cache_key(&self) -> String {
+        format!("{}/{}/{}", self.tenant_id, self.client_id, self.scope)
+    }
+
+    fn endpoint(&self, name: &str) -> String {
+        format!("{AUTHORITY}/{}/oauth2/v2.0/{name}", self.tenant_id)
+    }
+}
+
+/// A cached access token
+#[derive(Clone, Debug)]
+struct CachedToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Instant,
+}
+
+impl CachedToken {
+    fn is_valid(&self) -> bool {
+        self.expires_at > Instant::now() + REFRESH_MARGIN
+    }
+}
+
+/// A token endpoint response; errors such as `authorization_pending` are returned in `error`
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    expires_in: Option<u64>,
+    refresh_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+impl TokenResponse {
+    fn into_token(self) -> Result<CachedToken> {
+        match self.access_token {
+            Some(access_token) => Ok(CachedToken {
+                access_token,
+                refresh_token: self.refresh_token,
+                expires_at: Instant::now() + Duration::from_secs(self.expires_in.unwrap_or(0)),
+            }),
+            None => Err(IoError(anyhow!(
+                "Azure AD authentication failed: {}",
+                self.error_description
+                    .or(self.error)
+                    .unwrap_or_else(|| "no access token".to_string())
+            ))),
+        }
+    }
+}
+
+/// A device authorization response
+#[derive(Debug, Deserialize)]
+struct DeviceCodeResponse {
+    device_code: String,
+    message: String,
+    interval: u64,
+    expires_in: u64,
+}
+
+/// Get an access token for the options; a cached token is returned while it is valid, otherwise
+/// the token is refreshed or a new token is acquired
+async fn access_token(
+    options: &Options,
+    prompt: Option<&dyn AuthenticationPrompt>,
+) -> Result<String> {
+    let key = options.cache_key();
+    let mut tokens = TOKENS.lock().await;
+    let cached = tokens.get(&key);
+    if let Some(token) = cached.filter(|token| token.is_valid()) {
+        return Ok(token.access_token.clone());
+    }
+
+    let client = reqwest::Client::new();
+    let refresh_token = cached.and_then(|token| token.refresh_token.clone());
+    let token = match (&options.client_secret, refresh_token) {
+        (Some(client_secret), _) => client_credentials(&client, options, client_secret).await?,
+        (None, Some(refresh_token)) => match refresh(&client, options, &refresh_token).await {
+            Ok(token) => token,
+            Err(_) => device_code(&client, options, prompt).await?,
+        },
+        (None, None) => device_code(&client, options, prompt).await?,
+    };
+    let access_token = token.access_token.clone();
+    tokens.insert(key, token);
+    Ok(access_token)
+}
+
+/// Request a token from the token endpoint
+async fn request_token(
+    client: &reqwest::Client,
+    options: &Options,
+    form: &[(&str, &str)],
+) -> Result<TokenResponse> {
+    client
+        .post(options.endpoint("token"))
+        .form(form)
+        .send()
+        .await
+        .map_err(|error| IoError(error.into()))?
+        .json::<TokenResponse>()
+        .await
+        .map_err(|error| IoError(error.into()))
+}
+
+/// Acquire a token for a service principal with the client credentials flow
+async fn client_credentials(
+    client: &reqwest::Client,
+    options: &Options,
+    client_secret: &str,
+) -> Result<CachedToken> {
+    let form = [
+        ("grant_type", "client_credentials"),
+        ("client_id", options.client_id.as_str()),
+        ("client_secret", client_secret),
+        ("scope", options.scope.as_str()),
+    ];
+    request_token(client, options, &form).await?.into_token()
+}
+
+/// Acquire a new token with a refresh token
+async fn refresh(
+    client: &reqwest::Client,
+    options: &Options,
+    refresh_token: &str,
+) -> Result<CachedToken> {
+    let form = [
+        ("grant_type", "refresh_token"),
+        ("client_id", options.client_id.as_str()),
+        ("refresh_token", refresh_token),
+        ("scope", options.scope.as_str()),
+    ];
+    request_token(client, options, &form).await?.into_token()
+}
+
+/// Acquire a token for a user with the device code flow; the user is asked to sign in with a
+/// browser, with the instructions displayed by the prompt, and the token endpoint is polled until
+/// the sign in completes
+async fn device_code(
+    client: &reqwest::Client,
+    options: &Options,
+    prompt: Option<&dyn AuthenticationPrompt>,
+) -> Result<CachedToken> {
+    let scope = format!("{} offline_access", options.scope);
+    let device = client
+        .post(options.endpoint("devicecode"))
+        .form(&[
+            ("client_id", options.client_id.as_str()),
+            ("scope", scope.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|error| IoError(error.into()))?
+        .json::<DeviceCodeResponse>()
+        .await
+        .map_err(|error| IoError(error.into()))?;
+    if let Some(prompt) = prompt {
+        prompt.display(&device.message);
+    } else {
+        warn!("{}", device.message);
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(device.expires_in);
+    let mut interval = Duration::from_secs(device.interval.max(1));
+    let form = [
+        ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+        ("client_id", options.client_id.as_str()),
+        ("device_code", device.device_code.as_str()),
+    ];
+    loop {
+        tokio::time::sleep(interval).await;
+        let response = request_token(client, options, &form).await?;
+        match response.error.as_deref() {
+            Some("authorization_pending") => {}
+            Some("slow_down") => interval += Duration::from_secs(5),
+            _ => return response.into_token(),
+        }
+        if Instant::now() >= deadline {
+            return Err(IoError(anyhow!("Azure AD device code sign in timed out")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_azure_enabled() -> anyhow::Result<()> {
+        assert!(azure_enabled(&Url::parse(
+            "sqlserver://host/db?auth=azure"
+        )?));
+        assert!(!azure_enabled(&Url::parse("sqlserver://host/db")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_defaults() -> anyhow::Result<()> {
+        let url = Url::parse("sqlserver://server.database.windows.net/db?auth=azure")?;
+        let options = Options::parse(&url, None)?;
+        assert_eq!(options.tenant_id, DEFAULT_TENANT);
+        assert_eq!(options.client_id, DEFAULT_CLIENT_ID);
+        assert_eq!(options.client_secret, None);
+        assert_eq!(options.scope, SQL_SERVER_SCOPE);
+        assert_eq!(
+            options.endpoint("token"),
+            "https://login.microsoftonline.com/organizations/oauth2/v2.0/token"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_options() -> anyhow::Result<()> {
+        let url = Url::parse(
+            "snowflake://account.snowflakecomputing.com/?auth=azure&tenant_id=tenant&client_id=client&scope=api://snowflake/session:scope:analyst",
+        )?;
+        let options = Options::parse(&url, Some("secret".to_string()))?;
+        assert_eq!(options.tenant_id, "tenant");
+        assert_eq!(options.client_id, "client");
+        assert_eq!(options.client_secret, Some("secret".to_string()));
+        assert_eq!(options.scope, "api://snowflake/session:scope:analyst");
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_missing_scope() -> anyhow::Result<()> {
+        let url = Url::parse("snowflake://account.snowflakecomputing.com/?auth=azure")?;
+        assert!(Options::parse(&url, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_response() {
+        let response = TokenResponse {
+            access_token: Some("token".to_string()),
+            expires_in: Some(3600),
+            refresh_token: None,
+            error: None,
+            error_description: None,
+        };
+        let token = response.into_token().expect("token");
+        assert_eq!(token.access_token, "token");
+        assert!(token.is_valid());
+
+        let response = TokenResponse {
+            access_token: None,
+            expires_in: None,
+            refresh_token: None,
+            error: Some("invalid_client".to_string()),
+            error_description: None,
+        };
+        assert!(response.into_token().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_access_token_cached() -> anyhow::Result<()> {
+        let url = Url::parse("sqlserver://host/db?auth=azure&tenant_id=cached")?;
+        let options = Options::parse(&url, None)?;
+        TOKENS.lock().await.insert(
+            options.cache_key(),
+            CachedToken {
+                access_token: "cached".to_string(),
+                refresh_token: None,
+                expires_at: Instant::now() + Duration::from_secs(3600),
+            },
+        );
+        assert_eq!(access_token(&options, None).await?, "cached");
+        Ok(())
+    }
+}
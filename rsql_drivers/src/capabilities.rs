@@ -0,0 +1,299 @@
+use crate::error::Result;
+use crate::{
+    Connection, Error, Metadata, MockConnection, QueryResult, QueryStatistics, StatementMetadata,
+};
+use async_trait::async_trait;
+use futures_util::stream::BoxStream;
+use mockall::automock;
+use sqlparser::ast::Statement;
+use sqlparser::dialect::Dialect;
+use std::time::Duration;
+
+/// The optional capabilities of a connection; a capability is `None` when the database does not
+/// support it.  Connections that wrap another connection return the capabilities of the wrapped
+/// connection.
+pub trait Capabilities {
+    /// Get the bulk loading capability of the connection
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn BulkCopy> {
+        None
+    }
+
+    /// Get the replica capability of the connection
+    fn as_replica(&mut self) -> Option<&mut dyn Replica> {
+        None
+    }
+
+    /// Get the multiple result sets capability of the connection
+    fn as_multiple_results(&mut self) -> Option<&mut dyn MultipleResults> {
+        None
+    }
+
+    /// Get the notifications capability of the connection
+    fn as_notifications(&mut self) -> Option<&mut dyn Notifications> {
+        None
+    }
+
+    /// Get the server side export capability of the connection
+    fn as_export(&mut self) -> Option<&mut dyn Export> {
+        None
+    }
+
+    /// Get the historical reads capability of the connection
+    fn as_historical_reads(&mut self) -> Option<&mut dyn HistoricalReads> {
+        None
+    }
+}
+
+/// Bulk loading with the copy protocol of the database
+#[automock]
+#[async_trait]
+pub trait BulkCopy: Send + Sync {
+    /// Copy data into a table using the bulk loading protocol of the database (e.g. PostgreSQL
+    /// `COPY ... FROM STDIN`); `sql` is the copy statement and `data` is the stream of chunks in
+    /// the format specified by the statement.  Returns the number of rows copied.
+    async fn copy_in(
+        &mut self,
+        sql: &str,
+        data: BoxStream<'static, Result<Vec<u8>>>,
+    ) -> Result<u64>;
+}
+
+/// Synchronization of an embedded replica with the remote database
+#[automock]
+#[async_trait]
+pub trait Replica: Send + Sync {
+    /// Synchronize the embedded replica with the remote database (e.g. libSQL/Turso replicas)
+    async fn sync(&mut self) -> Result<()>;
+}
+
+/// Queries that return multiple result sets
+#[automock]
+#[async_trait]
+pub trait MultipleResults: Send + Sync {
+    /// Get the next result set of the last query, for statements that return multiple result sets
+    /// (e.g. stored procedures); returns `None` when there are no more result sets
+    async fn next_result(&mut self) -> Result<Option<Box<dyn QueryResult>>>;
+}
+
+/// Notification received on a channel that a connection is subscribed to (e.g. `PostgreSQL`
+/// `NOTIFY`)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    pub channel: String,
+    pub payload: String,
+}
+
+/// Notifications sent on channels
+#[automock]
+#[async_trait]
+pub trait Notifications: Send + Sync {
+    /// Subscribe to the notifications sent on a channel (e.g. PostgreSQL `LISTEN`); the stream
+    /// returns the notifications as they arrive and the subscription ends when it is dropped
+    async fn subscribe(
+        &mut self,
+        channel: &str,
+    ) -> Result<BoxStream<'static, Result<Notification>>>;
+}
+
+/// Server side export of query results
+#[automock]
+#[async_trait]
+pub trait Export: Send + Sync {
+    /// Export the results of a query to a destination of the database server using the unload
+    /// mechanism of the database (e.g. Redshift `UNLOAD` to Amazon S3), so that large results are
+    /// written by the server instead of being sent to the client; `options` are the driver
+    /// specific export options, e.g. `format=parquet`.  Returns the number of rows exported.
+    async fn export(&mut self, sql: &str, destination: &str, options: &[String]) -> Result<u64>;
+}
+
+/// Reads of historical data
+#[automock]
+#[async_trait]
+pub trait HistoricalReads: Send + Sync {
+    /// Read historical data as of the timestamp (e.g. CockroachDB `AS OF SYSTEM TIME`), or read
+    /// current data when the timestamp is `None`; the format of the timestamp is database
+    /// specific, e.g. `-10s` or `2024-01-01 00:00:00`
+    async fn set_as_of(&mut self, timestamp: Option<String>) -> Result<()>;
+}
+
+impl Capabilities for MockConnection {}
+
+/// Mock connection with optional capabilities; the methods of the connection are delegated to
+/// the mock connection, and a capability is supported when its mock is set.
+#[derive(Debug, Default)]
+pub struct MockCapableConnection {
+    pub connection: MockConnection,
+    pub bulk_copy: Option<MockBulkCopy>,
+    pub replica: Option<MockReplica>,
+    pub multiple_results: Option<MockMultipleResults>,
+    pub notifications: Option<MockNotifications>,
+    pub export: Option<MockExport>,
+    pub historical_reads: Option<MockHistoricalReads>,
+}
+
+impl Capabilities for MockCapableConnection {
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn BulkCopy> {
+        self.bulk_copy
+            .as_mut()
+            .map(|bulk_copy| bulk_copy as &mut dyn BulkCopy)
+    }
+
+    fn as_replica(&mut self) -> Option<&mut dyn Replica> {
+        self.replica
+            .as_mut()
+            .map(|replica| replica as &mut dyn Replica)
+    }
+
+    fn as_multiple_results(&mut self) -> Option<&mut dyn MultipleResults> {
+        self.multiple_results
+            .as_mut()
+            .map(|multiple_results| multiple_results as &mut dyn MultipleResults)
+    }
+
+    fn as_notifications(&mut self) -> Option<&mut dyn Notifications> {
+        self.notifications
+            .as_mut()
+            .map(|notifications| notifications as &mut dyn Notifications)
+    }
+
+    fn as_export(&mut self) -> Option<&mut dyn Export> {
+        self.export.as_mut().map(|export| export as &mut dyn Export)
+    }
+
+    fn as_historical_reads(&mut self) -> Option<&mut dyn HistoricalReads> {
+        self.historical_reads
+            .as_mut()
+            .map(|historical_reads| historical_reads as &mut dyn HistoricalReads)
+    }
+}
+
+#[async_trait]
+impl Connection for MockCapableConnection {
+    fn url(&self) -> &String {
+        self.connection.url()
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<u64> {
+        self.connection.execute(sql).await
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        self.connection.query(sql).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.connection.close().await
+    }
+
+    async fn metadata(&mut self) -> Result<Metadata> {
+        self.connection.metadata().await
+    }
+
+    async fn refresh_metadata(&mut self) -> Result<()> {
+        self.connection.refresh_metadata().await
+    }
+
+    fn statistics(&self) -> Option<QueryStatistics> {
+        self.connection.statistics()
+    }
+
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        self.connection.read_only_statement(read_only)
+    }
+
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.connection.set_read_only(read_only).await
+    }
+
+    fn is_read_only_sql(&self, sql: &str) -> bool {
+        self.connection.is_read_only_sql(sql)
+    }
+
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        self.connection.begin_transaction_statement()
+    }
+
+    fn is_connection_lost(&self, error: &Error) -> bool {
+        self.connection.is_connection_lost(error)
+    }
+
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        self.connection.set_timeout(timeout).await
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        self.connection.messages()
+    }
+
+    fn reconnected(&mut self) -> bool {
+        self.connection.reconnected()
+    }
+
+    async fn duplicate(&self) -> Result<Option<Box<dyn Connection>>> {
+        self.connection.duplicate().await
+    }
+
+    fn dialect(&self) -> Box<dyn Dialect> {
+        self.connection.dialect()
+    }
+
+    fn parse_sql(&self, sql: &str) -> StatementMetadata {
+        self.connection.parse_sql(sql)
+    }
+
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        self.connection.limit_sql(sql, limit)
+    }
+
+    async fn query_with_limit(&mut self, sql: &str, limit: usize) -> Result<Box<dyn QueryResult>> {
+        self.connection.query_with_limit(sql, limit).await
+    }
+
+    fn match_statement(&self, statement: &Statement) -> StatementMetadata {
+        self.connection.match_statement(statement)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures_util::stream;
+    use mockall::predicate::{always, eq};
+
+    #[test]
+    fn test_mock_connection_capabilities() {
+        let mut connection = MockConnection::new();
+        assert!(connection.as_bulk_copy().is_none());
+        assert!(connection.as_replica().is_none());
+        assert!(connection.as_multiple_results().is_none());
+        assert!(connection.as_notifications().is_none());
+        assert!(connection.as_export().is_none());
+        assert!(connection.as_historical_reads().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_mock_capable_connection() -> Result<()> {
+        let mut bulk_copy = MockBulkCopy::new();
+        bulk_copy
+            .expect_copy_in()
+            .with(eq("COPY users FROM STDIN"), always())
+            .returning(|_, _| Ok(2));
+        let mut connection = MockCapableConnection {
+            bulk_copy: Some(bulk_copy),
+            ..Default::default()
+        };
+        connection
+            .connection
+            .expect_execute()
+            .with(eq("DELETE FROM users"))
+            .returning(|_| Ok(1));
+
+        assert_eq!(connection.execute("DELETE FROM users").await?, 1);
+        let bulk_copy = connection.as_bulk_copy().expect("bulk copy");
+        let rows = bulk_copy
+            .copy_in("COPY users FROM STDIN", Box::pin(stream::empty()))
+            .await?;
+        assert_eq!(rows, 2);
+        Ok(())
+    }
+}
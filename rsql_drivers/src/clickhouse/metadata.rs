@@ -0,0 +1,216 @@
+use crate::{Column, Connection, Index, Metadata, Result, Schema, Table, Value};
+use indoc::indoc;
+
+pub(crate) async fn get_metadata(connection: &mut dyn Connection) -> Result<Metadata> {
+    let mut metadata = Metadata::with_dialect(connection.dialect());
+
+    retrieve_schemas(connection, &mut metadata).await?;
+
+    Ok(metadata)
+}
+
+async fn retrieve_schemas(connection: &mut dyn Connection, metadata: &mut Metadata) -> Result<()> {
+    let mut schemas = vec![];
+    let sql = indoc! { r"
+        SELECT
+            name,
+            name = currentDatabase() AS current_database
+        FROM
+            system.databases
+        ORDER BY
+            name
+    "};
+    let mut query_result = connection.query(sql).await?;
+
+    while let Some(row) = query_result.next().await {
+        let schema_name = match row.first() {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let current = matches!(row.get(1), Some(Value::U8(1)));
+        let schema = Schema::new(schema_name, current);
+        schemas.push(schema);
+    }
+
+    for mut schema in schemas {
+        if schema.current() {
+            retrieve_tables(connection, &mut schema).await?;
+            retrieve_indexes(connection, &mut schema).await?;
+        }
+        metadata.add(schema);
+    }
+
+    Ok(())
+}
+
+async fn retrieve_tables(connection: &mut dyn Connection, schema: &mut Schema) -> Result<()> {
+    let sql = indoc! { r"
+            SELECT
+                table,
+                name,
+                type,
+                default_expression
+            FROM
+                system.columns
+            WHERE
+                database = currentDatabase()
+            ORDER BY
+                table,
+                position
+        "};
+    let mut query_result = connection.query(sql).await?;
+
+    while let Some(row) = query_result.next().await {
+        let table_name = match row.first() {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let column_name = match row.get(1) {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let column_type = match row.get(2) {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let not_null = !column_type.starts_with("Nullable(");
+        let default_value = match row.get(3) {
+            Some(value) => {
+                let value = value.to_string();
+                if value.is_empty() {
+                    None
+                } else {
+                    Some(value)
+                }
+            }
+            None => continue,
+        };
+
+        let column = Column::new(column_name, column_type, not_null, default_value);
+        if let Some(table) = schema.get_mut(&table_name) {
+            table.add_column(column);
+        } else {
+            let mut table = Table::new(table_name);
+            table.add_column(column);
+            schema.add(table);
+        }
+    }
+
+    Ok(())
+}
+
+/// Retrieve the data skipping indexes; `ClickHouse` does not support unique indexes
+async fn retrieve_indexes(connection: &mut dyn Connection, schema: &mut Schema) -> Result<()> {
+    let sql = indoc! { r"
+            SELECT
+                table,
+                name,
+                expr
+            FROM
+                system.data_skipping_indices
+            WHERE
+                database = currentDatabase()
+            ORDER BY
+                table,
+                name
+        "};
+    let mut query_result = connection.query(sql).await?;
+
+    while let Some(row) = query_result.next().await {
+        let table_name = match row.first() {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let index_name = match row.get(1) {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let columns = match row.get(2) {
+            Some(value) => value
+                .to_string()
+                .split(',')
+                .map(|column| column.trim().to_string())
+                .collect(),
+            None => continue,
+        };
+
+        if let Some(table) = schema.get_mut(table_name) {
+            let index = Index::new(index_name, columns, false);
+            table.add_index(index);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MemoryQueryResult, MockConnection};
+    use sqlparser::dialect::ClickHouseDialect;
+
+    #[tokio::test]
+    async fn test_get_metadata() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_dialect()
+            .returning(|| Box::new(ClickHouseDialect {}));
+        connection.expect_query().returning(|sql| {
+            let (columns, rows) = if sql.contains("system.databases") {
+                (
+                    vec!["name", "current_database"],
+                    vec![
+                        vec![Value::String("default".to_string()), Value::U8(1)],
+                        vec![Value::String("system".to_string()), Value::U8(0)],
+                    ],
+                )
+            } else if sql.contains("system.columns") {
+                (
+                    vec!["table", "name", "type", "default_expression"],
+                    vec![
+                        vec![
+                            Value::String("users".to_string()),
+                            Value::String("id".to_string()),
+                            Value::String("UInt64".to_string()),
+                            Value::String(String::new()),
+                        ],
+                        vec![
+                            Value::String("users".to_string()),
+                            Value::String("email".to_string()),
+                            Value::String("Nullable(String)".to_string()),
+                            Value::String("''".to_string()),
+                        ],
+                    ],
+                )
+            } else {
+                (
+                    vec!["table", "name", "expr"],
+                    vec![vec![
+                        Value::String("users".to_string()),
+                        Value::String("users_email_idx".to_string()),
+                        Value::String("email".to_string()),
+                    ]],
+                )
+            };
+            let columns = columns.into_iter().map(ToString::to_string).collect();
+            Ok(Box::new(MemoryQueryResult::new(columns, rows)))
+        });
+
+        let metadata = get_metadata(&mut connection).await?;
+
+        assert_eq!(metadata.schemas().len(), 2);
+        let schema = metadata.current_schema().expect("schema");
+        assert_eq!(schema.name(), "default");
+        let table = schema.get("users").expect("users table");
+        let id_column = table.get_column("id").expect("id column");
+        assert!(id_column.not_null());
+        assert_eq!(id_column.default(), None);
+        let email_column = table.get_column("email").expect("email column");
+        assert!(!email_column.not_null());
+        assert_eq!(email_column.default(), Some("''"));
+        let index = table.get_index("users_email_idx").expect("index");
+        assert_eq!(index.columns(), ["email"]);
+        assert!(!index.unique());
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+pub mod driver;
+pub(crate) mod metadata;
+
+pub use driver::Driver;
@@ -1,8 +1,25 @@
+use crate::connect_options::connect_timeout;
 use crate::error::Result;
 use crate::postgresql::driver::Connection as PgConnection;
-use crate::{Metadata, QueryResult};
+use crate::reconnect::transaction_state;
+use crate::Error::{InvalidUrl, IoError};
+use crate::{ConnectOptions, Error, Metadata, QueryResult};
+use anyhow::anyhow;
 use async_trait::async_trait;
 use file_type::FileType;
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Location, Token, Tokenizer};
+use std::time::Duration;
+use tracing::debug;
+
+/// The number of times a statement is retried after a serialization failure by default
+const DEFAULT_MAX_RETRIES: usize = 3;
+/// The delay before the first retry; the delay doubles for each subsequent retry
+const RETRY_BACKOFF: Duration = Duration::from_millis(50);
+/// The SQLSTATE of serialization failures, which `CockroachDB` reports for transactions that must
+/// be retried
+const SERIALIZATION_FAILURE: &str = "40001";
 
 #[derive(Debug)]
 pub struct Driver;
@@ -18,7 +35,15 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url, password).await?;
+        let options = ConnectOptions::from_connection_url(&url, password)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
@@ -31,12 +56,38 @@ impl crate::Driver for Driver {
 pub struct Connection {
     url: String,
     inner: PgConnection,
+    /// The number of times a statement is retried after a serialization failure
+    max_retries: usize,
+    /// The timestamp of historical reads; queries are read `AS OF SYSTEM TIME` the timestamp
+    as_of: Option<String>,
+    /// Indicates if an explicit transaction is open; statements of explicit transactions are not
+    /// retried since the earlier statements of the transaction are lost with the failure
+    in_transaction: bool,
 }
 
 impl Connection {
-    pub async fn new(url: String, password: Option<String>) -> Result<Self> {
-        let inner = PgConnection::new(url.clone(), password).await?;
-        Ok(Self { url, inner })
+    pub async fn new(url: String, options: &ConnectOptions) -> Result<Self> {
+        let max_retries = parse_max_retries(options)?;
+        let inner = PgConnection::new(url.clone(), options).await?;
+        Ok(Self {
+            url,
+            inner,
+            max_retries,
+            as_of: None,
+            in_transaction: false,
+        })
+    }
+
+    /// Returns true if the statement should be retried after the error; the delay before the
+    /// retry doubles for each attempt
+    async fn retry(&self, attempt: usize, error: &Error) -> bool {
+        if !is_retryable(self.in_transaction, attempt, self.max_retries, error) {
+            return false;
+        }
+        let backoff = RETRY_BACKOFF * (1 << attempt.min(10));
+        debug!("retrying statement after serialization failure (attempt {attempt}): {error}");
+        tokio::time::sleep(backoff).await;
+        true
     }
 }
 
@@ -47,11 +98,30 @@ impl crate::Connection for Connection {
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
-        self.inner.execute(sql).await
+        let mut attempt = 0;
+        loop {
+            match self.inner.execute(sql).await {
+                Err(error) if self.retry(attempt, &error).await => attempt += 1,
+                result => {
+                    self.record_transaction(sql, result.is_ok());
+                    return result;
+                }
+            }
+        }
     }
 
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
-        self.inner.query(sql).await
+        let query = as_of_query(sql, self.as_of.as_deref())?;
+        let mut attempt = 0;
+        loop {
+            match self.inner.query(&query).await {
+                Err(error) if self.retry(attempt, &error).await => attempt += 1,
+                result => {
+                    self.record_transaction(sql, result.is_ok());
+                    return result;
+                }
+            }
+        }
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -61,4 +131,356 @@ impl crate::Connection for Connection {
     async fn metadata(&mut self) -> Result<Metadata> {
         self.inner.metadata().await
     }
+
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        self.inner.set_timeout(timeout).await
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        self.inner.messages()
+    }
+
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        self.inner.read_only_statement(read_only)
+    }
+
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.inner.set_read_only(read_only).await
+    }
+
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        self.inner.begin_transaction_statement()
+    }
+}
+
+#[async_trait]
+impl crate::HistoricalReads for Connection {
+    async fn set_as_of(&mut self, timestamp: Option<String>) -> Result<()> {
+        self.as_of = timestamp.as_deref().map(as_of_timestamp).transpose()?;
+        Ok(())
+    }
+}
+
+impl crate::Capabilities for Connection {
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn crate::BulkCopy> {
+        self.inner.as_bulk_copy()
+    }
+
+    fn as_historical_reads(&mut self) -> Option<&mut dyn crate::HistoricalReads> {
+        Some(self)
+    }
+}
+
+impl Connection {
+    /// Track whether an explicit transaction is open after the statement
+    fn record_transaction(&mut self, sql: &str, succeeded: bool) {
+        self.in_transaction = in_transaction_after(self.in_transaction, sql, succeeded);
+    }
+}
+
+/// Returns true if a statement that failed with the error may be retried; only serialization
+/// failures of implicit transactions are retried, since the earlier statements of an explicit
+/// transaction are rolled back with the failure
+fn is_retryable(in_transaction: bool, attempt: usize, max_retries: usize, error: &Error) -> bool {
+    !in_transaction && attempt < max_retries && is_serialization_failure(error)
+}
+
+/// Returns true if an explicit transaction is open after the statement; a failed `BEGIN` does not
+/// open a transaction, while a failed `COMMIT` or `ROLLBACK` still ends it
+fn in_transaction_after(in_transaction: bool, sql: &str, succeeded: bool) -> bool {
+    match transaction_state(sql) {
+        Some(true) => in_transaction || succeeded,
+        Some(false) => false,
+        None => in_transaction,
+    }
+}
+
+/// Get the maximum number of retries from the `max_retries` parameter
+fn parse_max_retries(options: &ConnectOptions) -> Result<usize> {
+    match options.parameters().get("max_retries") {
+        Some(value) => value
+            .parse()
+            .map_err(|_| InvalidUrl(format!("Invalid max_retries: {value}"))),
+        None => Ok(DEFAULT_MAX_RETRIES),
+    }
+}
+
+/// Returns true if the error is a serialization failure (SQLSTATE `40001`)
+fn is_serialization_failure(error: &Error) -> bool {
+    let Error::IoError(error) = error else {
+        return false;
+    };
+    error.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<sqlx::Error>(),
+            Some(sqlx::Error::Database(error))
+                if error.code().as_deref() == Some(SERIALIZATION_FAILURE)
+        )
+    })
+}
+
+/// Keywords that end the `FROM` clause of a query; `AS OF SYSTEM TIME` is inserted before them
+const FROM_CLAUSE_END: [Keyword; 12] = [
+    Keyword::WHERE,
+    Keyword::GROUP,
+    Keyword::HAVING,
+    Keyword::WINDOW,
+    Keyword::ORDER,
+    Keyword::LIMIT,
+    Keyword::OFFSET,
+    Keyword::FETCH,
+    Keyword::UNION,
+    Keyword::INTERSECT,
+    Keyword::EXCEPT,
+    Keyword::FOR,
+];
+
+/// Get the `AS OF SYSTEM TIME` expression for the timestamp.  Timestamps are quoted unless they
+/// are function calls with literal arguments, e.g. `follower_read_timestamp()` or
+/// `with_max_staleness('10s')`; other timestamps ending in `)` are rejected.
+fn as_of_timestamp(timestamp: &str) -> Result<String> {
+    let timestamp = timestamp.trim();
+    if !timestamp.ends_with(')') {
+        return Ok(format!("'{}'", timestamp.replace('\'', "''")));
+    }
+
+    let invalid = || IoError(anyhow!("Invalid AS OF SYSTEM TIME timestamp: {timestamp}"));
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, timestamp)
+        .tokenize()
+        .map_err(|_| invalid())?;
+    let valid = match tokens.as_slice() {
+        [Token::Word(word), Token::LParen, arguments @ .., Token::RParen] => {
+            word.quote_style.is_none()
+                && arguments.iter().all(|token| {
+                    matches!(
+                        token,
+                        Token::SingleQuotedString(_)
+                            | Token::Number(_, _)
+                            | Token::Comma
+                            | Token::Whitespace(_)
+                    )
+                })
+        }
+        _ => false,
+    };
+    if valid {
+        Ok(timestamp.to_string())
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Read the query as of the timestamp with `AS OF SYSTEM TIME`; the clause is inserted at the
+/// end of the `FROM` clause of the outermost query so that the `ORDER BY` and `LIMIT` of the
+/// query are kept.  Only queries (`SELECT` and `WITH`) that read from tables are changed.
+fn as_of_query(sql: &str, as_of: Option<&str>) -> Result<String> {
+    let Some(as_of) = as_of else {
+        return Ok(sql.to_string());
+    };
+    let query = sql.trim().trim_end_matches(';').trim_end();
+    let keyword = query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase();
+    if keyword != "SELECT" && keyword != "WITH" {
+        return Ok(sql.to_string());
+    }
+
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, query)
+        .tokenize_with_location()
+        .map_err(|error| IoError(error.into()))?;
+    let mut depth = 0usize;
+    let mut from = false;
+    let mut end = None;
+    for token in &tokens {
+        match &token.token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth = depth.saturating_sub(1),
+            Token::Word(word) if depth == 0 && !from => from = word.keyword == Keyword::FROM,
+            Token::Word(word) if depth == 0 && FROM_CLAUSE_END.contains(&word.keyword) => {
+                end = Some(byte_offset(query, &token.location));
+                break;
+            }
+            Token::SemiColon if depth == 0 && from => {
+                end = Some(byte_offset(query, &token.location));
+                break;
+            }
+            _ => {}
+        }
+    }
+    if !from {
+        return Ok(sql.to_string());
+    }
+
+    let (from_clause, rest) = query.split_at(end.unwrap_or(query.len()));
+    let from_clause = from_clause.trim_end();
+    if rest.is_empty() {
+        Ok(format!("{from_clause} AS OF SYSTEM TIME {as_of}"))
+    } else {
+        Ok(format!("{from_clause} AS OF SYSTEM TIME {as_of} {rest}"))
+    }
+}
+
+/// Convert a one-based line and column location of a token to a byte offset in the SQL
+fn byte_offset(sql: &str, location: &Location) -> usize {
+    let line = usize::try_from(location.line).unwrap_or_default();
+    let column = usize::try_from(location.column).unwrap_or_default();
+    let line_start: usize = sql
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let line = &sql[line_start..];
+    line_start
+        + line
+            .char_indices()
+            .nth(column.saturating_sub(1))
+            .map_or(line.len(), |(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_parse_max_retries() -> Result<()> {
+        let url = "cockroachdb://root@localhost:26257/defaultdb";
+        let options = ConnectOptions::from_url(url)?;
+        assert_eq!(parse_max_retries(&options)?, DEFAULT_MAX_RETRIES);
+        let options = options.with_parameter("max_retries", "5");
+        assert_eq!(parse_max_retries(&options)?, 5);
+        let options = ConnectOptions::from_url("cockroachdb://localhost?max_retries=foo")?;
+        assert!(parse_max_retries(&options).is_err());
+        Ok(())
+    }
+
+    /// Database error with the serialization failure SQLSTATE
+    #[derive(Debug)]
+    struct SerializationFailure;
+
+    impl std::fmt::Display for SerializationFailure {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "restart transaction")
+        }
+    }
+
+    impl std::error::Error for SerializationFailure {}
+
+    impl sqlx::error::DatabaseError for SerializationFailure {
+        fn message(&self) -> &'static str {
+            "restart transaction"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(SERIALIZATION_FAILURE))
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    #[test]
+    fn test_is_serialization_failure() {
+        let error = sqlx::Error::Database(Box::new(SerializationFailure));
+        assert!(is_serialization_failure(&Error::IoError(error.into())));
+        let error = sqlx::Error::Database(Box::new(SerializationFailure));
+        assert!(is_serialization_failure(&Error::IoError(
+            anyhow::Error::from(error).context("query failed")
+        )));
+        assert!(!is_serialization_failure(&Error::IoError(anyhow!("foo"))));
+        assert!(!is_serialization_failure(&Error::IoError(
+            sqlx::Error::RowNotFound.into()
+        )));
+        assert!(!is_serialization_failure(&InvalidUrl("foo".to_string())));
+    }
+
+    #[test]
+    fn test_as_of_timestamp() -> Result<()> {
+        assert_eq!(as_of_timestamp("-10s")?, "'-10s'");
+        assert_eq!(as_of_timestamp("it's")?, "'it''s'");
+        assert_eq!(
+            as_of_timestamp("follower_read_timestamp()")?,
+            "follower_read_timestamp()"
+        );
+        assert_eq!(
+            as_of_timestamp("with_max_staleness('10s')")?,
+            "with_max_staleness('10s')"
+        );
+        assert!(as_of_timestamp("now()) UNION SELECT password FROM users --(x)").is_err());
+        assert!(as_of_timestamp("pg_sleep(10)) ; DROP TABLE users; SELECT (1)").is_err());
+        assert!(as_of_timestamp("(SELECT now())").is_err());
+        assert!(as_of_timestamp("f(g())").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_as_of_query() -> Result<()> {
+        let as_of = Some("'-10s'");
+        assert_eq!(as_of_query("SELECT 1", None)?, "SELECT 1");
+        assert_eq!(
+            as_of_query("select * from users;", as_of)?,
+            "select * from users AS OF SYSTEM TIME '-10s'"
+        );
+        assert_eq!(
+            as_of_query("SELECT id FROM t WHERE id > 1 ORDER BY id DESC LIMIT 100", as_of)?,
+            "SELECT id FROM t AS OF SYSTEM TIME '-10s' WHERE id > 1 ORDER BY id DESC LIMIT 100"
+        );
+        assert_eq!(
+            as_of_query(
+                "WITH u AS (SELECT id FROM users WHERE id > 1) SELECT * FROM u ORDER BY id",
+                Some("follower_read_timestamp()")
+            )?,
+            "WITH u AS (SELECT id FROM users WHERE id > 1) SELECT * FROM u AS OF SYSTEM TIME \
+             follower_read_timestamp() ORDER BY id"
+        );
+        assert_eq!(
+            as_of_query(
+                "SELECT a.id FROM a JOIN b ON a.id = b.id\nWHERE a.name = 'where'",
+                as_of
+            )?,
+            "SELECT a.id FROM a JOIN b ON a.id = b.id AS OF SYSTEM TIME '-10s' \
+             WHERE a.name = 'where'"
+        );
+        assert_eq!(as_of_query("SELECT 1", as_of)?, "SELECT 1");
+        assert_eq!(as_of_query("SHOW TABLES", as_of)?, "SHOW TABLES");
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let failure =
+            || Error::IoError(sqlx::Error::Database(Box::new(SerializationFailure)).into());
+        assert!(is_retryable(false, 0, 3, &failure()));
+        assert!(is_retryable(false, 2, 3, &failure()));
+        assert!(!is_retryable(false, 3, 3, &failure()));
+        assert!(!is_retryable(true, 0, 3, &failure()));
+        assert!(!is_retryable(false, 0, 3, &Error::IoError(anyhow!("foo"))));
+    }
+
+    #[test]
+    fn test_in_transaction_after() {
+        assert!(in_transaction_after(false, "BEGIN", true));
+        assert!(!in_transaction_after(false, "BEGIN", false));
+        assert!(in_transaction_after(true, "SELECT 1", false));
+        assert!(in_transaction_after(true, "ROLLBACK TO SAVEPOINT a", true));
+        assert!(!in_transaction_after(true, "COMMIT", true));
+        assert!(!in_transaction_after(true, "ROLLBACK", false));
+        assert!(!in_transaction_after(false, "SELECT 1", true));
+    }
 }
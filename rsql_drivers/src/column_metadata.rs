@@ -0,0 +1,105 @@
+use crate::{Row, Value, ValueKind};
+
+/// Metadata for a column of a query result; attributes that are not reported by the driver are
+/// `None`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ColumnMetadata {
+    /// Name of the column
+    pub name: String,
+    /// Database type of the column, e.g. `INTEGER` or `varchar`
+    pub data_type: Option<String>,
+    /// Kind of the values of the column
+    pub kind: Option<ValueKind>,
+    /// Indicates if the column may contain null values
+    pub nullable: Option<bool>,
+}
+
+impl ColumnMetadata {
+    #[must_use]
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the database type of the column
+    #[must_use]
+    pub fn with_data_type<S: Into<String>>(mut self, data_type: S) -> Self {
+        self.data_type = Some(data_type.into());
+        self
+    }
+
+    /// Set the kind of the values of the column
+    #[must_use]
+    pub fn with_kind(mut self, kind: ValueKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Set if the column may contain null values
+    #[must_use]
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = Some(nullable);
+        self
+    }
+
+    /// Returns true if the values of the column are numeric
+    #[must_use]
+    pub fn is_numeric(&self) -> bool {
+        self.kind.is_some_and(ValueKind::is_numeric)
+    }
+}
+
+/// Complete the metadata of the columns with the values of the rows; the kind of a column is the
+/// kind of its first non-null value, and a column that contains a null value is nullable.
+pub(crate) fn infer_columns_metadata(columns: &mut [ColumnMetadata], rows: &[Row]) {
+    for (index, column) in columns.iter_mut().enumerate() {
+        let mut values = rows.iter().filter_map(|row| row.get(index));
+        if column.kind.is_none() {
+            column.kind = values.clone().find_map(Value::kind);
+        }
+        if column.nullable.is_none() && values.any(Value::is_null) {
+            column.nullable = Some(true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_column_metadata() {
+        let column = ColumnMetadata::new("id")
+            .with_data_type("INTEGER")
+            .with_kind(ValueKind::I64)
+            .with_nullable(false);
+        assert_eq!(column.name, "id");
+        assert_eq!(column.data_type, Some("INTEGER".to_string()));
+        assert_eq!(column.kind, Some(ValueKind::I64));
+        assert_eq!(column.nullable, Some(false));
+        assert!(column.is_numeric());
+        assert!(!ColumnMetadata::new("name").is_numeric());
+    }
+
+    #[test]
+    fn test_infer_columns_metadata() {
+        let mut columns = vec![
+            ColumnMetadata::new("id"),
+            ColumnMetadata::new("name"),
+            ColumnMetadata::new("created").with_kind(ValueKind::Date),
+        ];
+        let rows = vec![
+            vec![Value::I32(1), Value::Null, Value::Null],
+            vec![Value::I32(2), Value::String("foo".to_string()), Value::Null],
+        ];
+        infer_columns_metadata(&mut columns, &rows);
+        assert_eq!(columns[0].kind, Some(ValueKind::I32));
+        assert_eq!(columns[0].nullable, None);
+        assert_eq!(columns[1].kind, Some(ValueKind::String));
+        assert_eq!(columns[1].nullable, Some(true));
+        assert_eq!(columns[2].kind, Some(ValueKind::Date));
+        assert_eq!(columns[2].nullable, Some(true));
+    }
+}
@@ -1,14 +1,30 @@
+use crate::column_metadata::infer_columns_metadata;
 use crate::error::Result;
-use crate::{Metadata, Value};
+use crate::spill::SpillFile;
+use crate::{
+    BulkCopy, Capabilities, ColumnMetadata, Driver, Error, Export, HistoricalReads, Metadata,
+    MultipleResults, Notifications, QueryStatistics, Replica, Value,
+};
+use anyhow::anyhow;
 use async_trait::async_trait;
+use futures_util::stream::{self, BoxStream};
+use futures_util::Stream;
 use mockall::automock;
 use mockall::predicate::str;
-use sqlparser::ast::Statement;
-use sqlparser::dialect::{Dialect, GenericDialect};
+use sqlparser::ast::{self, Expr, Fetch, Query, SetExpr, Statement, Top, TopQuantity};
+use sqlparser::dialect::{AnsiDialect, Dialect, GenericDialect, MsSqlDialect};
 use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
+use std::fmt::{Debug, Formatter};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+use url::Url;
 
-use chrono::{NaiveTime, TimeDelta};
-use std::fmt::Debug;
+/// The time after which cached metadata is considered stale and is refreshed
+const METADATA_TTL: Duration = Duration::from_secs(60);
 
 /// A single row of a query result
 pub type Row = Vec<Value>;
@@ -17,7 +33,24 @@ pub type Row = Vec<Value>;
 #[async_trait]
 pub trait QueryResult: Debug + Send + Sync {
     async fn columns(&self) -> Vec<String>;
+
+    /// Get the metadata of the columns; by default only the names of the columns are available.
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.columns()
+            .await
+            .into_iter()
+            .map(ColumnMetadata::new)
+            .collect()
+    }
+
     async fn next(&mut self) -> Option<Row>;
+
+    /// Take the error that ended the rows early; `next` returns `None` when a row cannot be read,
+    /// so the error is checked once the rows have been read to avoid treating a truncated result
+    /// as complete.  Returns `None` when there was no error.
+    fn take_error(&mut self) -> Option<Error> {
+        None
+    }
 }
 
 /// Query result with a limit
@@ -45,6 +78,10 @@ impl QueryResult for LimitQueryResult {
         self.inner.columns().await
     }
 
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.inner.columns_metadata().await
+    }
+
     async fn next(&mut self) -> Option<Vec<Value>> {
         if self.row_index >= self.limit {
             return None;
@@ -54,25 +91,54 @@ impl QueryResult for LimitQueryResult {
         self.row_index += 1;
         value
     }
+
+    fn take_error(&mut self) -> Option<Error> {
+        self.inner.take_error()
+    }
 }
 
 /// In-memory query result
 #[derive(Clone, Debug, Default)]
 pub struct MemoryQueryResult {
     columns: Vec<String>,
+    columns_metadata: Vec<ColumnMetadata>,
     row_index: usize,
     rows: Vec<Row>,
+    spilled_rows: Option<Arc<SpillFile>>,
+    error: Option<String>,
 }
 
 impl MemoryQueryResult {
     #[must_use]
     pub fn new(columns: Vec<String>, rows: Vec<Row>) -> Self {
+        let mut columns_metadata: Vec<ColumnMetadata> =
+            columns.iter().map(ColumnMetadata::new).collect();
+        infer_columns_metadata(&mut columns_metadata, &rows);
         Self {
             columns,
+            columns_metadata,
             row_index: 0,
             rows,
+            spilled_rows: None,
+            error: None,
         }
     }
+
+    /// Set the metadata of the columns reported by the driver; attributes that are not reported
+    /// are inferred from the rows.
+    #[must_use]
+    pub fn with_columns_metadata(mut self, mut columns_metadata: Vec<ColumnMetadata>) -> Self {
+        infer_columns_metadata(&mut columns_metadata, &self.rows);
+        self.columns_metadata = columns_metadata;
+        self
+    }
+
+    /// Set the rows that follow the in-memory rows, which were written to a temporary file
+    #[must_use]
+    pub(crate) fn with_spilled_rows(mut self, spilled_rows: SpillFile) -> Self {
+        self.spilled_rows = Some(Arc::new(spilled_rows));
+        self
+    }
 }
 
 #[async_trait]
@@ -81,11 +147,80 @@ impl QueryResult for MemoryQueryResult {
         self.columns.clone()
     }
 
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.columns_metadata.clone()
+    }
+
     async fn next(&mut self) -> Option<Vec<Value>> {
-        let result = self.rows.get(self.row_index).cloned();
+        let result = match (self.rows.get(self.row_index), &self.spilled_rows) {
+            (Some(row), _) => Some(row.clone()),
+            (None, Some(spilled_rows)) => {
+                match spilled_rows.get(self.row_index - self.rows.len()) {
+                    Ok(row) => row,
+                    Err(error) => {
+                        self.error = Some(error.to_string());
+                        self.spilled_rows = None;
+                        None
+                    }
+                }
+            }
+            (None, None) => None,
+        };
         self.row_index += 1;
         result
     }
+
+    fn take_error(&mut self) -> Option<Error> {
+        let error = self.error.take()?;
+        Some(Error::IoError(anyhow!(
+            "unable to read the spilled rows: {error}"
+        )))
+    }
+}
+
+/// Adapter that exposes a query result as a [`Stream`] of rows, allowing the use of stream
+/// combinators (e.g. `while let Some(row) = stream.try_next().await? { ... }`).
+pub struct QueryResultStream {
+    inner: BoxStream<'static, Result<Row>>,
+}
+
+impl QueryResultStream {
+    #[must_use]
+    pub fn new(query_result: Box<dyn QueryResult>) -> Self {
+        let inner = stream::unfold(query_result, |mut query_result| async move {
+            let row = query_result.next().await?;
+            Some((Ok(row), query_result))
+        });
+        Self {
+            inner: Box::pin(inner),
+        }
+    }
+}
+
+impl From<Box<dyn QueryResult>> for QueryResultStream {
+    fn from(query_result: Box<dyn QueryResult>) -> Self {
+        Self::new(query_result)
+    }
+}
+
+impl Debug for QueryResultStream {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("QueryResultStream")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for QueryResultStream {
+    type Item = Result<Row>;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(context)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -96,10 +231,114 @@ pub enum StatementMetadata {
     Unknown,
 }
 
+/// Add a limit to the rows returned by the query, using the syntax of the dialect (`LIMIT`, `TOP`
+/// or `FETCH FIRST`); returns `None` when the statement is not a single query, the query already
+/// limits the rows, or the SQL contains comments.  The query is rewritten from the parsed
+/// statement, so comments, including optimizer hints (e.g. `/*+ INDEX(users) */`), would be lost.
+/// Drivers opt in to the rewrite by calling this from [`Connection::limit_sql`].
+pub(crate) fn limit_statement(dialect: &dyn Dialect, sql: &str, limit: usize) -> Option<String> {
+    let tokens = Tokenizer::new(dialect, sql).tokenize().ok()?;
+    let has_comments = tokens.iter().any(|token| {
+        matches!(
+            token,
+            Token::Whitespace(
+                Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_)
+            )
+        )
+    });
+    if has_comments {
+        return None;
+    }
+
+    let mut statements = Parser::parse_sql(dialect, sql).ok()?;
+    let [Statement::Query(query)] = statements.as_mut_slice() else {
+        return None;
+    };
+    if query.limit.is_some() || query.fetch.is_some() {
+        return None;
+    }
+    if let SetExpr::Select(select) = query.body.as_ref() {
+        if select.top.is_some() {
+            return None;
+        }
+    }
+
+    let quantity = Expr::Value(ast::Value::Number(limit.to_string(), false));
+    if dialect.is::<MsSqlDialect>() {
+        // TOP cannot be combined with OFFSET and applies to a single SELECT
+        if query.offset.is_some() {
+            return None;
+        }
+        let SetExpr::Select(select) = query.body.as_mut() else {
+            return None;
+        };
+        select.top = Some(Top {
+            with_ties: false,
+            percent: false,
+            quantity: Some(TopQuantity::Constant(limit as u64)),
+        });
+    } else if dialect.is::<AnsiDialect>() {
+        query.fetch = Some(Fetch {
+            with_ties: false,
+            percent: false,
+            quantity: Some(quantity),
+        });
+    } else {
+        query.limit = Some(quantity);
+    }
+    Some(query.to_string())
+}
+
+/// Returns true if the statement only reads from the database.  Statements that change settings
+/// (e.g. `SET`, or `PRAGMA` with a value) are not read-only, since they may make the session
+/// writable again.
+fn is_read_only_statement(statement: &Statement) -> bool {
+    match statement {
+        Statement::Query(query) => is_read_only_query(query),
+        Statement::Explain {
+            analyze, statement, ..
+        } => !*analyze || is_read_only_statement(statement),
+        Statement::ExplainTable { .. }
+        | Statement::ShowFunctions { .. }
+        | Statement::ShowVariable { .. }
+        | Statement::ShowStatus { .. }
+        | Statement::ShowVariables { .. }
+        | Statement::ShowCreate { .. }
+        | Statement::ShowColumns { .. }
+        | Statement::ShowTables { .. }
+        | Statement::ShowCollation { .. } => true,
+        Statement::Pragma { value, .. } => value.is_none(),
+        _ => false,
+    }
+}
+
+/// Returns true if the query and its common table expressions only read from the database; e.g.
+/// `SELECT ... INTO` and data modifying common table expressions write to the database
+fn is_read_only_query(query: &Query) -> bool {
+    let read_only_with = query.with.as_ref().is_none_or(|with| {
+        with.cte_tables
+            .iter()
+            .all(|cte| is_read_only_query(&cte.query))
+    });
+    read_only_with && is_read_only_set_expr(&query.body)
+}
+
+fn is_read_only_set_expr(set_expr: &SetExpr) -> bool {
+    match set_expr {
+        SetExpr::Select(select) => select.into.is_none(),
+        SetExpr::Query(query) => is_read_only_query(query),
+        SetExpr::SetOperation { left, right, .. } => {
+            is_read_only_set_expr(left) && is_read_only_set_expr(right)
+        }
+        SetExpr::Values(_) | SetExpr::Table(_) => true,
+        _ => false,
+    }
+}
+
 /// Connection to a database
 #[automock]
 #[async_trait]
-pub trait Connection: Debug + Send + Sync {
+pub trait Connection: Capabilities + Debug + Send + Sync {
     fn url(&self) -> &String;
     async fn execute(&mut self, sql: &str) -> Result<u64>;
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>>;
@@ -108,6 +347,83 @@ pub trait Connection: Debug + Send + Sync {
         unimplemented!()
     }
 
+    /// Refresh any cached metadata; connections that do not cache metadata do nothing
+    async fn refresh_metadata(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Statistics reported by the server for the last statement executed, if available
+    fn statistics(&self) -> Option<QueryStatistics> {
+        None
+    }
+
+    /// Get the statement that makes the session read-only, or writable again when `read_only` is
+    /// false; returns `None` when the database does not support read-only sessions
+    #[expect(unused_variables)]
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        None
+    }
+
+    /// Make the session read-only, or writable again when `read_only` is false, for databases that
+    /// support read-only sessions.  The default executes the read-only statement on the
+    /// connection; connections that use a pool must apply the setting to every connection taken
+    /// from the pool.
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        if let Some(statement) = self.read_only_statement(read_only) {
+            self.execute(statement).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns true if the SQL may be executed in read-only mode; every statement must be a query
+    /// or a statement that only displays information (e.g. `SHOW` or `EXPLAIN`).  SQL that cannot
+    /// be parsed is not read-only.
+    fn is_read_only_sql(&self, sql: &str) -> bool {
+        Parser::parse_sql(self.dialect().as_ref(), sql)
+            .is_ok_and(|statements| statements.iter().all(is_read_only_statement))
+    }
+
+    /// Get the statement that starts a transaction, which is ended with `COMMIT` or `ROLLBACK`;
+    /// returns `None` when the database does not support transactions, or the statements of the
+    /// connection are not guaranteed to be executed in the same session (e.g. connection pools)
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns true if the error indicates that the connection to the database was lost and a new
+    /// connection is required; drivers can override this to recognize driver specific errors
+    fn is_connection_lost(&self, error: &Error) -> bool {
+        error.is_connection_lost()
+    }
+
+    /// Set the maximum time that statements may run, or remove the limit when the timeout is zero.
+    /// Returns true if the database enforces the timeout; otherwise statements that exceed the
+    /// timeout must be cancelled by the caller.
+    #[expect(unused_variables)]
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Take the notices and warnings reported by the server for the last statement (e.g. PostgreSQL
+    /// `RAISE NOTICE`, MySQL `SHOW WARNINGS` or SQL Server `PRINT` messages); the messages are
+    /// returned once and an empty list is returned when there are no messages
+    fn messages(&mut self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Returns true, once, when the connection to the database was lost and re-established since
+    /// the last call; state that is not replayed, such as temporary tables, may have been lost
+    fn reconnected(&mut self) -> bool {
+        false
+    }
+
+    /// Open a new connection to the same database with the credentials that were resolved when
+    /// this connection was opened (e.g. a password provided by a credential provider or an
+    /// authentication token); returns `None` when the connection cannot be duplicated
+    async fn duplicate(&self) -> Result<Option<Box<dyn Connection>>> {
+        Ok(None)
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(GenericDialect)
     }
@@ -118,7 +434,11 @@ pub trait Connection: Debug + Send + Sync {
         if let Some(statement) = statements.first() {
             self.match_statement(statement)
         } else {
-            let command = if sql.len() > 6 { &sql[..6] } else { "" };
+            let command = if sql.len() > 6 {
+                sql.get(..6).unwrap_or_default()
+            } else {
+                ""
+            };
             if command.to_lowercase() == "select" {
                 StatementMetadata::Query
             } else {
@@ -127,6 +447,27 @@ pub trait Connection: Debug + Send + Sync {
         }
     }
 
+    /// Rewrite the query so that the database returns at most `limit` rows; returns `None` when
+    /// the query is not rewritten and the rows are limited by the client.  The query is not
+    /// rewritten by default, since the SQL regenerated by the parser is not guaranteed to be
+    /// equivalent for every database; drivers opt in with [`limit_statement`].
+    #[expect(unused_variables)]
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        None
+    }
+
+    /// Execute the query, asking the database for at most `limit` rows; 0 returns all the rows.
+    /// By default the query is rewritten with [`limit_sql`](Self::limit_sql); drivers that limit
+    /// the rows some other way (e.g. with a request parameter) override this.  Callers still need
+    /// to limit the rows of the result, since the limit is only applied by some databases.
+    async fn query_with_limit(&mut self, sql: &str, limit: usize) -> Result<Box<dyn QueryResult>> {
+        let limited_sql = match limit {
+            0 => None,
+            limit => self.limit_sql(sql, limit),
+        };
+        self.query(limited_sql.as_deref().unwrap_or(sql)).await
+    }
+
     fn default_match_statement(&self, statement: &Statement) -> StatementMetadata {
         match statement {
             Statement::CreateSchema { .. }
@@ -151,21 +492,100 @@ pub trait Connection: Debug + Send + Sync {
     }
 }
 
+/// Connection that caches the metadata of the wrapped connection.  Once the metadata is stale, the
+/// last known metadata continues to be returned while new metadata is retrieved in a background
+/// task using a separate connection to the database.  Background refreshes are only used for
+/// databases accessed through a host, since a separate connection to an in-memory or file database
+/// may not see the same objects; otherwise the metadata is retrieved from the wrapped connection.
 #[derive(Debug)]
 pub(crate) struct CachedMetadataConnection {
     connection: Box<dyn Connection>,
+    url: Option<String>,
+    driver: Option<Arc<dyn Driver>>,
+    password: Option<String>,
     metadata: Option<Metadata>,
-    timestamp: NaiveTime,
+    timestamp: Instant,
+    refresh: Option<JoinHandle<Result<Metadata>>>,
 }
 
 impl CachedMetadataConnection {
     #[must_use]
     pub fn new(connection: Box<dyn Connection>) -> Self {
-        let timestamp = chrono::Local::now().time();
         Self {
             connection,
+            url: None,
+            driver: None,
+            password: None,
             metadata: None,
-            timestamp,
+            timestamp: Instant::now(),
+            refresh: None,
+        }
+    }
+
+    /// Set the driver used to create the separate connection for background refreshes
+    #[must_use]
+    pub fn with_driver(mut self, driver: Arc<dyn Driver>, password: Option<String>) -> Self {
+        self.driver = Some(driver);
+        self.password = password;
+        self
+    }
+
+    /// Set the url of the connection, when it differs from the url used by the wrapped connection,
+    /// e.g. when an authentication driver removes its parameters before connecting
+    #[must_use]
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// Returns true if the metadata can be refreshed in the background
+    fn supports_background_refresh(&self) -> bool {
+        self.driver.is_some()
+            && Url::parse(self.url())
+                .is_ok_and(|url| url.host_str().is_some_and(|host| !host.is_empty()))
+    }
+
+    /// Start a background task to retrieve the metadata on a separate connection, unless one is
+    /// already running
+    fn spawn_refresh(&mut self) {
+        if self.refresh.is_some() {
+            return;
+        }
+        let Some(driver) = &self.driver else {
+            return;
+        };
+
+        let driver = Arc::clone(driver);
+        let url = self.url().clone();
+        let password = self.password.clone();
+        self.refresh = Some(tokio::spawn(async move {
+            let mut connection = driver.connect(url, password).await?;
+            let metadata = connection.metadata().await;
+            let _ = connection.close().await;
+            metadata
+        }));
+    }
+
+    /// Update the metadata with the result of a completed background refresh; the last known
+    /// metadata is kept if the refresh failed
+    async fn complete_refresh(&mut self) {
+        if !self.refresh.as_ref().is_some_and(JoinHandle::is_finished) {
+            return;
+        }
+        let Some(refresh) = self.refresh.take() else {
+            return;
+        };
+
+        if let Ok(Ok(metadata)) = refresh.await {
+            self.metadata = Some(metadata);
+            self.timestamp = Instant::now();
+        }
+    }
+
+    /// Stop any background refresh that is running
+    fn cancel_refresh(&mut self) {
+        if let Some(refresh) = self.refresh.take() {
+            refresh.abort();
         }
     }
 }
@@ -173,11 +593,12 @@ impl CachedMetadataConnection {
 #[async_trait]
 impl Connection for CachedMetadataConnection {
     fn url(&self) -> &String {
-        self.connection.url()
+        self.url.as_ref().unwrap_or_else(|| self.connection.url())
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
         if let StatementMetadata::DDL = self.parse_sql(sql) {
+            self.cancel_refresh();
             self.metadata = None;
         }
 
@@ -185,11 +606,14 @@ impl Connection for CachedMetadataConnection {
     }
 
     async fn metadata(&mut self) -> Result<Metadata> {
-        let now = chrono::Local::now().time();
-        let one_minute = TimeDelta::try_minutes(1).unwrap_or_default();
+        self.complete_refresh().await;
 
-        if self.timestamp + one_minute < now {
-            self.metadata = None;
+        if self.metadata.is_some() && self.timestamp.elapsed() > METADATA_TTL {
+            if self.supports_background_refresh() {
+                self.spawn_refresh();
+            } else {
+                self.metadata = None;
+            }
         }
 
         if let Some(metadata) = &self.metadata {
@@ -197,18 +621,89 @@ impl Connection for CachedMetadataConnection {
         } else {
             let metadata = self.connection.metadata().await?;
             self.metadata = Some(metadata.clone());
+            self.timestamp = Instant::now();
             Ok(metadata)
         }
     }
 
+    async fn refresh_metadata(&mut self) -> Result<()> {
+        if self.metadata.is_some() && self.supports_background_refresh() {
+            self.spawn_refresh();
+        } else {
+            self.metadata = None;
+        }
+        Ok(())
+    }
+
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
         self.connection.query(sql).await
     }
 
     async fn close(&mut self) -> Result<()> {
+        self.cancel_refresh();
         self.connection.close().await
     }
 
+    fn statistics(&self) -> Option<QueryStatistics> {
+        self.connection.statistics()
+    }
+
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        self.connection.read_only_statement(read_only)
+    }
+
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.connection.set_read_only(read_only).await
+    }
+
+    fn is_read_only_sql(&self, sql: &str) -> bool {
+        self.connection.is_read_only_sql(sql)
+    }
+
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        self.connection.begin_transaction_statement()
+    }
+
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        self.connection.limit_sql(sql, limit)
+    }
+
+    async fn query_with_limit(&mut self, sql: &str, limit: usize) -> Result<Box<dyn QueryResult>> {
+        self.connection.query_with_limit(sql, limit).await
+    }
+
+    fn is_connection_lost(&self, error: &Error) -> bool {
+        self.connection.is_connection_lost(error)
+    }
+
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        self.connection.set_timeout(timeout).await
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        self.connection.messages()
+    }
+
+    fn reconnected(&mut self) -> bool {
+        self.connection.reconnected()
+    }
+
+    /// The new connection is opened with the driver and password used for background refreshes
+    async fn duplicate(&self) -> Result<Option<Box<dyn Connection>>> {
+        let Some(driver) = &self.driver else {
+            return Ok(None);
+        };
+        let connection = driver
+            .connect(self.url().clone(), self.password.clone())
+            .await?;
+        let mut connection = CachedMetadataConnection::new(connection)
+            .with_driver(Arc::clone(driver), self.password.clone());
+        if let Some(url) = &self.url {
+            connection = connection.with_url(url.clone());
+        }
+        Ok(Some(Box::new(connection)))
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         self.connection.dialect()
     }
@@ -218,10 +713,58 @@ impl Connection for CachedMetadataConnection {
     }
 }
 
+impl Capabilities for CachedMetadataConnection {
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn BulkCopy> {
+        self.connection.as_bulk_copy()
+    }
+
+    fn as_multiple_results(&mut self) -> Option<&mut dyn MultipleResults> {
+        self.connection.as_multiple_results()
+    }
+
+    fn as_notifications(&mut self) -> Option<&mut dyn Notifications> {
+        self.connection.as_notifications()
+    }
+
+    fn as_export(&mut self) -> Option<&mut dyn Export> {
+        self.connection.as_export()
+    }
+
+    fn as_historical_reads(&mut self) -> Option<&mut dyn HistoricalReads> {
+        self.connection.as_historical_reads()
+    }
+
+    fn as_replica(&mut self) -> Option<&mut dyn Replica> {
+        if self.connection.as_replica().is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+/// The metadata is retrieved again after the replica is synchronized, since the remote database may
+/// have changed
+#[async_trait]
+impl Replica for CachedMetadataConnection {
+    async fn sync(&mut self) -> Result<()> {
+        let Some(replica) = self.connection.as_replica() else {
+            return Err(Error::IoError(anyhow!(
+                "sync is not supported for this connection"
+            )));
+        };
+        replica.sync().await?;
+        self.cancel_refresh();
+        self.metadata = None;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::Value;
+    use crate::{MockCapableConnection, MockDriver, MockReplica, Schema, Value, ValueKind};
+    use futures_util::{future, TryStreamExt};
 
     #[tokio::test]
     async fn test_memory_query_result_new() {
@@ -239,6 +782,34 @@ mod test {
         assert_eq!(value, &Value::String("foo".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_memory_query_result_columns_metadata() {
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let rows = vec![
+            vec![Value::I64(1), Value::Null],
+            vec![Value::I64(2), Value::String("foo".to_string())],
+        ];
+        let result = MemoryQueryResult::new(columns.clone(), rows.clone());
+
+        let columns_metadata = result.columns_metadata().await;
+        assert_eq!(columns_metadata.len(), 2);
+        assert_eq!(columns_metadata[0].name, "id");
+        assert_eq!(columns_metadata[0].data_type, None);
+        assert!(columns_metadata[0].is_numeric());
+        assert_eq!(columns_metadata[1].kind, Some(ValueKind::String));
+        assert_eq!(columns_metadata[1].nullable, Some(true));
+
+        let result = MemoryQueryResult::new(columns, rows).with_columns_metadata(vec![
+            ColumnMetadata::new("id").with_data_type("INTEGER"),
+            ColumnMetadata::new("name").with_data_type("TEXT"),
+        ]);
+        let result = LimitQueryResult::new(Box::new(result), 1);
+        let columns_metadata = result.columns_metadata().await;
+        assert_eq!(columns_metadata[0].data_type, Some("INTEGER".to_string()));
+        assert_eq!(columns_metadata[0].kind, Some(ValueKind::I64));
+        assert_eq!(columns_metadata[1].data_type, Some("TEXT".to_string()));
+    }
+
     #[tokio::test]
     async fn test_limit_query_result() {
         let columns = vec!["id".to_string()];
@@ -285,6 +856,47 @@ mod test {
         assert_eq!(data, ["1".to_string()]);
     }
 
+    #[tokio::test]
+    async fn test_query_result_stream() -> Result<()> {
+        let columns = vec!["id".to_string()];
+        let rows = vec![
+            vec![Value::I64(1)],
+            vec![Value::I64(2)],
+            vec![Value::I64(3)],
+        ];
+        let memory_result = MemoryQueryResult::new(columns, rows);
+        let mut stream = QueryResultStream::new(Box::new(memory_result));
+
+        let mut data: Vec<String> = Vec::new();
+        while let Some(row) = stream.try_next().await? {
+            let value = row.first().expect("no value");
+            data.push(value.to_string());
+        }
+
+        assert_eq!(data, ["1".to_string(), "2".to_string(), "3".to_string()]);
+        assert!(format!("{stream:?}").starts_with("QueryResultStream"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_result_stream_combinators() -> Result<()> {
+        let columns = vec!["id".to_string()];
+        let rows = vec![
+            vec![Value::I64(1)],
+            vec![Value::I64(2)],
+            vec![Value::I64(3)],
+        ];
+        let memory_result: Box<dyn QueryResult> = Box::new(MemoryQueryResult::new(columns, rows));
+
+        let rows: Vec<Row> = QueryResultStream::from(memory_result)
+            .try_filter(|row| future::ready(row.first() != Some(&Value::I64(2))))
+            .try_collect()
+            .await?;
+
+        assert_eq!(rows, vec![vec![Value::I64(1)], vec![Value::I64(3)]]);
+        Ok(())
+    }
+
     #[derive(Debug, PartialEq)]
     struct SampleConnection {
         url: String,
@@ -312,6 +924,8 @@ mod test {
         }
     }
 
+    impl Capabilities for SampleConnection {}
+
     #[test]
     fn test_default_parse_sql() {
         let connection = SampleConnection {
@@ -371,4 +985,286 @@ mod test {
             assert!(matches!(result, StatementMetadata::Unknown));
         }
     }
+
+    #[test]
+    fn test_default_is_read_only_sql() {
+        let connection = SampleConnection {
+            url: "test".to_string(),
+        };
+        let read_only = vec![
+            "SELECT * FROM users",
+            "WITH active AS (SELECT * FROM users) SELECT * FROM active",
+            "SELECT 1 UNION SELECT 2",
+            "EXPLAIN SELECT * FROM users",
+            "SHOW TABLES",
+            "",
+        ];
+        for sql in read_only {
+            assert!(connection.is_read_only_sql(sql), "{sql}");
+        }
+
+        let writes = vec![
+            "INSERT INTO users (id) VALUES (1)",
+            "TRUNCATE users",
+            "MERGE INTO users USING staging ON users.id = staging.id WHEN MATCHED THEN DELETE",
+            "GRANT SELECT ON users TO guest",
+            "COPY users FROM STDIN",
+            "CREATE FUNCTION f() RETURNS INT AS 'SELECT 1' LANGUAGE SQL",
+            "SELECT * INTO backup FROM users",
+            "EXPLAIN ANALYZE DELETE FROM users",
+            "PRAGMA query_only = OFF",
+            "SET SESSION CHARACTERISTICS AS TRANSACTION READ WRITE",
+            "SELECT 1; DROP TABLE users",
+            "SELECT * FROM",
+        ];
+        for sql in writes {
+            assert!(!connection.is_read_only_sql(sql), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_limit_sql() {
+        let connection = SampleConnection {
+            url: "test".to_string(),
+        };
+        assert_eq!(connection.limit_sql("SELECT * FROM users", 100), None);
+    }
+
+    #[test]
+    fn test_limit_statement() {
+        let dialect = GenericDialect {};
+        assert_eq!(
+            limit_statement(&dialect, "SELECT * FROM users ORDER BY id;", 100),
+            Some("SELECT * FROM users ORDER BY id LIMIT 100".to_string())
+        );
+        assert_eq!(
+            limit_statement(&dialect, "SELECT 1 UNION SELECT 2", 10),
+            Some("SELECT 1 UNION SELECT 2 LIMIT 10".to_string())
+        );
+        assert_eq!(
+            limit_statement(&dialect, "SELECT * FROM users LIMIT 5", 100),
+            None
+        );
+        assert_eq!(limit_statement(&dialect, "DELETE FROM users", 100), None);
+        assert_eq!(limit_statement(&dialect, "SELECT 1; SELECT 2", 100), None);
+        assert_eq!(limit_statement(&dialect, "SELECT", 100), None);
+    }
+
+    #[test]
+    fn test_limit_statement_comments() {
+        let dialect = GenericDialect {};
+        assert_eq!(
+            limit_statement(&dialect, "SELECT /*+ INDEX(users) */ * FROM users", 100),
+            None
+        );
+        assert_eq!(
+            limit_statement(&dialect, "-- all users\nSELECT * FROM users", 100),
+            None
+        );
+        assert_eq!(
+            limit_statement(&dialect, "SELECT '/* text */' FROM users", 100),
+            Some("SELECT '/* text */' FROM users LIMIT 100".to_string())
+        );
+    }
+
+    #[test]
+    fn test_limit_statement_dialects() {
+        assert_eq!(
+            limit_statement(&MsSqlDialect {}, "SELECT id FROM users", 100),
+            Some("SELECT TOP 100 id FROM users".to_string())
+        );
+        assert_eq!(
+            limit_statement(&MsSqlDialect {}, "SELECT TOP 5 id FROM users", 100),
+            None
+        );
+        assert_eq!(
+            limit_statement(&MsSqlDialect {}, "SELECT 1 UNION SELECT 2", 100),
+            None
+        );
+        assert_eq!(
+            limit_statement(&AnsiDialect {}, "SELECT id FROM users", 100),
+            Some("SELECT id FROM users FETCH FIRST 100 ROWS ONLY".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_sql_multibyte_prefix() {
+        let connection = SampleConnection {
+            url: "test".to_string(),
+        };
+        let result = connection.parse_sql("selec\u{e9}t");
+        assert!(matches!(result, StatementMetadata::Unknown));
+    }
+
+    fn schema_metadata(schema: &str) -> Metadata {
+        let mut metadata = Metadata::default();
+        metadata.add(Schema::new(schema, true));
+        metadata
+    }
+
+    #[tokio::test]
+    async fn test_cached_metadata_connection() -> Result<()> {
+        let mut mock_connection = MockConnection::new();
+        mock_connection
+            .expect_metadata()
+            .times(1)
+            .returning(|| Ok(schema_metadata("default")));
+        let mut connection = CachedMetadataConnection::new(Box::new(mock_connection));
+
+        assert!(connection.metadata().await?.get("default").is_some());
+        assert!(connection.metadata().await?.get("default").is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_metadata_connection_ddl() -> Result<()> {
+        let mut mock_connection = MockConnection::new();
+        mock_connection
+            .expect_metadata()
+            .times(2)
+            .returning(|| Ok(schema_metadata("default")));
+        mock_connection
+            .expect_dialect()
+            .returning(|| Box::new(GenericDialect));
+        mock_connection
+            .expect_match_statement()
+            .returning(|_| StatementMetadata::DDL);
+        mock_connection.expect_execute().returning(|_| Ok(0));
+        let mut connection = CachedMetadataConnection::new(Box::new(mock_connection));
+
+        let _ = connection.metadata().await?;
+        let _ = connection.execute("CREATE TABLE foo (id INT)").await?;
+        let _ = connection.metadata().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_metadata_connection_sync() -> Result<()> {
+        let mut replica = MockReplica::new();
+        replica.expect_sync().times(1).returning(|| Ok(()));
+        let mut mock_connection = MockCapableConnection {
+            replica: Some(replica),
+            ..Default::default()
+        };
+        mock_connection
+            .connection
+            .expect_metadata()
+            .times(2)
+            .returning(|| Ok(schema_metadata("default")));
+        let mut connection = CachedMetadataConnection::new(Box::new(mock_connection));
+
+        let _ = connection.metadata().await?;
+        connection.as_replica().expect("replica").sync().await?;
+        let _ = connection.metadata().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_metadata_connection_not_replica() {
+        let mut connection = CachedMetadataConnection::new(Box::new(MockConnection::new()));
+        assert!(connection.as_replica().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cached_metadata_connection_duplicate() -> Result<()> {
+        let mut mock_driver = MockDriver::new();
+        mock_driver
+            .expect_connect()
+            .withf(|url, password| {
+                url == "mock://localhost/database" && password.as_deref() == Some("secret")
+            })
+            .times(1)
+            .returning(|_, _| {
+                let mut mock_connection = MockConnection::new();
+                mock_connection
+                    .expect_url()
+                    .return_const("mock://localhost/database".to_string());
+                Ok(Box::new(mock_connection))
+            });
+        let mut mock_connection = MockConnection::new();
+        mock_connection
+            .expect_url()
+            .return_const("mock://localhost/database".to_string());
+        let connection = CachedMetadataConnection::new(Box::new(mock_connection))
+            .with_driver(Arc::new(mock_driver), Some("secret".to_string()));
+
+        let duplicate = connection.duplicate().await?.expect("duplicate");
+        assert_eq!(duplicate.url(), "mock://localhost/database");
+        assert!(
+            CachedMetadataConnection::new(Box::new(MockConnection::new()))
+                .duplicate()
+                .await?
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_metadata_connection_background_refresh() -> Result<()> {
+        let mut mock_driver = MockDriver::new();
+        mock_driver.expect_connect().returning(|_, _| {
+            let mut mock_connection = MockConnection::new();
+            mock_connection
+                .expect_metadata()
+                .returning(|| Ok(schema_metadata("refreshed")));
+            mock_connection.expect_close().returning(|| Ok(()));
+            Ok(Box::new(mock_connection))
+        });
+        let mut mock_connection = MockConnection::new();
+        mock_connection
+            .expect_url()
+            .return_const("mock://localhost/database".to_string());
+        mock_connection
+            .expect_metadata()
+            .times(1)
+            .returning(|| Ok(schema_metadata("default")));
+        let mut connection = CachedMetadataConnection::new(Box::new(mock_connection))
+            .with_driver(Arc::new(mock_driver), None);
+
+        assert!(connection.metadata().await?.get("default").is_some());
+        connection.refresh_metadata().await?;
+        assert!(connection.metadata().await?.get("default").is_some());
+        while !connection
+            .refresh
+            .as_ref()
+            .is_some_and(JoinHandle::is_finished)
+        {
+            tokio::task::yield_now().await;
+        }
+        assert!(connection.metadata().await?.get("refreshed").is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cached_metadata_connection_refresh_without_host() -> Result<()> {
+        let mut mock_connection = MockConnection::new();
+        mock_connection
+            .expect_url()
+            .return_const("mock://".to_string());
+        mock_connection
+            .expect_metadata()
+            .times(2)
+            .returning(|| Ok(schema_metadata("default")));
+        let mut connection = CachedMetadataConnection::new(Box::new(mock_connection))
+            .with_driver(Arc::new(MockDriver::new()), None);
+
+        let _ = connection.metadata().await?;
+        connection.refresh_metadata().await?;
+        assert!(connection.refresh.is_none());
+        let _ = connection.metadata().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_cached_metadata_connection_url() {
+        let mut mock_connection = MockConnection::new();
+        mock_connection
+            .expect_url()
+            .return_const("mock://localhost".to_string());
+        let connection = CachedMetadataConnection::new(Box::new(mock_connection));
+        assert_eq!(connection.url(), "mock://localhost");
+
+        let connection = connection.with_url("mock://localhost?auth=iam".to_string());
+        assert_eq!(connection.url(), "mock://localhost?auth=iam");
+    }
 }
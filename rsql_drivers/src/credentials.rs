@@ -0,0 +1,262 @@
+use crate::error::Result;
+use crate::Error::IoError;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use mockall::automock;
+use std::fmt::Debug;
+use std::io::Write;
+use std::process::{Command, Stdio};
+#[cfg(feature = "keyring")]
+use tracing::debug;
+use url::Url;
+
+/// The service name used to store passwords in the OS keyring
+#[cfg(feature = "keyring")]
+const KEYRING_SERVICE: &str = "rsql";
+
+/// Provides passwords for connection URLs that do not contain a password
+#[automock]
+#[async_trait]
+pub trait CredentialProvider: Debug + Send + Sync {
+    /// Get the password for the user and host of the URL; returns `None` if no password is stored
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the credential store cannot be read.
+    async fn get(&self, url: &Url) -> Result<Option<String>>;
+
+    /// Store the password for the user and host of the URL
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the credential store cannot be updated.
+    async fn store(&self, url: &Url, password: &str) -> Result<()>;
+}
+
+/// Displays the messages of interactive sign in flows to the user, e.g. the instructions to sign in
+/// to Azure AD with a device code
+#[automock]
+pub trait AuthenticationPrompt: Debug + Send + Sync {
+    /// Display the message to the user
+    fn display(&self, message: &str);
+}
+
+/// Run a blocking credential store operation on the blocking thread pool so that the runtime is
+/// not blocked while the OS keyring or a credential helper responds
+async fn run_blocking<T, F>(operation: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|error| IoError(error.into()))?
+}
+
+/// Get the key used to identify the credentials of a URL, e.g. `user@localhost:5432`; returns
+/// `None` when the URL does not contain both a user and a host.
+#[must_use]
+pub fn credential_key(url: &Url) -> Option<String> {
+    let username = url.username();
+    let host = url.host_str().filter(|host| !host.is_empty())?;
+    if username.is_empty() {
+        return None;
+    }
+    match url.port() {
+        Some(port) => Some(format!("{username}@{host}:{port}")),
+        None => Some(format!("{username}@{host}")),
+    }
+}
+
+/// Credential provider that stores passwords in the OS keyring (macOS Keychain, Windows
+/// Credential Manager or the Linux Secret Service)
+#[cfg(feature = "keyring")]
+#[derive(Debug, Default)]
+pub struct KeyringProvider;
+
+#[cfg(feature = "keyring")]
+#[async_trait]
+impl CredentialProvider for KeyringProvider {
+    async fn get(&self, url: &Url) -> Result<Option<String>> {
+        let Some(key) = credential_key(url) else {
+            return Ok(None);
+        };
+        run_blocking(move || {
+            let entry = keyring::Entry::new(KEYRING_SERVICE, &key)?;
+            match entry.get_password() {
+                Ok(password) => Ok(Some(password)),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(error) => {
+                    debug!("unable to read the password for {key} from the keyring: {error}");
+                    Ok(None)
+                }
+            }
+        })
+        .await
+    }
+
+    async fn store(&self, url: &Url, password: &str) -> Result<()> {
+        let Some(key) = credential_key(url) else {
+            return Err(IoError(anyhow!(
+                "a user and host are required to store a password"
+            )));
+        };
+        let password = password.to_string();
+        run_blocking(move || {
+            let entry = keyring::Entry::new(KEYRING_SERVICE, &key)?;
+            entry.set_password(&password)?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Credential provider that runs an external credential helper command.  The helper is invoked
+/// with a `get` or `store` argument and receives `key=value` lines on stdin (`protocol`, `host`,
+/// `port`, `username` and, when storing, `password`), following the git credential helper
+/// protocol; for `get` the helper writes a `password=<password>` line to stdout.
+#[derive(Clone, Debug)]
+pub struct HelperProvider {
+    command: String,
+}
+
+impl HelperProvider {
+    /// Create a provider for the helper command; arguments are separated by whitespace
+    #[must_use]
+    pub fn new<S: Into<String>>(command: S) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+
+    /// Run the helper with the action on the blocking thread pool
+    async fn run(&self, action: &str, url: &Url, password: Option<&str>) -> Result<Option<String>> {
+        let helper = self.clone();
+        let action = action.to_string();
+        let url = url.clone();
+        let password = password.map(ToString::to_string);
+        run_blocking(move || helper.run_command(&action, &url, password.as_deref())).await
+    }
+
+    /// Run the helper with the action and write the attributes of the URL to stdin
+    fn run_command(
+        &self,
+        action: &str,
+        url: &Url,
+        password: Option<&str>,
+    ) -> Result<Option<String>> {
+        let mut parts = self.command.split_whitespace();
+        let program = parts
+            .next()
+            .ok_or_else(|| IoError(anyhow!("credential helper command is empty")))?;
+        let mut child = Command::new(program)
+            .args(parts)
+            .arg(action)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|error| IoError(anyhow!("unable to start {program}: {error}")))?;
+
+        let mut input = format!("protocol={}\n", url.scheme());
+        if let Some(host) = url.host_str() {
+            input.push_str(&format!("host={host}\n"));
+        }
+        if let Some(port) = url.port() {
+            input.push_str(&format!("port={port}\n"));
+        }
+        if !url.username().is_empty() {
+            input.push_str(&format!("username={}\n", url.username()));
+        }
+        if let Some(password) = password {
+            input.push_str(&format!("password={password}\n"));
+        }
+        input.push('\n');
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(IoError(anyhow!(
+                "credential helper {program} failed: {}",
+                output.status
+            )));
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let password = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("password="))
+            .map(ToString::to_string);
+        Ok(password)
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for HelperProvider {
+    async fn get(&self, url: &Url) -> Result<Option<String>> {
+        self.run("get", url, None).await
+    }
+
+    async fn store(&self, url: &Url, password: &str) -> Result<()> {
+        self.run("store", url, Some(password)).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use indoc::indoc;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_credential_key() -> anyhow::Result<()> {
+        let url = Url::parse("postgresql://user@localhost:5432/db")?;
+        assert_eq!(
+            credential_key(&url),
+            Some("user@localhost:5432".to_string())
+        );
+        let url = Url::parse("mysql://user@localhost/db")?;
+        assert_eq!(credential_key(&url), Some("user@localhost".to_string()));
+        let url = Url::parse("postgresql://localhost/db")?;
+        assert_eq!(credential_key(&url), None);
+        let url = Url::parse("rusqlite://")?;
+        assert_eq!(credential_key(&url), None);
+        Ok(())
+    }
+
+    const HELPER: &str = indoc! {r#"
+        input=$(cat)
+        case "$1" in
+          get) case "$input" in *"username=user"*) echo "password=secret" ;; esac ;;
+          store) case "$input" in *"password=secret"*) ;; *) exit 1 ;; esac ;;
+          *) exit 1 ;;
+        esac
+    "#};
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_helper_provider() -> anyhow::Result<()> {
+        let mut script = NamedTempFile::new()?;
+        script.write_all(HELPER.as_bytes())?;
+        let provider = HelperProvider::new(format!("sh {}", script.path().to_string_lossy()));
+
+        let url = Url::parse("postgresql://user@localhost/db")?;
+        assert_eq!(provider.get(&url).await?, Some("secret".to_string()));
+        provider.store(&url, "secret").await?;
+        assert!(provider.store(&url, "foo").await.is_err());
+
+        let url = Url::parse("postgresql://other@localhost/db")?;
+        assert_eq!(provider.get(&url).await?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_helper_provider_empty_command() -> anyhow::Result<()> {
+        let provider = HelperProvider::new(" ");
+        let url = Url::parse("postgresql://user@localhost/db")?;
+        assert!(provider.get(&url).await.is_err());
+        Ok(())
+    }
+}
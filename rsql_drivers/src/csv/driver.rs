@@ -17,7 +17,8 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let url = format!("{url}?separator=,");
+        let query_separator = if url.contains('?') { '&' } else { '?' };
+        let url = format!("{url}{query_separator}separator=,");
         DelimitedDriver.connect(url, password).await
     }
 
@@ -70,4 +71,30 @@ mod test {
         connection.close().await?;
         Ok(())
     }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_compressed_file() -> anyhow::Result<()> {
+        let database_url = dataset_url("csv", "users.csv.gz");
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection
+            .query("SELECT id, name FROM users ORDER BY id")
+            .await?;
+
+        assert_eq!(query_result.columns().await, vec!["id", "name"]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(1), Value::String("John Doe".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(2), Value::String("Jane Smith".to_string())])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
 }
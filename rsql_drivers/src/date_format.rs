@@ -0,0 +1,82 @@
+use crate::Value;
+use std::fmt::Write;
+
+/// The strftime patterns used to display date, time and date time values, e.g. `%d/%m/%Y`;
+/// values are displayed in the default ISO 8601 format when a pattern is not set.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DateFormat {
+    pub date: Option<String>,
+    pub time: Option<String>,
+    pub datetime: Option<String>,
+}
+
+impl DateFormat {
+    /// Format a date, time or date time value with the matching pattern.  Returns `None` for
+    /// other values, when the pattern is not set, or when the pattern cannot format the value,
+    /// e.g. a date pattern that contains hours.
+    #[must_use]
+    pub fn format(&self, value: &Value) -> Option<String> {
+        let mut formatted = String::new();
+        let result = match value {
+            Value::Date(date) => write!(formatted, "{}", date.format(self.date.as_deref()?)),
+            Value::Time(time) => write!(formatted, "{}", time.format(self.time.as_deref()?)),
+            Value::DateTime(datetime) => {
+                write!(formatted, "{}", datetime.format(self.datetime.as_deref()?))
+            }
+            _ => return None,
+        };
+        result.ok().map(|()| formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    fn date_format() -> DateFormat {
+        DateFormat {
+            date: Some("%d/%m/%Y".to_string()),
+            time: Some("%H:%M".to_string()),
+            datetime: Some("%d/%m/%Y %H:%M".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        let date_format = date_format();
+        let date = NaiveDate::from_ymd_opt(2000, 12, 31).expect("invalid date");
+        let time = NaiveTime::from_hms_opt(12, 13, 14).expect("invalid time");
+        let datetime = NaiveDateTime::new(date, time);
+
+        assert_eq!(
+            date_format.format(&Value::Date(date)),
+            Some("31/12/2000".to_string())
+        );
+        assert_eq!(
+            date_format.format(&Value::Time(time)),
+            Some("12:13".to_string())
+        );
+        assert_eq!(
+            date_format.format(&Value::DateTime(datetime)),
+            Some("31/12/2000 12:13".to_string())
+        );
+        assert_eq!(date_format.format(&Value::I64(42)), None);
+    }
+
+    #[test]
+    fn test_format_default() {
+        let date = NaiveDate::from_ymd_opt(2000, 12, 31).expect("invalid date");
+        assert_eq!(DateFormat::default().format(&Value::Date(date)), None);
+    }
+
+    #[test]
+    fn test_format_invalid_pattern() {
+        let date_format = DateFormat {
+            date: Some("%H:%M".to_string()),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2000, 12, 31).expect("invalid date");
+        assert_eq!(date_format.format(&Value::Date(date)), None);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::{Index, Table};
+use sqlparser::dialect::Dialect;
+use std::fmt::Write;
+
+/// Prefix of the indexes that `SQLite` creates for primary key and unique constraints; the indexes
+/// cannot be created explicitly, so they are generated as table constraints
+const SQLITE_AUTOINDEX_PREFIX: &str = "sqlite_autoindex_";
+
+impl Table {
+    /// Generate the `CREATE TABLE` statement of the table, including the columns, data types,
+    /// nullability, default values and primary key, followed by a `CREATE INDEX` statement for
+    /// each index. Identifiers are quoted with the quote style of the dialect when required.
+    ///
+    /// The metadata does not identify primary keys, so a unique index is used as the primary key
+    /// when the index is named using the conventions of the database, e.g. `PRIMARY` (`MySQL`),
+    /// `<table>_pkey` (`PostgreSQL`) or `PK_<table>` (SQL Server).
+    #[must_use]
+    pub fn ddl(&self, dialect: &dyn Dialect) -> String {
+        let quote = |identifier: &str| quote_identifier(dialect, identifier);
+        let mut definitions: Vec<String> = self
+            .columns()
+            .iter()
+            .map(|column| {
+                let mut definition = format!("{} {}", quote(column.name()), column.data_type());
+                if column.not_null() {
+                    definition.push_str(" NOT NULL");
+                }
+                if let Some(default) = column.default() {
+                    let _ = write!(definition, " DEFAULT {default}");
+                }
+                definition
+            })
+            .collect();
+
+        let primary_key = self
+            .indexes()
+            .into_iter()
+            .find(|index| is_primary_key(index));
+        if let Some(index) = primary_key {
+            definitions.push(format!("PRIMARY KEY ({})", quote_columns(dialect, index)));
+        }
+        let (constraints, indexes): (Vec<&Index>, Vec<&Index>) = self
+            .indexes()
+            .into_iter()
+            .filter(|index| {
+                !primary_key.is_some_and(|primary_key| std::ptr::eq(primary_key, *index))
+            })
+            .partition(|index| index.name().starts_with(SQLITE_AUTOINDEX_PREFIX));
+        for index in constraints {
+            definitions.push(format!("UNIQUE ({})", quote_columns(dialect, index)));
+        }
+
+        let table_name = quote(self.name());
+        let mut ddl = format!(
+            "CREATE TABLE {table_name} (\n    {}\n);",
+            definitions.join(",\n    ")
+        );
+        for index in indexes {
+            let unique = if index.unique() { "UNIQUE " } else { "" };
+            let _ = write!(
+                ddl,
+                "\nCREATE {unique}INDEX {} ON {table_name} ({});",
+                quote(index.name()),
+                quote_columns(dialect, index)
+            );
+        }
+        ddl
+    }
+}
+
+/// Check if the index is the primary key of the table based on the naming conventions of the
+/// databases
+fn is_primary_key(index: &Index) -> bool {
+    let name = index.name().to_lowercase();
+    index.unique() && (name == "primary" || name.ends_with("_pkey") || name.starts_with("pk_"))
+}
+
+/// Quote the columns of the index and join them with commas
+fn quote_columns(dialect: &dyn Dialect, index: &Index) -> String {
+    index
+        .columns()
+        .iter()
+        .map(|column| quote_identifier(dialect, column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Quote the identifier when it is not a lowercase identifier, using the quote style of the
+/// dialect, or double quotes when the dialect does not define a quote style
+fn quote_identifier(dialect: &dyn Dialect, identifier: &str) -> String {
+    let simple = identifier
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if simple {
+        return identifier.to_string();
+    }
+
+    let quote = dialect.identifier_quote_style(identifier).unwrap_or('"');
+    let end_quote = if quote == '[' { ']' } else { quote };
+    let escaped = identifier.replace(end_quote, &format!("{end_quote}{end_quote}"));
+    format!("{quote}{escaped}{end_quote}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Column;
+    use indoc::indoc;
+    use sqlparser::dialect::{GenericDialect, MySqlDialect};
+
+    fn table() -> Table {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("id", "INTEGER", true, None));
+        table.add_column(Column::new("email", "VARCHAR(100)", true, None));
+        table.add_column(Column::new("Name", "TEXT", false, Some("'foo'")));
+        table.add_index(Index::new("users_pkey", vec!["id"], true));
+        table.add_index(Index::new("users_email_key", vec!["email"], true));
+        table.add_index(Index::new("users_name_idx", vec!["Name", "email"], false));
+        table
+    }
+
+    #[test]
+    fn test_ddl() {
+        let ddl = table().ddl(&GenericDialect);
+        let expected = indoc! {r#"
+            CREATE TABLE users (
+                id INTEGER NOT NULL,
+                email VARCHAR(100) NOT NULL,
+                "Name" TEXT DEFAULT 'foo',
+                PRIMARY KEY (id)
+            );
+            CREATE UNIQUE INDEX users_email_key ON users (email);
+            CREATE INDEX users_name_idx ON users ("Name", email);"#};
+        assert_eq!(ddl, expected);
+    }
+
+    #[test]
+    fn test_ddl_mysql() {
+        let mut table = Table::new("Users");
+        table.add_column(Column::new("id", "int", true, None));
+        table.add_index(Index::new("PRIMARY", vec!["id"], true));
+        let ddl = table.ddl(&MySqlDialect {});
+        let expected = indoc! {r"
+            CREATE TABLE `Users` (
+                id int NOT NULL,
+                PRIMARY KEY (id)
+            );"};
+        assert_eq!(ddl, expected);
+    }
+
+    #[test]
+    fn test_ddl_sqlite_autoindex() {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("email", "TEXT", false, None));
+        table.add_index(Index::new("sqlite_autoindex_users_1", vec!["email"], true));
+        let ddl = table.ddl(&GenericDialect);
+        let expected = indoc! {r"
+            CREATE TABLE users (
+                email TEXT,
+                UNIQUE (email)
+            );"};
+        assert_eq!(ddl, expected);
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(quote_identifier(&GenericDialect, "users"), "users");
+        assert_eq!(quote_identifier(&GenericDialect, "_id2"), "_id2");
+        assert_eq!(quote_identifier(&GenericDialect, "Users"), "\"Users\"");
+        assert_eq!(quote_identifier(&GenericDialect, "1st"), "\"1st\"");
+        assert_eq!(quote_identifier(&GenericDialect, "a\"b"), "\"a\"\"b\"");
+        assert_eq!(quote_identifier(&MySqlDialect {}, "a b"), "`a b`");
+    }
+}
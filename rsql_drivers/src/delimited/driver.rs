@@ -1,15 +1,22 @@
 use crate::error::Result;
+use crate::polars::driver::parse_column_types;
+use crate::polars::write::{FileFormat, FileWriter};
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use crate::Error::ConversionError;
 use async_trait::async_trait;
 use file_type::FileType;
 use polars::io::SerReader;
-use polars::prelude::{CsvParseOptions, CsvReadOptions, IntoLazy};
+use polars::prelude::{
+    CsvEncoding, CsvParseOptions, CsvReadOptions, DataFrame, DataType, Field, IntoLazy,
+    IntoSeries, Schema, StringMethods,
+};
 use polars_sql::SQLContext;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fs::File;
+use std::io::{Cursor, Read};
+use std::sync::Arc;
 use url::Url;
 
 #[derive(Debug)]
@@ -31,7 +38,8 @@ impl crate::Driver for Driver {
             parsed_url.query_pairs().into_owned().collect();
 
         // Read Options
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let file = File::open(&file_name)?;
         let has_header = query_parameters
             .get("has_header")
@@ -52,18 +60,16 @@ impl crate::Driver for Driver {
             }
             None => Some(100),
         };
-        let skip_rows = query_parameters
-            .get("skip_rows")
-            .unwrap_or(&"0".to_string())
-            .parse::<usize>()
-            .map_err(|error| ConversionError(error.to_string()))?;
-        let skip_rows_after_header = query_parameters
-            .get("skip_rows_after_header")
-            .unwrap_or(&"0".to_string())
-            .parse::<usize>()
-            .map_err(|error| ConversionError(error.to_string()))?;
+        let skip_rows = parse_usize(&query_parameters, "skip_rows")?;
+        let skip_rows_after_header = parse_usize(&query_parameters, "skip_rows_after_header")?;
+        let schema_overwrite = schema_overwrite(&query_parameters)?;
 
         // Parse Options
+        let comment_prefix = query_parameters.get("comment").map(String::as_str);
+        let encoding = match query_parameters.get("encoding") {
+            Some(encoding) => Encoding::from_str(encoding)?,
+            None => Encoding::Utf8,
+        };
         let eol = match query_parameters.get("eol") {
             Some(eol) => string_to_ascii_char(eol)?,
             None => b'\n',
@@ -72,32 +78,67 @@ impl crate::Driver for Driver {
             Some(quote) => Some(string_to_ascii_char(quote)?),
             None => None,
         };
-        let separator = match query_parameters.get("separator") {
+        // The delimiter parameter takes precedence over the separator parameter, which is added to
+        // the url by the csv and tsv drivers
+        let separator = match query_parameters
+            .get("delimiter")
+            .or_else(|| query_parameters.get("separator"))
+        {
             Some(separator) => string_to_ascii_char(separator)?,
             None => b',',
         };
+        if separator == eol || quote == Some(separator) || quote == Some(eol) {
+            return Err(ConversionError(
+                "The separator, quote and eol characters must be distinct".to_string(),
+            ));
+        }
 
-        let data_frame = CsvReadOptions::default()
+        // Locale Options
+        let (decimal_comma, date_format) = locale_options(&query_parameters, separator)?;
+
+        let read_options = CsvReadOptions::default()
             .with_has_header(has_header)
             .with_ignore_errors(ignore_errors)
             .with_infer_schema_length(infer_schema_length)
             .with_skip_rows(skip_rows)
             .with_skip_rows_after_header(skip_rows_after_header)
+            .with_schema_overwrite(schema_overwrite)
             .with_parse_options(
                 CsvParseOptions::default()
+                    .with_comment_prefix(comment_prefix)
+                    .with_decimal_comma(decimal_comma)
+                    .with_encoding(encoding.csv_encoding())
                     .with_eol_char(eol)
                     .with_quote_char(quote)
                     .with_separator(separator),
             )
-            .with_rechunk(true)
-            .into_reader_with_file_handle(file)
-            .finish()?;
+            .with_rechunk(true);
+        let data_frame = read_data_frame(file, read_options, encoding, date_format)?;
 
         let table_name = crate::polars::driver::get_table_name(file_name)?;
         let mut context = SQLContext::new();
         context.register(table_name.as_str(), data_frame.lazy());
 
-        let connection = Connection::new(url, context).await?;
+        let mut connection = Connection::new(url, context).await?;
+        // Changes are only written back when the file is read as is, so that rewriting the file
+        // does not discard skipped rows, comments or the original encoding
+        let writable = !local_file.is_temporary()
+            && skip_rows == 0
+            && skip_rows_after_header == 0
+            && comment_prefix.is_none()
+            && encoding == Encoding::Utf8
+            && eol == b'\n'
+            && !decimal_comma
+            && date_format.is_none();
+        if writable {
+            let format = FileFormat::Csv {
+                has_header,
+                quote,
+                separator,
+            };
+            let writer = FileWriter::new(local_file.path(), table_name, format);
+            connection = connection.with_writer(writer);
+        }
         Ok(Box::new(connection))
     }
 
@@ -106,6 +147,170 @@ impl crate::Driver for Driver {
     }
 }
 
+/// Parse the numeric query parameter; the value is 0 when the parameter is not specified
+fn parse_usize(query_parameters: &HashMap<String, String>, name: &str) -> Result<usize> {
+    match query_parameters.get(name) {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|error| ConversionError(error.to_string())),
+        None => Ok(0),
+    }
+}
+
+/// Get the schema of the columns with overridden types; the columns are read using the specified
+/// type, rather than casting the inferred type, so that values such as zip codes with leading zeros
+/// are preserved
+fn schema_overwrite(query_parameters: &HashMap<String, String>) -> Result<Option<Arc<Schema>>> {
+    let Some(types) = query_parameters.get("types") else {
+        return Ok(None);
+    };
+    let schema: Schema = parse_column_types(types)?
+        .into_iter()
+        .map(|(column, data_type)| Field::new(column.into(), data_type))
+        .collect();
+    Ok(Some(Arc::new(schema)))
+}
+
+/// Get the decimal comma and date format options; the `decimal_comma` and `date_format`
+/// parameters take precedence over the conventions of the locale
+fn locale_options(
+    query_parameters: &HashMap<String, String>,
+    separator: u8,
+) -> Result<(bool, Option<&str>)> {
+    let locale = match query_parameters.get("locale") {
+        Some(locale) => Some(Locale::from_str(locale)?),
+        None => None,
+    };
+    let decimal_comma = match query_parameters.get("decimal_comma") {
+        Some(decimal_comma) => decimal_comma == "true",
+        None => locale.is_some_and(|locale| locale.decimal_comma),
+    };
+    let date_format = query_parameters
+        .get("date_format")
+        .map(String::as_str)
+        .or(locale.map(|locale| locale.date_format));
+    if decimal_comma && separator == b',' {
+        return Err(ConversionError(
+            "The decimal comma cannot be used with the ',' separator; specify a delimiter"
+                .to_string(),
+        ));
+    }
+    Ok((decimal_comma, date_format))
+}
+
+/// Read the file into a data frame; Latin-1 files are converted to UTF-8 before they are read and
+/// the date columns are parsed when a date format is specified
+fn read_data_frame(
+    file: File,
+    read_options: CsvReadOptions,
+    encoding: Encoding,
+    date_format: Option<&str>,
+) -> Result<DataFrame> {
+    let data_frame = if encoding == Encoding::Latin1 {
+        let data = latin1_to_utf8(file)?;
+        read_options
+            .into_reader_with_file_handle(Cursor::new(data))
+            .finish()?
+    } else {
+        read_options.into_reader_with_file_handle(file).finish()?
+    };
+    match date_format {
+        Some(date_format) => parse_date_columns(data_frame, date_format),
+        None => Ok(data_frame),
+    }
+}
+
+/// The character encodings supported for delimited files
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Encoding {
+    Utf8,
+    Utf8Lossy,
+    Latin1,
+}
+
+impl Encoding {
+    fn from_str(value: &str) -> Result<Self> {
+        match value.to_lowercase().replace('_', "-").as_str() {
+            "utf8" | "utf-8" => Ok(Encoding::Utf8),
+            "utf8-lossy" | "utf-8-lossy" => Ok(Encoding::Utf8Lossy),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(Encoding::Latin1),
+            _ => Err(ConversionError(format!("Invalid encoding: {value}"))),
+        }
+    }
+
+    /// Get the encoding used by the CSV reader; Latin-1 files are converted to UTF-8 before they
+    /// are read
+    fn csv_encoding(self) -> CsvEncoding {
+        match self {
+            Encoding::Utf8 | Encoding::Latin1 => CsvEncoding::Utf8,
+            Encoding::Utf8Lossy => CsvEncoding::LossyUtf8,
+        }
+    }
+}
+
+/// Read the Latin-1 (ISO-8859-1) encoded file and convert it to UTF-8; each byte is mapped to the
+/// Unicode code point with the same value
+fn latin1_to_utf8(mut file: File) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let data: String = bytes.into_iter().map(char::from).collect();
+    Ok(data.into_bytes())
+}
+
+/// The number and date conventions of a locale
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Locale {
+    decimal_comma: bool,
+    date_format: &'static str,
+}
+
+impl Locale {
+    /// Get the conventions for a locale such as `de`, `de-DE` or `en_US`; the language is used to
+    /// select the conventions, except for English where the region determines the date order
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.to_lowercase().replace('_', "-");
+        let (language, region) = value.split_once('-').unwrap_or((value.as_str(), ""));
+        let (decimal_comma, date_format) = match language {
+            "en" if region == "us" => (false, "%m/%d/%Y"),
+            "en" => (false, "%d/%m/%Y"),
+            "ja" | "zh" => (false, "%Y/%m/%d"),
+            "ko" => (false, "%Y.%m.%d"),
+            "de" | "cs" | "da" | "fi" | "nb" | "no" | "pl" | "ru" | "tr" | "uk" => {
+                (true, "%d.%m.%Y")
+            }
+            "es" | "fr" | "it" | "pt" => (true, "%d/%m/%Y"),
+            "nl" => (true, "%d-%m-%Y"),
+            "sv" => (true, "%Y-%m-%d"),
+            _ => return Err(ConversionError(format!("Invalid locale: {value}"))),
+        };
+        Ok(Locale {
+            decimal_comma,
+            date_format,
+        })
+    }
+}
+
+/// Convert the string columns where every value matches the strftime date format to date columns;
+/// other string columns are left unchanged
+fn parse_date_columns(mut data_frame: DataFrame, date_format: &str) -> Result<DataFrame> {
+    let columns = data_frame
+        .get_columns()
+        .iter()
+        .filter(|column| column.dtype() == &DataType::String)
+        .cloned()
+        .collect::<Vec<_>>();
+    for column in columns {
+        let values = column.str()?;
+        let dates = values.as_date(Some(date_format), false)?;
+        if values.len() == values.null_count() || dates.null_count() != values.null_count() {
+            continue;
+        }
+        let series = dates.into_series().with_name(column.name().clone());
+        data_frame.with_column(series)?;
+    }
+    Ok(data_frame)
+}
+
 fn string_to_ascii_char(value: &String) -> Result<u8> {
     let chars = value.chars().collect::<Vec<char>>();
     if chars.len() != 1 {
@@ -122,8 +327,12 @@ fn string_to_ascii_char(value: &String) -> Result<u8> {
 
 #[cfg(test)]
 mod test {
+    use super::*;
     use crate::test::dataset_url;
     use crate::{DriverManager, Value};
+    use chrono::NaiveDate;
+    use std::io::Write;
+    use tempfile::TempDir;
 
     fn database_url() -> String {
         let path = dataset_url("delimited", "users.pipe");
@@ -140,6 +349,28 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_driver_connect_duplicate_characters() {
+        let path = dataset_url("delimited", "users.pipe");
+        let driver_manager = DriverManager::default();
+
+        for options in ["separator=|&quote=|", "separator=|&eol=|", "quote=%0A"] {
+            let database_url = format!("{path}?{options}");
+            assert!(driver_manager.connect(&database_url).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_invalid_character() {
+        let path = dataset_url("delimited", "users.pipe");
+        let driver_manager = DriverManager::default();
+
+        for options in ["separator=", "separator=||", "quote=%C3%A9"] {
+            let database_url = format!("{path}?{options}");
+            assert!(driver_manager.connect(&database_url).await.is_err());
+        }
+    }
+
     #[tokio::test]
     async fn test_connection_interface() -> anyhow::Result<()> {
         let database_url = database_url();
@@ -164,4 +395,170 @@ mod test {
         connection.close().await?;
         Ok(())
     }
+
+    /// Write the data to a `users.csv` file in a temporary directory and return the url of the
+    /// file with the options
+    fn temp_file_url(directory: &TempDir, data: &[u8], options: &str) -> anyhow::Result<String> {
+        let path = directory.path().join("users.csv");
+        let mut file = File::create(&path)?;
+        file.write_all(data)?;
+        let path = path
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/");
+        #[cfg(target_os = "windows")]
+        let path = format!("/{path}");
+        Ok(format!("delimited://{path}?{options}"))
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_dialect_options() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let data = b"exported by foo\n# comment\n1;'Doe; John'\n2;Jane Smith\n";
+        let database_url = temp_file_url(
+            &directory,
+            data,
+            "delimiter=;&quote='&has_header=false&skip_rows=1&comment=%23",
+        )?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection
+            .query("SELECT * FROM users ORDER BY column_1")
+            .await?;
+
+        assert_eq!(query_result.columns().await, vec!["column_1", "column_2"]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(1), Value::String("Doe; John".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(2), Value::String("Jane Smith".to_string())])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_types() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let data = b"id,zip\n1,02134\n";
+        let database_url = temp_file_url(&directory, data, "types=zip:string")?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection.query("SELECT id, zip FROM users").await?;
+
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(1), Value::String("02134".to_string())])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_locale() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let data = b"id;amount;created\n1;1234,5;31.12.2024\n";
+        let database_url = temp_file_url(&directory, data, "delimiter=;&locale=de")?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection
+            .query("SELECT id, amount, created FROM users")
+            .await?;
+
+        let created = NaiveDate::from_ymd_opt(2024, 12, 31).expect("invalid date");
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![
+                Value::I64(1),
+                Value::F64(1234.5),
+                Value::Date(created)
+            ])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_decimal_comma_separator() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let data = b"id,amount\n1,2\n";
+        let database_url = temp_file_url(&directory, data, "decimal_comma=true")?;
+        let driver_manager = DriverManager::default();
+        assert!(driver_manager.connect(&database_url).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_locale_from_str() -> anyhow::Result<()> {
+        let locale = Locale::from_str("de_DE")?;
+        assert!(locale.decimal_comma);
+        assert_eq!(locale.date_format, "%d.%m.%Y");
+        let locale = Locale::from_str("en-US")?;
+        assert!(!locale.decimal_comma);
+        assert_eq!(locale.date_format, "%m/%d/%Y");
+        assert_eq!(Locale::from_str("en-GB")?.date_format, "%d/%m/%Y");
+        assert!(Locale::from_str("foo").is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_insert() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let data = b"id;name\n1;John Doe\n";
+        let database_url = temp_file_url(&directory, data, "delimiter=;")?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let rows = connection
+            .execute("INSERT INTO users (id, name) VALUES (2, 'Jane Smith')")
+            .await?;
+
+        assert_eq!(rows, 1);
+        connection.close().await?;
+        let data = std::fs::read_to_string(directory.path().join("users.csv"))?;
+        assert_eq!(data, "id;name\n1;John Doe\n2;Jane Smith\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_latin1() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let data = b"id,name\n1,Jos\xe9\n";
+        let database_url = temp_file_url(&directory, data, "encoding=latin1")?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection.query("SELECT name FROM users").await?;
+
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::String("José".to_string())])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_invalid_encoding() {
+        let path = dataset_url("delimited", "users.pipe");
+        let driver_manager = DriverManager::default();
+        let database_url = format!("{path}?separator=|&encoding=foo");
+        assert!(driver_manager.connect(&database_url).await.is_err());
+    }
+
+    #[test]
+    fn test_encoding_from_str() -> anyhow::Result<()> {
+        assert_eq!(Encoding::from_str("UTF-8")?, Encoding::Utf8);
+        assert_eq!(Encoding::from_str("utf8_lossy")?, Encoding::Utf8Lossy);
+        assert_eq!(Encoding::from_str("latin1")?, Encoding::Latin1);
+        assert_eq!(Encoding::from_str("ISO-8859-1")?, Encoding::Latin1);
+        assert!(Encoding::from_str("foo").is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,169 @@
+use crate::error::Result;
+use crate::Error::ConversionError;
+use crate::{QueryResult, Value};
+use futures_util::stream::{self, BoxStream};
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+
+/// Deserialize a row into a type implementing [`DeserializeOwned`]; values are mapped to fields
+/// by column name.
+pub trait DeserializeRow {
+    /// Deserialize the row using the column names of the query result
+    ///
+    /// # Errors
+    /// * If the number of values does not match the number of columns
+    /// * If a value cannot be converted to the type of the corresponding field
+    fn deserialize<T: DeserializeOwned>(&self, columns: &[String]) -> Result<T>;
+}
+
+impl DeserializeRow for [Value] {
+    fn deserialize<T: DeserializeOwned>(&self, columns: &[String]) -> Result<T> {
+        if columns.len() != self.len() {
+            return Err(ConversionError(format!(
+                "expected {} values, found {}",
+                columns.len(),
+                self.len()
+            )));
+        }
+
+        let mut object = serde_json::Map::with_capacity(columns.len());
+        for (column, value) in columns.iter().zip(self) {
+            object.insert(column.clone(), to_json(value)?);
+        }
+        serde_json::from_value(serde_json::Value::Object(object))
+            .map_err(|error| ConversionError(error.to_string()))
+    }
+}
+
+/// Create a stream that deserializes each row of the query result; see [`DeserializeRow`].
+#[must_use]
+pub fn deserialize_stream<T>(query_result: Box<dyn QueryResult>) -> BoxStream<'static, Result<T>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    stream::unfold(
+        (query_result, None),
+        |(mut query_result, columns): (Box<dyn QueryResult>, Option<Vec<String>>)| async move {
+            let columns = match columns {
+                Some(columns) => columns,
+                None => query_result.columns().await,
+            };
+            let row = query_result.next().await?;
+            let value = row.deserialize(&columns);
+            Some((value, (query_result, Some(columns))))
+        },
+    )
+    .boxed()
+}
+
+/// Convert a value to JSON; 128-bit integers are converted to numbers when they fit in 64 bits,
+/// since they are otherwise serialized as strings.
+fn to_json(value: &Value) -> Result<serde_json::Value> {
+    let value = match value {
+        Value::I128(number) => i64::try_from(*number).map_or_else(
+            |_| serde_json::Value::String(number.to_string()),
+            serde_json::Value::from,
+        ),
+        Value::U128(number) => u64::try_from(*number).map_or_else(
+            |_| serde_json::Value::String(number.to_string()),
+            serde_json::Value::from,
+        ),
+        value => serde_json::to_value(value).map_err(|error| ConversionError(error.to_string()))?,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::MemoryQueryResult;
+    use chrono::NaiveDate;
+    use futures_util::TryStreamExt;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct User {
+        id: i64,
+        name: String,
+        email: Option<String>,
+        birth_date: NaiveDate,
+    }
+
+    fn columns() -> Vec<String> {
+        vec![
+            "id".to_string(),
+            "name".to_string(),
+            "email".to_string(),
+            "birth_date".to_string(),
+        ]
+    }
+
+    fn user_row(id: i32, name: &str) -> Vec<Value> {
+        vec![
+            Value::I32(id),
+            Value::String(name.to_string()),
+            Value::Null,
+            Value::Date(NaiveDate::from_ymd_opt(2000, 1, 2).expect("invalid date")),
+        ]
+    }
+
+    #[test]
+    fn test_deserialize() -> Result<()> {
+        let user: User = user_row(1, "John Doe").deserialize(&columns())?;
+        assert_eq!(
+            user,
+            User {
+                id: 1,
+                name: "John Doe".to_string(),
+                email: None,
+                birth_date: NaiveDate::from_ymd_opt(2000, 1, 2).expect("invalid date"),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_i128() -> Result<()> {
+        #[derive(Debug, Deserialize)]
+        struct Numbers {
+            small: i64,
+            large: String,
+        }
+
+        let columns = vec!["small".to_string(), "large".to_string()];
+        let numbers: Numbers = [Value::I128(42), Value::U128(u128::MAX)].deserialize(&columns)?;
+        assert_eq!(numbers.small, 42);
+        assert_eq!(numbers.large, u128::MAX.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_deserialize_column_mismatch() {
+        let result = [Value::I32(1)].deserialize::<User>(&columns());
+        assert!(matches!(result, Err(ConversionError(_))));
+    }
+
+    #[test]
+    fn test_deserialize_invalid_type() {
+        let mut row = user_row(1, "John Doe");
+        row[0] = Value::String("foo".to_string());
+        let result = row.deserialize::<User>(&columns());
+        assert!(matches!(result, Err(ConversionError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_stream() -> Result<()> {
+        let query_result = MemoryQueryResult::new(
+            columns(),
+            vec![user_row(1, "John Doe"), user_row(2, "Jane Smith")],
+        );
+
+        let users: Vec<User> = deserialize_stream(Box::new(query_result))
+            .try_collect()
+            .await?;
+
+        let names: Vec<&str> = users.iter().map(|user| user.name.as_str()).collect();
+        assert_eq!(names, vec!["John Doe", "Jane Smith"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+use crate::error::Result;
+use crate::polars::Connection;
+use crate::Error::{InvalidUrl, IoError};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use file_type::FileType;
+use polars::io::SerReader;
+use polars::prelude::{
+    concat, lit, CsvParseOptions, CsvReadOptions, DataFrame, IntoLazy, IpcReader, JsonLineReader,
+    JsonReader, ParquetReader, UnionArgs,
+};
+use polars_sql::SQLContext;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Queries the files of a directory as a single table, e.g. `dir://data/events/**/*.parquet`.
+/// Subdirectories named `<column>=<value>` (hive style partitions) are added as columns.
+#[derive(Debug)]
+pub struct Driver;
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        "dir"
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        _password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let parsed_url = Url::parse(url.as_str())?;
+        let query_parameters: HashMap<String, String> =
+            parsed_url.query_pairs().into_owned().collect();
+
+        let (directory, pattern) = parse_path(&url)?;
+        let mut lazy_frames = Vec::new();
+        for relative_path in find_files(&directory, &pattern)? {
+            let path = relative_path
+                .iter()
+                .fold(directory.clone(), |path, component| path.join(component));
+            let Some(data_frame) = read_file(&path)? else {
+                continue;
+            };
+
+            let mut lazy_frame = data_frame.lazy();
+            let directories = relative_path
+                .split_last()
+                .map(|(_, directories)| directories)
+                .unwrap_or_default();
+            for (column, value) in directories.iter().filter_map(|name| name.split_once('=')) {
+                lazy_frame = lazy_frame.with_column(lit(value).alias(column));
+            }
+            lazy_frames.push(lazy_frame);
+        }
+        if lazy_frames.is_empty() {
+            return Err(IoError(anyhow!("No files found: {url}")));
+        }
+
+        let table_name = match query_parameters.get("table") {
+            Some(table_name) => table_name.to_string(),
+            None => crate::polars::driver::get_table_name(directory.to_string_lossy())?,
+        };
+        let union_args = UnionArgs {
+            to_supertypes: true,
+            ..UnionArgs::default()
+        };
+        let lazy_frame = concat(lazy_frames, union_args)?;
+        let mut context = SQLContext::new();
+        context.register(table_name.as_str(), lazy_frame);
+
+        let connection = Connection::new(url, context).await?;
+        Ok(Box::new(connection))
+    }
+
+    fn supports_file_type(&self, _file_type: &FileType) -> bool {
+        false
+    }
+}
+
+/// Split the path of the url into the directory and the glob pattern of the files; when the path
+/// does not contain a wildcard, all files in the directory and its subdirectories are matched
+fn parse_path(url: &str) -> Result<(PathBuf, Vec<String>)> {
+    let path = url
+        .split_once("://")
+        .map(|(_, path)| path.split('?').next().unwrap_or(path))
+        .ok_or_else(|| InvalidUrl(format!("Invalid directory url: {url}")))?;
+    #[cfg(target_os = "windows")]
+    let path = if path.contains(':') {
+        // Strip preceding '/' character for Windows absolute path (e.g. /C:/foo)
+        path.strip_prefix('/').unwrap_or(path)
+    } else {
+        path
+    };
+
+    let components: Vec<&str> = path.split('/').collect();
+    let index = components
+        .iter()
+        .position(|component| component.contains('*'))
+        .unwrap_or(components.len());
+    let (directory, pattern) = components.split_at(index);
+    let directory = directory.join("/");
+    let directory = if directory.is_empty() {
+        PathBuf::from(".")
+    } else {
+        PathBuf::from(directory)
+    };
+    let pattern = if pattern.is_empty() {
+        vec!["**".to_string(), "*".to_string()]
+    } else {
+        pattern.iter().map(ToString::to_string).collect()
+    };
+
+    if !directory.is_dir() {
+        let directory = directory.to_string_lossy();
+        return Err(IoError(anyhow!("Directory not found: {directory}")));
+    }
+    Ok((directory, pattern))
+}
+
+/// Find the files in the directory that match the pattern; the paths of the files are returned as
+/// the components relative to the directory, sorted so that the order of the rows is consistent
+fn find_files(directory: &Path, pattern: &[String]) -> Result<Vec<Vec<String>>> {
+    let mut files = Vec::new();
+    let mut directories = vec![(directory.to_path_buf(), Vec::new())];
+    while let Some((path, components)) = directories.pop() {
+        for entry in std::fs::read_dir(&path)? {
+            let entry = entry?;
+            let mut components = components.clone();
+            components.push(entry.file_name().to_string_lossy().to_string());
+            if entry.file_type()?.is_dir() {
+                directories.push((entry.path(), components));
+            } else if matches(pattern, &components) {
+                files.push(components);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Check if the path components match the glob pattern components; `*` matches any characters in
+/// a file or directory name and `**` matches any number of directories
+fn matches(pattern: &[String], path: &[String]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((component, remaining)) if component == "**" => {
+            matches(remaining, path) || (!path.is_empty() && matches(pattern, &path[1..]))
+        }
+        Some((component, remaining)) => path
+            .split_first()
+            .is_some_and(|(name, path)| matches_name(component, name) && matches(remaining, path)),
+    }
+}
+
+/// Check if the name matches the pattern, where `*` matches any sequence of characters
+fn matches_name(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, remaining)) => {
+            let Some(name) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=name.len())
+                .filter(|index| name.is_char_boundary(*index))
+                .any(|index| matches_name(remaining, &name[index..]))
+        }
+    }
+}
+
+/// Read the file with the reader for the file extension; `None` is returned for files that are
+/// not supported, e.g. `_SUCCESS` marker files
+fn read_file(path: &Path) -> Result<Option<DataFrame>> {
+    let extension = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    let data_frame = match extension.as_str() {
+        "arrow" | "feather" | "ipc" => IpcReader::new(File::open(path)?).finish()?,
+        "csv" | "tsv" => {
+            let separator = if extension == "tsv" { b'\t' } else { b',' };
+            CsvReadOptions::default()
+                .with_parse_options(CsvParseOptions::default().with_separator(separator))
+                .try_into_reader_with_file_path(Some(path.to_path_buf()))?
+                .finish()?
+        }
+        "json" => JsonReader::new(File::open(path)?).finish()?,
+        "jsonl" | "ndjson" => JsonLineReader::new(File::open(path)?).finish()?,
+        "parquet" => ParquetReader::new(File::open(path)?).finish()?,
+        _ => return Ok(None),
+    };
+    Ok(Some(data_frame))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DriverManager, Value};
+    use tempfile::TempDir;
+
+    fn pattern(pattern: &str) -> Vec<String> {
+        pattern.split('/').map(ToString::to_string).collect()
+    }
+
+    fn database_url(directory: &TempDir, pattern: &str) -> anyhow::Result<String> {
+        let events = directory.path().join("events");
+        for (date, id) in [("2024-01-01", 1), ("2024-01-02", 2)] {
+            let partition = events.join(format!("date={date}"));
+            std::fs::create_dir_all(&partition)?;
+            std::fs::write(partition.join("part.csv"), format!("id\n{id}\n"))?;
+        }
+        std::fs::write(events.join("_SUCCESS"), "")?;
+
+        let path = events
+            .to_string_lossy()
+            .replace(std::path::MAIN_SEPARATOR, "/");
+        #[cfg(target_os = "windows")]
+        let path = format!("/{path}");
+        Ok(format!("dir://{path}{pattern}"))
+    }
+
+    #[tokio::test]
+    async fn test_connection_interface() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let database_url = database_url(&directory, "/**/*.csv")?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection
+            .query("SELECT id, date FROM events ORDER BY id")
+            .await?;
+
+        assert_eq!(query_result.columns().await, vec!["id", "date"]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(1), Value::String("2024-01-01".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(2), Value::String("2024-01-02".to_string())])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_directory_table_name() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let database_url = database_url(&directory, "?table=logs")?;
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection.query("SELECT COUNT(*) FROM logs").await?;
+
+        assert_eq!(query_result.next().await, Some(vec![Value::U32(2)]));
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_no_files() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let database_url = database_url(&directory, "/*.parquet")?;
+        let driver_manager = DriverManager::default();
+        assert!(driver_manager.connect(&database_url).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches() {
+        let path = pattern("date=2024-01-01/part.csv");
+        assert!(matches(&pattern("**/*.csv"), &path));
+        assert!(matches(&pattern("*/*.csv"), &path));
+        assert!(matches(&pattern("date=*/part.*"), &path));
+        assert!(!matches(&pattern("*.csv"), &path));
+        assert!(!matches(&pattern("**/*.parquet"), &path));
+        assert!(matches(&pattern("**/*.csv"), &pattern("part.csv")));
+    }
+
+    #[test]
+    fn test_matches_name() {
+        assert!(matches_name("*", "users.csv"));
+        assert!(matches_name("users.*", "users.csv"));
+        assert!(matches_name("*.csv", "users.csv"));
+        assert!(matches_name("u*s.c*", "users.csv"));
+        assert!(!matches_name("*.csv", "users.parquet"));
+        assert!(!matches_name("users", "users.csv"));
+    }
+}
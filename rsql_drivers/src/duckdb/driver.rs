@@ -1,9 +1,10 @@
+use crate::connection::limit_statement;
 use crate::duckdb::metadata;
 use crate::error::{Error, Result};
 use crate::url::UrlExtension;
 use crate::value::Value;
-use crate::Error::UnsupportedColumnType;
-use crate::{MemoryQueryResult, Metadata, QueryResult, StatementMetadata};
+use crate::Error::{InvalidUrl, UnsupportedColumnType};
+use crate::{Interval, MemoryQueryResult, Metadata, QueryResult, StatementMetadata};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
@@ -17,6 +18,21 @@ use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use url::Url;
 
+/// Query parameter with a comma separated list of the extensions to install and load
+const EXTENSIONS: &str = "extensions";
+/// Prefix of the query parameters that configure access to S3, e.g. `s3_region=us-east-1`
+const S3_PREFIX: &str = "s3_";
+/// The S3 settings that may be set from the query parameters
+const S3_SETTINGS: [&str; 7] = [
+    "s3_access_key_id",
+    "s3_endpoint",
+    "s3_region",
+    "s3_secret_access_key",
+    "s3_session_token",
+    "s3_url_style",
+    "s3_use_ssl",
+];
+
 #[derive(Debug)]
 pub struct Driver;
 
@@ -58,6 +74,11 @@ impl Connection {
             duckdb::Connection::open_in_memory()?
         };
 
+        let sql = connection_sql(&parsed_url)?;
+        if !sql.is_empty() {
+            connection.execute_batch(&sql)?;
+        }
+
         Ok(Connection {
             url,
             connection: Arc::new(Mutex::new(connection)),
@@ -65,6 +86,34 @@ impl Connection {
     }
 }
 
+/// Build the statements that install and load the extensions and configure S3 from the query
+/// parameters of the url; extension names are restricted to alphanumeric characters and `_`
+/// since they cannot be bound as parameters of the `INSTALL` and `LOAD` statements.
+fn connection_sql(url: &Url) -> Result<String> {
+    let mut sql = String::new();
+    for (key, value) in url.query_pairs() {
+        if key == EXTENSIONS {
+            for extension in value.split(',').map(str::trim) {
+                let valid = !extension.is_empty()
+                    && extension
+                        .chars()
+                        .all(|character| character.is_ascii_alphanumeric() || character == '_');
+                if !valid {
+                    return Err(InvalidUrl(format!("Invalid extension: {extension}")));
+                }
+                sql.push_str(&format!("INSTALL {extension}; LOAD {extension};\n"));
+            }
+        } else if key.starts_with(S3_PREFIX) {
+            if !S3_SETTINGS.contains(&key.as_ref()) {
+                return Err(InvalidUrl(format!("Invalid S3 setting: {key}")));
+            }
+            let value = value.replace('\'', "''");
+            sql.push_str(&format!("SET {key} = '{value}';\n"));
+        }
+    }
+    Ok(sql)
+}
+
 #[async_trait]
 impl crate::Connection for Connection {
     fn url(&self) -> &String {
@@ -116,6 +165,10 @@ impl crate::Connection for Connection {
         Box::new(DuckDbDialect {})
     }
 
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
+
     fn match_statement(&self, statement: &Statement) -> StatementMetadata {
         let default = self.default_match_statement(statement);
         match default {
@@ -133,6 +186,8 @@ impl crate::Connection for Connection {
     }
 }
 
+impl crate::Capabilities for Connection {}
+
 impl Connection {
     fn convert_to_value(row: &Row, column_name: &String, column_index: usize) -> Result<Value> {
         let value_ref = row.get_ref(column_index)?;
@@ -150,7 +205,7 @@ impl Connection {
             ValueRef::UBigInt(value) => Value::U64(value),
             ValueRef::Float(value) => Value::F32(value),
             ValueRef::Double(value) => Value::F64(value),
-            ValueRef::Decimal(value) => Value::String(value.to_string()),
+            ValueRef::Decimal(value) => Value::Decimal(value),
             ValueRef::Text(value) => {
                 let value = String::from_utf8(value.to_vec())?;
                 Value::String(value)
@@ -186,6 +241,11 @@ impl Connection {
                 let date_time = start_date_time.add(duration);
                 Value::DateTime(date_time)
             }
+            ValueRef::Interval {
+                months,
+                days,
+                nanos,
+            } => Value::Interval(Interval::new(months, days, nanos)),
             _ => {
                 let data_type = value_ref.data_type();
                 return Err(UnsupportedColumnType {
@@ -201,10 +261,12 @@ impl Connection {
 
 #[cfg(test)]
 mod test {
+    use super::connection_sql;
     use crate::test::dataset_url;
-    use crate::{DriverManager, StatementMetadata, Value};
+    use crate::{DriverManager, Interval, StatementMetadata, Value};
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
     use indoc::indoc;
+    use url::Url;
 
     const DATABASE_URL: &str = "duckdb://";
 
@@ -217,6 +279,42 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_connection_sql() -> anyhow::Result<()> {
+        let url = Url::parse(
+            "duckdb://?extensions=httpfs,%20parquet&s3_region=us-east-1&s3_secret_access_key=a'b",
+        )?;
+        let sql = connection_sql(&url)?;
+        assert_eq!(
+            sql,
+            indoc! {r"
+                INSTALL httpfs; LOAD httpfs;
+                INSTALL parquet; LOAD parquet;
+                SET s3_region = 'us-east-1';
+                SET s3_secret_access_key = 'a''b';
+            "}
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_sql_invalid() -> anyhow::Result<()> {
+        let url = Url::parse("duckdb://?extensions=httpfs;%20DROP%20TABLE%20users")?;
+        assert!(connection_sql(&url).is_err());
+        let url = Url::parse("duckdb://?extensions=httpfs,")?;
+        assert!(connection_sql(&url).is_err());
+        let url = Url::parse("duckdb://?s3_unknown=value")?;
+        assert!(connection_sql(&url).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_invalid_extension() {
+        let driver_manager = DriverManager::default();
+        let result = driver_manager.connect("duckdb://?extensions=a-b").await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_connection_interface() -> anyhow::Result<()> {
         let database_url = dataset_url("duckdb", "users.duckdb");
@@ -327,7 +425,7 @@ mod test {
             assert_eq!(row.get(13).cloned(), Some(Value::F64(123.0)));
             assert_eq!(
                 row.get(14).cloned(),
-                Some(Value::String("123.00".to_string()))
+                Some(Value::Decimal(rust_decimal::Decimal::new(12_300, 2)))
             );
             let date = NaiveDate::from_ymd_opt(2022, 1, 1).expect("invalid date");
             assert_eq!(row.get(15).cloned(), Some(Value::Date(date)));
@@ -341,6 +439,24 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_interval() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let mut query_result = connection
+            .query("SELECT INTERVAL '1 year 2 days 3 hours'")
+            .await?;
+        let row = query_result.next().await.expect("no row");
+        assert_eq!(
+            row.first().cloned(),
+            Some(Value::Interval(Interval::new(12, 2, 10_800_000_000_000)))
+        );
+
+        connection.close().await?;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_dialect() -> anyhow::Result<()> {
         let driver_manager = DriverManager::default();
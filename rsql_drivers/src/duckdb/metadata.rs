@@ -12,17 +12,36 @@ pub(crate) async fn get_metadata(connection: &mut dyn Connection) -> Result<Meta
 
 async fn retrieve_schemas(connection: &mut dyn Connection, metadata: &mut Metadata) -> Result<()> {
     let mut schemas = vec![];
-    let sql = indoc! { "SELECT name FROM pragma_database_list" };
+    let sql = indoc! { r"
+            SELECT
+                catalog_name,
+                schema_name,
+                catalog_name = current_database() AS current_catalog,
+                schema_name = current_schema() AS current_schema
+            FROM
+                information_schema.schemata
+            WHERE
+                catalog_name NOT IN ('system', 'temp')
+                AND schema_name NOT IN ('information_schema', 'pg_catalog')
+            ORDER BY
+                catalog_name,
+                schema_name
+        "};
     let mut query_result = connection.query(sql).await?;
-    let mut current = true;
 
     while let Some(row) = query_result.next().await {
-        let database_name = match row.first() {
+        let catalog_name = match row.first() {
+            Some(value) => value.to_string(),
+            None => continue,
+        };
+        let schema_name = match row.get(1) {
             Some(value) => value.to_string(),
             None => continue,
         };
-        let schema = Schema::new(database_name, current);
-        current = false;
+        let current_catalog = matches!(row.get(2), Some(Value::Bool(true)));
+        let current_schema = matches!(row.get(3), Some(Value::Bool(true)));
+        let name = schema_name_for(&catalog_name, &schema_name, current_catalog);
+        let schema = Schema::new(name, current_catalog && current_schema);
         schemas.push(schema);
     }
 
@@ -39,6 +58,16 @@ async fn retrieve_schemas(connection: &mut dyn Connection, metadata: &mut Metada
     Ok(())
 }
 
+/// Schemas in the current catalog are named by the schema name; schemas in attached catalogs are
+/// qualified with the catalog name, e.g. `other.main`
+fn schema_name_for(catalog_name: &str, schema_name: &str, current_catalog: bool) -> String {
+    if current_catalog {
+        schema_name.to_string()
+    } else {
+        format!("{catalog_name}.{schema_name}")
+    }
+}
+
 async fn retrieve_tables(connection: &mut dyn Connection, schema: &mut Schema) -> Result<()> {
     let sql = indoc! { r"
             SELECT
@@ -49,6 +78,9 @@ async fn retrieve_tables(connection: &mut dyn Connection, schema: &mut Schema) -
                 column_default
             FROM
                 information_schema.columns
+            WHERE
+                table_catalog = current_database()
+                AND table_schema = current_schema()
             ORDER BY
                 table_name,
                 ordinal_position
@@ -105,6 +137,9 @@ async fn retrieve_indexes(connection: &mut dyn Connection, schema: &mut Schema)
                 is_unique
             FROM
                 duckdb_indexes()
+            WHERE
+                database_name = current_database()
+                AND schema_name = current_schema()
             ORDER BY
                 table_name,
                 index_name
@@ -153,6 +188,7 @@ async fn retrieve_indexes(connection: &mut dyn Connection, schema: &mut Schema)
 
 #[cfg(test)]
 mod test {
+    use super::schema_name_for;
     use crate::DriverManager;
 
     const DATABASE_URL: &str = "duckdb://";
@@ -224,4 +260,44 @@ mod test {
         connection.close().await?;
         Ok(())
     }
+
+    #[test]
+    fn test_schema_name_for() {
+        assert_eq!(schema_name_for("memory", "main", true), "main");
+        assert_eq!(schema_name_for("other", "main", false), "other.main");
+    }
+
+    #[tokio::test]
+    async fn test_schemas() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let _ = connection
+            .execute("CREATE TABLE users (id INTEGER)")
+            .await?;
+        let _ = connection.execute("CREATE SCHEMA sales").await?;
+        let _ = connection
+            .execute("CREATE TABLE sales.orders (id INTEGER)")
+            .await?;
+        let _ = connection.execute("ATTACH ':memory:' AS other").await?;
+        let _ = connection
+            .execute("CREATE TABLE other.main.products (id INTEGER)")
+            .await?;
+
+        let metadata = connection.metadata().await?;
+        let schema_names: Vec<&str> = metadata
+            .schemas()
+            .iter()
+            .map(|schema| schema.name())
+            .collect();
+        assert_eq!(schema_names, vec!["main", "other.main", "sales"]);
+
+        let schema = metadata.current_schema().expect("schema");
+        assert_eq!(schema.name(), "main");
+        assert_eq!(schema.tables().len(), 1);
+        assert!(schema.get("users").is_some());
+
+        connection.close().await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,425 @@
+use crate::dynamodb::metadata;
+use crate::error::Result;
+use crate::Error::{ConversionError, InvalidUrl, IoError};
+use crate::{MemoryQueryResult, Metadata, QueryResult, Row, Value};
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_dynamodb::config::Credentials;
+use aws_sdk_dynamodb::types::AttributeValue;
+use aws_sdk_dynamodb::Client;
+use file_type::FileType;
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
+use sqlparser::dialect::{Dialect, GenericDialect};
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use url::Url;
+
+/// An item returned by a `PartiQL` statement
+type Item = HashMap<String, AttributeValue>;
+
+#[derive(Debug)]
+pub struct Driver;
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        "dynamodb"
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let connection = Connection::new(url, password).await?;
+        Ok(Box::new(connection))
+    }
+
+    fn supports_file_type(&self, _file_type: &FileType) -> bool {
+        false
+    }
+}
+
+/// Options parsed from a `DynamoDB` URL
+#[derive(Clone, Debug, PartialEq)]
+struct Options {
+    region: String,
+    access_key_id: Option<String>,
+    table: Option<String>,
+    endpoint: Option<String>,
+}
+
+impl Options {
+    /// Parse the options from a
+    /// `dynamodb://[<access_key_id>:<secret_access_key>@]<region>[/<table>][?endpoint=<url>]` URL.
+    fn parse(url: &str) -> Result<Self> {
+        let parsed_url = Url::parse(url)?;
+        let query_parameters: HashMap<String, String> =
+            parsed_url.query_pairs().into_owned().collect();
+        let region = parsed_url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| InvalidUrl("DynamoDB URL requires a region".to_string()))?
+            .to_string();
+        let access_key_id = Some(parsed_url.username().to_string()).filter(|user| !user.is_empty());
+        let table = Some(parsed_url.path().trim_start_matches('/').to_string())
+            .filter(|table| !table.is_empty());
+
+        Ok(Self {
+            region,
+            access_key_id,
+            table,
+            endpoint: query_parameters.get("endpoint").cloned(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Connection {
+    url: String,
+    options: Options,
+    client: Client,
+}
+
+impl Connection {
+    /// Create a new `DynamoDB` connection; credentials are read from the URL when specified,
+    /// otherwise the default AWS credential chain (environment, shared config files, instance
+    /// metadata) is used.
+    pub(crate) async fn new(url: String, password: Option<String>) -> Result<Connection> {
+        let options = Options::parse(url.as_str())?;
+        let mut loader = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(options.region.clone()));
+        if let (Some(access_key_id), Some(secret_access_key)) = (&options.access_key_id, password) {
+            let credentials =
+                Credentials::new(access_key_id, secret_access_key, None, None, "rsql");
+            loader = loader.credentials_provider(credentials);
+        }
+        if let Some(endpoint) = &options.endpoint {
+            loader = loader.endpoint_url(endpoint);
+        }
+        let config = loader.load().await;
+
+        Ok(Connection {
+            url,
+            options,
+            client: Client::new(&config),
+        })
+    }
+
+    /// Execute the `PartiQL` statement and read the pages of items; when `limit` is not 0, at most
+    /// `limit` items are requested and no more pages are read once the limit is reached
+    async fn execute_statement(&self, sql: &str, limit: usize) -> Result<Vec<Item>> {
+        let mut items = Vec::new();
+        let mut next_token = None;
+        loop {
+            let page_limit = match limit {
+                0 => None,
+                limit => Some(i32::try_from(limit - items.len()).unwrap_or(i32::MAX)),
+            };
+            let response = self
+                .client
+                .execute_statement()
+                .statement(sql)
+                .set_limit(page_limit)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(|error| IoError(error.into()))?;
+            items.extend(response.items().iter().cloned());
+            if limit > 0 && items.len() >= limit {
+                items.truncate(limit);
+                break;
+            }
+
+            next_token = response.next_token().map(ToString::to_string);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+#[async_trait]
+impl crate::Connection for Connection {
+    fn url(&self) -> &String {
+        &self.url
+    }
+
+    /// Execute the statement; PartiQL `INSERT`, `UPDATE` and `DELETE` statements modify a single
+    /// item, and the number of items returned is reported for other statements
+    async fn execute(&mut self, sql: &str) -> Result<u64> {
+        let items = self.execute_statement(sql, 0).await?;
+        let keyword = sql
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_uppercase();
+        let rows = match keyword.as_str() {
+            "INSERT" | "UPDATE" | "DELETE" => 1,
+            _ => u64::try_from(items.len())?,
+        };
+        Ok(rows)
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        self.query_with_limit(sql, 0).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn metadata(&mut self) -> Result<Metadata> {
+        metadata::get_metadata(
+            &self.client,
+            &self.options.region,
+            self.options.table.as_deref(),
+        )
+        .await
+    }
+
+    /// PartiQL has no `LIMIT` clause, so the limit is applied with the `Limit` parameter of
+    /// `ExecuteStatement` instead
+    async fn query_with_limit(&mut self, sql: &str, limit: usize) -> Result<Box<dyn QueryResult>> {
+        let items = self.execute_statement(sql, limit).await?;
+        let (columns, rows) = convert_items(&items)?;
+        Ok(Box::new(MemoryQueryResult::new(columns, rows)))
+    }
+
+    fn dialect(&self) -> Box<dyn Dialect> {
+        Box::new(GenericDialect)
+    }
+}
+
+impl crate::Capabilities for Connection {}
+
+/// Convert the items to rows; items do not share a schema, so the columns are the names of all
+/// the attributes of the items in alphabetical order, and missing attributes are null values
+fn convert_items(items: &[Item]) -> Result<(Vec<String>, Vec<Row>)> {
+    let columns: Vec<String> = items
+        .iter()
+        .flat_map(HashMap::keys)
+        .cloned()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let rows = items
+        .iter()
+        .map(|item| {
+            columns
+                .iter()
+                .map(|column| match item.get(column) {
+                    Some(value) => convert_to_value(value),
+                    None => Ok(Value::Null),
+                })
+                .collect::<Result<Row>>()
+        })
+        .collect::<Result<Vec<Row>>>()?;
+    Ok((columns, rows))
+}
+
+/// Convert a `DynamoDB` attribute value to a value; lists and sets are arrays and maps are maps
+/// with the keys in alphabetical order
+fn convert_to_value(value: &AttributeValue) -> Result<Value> {
+    let value = match value {
+        AttributeValue::Null(_) => Value::Null,
+        AttributeValue::Bool(value) => Value::Bool(*value),
+        AttributeValue::S(value) => Value::String(value.clone()),
+        AttributeValue::N(value) => convert_number(value)?,
+        AttributeValue::B(value) => Value::Bytes(value.as_ref().to_vec()),
+        AttributeValue::Ss(values) => {
+            Value::Array(values.iter().cloned().map(Value::String).collect())
+        }
+        AttributeValue::Ns(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| convert_number(value))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        AttributeValue::Bs(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| Value::Bytes(value.as_ref().to_vec()))
+                .collect(),
+        ),
+        AttributeValue::L(values) => Value::Array(
+            values
+                .iter()
+                .map(convert_to_value)
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        AttributeValue::M(values) => {
+            let mut keys: Vec<&String> = values.keys().collect();
+            keys.sort();
+            let mut map = IndexMap::new();
+            for key in keys {
+                if let Some(value) = values.get(key) {
+                    map.insert(Value::String(key.clone()), convert_to_value(value)?);
+                }
+            }
+            Value::Map(map)
+        }
+        _ => {
+            return Err(IoError(anyhow::anyhow!(
+                "Unsupported DynamoDB attribute value: {value:?}"
+            )))
+        }
+    };
+    Ok(value)
+}
+
+/// Convert a `DynamoDB` number; integers are 64-bit integers when they fit, and other numbers are
+/// decimals, or floats when they exceed the precision of a decimal
+fn convert_number(value: &str) -> Result<Value> {
+    if let Ok(number) = value.parse::<i64>() {
+        return Ok(Value::I64(number));
+    }
+    if let Ok(number) = Decimal::from_str(value).or_else(|_| Decimal::from_scientific(value)) {
+        return Ok(Value::Decimal(number));
+    }
+    value
+        .parse::<f64>()
+        .map(Value::F64)
+        .map_err(|error| ConversionError(format!("invalid number [{value}]: {error}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_sdk_dynamodb::primitives::Blob;
+
+    #[test]
+    fn test_options() -> Result<()> {
+        let options =
+            Options::parse("dynamodb://AKID@us-east-1/users?endpoint=http://localhost:8000")?;
+        assert_eq!(
+            options,
+            Options {
+                region: "us-east-1".to_string(),
+                access_key_id: Some("AKID".to_string()),
+                table: Some("users".to_string()),
+                endpoint: Some("http://localhost:8000".to_string()),
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_defaults() -> Result<()> {
+        let options = Options::parse("dynamodb://eu-west-1")?;
+        assert_eq!(options.region, "eu-west-1");
+        assert_eq!(options.access_key_id, None);
+        assert_eq!(options.table, None);
+        assert_eq!(options.endpoint, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_errors() {
+        assert!(Options::parse("dynamodb://").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_limit_sql() -> Result<()> {
+        let connection = Connection::new("dynamodb://us-east-1".to_string(), None).await?;
+        let limited_sql = crate::Connection::limit_sql(&connection, "SELECT * FROM users", 10);
+        assert_eq!(limited_sql, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_value() -> Result<()> {
+        assert_eq!(convert_to_value(&AttributeValue::Null(true))?, Value::Null);
+        assert_eq!(
+            convert_to_value(&AttributeValue::Bool(true))?,
+            Value::Bool(true)
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::S("foo".to_string()))?,
+            Value::String("foo".to_string())
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::N("42".to_string()))?,
+            Value::I64(42)
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::N("12.34".to_string()))?,
+            Value::Decimal(Decimal::new(1234, 2))
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::N("1E+100".to_string()))?,
+            Value::F64(1e100)
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::B(Blob::new(b"foo".to_vec())))?,
+            Value::Bytes(b"foo".to_vec())
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::Ss(vec!["a".to_string(), "b".to_string()]))?,
+            Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string())
+            ])
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::Ns(vec!["1".to_string()]))?,
+            Value::Array(vec![Value::I64(1)])
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::Bs(vec![Blob::new(b"a".to_vec())]))?,
+            Value::Array(vec![Value::Bytes(b"a".to_vec())])
+        );
+        assert_eq!(
+            convert_to_value(&AttributeValue::L(vec![
+                AttributeValue::S("a".to_string()),
+                AttributeValue::N("1".to_string()),
+            ]))?,
+            Value::Array(vec![Value::String("a".to_string()), Value::I64(1)])
+        );
+
+        let map = HashMap::from([
+            ("name".to_string(), AttributeValue::S("foo".to_string())),
+            ("age".to_string(), AttributeValue::N("42".to_string())),
+        ]);
+        let mut expected = IndexMap::new();
+        expected.insert(Value::String("age".to_string()), Value::I64(42));
+        expected.insert(Value::String("name".to_string()), Value::String("foo".to_string()));
+        assert_eq!(
+            convert_to_value(&AttributeValue::M(map))?,
+            Value::Map(expected)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_items() -> Result<()> {
+        let items = vec![
+            HashMap::from([
+                ("id".to_string(), AttributeValue::N("1".to_string())),
+                ("name".to_string(), AttributeValue::S("foo".to_string())),
+            ]),
+            HashMap::from([
+                ("id".to_string(), AttributeValue::N("2".to_string())),
+                ("email".to_string(), AttributeValue::S("bar@example.com".to_string())),
+            ]),
+        ];
+
+        let (columns, rows) = convert_items(&items)?;
+
+        assert_eq!(columns, vec!["email", "id", "name"]);
+        assert_eq!(
+            rows,
+            vec![
+                vec![Value::Null, Value::I64(1), Value::String("foo".to_string())],
+                vec![
+                    Value::String("bar@example.com".to_string()),
+                    Value::I64(2),
+                    Value::Null
+                ],
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use crate::Error::IoError;
+use crate::{Column, Index, Metadata, Result, Schema, Table};
+use aws_sdk_dynamodb::types::{ScalarAttributeType, TableDescription};
+use aws_sdk_dynamodb::Client;
+
+pub(crate) async fn get_metadata(
+    client: &Client,
+    region: &str,
+    table_name: Option<&str>,
+) -> Result<Metadata> {
+    let mut metadata = Metadata::new();
+    let mut schema = Schema::new(region, true);
+
+    let table_names = match table_name {
+        Some(table_name) => vec![table_name.to_string()],
+        None => list_tables(client).await?,
+    };
+    for table_name in table_names {
+        let response = client
+            .describe_table()
+            .table_name(&table_name)
+            .send()
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        if let Some(description) = response.table() {
+            schema.add(convert_table(&table_name, description));
+        }
+    }
+    metadata.add(schema);
+
+    Ok(metadata)
+}
+
+async fn list_tables(client: &Client) -> Result<Vec<String>> {
+    let mut table_names = Vec::new();
+    let mut exclusive_start_table_name = None;
+    loop {
+        let response = client
+            .list_tables()
+            .set_exclusive_start_table_name(exclusive_start_table_name)
+            .send()
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        table_names.extend(response.table_names().iter().cloned());
+
+        exclusive_start_table_name = response.last_evaluated_table_name().map(ToString::to_string);
+        if exclusive_start_table_name.is_none() {
+            break;
+        }
+    }
+    Ok(table_names)
+}
+
+/// Convert the table description; only the key attributes are defined by `DynamoDB`, so the columns
+/// are the attribute definitions and the indexes are the primary key and global secondary indexes
+fn convert_table(table_name: &str, description: &TableDescription) -> Table {
+    let mut table = Table::new(table_name);
+
+    for attribute in description.attribute_definitions() {
+        let data_type = match attribute.attribute_type() {
+            ScalarAttributeType::S => "string".to_string(),
+            ScalarAttributeType::N => "number".to_string(),
+            ScalarAttributeType::B => "binary".to_string(),
+            attribute_type => attribute_type.as_str().to_lowercase(),
+        };
+        let column = Column::new(attribute.attribute_name().to_string(), data_type, true, None);
+        table.add_column(column);
+    }
+
+    let key_columns: Vec<String> = description
+        .key_schema()
+        .iter()
+        .map(|key| key.attribute_name().to_string())
+        .collect();
+    if !key_columns.is_empty() {
+        table.add_index(Index::new("PRIMARY".to_string(), key_columns, true));
+    }
+
+    for index in description.global_secondary_indexes() {
+        let Some(index_name) = index.index_name() else {
+            continue;
+        };
+        let columns = index
+            .key_schema()
+            .iter()
+            .map(|key| key.attribute_name().to_string())
+            .collect();
+        table.add_index(Index::new(index_name.to_string(), columns, false));
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aws_sdk_dynamodb::types::{
+        AttributeDefinition, GlobalSecondaryIndexDescription, KeySchemaElement, KeyType,
+    };
+
+    fn key(name: &str, key_type: KeyType) -> KeySchemaElement {
+        KeySchemaElement::builder()
+            .attribute_name(name)
+            .key_type(key_type)
+            .build()
+            .expect("key schema element")
+    }
+
+    fn attribute(name: &str, attribute_type: ScalarAttributeType) -> AttributeDefinition {
+        AttributeDefinition::builder()
+            .attribute_name(name)
+            .attribute_type(attribute_type)
+            .build()
+            .expect("attribute definition")
+    }
+
+    #[test]
+    fn test_convert_table() {
+        let description = TableDescription::builder()
+            .attribute_definitions(attribute("id", ScalarAttributeType::S))
+            .attribute_definitions(attribute("created", ScalarAttributeType::N))
+            .attribute_definitions(attribute("email", ScalarAttributeType::S))
+            .key_schema(key("id", KeyType::Hash))
+            .key_schema(key("created", KeyType::Range))
+            .global_secondary_indexes(
+                GlobalSecondaryIndexDescription::builder()
+                    .index_name("email_idx")
+                    .key_schema(key("email", KeyType::Hash))
+                    .build(),
+            )
+            .build();
+
+        let table = convert_table("users", &description);
+
+        assert_eq!(table.name(), "users");
+        let columns: Vec<(&str, &str)> = table
+            .columns()
+            .iter()
+            .map(|column| (column.name(), column.data_type()))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![("id", "string"), ("created", "number"), ("email", "string")]
+        );
+        let primary = table.get_index("PRIMARY").expect("primary index");
+        assert_eq!(primary.columns(), ["id", "created"]);
+        assert!(primary.unique());
+        let email_index = table.get_index("email_idx").expect("email index");
+        assert_eq!(email_index.columns(), ["email"]);
+        assert!(!email_index.unique());
+    }
+}
@@ -25,6 +25,45 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Returns true if the error indicates that the connection to the database was lost, e.g. the
+    /// server closed the connection or the network failed
+    #[must_use]
+    pub fn is_connection_lost(&self) -> bool {
+        let Error::IoError(error) = self else {
+            return false;
+        };
+        error.chain().any(|cause| {
+            if let Some(error) = cause.downcast_ref::<std::io::Error>() {
+                return matches!(
+                    error.kind(),
+                    std::io::ErrorKind::BrokenPipe
+                        | std::io::ErrorKind::ConnectionAborted
+                        | std::io::ErrorKind::ConnectionReset
+                        | std::io::ErrorKind::NotConnected
+                        | std::io::ErrorKind::UnexpectedEof
+                );
+            }
+            #[cfg(any(feature = "mysql", feature = "postgresql", feature = "sqlite"))]
+            if let Some(error) = cause.downcast_ref::<sqlx::Error>() {
+                return matches!(
+                    error,
+                    sqlx::Error::Io(_) | sqlx::Error::PoolClosed | sqlx::Error::WorkerCrashed
+                );
+            }
+            #[cfg(feature = "postgres")]
+            if let Some(error) = cause.downcast_ref::<tokio_postgres::Error>() {
+                return error.is_closed();
+            }
+            #[cfg(feature = "sqlserver")]
+            if let Some(error) = cause.downcast_ref::<tiberius::error::Error>() {
+                return matches!(error, tiberius::error::Error::Io { .. });
+            }
+            false
+        })
+    }
+}
+
 /// Converts a [`duckdb::Error`] into an [`IoError`](Error::IoError)
 #[cfg(feature = "duckdb")]
 impl From<duckdb::Error> for Error {
@@ -33,6 +72,14 @@ impl From<duckdb::Error> for Error {
     }
 }
 
+/// Converts a [`keyring::Error`] into an [`IoError`](Error::IoError)
+#[cfg(feature = "keyring")]
+impl From<keyring::Error> for Error {
+    fn from(error: keyring::Error) -> Self {
+        Error::IoError(error.into())
+    }
+}
+
 /// Converts a [`libsql::Error`] into an [`IoError`](Error::IoError)
 #[cfg(feature = "libsql")]
 impl From<libsql::Error> for Error {
@@ -47,6 +94,7 @@ impl From<libsql::Error> for Error {
     feature = "avro",
     feature = "csv",
     feature = "delimited",
+    feature = "https",
     feature = "json",
     feature = "jsonl",
     feature = "parquet",
@@ -219,6 +267,22 @@ mod test {
         assert_eq!(io_error.to_string(), "test");
     }
 
+    #[test]
+    fn test_is_connection_lost() {
+        let error = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        assert!(Error::from(error).is_connection_lost());
+        let error = std::io::Error::new(std::io::ErrorKind::Other, "test");
+        assert!(!Error::from(error).is_connection_lost());
+        assert!(!Error::InvalidUrl("test".to_string()).is_connection_lost());
+    }
+
+    #[cfg(any(feature = "mysql", feature = "postgresql", feature = "sqlite"))]
+    #[test]
+    fn test_sqlx_is_connection_lost() {
+        assert!(Error::from(sqlx::Error::PoolClosed).is_connection_lost());
+        assert!(!Error::from(sqlx::Error::RowNotFound).is_connection_lost());
+    }
+
     #[test]
     fn test_from_utf8_error() {
         let invalid_utf8: Vec<u8> = vec![0, 159, 146, 150];
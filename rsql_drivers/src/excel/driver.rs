@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use crate::Error::{ConversionError, IoError};
 use async_trait::async_trait;
 use calamine::{open_workbook_auto_from_rs, Data, Range, Reader};
@@ -34,7 +34,8 @@ impl crate::Driver for Driver {
         let query_parameters: HashMap<String, String> =
             parsed_url.query_pairs().into_owned().collect();
 
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let has_header = query_parameters
             .get("has_header")
             .map_or(true, |value| value == "true");
@@ -65,16 +66,48 @@ impl crate::Driver for Driver {
             .parse::<usize>()
             .map_err(|error| ConversionError(error.to_string()))?;
 
+        let cell_range = match query_parameters.get("range") {
+            Some(cell_range) => Some(parse_cell_range(cell_range)?),
+            None => None,
+        };
+        let header_row = match query_parameters.get("header_row") {
+            Some(header_row) => {
+                let header_row = header_row
+                    .parse::<u32>()
+                    .map_err(|error| ConversionError(error.to_string()))?;
+                if header_row == 0 {
+                    return Err(ConversionError(
+                        "The header row must be greater than 0".to_string(),
+                    ));
+                }
+                Some(header_row)
+            }
+            None => None,
+        };
+
         let mut context = SQLContext::new();
         let data = read(&file_name).await?;
         let mut sheets =
             open_workbook_auto_from_rs(Cursor::new(data)).map_err(|error| IoError(error.into()))?;
         let sheet_names = sheets.sheet_names();
+        let file_table_name = crate::polars::driver::get_table_name(file_name.clone())?;
 
         for sheet_name in &sheet_names {
-            let range = sheets
+            let mut range = sheets
                 .worksheet_range(sheet_name)
                 .map_err(|error| IoError(error.into()))?;
+            if let Some((start, end)) = cell_range {
+                range = range.range(start, end);
+            }
+            // The header row is the row number displayed by spreadsheets, so the rows of the range
+            // before the header row are skipped
+            let skip_rows = match header_row {
+                Some(header_row) => {
+                    let start_row = range.start().map_or(0, |(row, _)| row);
+                    usize::try_from((header_row - 1).saturating_sub(start_row))?
+                }
+                None => skip_rows,
+            };
             let json = range_to_json(&range, has_header, skip_rows, skip_rows_after_header)?;
             let cursor = Cursor::new(json.as_bytes());
             let data_frame = JsonReader::new(cursor)
@@ -83,21 +116,14 @@ impl crate::Driver for Driver {
                 .with_ignore_errors(ignore_errors)
                 .finish()?;
 
-            let mut table_name = crate::polars::driver::get_table_name(file_name.clone())?;
-            if sheet_names.len() > 1 {
-                let sheet_table_suffix = sheet_name
-                    .chars()
-                    .map(|character| {
-                        if character.is_alphanumeric() {
-                            character
-                        } else {
-                            '_'
-                        }
-                    })
-                    .collect::<String>();
-                table_name = format!("{table_name}__{sheet_table_suffix}");
-            }
-            context.register(table_name.as_str(), data_frame.lazy());
+            // Workbooks with a single sheet use the file name as the table name, otherwise each
+            // sheet is a table, e.g. SELECT * FROM "Sheet2"
+            let table_name = if sheet_names.len() > 1 {
+                sheet_name.as_str()
+            } else {
+                file_table_name.as_str()
+            };
+            context.register(table_name, data_frame.lazy());
         }
 
         let connection = Connection::new(url, context).await?;
@@ -160,6 +186,46 @@ fn range_to_json(
     Ok(json)
 }
 
+/// Parse a cell range, e.g. `A1:D20`, into the zero based (row, column) positions of the start and
+/// end cells
+fn parse_cell_range(cell_range: &str) -> Result<((u32, u32), (u32, u32))> {
+    let (start, end) = cell_range
+        .split_once(':')
+        .ok_or_else(|| ConversionError(format!("Invalid cell range: {cell_range}")))?;
+    Ok((parse_cell(start)?, parse_cell(end)?))
+}
+
+/// Parse a cell reference, e.g. `B3`, into the zero based (row, column) position of the cell
+fn parse_cell(cell: &str) -> Result<(u32, u32)> {
+    let cell = cell.trim().to_uppercase();
+    let invalid_cell = || ConversionError(format!("Invalid cell: {cell}"));
+    let split = cell
+        .find(|character: char| character.is_ascii_digit())
+        .ok_or_else(invalid_cell)?;
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty()
+        || !letters
+            .chars()
+            .all(|character| character.is_ascii_uppercase())
+    {
+        return Err(invalid_cell());
+    }
+
+    let mut column: u32 = 0;
+    for character in letters.chars() {
+        let value = u32::from(character) - u32::from('A') + 1;
+        column = column
+            .checked_mul(26)
+            .and_then(|column| column.checked_add(value))
+            .ok_or_else(invalid_cell)?;
+    }
+    let row = digits.parse::<u32>().map_err(|_| invalid_cell())?;
+    if row == 0 {
+        return Err(invalid_cell());
+    }
+    Ok((row - 1, column - 1))
+}
+
 #[expect(clippy::cast_possible_truncation)]
 /// Generate column names the same as Spreadsheets, A-Z, AA-AZ, BA-BZ, etc.
 fn column_name(mut column: usize) -> String {
@@ -176,6 +242,7 @@ fn column_name(mut column: usize) -> String {
 
 #[cfg(test)]
 mod test {
+    use super::*;
     use crate::test::dataset_url;
     use crate::{DriverManager, Value};
 
@@ -217,4 +284,47 @@ mod test {
         connection.close().await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_header_row_and_range() -> anyhow::Result<()> {
+        let database_url = format!("{}?header_row=1&range=B1:B3", database_url());
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection.query("SELECT * FROM users").await?;
+
+        assert_eq!(query_result.columns().await, vec!["name"]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::String("John Doe".to_string())])
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_cell() -> anyhow::Result<()> {
+        assert_eq!(parse_cell("A1")?, (0, 0));
+        assert_eq!(parse_cell("b3")?, (2, 1));
+        assert_eq!(parse_cell("AA10")?, (9, 26));
+        assert!(parse_cell("A").is_err());
+        assert!(parse_cell("1").is_err());
+        assert!(parse_cell("A0").is_err());
+        assert!(parse_cell("A1B").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_cell_range() -> anyhow::Result<()> {
+        assert_eq!(parse_cell_range("A1:D20")?, ((0, 0), (19, 3)));
+        assert!(parse_cell_range("A1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+    }
 }
@@ -0,0 +1,312 @@
+use crate::error::Result;
+use crate::exec::protocol::{to_metadata, to_row, ExecRequest, ExecResponse};
+use crate::Error::{InvalidUrl, IoError};
+use crate::{MemoryQueryResult, Metadata, QueryResult};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use file_type::FileType;
+use std::ffi::OsStr;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use url::Url;
+
+#[derive(Debug)]
+pub struct Driver;
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        "exec"
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let connection = Connection::new(url, password).await?;
+        Ok(Box::new(connection))
+    }
+
+    fn supports_file_type(&self, _file_type: &FileType) -> bool {
+        false
+    }
+}
+
+/// Connection to an external process that implements the [`protocol`](crate::exec::protocol)
+#[derive(Debug)]
+pub(crate) struct Connection {
+    url: String,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Connection {
+    /// Start the external process for an `exec://<command>[?arg=<argument>...]` URL and send the
+    /// connect request.
+    pub(crate) async fn new(url: String, password: Option<String>) -> Result<Connection> {
+        let (command, arguments) = parse_url(url.as_str())?;
+        Self::spawn(command.as_ref(), &arguments, url, password).await
+    }
+
+    /// Start the external process with the arguments and send the connect request for the URL
+    pub(crate) async fn spawn(
+        command: &OsStr,
+        arguments: &[String],
+        url: String,
+        password: Option<String>,
+    ) -> Result<Connection> {
+        let command_name = command.to_string_lossy().to_string();
+        let mut child = Command::new(command)
+            .args(arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|error| IoError(anyhow!("unable to start {command_name}: {error}")))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| IoError(anyhow!("unable to open stdin for {command_name}")))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| IoError(anyhow!("unable to open stdout for {command_name}")))?;
+
+        let mut connection = Connection {
+            url: url.clone(),
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        };
+        match connection
+            .request(&ExecRequest::Connect { url, password })
+            .await?
+        {
+            ExecResponse::Ok => Ok(connection),
+            response => Err(unexpected_response(&response)),
+        }
+    }
+
+    /// Send a request to the external process and read the response; error responses are
+    /// converted to errors.
+    async fn request(&mut self, request: &ExecRequest) -> Result<ExecResponse> {
+        match self.send(request).await? {
+            ExecResponse::Error { message } => Err(IoError(anyhow!(message))),
+            response => Ok(response),
+        }
+    }
+
+    /// Send a request to the external process and read the response
+    async fn send(&mut self, request: &ExecRequest) -> Result<ExecResponse> {
+        let mut line = serde_json::to_string(request).map_err(|error| IoError(error.into()))?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line).await? == 0 {
+            return Err(IoError(anyhow!("process exited before responding")));
+        }
+        serde_json::from_str(&line).map_err(|error| IoError(error.into()))
+    }
+}
+
+/// Parse the command and arguments from the URL; arguments are specified with repeated `arg`
+/// query parameters.
+fn parse_url(url: &str) -> Result<(String, Vec<String>)> {
+    let parsed_url = Url::parse(url)?;
+    let scheme = parsed_url.scheme();
+    let start_index = scheme.len() + 3;
+    let end_index = url.find('?').unwrap_or(url.len());
+    let command = url
+        .get(start_index..end_index)
+        .filter(|command| !command.is_empty())
+        .ok_or_else(|| InvalidUrl(format!("Invalid exec url: {url}")))?;
+    let arguments = parsed_url
+        .query_pairs()
+        .filter(|(key, _)| key == "arg")
+        .map(|(_, value)| value.to_string())
+        .collect();
+    Ok((command.to_string(), arguments))
+}
+
+/// Create an error for a response that does not match the request
+fn unexpected_response(response: &ExecResponse) -> crate::Error {
+    IoError(anyhow!("unexpected response: {response:?}"))
+}
+
+#[async_trait]
+impl crate::Connection for Connection {
+    fn url(&self) -> &String {
+        &self.url
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<u64> {
+        let request = ExecRequest::Execute {
+            sql: sql.to_string(),
+        };
+        match self.request(&request).await? {
+            ExecResponse::Execute { rows } => Ok(rows),
+            response => Err(unexpected_response(&response)),
+        }
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        let request = ExecRequest::Query {
+            sql: sql.to_string(),
+        };
+        match self.request(&request).await? {
+            ExecResponse::Query { columns, rows } => {
+                let rows = rows.into_iter().map(to_row).collect();
+                Ok(Box::new(MemoryQueryResult::new(columns, rows)))
+            }
+            response => Err(unexpected_response(&response)),
+        }
+    }
+
+    async fn metadata(&mut self) -> Result<Metadata> {
+        match self.send(&ExecRequest::Metadata).await? {
+            ExecResponse::Metadata { schemas } => Ok(to_metadata(schemas)),
+            // Processes that do not support metadata answer with an error
+            ExecResponse::Error { .. } => Ok(Metadata::default()),
+            response => Err(unexpected_response(&response)),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        let response = self.request(&ExecRequest::Close).await;
+        let _ = self.stdin.shutdown().await;
+        self.child.wait().await?;
+        match response? {
+            ExecResponse::Ok => Ok(()),
+            response => Err(unexpected_response(&response)),
+        }
+    }
+}
+
+impl crate::Capabilities for Connection {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{DriverManager, Value};
+    use indoc::indoc;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const SCRIPT: &str = indoc! {r#"
+        while IFS= read -r line; do
+          case "$line" in
+            *'"method":"connect"'*) echo '{"type":"ok"}' ;;
+            *'"method":"execute"'*) echo '{"type":"execute","rows":2}' ;;
+            *'"method":"query"'*'fail'*) echo '{"type":"error","message":"query failed"}' ;;
+            *'"method":"query"'*) echo '{"type":"query","columns":["id","name"],"rows":[[1,"John Doe"],[2,"Jane Smith"]]}' ;;
+            *'"method":"metadata"'*) echo '{"type":"metadata","schemas":[{"name":"main","current":true,"tables":[{"name":"users","columns":[{"name":"id","data_type":"INTEGER"}]}]}]}' ;;
+            *'"method":"close"'*) echo '{"type":"ok"}'; exit 0 ;;
+          esac
+        done
+    "#};
+
+    fn script() -> anyhow::Result<NamedTempFile> {
+        let mut file = NamedTempFile::new()?;
+        file.write_all(SCRIPT.as_bytes())?;
+        Ok(file)
+    }
+
+    fn database_url(script: &NamedTempFile) -> String {
+        let script = script.path().to_string_lossy();
+        format!("exec://sh?arg={script}")
+    }
+
+    #[test]
+    fn test_parse_url() -> anyhow::Result<()> {
+        let (command, arguments) = parse_url("exec:///usr/bin/driver?arg=--foo&x=y&arg=bar")?;
+        assert_eq!(command, "/usr/bin/driver");
+        assert_eq!(arguments, vec!["--foo".to_string(), "bar".to_string()]);
+
+        let (command, arguments) = parse_url("exec://driver")?;
+        assert_eq!(command, "driver");
+        assert!(arguments.is_empty());
+
+        assert!(parse_url("exec://").is_err());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connection_interface() -> anyhow::Result<()> {
+        let script = script()?;
+        let database_url = database_url(&script);
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+        assert_eq!(&database_url, connection.url());
+
+        let rows = connection.execute("DELETE FROM users").await?;
+        assert_eq!(rows, 2);
+
+        let mut query_result = connection
+            .query("SELECT id, name FROM users ORDER BY id")
+            .await?;
+        assert_eq!(query_result.columns().await, vec!["id", "name"]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(1), Value::String("John Doe".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(2), Value::String("Jane Smith".to_string())])
+        );
+        assert!(query_result.next().await.is_none());
+
+        let error = connection.query("SELECT fail").await;
+        assert!(error.is_err_and(|error| error.to_string() == "query failed"));
+
+        let metadata = connection.metadata().await?;
+        let schema = metadata.current_schema().expect("schema");
+        let table = schema.get("users").expect("users table");
+        assert!(table.get_column("id").is_some());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_metadata_unsupported() -> anyhow::Result<()> {
+        let mut file = NamedTempFile::new()?;
+        file.write_all(
+            indoc! {r#"
+                while IFS= read -r line; do
+                  case "$line" in
+                    *'"method":"close"'*) echo '{"type":"ok"}'; exit 0 ;;
+                    *'"method":"connect"'*) echo '{"type":"ok"}' ;;
+                    *) echo '{"type":"error","message":"unsupported"}' ;;
+                  esac
+                done
+            "#}
+            .as_bytes(),
+        )?;
+        let database_url = database_url(&file);
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let metadata = connection.metadata().await?;
+        assert!(metadata.schemas().is_empty());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connect_invalid_command() {
+        let driver_manager = DriverManager::default();
+        let result = driver_manager
+            .connect("exec://rsql-command-that-does-not-exist")
+            .await;
+        assert!(result.is_err());
+    }
+}
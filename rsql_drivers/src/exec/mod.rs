@@ -0,0 +1,4 @@
+pub mod driver;
+pub mod protocol;
+
+pub use driver::Driver;
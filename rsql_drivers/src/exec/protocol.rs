@@ -0,0 +1,250 @@
+//! JSON over stdio protocol used by the `exec` driver to communicate with an external process.
+//!
+//! Each request is written to the standard input of the process as a single line of JSON and the
+//! process must write a single line of JSON to standard output with the response. A `connect`
+//! request is always sent first and a `close` request is sent before the process is stopped.
+//!
+//! ```text
+//! > {"method":"connect","url":"exec://my-driver?database=sales","password":null}
+//! < {"type":"ok"}
+//! > {"method":"query","sql":"SELECT id, name FROM users"}
+//! < {"type":"query","columns":["id","name"],"rows":[[1,"John Doe"],[2,"Jane Smith"]]}
+//! > {"method":"execute","sql":"DELETE FROM users"}
+//! < {"type":"execute","rows":2}
+//! > {"method":"metadata"}
+//! < {"type":"metadata","schemas":[{"name":"main","current":true,"tables":[{"name":"users","columns":[{"name":"id","data_type":"INTEGER"}]}]}]}
+//! > {"method":"close"}
+//! < {"type":"ok"}
+//! ```
+//!
+//! Any request may be answered with `{"type":"error","message":"..."}`; a process that does not
+//! support the `metadata` request should answer it with an error, and empty metadata is used.
+
+use crate::{Column, Metadata, Row, Schema, Table, Value};
+use serde::{Deserialize, Serialize};
+
+/// Request sent to the external process
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum ExecRequest {
+    /// Connect to the database; sent once when the process is started
+    Connect {
+        url: String,
+        password: Option<String>,
+    },
+    /// Execute a statement and return the number of rows affected
+    Execute { sql: String },
+    /// Execute a query and return the columns and rows
+    Query { sql: String },
+    /// Return the schemas, tables and columns of the database
+    Metadata,
+    /// Close the connection; the process should exit after responding
+    Close,
+}
+
+/// Response returned by the external process
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExecResponse {
+    /// Successful response to a `connect` or `close` request
+    Ok,
+    /// Response to an `execute` request
+    Execute { rows: u64 },
+    /// Response to a `query` request
+    Query {
+        columns: Vec<String>,
+        rows: Vec<Vec<serde_json::Value>>,
+    },
+    /// Response to a `metadata` request
+    Metadata { schemas: Vec<ExecSchema> },
+    /// Error response to any request
+    Error { message: String },
+}
+
+/// Schema returned in a `metadata` response
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExecSchema {
+    pub name: String,
+    #[serde(default)]
+    pub current: bool,
+    #[serde(default)]
+    pub tables: Vec<ExecTable>,
+}
+
+/// Table returned in a `metadata` response
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExecTable {
+    pub name: String,
+    #[serde(default)]
+    pub columns: Vec<ExecColumn>,
+}
+
+/// Column returned in a `metadata` response
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExecColumn {
+    pub name: String,
+    #[serde(default)]
+    pub data_type: String,
+    #[serde(default)]
+    pub not_null: bool,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// Convert the schemas from a metadata response to [`Metadata`]
+#[must_use]
+pub fn to_metadata(schemas: Vec<ExecSchema>) -> Metadata {
+    let mut metadata = Metadata::default();
+    for exec_schema in schemas {
+        let mut schema = Schema::new(exec_schema.name, exec_schema.current);
+        for exec_table in exec_schema.tables {
+            let mut table = Table::new(exec_table.name);
+            for column in exec_table.columns {
+                table.add_column(Column::new(
+                    column.name,
+                    column.data_type,
+                    column.not_null,
+                    column.default,
+                ));
+            }
+            schema.add(table);
+        }
+        metadata.add(schema);
+    }
+    metadata
+}
+
+/// Convert a row of JSON values from a query response to a [`Row`]; arrays and objects are
+/// converted to [`Value::Json`].
+#[must_use]
+pub fn to_row(values: Vec<serde_json::Value>) -> Row {
+    values.into_iter().map(to_value).collect()
+}
+
+/// Convert a JSON value to a [`Value`]
+fn to_value(value: serde_json::Value) -> Value {
+    match value {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(value) => Value::Bool(value),
+        serde_json::Value::Number(number) => {
+            if let Some(value) = number.as_i64() {
+                Value::I64(value)
+            } else if let Some(value) = number.as_u64() {
+                Value::U64(value)
+            } else {
+                number.as_f64().map_or(Value::Null, Value::F64)
+            }
+        }
+        serde_json::Value::String(value) => Value::String(value),
+        value => Value::Json(value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_serialization() -> anyhow::Result<()> {
+        let request = ExecRequest::Connect {
+            url: "exec://driver".to_string(),
+            password: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&request)?,
+            r#"{"method":"connect","url":"exec://driver","password":null}"#
+        );
+        let request = ExecRequest::Query {
+            sql: "SELECT 1".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&request)?,
+            r#"{"method":"query","sql":"SELECT 1"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&ExecRequest::Metadata)?,
+            r#"{"method":"metadata"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&ExecRequest::Close)?,
+            r#"{"method":"close"}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_response_deserialization() -> anyhow::Result<()> {
+        let response: ExecResponse = serde_json::from_str(r#"{"type":"ok"}"#)?;
+        assert_eq!(response, ExecResponse::Ok);
+        let response: ExecResponse = serde_json::from_str(r#"{"type":"execute","rows":3}"#)?;
+        assert_eq!(response, ExecResponse::Execute { rows: 3 });
+        let response: ExecResponse =
+            serde_json::from_str(r#"{"type":"query","columns":["id"],"rows":[[1]]}"#)?;
+        assert_eq!(
+            response,
+            ExecResponse::Query {
+                columns: vec!["id".to_string()],
+                rows: vec![vec![json!(1)]],
+            }
+        );
+        let response: ExecResponse =
+            serde_json::from_str(r#"{"type":"error","message":"failed"}"#)?;
+        assert_eq!(
+            response,
+            ExecResponse::Error {
+                message: "failed".to_string()
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_metadata() -> anyhow::Result<()> {
+        let response: ExecResponse = serde_json::from_str(
+            r#"{"type":"metadata","schemas":[{"name":"main","current":true,"tables":[{"name":"users","columns":[{"name":"id","data_type":"INTEGER","not_null":true},{"name":"name"}]}]}]}"#,
+        )?;
+        let ExecResponse::Metadata { schemas } = response else {
+            panic!("expected metadata response");
+        };
+        let metadata = to_metadata(schemas);
+        let schema = metadata.current_schema().expect("current schema");
+        assert_eq!(schema.name(), "main");
+        let table = schema.get("users").expect("users table");
+        let id = table.get_column("id").expect("id column");
+        assert_eq!(id.data_type(), "INTEGER");
+        assert!(id.not_null());
+        let name = table.get_column("name").expect("name column");
+        assert_eq!(name.data_type(), "");
+        assert!(!name.not_null());
+        assert_eq!(name.default(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_row() {
+        let row = to_row(vec![
+            json!(null),
+            json!(true),
+            json!(-1),
+            json!(u64::MAX),
+            json!(1.5),
+            json!("foo"),
+            json!([1, 2]),
+            json!({"a": 1}),
+        ]);
+        assert_eq!(
+            row,
+            vec![
+                Value::Null,
+                Value::Bool(true),
+                Value::I64(-1),
+                Value::U64(u64::MAX),
+                Value::F64(1.5),
+                Value::String("foo".to_string()),
+                Value::Json(json!([1, 2])),
+                Value::Json(json!({"a": 1})),
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::connection::CachedMetadataConnection;
 use crate::error::Result;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use crate::DriverManager;
 use crate::Error::{DriverNotFound, IoError};
 use async_trait::async_trait;
@@ -22,7 +22,8 @@ impl crate::Driver for Driver {
         _password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
         let parsed_url = Url::parse(url.as_str())?;
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let file_type = FileType::try_from_file(&file_name)
             .await
             .map_err(|error| IoError(error.into()))?;
@@ -0,0 +1,578 @@
+use crate::error::Result;
+use crate::flightsql::metadata;
+use crate::Error::{ConversionError, InvalidUrl, IoError};
+use crate::{MemoryQueryResult, Metadata, QueryResult, Row, Value};
+use arrow::array::{Array, AsArray, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    DataType, Date32Type, Date64Type, Decimal128Type, Float16Type, Float32Type, Float64Type,
+    Int16Type, Int32Type, Int64Type, Int8Type, Time32MillisecondType, Time32SecondType,
+    Time64MicrosecondType, Time64NanosecondType, TimeUnit, TimestampMicrosecondType,
+    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt16Type,
+    UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::util::display::array_value_to_string;
+use arrow_flight::sql::client::FlightSqlServiceClient;
+use arrow_flight::sql::CommandGetTables;
+use arrow_flight::FlightInfo;
+use async_trait::async_trait;
+use file_type::FileType;
+use futures_util::TryStreamExt;
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
+use sqlparser::dialect::{Dialect, GenericDialect};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+use url::Url;
+
+/// The prefix of the query parameters that are sent to the server as request headers
+const HEADER_PREFIX: &str = "header.";
+
+#[derive(Debug)]
+pub struct Driver;
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        "flightsql"
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let connection = Connection::new(url, password).await?;
+        Ok(Box::new(connection))
+    }
+
+    fn supports_file_type(&self, _file_type: &FileType) -> bool {
+        false
+    }
+}
+
+/// Options parsed from a `FlightSQL` URL
+#[derive(Clone, Debug, PartialEq)]
+struct Options {
+    endpoint: String,
+    secure: bool,
+    user: Option<String>,
+    auth_token: Option<String>,
+    schema: Option<String>,
+    headers: Vec<(String, String)>,
+}
+
+impl Options {
+    /// Parse the options from a
+    /// `flightsql://[<user>[:<password>]@]<host>:<port>[?secure=<true/false>][&auth_token=<token>]`
+    /// URL; the `database` query parameter and the `header.<name>` query parameters are sent to
+    /// the server as request headers.
+    fn parse(url: &str) -> Result<Self> {
+        let parsed_url = Url::parse(url)?;
+        let query_parameters: HashMap<String, String> =
+            parsed_url.query_pairs().into_owned().collect();
+        let host = parsed_url
+            .host_str()
+            .ok_or_else(|| InvalidUrl(format!("Missing host: {url}")))?;
+        let secure = query_parameters
+            .get("secure")
+            .is_some_and(|secure| secure == "true");
+        let port = match (parsed_url.port(), secure) {
+            (Some(port), _) => port,
+            (None, true) => 443,
+            (None, false) => return Err(InvalidUrl(format!("Missing port: {url}"))),
+        };
+        let scheme = if secure { "https" } else { "http" };
+        let user = Some(parsed_url.username().to_string()).filter(|user| !user.is_empty());
+
+        let mut headers = Vec::new();
+        if let Some(database) = query_parameters.get("database") {
+            headers.push(("database".to_string(), database.clone()));
+        }
+        for (key, value) in parsed_url.query_pairs().into_owned() {
+            if let Some(name) = key.strip_prefix(HEADER_PREFIX) {
+                headers.push((name.to_lowercase(), value));
+            }
+        }
+
+        Ok(Self {
+            endpoint: format!("{scheme}://{host}:{port}"),
+            secure,
+            user,
+            auth_token: query_parameters.get("auth_token").cloned(),
+            schema: query_parameters.get("schema").cloned(),
+            headers,
+        })
+    }
+}
+
+/// Connection to an [Arrow Flight SQL](https://arrow.apache.org/docs/format/FlightSql.html)
+/// server, e.g. `InfluxDB` 3 or Dremio
+pub(crate) struct Connection {
+    url: String,
+    schema: Option<String>,
+    client: FlightSqlServiceClient<Channel>,
+}
+
+impl Connection {
+    pub(crate) async fn new(url: String, password: Option<String>) -> Result<Connection> {
+        let parsed_url = Url::parse(url.as_str())?;
+        let password = password.or_else(|| parsed_url.password().map(ToString::to_string));
+        let options = Options::parse(url.as_str())?;
+
+        let mut endpoint =
+            Endpoint::from_shared(options.endpoint.clone()).map_err(|error| IoError(error.into()))?;
+        if options.secure {
+            endpoint = endpoint
+                .tls_config(ClientTlsConfig::new().with_native_roots())
+                .map_err(|error| IoError(error.into()))?;
+        }
+        let channel = endpoint
+            .connect()
+            .await
+            .map_err(|error| IoError(error.into()))?;
+
+        let mut client = FlightSqlServiceClient::new(channel);
+        for (name, value) in options.headers {
+            client.set_header(name, value);
+        }
+        if let Some(auth_token) = options.auth_token {
+            client.set_token(auth_token);
+        } else if let Some(user) = options.user {
+            let password = password.unwrap_or_default();
+            client
+                .handshake(user.as_str(), password.as_str())
+                .await
+                .map_err(|error| IoError(error.into()))?;
+        }
+
+        Ok(Connection {
+            url,
+            schema: options.schema,
+            client,
+        })
+    }
+
+    /// Retrieve the record batches of all the endpoints of the flight
+    async fn fetch(&mut self, flight_info: FlightInfo) -> Result<Vec<RecordBatch>> {
+        let mut batches = Vec::new();
+        for endpoint in flight_info.endpoint {
+            let Some(ticket) = endpoint.ticket else {
+                continue;
+            };
+            let stream = self
+                .client
+                .do_get(ticket)
+                .await
+                .map_err(|error| IoError(error.into()))?;
+            let endpoint_batches: Vec<RecordBatch> = stream
+                .try_collect()
+                .await
+                .map_err(|error| IoError(error.into()))?;
+            batches.extend(endpoint_batches);
+        }
+        Ok(batches)
+    }
+
+    /// Retrieve the tables, including the schema of each table
+    pub(crate) async fn tables(&mut self) -> Result<Vec<RecordBatch>> {
+        let command = CommandGetTables {
+            include_schema: true,
+            ..CommandGetTables::default()
+        };
+        let flight_info = self
+            .client
+            .get_tables(command)
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        self.fetch(flight_info).await
+    }
+
+    pub(crate) fn schema(&self) -> Option<&str> {
+        self.schema.as_deref()
+    }
+}
+
+#[async_trait]
+impl crate::Connection for Connection {
+    fn url(&self) -> &String {
+        &self.url
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<u64> {
+        let rows = self
+            .client
+            .execute_update(sql.to_string(), None)
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        // Servers report -1 when the number of rows affected is unknown
+        Ok(u64::try_from(rows).unwrap_or_default())
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        let flight_info = self
+            .client
+            .execute(sql.to_string(), None)
+            .await
+            .map_err(|error| IoError(error.into()))?;
+        let schema = flight_info
+            .clone()
+            .try_decode_schema()
+            .map_err(|error| IoError(error.into()))?;
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().to_string())
+            .collect();
+
+        let mut rows = Vec::new();
+        for batch in self.fetch(flight_info).await? {
+            rows.extend(convert_batch(&batch)?);
+        }
+
+        Ok(Box::new(MemoryQueryResult::new(columns, rows)))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn metadata(&mut self) -> Result<Metadata> {
+        metadata::get_metadata(self).await
+    }
+
+    fn dialect(&self) -> Box<dyn Dialect> {
+        Box::new(GenericDialect)
+    }
+}
+
+impl crate::Capabilities for Connection {}
+
+impl std::fmt::Debug for Connection {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("Connection")
+            .field("url", &self.url)
+            .field("schema", &self.schema)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Convert the record batch to rows; dictionary encoded columns, e.g. `InfluxDB` tags, are decoded
+/// to their values
+pub(crate) fn convert_batch(batch: &RecordBatch) -> Result<Vec<Row>> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| match column.data_type() {
+            DataType::Dictionary(_, value_type) => {
+                cast(column, value_type).map_err(|error| IoError(error.into()))
+            }
+            _ => Ok(Arc::clone(column)),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    (0..batch.num_rows())
+        .map(|index| {
+            columns
+                .iter()
+                .map(|column| convert_to_value(column.as_ref(), index))
+                .collect::<Result<Row>>()
+        })
+        .collect()
+}
+
+/// Convert the value at the index of the array; timestamps are converted to UTC, lists to arrays,
+/// structs and maps to maps, and types without an equivalent value, e.g. intervals, are formatted
+/// as strings
+pub(crate) fn convert_to_value(array: &dyn Array, index: usize) -> Result<Value> {
+    if array.is_null(index) {
+        return Ok(Value::Null);
+    }
+
+    let value = match array.data_type() {
+        DataType::Null => Value::Null,
+        DataType::Boolean => Value::Bool(array.as_boolean().value(index)),
+        DataType::Int8 => Value::I8(array.as_primitive::<Int8Type>().value(index)),
+        DataType::Int16 => Value::I16(array.as_primitive::<Int16Type>().value(index)),
+        DataType::Int32 => Value::I32(array.as_primitive::<Int32Type>().value(index)),
+        DataType::Int64 => Value::I64(array.as_primitive::<Int64Type>().value(index)),
+        DataType::UInt8 => Value::U8(array.as_primitive::<UInt8Type>().value(index)),
+        DataType::UInt16 => Value::U16(array.as_primitive::<UInt16Type>().value(index)),
+        DataType::UInt32 => Value::U32(array.as_primitive::<UInt32Type>().value(index)),
+        DataType::UInt64 => Value::U64(array.as_primitive::<UInt64Type>().value(index)),
+        DataType::Float16 => Value::F32(array.as_primitive::<Float16Type>().value(index).to_f32()),
+        DataType::Float32 => Value::F32(array.as_primitive::<Float32Type>().value(index)),
+        DataType::Float64 => Value::F64(array.as_primitive::<Float64Type>().value(index)),
+        DataType::Decimal128(_, scale) => {
+            let value = array.as_primitive::<Decimal128Type>().value(index);
+            let scale = u32::try_from(*scale)
+                .map_err(|_| ConversionError(format!("invalid decimal scale: {scale}")))?;
+            let decimal = Decimal::try_from_i128_with_scale(value, scale)
+                .map_err(|error| ConversionError(error.to_string()))?;
+            Value::Decimal(decimal)
+        }
+        DataType::Utf8 => Value::String(array.as_string::<i32>().value(index).to_string()),
+        DataType::LargeUtf8 => Value::String(array.as_string::<i64>().value(index).to_string()),
+        DataType::Utf8View => Value::String(array.as_string_view().value(index).to_string()),
+        DataType::Binary => Value::Bytes(array.as_binary::<i32>().value(index).to_vec()),
+        DataType::LargeBinary => Value::Bytes(array.as_binary::<i64>().value(index).to_vec()),
+        DataType::BinaryView => Value::Bytes(array.as_binary_view().value(index).to_vec()),
+        DataType::FixedSizeBinary(_) => {
+            Value::Bytes(array.as_fixed_size_binary().value(index).to_vec())
+        }
+        DataType::Date32 => {
+            let date = array.as_primitive::<Date32Type>().value_as_date(index);
+            Value::Date(date.ok_or_else(|| invalid_value(array, index))?)
+        }
+        DataType::Date64 => {
+            let date = array.as_primitive::<Date64Type>().value_as_date(index);
+            Value::Date(date.ok_or_else(|| invalid_value(array, index))?)
+        }
+        DataType::Time32(unit) => {
+            let time = match unit {
+                TimeUnit::Second => array.as_primitive::<Time32SecondType>().value_as_time(index),
+                _ => array
+                    .as_primitive::<Time32MillisecondType>()
+                    .value_as_time(index),
+            };
+            Value::Time(time.ok_or_else(|| invalid_value(array, index))?)
+        }
+        DataType::Time64(unit) => {
+            let time = match unit {
+                TimeUnit::Microsecond => array
+                    .as_primitive::<Time64MicrosecondType>()
+                    .value_as_time(index),
+                _ => array
+                    .as_primitive::<Time64NanosecondType>()
+                    .value_as_time(index),
+            };
+            Value::Time(time.ok_or_else(|| invalid_value(array, index))?)
+        }
+        DataType::Timestamp(unit, _) => {
+            let date_time = match unit {
+                TimeUnit::Second => array
+                    .as_primitive::<TimestampSecondType>()
+                    .value_as_datetime(index),
+                TimeUnit::Millisecond => array
+                    .as_primitive::<TimestampMillisecondType>()
+                    .value_as_datetime(index),
+                TimeUnit::Microsecond => array
+                    .as_primitive::<TimestampMicrosecondType>()
+                    .value_as_datetime(index),
+                TimeUnit::Nanosecond => array
+                    .as_primitive::<TimestampNanosecondType>()
+                    .value_as_datetime(index),
+            };
+            Value::DateTime(date_time.ok_or_else(|| invalid_value(array, index))?)
+        }
+        DataType::List(_) => convert_array(array.as_list::<i32>().value(index).as_ref())?,
+        DataType::LargeList(_) => convert_array(array.as_list::<i64>().value(index).as_ref())?,
+        DataType::FixedSizeList(_, _) => {
+            convert_array(array.as_fixed_size_list().value(index).as_ref())?
+        }
+        DataType::Struct(_) => convert_struct(array, index)?,
+        DataType::Map(_, _) => convert_map(array, index)?,
+        _ => {
+            let value =
+                array_value_to_string(array, index).map_err(|error| IoError(error.into()))?;
+            Value::String(value)
+        }
+    };
+    Ok(value)
+}
+
+/// Convert all the values of the array to an array value
+fn convert_array(array: &dyn Array) -> Result<Value> {
+    let values = (0..array.len())
+        .map(|index| convert_to_value(array, index))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Value::Array(values))
+}
+
+/// Convert the struct at the index to a map of the field names to the values
+fn convert_struct(array: &dyn Array, index: usize) -> Result<Value> {
+    let struct_array = array.as_struct();
+    let mut map = IndexMap::new();
+    for (field, column) in struct_array.fields().iter().zip(struct_array.columns()) {
+        let value = convert_to_value(column.as_ref(), index)?;
+        map.insert(Value::String(field.name().to_string()), value);
+    }
+    Ok(Value::Map(map))
+}
+
+/// Convert the map at the index to a map of the keys to the values
+fn convert_map(array: &dyn Array, index: usize) -> Result<Value> {
+    let entries = array.as_map().value(index);
+    let mut map = IndexMap::new();
+    for entry in 0..entries.len() {
+        let key = convert_to_value(entries.column(0).as_ref(), entry)?;
+        let value = convert_to_value(entries.column(1).as_ref(), entry)?;
+        map.insert(key, value);
+    }
+    Ok(Value::Map(map))
+}
+
+fn invalid_value(array: &dyn Array, index: usize) -> crate::Error {
+    ConversionError(format!(
+        "invalid {} value at index {index}",
+        array.data_type()
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{
+        BooleanArray, Date32Array, Decimal128Array, DictionaryArray, Int32Array, Int64Array,
+        IntervalYearMonthArray, ListArray, StringArray, StructArray, TimestampNanosecondArray,
+        UInt64Array,
+    };
+    use arrow::datatypes::{Field, Schema};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_options() -> Result<()> {
+        let options = Options::parse(
+            "flightsql://localhost:8181?auth_token=secret&database=metrics&header.X-Tenant=acme&schema=iox",
+        )?;
+        assert_eq!(
+            options,
+            Options {
+                endpoint: "http://localhost:8181".to_string(),
+                secure: false,
+                user: None,
+                auth_token: Some("secret".to_string()),
+                schema: Some("iox".to_string()),
+                headers: vec![
+                    ("database".to_string(), "metrics".to_string()),
+                    ("x-tenant".to_string(), "acme".to_string()),
+                ],
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_secure() -> Result<()> {
+        let options = Options::parse("flightsql://dremio@example.com?secure=true")?;
+        assert_eq!(options.endpoint, "https://example.com:443");
+        assert!(options.secure);
+        assert_eq!(options.user, Some("dremio".to_string()));
+        assert_eq!(options.auth_token, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_options_missing_port() {
+        assert!(Options::parse("flightsql://localhost").is_err());
+    }
+
+    #[test]
+    fn test_convert_batch() -> Result<()> {
+        let tags: DictionaryArray<Int32Type> =
+            vec![Some("a"), None, Some("a")].into_iter().collect();
+        let timestamp = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .and_then(|date| date.and_hms_opt(3, 4, 5))
+            .expect("timestamp");
+        let nanoseconds = timestamp.and_utc().timestamp_nanos_opt().expect("nanoseconds");
+        let schema = Schema::new(vec![
+            Field::new("tag", tags.data_type().clone(), true),
+            Field::new("value", DataType::Int64, true),
+            Field::new("time", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(tags),
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), None])),
+                Arc::new(TimestampNanosecondArray::from(vec![nanoseconds; 3])),
+            ],
+        )
+        .map_err(|error| IoError(error.into()))?;
+
+        let rows = convert_batch(&batch)?;
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Value::String("a".to_string()),
+                    Value::I64(1),
+                    Value::DateTime(timestamp)
+                ],
+                vec![Value::Null, Value::I64(2), Value::DateTime(timestamp)],
+                vec![
+                    Value::String("a".to_string()),
+                    Value::Null,
+                    Value::DateTime(timestamp)
+                ],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_value() -> Result<()> {
+        let array = BooleanArray::from(vec![true]);
+        assert_eq!(convert_to_value(&array, 0)?, Value::Bool(true));
+        let array = UInt64Array::from(vec![u64::MAX]);
+        assert_eq!(convert_to_value(&array, 0)?, Value::U64(u64::MAX));
+        let array = StringArray::from(vec!["foo"]);
+        assert_eq!(
+            convert_to_value(&array, 0)?,
+            Value::String("foo".to_string())
+        );
+        let array = Decimal128Array::from(vec![1234])
+            .with_precision_and_scale(10, 2)
+            .map_err(|error| IoError(error.into()))?;
+        assert_eq!(
+            convert_to_value(&array, 0)?,
+            Value::Decimal(Decimal::new(1234, 2))
+        );
+        let array = Date32Array::from(vec![19_724]);
+        assert_eq!(
+            convert_to_value(&array, 0)?,
+            Value::Date(NaiveDate::from_ymd_opt(2024, 1, 2).expect("date"))
+        );
+        let array = ListArray::from_iter_primitive::<Int32Type, _, _>(vec![Some(vec![
+            Some(1),
+            None,
+        ])]);
+        assert_eq!(
+            convert_to_value(&array, 0)?,
+            Value::Array(vec![Value::I32(1), Value::Null])
+        );
+
+        let array = StructArray::from(vec![
+            (
+                Arc::new(Field::new("id", DataType::Int32, false)),
+                Arc::new(Int32Array::from(vec![42])) as _,
+            ),
+            (
+                Arc::new(Field::new("name", DataType::Utf8, false)),
+                Arc::new(StringArray::from(vec!["foo"])) as _,
+            ),
+        ]);
+        let mut expected = IndexMap::new();
+        expected.insert(Value::String("id".to_string()), Value::I32(42));
+        expected.insert(Value::String("name".to_string()), Value::String("foo".to_string()));
+        assert_eq!(convert_to_value(&array, 0)?, Value::Map(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_value_null() -> Result<()> {
+        let array = Int32Array::from(vec![None]);
+        assert_eq!(convert_to_value(&array, 0)?, Value::Null);
+        Ok(())
+    }
+
+    #[test]
+    fn test_convert_to_value_unsupported_type() -> Result<()> {
+        let array = IntervalYearMonthArray::from(vec![14]);
+        assert!(matches!(convert_to_value(&array, 0)?, Value::String(_)));
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::flightsql::driver::{convert_to_value, Connection};
+use crate::Connection as _;
+use crate::Error::IoError;
+use crate::{Column, Metadata, Result, Schema, Table, Value};
+use arrow::array::RecordBatch;
+use arrow::ipc::convert::try_schema_from_ipc_buffer;
+
+/// Schemas that contain the system tables of the server
+const SYSTEM_SCHEMAS: [&str; 2] = ["information_schema", "system"];
+
+pub(crate) async fn get_metadata(connection: &mut Connection) -> Result<Metadata> {
+    let mut metadata = Metadata::with_dialect(connection.dialect());
+    let batches = connection.tables().await?;
+    let current_schema = connection.schema().map(ToString::to_string);
+
+    add_tables(&mut metadata, &batches, current_schema.as_deref())?;
+
+    Ok(metadata)
+}
+
+/// Add the tables returned by the `GetTables` command; the current schema is the `schema` URL
+/// parameter when specified, otherwise the first schema that is not a system schema
+fn add_tables(
+    metadata: &mut Metadata,
+    batches: &[RecordBatch],
+    current_schema: Option<&str>,
+) -> Result<()> {
+    let mut schemas: Vec<Schema> = Vec::new();
+
+    for batch in batches {
+        let (Some(schema_names), Some(table_names), Some(table_schemas)) = (
+            batch.column_by_name("db_schema_name"),
+            batch.column_by_name("table_name"),
+            batch.column_by_name("table_schema"),
+        ) else {
+            continue;
+        };
+
+        for index in 0..batch.num_rows() {
+            let schema_name = match convert_to_value(schema_names.as_ref(), index)? {
+                Value::Null => "default".to_string(),
+                value => value.to_string(),
+            };
+            let table_name = convert_to_value(table_names.as_ref(), index)?.to_string();
+            let mut table = Table::new(table_name);
+
+            if let Value::Bytes(bytes) = convert_to_value(table_schemas.as_ref(), index)? {
+                let table_schema =
+                    try_schema_from_ipc_buffer(&bytes).map_err(|error| IoError(error.into()))?;
+                for field in table_schema.fields() {
+                    let data_type = field.data_type().to_string();
+                    let column = Column::new(
+                        field.name().to_string(),
+                        data_type,
+                        !field.is_nullable(),
+                        None,
+                    );
+                    table.add_column(column);
+                }
+            }
+
+            if let Some(schema) = schemas
+                .iter_mut()
+                .find(|schema| schema.name() == schema_name)
+            {
+                schema.add(table);
+            } else {
+                let current = match current_schema {
+                    Some(current_schema) => current_schema == schema_name,
+                    None => {
+                        !SYSTEM_SCHEMAS.contains(&schema_name.as_str())
+                            && !schemas.iter().any(Schema::current)
+                    }
+                };
+                let mut schema = Schema::new(schema_name, current);
+                schema.add(table);
+                schemas.push(schema);
+            }
+        }
+    }
+
+    for schema in schemas {
+        metadata.add(schema);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::{BinaryArray, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema as ArrowSchema, TimeUnit};
+    use arrow::ipc::writer::IpcWriteOptions;
+    use arrow_flight::{IpcMessage, SchemaAsIpc};
+    use std::sync::Arc;
+
+    fn schema_bytes(schema: &ArrowSchema) -> Result<Vec<u8>> {
+        let IpcMessage(bytes) = SchemaAsIpc::new(schema, &IpcWriteOptions::default())
+            .try_into()
+            .map_err(|error: arrow::error::ArrowError| IoError(error.into()))?;
+        Ok(bytes.to_vec())
+    }
+
+    #[test]
+    fn test_add_tables() -> Result<()> {
+        let cpu = ArrowSchema::new(vec![
+            Field::new("host", DataType::Utf8, true),
+            Field::new("time", DataType::Timestamp(TimeUnit::Nanosecond, None), false),
+        ]);
+        let tables = ArrowSchema::new(vec![Field::new("table_name", DataType::Utf8, false)]);
+        let cpu_bytes = schema_bytes(&cpu)?;
+        let tables_bytes = schema_bytes(&tables)?;
+        let batch = RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![
+                Field::new("db_schema_name", DataType::Utf8, true),
+                Field::new("table_name", DataType::Utf8, false),
+                Field::new("table_schema", DataType::Binary, false),
+            ])),
+            vec![
+                Arc::new(StringArray::from(vec!["information_schema", "iox"])),
+                Arc::new(StringArray::from(vec!["tables", "cpu"])),
+                Arc::new(BinaryArray::from(vec![
+                    tables_bytes.as_slice(),
+                    cpu_bytes.as_slice(),
+                ])),
+            ],
+        )
+        .map_err(|error| IoError(error.into()))?;
+        let mut metadata = Metadata::default();
+
+        add_tables(&mut metadata, &[batch], None)?;
+
+        let schema = metadata.current_schema().expect("current schema");
+        assert_eq!(schema.name(), "iox");
+        let table = schema.get("cpu").expect("table");
+        let columns: Vec<(&str, &str, bool)> = table
+            .columns()
+            .iter()
+            .map(|column| (column.name(), column.data_type(), column.not_null()))
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                ("host", "Utf8", false),
+                ("time", "Timestamp(Nanosecond, None)", true)
+            ]
+        );
+        let system_schema = metadata.get("information_schema").expect("schema");
+        assert!(!system_schema.current());
+        assert!(system_schema.get("tables").is_some());
+        Ok(())
+    }
+}
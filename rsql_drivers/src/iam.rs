@@ -0,0 +1,219 @@
+use crate::error::Result;
+use crate::Error::{InvalidUrl, IoError};
+use async_trait::async_trait;
+use aws_config::{BehaviorVersion, Region, SdkConfig};
+use aws_sdk_rds::auth_token::{AuthTokenGenerator, Config};
+use file_type::FileType;
+use std::sync::Arc;
+use url::Url;
+
+/// Returns true if the URL requests AWS IAM authentication with the `auth=iam` query parameter
+#[must_use]
+pub(crate) fn iam_enabled(url: &Url) -> bool {
+    url.query_pairs()
+        .any(|(key, value)| key == "auth" && value.eq_ignore_ascii_case("iam"))
+}
+
+/// Driver that authenticates with AWS IAM before connecting with the wrapped driver.  A new
+/// authentication token, or temporary Redshift credentials, are generated for every connection so
+/// that reconnecting never uses an expired token.  Credentials are resolved with the AWS SDK
+/// credential chain (environment, profile, web identity, container or instance metadata).
+#[derive(Debug)]
+pub(crate) struct Driver {
+    inner: Arc<dyn crate::Driver>,
+}
+
+impl Driver {
+    pub(crate) fn new(inner: Arc<dyn crate::Driver>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        self.inner.identifier()
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        _password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let url = Url::parse(url.as_str())?;
+        let config = sdk_config(&url).await;
+        let mut url = connection_url(&url);
+        let host = url
+            .host_str()
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| InvalidUrl("IAM authentication requires a host".to_string()))?
+            .to_string();
+        if url.username().is_empty() {
+            return Err(InvalidUrl("IAM authentication requires a user".to_string()));
+        }
+
+        let password = if url.scheme() == "redshift" {
+            let (user, password) = redshift_credentials(&config, &url, &host).await?;
+            url.set_username(&user)
+                .map_err(|()| InvalidUrl(format!("invalid Redshift user: {user}")))?;
+            password
+        } else {
+            rds_auth_token(&config, &url, host).await?
+        };
+
+        self.inner.connect(url.to_string(), Some(password)).await
+    }
+
+    fn supports_file_type(&self, file_type: &FileType) -> bool {
+        self.inner.supports_file_type(file_type)
+    }
+}
+
+/// Get the URL used to connect to the database; the `auth` and `region` parameters are removed
+/// since they are not recognized by the database drivers.
+fn connection_url(url: &Url) -> Url {
+    let mut connection_url = url.clone();
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "auth" && key != "region")
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    if query.is_empty() {
+        connection_url.set_query(None);
+    } else {
+        connection_url.query_pairs_mut().clear().extend_pairs(query);
+    }
+    let _ = connection_url.set_password(None);
+    connection_url
+}
+
+/// Get the AWS region from the `region` query parameter or the host of an RDS or Redshift
+/// endpoint, e.g. `<instance>.<id>.<region>.rds.amazonaws.com`.
+fn region(url: &Url) -> Option<String> {
+    if let Some((_, region)) = url.query_pairs().find(|(key, _)| key == "region") {
+        return Some(region.to_string());
+    }
+    let host = url.host_str()?;
+    let labels: Vec<&str> = host.split('.').collect();
+    if labels.len() >= 5 && host.ends_with(".amazonaws.com") {
+        return Some(labels[2].to_string());
+    }
+    None
+}
+
+/// Load the AWS SDK configuration, using the region of the URL when it can be determined
+async fn sdk_config(url: &Url) -> SdkConfig {
+    let mut loader = aws_config::defaults(BehaviorVersion::latest());
+    if let Some(region) = region(url) {
+        loader = loader.region(Region::new(region));
+    }
+    loader.load().await
+}
+
+/// Get the default port for the scheme of the URL
+fn default_port(url: &Url) -> u16 {
+    match url.scheme() {
+        "mysql" | "mariadb" => 3306,
+        "redshift" => 5439,
+        "cockroachdb" => 26257,
+        _ => 5432,
+    }
+}
+
+/// Generate an RDS IAM authentication token; tokens are valid for 15 minutes
+async fn rds_auth_token(config: &SdkConfig, url: &Url, host: String) -> Result<String> {
+    let port = url.port().unwrap_or_else(|| default_port(url));
+    let token_config = Config::builder()
+        .hostname(host)
+        .port(u64::from(port))
+        .username(url.username())
+        .build()
+        .map_err(|error| IoError(anyhow::anyhow!(error)))?;
+    let token = AuthTokenGenerator::new(token_config)
+        .auth_token(config)
+        .await
+        .map_err(|error| IoError(anyhow::anyhow!("unable to generate IAM token: {error}")))?;
+    Ok(token.as_str().to_string())
+}
+
+/// Get temporary Redshift credentials for the user of the URL; the cluster identifier is the first
+/// label of the host.  Returns the database user, which is prefixed with `IAM:`, and the password.
+async fn redshift_credentials(
+    config: &SdkConfig,
+    url: &Url,
+    host: &str,
+) -> Result<(String, String)> {
+    let cluster_identifier = host.split('.').next().unwrap_or(host);
+    let database = url.path().trim_start_matches('/');
+    let client = aws_sdk_redshift::Client::new(config);
+    let mut request = client
+        .get_cluster_credentials()
+        .cluster_identifier(cluster_identifier)
+        .db_user(url.username());
+    if !database.is_empty() {
+        request = request.db_name(database);
+    }
+    let credentials = request
+        .send()
+        .await
+        .map_err(|error| IoError(error.into()))?;
+    match (credentials.db_user, credentials.db_password) {
+        (Some(user), Some(password)) => Ok((user, password)),
+        _ => Err(IoError(anyhow::anyhow!(
+            "Redshift did not return credentials for {cluster_identifier}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_iam_enabled() -> anyhow::Result<()> {
+        assert!(iam_enabled(&Url::parse(
+            "postgresql://user@host/db?auth=iam"
+        )?));
+        assert!(iam_enabled(&Url::parse("mysql://user@host/db?auth=IAM")?));
+        assert!(!iam_enabled(&Url::parse("postgresql://user@host/db")?));
+        assert!(!iam_enabled(&Url::parse(
+            "postgresql://user@host/db?auth=password"
+        )?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_connection_url() -> anyhow::Result<()> {
+        let url = Url::parse("postgresql://user@host/db?auth=iam&region=us-east-1")?;
+        assert_eq!(connection_url(&url).as_str(), "postgresql://user@host/db");
+        let url = Url::parse("postgresql://user@host/db?auth=iam&sslmode=require")?;
+        assert_eq!(
+            connection_url(&url).as_str(),
+            "postgresql://user@host/db?sslmode=require"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_region() -> anyhow::Result<()> {
+        let url = Url::parse("postgresql://user@db.abc123.us-west-2.rds.amazonaws.com/db")?;
+        assert_eq!(region(&url), Some("us-west-2".to_string()));
+        let url = Url::parse(
+            "redshift://user@cluster.abc123.eu-west-1.redshift.amazonaws.com:5439/db?auth=iam",
+        )?;
+        assert_eq!(region(&url), Some("eu-west-1".to_string()));
+        let url = Url::parse("postgresql://user@localhost/db?auth=iam&region=us-east-1")?;
+        assert_eq!(region(&url), Some("us-east-1".to_string()));
+        let url = Url::parse("postgresql://user@localhost/db")?;
+        assert_eq!(region(&url), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_port() -> anyhow::Result<()> {
+        assert_eq!(default_port(&Url::parse("postgresql://host")?), 5432);
+        assert_eq!(default_port(&Url::parse("mysql://host")?), 3306);
+        assert_eq!(default_port(&Url::parse("redshift://host")?), 5439);
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use num_format::Locale;
+use std::fmt;
+use std::fmt::Write;
+
+const NANOSECONDS_PER_SECOND: u64 = 1_000_000_000;
+
+/// A span of time with separate month, day and nanosecond components; months and days are not
+/// converted to a fixed number of nanoseconds since their length varies.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Interval {
+    months: i32,
+    days: i32,
+    nanoseconds: i64,
+}
+
+impl Interval {
+    #[must_use]
+    pub fn new(months: i32, days: i32, nanoseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanoseconds,
+        }
+    }
+
+    #[must_use]
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+
+    #[must_use]
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    #[must_use]
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds
+    }
+
+    /// Format the interval using the localized unit names, e.g. `1 year 2 months 3 days 04:05:06`
+    #[must_use]
+    pub fn to_formatted_string(&self, locale: &Locale) -> String {
+        let locale = locale.name();
+        let years = self.months / 12;
+        let months = self.months % 12;
+        let mut parts = Vec::new();
+
+        if years != 0 {
+            let part = if years.abs() == 1 {
+                t!("interval_year", locale = locale, count = years)
+            } else {
+                t!("interval_years", locale = locale, count = years)
+            };
+            parts.push(part.to_string());
+        }
+        if months != 0 {
+            let part = if months.abs() == 1 {
+                t!("interval_month", locale = locale, count = months)
+            } else {
+                t!("interval_months", locale = locale, count = months)
+            };
+            parts.push(part.to_string());
+        }
+        if self.days != 0 {
+            let part = if self.days.abs() == 1 {
+                t!("interval_day", locale = locale, count = self.days)
+            } else {
+                t!("interval_days", locale = locale, count = self.days)
+            };
+            parts.push(part.to_string());
+        }
+        if self.nanoseconds != 0 || parts.is_empty() {
+            parts.push(self.time());
+        }
+
+        parts.join(" ")
+    }
+
+    /// Format the interval as an ISO 8601 duration, e.g. `P1Y2M3DT4H5M6S`
+    #[must_use]
+    pub fn to_iso8601(&self) -> String {
+        let years = self.months / 12;
+        let months = self.months % 12;
+        let mut duration = "P".to_string();
+
+        if years != 0 {
+            let _ = write!(duration, "{years}Y");
+        }
+        if months != 0 {
+            let _ = write!(duration, "{months}M");
+        }
+        if self.days != 0 {
+            let _ = write!(duration, "{}D", self.days);
+        }
+        if self.nanoseconds != 0 {
+            let sign = if self.nanoseconds < 0 { "-" } else { "" };
+            let (hours, minutes, seconds, fraction) = self.time_components();
+            duration.push('T');
+            if hours != 0 {
+                let _ = write!(duration, "{sign}{hours}H");
+            }
+            if minutes != 0 {
+                let _ = write!(duration, "{sign}{minutes}M");
+            }
+            if seconds != 0 || !fraction.is_empty() {
+                let _ = write!(duration, "{sign}{seconds}{fraction}S");
+            }
+        }
+
+        if duration == "P" {
+            "PT0S".to_string()
+        } else {
+            duration
+        }
+    }
+
+    /// Format the nanosecond component as `[-]HH:MM:SS[.fraction]`
+    fn time(&self) -> String {
+        let sign = if self.nanoseconds < 0 { "-" } else { "" };
+        let (hours, minutes, seconds, fraction) = self.time_components();
+        format!("{sign}{hours:02}:{minutes:02}:{seconds:02}{fraction}")
+    }
+
+    /// Split the absolute value of the nanosecond component into hours, minutes, seconds and a
+    /// fraction of a second; the fraction is empty or includes the leading `.`
+    fn time_components(&self) -> (u64, u64, u64, String) {
+        let nanoseconds = self.nanoseconds.unsigned_abs();
+        let total_seconds = nanoseconds / NANOSECONDS_PER_SECOND;
+        let fraction = nanoseconds % NANOSECONDS_PER_SECOND;
+        let fraction = if fraction == 0 {
+            String::new()
+        } else {
+            format!(".{fraction:09}").trim_end_matches('0').to_string()
+        };
+        (
+            total_seconds / 3600,
+            total_seconds / 60 % 60,
+            total_seconds % 60,
+            fraction,
+        )
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_formatted_string(&Locale::en))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HOUR: i64 = 3_600_000_000_000;
+
+    #[test]
+    fn test_accessors() {
+        let interval = Interval::new(1, 2, 3);
+        assert_eq!(interval.months(), 1);
+        assert_eq!(interval.days(), 2);
+        assert_eq!(interval.nanoseconds(), 3);
+    }
+
+    #[test]
+    fn test_to_formatted_string() {
+        let interval = Interval::new(14, 3, 4 * HOUR + 306_789_000_000);
+        assert_eq!(
+            interval.to_formatted_string(&Locale::en),
+            "1 year 2 months 3 days 04:05:06.789"
+        );
+        assert_eq!(
+            interval.to_formatted_string(&Locale::de),
+            "1 Jahr 2 Monate 3 Tage 04:05:06.789"
+        );
+        assert_eq!(
+            Interval::new(25, 1, 0).to_formatted_string(&Locale::en),
+            "2 years 1 month 1 day"
+        );
+        assert_eq!(
+            Interval::new(0, -2, -HOUR).to_formatted_string(&Locale::en),
+            "-2 days -01:00:00"
+        );
+        assert_eq!(
+            Interval::default().to_formatted_string(&Locale::en),
+            "00:00:00"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let interval = Interval::new(0, 1, 90 * 1_000_000_000);
+        assert_eq!(interval.to_string(), "1 day 00:01:30");
+    }
+
+    #[test]
+    fn test_to_iso8601() {
+        let interval = Interval::new(14, 3, 4 * HOUR + 306_789_000_000);
+        assert_eq!(interval.to_iso8601(), "P1Y2M3DT4H5M6.789S");
+        assert_eq!(Interval::new(0, 0, -HOUR).to_iso8601(), "PT-1H");
+        assert_eq!(Interval::new(1, 0, 0).to_iso8601(), "P1M");
+        assert_eq!(Interval::default().to_iso8601(), "PT0S");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use crate::Error::ConversionError;
 use async_trait::async_trait;
 use file_type::FileType;
@@ -31,7 +31,8 @@ impl crate::Driver for Driver {
             parsed_url.query_pairs().into_owned().collect();
 
         // Read Options
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let file = File::open(&file_name)?;
         let ignore_errors = query_parameters
             .get("ignore_errors")
@@ -1,6 +1,7 @@
 use crate::error::Result;
+use crate::polars::write::{FileFormat, FileWriter};
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use crate::Error::ConversionError;
 use async_trait::async_trait;
 use file_type::FileType;
@@ -31,7 +32,8 @@ impl crate::Driver for Driver {
             parsed_url.query_pairs().into_owned().collect();
 
         // Read Options
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let file = File::open(&file_name)?;
         let ignore_errors = query_parameters
             .get("ignore_errors")
@@ -60,7 +62,11 @@ impl crate::Driver for Driver {
         let mut context = SQLContext::new();
         context.register(table_name.as_str(), data_frame.lazy());
 
-        let connection = Connection::new(url, context).await?;
+        let mut connection = Connection::new(url, context).await?;
+        if !local_file.is_temporary() {
+            let writer = FileWriter::new(local_file.path(), table_name, FileFormat::JsonLines);
+            connection = connection.with_writer(writer);
+        }
         Ok(Box::new(connection))
     }
 
@@ -112,4 +118,30 @@ mod test {
         connection.close().await?;
         Ok(())
     }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_compressed_file() -> anyhow::Result<()> {
+        let database_url = dataset_url("jsonl", "users.jsonl.zst");
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(&database_url).await?;
+
+        let mut query_result = connection
+            .query("SELECT id, name FROM users ORDER BY id")
+            .await?;
+
+        assert_eq!(query_result.columns().await, vec!["id", "name"]);
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(1), Value::String("John Doe".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I64(2), Value::String("Jane Smith".to_string())])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
 }
@@ -8,23 +8,49 @@ extern crate rust_i18n;
 
 #[cfg(feature = "arrow")]
 mod arrow;
+#[cfg(feature = "athena")]
+mod athena;
 #[cfg(feature = "avro")]
 mod avro;
+#[cfg(feature = "azure-ad")]
+mod azure;
+mod capabilities;
+#[cfg(feature = "clickhouse")]
+mod clickhouse;
 #[cfg(feature = "cockroachdb")]
 mod cockroachdb;
+mod column_metadata;
+mod connect_options;
 mod connection;
+mod credentials;
 #[cfg(feature = "csv")]
 mod csv;
+mod date_format;
+mod ddl;
 #[cfg(feature = "delimited")]
 mod delimited;
+mod deserialize;
+#[cfg(feature = "dir")]
+mod dir;
 mod driver;
 #[cfg(feature = "duckdb")]
 mod duckdb;
+#[cfg(feature = "dynamodb")]
+mod dynamodb;
 mod error;
 #[cfg(feature = "excel")]
 mod excel;
+#[cfg(feature = "exec")]
+mod exec;
 #[cfg(feature = "file")]
 mod file;
+#[cfg(feature = "flightsql")]
+mod flightsql;
+#[cfg(feature = "https")]
+mod https;
+#[cfg(feature = "aws-iam")]
+mod iam;
+mod interval;
 #[cfg(feature = "json")]
 mod json;
 #[cfg(feature = "jsonl")]
@@ -33,19 +59,26 @@ mod jsonl;
 mod libsql;
 #[cfg(feature = "mariadb")]
 mod mariadb;
+#[cfg(any(feature = "postgresql", feature = "sqlserver"))]
+mod messages;
 mod metadata;
+mod metadata_diff;
 #[cfg(feature = "mysql")]
 mod mysql;
 #[cfg(feature = "ods")]
 mod ods;
 #[cfg(feature = "parquet")]
 mod parquet;
+#[cfg(feature = "plugins")]
+mod plugin;
 #[cfg(any(
     feature = "arrow",
     feature = "avro",
     feature = "csv",
     feature = "delimited",
+    feature = "dir",
     feature = "excel",
+    feature = "https",
     feature = "json",
     feature = "jsonl",
     feature = "ods",
@@ -59,16 +92,39 @@ mod polars;
 mod postgres;
 #[cfg(feature = "postgresql")]
 mod postgresql;
+mod reconnect;
 #[cfg(feature = "redshift")]
 mod redshift;
 #[cfg(feature = "rusqlite")]
 mod rusqlite;
+mod snapshot;
 #[cfg(feature = "snowflake")]
 mod snowflake;
+mod spill;
 #[cfg(feature = "sqlite")]
 mod sqlite;
+#[cfg(any(feature = "rusqlite", feature = "sqlite"))]
+mod sqlite_options;
 #[cfg(feature = "sqlserver")]
 mod sqlserver;
+mod statistics;
+#[cfg(any(
+    feature = "arrow",
+    feature = "avro",
+    feature = "csv",
+    feature = "delimited",
+    feature = "dir",
+    feature = "excel",
+    feature = "file",
+    feature = "json",
+    feature = "jsonl",
+    feature = "ods",
+    feature = "parquet",
+    feature = "tsv",
+    feature = "xml",
+    feature = "yaml",
+))]
+mod storage;
 #[cfg(test)]
 mod test;
 #[cfg(feature = "tsv")]
@@ -80,13 +136,37 @@ mod xml;
 #[cfg(feature = "yaml")]
 mod yaml;
 
+pub use capabilities::{
+    BulkCopy, Capabilities, Export, HistoricalReads, MockBulkCopy, MockCapableConnection,
+    MockExport, MockHistoricalReads, MockMultipleResults, MockNotifications, MockReplica,
+    MultipleResults, Notification, Notifications, Replica,
+};
+pub use column_metadata::ColumnMetadata;
+pub use connect_options::{ConnectOptions, TlsMode};
 pub use connection::{
-    Connection, LimitQueryResult, MemoryQueryResult, MockConnection, QueryResult, StatementMetadata,
+    Connection, LimitQueryResult, MemoryQueryResult, MockConnection, QueryResult,
+    QueryResultStream, Row, StatementMetadata,
+};
+#[cfg(feature = "keyring")]
+pub use credentials::KeyringProvider;
+pub use credentials::{
+    credential_key, AuthenticationPrompt, CredentialProvider, HelperProvider,
+    MockAuthenticationPrompt, MockCredentialProvider,
 };
+pub use date_format::DateFormat;
+pub use deserialize::{deserialize_stream, DeserializeRow};
 pub use driver::{Driver, DriverManager, MockDriver};
 pub use error::{Error, Result};
+#[cfg(feature = "exec")]
+pub use exec::protocol::{ExecColumn, ExecRequest, ExecResponse, ExecSchema, ExecTable};
+pub use interval::Interval;
 pub use metadata::{Column, Index, Metadata, Schema, Table};
-pub use value::Value;
+pub use metadata_diff::{Change, Difference, ObjectKind};
+pub use reconnect::{is_session_statement, transaction_state, ReconnectOptions};
+pub use snapshot::{read_snapshot, write_snapshot};
+pub use spill::RowBuffer;
+pub use statistics::QueryStatistics;
+pub use value::{Value, ValueKind};
 
 use rust_i18n::i18n;
 
@@ -1,6 +1,7 @@
 use crate::error::Result;
 use crate::libsql::metadata;
 use crate::value::Value;
+use crate::Error::InvalidUrl;
 use crate::{MemoryQueryResult, Metadata, QueryResult};
 use async_trait::async_trait;
 use file_type::FileType;
@@ -8,6 +9,7 @@ use libsql::replication::Frames;
 use libsql::Builder;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::time::Duration;
 use url::Url;
 
 #[derive(Debug)]
@@ -35,7 +37,9 @@ impl crate::Driver for Driver {
 
 pub(crate) struct Connection {
     url: String,
+    database: libsql::Database,
     connection: libsql::Connection,
+    replica: bool,
 }
 
 impl Connection {
@@ -43,14 +47,26 @@ impl Connection {
         let parsed_url = Url::parse(url.as_str())?;
         let host = parsed_url.host();
         let file_name = parsed_url.path();
+        let params: HashMap<String, String> = parsed_url.query_pairs().into_owned().collect();
+        let replica_file = params.get("replica");
 
         let database = if let Some(host) = host {
-            let params: HashMap<String, String> = parsed_url.query_pairs().into_owned().collect();
             let auth_token = params.get("auth_token").map_or("", |value| value.as_str());
             let database_url = format!("libsql://{host}");
-            Builder::new_remote(database_url, auth_token.to_string())
-                .build()
-                .await?
+            if let Some(replica_file) = replica_file {
+                let mut builder =
+                    Builder::new_remote_replica(replica_file, database_url, auth_token.to_string());
+                if let Some(sync_interval) = sync_interval(&params)? {
+                    builder = builder.sync_interval(sync_interval);
+                }
+                let database = builder.build().await?;
+                database.sync().await?;
+                database
+            } else {
+                Builder::new_remote(database_url, auth_token.to_string())
+                    .build()
+                    .await?
+            }
         } else if file_name.is_empty() {
             Builder::new_local(":memory:").build().await?
         } else {
@@ -61,9 +77,31 @@ impl Connection {
         };
 
         let connection = database.connect()?;
+        let replica = host.is_some() && replica_file.is_some();
+
+        Ok(Connection {
+            url,
+            database,
+            connection,
+            replica,
+        })
+    }
+}
 
-        Ok(Connection { url, connection })
+/// Get the interval, in seconds, at which an embedded replica is synchronized in the background
+fn sync_interval(params: &HashMap<String, String>) -> Result<Option<Duration>> {
+    let Some(sync_interval) = params.get("sync_interval") else {
+        return Ok(None);
+    };
+    let seconds = sync_interval
+        .parse::<u64>()
+        .map_err(|_| InvalidUrl(format!("Invalid sync_interval: {sync_interval}")))?;
+    if seconds == 0 {
+        return Err(InvalidUrl(format!(
+            "Invalid sync_interval: {sync_interval}"
+        )));
     }
+    Ok(Some(Duration::from_secs(seconds)))
 }
 
 #[async_trait]
@@ -110,6 +148,25 @@ impl crate::Connection for Connection {
     }
 }
 
+#[async_trait]
+impl crate::Replica for Connection {
+    async fn sync(&mut self) -> Result<()> {
+        let _ = self.database.sync().await?;
+        Ok(())
+    }
+}
+
+/// Only embedded replicas can be synchronized with the remote database
+impl crate::Capabilities for Connection {
+    fn as_replica(&mut self) -> Option<&mut dyn crate::Replica> {
+        if self.replica {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
 impl Connection {
     fn convert_to_value(row: &libsql::Row, column_index: i32) -> Result<Value> {
         let value = match row.get_value(column_index)? {
@@ -134,7 +191,10 @@ impl Debug for Connection {
 
 #[cfg(test)]
 mod test {
+    use super::sync_interval;
     use crate::{DriverManager, Value};
+    use std::collections::HashMap;
+    use std::time::Duration;
 
     const DATABASE_URL: &str = "libsql://";
 
@@ -157,6 +217,28 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sync_not_replica() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+        assert!(connection.as_replica().is_none());
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_interval() -> anyhow::Result<()> {
+        let mut params = HashMap::new();
+        assert_eq!(sync_interval(&params)?, None);
+        params.insert("sync_interval".to_string(), "60".to_string());
+        assert_eq!(sync_interval(&params)?, Some(Duration::from_secs(60)));
+        params.insert("sync_interval".to_string(), "0".to_string());
+        assert!(sync_interval(&params).is_err());
+        params.insert("sync_interval".to_string(), "1m".to_string());
+        assert!(sync_interval(&params).is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_connection_interface() -> anyhow::Result<()> {
         let driver_manager = DriverManager::default();
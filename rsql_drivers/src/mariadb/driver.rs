@@ -1,5 +1,7 @@
+use crate::connect_options::connect_timeout;
 use crate::error::Result;
 use crate::mysql::driver::Connection;
+use crate::ConnectOptions;
 use async_trait::async_trait;
 use file_type::FileType;
 
@@ -17,7 +19,15 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url, password).await?;
+        let options = ConnectOptions::from_connection_url(&url, password)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
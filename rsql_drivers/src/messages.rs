@@ -0,0 +1,153 @@
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::instrument::WithSubscriber;
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Dispatch, Event, Level, Metadata, Subscriber};
+
+/// Run the future and return its output with the messages reported by the server while it was
+/// running, e.g. the output of SQL Server `PRINT` or `PostgreSQL` `RAISE NOTICE` statements.
+///
+/// Tiberius and `SQLx` do not return server messages to the caller; they log them as `INFO` or
+/// `WARN` events, so the events of the `target` are recorded by a subscriber that is the default
+/// while the future is polled. All events and spans are forwarded to the previous default
+/// subscriber so logging is unchanged.
+pub(crate) async fn capture_messages<F: Future>(
+    target: &'static str,
+    future: F,
+) -> (F::Output, Vec<(Level, String)>) {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = MessageSubscriber {
+        inner: tracing::dispatcher::get_default(Dispatch::clone),
+        target,
+        messages: Arc::clone(&messages),
+    };
+    let output = future.with_subscriber(subscriber).await;
+    let messages = messages
+        .lock()
+        .map(|mut messages| mem::take(&mut *messages))
+        .unwrap_or_default();
+    (output, messages)
+}
+
+/// Subscriber that records server messages and forwards everything to the inner subscriber
+struct MessageSubscriber {
+    inner: Dispatch,
+    target: &'static str,
+    messages: Arc<Mutex<Vec<(Level, String)>>>,
+}
+
+impl MessageSubscriber {
+    /// Returns true if the event is a server message logged for the target
+    fn is_message(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_event()
+            && (*metadata.level() == Level::INFO || *metadata.level() == Level::WARN)
+            && metadata.target().starts_with(self.target)
+    }
+}
+
+impl Subscriber for MessageSubscriber {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.is_message(metadata) || self.inner.enabled(metadata)
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.inner.new_span(span)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        self.inner.record(span, values);
+    }
+
+    fn record_follows_from(&self, span: &Id, follows: &Id) {
+        self.inner.record_follows_from(span, follows);
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        if self.is_message(event.metadata()) {
+            let mut visitor = MessageVisitor::default();
+            event.record(&mut visitor);
+            if let (Some(message), Ok(mut messages)) = (visitor.message, self.messages.lock()) {
+                messages.push((*event.metadata().level(), message));
+            }
+        }
+        if self.inner.enabled(event.metadata()) {
+            self.inner.event(event);
+        }
+    }
+
+    fn enter(&self, span: &Id) {
+        self.inner.enter(span);
+    }
+
+    fn exit(&self, span: &Id) {
+        self.inner.exit(span);
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.inner.clone_span(id)
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        self.inner.try_close(id)
+    }
+}
+
+/// Visitor that reads the message of an event
+#[derive(Default)]
+struct MessageVisitor {
+    message: Option<String>,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = Some(format!("{value:?}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_capture_messages() {
+        let (output, messages) = capture_messages("tiberius", async {
+            tracing::info!(target: "tiberius::tds::stream::token", "{}", "Hello, world");
+            tracing::info!(target: "rsql", "not a message");
+            tracing::debug!(target: "tiberius::tds::stream::token", "debug");
+            42
+        })
+        .await;
+
+        assert_eq!(output, 42);
+        assert_eq!(messages, vec![(Level::INFO, "Hello, world".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_capture_messages_str() {
+        let ((), messages) = capture_messages("sqlx::postgres::notice", async {
+            tracing::warn!(target: "sqlx::postgres::notice", message = "value \"truncated\"");
+        })
+        .await;
+
+        assert_eq!(
+            messages,
+            vec![(Level::WARN, "value \"truncated\"".to_string())]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use crate::{Column, Index, Metadata, Schema, Table};
+
+/// The kind of change between the source and target metadata
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Change {
+    /// The object only exists in the target
+    Added,
+    /// The object only exists in the source
+    Removed,
+    /// The object exists in both, with a different definition
+    Changed,
+}
+
+/// The kind of database object that is different
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ObjectKind {
+    Schema,
+    Table,
+    Column,
+    Index,
+}
+
+/// A difference between the source and target metadata
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Difference {
+    pub change: Change,
+    pub kind: ObjectKind,
+    /// The qualified name of the object, e.g. `schema.table.column`
+    pub name: String,
+    /// The definition of the object in the source; `None` when the object was added
+    pub source: Option<String>,
+    /// The definition of the object in the target; `None` when the object was removed
+    pub target: Option<String>,
+}
+
+impl Difference {
+    fn added(kind: ObjectKind, name: String, target: Option<String>) -> Self {
+        Self {
+            change: Change::Added,
+            kind,
+            name,
+            source: None,
+            target,
+        }
+    }
+
+    fn removed(kind: ObjectKind, name: String, source: Option<String>) -> Self {
+        Self {
+            change: Change::Removed,
+            kind,
+            name,
+            source,
+            target: None,
+        }
+    }
+}
+
+impl Metadata {
+    /// Compare the schemas, tables, columns and indexes of the metadata with the target metadata;
+    /// the differences are returned in the order of the source objects followed by the objects
+    /// that were added in the target.
+    ///
+    /// Schemas are matched by name, except for the current schemas which are always compared with
+    /// each other, as the current schema of each connection usually has a different name, e.g.
+    /// `main` and `public`.
+    #[must_use]
+    pub fn diff(&self, target: &Metadata) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let source_current = self.current_schema();
+        let target_current = target.current_schema();
+        let find_target = |schema: &Schema| {
+            if schema.current() && target_current.is_some() {
+                target_current
+            } else {
+                target
+                    .schemas()
+                    .into_iter()
+                    .find(|target_schema| target_schema.name() == schema.name())
+                    .filter(|target_schema| !target_schema.current() || source_current.is_none())
+            }
+        };
+
+        let mut matched = Vec::new();
+        for schema in self.schemas() {
+            match find_target(schema) {
+                Some(target_schema) => {
+                    matched.push(target_schema.name().to_string());
+                    diff_schema(&mut differences, schema, target_schema);
+                }
+                None => differences.push(Difference::removed(
+                    ObjectKind::Schema,
+                    schema.name().to_string(),
+                    None,
+                )),
+            }
+        }
+        for schema in target.schemas() {
+            if !matched.iter().any(|name| name == schema.name()) {
+                differences.push(Difference::added(
+                    ObjectKind::Schema,
+                    schema.name().to_string(),
+                    None,
+                ));
+            }
+        }
+
+        differences
+    }
+}
+
+fn diff_schema(differences: &mut Vec<Difference>, source: &Schema, target: &Schema) {
+    for table in source.tables() {
+        let name = format!("{}.{}", source.name(), table.name());
+        match target.get(table.name()) {
+            Some(target_table) => diff_table(differences, &name, table, target_table),
+            None => differences.push(Difference::removed(ObjectKind::Table, name, None)),
+        }
+    }
+    for table in target.tables() {
+        if source.get(table.name()).is_none() {
+            let name = format!("{}.{}", target.name(), table.name());
+            differences.push(Difference::added(ObjectKind::Table, name, None));
+        }
+    }
+}
+
+fn diff_table(differences: &mut Vec<Difference>, table_name: &str, source: &Table, target: &Table) {
+    for column in source.columns() {
+        let name = format!("{table_name}.{}", column.name());
+        let definition = column_definition(column);
+        match target.get_column(column.name()) {
+            Some(target_column) => {
+                let target_definition = column_definition(target_column);
+                if definition != target_definition {
+                    differences.push(Difference {
+                        change: Change::Changed,
+                        kind: ObjectKind::Column,
+                        name,
+                        source: Some(definition),
+                        target: Some(target_definition),
+                    });
+                }
+            }
+            None => differences.push(Difference::removed(
+                ObjectKind::Column,
+                name,
+                Some(definition),
+            )),
+        }
+    }
+    for column in target.columns() {
+        if source.get_column(column.name()).is_none() {
+            let name = format!("{table_name}.{}", column.name());
+            let definition = column_definition(column);
+            differences.push(Difference::added(
+                ObjectKind::Column,
+                name,
+                Some(definition),
+            ));
+        }
+    }
+
+    for index in source.indexes() {
+        let name = format!("{table_name}.{}", index.name());
+        let definition = index_definition(index);
+        match target.get_index(index.name()) {
+            Some(target_index) => {
+                let target_definition = index_definition(target_index);
+                if definition != target_definition {
+                    differences.push(Difference {
+                        change: Change::Changed,
+                        kind: ObjectKind::Index,
+                        name,
+                        source: Some(definition),
+                        target: Some(target_definition),
+                    });
+                }
+            }
+            None => differences.push(Difference::removed(
+                ObjectKind::Index,
+                name,
+                Some(definition),
+            )),
+        }
+    }
+    for index in target.indexes() {
+        if source.get_index(index.name()).is_none() {
+            let name = format!("{table_name}.{}", index.name());
+            let definition = index_definition(index);
+            differences.push(Difference::added(ObjectKind::Index, name, Some(definition)));
+        }
+    }
+}
+
+/// Get the definition of a column, e.g. `VARCHAR(20) NOT NULL DEFAULT 'foo'`; data types are
+/// compared case-insensitively
+fn column_definition(column: &Column) -> String {
+    let mut definition = column.data_type().to_uppercase();
+    if column.not_null() {
+        definition.push_str(" NOT NULL");
+    }
+    if let Some(default) = column.default() {
+        definition.push_str(" DEFAULT ");
+        definition.push_str(default);
+    }
+    definition
+}
+
+/// Get the definition of an index, e.g. `UNIQUE (id, name)`
+fn index_definition(index: &Index) -> String {
+    let columns = index.columns().join(", ");
+    if index.unique() {
+        format!("UNIQUE ({columns})")
+    } else {
+        format!("({columns})")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(schema_name: &str, email_type: &str, unique: bool) -> Metadata {
+        let mut table = Table::new("users");
+        table.add_column(Column::new("id", "INTEGER", true, None));
+        table.add_column(Column::new("email", email_type, false, None));
+        table.add_index(Index::new("users_email_idx", vec!["email"], unique));
+        let mut schema = Schema::new(schema_name, true);
+        schema.add(table);
+        let mut metadata = Metadata::new();
+        metadata.add(schema);
+        metadata
+    }
+
+    #[test]
+    fn test_diff_equal() {
+        let source = metadata("main", "TEXT", false);
+        let target = metadata("public", "text", false);
+        assert!(source.diff(&target).is_empty());
+    }
+
+    #[test]
+    fn test_diff_changed() {
+        let source = metadata("main", "TEXT", false);
+        let target = metadata("public", "VARCHAR(100)", true);
+        let differences = source.diff(&target);
+
+        assert_eq!(
+            differences,
+            vec![
+                Difference {
+                    change: Change::Changed,
+                    kind: ObjectKind::Column,
+                    name: "main.users.email".to_string(),
+                    source: Some("TEXT".to_string()),
+                    target: Some("VARCHAR(100)".to_string()),
+                },
+                Difference {
+                    change: Change::Changed,
+                    kind: ObjectKind::Index,
+                    name: "main.users.users_email_idx".to_string(),
+                    source: Some("(email)".to_string()),
+                    target: Some("UNIQUE (email)".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_added_and_removed() {
+        let mut source = metadata("main", "TEXT", false);
+        let mut target = metadata("main", "TEXT", false);
+        if let Some(table) = source
+            .get_mut("main")
+            .and_then(|schema| schema.get_mut("users"))
+        {
+            table.add_column(Column::new("name", "TEXT", false, Some("'foo'")));
+        }
+        if let Some(schema) = target.get_mut("main") {
+            schema.add(Table::new("orders"));
+        }
+        target.add(Schema::new("audit", false));
+        source.add(Schema::new("archive", false));
+
+        let differences = source.diff(&target);
+
+        assert_eq!(
+            differences,
+            vec![
+                Difference::removed(
+                    ObjectKind::Column,
+                    "main.users.name".to_string(),
+                    Some("TEXT DEFAULT 'foo'".to_string())
+                ),
+                Difference::added(ObjectKind::Table, "main.orders".to_string(), None),
+                Difference::removed(ObjectKind::Schema, "archive".to_string(), None),
+                Difference::added(ObjectKind::Schema, "audit".to_string(), None),
+            ]
+        );
+    }
+}
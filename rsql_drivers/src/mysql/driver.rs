@@ -1,17 +1,24 @@
+use crate::connect_options::connect_timeout;
+use crate::connection::limit_statement;
 use crate::error::Result;
 use crate::mysql::metadata;
 use crate::value::Value;
-use crate::Error::UnsupportedColumnType;
-use crate::{MemoryQueryResult, Metadata, QueryResult};
+use crate::Error::{ConversionError, InvalidUrl, UnsupportedColumnType};
+use crate::{
+    ColumnMetadata, ConnectOptions, Interval, MemoryQueryResult, Metadata, QueryResult, TlsMode,
+};
 use async_trait::async_trait;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use file_type::FileType;
 use sqlparser::dialect::{Dialect, MySqlDialect};
-use sqlx::mysql::{MySqlColumn, MySqlConnectOptions, MySqlRow};
+use sqlx::mysql::{MySqlColumn, MySqlConnectOptions, MySqlRow, MySqlSslMode};
+use sqlx::pool::PoolConnection;
 use sqlx::types::time::OffsetDateTime;
-use sqlx::{Column, MySqlPool, Row};
+use sqlx::{Column, MySql, MySqlPool, Row, TypeInfo};
+use std::mem;
 use std::str::FromStr;
 use std::string::ToString;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct Driver;
@@ -27,7 +34,15 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url, password).await?;
+        let options = ConnectOptions::from_connection_url(&url, password)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
@@ -40,16 +55,66 @@ impl crate::Driver for Driver {
 pub(crate) struct Connection {
     url: String,
     pool: MySqlPool,
+    timeout: Option<Duration>,
+    read_only: Option<bool>,
+    /// Warnings reported by the server for the last statement
+    messages: Vec<String>,
 }
 
 impl Connection {
-    pub(crate) async fn new(url: String, _password: Option<String>) -> Result<Connection> {
-        let options = MySqlConnectOptions::from_str(url.as_str())?;
-        let pool = MySqlPool::connect_with(options).await?;
-        let connection = Connection { url, pool };
+    pub(crate) async fn new(url: String, options: &ConnectOptions) -> Result<Connection> {
+        let pool = MySqlPool::connect_with(connect_options(options)?).await?;
+        let connection = Connection {
+            url,
+            pool,
+            timeout: None,
+            read_only: None,
+            messages: Vec::new(),
+        };
 
         Ok(connection)
     }
+
+    /// Get a connection from the pool; the statement timeout and read-only mode are applied to the
+    /// connection since session settings are not shared by the connections of the pool.  `MySQL`
+    /// limits the time of `SELECT` statements with `max_execution_time` (milliseconds) and
+    /// `MariaDB` limits all statements with `max_statement_time` (seconds).
+    async fn acquire(&self) -> Result<PoolConnection<MySql>> {
+        let mut connection = self.pool.acquire().await?;
+        if let Some(timeout) = self.timeout {
+            let statement = if self.url.starts_with("mariadb:") {
+                format!("SET SESSION max_statement_time = {}", timeout.as_secs_f64())
+            } else {
+                format!("SET SESSION max_execution_time = {}", timeout.as_millis())
+            };
+            sqlx::query(&statement).execute(&mut *connection).await?;
+        }
+        if let Some(read_only) = self.read_only {
+            let statement = if read_only {
+                "SET SESSION TRANSACTION READ ONLY"
+            } else {
+                "SET SESSION TRANSACTION READ WRITE"
+            };
+            sqlx::query(statement).execute(&mut *connection).await?;
+        }
+        Ok(connection)
+    }
+
+    /// Get the warnings of the last statement executed on the connection, formatted like the
+    /// `mysql` client, e.g. `Warning (Code 1265): Data truncated for column 'name' at row 1`
+    async fn warnings(connection: &mut PoolConnection<MySql>) -> Result<Vec<String>> {
+        let rows = sqlx::query("SHOW WARNINGS")
+            .fetch_all(&mut **connection)
+            .await?;
+        let mut warnings = Vec::new();
+        for row in rows {
+            let level: String = row.try_get("Level")?;
+            let code: u32 = row.try_get("Code")?;
+            let message: String = row.try_get("Message")?;
+            warnings.push(format!("{level} (Code {code}): {message}"));
+        }
+        Ok(warnings)
+    }
 }
 
 #[async_trait]
@@ -59,7 +124,13 @@ impl crate::Connection for Connection {
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
-        let rows = sqlx::query(sql).execute(&self.pool).await?.rows_affected();
+        let mut connection = self.acquire().await?;
+        let rows = sqlx::query(sql)
+            .execute(&mut *connection)
+            .await?
+            .rows_affected();
+        // The statement succeeded, so failing to read the warnings does not fail the statement
+        self.messages = Self::warnings(&mut connection).await.unwrap_or_default();
         Ok(rows)
     }
 
@@ -68,7 +139,9 @@ impl crate::Connection for Connection {
     }
 
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
-        let query_rows = sqlx::query(sql).fetch_all(&self.pool).await?;
+        let mut connection = self.acquire().await?;
+        let query_rows = sqlx::query(sql).fetch_all(&mut *connection).await?;
+        self.messages = Self::warnings(&mut connection).await.unwrap_or_default();
         let columns: Vec<String> = query_rows
             .first()
             .map(|row| {
@@ -78,6 +151,18 @@ impl crate::Connection for Connection {
                     .collect()
             })
             .unwrap_or_default();
+        let columns_metadata: Vec<ColumnMetadata> = query_rows
+            .first()
+            .map(|row| {
+                row.columns()
+                    .iter()
+                    .map(|column| {
+                        ColumnMetadata::new(column.name())
+                            .with_data_type(column.type_info().name())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let mut rows = Vec::new();
         for row in query_rows {
@@ -89,7 +174,8 @@ impl crate::Connection for Connection {
             rows.push(row_data);
         }
 
-        let query_result = MemoryQueryResult::new(columns, rows);
+        let query_result =
+            MemoryQueryResult::new(columns, rows).with_columns_metadata(columns_metadata);
         Ok(Box::new(query_result))
     }
 
@@ -98,75 +184,74 @@ impl crate::Connection for Connection {
         Ok(())
     }
 
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        self.timeout = Some(timeout);
+        Ok(true)
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        mem::take(&mut self.messages)
+    }
+
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.read_only = Some(read_only);
+        Ok(())
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(MySqlDialect {})
     }
+
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
 }
 
+impl crate::Capabilities for Connection {}
+
 impl Connection {
     fn convert_to_value(row: &MySqlRow, column: &MySqlColumn) -> Result<Value> {
         let column_name = column.name();
 
-        if let Ok(value) = row.try_get::<Option<String>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::String(v)),
-                None => Ok(Value::Null),
-            }
+        if Self::is_set(column) {
+            let value = row.try_get_unchecked::<Option<String>, &str>(column_name)?;
+            Ok(value.map_or(Value::Null, |v| Self::set_value(&v)))
+        } else if let Ok(value) = row.try_get::<Option<String>, &str>(column_name) {
+            Ok(value.map_or(Value::Null, Value::String))
         } else if let Ok(value) = row.try_get::<Option<Vec<u8>>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::Bytes(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::Bytes))
         } else if let Ok(value) = row.try_get::<Option<i16>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::I16(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::I16))
         } else if let Ok(value) = row.try_get::<Option<i32>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::I32(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::I32))
         } else if let Ok(value) = row.try_get::<Option<i64>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::I64(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::I64))
+        } else if let Ok(value) = row.try_get::<Option<u16>, &str>(column_name) {
+            Ok(value.map_or(Value::Null, Value::U16))
+        } else if let Ok(value) = row.try_get::<Option<u32>, &str>(column_name) {
+            Ok(value.map_or(Value::Null, Value::U32))
+        } else if let Ok(value) = row.try_get::<Option<u64>, &str>(column_name) {
+            Ok(value.map_or(Value::Null, Value::U64))
         } else if let Ok(value) = row.try_get::<Option<f32>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::F32(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::F32))
         } else if let Ok(value) = row.try_get::<Option<f64>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::F64(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::F64))
         } else if let Ok(value) = row.try_get::<Option<rust_decimal::Decimal>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::String(v.to_string())),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::Decimal))
         } else if let Ok(value) = row.try_get::<Option<bool>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::Bool(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::Bool))
         } else if let Ok(value) = row.try_get::<Option<NaiveDate>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::Date(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::Date))
         } else if let Ok(value) = row.try_get::<Option<NaiveTime>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::Time(v)),
+            Ok(value.map_or(Value::Null, Value::Time))
+        } else if column.type_info().name() == "TIME" {
+            // TIME values that are negative or exceed 24 hours are spans of time
+            match row.try_get_unchecked::<Option<&[u8]>, &str>(column_name)? {
+                Some(v) => Ok(Value::Interval(Self::time_interval(v)?)),
                 None => Ok(Value::Null),
             }
         } else if let Ok(value) = row.try_get::<Option<NaiveDateTime>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::DateTime(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::DateTime))
         } else if let Ok(value) = row.try_get::<Option<OffsetDateTime>, &str>(column_name) {
             match value {
                 Some(v) => {
@@ -181,10 +266,7 @@ impl Connection {
                 None => Ok(Value::Null),
             }
         } else if let Ok(value) = row.try_get::<Option<serde_json::Value>, &str>(column_name) {
-            match value {
-                Some(v) => Ok(Value::Json(v)),
-                None => Ok(Value::Null),
-            }
+            Ok(value.map_or(Value::Null, Value::Json))
         } else {
             let column_type = column.type_info();
             let type_name = format!("{column_type:?}");
@@ -194,4 +276,149 @@ impl Connection {
             });
         }
     }
+
+    /// Returns true if the column is a SET column; `MySQL` sends SET columns as strings with the
+    /// SET column flag, and the flags are only available from the debug format of the type.
+    fn is_set(column: &MySqlColumn) -> bool {
+        let column_type = column.type_info();
+        column_type.name() == "SET" || format!("{column_type:?}").contains("SET")
+    }
+
+    /// Convert the comma separated members of a SET value to an array of strings
+    fn set_value(value: &str) -> Value {
+        let members = value
+            .split(',')
+            .filter(|member| !member.is_empty())
+            .map(|member| Value::String(member.to_string()))
+            .collect();
+        Value::Array(members)
+    }
+
+    /// Convert a `MySQL` TIME value to an interval; the binary format is a sign byte, a
+    /// little-endian day count, hours, minutes, seconds and optional little-endian microseconds.
+    fn time_interval(bytes: &[u8]) -> Result<Interval> {
+        let error = || ConversionError(format!("invalid TIME value: {bytes:?}"));
+        if bytes.is_empty() {
+            return Ok(Interval::default());
+        }
+        if bytes.len() != 8 && bytes.len() != 12 {
+            return Err(error());
+        }
+
+        let negative = bytes[0] == 1;
+        let days = u32::from_le_bytes(bytes[1..5].try_into().map_err(|_| error())?);
+        let hours = u64::from(bytes[5]);
+        let minutes = u64::from(bytes[6]);
+        let seconds = u64::from(bytes[7]);
+        let microseconds = match bytes.get(8..12) {
+            Some(microseconds) => u32::from_le_bytes(microseconds.try_into().map_err(|_| error())?),
+            None => 0,
+        };
+
+        let seconds = ((u64::from(days) * 24 + hours) * 60 + minutes) * 60 + seconds;
+        let nanoseconds = seconds * 1_000_000_000 + u64::from(microseconds) * 1_000;
+        let nanoseconds = i64::try_from(nanoseconds)?;
+        let nanoseconds = if negative { -nanoseconds } else { nanoseconds };
+        Ok(Interval::new(0, 0, nanoseconds))
+    }
+}
+
+/// Build the `SQLx` connect options from the options; parameters of the options, e.g. `ssl-mode`,
+/// take precedence over the TLS mode
+fn connect_options(options: &ConnectOptions) -> Result<MySqlConnectOptions> {
+    let mut connect_options = MySqlConnectOptions::new();
+    if let Some(host) = options.host() {
+        connect_options = connect_options.host(host);
+    }
+    if let Some(port) = options.port() {
+        connect_options = connect_options.port(port);
+    }
+    if let Some(username) = options.username() {
+        connect_options = connect_options.username(username);
+    }
+    if let Some(password) = options.password() {
+        connect_options = connect_options.password(password);
+    }
+    if let Some(database) = options.database() {
+        connect_options = connect_options.database(database);
+    }
+    connect_options = match options.tls() {
+        Some(TlsMode::Disable) => connect_options.ssl_mode(MySqlSslMode::Disabled),
+        Some(TlsMode::Require) => connect_options.ssl_mode(MySqlSslMode::Required),
+        Some(TlsMode::VerifyFull) => connect_options.ssl_mode(MySqlSslMode::VerifyIdentity),
+        None => connect_options,
+    };
+
+    for (key, value) in options.parameters() {
+        let invalid = || InvalidUrl(format!("Invalid parameter: {key}={value}"));
+        connect_options = match key.as_str() {
+            "ssl-mode" => {
+                connect_options.ssl_mode(MySqlSslMode::from_str(value).map_err(|_| invalid())?)
+            }
+            "ssl-ca" => connect_options.ssl_ca(value),
+            "ssl-cert" => connect_options.ssl_client_cert(value),
+            "ssl-key" => connect_options.ssl_client_key(value),
+            "statement-cache-capacity" => {
+                connect_options.statement_cache_capacity(value.parse().map_err(|_| invalid())?)
+            }
+            "charset" => connect_options.charset(value),
+            "collation" => connect_options.collation(value),
+            "socket" => connect_options.socket(value),
+            _ => connect_options,
+        };
+    }
+    Ok(connect_options)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_connect_options() -> Result<()> {
+        let url = "mysql://root@db.example.com:3307/app?ssl-mode=VERIFY_CA";
+        let options = ConnectOptions::from_connection_url(url, Some("secret".to_string()))?
+            .with_tls(TlsMode::Require);
+        let mysql_options = connect_options(&options)?;
+        assert_eq!(mysql_options.get_host(), "db.example.com");
+        assert_eq!(mysql_options.get_port(), 3307);
+        assert_eq!(mysql_options.get_username(), "root");
+        assert_eq!(mysql_options.get_database(), Some("app"));
+        assert!(matches!(
+            mysql_options.get_ssl_mode(),
+            MySqlSslMode::VerifyCa
+        ));
+
+        let options = options.with_parameter("ssl-mode", "foo");
+        assert!(connect_options(&options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_interval() -> Result<()> {
+        let interval = Connection::time_interval(&[0, 34, 0, 0, 0, 22, 59, 59])?;
+        assert_eq!(
+            interval,
+            Interval::new(0, 0, 838 * 3_600_000_000_000 + 3_599_000_000_000)
+        );
+
+        let interval = Connection::time_interval(&[1, 0, 0, 0, 0, 1, 2, 3, 160, 134, 1, 0])?;
+        assert_eq!(interval, Interval::new(0, 0, -3_723_100_000_000));
+
+        assert_eq!(Connection::time_interval(&[])?, Interval::default());
+        assert!(Connection::time_interval(&[0, 1]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_value() {
+        assert_eq!(
+            Connection::set_value("read,write"),
+            Value::Array(vec![
+                Value::String("read".to_string()),
+                Value::String("write".to_string()),
+            ])
+        );
+        assert_eq!(Connection::set_value(""), Value::Array(vec![]));
+    }
 }
@@ -1,13 +1,13 @@
 use crate::error::Result;
+use crate::polars::write::{FileFormat, FileWriter};
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use async_trait::async_trait;
 use file_type::FileType;
 use polars::io::SerReader;
 use polars::prelude::{IntoLazy, ParquetReader};
 use polars_sql::SQLContext;
 use std::fs::File;
-use url::Url;
 
 #[derive(Debug)]
 pub struct Driver;
@@ -23,8 +23,8 @@ impl crate::Driver for Driver {
         url: String,
         _password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let parsed_url = Url::parse(url.as_str())?;
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let file = File::open(&file_name)?;
 
         let data_frame = ParquetReader::new(file).set_rechunk(true).finish()?;
@@ -33,7 +33,11 @@ impl crate::Driver for Driver {
         let mut context = SQLContext::new();
         context.register(table_name.as_str(), data_frame.lazy());
 
-        let connection = Connection::new(url, context).await?;
+        let mut connection = Connection::new(url, context).await?;
+        if !local_file.is_temporary() {
+            let writer = FileWriter::new(local_file.path(), table_name, FileFormat::Parquet);
+            connection = connection.with_writer(writer);
+        }
         Ok(Box::new(connection))
     }
 
@@ -0,0 +1,218 @@
+use crate::error::Result;
+use crate::exec::driver::Connection;
+use async_trait::async_trait;
+use file_type::FileType;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Prefix of the executables that provide plugin drivers (e.g. `rsql-driver-foo` for `foo://`)
+const PLUGIN_PREFIX: &str = "rsql-driver-";
+
+/// A driver provided by an external executable that implements the
+/// [`exec protocol`](crate::exec::protocol); the URL is passed to the executable unchanged.
+#[derive(Debug)]
+pub struct Driver {
+    identifier: &'static str,
+    command: PathBuf,
+}
+
+impl Driver {
+    /// Create a new plugin driver for the identifier and executable
+    #[must_use]
+    pub fn new(identifier: &'static str, command: PathBuf) -> Self {
+        Self {
+            identifier,
+            command,
+        }
+    }
+}
+
+#[async_trait]
+impl crate::Driver for Driver {
+    fn identifier(&self) -> &'static str {
+        self.identifier
+    }
+
+    async fn connect(
+        &self,
+        url: String,
+        password: Option<String>,
+    ) -> Result<Box<dyn crate::Connection>> {
+        let connection = Connection::spawn(self.command.as_os_str(), &[], url, password).await?;
+        Ok(Box::new(connection))
+    }
+
+    fn supports_file_type(&self, _file_type: &FileType) -> bool {
+        false
+    }
+}
+
+/// Get the plugin drivers found on the `PATH`; plugins are discovered once and cached for the
+/// lifetime of the process.
+pub fn plugins() -> Vec<Driver> {
+    static PLUGINS: OnceLock<Vec<(&'static str, PathBuf)>> = OnceLock::new();
+    PLUGINS
+        .get_or_init(|| {
+            let paths = std::env::var_os("PATH")
+                .map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>())
+                .unwrap_or_default();
+            discover(&paths)
+                .into_iter()
+                .map(|(identifier, command)| {
+                    let identifier: &'static str = Box::leak(identifier.into_boxed_str());
+                    (identifier, command)
+                })
+                .collect()
+        })
+        .iter()
+        .map(|(identifier, command)| Driver::new(identifier, command.clone()))
+        .collect()
+}
+
+/// Discover the plugin executables in the directories; when the same plugin is found in more than
+/// one directory, the first one is used.
+fn discover(paths: &[PathBuf]) -> Vec<(String, PathBuf)> {
+    let mut plugins: Vec<(String, PathBuf)> = Vec::new();
+    for path in paths {
+        let Ok(entries) = std::fs::read_dir(path) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(identifier) = plugin_identifier(&path) else {
+                continue;
+            };
+            if is_executable(&path) && !plugins.iter().any(|(existing, _)| *existing == identifier)
+            {
+                plugins.push((identifier, path));
+            }
+        }
+    }
+    plugins
+}
+
+/// Get the driver identifier from a plugin executable path; the identifier must be a valid URL
+/// scheme.
+fn plugin_identifier(path: &Path) -> Option<String> {
+    let file_name = path.file_name()?.to_str()?;
+    let file_name = file_name.strip_suffix(".exe").unwrap_or(file_name);
+    let identifier = file_name.strip_prefix(PLUGIN_PREFIX)?.to_lowercase();
+    let mut chars = identifier.chars();
+    let valid = chars.next().is_some_and(|char| char.is_ascii_alphabetic())
+        && chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'));
+    valid.then_some(identifier)
+}
+
+/// Check if the path is an executable file
+fn is_executable(path: &Path) -> bool {
+    let Ok(metadata) = path.metadata() else {
+        return false;
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        metadata.is_file()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Driver as _, Value};
+    use indoc::indoc;
+
+    #[test]
+    fn test_plugin_identifier() {
+        assert_eq!(
+            plugin_identifier(Path::new("/bin/rsql-driver-foo")),
+            Some("foo".to_string())
+        );
+        assert_eq!(
+            plugin_identifier(Path::new("rsql-driver-Foo.exe")),
+            Some("foo".to_string())
+        );
+        assert_eq!(
+            plugin_identifier(Path::new("rsql-driver-foo+bar")),
+            Some("foo+bar".to_string())
+        );
+        assert_eq!(plugin_identifier(Path::new("rsql-driver-")), None);
+        assert_eq!(plugin_identifier(Path::new("rsql-driver-1foo")), None);
+        assert_eq!(plugin_identifier(Path::new("rsql-driver-foo_bar")), None);
+        assert_eq!(plugin_identifier(Path::new("rsql")), None);
+    }
+
+    #[cfg(unix)]
+    fn write_plugin(directory: &Path, name: &str) -> anyhow::Result<PathBuf> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = indoc! {r#"
+            #!/bin/sh
+            while IFS= read -r line; do
+              case "$line" in
+                *'"method":"query"'*) echo '{"type":"query","columns":["id"],"rows":[[1]]}' ;;
+                *'"method":"metadata"'*) echo '{"type":"error","message":"unsupported"}' ;;
+                *'"method":"close"'*) echo '{"type":"ok"}'; exit 0 ;;
+                *) echo '{"type":"ok"}' ;;
+              esac
+            done
+        "#};
+        let path = directory.join(name);
+        std::fs::write(&path, script)?;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+        Ok(path)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_discover() -> anyhow::Result<()> {
+        let first = tempfile::tempdir()?;
+        let second = tempfile::tempdir()?;
+        let foo = write_plugin(first.path(), "rsql-driver-foo")?;
+        write_plugin(second.path(), "rsql-driver-foo")?;
+        let bar = write_plugin(second.path(), "rsql-driver-bar")?;
+        write_plugin(second.path(), "not-a-plugin")?;
+        std::fs::write(second.path().join("rsql-driver-baz"), "not executable")?;
+
+        let paths = vec![
+            first.path().to_path_buf(),
+            PathBuf::from("/rsql/directory/does/not/exist"),
+            second.path().to_path_buf(),
+        ];
+        let mut plugins = discover(&paths);
+        plugins.sort();
+
+        assert_eq!(
+            plugins,
+            vec![("bar".to_string(), bar), ("foo".to_string(), foo)]
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_driver_connect() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let command = write_plugin(directory.path(), "rsql-driver-foo")?;
+        let driver = Driver::new("foo", command);
+        assert_eq!(driver.identifier(), "foo");
+
+        let url = "foo://localhost/database".to_string();
+        let mut connection = driver.connect(url.clone(), None).await?;
+        assert_eq!(&url, connection.url());
+
+        let mut query_result = connection.query("SELECT 1").await?;
+        assert_eq!(query_result.columns().await, vec!["id"]);
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(1)]));
+        assert!(query_result.next().await.is_none());
+
+        let metadata = connection.metadata().await?;
+        assert!(metadata.schemas().is_empty());
+
+        connection.close().await?;
+        Ok(())
+    }
+}
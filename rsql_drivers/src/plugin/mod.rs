@@ -0,0 +1,3 @@
+pub mod driver;
+
+pub use driver::plugins;
@@ -1,29 +1,52 @@
 use crate::error::Result;
 use crate::polars::metadata;
 use crate::polars::value::ToValue;
+use crate::polars::write::FileWriter;
 use crate::Error::{ConversionError, InvalidUrl};
-use crate::{MemoryQueryResult, Metadata, QueryResult};
+use crate::{ColumnMetadata, MemoryQueryResult, Metadata, QueryResult};
 use async_trait::async_trait;
+use polars::prelude::{col, DataType, Expr, IntoLazy};
 use polars_sql::SQLContext;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
 use std::fmt::{Debug, Formatter};
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use url::Url;
 
 /// Connection for drivers based on Polars `SQLContext`
 pub struct Connection {
     url: String,
     context: Arc<Mutex<SQLContext>>,
+    writer: Option<FileWriter>,
 }
 
 impl Connection {
+    /// Create a new connection; the column types of the tables are overridden by the `types`
+    /// query parameter of the url, e.g. `types=zip:string,amount:decimal`
     #[expect(clippy::unused_async)]
-    pub async fn new(url: String, context: SQLContext) -> Result<Self> {
+    pub async fn new(url: String, mut context: SQLContext) -> Result<Self> {
+        let parsed_url = Url::parse(url.as_str())?;
+        if let Some((_, types)) = parsed_url.query_pairs().find(|(key, _)| key == "types") {
+            let column_types = parse_column_types(&types)?;
+            cast_columns(&mut context, &column_types)?;
+        }
+
         Ok(Self {
             url,
             context: Arc::new(Mutex::new(context)),
+            writer: None,
         })
     }
+
+    /// Write the changes made by `INSERT`, `UPDATE` and `DELETE` statements back to the file that
+    /// the table was read from
+    #[must_use]
+    pub(crate) fn with_writer(mut self, writer: FileWriter) -> Self {
+        self.writer = Some(writer);
+        self
+    }
 }
 
 impl Connection {
@@ -33,6 +56,73 @@ impl Connection {
     }
 }
 
+/// Parse the column types of the `types` query parameter; the types are specified as a comma
+/// separated list of `<column>:<type>` pairs
+pub(crate) fn parse_column_types(types: &str) -> Result<Vec<(String, DataType)>> {
+    types
+        .split(',')
+        .filter(|column_type| !column_type.trim().is_empty())
+        .map(|column_type| {
+            let Some((column, data_type)) = column_type.rsplit_once(':') else {
+                return Err(ConversionError(format!(
+                    "Invalid column type; expected <column>:<type>: {column_type}"
+                )));
+            };
+            Ok((column.trim().to_string(), data_type_from_str(data_type)?))
+        })
+        .collect()
+}
+
+/// Get the Polars data type for the type name
+fn data_type_from_str(data_type: &str) -> Result<DataType> {
+    let data_type = match data_type.trim().to_lowercase().as_str() {
+        "bool" | "boolean" => DataType::Boolean,
+        "i8" | "tinyint" => DataType::Int8,
+        "i16" | "smallint" => DataType::Int16,
+        "i32" | "int" | "integer" => DataType::Int32,
+        "i64" | "bigint" | "long" => DataType::Int64,
+        "u8" => DataType::UInt8,
+        "u16" => DataType::UInt16,
+        "u32" => DataType::UInt32,
+        "u64" => DataType::UInt64,
+        "f32" | "float" | "real" => DataType::Float32,
+        // Decimal values are not supported by the Polars features used by the drivers, so they are
+        // read as 64-bit floating point values
+        "f64" | "double" | "decimal" | "numeric" => DataType::Float64,
+        "date" => DataType::Date,
+        "str" | "string" | "text" | "varchar" => DataType::String,
+        _ => return Err(ConversionError(format!("Invalid data type: {data_type}"))),
+    };
+    Ok(data_type)
+}
+
+/// Cast the columns of all tables in the context to the specified types; an error is returned if
+/// a column does not exist in any of the tables
+fn cast_columns(context: &mut SQLContext, column_types: &[(String, DataType)]) -> Result<()> {
+    let mut unknown_columns: Vec<&String> = column_types.iter().map(|(column, _)| column).collect();
+    for table_name in context.get_tables() {
+        let mut lazy_frame = context.execute(&format!("SELECT * FROM \"{table_name}\""))?;
+        let schema = lazy_frame.collect_schema()?;
+        let casts: Vec<Expr> = column_types
+            .iter()
+            .filter(|(column, _)| schema.contains(column))
+            .map(|(column, data_type)| col(column.as_str()).cast(data_type.clone()))
+            .collect();
+        if casts.is_empty() {
+            continue;
+        }
+
+        unknown_columns.retain(|column| !schema.contains(column));
+        let data_frame = lazy_frame.with_columns(casts).collect()?;
+        context.register(table_name.as_str(), data_frame.lazy());
+    }
+
+    if let Some(column) = unknown_columns.first() {
+        return Err(ConversionError(format!("Invalid column: {column}")));
+    }
+    Ok(())
+}
+
 /// Get the table name from the file name
 pub(crate) fn get_table_name<S: AsRef<str>>(file_name: S) -> Result<String> {
     let file_name = file_name.as_ref();
@@ -53,6 +143,14 @@ impl crate::Connection for Connection {
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
         let mut context = self.context.lock().await;
+        if let Some(writer) = &self.writer {
+            let statements = Parser::parse_sql(&GenericDialect, sql).unwrap_or_default();
+            if let [statement] = statements.as_slice() {
+                if let Some(rows) = writer.execute(&mut context, statement)? {
+                    return Ok(rows);
+                }
+            }
+        }
         let result = context.execute(sql)?;
         let data_frame = result.collect()?;
         let rows = u64::try_from(data_frame.height())?;
@@ -68,6 +166,14 @@ impl crate::Connection for Connection {
             .iter()
             .map(ToString::to_string)
             .collect();
+        let columns_metadata: Vec<ColumnMetadata> = data_frame
+            .get_columns()
+            .iter()
+            .map(|column| {
+                ColumnMetadata::new(column.name().as_str())
+                    .with_data_type(column.dtype().to_string())
+            })
+            .collect();
         let mut rows = Vec::new();
 
         // Convert the data frame to a vector of rows
@@ -87,7 +193,8 @@ impl crate::Connection for Connection {
             }
         }
 
-        let query_result = MemoryQueryResult::new(columns, rows);
+        let query_result =
+            MemoryQueryResult::new(columns, rows).with_columns_metadata(columns_metadata);
         Ok(Box::new(query_result))
     }
 
@@ -100,6 +207,8 @@ impl crate::Connection for Connection {
     }
 }
 
+impl crate::Capabilities for Connection {}
+
 #[expect(clippy::missing_fields_in_debug)]
 impl Debug for Connection {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -129,6 +238,10 @@ mod test {
             .await?;
 
         assert_eq!(query_result.columns().await, vec!["id", "name"]);
+        let columns_metadata = query_result.columns_metadata().await;
+        assert_eq!(columns_metadata[0].data_type, Some("i64".to_string()));
+        assert!(columns_metadata[0].is_numeric());
+        assert_eq!(columns_metadata[1].data_type, Some("str".to_string()));
         assert_eq!(
             query_result.next().await,
             Some(vec![Value::I64(1), Value::String("John Doe".to_string())])
@@ -142,4 +255,60 @@ mod test {
         connection.close().await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_connection_types() -> anyhow::Result<()> {
+        let zips = Series::new("zip".into(), &[2134i64, 90210i64]);
+        let amounts = Series::new("amount".into(), &["1.5", "2"]);
+        let data_frame = DataFrame::new(vec![Column::from(zips), Column::from(amounts)])?;
+        let mut context = SQLContext::new();
+        context.register("orders", data_frame.lazy());
+        let url = "polars://?types=zip:string,amount:decimal".to_string();
+        let mut connection = super::Connection::new(url, context).await?;
+
+        let mut query_result = connection
+            .query("SELECT zip, amount FROM orders ORDER BY amount")
+            .await?;
+
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::String("2134".to_string()), Value::F64(1.5)])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::String("90210".to_string()), Value::F64(2.0)])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_connection_types_invalid_column() -> anyhow::Result<()> {
+        let ids = Series::new("id".into(), &[1i64, 2i64]);
+        let data_frame = DataFrame::new(vec![Column::from(ids)])?;
+        let mut context = SQLContext::new();
+        context.register("users", data_frame.lazy());
+        let url = "polars://?types=foo:string".to_string();
+
+        assert!(super::Connection::new(url, context).await.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_column_types() -> anyhow::Result<()> {
+        let column_types = parse_column_types("zip:string, amount:DECIMAL,created:date")?;
+        assert_eq!(
+            column_types,
+            vec![
+                ("zip".to_string(), DataType::String),
+                ("amount".to_string(), DataType::Float64),
+                ("created".to_string(), DataType::Date),
+            ]
+        );
+        assert!(parse_column_types("zip").is_err());
+        assert!(parse_column_types("zip:foo").is_err());
+        Ok(())
+    }
 }
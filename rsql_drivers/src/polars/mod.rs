@@ -1,5 +1,6 @@
 pub mod driver;
 mod metadata;
 mod value;
+pub(crate) mod write;
 
 pub use driver::Connection;
@@ -0,0 +1,502 @@
+use crate::error::Result;
+use crate::Error::ConversionError;
+use polars::prelude::{
+    col, concat, lit, CsvWriter, DataFrame, Expr, IntoLazy, JsonFormat, JsonWriter, LazyFrame,
+    Null, ParquetWriter, SerWriter, UnionArgs,
+};
+use polars_sql::SQLContext;
+use sqlparser::ast::{
+    Assignment, AssignmentTarget, Delete, Expr as SqlExpr, FromTable, Insert, ObjectName, Query,
+    SetExpr, Statement, TableFactor, TableWithJoins,
+};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// The format used to write a table back to its file
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum FileFormat {
+    Csv {
+        has_header: bool,
+        quote: Option<u8>,
+        separator: u8,
+    },
+    JsonLines,
+    Parquet,
+}
+
+/// Writes the changes to a table back to the file that the table was read from, so that `INSERT`,
+/// `UPDATE` and `DELETE` statements can be executed against file drivers
+#[derive(Clone, Debug)]
+pub(crate) struct FileWriter {
+    path: PathBuf,
+    table_name: String,
+    format: FileFormat,
+}
+
+impl FileWriter {
+    pub(crate) fn new<P: AsRef<Path>, S: Into<String>>(
+        path: P,
+        table_name: S,
+        format: FileFormat,
+    ) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            table_name: table_name.into(),
+            format,
+        }
+    }
+
+    /// Execute the `INSERT`, `UPDATE` or `DELETE` statement against the table of the writer and
+    /// rewrite the file; `None` is returned when the statement is not a data modification
+    /// statement for the table.
+    pub(crate) fn execute(
+        &self,
+        context: &mut SQLContext,
+        statement: &Statement,
+    ) -> Result<Option<u64>> {
+        let (table_name, result) = match statement {
+            Statement::Insert(Insert {
+                table_name,
+                columns,
+                source: Some(source),
+                ..
+            }) => (table_name, Modification::Insert { columns, source }),
+            Statement::Update {
+                table,
+                assignments,
+                selection,
+                ..
+            } => {
+                let Some(table_name) = relation_name(table) else {
+                    return Ok(None);
+                };
+                (
+                    table_name,
+                    Modification::Update {
+                        assignments,
+                        selection: selection.as_ref(),
+                    },
+                )
+            }
+            Statement::Delete(Delete {
+                from, selection, ..
+            }) => {
+                let tables = match from {
+                    FromTable::WithFromKeyword(tables) | FromTable::WithoutKeyword(tables) => {
+                        tables
+                    }
+                };
+                let [table] = tables.as_slice() else {
+                    return Ok(None);
+                };
+                let Some(table_name) = relation_name(table) else {
+                    return Ok(None);
+                };
+                (
+                    table_name,
+                    Modification::Delete {
+                        selection: selection.as_ref(),
+                    },
+                )
+            }
+            _ => return Ok(None),
+        };
+        if table_name
+            .0
+            .last()
+            .is_none_or(|ident| ident.value != self.table_name)
+        {
+            return Ok(None);
+        }
+
+        let table = context.execute(&select_all(&self.table_name))?;
+        let (lazy_frame, rows) = match result {
+            Modification::Insert { columns, source } => {
+                let inserted = insert(context, &table, columns, source)?;
+                let rows = inserted.height();
+                (
+                    concat([table, inserted.lazy()], UnionArgs::default())?,
+                    rows,
+                )
+            }
+            Modification::Update {
+                assignments,
+                selection,
+            } => update(context, &self.table_name, table, assignments, selection)?,
+            Modification::Delete { selection } => delete(context, &self.table_name, selection)?,
+        };
+
+        let mut data_frame = lazy_frame.collect()?;
+        self.write(&mut data_frame)?;
+        context.register(&self.table_name, data_frame.lazy());
+        Ok(Some(u64::try_from(rows)?))
+    }
+
+    /// Write the data frame to a temporary file in the directory of the file and replace the file,
+    /// so that the file is not left partially written if an error occurs
+    fn write(&self, data_frame: &mut DataFrame) -> Result<()> {
+        let directory = match self.path.parent() {
+            Some(directory) if !directory.as_os_str().is_empty() => directory,
+            _ => Path::new("."),
+        };
+        let mut temp_file = NamedTempFile::new_in(directory)?;
+        let file = temp_file.as_file_mut();
+        match &self.format {
+            FileFormat::Csv {
+                has_header,
+                quote,
+                separator,
+            } => {
+                let mut writer = CsvWriter::new(file)
+                    .include_header(*has_header)
+                    .with_separator(*separator);
+                if let Some(quote) = quote {
+                    writer = writer.with_quote_char(*quote);
+                }
+                writer.finish(data_frame)?;
+            }
+            FileFormat::JsonLines => {
+                JsonWriter::new(file)
+                    .with_json_format(JsonFormat::JsonLines)
+                    .finish(data_frame)?;
+            }
+            FileFormat::Parquet => {
+                ParquetWriter::new(file).finish(data_frame)?;
+            }
+        }
+        temp_file.persist(&self.path).map_err(|error| error.error)?;
+        Ok(())
+    }
+}
+
+/// The data modification statement to execute
+enum Modification<'a> {
+    Insert {
+        columns: &'a [sqlparser::ast::Ident],
+        source: &'a Query,
+    },
+    Update {
+        assignments: &'a [Assignment],
+        selection: Option<&'a SqlExpr>,
+    },
+    Delete {
+        selection: Option<&'a SqlExpr>,
+    },
+}
+
+/// Get the name of the table; `None` is returned for joins and table expressions other than a
+/// table name
+fn relation_name(table: &TableWithJoins) -> Option<&ObjectName> {
+    match &table.relation {
+        TableFactor::Table { name, .. } if table.joins.is_empty() => Some(name),
+        _ => None,
+    }
+}
+
+fn select_all(table_name: &str) -> String {
+    format!("SELECT * FROM \"{table_name}\"")
+}
+
+/// Condition that is true for the rows matching the selection; rows where the selection evaluates
+/// to null are not matched
+fn matches(selection: Option<&SqlExpr>) -> String {
+    match selection {
+        Some(selection) => format!("COALESCE(({selection}), FALSE)"),
+        None => "TRUE".to_string(),
+    }
+}
+
+/// Evaluate the rows to insert and cast the values to the column types of the table; columns that
+/// are not specified are null
+fn insert(
+    context: &mut SQLContext,
+    table: &LazyFrame,
+    columns: &[sqlparser::ast::Ident],
+    source: &Query,
+) -> Result<DataFrame> {
+    let mut table = table.clone();
+    let schema = table.collect_schema()?;
+    let column_names: Vec<String> = if columns.is_empty() {
+        schema.iter_names().map(ToString::to_string).collect()
+    } else {
+        columns.iter().map(|column| column.value.clone()).collect()
+    };
+
+    for column in &column_names {
+        if !schema.contains(column) {
+            return Err(ConversionError(format!("Invalid column: {column}")));
+        }
+    }
+
+    // Polars does not support VALUES lists, so each row is converted to a SELECT statement
+    let sql = match source.body.as_ref() {
+        SetExpr::Values(values) => values
+            .rows
+            .iter()
+            .map(|row| {
+                if row.len() != column_names.len() {
+                    return Err(ConversionError(format!(
+                        "Expected {} values; found {}",
+                        column_names.len(),
+                        row.len()
+                    )));
+                }
+                let expressions: Vec<String> = row
+                    .iter()
+                    .zip(&column_names)
+                    .map(|(value, column)| format!("{value} AS \"{column}\""))
+                    .collect();
+                Ok(format!("SELECT {}", expressions.join(", ")))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(" UNION ALL "),
+        _ => source.to_string(),
+    };
+    let mut inserted = context.execute(&sql)?;
+    let inserted_schema = inserted.collect_schema()?;
+    if inserted_schema.len() != column_names.len() {
+        return Err(ConversionError(format!(
+            "Expected {} columns; found {}",
+            column_names.len(),
+            inserted_schema.len()
+        )));
+    }
+
+    let mut expressions = Vec::new();
+    for (name, data_type) in schema.iter() {
+        let expression = match column_names
+            .iter()
+            .position(|column| column == name.as_str())
+        {
+            Some(index) => {
+                let inserted_name = inserted_schema
+                    .get_at_index(index)
+                    .map(|(inserted_name, _)| inserted_name.to_string())
+                    .unwrap_or_default();
+                col(inserted_name.as_str())
+            }
+            None => lit(Null {}),
+        };
+        expressions.push(expression.cast(data_type.clone()).alias(name.as_str()));
+    }
+    Ok(inserted.select(expressions).collect()?)
+}
+
+/// Evaluate the assignments for the rows that match the selection, returning the updated table and
+/// the number of updated rows
+fn update(
+    context: &mut SQLContext,
+    table_name: &str,
+    mut table: LazyFrame,
+    assignments: &[Assignment],
+    selection: Option<&SqlExpr>,
+) -> Result<(LazyFrame, usize)> {
+    let schema = table.collect_schema()?;
+    let condition = matches(selection);
+    let mut expressions = Vec::new();
+    for (name, _) in schema.iter() {
+        let assignment = assignments
+            .iter()
+            .find(|assignment| match &assignment.target {
+                AssignmentTarget::ColumnName(column) => column
+                    .0
+                    .last()
+                    .is_some_and(|ident| ident.value == name.as_str()),
+                AssignmentTarget::Tuple(_) => false,
+            });
+        let expression = match assignment {
+            Some(assignment) => format!(
+                "CASE WHEN {condition} THEN {} ELSE \"{name}\" END AS \"{name}\"",
+                assignment.value
+            ),
+            None => format!("\"{name}\""),
+        };
+        expressions.push(expression);
+    }
+    for assignment in assignments {
+        let AssignmentTarget::ColumnName(column) = &assignment.target else {
+            return Err(ConversionError(format!(
+                "Unsupported assignment: {assignment}"
+            )));
+        };
+        if !column
+            .0
+            .last()
+            .is_some_and(|ident| schema.contains(ident.value.as_str()))
+        {
+            return Err(ConversionError(format!("Invalid column: {column}")));
+        }
+    }
+
+    let rows = count(context, table_name, &condition)?;
+    let updated = context.execute(&format!(
+        "SELECT {} FROM \"{table_name}\"",
+        expressions.join(", ")
+    ))?;
+    // Cast the updated columns back to the original types of the table
+    let casts: Vec<Expr> = schema
+        .iter()
+        .map(|(name, data_type)| col(name.as_str()).cast(data_type.clone()))
+        .collect();
+    Ok((updated.select(casts), rows))
+}
+
+/// Remove the rows that match the selection, returning the remaining rows and the number of
+/// deleted rows
+fn delete(
+    context: &mut SQLContext,
+    table_name: &str,
+    selection: Option<&SqlExpr>,
+) -> Result<(LazyFrame, usize)> {
+    let condition = matches(selection);
+    let rows = count(context, table_name, &condition)?;
+    let remaining = context.execute(&format!(
+        "SELECT * FROM \"{table_name}\" WHERE NOT {condition}"
+    ))?;
+    Ok((remaining, rows))
+}
+
+/// Count the rows of the table that match the condition
+fn count(context: &mut SQLContext, table_name: &str, condition: &str) -> Result<usize> {
+    let data_frame = context
+        .execute(&format!("SELECT * FROM \"{table_name}\" WHERE {condition}"))?
+        .collect()?;
+    Ok(data_frame.height())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::io::SerReader;
+    use polars::prelude::{Column, CsvReadOptions, NamedFrom, Series};
+    use sqlparser::dialect::GenericDialect;
+    use sqlparser::parser::Parser;
+    use tempfile::TempDir;
+
+    fn context() -> anyhow::Result<SQLContext> {
+        let ids = Series::new("id".into(), &[1i64, 2i64]);
+        let names = Series::new("name".into(), &["John Doe", "Jane Smith"]);
+        let data_frame = DataFrame::new(vec![Column::from(ids), Column::from(names)])?;
+        let mut context = SQLContext::new();
+        context.register("users", data_frame.lazy());
+        Ok(context)
+    }
+
+    fn execute(
+        writer: &FileWriter,
+        context: &mut SQLContext,
+        sql: &str,
+    ) -> anyhow::Result<Option<u64>> {
+        let statements = Parser::parse_sql(&GenericDialect, sql)?;
+        let statement = statements.first().expect("statement");
+        Ok(writer.execute(context, statement)?)
+    }
+
+    fn read_csv(path: &Path) -> anyhow::Result<DataFrame> {
+        let data_frame = CsvReadOptions::default()
+            .try_into_reader_with_file_path(Some(path.to_path_buf()))?
+            .finish()?;
+        Ok(data_frame)
+    }
+
+    fn csv_writer(directory: &TempDir) -> FileWriter {
+        let format = FileFormat::Csv {
+            has_header: true,
+            quote: None,
+            separator: b',',
+        };
+        FileWriter::new(directory.path().join("users.csv"), "users", format)
+    }
+
+    #[test]
+    fn test_insert() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let writer = csv_writer(&directory);
+        let mut context = context()?;
+
+        let rows = execute(
+            &writer,
+            &mut context,
+            "INSERT INTO users (name, id) VALUES ('Alice', 3), ('Bob', 4)",
+        )?;
+
+        assert_eq!(rows, Some(2));
+        let data_frame = read_csv(&writer.path)?;
+        assert_eq!(data_frame.height(), 4);
+        let names: Vec<Option<&str>> = data_frame.column("name")?.str()?.into_iter().collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("John Doe"),
+                Some("Jane Smith"),
+                Some("Alice"),
+                Some("Bob")
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_invalid_column() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let writer = csv_writer(&directory);
+        let mut context = context()?;
+
+        let result = execute(&writer, &mut context, "INSERT INTO users (foo) VALUES (1)");
+
+        assert!(result.is_err());
+        assert!(!writer.path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_update() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let writer = csv_writer(&directory);
+        let mut context = context()?;
+
+        let rows = execute(
+            &writer,
+            &mut context,
+            "UPDATE users SET name = 'Jane Doe' WHERE id = 2",
+        )?;
+
+        assert_eq!(rows, Some(1));
+        let data_frame = read_csv(&writer.path)?;
+        let names: Vec<Option<&str>> = data_frame.column("name")?.str()?.into_iter().collect();
+        assert_eq!(names, vec![Some("John Doe"), Some("Jane Doe")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let writer = csv_writer(&directory);
+        let mut context = context()?;
+
+        let rows = execute(&writer, &mut context, "DELETE FROM users WHERE id = 1")?;
+
+        assert_eq!(rows, Some(1));
+        let data_frame = read_csv(&writer.path)?;
+        let ids: Vec<Option<i64>> = data_frame.column("id")?.i64()?.into_iter().collect();
+        assert_eq!(ids, vec![Some(2)]);
+        let data_frame = context.execute("SELECT * FROM users")?.collect()?;
+        assert_eq!(data_frame.height(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_other_statements() -> anyhow::Result<()> {
+        let directory = TempDir::new()?;
+        let writer = csv_writer(&directory);
+        let mut context = context()?;
+
+        assert_eq!(execute(&writer, &mut context, "SELECT * FROM users")?, None);
+        assert_eq!(
+            execute(&writer, &mut context, "DELETE FROM contacts WHERE id = 1")?,
+            None
+        );
+        assert!(!writer.path.exists());
+        Ok(())
+    }
+}
@@ -1,21 +1,31 @@
+use crate::connection::limit_statement;
 use crate::error::Result;
 use crate::value::Value;
 use crate::Error::UnsupportedColumnType;
-use crate::{postgresql, Error, MemoryQueryResult, Metadata, QueryResult, StatementMetadata};
+use crate::{
+    postgresql, ColumnMetadata, Error, Interval, MemoryQueryResult, Metadata, Notification,
+    QueryResult, StatementMetadata,
+};
 use async_trait::async_trait;
 use bit_vec::BitVec;
+use bytes::Bytes;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use file_type::FileType;
+use futures_util::stream::BoxStream;
+use futures_util::{SinkExt, StreamExt};
 use postgresql_embedded::{PostgreSQL, Settings, Status, VersionReq};
 use sqlparser::ast::Statement;
 use sqlparser::dialect::{Dialect, PostgreSqlDialect};
 use std::collections::HashMap;
+use std::mem;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::string::ToString;
-use std::time::SystemTime;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::{self, UnboundedSender};
 use tokio_postgres::types::{FromSql, Type};
-use tokio_postgres::{Client, Column, NoTls, Row};
+use tokio_postgres::{AsyncMessage, Client, Column, NoTls, Row};
 use tracing::debug;
 use url::Url;
 
@@ -49,6 +59,10 @@ pub(crate) struct Connection {
     url: String,
     postgresql: Option<PostgreSQL>,
     client: Client,
+    /// Notices reported by the server, e.g. with `RAISE NOTICE`
+    notices: Arc<Mutex<Vec<String>>>,
+    /// Sender for the notifications of the current subscription, e.g. with `LISTEN`
+    notifications: Arc<Mutex<Option<UnboundedSender<Notification>>>>,
 }
 
 impl Connection {
@@ -72,8 +86,8 @@ impl Connection {
             if let Some(config_dir) = query_parameters.get("installation_dir") {
                 settings.installation_dir = PathBuf::from(config_dir);
             }
-            if let Some(password) = password {
-                settings.password = password;
+            if let Some(password) = &password {
+                settings.password.clone_from(password);
             }
 
             let mut postgresql = PostgreSQL::new(settings);
@@ -91,16 +105,49 @@ impl Connection {
             None
         };
 
-        let (client, connection) = tokio_postgres::connect(database_url.as_str(), NoTls).await?;
+        let mut config = tokio_postgres::Config::from_str(database_url.as_str())?;
+        if let Some(password) = password {
+            config.password(password);
+        }
+        let (client, mut connection) = config.connect(NoTls).await?;
+        let notices = Arc::new(Mutex::new(Vec::new()));
+        let connection_notices = Arc::clone(&notices);
+        let notifications: Arc<Mutex<Option<UnboundedSender<Notification>>>> =
+            Arc::new(Mutex::new(None));
+        let connection_notifications = Arc::clone(&notifications);
         tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                eprintln!("connection error: {e}");
+            let mut messages = futures_util::stream::poll_fn(|cx| connection.poll_message(cx));
+            while let Some(message) = messages.next().await {
+                match message {
+                    Ok(AsyncMessage::Notice(notice)) => {
+                        if let Ok(mut notices) = connection_notices.lock() {
+                            notices.push(format!("{}: {}", notice.severity(), notice.message()));
+                        }
+                    }
+                    Ok(AsyncMessage::Notification(notification)) => {
+                        if let Ok(sender) = connection_notifications.lock() {
+                            if let Some(sender) = sender.as_ref() {
+                                let _ = sender.send(Notification {
+                                    channel: notification.channel().to_string(),
+                                    payload: notification.payload().to_string(),
+                                });
+                            }
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        eprintln!("connection error: {e}");
+                        break;
+                    }
+                }
             }
         });
         let connection = Connection {
             url,
             postgresql,
             client,
+            notices,
+            notifications,
         };
 
         Ok(connection)
@@ -114,6 +161,7 @@ impl crate::Connection for Connection {
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
+        self.take_notices();
         let rows = self.client.execute(sql, &[]).await?;
         Ok(rows)
     }
@@ -123,12 +171,17 @@ impl crate::Connection for Connection {
     }
 
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        self.take_notices();
         let statement = self.client.prepare(sql).await?;
         let query_columns = statement.columns();
         let columns: Vec<String> = query_columns
             .iter()
             .map(|column| column.name().to_string())
             .collect();
+        let columns_metadata: Vec<ColumnMetadata> = query_columns
+            .iter()
+            .map(|column| ColumnMetadata::new(column.name()).with_data_type(column.type_().name()))
+            .collect();
 
         let query_rows = self.client.query(sql, &[]).await?;
         let mut rows = Vec::new();
@@ -141,7 +194,8 @@ impl crate::Connection for Connection {
             rows.push(row);
         }
 
-        let query_result = MemoryQueryResult::new(columns, rows);
+        let query_result =
+            MemoryQueryResult::new(columns, rows).with_columns_metadata(columns_metadata);
         Ok(Box::new(query_result))
     }
 
@@ -157,10 +211,36 @@ impl crate::Connection for Connection {
         Ok(())
     }
 
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        let statement = format!("SET statement_timeout = {}", timeout.as_millis());
+        self.client.execute(statement.as_str(), &[]).await?;
+        Ok(true)
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        self.take_notices()
+    }
+
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        if read_only {
+            Some("SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY")
+        } else {
+            Some("SET SESSION CHARACTERISTICS AS TRANSACTION READ WRITE")
+        }
+    }
+
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        Some("BEGIN")
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(PostgreSqlDialect {})
     }
 
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
+
     fn match_statement(&self, statement: &Statement) -> StatementMetadata {
         let default = self.default_match_statement(statement);
         match default {
@@ -175,7 +255,66 @@ impl crate::Connection for Connection {
     }
 }
 
+#[async_trait]
+impl crate::Notifications for Connection {
+    async fn subscribe(
+        &mut self,
+        channel: &str,
+    ) -> Result<BoxStream<'static, Result<Notification>>> {
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        if let Ok(mut notifications) = self.notifications.lock() {
+            *notifications = Some(sender);
+        }
+        let statement = format!("LISTEN \"{}\"", channel.replace('"', "\"\""));
+        self.client.batch_execute(statement.as_str()).await?;
+
+        // Channels from earlier subscriptions are still listened to by the session
+        let channel = channel.to_string();
+        let notifications = futures_util::stream::poll_fn(move |cx| receiver.poll_recv(cx))
+            .filter(move |notification| {
+                futures_util::future::ready(notification.channel == channel)
+            })
+            .map(Ok);
+        Ok(notifications.boxed())
+    }
+}
+
+#[async_trait]
+impl crate::BulkCopy for Connection {
+    async fn copy_in(
+        &mut self,
+        sql: &str,
+        mut data: BoxStream<'static, Result<Vec<u8>>>,
+    ) -> Result<u64> {
+        // The copy is aborted if the sink is dropped before it is finished
+        let mut sink = Box::pin(self.client.copy_in::<_, Bytes>(sql).await?);
+        while let Some(chunk) = data.next().await {
+            sink.send(Bytes::from(chunk?)).await?;
+        }
+        let rows = sink.as_mut().finish().await?;
+        Ok(rows)
+    }
+}
+
+impl crate::Capabilities for Connection {
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn crate::BulkCopy> {
+        Some(self)
+    }
+
+    fn as_notifications(&mut self) -> Option<&mut dyn crate::Notifications> {
+        Some(self)
+    }
+}
+
 impl Connection {
+    /// Take the notices reported by the server since they were last taken
+    fn take_notices(&self) -> Vec<String> {
+        self.notices
+            .lock()
+            .map(|mut notices| mem::take(&mut *notices))
+            .unwrap_or_default()
+    }
+
     pub(crate) fn convert_to_value(
         row: &Row,
         column: &Column,
@@ -204,6 +343,8 @@ impl Connection {
             Type::FLOAT4_ARRAY => Self::get_array(row, column_index, |v: f32| Value::F32(v))?,
             Type::FLOAT8 => Self::get_single(row, column_index, |v: f64| Value::F64(v))?,
             Type::FLOAT8_ARRAY => Self::get_array(row, column_index, |v: f64| Value::F64(v))?,
+            Type::NUMERIC => Self::get_single(row, column_index, Value::Decimal)?,
+            Type::NUMERIC_ARRAY => Self::get_array(row, column_index, Value::Decimal)?,
             Type::TEXT | Type::VARCHAR | Type::CHAR | Type::BPCHAR | Type::NAME => {
                 Self::get_single(row, column_index, |v: String| Value::String(v))?
             }
@@ -240,6 +381,8 @@ impl Connection {
                     None => Value::Null,
                 }
             }
+            Type::INTERVAL => Self::get_single(row, column_index, PgInterval::to_value)?,
+            Type::INTERVAL_ARRAY => Self::get_array(row, column_index, PgInterval::to_value)?,
             Type::OID => Self::get_single(row, column_index, |v: u32| Value::U32(v))?,
             Type::OID_ARRAY => Self::get_array(row, column_index, |v: u32| Value::U32(v))?,
             Type::VOID => Value::Null, // pg_sleep() returns void
@@ -293,10 +436,47 @@ impl Connection {
     }
 }
 
+/// `PostgreSQL` interval; the binary format is the microseconds, days and months
+#[derive(Clone, Copy, Debug)]
+struct PgInterval {
+    months: i32,
+    days: i32,
+    microseconds: i64,
+}
+
+impl<'a> FromSql<'a> for PgInterval {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> std::result::Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        let microseconds = raw.get(0..8).ok_or("invalid interval")?;
+        let days = raw.get(8..12).ok_or("invalid interval")?;
+        let months = raw.get(12..16).ok_or("invalid interval")?;
+        Ok(PgInterval {
+            months: i32::from_be_bytes(months.try_into()?),
+            days: i32::from_be_bytes(days.try_into()?),
+            microseconds: i64::from_be_bytes(microseconds.try_into()?),
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::INTERVAL
+    }
+}
+
+impl PgInterval {
+    fn to_value(self) -> Value {
+        let nanoseconds = self.microseconds.saturating_mul(1_000);
+        Value::Interval(Interval::new(self.months, self.days, nanoseconds))
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::{DriverManager, Value};
+    use crate::{DriverManager, Interval, Notification, Value};
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use futures_util::StreamExt;
+    use rust_decimal::Decimal;
     use serde_json::json;
 
     const DATABASE_URL: &str = "postgres://?embedded=true";
@@ -342,6 +522,91 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_messages() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let _ = connection
+            .execute("DO $$ BEGIN RAISE NOTICE 'hello'; RAISE WARNING 'careful'; END $$")
+            .await?;
+        assert_eq!(
+            connection.messages(),
+            vec!["NOTICE: hello".to_string(), "WARNING: careful".to_string()]
+        );
+        assert!(connection.messages().is_empty());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let mut notifications = connection
+            .as_notifications()
+            .expect("notifications")
+            .subscribe("events")
+            .await?;
+        let _ = connection.execute("NOTIFY events, 'created'").await?;
+        let notification = notifications
+            .next()
+            .await
+            .expect("expected a notification")?;
+        assert_eq!(
+            notification,
+            Notification {
+                channel: "events".to_string(),
+                payload: "created".to_string(),
+            }
+        );
+
+        drop(notifications);
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_in() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let _ = connection
+            .execute("CREATE TABLE person (id INTEGER, name VARCHAR(20))")
+            .await?;
+        let data = futures_util::stream::iter(vec![
+            Ok(b"id,name\n1,foo\n".to_vec()),
+            Ok(b"2,\n".to_vec()),
+        ]);
+        let rows = connection
+            .as_bulk_copy()
+            .expect("bulk copy")
+            .copy_in(
+                "COPY person FROM STDIN WITH (FORMAT csv, HEADER true)",
+                Box::pin(data),
+            )
+            .await?;
+        assert_eq!(rows, 2);
+
+        let mut query_result = connection
+            .query("SELECT id, name FROM person ORDER BY id")
+            .await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I32(1), Value::String("foo".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I32(2), Value::Null])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
+
     async fn test_data_type(sql: &str) -> anyhow::Result<Option<Value>> {
         let driver_manager = DriverManager::default();
         let mut connection = driver_manager.connect(DATABASE_URL).await?;
@@ -519,6 +784,22 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_data_type_decimal() -> anyhow::Result<()> {
+        let result = test_data_type("SELECT 1.234::NUMERIC").await?;
+        let value = result.expect("value is None");
+        assert_eq!(value, Value::Decimal(Decimal::new(1234, 3)));
+
+        let result = test_data_type("SELECT ARRAY[0,1.234]::NUMERIC[]").await?;
+        assert!(result.is_some());
+        if let Some(Value::Array(value)) = result {
+            assert_eq!(value.len(), 2);
+            assert_eq!(value[0], Value::Decimal(Decimal::new(0, 0)));
+            assert_eq!(value[1], Value::Decimal(Decimal::new(1234, 3)));
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_data_type_date() -> anyhow::Result<()> {
         let result = test_data_type("SELECT CAST('1983-01-01' as date)").await?;
@@ -554,6 +835,29 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_data_type_interval() -> anyhow::Result<()> {
+        let result =
+            test_data_type("SELECT CAST('1 year 2 months 3 days 04:05:06.5' as interval)").await?;
+        let value = result.expect("value is None");
+        assert_eq!(
+            value,
+            Value::Interval(Interval::new(14, 3, 14_706_500_000_000))
+        );
+
+        let result = test_data_type("SELECT ARRAY['1 day', '-1 hour']::interval[]").await?;
+        assert!(result.is_some());
+        if let Some(Value::Array(value)) = result {
+            assert_eq!(value.len(), 2);
+            assert_eq!(value[0], Value::Interval(Interval::new(0, 1, 0)));
+            assert_eq!(
+                value[1],
+                Value::Interval(Interval::new(0, 0, -3_600_000_000_000))
+            );
+        }
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_data_type_json() -> anyhow::Result<()> {
         let result = test_data_type(r#"SELECT CAST('{"key": "value"}' as json)"#).await?;
@@ -0,0 +1,392 @@
+use crate::error::Result;
+use crate::Error::ConversionError;
+use crate::{Interval, Value};
+use chrono::{NaiveDate, NaiveTime, TimeDelta};
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
+use sqlx::postgres::{PgTypeInfo, PgTypeKind};
+use sqlx::TypeInfo;
+
+/// Decode the binary representation of a value using the type information that `PostgreSQL`
+/// provides for the column; enums are decoded as strings, composites as maps keyed by field name
+/// and domains as their base type.
+pub(crate) fn decode(type_info: &PgTypeInfo, bytes: &[u8]) -> Result<Value> {
+    match type_info.kind() {
+        PgTypeKind::Enum(_) => decode_simple("TEXT", bytes),
+        PgTypeKind::Composite(fields) => decode_composite(fields, bytes),
+        PgTypeKind::Domain(base_type) => decode(base_type, bytes),
+        PgTypeKind::Array(element_type) => decode_array(element_type, bytes),
+        PgTypeKind::Simple => decode_simple(type_info.name(), bytes),
+        _ => Err(ConversionError(format!(
+            "unsupported type: {}",
+            type_info.name()
+        ))),
+    }
+}
+
+/// Decode a composite; the binary format is the number of fields followed by the type, length
+/// and value of each field.
+fn decode_composite(fields: &[(String, PgTypeInfo)], bytes: &[u8]) -> Result<Value> {
+    let mut reader = Reader::new(bytes);
+    let count = reader.read_i32()?;
+    let mut values = IndexMap::new();
+
+    for index in 0..usize::try_from(count)? {
+        let (name, type_info) = fields
+            .get(index)
+            .ok_or_else(|| ConversionError(format!("unexpected composite field {index}")))?;
+        let _oid = reader.read_u32()?;
+        let value = match reader.read_value()? {
+            Some(bytes) => decode(type_info, bytes)?,
+            None => Value::Null,
+        };
+        values.insert(Value::String(name.clone()), value);
+    }
+
+    Ok(Value::Map(values))
+}
+
+/// Decode a one dimensional array; the binary format is the number of dimensions, a null flag,
+/// the element type, the length and lower bound of each dimension and the elements.
+fn decode_array(element_type: &PgTypeInfo, bytes: &[u8]) -> Result<Value> {
+    let mut reader = Reader::new(bytes);
+    let dimensions = reader.read_i32()?;
+    let _has_nulls = reader.read_i32()?;
+    let _oid = reader.read_u32()?;
+    if dimensions == 0 {
+        return Ok(Value::Array(Vec::new()));
+    }
+    if dimensions != 1 {
+        return Err(ConversionError(format!(
+            "unsupported array dimensions: {dimensions}"
+        )));
+    }
+    let length = reader.read_i32()?;
+    let _lower_bound = reader.read_i32()?;
+
+    let mut values = Vec::new();
+    for _ in 0..length {
+        let value = match reader.read_value()? {
+            Some(bytes) => decode(element_type, bytes)?,
+            None => Value::Null,
+        };
+        values.push(value);
+    }
+    Ok(Value::Array(values))
+}
+
+/// Decode the binary representation of a built-in type; infinite dates and timestamps are decoded
+/// as the strings `infinity` and `-infinity`, as `PostgreSQL` displays them.
+fn decode_simple(type_name: &str, bytes: &[u8]) -> Result<Value> {
+    let mut reader = Reader::new(bytes);
+    let value = match type_name {
+        "BOOL" => Value::Bool(reader.read_bytes(1)?[0] != 0),
+        "INT2" => Value::I16(i16::from_be_bytes(reader.read_array()?)),
+        "INT4" => Value::I32(reader.read_i32()?),
+        "INT8" => Value::I64(reader.read_i64()?),
+        "OID" => Value::U32(reader.read_u32()?),
+        "FLOAT4" => Value::F32(f32::from_be_bytes(reader.read_array()?)),
+        "FLOAT8" => Value::F64(f64::from_be_bytes(reader.read_array()?)),
+        "NUMERIC" => Value::Decimal(decode_numeric(&mut reader)?),
+        "TEXT" | "VARCHAR" | "BPCHAR" | "NAME" | "\"CHAR\"" | "CITEXT" => {
+            Value::String(utf8(bytes)?)
+        }
+        "JSON" => json(bytes)?,
+        // the first byte is the jsonb format version
+        "JSONB" => json(bytes.get(1..).unwrap_or_default())?,
+        "BYTEA" => Value::Bytes(bytes.to_vec()),
+        "UUID" => Value::Uuid(
+            uuid::Uuid::from_slice(bytes)
+                .map_err(|error| ConversionError(format!("invalid uuid value: {error}")))?,
+        ),
+        "DATE" => match reader.read_i32()? {
+            i32::MAX => Value::String("infinity".to_string()),
+            i32::MIN => Value::String("-infinity".to_string()),
+            days => Value::Date(
+                epoch_date()
+                    .checked_add_signed(TimeDelta::days(i64::from(days)))
+                    .ok_or_else(|| out_of_range(type_name))?,
+            ),
+        },
+        "TIME" => {
+            let microseconds = TimeDelta::microseconds(reader.read_i64()?);
+            Value::Time(NaiveTime::MIN + microseconds)
+        }
+        "TIMESTAMP" | "TIMESTAMPTZ" => match reader.read_i64()? {
+            i64::MAX => Value::String("infinity".to_string()),
+            i64::MIN => Value::String("-infinity".to_string()),
+            microseconds => Value::DateTime(
+                epoch_date()
+                    .and_time(NaiveTime::MIN)
+                    .checked_add_signed(TimeDelta::microseconds(microseconds))
+                    .ok_or_else(|| out_of_range(type_name))?,
+            ),
+        },
+        "INTERVAL" => {
+            let microseconds = reader.read_i64()?;
+            let days = reader.read_i32()?;
+            let months = reader.read_i32()?;
+            Value::Interval(Interval::new(
+                months,
+                days,
+                microseconds.saturating_mul(1_000),
+            ))
+        }
+        _ => {
+            return Err(ConversionError(format!("unsupported type: {type_name}")));
+        }
+    };
+    Ok(value)
+}
+
+/// Decode a numeric; the binary format is the number of base 10,000 digits, the weight of the
+/// first digit, the sign, the display scale and the digits.
+fn decode_numeric(reader: &mut Reader) -> Result<Decimal> {
+    let digits = i16::from_be_bytes(reader.read_array()?);
+    let weight = i16::from_be_bytes(reader.read_array()?);
+    let sign = u16::from_be_bytes(reader.read_array()?);
+    let scale = u16::from_be_bytes(reader.read_array()?);
+    let overflow = || ConversionError("numeric value out of range".to_string());
+
+    if sign == 0xC000 {
+        return Err(ConversionError("numeric NaN is not supported".to_string()));
+    }
+
+    let mut mantissa: i128 = 0;
+    for _ in 0..digits {
+        let digit = i16::from_be_bytes(reader.read_array()?);
+        mantissa = mantissa
+            .checked_mul(10_000)
+            .and_then(|mantissa| mantissa.checked_add(i128::from(digit)))
+            .ok_or_else(overflow)?;
+    }
+
+    let exponent = 4 * (i32::from(weight) - i32::from(digits) + 1);
+    let mut decimal = if exponent >= 0 {
+        let multiplier = 10_i128
+            .checked_pow(exponent.unsigned_abs())
+            .ok_or_else(overflow)?;
+        let mantissa = mantissa.checked_mul(multiplier).ok_or_else(overflow)?;
+        Decimal::try_from_i128_with_scale(mantissa, 0).map_err(|_| overflow())?
+    } else {
+        Decimal::try_from_i128_with_scale(mantissa, exponent.unsigned_abs())
+            .map_err(|_| overflow())?
+    };
+    decimal.rescale(u32::from(scale));
+    if sign == 0x4000 {
+        decimal.set_sign_negative(true);
+    }
+    Ok(decimal)
+}
+
+/// Error for a date or timestamp that is outside the range of the supported dates
+fn out_of_range(type_name: &str) -> crate::Error {
+    ConversionError(format!("{type_name} value out of range"))
+}
+
+/// The `PostgreSQL` epoch used for dates and timestamps
+fn epoch_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default()
+}
+
+fn utf8(bytes: &[u8]) -> Result<String> {
+    String::from_utf8(bytes.to_vec()).map_err(|error| ConversionError(error.to_string()))
+}
+
+fn json(bytes: &[u8]) -> Result<Value> {
+    let value = serde_json::from_slice(bytes)
+        .map_err(|error| ConversionError(format!("invalid json value: {error}")))?;
+    Ok(Value::Json(value))
+}
+
+/// Reader for big-endian binary values
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        let end = self.position + length;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| ConversionError("unexpected end of value".to_string()))?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self.read_bytes(N)?;
+        bytes
+            .try_into()
+            .map_err(|_| ConversionError("unexpected end of value".to_string()))
+    }
+
+    fn read_i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.read_array()?))
+    }
+
+    fn read_i64(&mut self) -> Result<i64> {
+        Ok(i64::from_be_bytes(self.read_array()?))
+    }
+
+    /// Read a length prefixed value; a length of -1 is a null value
+    fn read_value(&mut self) -> Result<Option<&'a [u8]>> {
+        let length = self.read_i32()?;
+        if length < 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.read_bytes(usize::try_from(length)?)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sqlx::{Postgres, Type};
+
+    fn field(bytes: &mut Vec<u8>, oid: u32, value: Option<&[u8]>) {
+        bytes.extend_from_slice(&oid.to_be_bytes());
+        match value {
+            Some(value) => {
+                let length = i32::try_from(value.len()).expect("length");
+                bytes.extend_from_slice(&length.to_be_bytes());
+                bytes.extend_from_slice(value);
+            }
+            None => bytes.extend_from_slice(&(-1_i32).to_be_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_decode_composite() -> Result<()> {
+        let fields = vec![
+            ("id".to_string(), <i32 as Type<Postgres>>::type_info()),
+            ("name".to_string(), <String as Type<Postgres>>::type_info()),
+            ("active".to_string(), <bool as Type<Postgres>>::type_info()),
+        ];
+        let mut bytes = 3_i32.to_be_bytes().to_vec();
+        field(&mut bytes, 23, Some(&42_i32.to_be_bytes()));
+        field(&mut bytes, 25, Some(b"foo"));
+        field(&mut bytes, 16, None);
+
+        let value = decode_composite(&fields, &bytes)?;
+
+        let mut expected = IndexMap::new();
+        expected.insert(Value::String("id".to_string()), Value::I32(42));
+        expected.insert(
+            Value::String("name".to_string()),
+            Value::String("foo".to_string()),
+        );
+        expected.insert(Value::String("active".to_string()), Value::Null);
+        assert_eq!(value, Value::Map(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_composite_invalid() {
+        let fields = vec![("id".to_string(), <i32 as Type<Postgres>>::type_info())];
+        let bytes = 1_i32.to_be_bytes();
+        assert!(decode_composite(&fields, &bytes).is_err());
+    }
+
+    #[test]
+    fn test_decode_array() -> Result<()> {
+        let mut bytes = Vec::new();
+        for value in [1_i32, 0, 25, 2, 1] {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        for value in [b"a".as_slice(), b"b".as_slice()] {
+            bytes.extend_from_slice(&1_i32.to_be_bytes());
+            bytes.extend_from_slice(value);
+        }
+
+        let value = decode_array(&<String as Type<Postgres>>::type_info(), &bytes)?;
+        assert_eq!(
+            value,
+            Value::Array(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string())
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_simple() -> Result<()> {
+        assert_eq!(decode_simple("BOOL", &[1])?, Value::Bool(true));
+        assert_eq!(
+            decode_simple("INT8", &(-7_i64).to_be_bytes())?,
+            Value::I64(-7)
+        );
+        assert_eq!(
+            decode_simple("FLOAT8", &1.5_f64.to_be_bytes())?,
+            Value::F64(1.5)
+        );
+        assert_eq!(
+            decode_simple("DATE", &1_i32.to_be_bytes())?,
+            Value::Date(NaiveDate::from_ymd_opt(2000, 1, 2).expect("invalid date"))
+        );
+        assert_eq!(
+            decode_simple("JSONB", b"\x01{\"a\":1}")?,
+            Value::Json(serde_json::json!({"a": 1}))
+        );
+        assert!(decode_simple("POINT", &[]).is_err());
+        assert!(decode_simple("INT4", &[0, 1]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_infinity() -> Result<()> {
+        let infinity = Value::String("infinity".to_string());
+        let negative_infinity = Value::String("-infinity".to_string());
+        assert_eq!(decode_simple("DATE", &i32::MAX.to_be_bytes())?, infinity);
+        assert_eq!(
+            decode_simple("DATE", &i32::MIN.to_be_bytes())?,
+            negative_infinity
+        );
+        assert_eq!(
+            decode_simple("TIMESTAMP", &i64::MAX.to_be_bytes())?,
+            infinity
+        );
+        assert_eq!(
+            decode_simple("TIMESTAMPTZ", &i64::MIN.to_be_bytes())?,
+            negative_infinity
+        );
+        assert!(decode_simple("DATE", &(i32::MAX - 1).to_be_bytes()).is_err());
+        assert!(decode_simple("TIMESTAMP", &(i64::MAX - 1).to_be_bytes()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_numeric() -> Result<()> {
+        // -12345.678: digits [1, 2345, 6780], weight 1, negative, scale 3
+        let mut bytes = Vec::new();
+        for value in [3_u16, 1, 0x4000, 3, 1, 2345, 6780] {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        assert_eq!(
+            decode_simple("NUMERIC", &bytes)?,
+            Value::Decimal(Decimal::new(-12_345_678, 3))
+        );
+
+        // 0.5: digits [5000], weight -1, scale 1
+        let mut bytes = Vec::new();
+        for value in [1_u16, 0xFFFF, 0, 1, 5000] {
+            bytes.extend_from_slice(&value.to_be_bytes());
+        }
+        assert_eq!(
+            decode_simple("NUMERIC", &bytes)?,
+            Value::Decimal(Decimal::new(5, 1))
+        );
+        Ok(())
+    }
+}
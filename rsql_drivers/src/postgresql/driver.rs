@@ -1,26 +1,39 @@
+use crate::connect_options::connect_timeout;
+use crate::connection::limit_statement;
 use crate::error::Result;
-use crate::postgresql::metadata;
+use crate::messages::capture_messages;
+use crate::postgresql::{custom, metadata};
 use crate::value::Value;
-use crate::Error::UnsupportedColumnType;
-use crate::{Error, MemoryQueryResult, Metadata, QueryResult, StatementMetadata};
+use crate::Error::{InvalidUrl, UnsupportedColumnType};
+use crate::{
+    ColumnMetadata, ConnectOptions, Error, Interval, MemoryQueryResult, Metadata, Notification,
+    QueryResult, StatementMetadata, TlsMode,
+};
 use async_trait::async_trait;
 use bit_vec::BitVec;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use file_type::FileType;
+use futures_util::stream::BoxStream;
+use futures_util::StreamExt;
 use postgresql_embedded::{PostgreSQL, Settings, Status, VersionReq};
 use sqlparser::ast::Statement;
 use sqlparser::dialect::{Dialect, PostgreSqlDialect};
-use sqlx::postgres::types::Oid;
-use sqlx::postgres::{PgColumn, PgConnectOptions, PgRow};
-use sqlx::{Column, ColumnIndex, Decode, PgPool, Row, Type};
-use std::collections::HashMap;
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::types::{Oid, PgInterval};
+use sqlx::postgres::{PgColumn, PgConnectOptions, PgListener, PgRow, PgSslMode};
+use sqlx::types::ipnetwork::IpNetwork;
+use sqlx::types::mac_address::MacAddress;
+use sqlx::{Column, ColumnIndex, Decode, PgPool, Postgres, Row, Type, TypeInfo};
+use std::mem;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::string::ToString;
-use tracing::debug;
-use url::Url;
+use std::time::Duration;
+use tracing::{debug, Level};
 
 const POSTGRESQL_EMBEDDED_VERSION: &str = "=17.2.0";
+/// Target of the events `SQLx` logs for the notices reported by the server
+const NOTICE_TARGET: &str = "sqlx::postgres::notice";
 
 #[derive(Debug)]
 pub struct Driver;
@@ -36,7 +49,15 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url, password).await?;
+        let options = ConnectOptions::from_connection_url(&url, password)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
@@ -50,31 +71,34 @@ pub(crate) struct Connection {
     url: String,
     postgresql: Option<PostgreSQL>,
     pool: PgPool,
+    timeout: Option<Duration>,
+    read_only: Option<bool>,
+    /// Notices reported by the server for the last statement, e.g. with `RAISE NOTICE`
+    messages: Vec<String>,
 }
 
 impl Connection {
-    pub(crate) async fn new(url: String, password: Option<String>) -> Result<Connection> {
-        let parsed_url = Url::parse(url.as_str())?;
-        let query_parameters: HashMap<String, String> =
-            parsed_url.query_pairs().into_owned().collect();
-        let embedded = query_parameters
+    /// Connect with the options; an embedded server is started when the `embedded` parameter is
+    /// `true`, and the URL is used to configure the embedded server
+    pub(crate) async fn new(url: String, options: &ConnectOptions) -> Result<Connection> {
+        let parameters = options.parameters();
+        let embedded = parameters
             .get("embedded")
             .is_some_and(|value| value == "true");
-        let mut database_url = url.to_string();
 
-        let postgresql = if embedded {
+        let (postgresql, options) = if embedded {
             let mut settings = Settings::from_url(url.clone())?;
 
-            if !query_parameters.contains_key("version") {
+            if !parameters.contains_key("version") {
                 let version = VersionReq::from_str(POSTGRESQL_EMBEDDED_VERSION)
                     .map_err(|error| Error::IoError(error.into()))?;
                 settings.version = version;
             }
-            if let Some(config_dir) = query_parameters.get("installation_dir") {
+            if let Some(config_dir) = parameters.get("installation_dir") {
                 settings.installation_dir = PathBuf::from(config_dir);
             }
-            if let Some(password) = password {
-                settings.password = password;
+            if let Some(password) = options.password() {
+                settings.password = password.to_string();
             }
 
             let mut postgresql = PostgreSQL::new(settings);
@@ -86,22 +110,48 @@ impl Connection {
             let database_name = "embedded";
             postgresql.create_database(database_name).await?;
             let settings = postgresql.settings();
-            database_url = settings.url(database_name);
-            Some(postgresql)
+            let options = PgConnectOptions::new()
+                .host(&settings.host)
+                .port(settings.port)
+                .username(&settings.username)
+                .password(&settings.password)
+                .database(database_name);
+            (Some(postgresql), options)
         } else {
-            None
+            (None, connect_options(options)?)
         };
 
-        let options = PgConnectOptions::from_str(database_url.as_str())?;
         let pool = PgPool::connect_with(options).await?;
         let connection = Connection {
             url,
             postgresql,
             pool,
+            timeout: None,
+            read_only: None,
+            messages: Vec::new(),
         };
 
         Ok(connection)
     }
+
+    /// Get a connection from the pool; the statement timeout and read-only mode are applied to the
+    /// connection since session settings are not shared by the connections of the pool
+    async fn acquire(&self) -> Result<PoolConnection<Postgres>> {
+        let mut connection = self.pool.acquire().await?;
+        if let Some(timeout) = self.timeout {
+            let statement = format!("SET statement_timeout = {}", timeout.as_millis());
+            sqlx::query(&statement).execute(&mut *connection).await?;
+        }
+        if let Some(read_only) = self.read_only {
+            let statement = if read_only {
+                "SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY"
+            } else {
+                "SET SESSION CHARACTERISTICS AS TRANSACTION READ WRITE"
+            };
+            sqlx::query(statement).execute(&mut *connection).await?;
+        }
+        Ok(connection)
+    }
 }
 
 #[async_trait]
@@ -111,7 +161,11 @@ impl crate::Connection for Connection {
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
-        let rows = sqlx::query(sql).execute(&self.pool).await?.rows_affected();
+        let mut connection = self.acquire().await?;
+        let (result, messages) =
+            capture_messages(NOTICE_TARGET, sqlx::query(sql).execute(&mut *connection)).await;
+        self.messages = Self::notices(messages);
+        let rows = result?.rows_affected();
         Ok(rows)
     }
 
@@ -120,7 +174,11 @@ impl crate::Connection for Connection {
     }
 
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
-        let query_rows = sqlx::query(sql).fetch_all(&self.pool).await?;
+        let mut connection = self.acquire().await?;
+        let (query_rows, messages) =
+            capture_messages(NOTICE_TARGET, sqlx::query(sql).fetch_all(&mut *connection)).await;
+        self.messages = Self::notices(messages);
+        let query_rows = query_rows?;
         let columns: Vec<String> = query_rows
             .first()
             .map(|row| {
@@ -130,6 +188,18 @@ impl crate::Connection for Connection {
                     .collect()
             })
             .unwrap_or_default();
+        let columns_metadata: Vec<ColumnMetadata> = query_rows
+            .first()
+            .map(|row| {
+                row.columns()
+                    .iter()
+                    .map(|column| {
+                        ColumnMetadata::new(column.name())
+                            .with_data_type(column.type_info().name())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let mut rows = Vec::new();
         for row in query_rows {
@@ -141,7 +211,8 @@ impl crate::Connection for Connection {
             rows.push(row_data);
         }
 
-        let query_result = MemoryQueryResult::new(columns, rows);
+        let query_result =
+            MemoryQueryResult::new(columns, rows).with_columns_metadata(columns_metadata);
         Ok(Box::new(query_result))
     }
 
@@ -159,10 +230,28 @@ impl crate::Connection for Connection {
         Ok(())
     }
 
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        self.timeout = Some(timeout);
+        Ok(true)
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        mem::take(&mut self.messages)
+    }
+
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.read_only = Some(read_only);
+        Ok(())
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(PostgreSqlDialect {})
     }
 
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
+
     fn match_statement(&self, statement: &Statement) -> StatementMetadata {
         let default = self.default_match_statement(statement);
         match default {
@@ -177,7 +266,76 @@ impl crate::Connection for Connection {
     }
 }
 
+#[async_trait]
+impl crate::Notifications for Connection {
+    async fn subscribe(
+        &mut self,
+        channel: &str,
+    ) -> Result<BoxStream<'static, Result<Notification>>> {
+        let mut listener = PgListener::connect_with(&self.pool).await?;
+        listener.listen(channel).await?;
+        let notifications = listener.into_stream().map(|notification| {
+            let notification = notification?;
+            Ok(Notification {
+                channel: notification.channel().to_string(),
+                payload: notification.payload().to_string(),
+            })
+        });
+        Ok(notifications.boxed())
+    }
+}
+
+#[async_trait]
+impl crate::BulkCopy for Connection {
+    async fn copy_in(
+        &mut self,
+        sql: &str,
+        mut data: BoxStream<'static, Result<Vec<u8>>>,
+    ) -> Result<u64> {
+        let mut connection = self.acquire().await?;
+        let mut copy = connection.copy_in_raw(sql).await?;
+        while let Some(chunk) = data.next().await {
+            match chunk {
+                Ok(chunk) => {
+                    copy.send(chunk).await?;
+                }
+                Err(error) => {
+                    copy.abort(error.to_string()).await?;
+                    return Err(error);
+                }
+            }
+        }
+        let rows = copy.finish().await?;
+        Ok(rows)
+    }
+}
+
+impl crate::Capabilities for Connection {
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn crate::BulkCopy> {
+        Some(self)
+    }
+
+    fn as_notifications(&mut self) -> Option<&mut dyn crate::Notifications> {
+        Some(self)
+    }
+}
+
 impl Connection {
+    /// Format the notices logged by `SQLx` with the severity reported by the server
+    fn notices(messages: Vec<(Level, String)>) -> Vec<String> {
+        messages
+            .into_iter()
+            .map(|(level, message)| {
+                let severity = if level == Level::WARN {
+                    "WARNING"
+                } else {
+                    "NOTICE"
+                };
+                format!("{severity}: {message}")
+            })
+            .collect()
+    }
+
     #[expect(clippy::too_many_lines)]
     fn convert_to_value(row: &PgRow, column: &PgColumn) -> Result<Value> {
         let column_type = column.type_info();
@@ -246,8 +404,16 @@ impl Connection {
             // "PolygonArray" => Value::Null,
             // "Line" => Value::Null,
             // "LineArray" => Value::Null,
-            // "Cidr" => Value::Null,
-            // "CidrArray" => Value::Null,
+            "Cidr" => Self::get_value(row, column_name, |v: IpNetwork| {
+                Value::String(v.to_string())
+            })?,
+            "CidrArray" => Self::get_value(row, column_name, |v: Vec<IpNetwork>| {
+                Value::Array(
+                    v.into_iter()
+                        .map(|v| Value::String(v.to_string()))
+                        .collect(),
+                )
+            })?,
             "Float4" => Self::get_value(row, column_name, |v: f32| Value::F32(v))?,
             "Float4Array" => Self::get_value(row, column_name, |v: Vec<f32>| {
                 Value::Array(v.into_iter().map(Value::F32).collect())
@@ -259,12 +425,31 @@ impl Connection {
             // "Unknown" => Value::Null,
             // "Circle" => Value::Null,
             // "CircleArray" => Value::Null,
-            // "Macaddr" => Value::Null,
-            // "MacaddrArray" => Value::Null,
-            // "Macaddr8" => Value::Null,
+            "Macaddr" => Self::get_value(row, column_name, |v: MacAddress| {
+                Value::String(v.to_string().to_lowercase())
+            })?,
+            "MacaddrArray" => Self::get_value(row, column_name, |v: Vec<MacAddress>| {
+                Value::Array(
+                    v.into_iter()
+                        .map(|v| Value::String(v.to_string().to_lowercase()))
+                        .collect(),
+                )
+            })?,
+            "Macaddr8" => match row.try_get_unchecked::<Option<&[u8]>, &str>(column_name)? {
+                Some(v) => Value::String(Self::mac_address_string(v)),
+                None => Value::Null,
+            },
             // "Macaddr8Array" => Value::Null,
-            // "Inet" => Value::Null,
-            // "InetArray" => Value::Null,
+            "Inet" => Self::get_value(row, column_name, |v: IpNetwork| {
+                Value::String(Self::inet_string(&v))
+            })?,
+            "InetArray" => Self::get_value(row, column_name, |v: Vec<IpNetwork>| {
+                Value::Array(
+                    v.into_iter()
+                        .map(|v| Value::String(Self::inet_string(&v)))
+                        .collect(),
+                )
+            })?,
             "Date" => Self::get_value(row, column_name, |v: NaiveDate| Value::Date(v))?,
             "DateArray" => Self::get_value(row, column_name, |v: Vec<NaiveDate>| {
                 Value::Array(v.into_iter().map(Value::Date).collect())
@@ -293,8 +478,10 @@ impl Connection {
                     )
                 })?
             }
-            // "Interval" => Value::Null,
-            // "IntervalArray" => Value::Null,
+            "Interval" => Self::get_value(row, column_name, Self::interval_value)?,
+            "IntervalArray" => Self::get_value(row, column_name, |v: Vec<PgInterval>| {
+                Value::Array(v.into_iter().map(Self::interval_value).collect())
+            })?,
             "Bit" | "Varbit" => Self::get_value(row, column_name, |v: BitVec| {
                 Value::String(Self::bit_string(&v))
             })?,
@@ -306,15 +493,11 @@ impl Connection {
                 )
             })?,
             "Numeric" => Self::get_value(row, column_name, |v: rust_decimal::Decimal| {
-                Value::String(v.to_string())
+                Value::Decimal(v)
             })?,
             "NumericArray" => {
                 Self::get_value(row, column_name, |v: Vec<rust_decimal::Decimal>| {
-                    Value::Array(
-                        v.into_iter()
-                            .map(|v| Value::String(v.to_string()))
-                            .collect(),
-                    )
+                    Value::Array(v.into_iter().map(Value::Decimal).collect())
                 })?
             }
             // Some(&"Record"Some(& => Value::Null,
@@ -340,7 +523,10 @@ impl Connection {
             // "Money" => Value::Null,
             // "MoneyArray" => Value::Null,
             "Void" => Value::Null, // pg_sleep() returns void
-            // "Custom" => Value::Null,
+            "Custom" => match row.try_get_unchecked::<Option<&[u8]>, &str>(column_name)? {
+                Some(v) => custom::decode(column.type_info(), v)?,
+                None => Value::Null,
+            },
             // "DeclareWithName" => Value::Null,
             // "DeclareWithOid" => Value::Null,
             _ => {
@@ -365,6 +551,36 @@ impl Connection {
         }
     }
 
+    fn interval_value(
+        PgInterval {
+            months,
+            days,
+            microseconds,
+        }: PgInterval,
+    ) -> Value {
+        let nanoseconds = microseconds.saturating_mul(1_000);
+        Value::Interval(Interval::new(months, days, nanoseconds))
+    }
+
+    /// Format an inet value the same as `PostgreSQL`; the netmask is omitted for a single host
+    fn inet_string(value: &IpNetwork) -> String {
+        let host_prefix = if value.is_ipv4() { 32 } else { 128 };
+        if value.prefix() == host_prefix {
+            value.ip().to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Format the bytes of a MAC address as colon separated lowercase hexadecimal
+    fn mac_address_string(value: &[u8]) -> String {
+        value
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<Vec<String>>()
+            .join(":")
+    }
+
     fn bit_string(value: &BitVec) -> String {
         let bit_string: String = value
             .iter()
@@ -374,15 +590,113 @@ impl Connection {
     }
 }
 
+/// Build the `SQLx` connect options from the options; parameters of the options, e.g. `sslmode`,
+/// take precedence over the TLS mode
+fn connect_options(options: &ConnectOptions) -> Result<PgConnectOptions> {
+    let mut connect_options = PgConnectOptions::new();
+    if let Some(host) = options.host() {
+        connect_options = connect_options.host(host);
+    }
+    if let Some(port) = options.port() {
+        connect_options = connect_options.port(port);
+    }
+    if let Some(username) = options.username() {
+        connect_options = connect_options.username(username);
+    }
+    if let Some(password) = options.password() {
+        connect_options = connect_options.password(password);
+    }
+    if let Some(database) = options.database() {
+        connect_options = connect_options.database(database);
+    }
+    connect_options = match options.tls() {
+        Some(TlsMode::Disable) => connect_options.ssl_mode(PgSslMode::Disable),
+        Some(TlsMode::Require) => connect_options.ssl_mode(PgSslMode::Require),
+        Some(TlsMode::VerifyFull) => connect_options.ssl_mode(PgSslMode::VerifyFull),
+        None => connect_options,
+    };
+
+    for (key, value) in options.parameters() {
+        let invalid = || InvalidUrl(format!("Invalid parameter: {key}={value}"));
+        connect_options = match key.as_str() {
+            "sslmode" => {
+                connect_options.ssl_mode(PgSslMode::from_str(value).map_err(|_| invalid())?)
+            }
+            "sslrootcert" => connect_options.ssl_root_cert(value),
+            "sslcert" => connect_options.ssl_client_cert(value),
+            "sslkey" => connect_options.ssl_client_key(value),
+            "application_name" => connect_options.application_name(value),
+            "statement-cache-capacity" => {
+                connect_options.statement_cache_capacity(value.parse().map_err(|_| invalid())?)
+            }
+            "options" => connect_options.options(server_options(value)),
+            _ => connect_options,
+        };
+    }
+    Ok(connect_options)
+}
+
+/// Parse the run-time parameters of the `options` parameter, e.g. `-c search_path=app`
+fn server_options(value: &str) -> Vec<(String, String)> {
+    let mut settings = Vec::new();
+    let mut words = value.split_whitespace();
+    while let Some(word) = words.next() {
+        let setting = match word {
+            "-c" => words.next(),
+            _ => word.strip_prefix("-c").or_else(|| word.strip_prefix("--")),
+        };
+        if let Some((name, value)) = setting.and_then(|setting| setting.split_once('=')) {
+            settings.push((name.to_string(), value.to_string()));
+        }
+    }
+    settings
+}
+
 #[cfg(test)]
 mod test {
-
-    use crate::{DriverManager, Value};
+    use super::{connect_options, server_options, Connection};
+    use crate::{
+        ConnectOptions, Connection as _, DriverManager, Interval, Notification, TlsMode, Value,
+    };
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use futures_util::StreamExt;
+    use indexmap::IndexMap;
     use serde_json::json;
 
     const DATABASE_URL: &str = "postgresql://?embedded=true";
 
+    #[test]
+    fn test_connect_options() -> anyhow::Result<()> {
+        let url = "postgresql://postgres@db.example.com:5433/app?application_name=rsql";
+        let options = ConnectOptions::from_url(url)?.with_tls(TlsMode::VerifyFull);
+        let pg_options = connect_options(&options)?;
+        assert_eq!(pg_options.get_host(), "db.example.com");
+        assert_eq!(pg_options.get_port(), 5433);
+        assert_eq!(pg_options.get_username(), "postgres");
+        assert_eq!(pg_options.get_database(), Some("app"));
+        assert_eq!(pg_options.get_application_name(), Some("rsql"));
+        assert!(matches!(
+            pg_options.get_ssl_mode(),
+            sqlx::postgres::PgSslMode::VerifyFull
+        ));
+
+        let options = options.with_parameter("sslmode", "foo");
+        assert!(connect_options(&options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_server_options() {
+        assert_eq!(
+            server_options("-c search_path=app --statement_timeout=5s -cwork_mem=4MB"),
+            vec![
+                ("search_path".to_string(), "app".to_string()),
+                ("statement_timeout".to_string(), "5s".to_string()),
+                ("work_mem".to_string(), "4MB".to_string()),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_driver_connect() -> anyhow::Result<()> {
         let driver_manager = DriverManager::default();
@@ -424,6 +738,121 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_only_applies_to_pooled_connections() -> anyhow::Result<()> {
+        let options = ConnectOptions::from_url(DATABASE_URL)?;
+        let mut connection = Connection::new(DATABASE_URL.to_string(), &options).await?;
+        let _ = connection
+            .execute("CREATE TABLE person (id INTEGER)")
+            .await?;
+        connection.set_read_only(true).await?;
+        let _ = connection.query("SELECT id FROM person").await?;
+
+        // Hold a connection of the pool so that the statement runs on a second connection
+        let held = connection.acquire().await?;
+        let result = connection
+            .execute("INSERT INTO person (id) VALUES (1)")
+            .await;
+        assert!(result.is_err());
+        drop(held);
+
+        connection.set_read_only(false).await?;
+        let rows = connection
+            .execute("INSERT INTO person (id) VALUES (1)")
+            .await?;
+        assert_eq!(rows, 1);
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_messages() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let _ = connection
+            .execute("DO $$ BEGIN RAISE NOTICE 'hello'; RAISE WARNING 'careful'; END $$")
+            .await?;
+        assert_eq!(
+            connection.messages(),
+            vec!["NOTICE: hello".to_string(), "WARNING: careful".to_string()]
+        );
+        assert!(connection.messages().is_empty());
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subscribe() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let mut notifications = connection
+            .as_notifications()
+            .expect("notifications")
+            .subscribe("events")
+            .await?;
+        let _ = connection
+            .execute("SELECT pg_notify('events', 'created')")
+            .await?;
+        let notification = notifications
+            .next()
+            .await
+            .expect("expected a notification")?;
+        assert_eq!(
+            notification,
+            Notification {
+                channel: "events".to_string(),
+                payload: "created".to_string(),
+            }
+        );
+
+        drop(notifications);
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_copy_in() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let _ = connection
+            .execute("CREATE TABLE person (id INTEGER, name VARCHAR(20))")
+            .await?;
+        let data = futures_util::stream::iter(vec![
+            Ok(b"id,name\n1,foo\n".to_vec()),
+            Ok(b"2,\n".to_vec()),
+        ]);
+        let rows = connection
+            .as_bulk_copy()
+            .expect("bulk copy")
+            .copy_in(
+                "COPY person FROM STDIN WITH (FORMAT csv, HEADER true)",
+                Box::pin(data),
+            )
+            .await?;
+        assert_eq!(rows, 2);
+
+        let mut query_result = connection
+            .query("SELECT id, name FROM person ORDER BY id")
+            .await?;
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I32(1), Value::String("foo".to_string())])
+        );
+        assert_eq!(
+            query_result.next().await,
+            Some(vec![Value::I32(2), Value::Null])
+        );
+        assert!(query_result.next().await.is_none());
+
+        connection.close().await?;
+        Ok(())
+    }
+
     async fn test_data_type(sql: &str) -> anyhow::Result<Option<Value>> {
         let driver_manager = DriverManager::default();
         let mut connection = driver_manager.connect(DATABASE_URL).await?;
@@ -504,11 +933,11 @@ mod test {
 
         let result = test_data_type("SELECT CAST(1.234 as numeric)").await?;
         let value = result.expect("value is None");
-        assert_eq!(value, Value::String("1.234".to_string()));
+        assert_eq!(value, Value::Decimal(rust_decimal::Decimal::new(1234, 3)));
 
         let result = test_data_type("SELECT CAST(1.234 as decimal)").await?;
         let value = result.expect("value is None");
-        assert_eq!(value, Value::String("1.234".to_string()));
+        assert_eq!(value, Value::Decimal(rust_decimal::Decimal::new(1234, 3)));
         Ok(())
     }
 
@@ -643,6 +1072,115 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_data_type_interval() -> anyhow::Result<()> {
+        let result =
+            test_data_type("SELECT CAST('1 year 2 months 3 days 04:05:06.5' as interval)").await?;
+        let value = result.expect("value is None");
+        assert_eq!(
+            value,
+            Value::Interval(Interval::new(14, 3, 14_706_500_000_000))
+        );
+
+        let result = test_data_type("SELECT ARRAY['1 day', '-1 hour']::interval[]").await?;
+        assert!(result.is_some());
+        if let Some(Value::Array(value)) = result {
+            assert_eq!(value.len(), 2);
+            assert_eq!(value[0], Value::Interval(Interval::new(0, 1, 0)));
+            assert_eq!(
+                value[1],
+                Value::Interval(Interval::new(0, 0, -3_600_000_000_000))
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_data_type_network() -> anyhow::Result<()> {
+        let result = test_data_type("SELECT CAST('192.168.1.5' as inet)").await?;
+        assert_eq!(result, Some(Value::String("192.168.1.5".to_string())));
+
+        let result = test_data_type("SELECT CAST('10.1.0.0/16' as inet)").await?;
+        assert_eq!(result, Some(Value::String("10.1.0.0/16".to_string())));
+
+        let result = test_data_type("SELECT CAST('2001:db8::/32' as cidr)").await?;
+        assert_eq!(result, Some(Value::String("2001:db8::/32".to_string())));
+
+        let result = test_data_type("SELECT CAST('08:00:2B:01:02:03' as macaddr)").await?;
+        assert_eq!(result, Some(Value::String("08:00:2b:01:02:03".to_string())));
+
+        let result = test_data_type("SELECT CAST('08:00:2b:01:02:03:04:05' as macaddr8)").await?;
+        assert_eq!(
+            result,
+            Some(Value::String("08:00:2b:01:02:03:04:05".to_string()))
+        );
+
+        let result = test_data_type("SELECT ARRAY['192.168.1.5', '::1/64']::inet[]").await?;
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![
+                Value::String("192.168.1.5".to_string()),
+                Value::String("::1/64".to_string()),
+            ]))
+        );
+
+        let result = test_data_type("SELECT ARRAY['10.0.0.0/8']::cidr[]").await?;
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![Value::String("10.0.0.0/8".to_string())]))
+        );
+
+        let result = test_data_type("SELECT ARRAY['08:00:2b:01:02:03']::macaddr[]").await?;
+        assert_eq!(
+            result,
+            Some(Value::Array(vec![Value::String(
+                "08:00:2b:01:02:03".to_string()
+            )]))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_data_type_custom() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+        let _ = connection
+            .execute("CREATE TYPE mood AS ENUM ('sad', 'ok', 'happy')")
+            .await?;
+        let _ = connection
+            .execute("CREATE TYPE person AS (name TEXT, age INT4, mood mood)")
+            .await?;
+
+        let mut query_result = connection
+            .query("SELECT 'happy'::mood, ROW('foo', 42, 'ok')::person, ARRAY['sad', 'ok']::mood[]")
+            .await?;
+        let row = query_result.next().await.expect("no row");
+        assert_eq!(row[0], Value::String("happy".to_string()));
+        let mut person = IndexMap::new();
+        person.insert(
+            Value::String("name".to_string()),
+            Value::String("foo".to_string()),
+        );
+        person.insert(Value::String("age".to_string()), Value::I32(42));
+        person.insert(
+            Value::String("mood".to_string()),
+            Value::String("ok".to_string()),
+        );
+        assert_eq!(row[1], Value::Map(person));
+        assert_eq!(
+            row[2],
+            Value::Array(vec![
+                Value::String("sad".to_string()),
+                Value::String("ok".to_string())
+            ])
+        );
+
+        let _ = connection.execute("DROP TYPE person").await?;
+        let _ = connection.execute("DROP TYPE mood").await?;
+        connection.close().await?;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_data_type_json() -> anyhow::Result<()> {
         let result = test_data_type(r#"SELECT CAST('{"key": "value"}' as json)"#).await?;
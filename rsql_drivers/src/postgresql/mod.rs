@@ -1,3 +1,4 @@
+pub(crate) mod custom;
 pub mod driver;
 pub(crate) mod metadata;
 
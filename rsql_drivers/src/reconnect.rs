@@ -0,0 +1,754 @@
+use crate::error::Result;
+use crate::Error::IoError;
+use crate::{
+    BulkCopy, Capabilities, Connection, Driver, Error, Export, HistoricalReads, Metadata,
+    MultipleResults, Notifications, QueryResult, QueryStatistics, Replica, StatementMetadata,
+};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use sqlparser::ast::Statement;
+use sqlparser::dialect::Dialect;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// The maximum delay between reconnect attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Options for reconnecting when the connection to the database is lost
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReconnectOptions {
+    /// The number of attempts to reconnect; 0 disables reconnecting
+    pub attempts: usize,
+    /// The delay before the first attempt; the delay doubles for each subsequent attempt
+    pub backoff: Duration,
+    /// Replay the statements that changed the session settings (`SET` and `USE`) after
+    /// reconnecting
+    pub replay_session: bool,
+}
+
+impl Default for ReconnectOptions {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            backoff: Duration::from_millis(100),
+            replay_session: true,
+        }
+    }
+}
+
+/// Connection that reconnects to the database when the wrapped connection is lost.  Read-only
+/// queries and session statements are retried on the new connection; other statements, including
+/// queries that modify the database such as `INSERT ... RETURNING`, are not retried since they may
+/// have been applied before the connection was lost, and an error is returned so that the
+/// statement can be run again if required.  Transactions that were open when the connection
+/// was lost are rolled back by the database, so nothing is retried while a transaction is open.
+#[derive(Debug)]
+pub(crate) struct ReconnectConnection {
+    connection: Box<dyn Connection>,
+    driver: Arc<dyn Driver>,
+    url: String,
+    password: Option<String>,
+    options: ReconnectOptions,
+    session: Vec<String>,
+    timeout: Option<Duration>,
+    read_only: Option<bool>,
+    as_of: Option<String>,
+    in_transaction: bool,
+    reconnected: bool,
+}
+
+impl ReconnectConnection {
+    pub(crate) fn new(
+        connection: Box<dyn Connection>,
+        driver: Arc<dyn Driver>,
+        url: String,
+        password: Option<String>,
+        options: ReconnectOptions,
+    ) -> Self {
+        Self {
+            connection,
+            driver,
+            url,
+            password,
+            options,
+            session: Vec::new(),
+            timeout: None,
+            read_only: None,
+            as_of: None,
+            in_transaction: false,
+            reconnected: false,
+        }
+    }
+
+    /// Track whether an explicit transaction is open after the statement succeeds
+    fn record_transaction(&mut self, sql: &str) {
+        if let Some(in_transaction) = transaction_state(sql) {
+            self.in_transaction = in_transaction;
+        }
+    }
+
+    /// Reconnect after the connection was lost; an error is returned when a transaction was open,
+    /// since the database rolled it back and statements of the transaction must not be retried
+    async fn recover(&mut self, error: Error) -> Result<()> {
+        self.reconnect().await?;
+        if std::mem::take(&mut self.in_transaction) {
+            return Err(IoError(anyhow!(
+                "connection lost; reconnected but the open transaction was rolled back: {error}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Record the statement if it changes the session settings so that it can be replayed after
+    /// reconnecting
+    fn record_session(&mut self, sql: &str) {
+        if self.options.replay_session && is_session_statement(sql) {
+            self.session.push(sql.to_string());
+        }
+    }
+
+    /// Execute the query with at most `limit` rows (0 returns all the rows), reconnecting when
+    /// the connection was lost; the query is only retried if it is read-only
+    async fn query_with_recovery(
+        &mut self,
+        sql: &str,
+        limit: usize,
+    ) -> Result<Box<dyn QueryResult>> {
+        match self.inner_query(sql, limit).await {
+            Ok(query_result) => {
+                self.record_transaction(sql);
+                Ok(query_result)
+            }
+            Err(error) if self.should_reconnect(&error) => {
+                let message = error.to_string();
+                let read_only = self.connection.is_read_only_sql(sql);
+                self.recover(error).await?;
+                if !read_only {
+                    return Err(IoError(anyhow!(
+                        "connection lost; reconnected but the statement was not retried: {message}"
+                    )));
+                }
+                self.inner_query(sql, limit).await
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Execute the query on the wrapped connection
+    async fn inner_query(&mut self, sql: &str, limit: usize) -> Result<Box<dyn QueryResult>> {
+        match limit {
+            0 => self.connection.query(sql).await,
+            limit => self.connection.query_with_limit(sql, limit).await,
+        }
+    }
+
+    /// Returns true if the error is a lost connection that should be reconnected
+    fn should_reconnect(&self, error: &Error) -> bool {
+        self.options.attempts > 0 && self.connection.is_connection_lost(error)
+    }
+
+    /// Connect to the database again, waiting before each attempt with an exponential backoff,
+    /// and replay the session statements on the new connection
+    async fn reconnect(&mut self) -> Result<()> {
+        let mut backoff = self.options.backoff;
+        let mut last_error = None;
+        for attempt in 1..=self.options.attempts {
+            tokio::time::sleep(backoff).await;
+            match self
+                .driver
+                .connect(self.url.clone(), self.password.clone())
+                .await
+            {
+                Ok(connection) => {
+                    let _ = self.connection.close().await;
+                    self.connection = connection;
+                    for statement in &self.session {
+                        self.connection.execute(statement).await?;
+                    }
+                    if let Some(timeout) = self.timeout {
+                        self.connection.set_timeout(timeout).await?;
+                    }
+                    if let Some(read_only) = self.read_only {
+                        self.connection.set_read_only(read_only).await?;
+                    }
+                    if let (Some(_), Some(historical_reads)) =
+                        (&self.as_of, self.connection.as_historical_reads())
+                    {
+                        historical_reads.set_as_of(self.as_of.clone()).await?;
+                    }
+                    warn!("reconnected to the database after {attempt} attempt(s)");
+                    self.reconnected = true;
+                    return Ok(());
+                }
+                Err(error) => {
+                    debug!("reconnect attempt {attempt} failed: {error}");
+                    last_error = Some(error);
+                }
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+        Err(last_error.unwrap_or_else(|| IoError(anyhow!("unable to reconnect to the database"))))
+    }
+}
+
+/// Returns whether a transaction is open after the statement, or `None` if the statement does not
+/// begin or end a transaction
+#[must_use]
+pub fn transaction_state(sql: &str) -> Option<bool> {
+    let mut keywords = sql
+        .split(|c: char| c.is_whitespace() || c == ';')
+        .filter(|keyword| !keyword.is_empty());
+    let keyword = keywords.next()?.to_ascii_uppercase();
+    let next = keywords.next().map(str::to_ascii_uppercase);
+    match keyword.as_str() {
+        "BEGIN" => Some(true),
+        "START" if next.as_deref() == Some("TRANSACTION") => Some(true),
+        // Rolling back to a savepoint keeps the transaction open
+        "ROLLBACK" if next.as_deref() == Some("TO") => None,
+        "COMMIT" | "END" | "ABORT" | "ROLLBACK" => Some(false),
+        _ => None,
+    }
+}
+
+/// Returns true if the statement changes the session settings
+#[must_use]
+pub fn is_session_statement(sql: &str) -> bool {
+    sql.split_whitespace().next().is_some_and(|keyword| {
+        keyword.eq_ignore_ascii_case("set") || keyword.eq_ignore_ascii_case("use")
+    })
+}
+
+#[async_trait]
+impl Connection for ReconnectConnection {
+    fn url(&self) -> &String {
+        self.connection.url()
+    }
+
+    async fn execute(&mut self, sql: &str) -> Result<u64> {
+        match self.connection.execute(sql).await {
+            Ok(rows) => {
+                self.record_session(sql);
+                self.record_transaction(sql);
+                Ok(rows)
+            }
+            Err(error) if self.should_reconnect(&error) => {
+                let message = error.to_string();
+                self.recover(error).await?;
+                if !is_session_statement(sql) {
+                    return Err(IoError(anyhow!(
+                        "connection lost; reconnected but the statement was not retried: {message}"
+                    )));
+                }
+                let rows = self.connection.execute(sql).await?;
+                self.record_session(sql);
+                Ok(rows)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
+        self.query_with_recovery(sql, 0).await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        self.connection.close().await
+    }
+
+    async fn metadata(&mut self) -> Result<Metadata> {
+        match self.connection.metadata().await {
+            Err(error) if self.should_reconnect(&error) => {
+                self.recover(error).await?;
+                self.connection.metadata().await
+            }
+            result => result,
+        }
+    }
+
+    async fn refresh_metadata(&mut self) -> Result<()> {
+        self.connection.refresh_metadata().await
+    }
+
+    fn statistics(&self) -> Option<QueryStatistics> {
+        self.connection.statistics()
+    }
+
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        self.connection.read_only_statement(read_only)
+    }
+
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.connection.set_read_only(read_only).await?;
+        self.read_only = Some(read_only);
+        Ok(())
+    }
+
+    fn is_read_only_sql(&self, sql: &str) -> bool {
+        self.connection.is_read_only_sql(sql)
+    }
+
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        self.connection.begin_transaction_statement()
+    }
+
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        self.connection.limit_sql(sql, limit)
+    }
+
+    async fn query_with_limit(&mut self, sql: &str, limit: usize) -> Result<Box<dyn QueryResult>> {
+        self.query_with_recovery(sql, limit).await
+    }
+
+    fn is_connection_lost(&self, error: &Error) -> bool {
+        self.connection.is_connection_lost(error)
+    }
+
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        let enforced = self.connection.set_timeout(timeout).await?;
+        self.timeout = Some(timeout);
+        Ok(enforced)
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        self.connection.messages()
+    }
+
+    fn reconnected(&mut self) -> bool {
+        std::mem::take(&mut self.reconnected)
+    }
+
+    fn dialect(&self) -> Box<dyn Dialect> {
+        self.connection.dialect()
+    }
+
+    fn match_statement(&self, statement: &Statement) -> StatementMetadata {
+        self.connection.match_statement(statement)
+    }
+}
+
+impl Capabilities for ReconnectConnection {
+    fn as_bulk_copy(&mut self) -> Option<&mut dyn BulkCopy> {
+        self.connection.as_bulk_copy()
+    }
+
+    fn as_replica(&mut self) -> Option<&mut dyn Replica> {
+        self.connection.as_replica()
+    }
+
+    fn as_multiple_results(&mut self) -> Option<&mut dyn MultipleResults> {
+        self.connection.as_multiple_results()
+    }
+
+    fn as_notifications(&mut self) -> Option<&mut dyn Notifications> {
+        self.connection.as_notifications()
+    }
+
+    fn as_export(&mut self) -> Option<&mut dyn Export> {
+        self.connection.as_export()
+    }
+
+    fn as_historical_reads(&mut self) -> Option<&mut dyn HistoricalReads> {
+        if self.connection.as_historical_reads().is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+/// The timestamp is recorded so that it is applied again when the connection is re-established
+#[async_trait]
+impl HistoricalReads for ReconnectConnection {
+    async fn set_as_of(&mut self, timestamp: Option<String>) -> Result<()> {
+        let Some(historical_reads) = self.connection.as_historical_reads() else {
+            return Err(IoError(anyhow!(
+                "historical reads are not supported for this connection"
+            )));
+        };
+        historical_reads.set_as_of(timestamp.clone()).await?;
+        self.as_of = timestamp;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        MemoryQueryResult, MockCapableConnection, MockConnection, MockDriver, MockHistoricalReads,
+    };
+    use mockall::predicate::eq;
+
+    fn lost() -> Error {
+        std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset").into()
+    }
+
+    fn options() -> ReconnectOptions {
+        ReconnectOptions {
+            attempts: 2,
+            backoff: Duration::from_millis(1),
+            replay_session: true,
+        }
+    }
+
+    fn lost_connection() -> MockConnection {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_is_connection_lost()
+            .returning(Error::is_connection_lost);
+        connection
+            .expect_is_read_only_sql()
+            .returning(|sql| sql.starts_with("SELECT"));
+        connection.expect_close().returning(|| Ok(()));
+        connection
+    }
+
+    #[test]
+    fn test_is_session_statement() {
+        assert!(is_session_statement("SET search_path TO public"));
+        assert!(is_session_statement("  use db"));
+        assert!(!is_session_statement("SELECT 1"));
+        assert!(!is_session_statement("settings"));
+        assert!(!is_session_statement(""));
+    }
+
+    #[test]
+    fn test_transaction_state() {
+        assert_eq!(transaction_state("BEGIN"), Some(true));
+        assert_eq!(transaction_state("begin;"), Some(true));
+        assert_eq!(transaction_state("START TRANSACTION READ ONLY"), Some(true));
+        assert_eq!(transaction_state("COMMIT"), Some(false));
+        assert_eq!(transaction_state("end"), Some(false));
+        assert_eq!(transaction_state("ROLLBACK"), Some(false));
+        assert_eq!(transaction_state("ROLLBACK TO SAVEPOINT a"), None);
+        assert_eq!(transaction_state("START replica"), None);
+        assert_eq!(transaction_state("SELECT 1"), None);
+        assert_eq!(transaction_state(""), None);
+    }
+
+    #[test]
+    fn test_reconnect_options_default() {
+        let options = ReconnectOptions::default();
+        assert_eq!(options.attempts, 5);
+        assert_eq!(options.backoff, Duration::from_millis(100));
+        assert!(options.replay_session);
+    }
+
+    #[tokio::test]
+    async fn test_query_reconnects_and_replays_session() -> anyhow::Result<()> {
+        let mut connection = lost_connection();
+        connection
+            .expect_execute()
+            .with(eq("SET search_path TO test"))
+            .returning(|_| Ok(0));
+        connection.expect_query().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection
+                .expect_execute()
+                .with(eq("SET search_path TO test"))
+                .times(1)
+                .returning(|_| Ok(0));
+            connection.expect_query().returning(|_| {
+                Ok(
+                    Box::new(MemoryQueryResult::new(vec!["id".to_string()], vec![]))
+                        as Box<dyn QueryResult>,
+                )
+            });
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        connection.execute("SET search_path TO test").await?;
+        assert!(!connection.reconnected());
+        let query_result = connection.query("SELECT id FROM t").await?;
+        assert_eq!(query_result.columns().await, vec!["id".to_string()]);
+        assert!(connection.reconnected());
+        assert!(!connection.reconnected());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_is_not_retried_in_transaction() -> anyhow::Result<()> {
+        let mut connection = lost_connection();
+        connection.expect_execute().with(eq("BEGIN")).returning(|_| Ok(0));
+        connection.expect_query().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection.expect_query().times(1).returning(|_| {
+                Ok(
+                    Box::new(MemoryQueryResult::new(vec!["id".to_string()], vec![]))
+                        as Box<dyn QueryResult>,
+                )
+            });
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        connection.execute("BEGIN").await?;
+        let result = connection.query("SELECT id FROM t").await;
+        assert!(result.is_err_and(|error| error.to_string().contains("transaction")));
+        assert!(connection.reconnected());
+
+        // The transaction was rolled back by the database, so later queries run normally
+        let query_result = connection.query("SELECT id FROM t").await?;
+        assert_eq!(query_result.columns().await, vec!["id".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_is_retried_after_commit() -> anyhow::Result<()> {
+        let mut connection = lost_connection();
+        connection.expect_execute().returning(|_| Ok(0));
+        connection.expect_query().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection.expect_query().times(1).returning(|_| {
+                Ok(
+                    Box::new(MemoryQueryResult::new(vec!["id".to_string()], vec![]))
+                        as Box<dyn QueryResult>,
+                )
+            });
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        connection.execute("START TRANSACTION").await?;
+        connection.execute("COMMIT").await?;
+        let query_result = connection.query("SELECT id FROM t").await?;
+        assert_eq!(query_result.columns().await, vec!["id".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_refresh_metadata() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_refresh_metadata()
+            .times(1)
+            .returning(|| Ok(()));
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(MockDriver::new()),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        connection.refresh_metadata().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_applies_as_of() -> anyhow::Result<()> {
+        let mut historical_reads = MockHistoricalReads::new();
+        historical_reads
+            .expect_set_as_of()
+            .with(eq(Some("-10s".to_string())))
+            .returning(|_| Ok(()));
+        let mut connection = MockCapableConnection {
+            connection: lost_connection(),
+            historical_reads: Some(historical_reads),
+            ..Default::default()
+        };
+        connection
+            .connection
+            .expect_query()
+            .returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut historical_reads = MockHistoricalReads::new();
+            historical_reads
+                .expect_set_as_of()
+                .with(eq(Some("-10s".to_string())))
+                .times(1)
+                .returning(|_| Ok(()));
+            let mut connection = MockCapableConnection {
+                historical_reads: Some(historical_reads),
+                ..Default::default()
+            };
+            connection.connection.expect_query().returning(|_| {
+                Ok(
+                    Box::new(MemoryQueryResult::new(vec!["id".to_string()], vec![]))
+                        as Box<dyn QueryResult>,
+                )
+            });
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "cockroachdb://localhost".to_string(),
+            None,
+            options(),
+        );
+        connection
+            .as_historical_reads()
+            .expect("historical reads")
+            .set_as_of(Some("-10s".to_string()))
+            .await?;
+        let query_result = connection.query("SELECT id FROM t").await?;
+        assert_eq!(query_result.columns().await, vec!["id".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_applies_read_only() -> anyhow::Result<()> {
+        let mut connection = lost_connection();
+        connection
+            .expect_set_read_only()
+            .with(eq(true))
+            .returning(|_| Ok(()));
+        connection.expect_query().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection
+                .expect_set_read_only()
+                .with(eq(true))
+                .times(1)
+                .returning(|_| Ok(()));
+            connection.expect_query().returning(|_| {
+                Ok(
+                    Box::new(MemoryQueryResult::new(vec!["id".to_string()], vec![]))
+                        as Box<dyn QueryResult>,
+                )
+            });
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        connection.set_read_only(true).await?;
+        let query_result = connection.query("SELECT id FROM t").await?;
+        assert_eq!(query_result.columns().await, vec!["id".to_string()]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_execute_is_not_retried() -> anyhow::Result<()> {
+        let mut connection = lost_connection();
+        connection.expect_execute().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection.expect_execute().never();
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        assert!(connection.execute("DELETE FROM t").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_is_not_retried_if_not_read_only() -> anyhow::Result<()> {
+        let mut connection = lost_connection();
+        connection.expect_query().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().times(1).returning(|_, _| {
+            let mut connection = MockConnection::new();
+            connection.expect_query().never();
+            Ok(Box::new(connection))
+        });
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        let result = connection
+            .query("INSERT INTO t (id) VALUES (1) RETURNING id")
+            .await;
+        assert!(result.is_err_and(|error| error.to_string().contains("not retried")));
+        assert!(connection.reconnected());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_fails() {
+        let mut connection = lost_connection();
+        connection.expect_query().returning(|_| Err(lost()));
+
+        let mut driver = MockDriver::new();
+        driver
+            .expect_connect()
+            .times(2)
+            .returning(|_, _| Err(lost()));
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        assert!(connection.query("SELECT 1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_other_errors_are_returned() {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_is_connection_lost()
+            .returning(Error::is_connection_lost);
+        connection
+            .expect_query()
+            .returning(|_| Err(IoError(anyhow!("syntax error"))));
+
+        let mut driver = MockDriver::new();
+        driver.expect_connect().never();
+
+        let mut connection = ReconnectConnection::new(
+            Box::new(connection),
+            Arc::new(driver),
+            "postgresql://localhost".to_string(),
+            None,
+            options(),
+        );
+        assert!(connection.query("SELEC 1").await.is_err());
+    }
+}
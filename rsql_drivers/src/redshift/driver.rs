@@ -1,9 +1,14 @@
+use crate::connect_options::connect_timeout;
 use crate::error::Result;
 use crate::postgresql::driver::Connection as PgConnection;
-use crate::{Metadata, QueryResult};
+use crate::redshift::metadata;
+use crate::Error::IoError;
+use crate::{ConnectOptions, Connection as _, Metadata, QueryResult, Value};
+use anyhow::anyhow;
 use async_trait::async_trait;
 use file_type::FileType;
 use sqlparser::dialect::{Dialect, RedshiftSqlDialect};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct Driver;
@@ -19,7 +24,15 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url, password).await?;
+        let options = ConnectOptions::from_connection_url(&url, password)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
@@ -35,8 +48,8 @@ pub struct Connection {
 }
 
 impl Connection {
-    pub async fn new(url: String, password: Option<String>) -> Result<Self> {
-        let inner = PgConnection::new(url.clone(), password).await?;
+    pub async fn new(url: String, options: &ConnectOptions) -> Result<Self> {
+        let inner = PgConnection::new(url.clone(), options).await?;
         Ok(Self { url, inner })
     }
 }
@@ -60,10 +73,154 @@ impl crate::Connection for Connection {
     }
 
     async fn metadata(&mut self) -> Result<Metadata> {
-        self.inner.metadata().await
+        let mut metadata = self.inner.metadata().await?;
+        metadata::retrieve_external_tables(self, &mut metadata).await?;
+        Ok(metadata)
+    }
+
+    async fn set_timeout(&mut self, timeout: Duration) -> Result<bool> {
+        self.inner.set_timeout(timeout).await
+    }
+
+    fn messages(&mut self) -> Vec<String> {
+        self.inner.messages()
     }
 
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(RedshiftSqlDialect {})
     }
 }
+
+#[async_trait]
+impl crate::Export for Connection {
+    async fn export(&mut self, sql: &str, destination: &str, options: &[String]) -> Result<u64> {
+        let unload = unload_statement(sql, destination, options)?;
+        self.inner.execute(&unload).await?;
+
+        let mut query_result = self.inner.query("SELECT pg_last_unload_count()").await?;
+        let rows = match query_result.next().await.as_ref().and_then(|row| row.first()) {
+            Some(Value::I64(rows)) => u64::try_from(*rows).unwrap_or_default(),
+            Some(Value::I32(rows)) => u64::try_from(*rows).unwrap_or_default(),
+            _ => 0,
+        };
+        Ok(rows)
+    }
+}
+
+impl crate::Capabilities for Connection {
+    fn as_export(&mut self) -> Option<&mut dyn crate::Export> {
+        Some(self)
+    }
+}
+
+/// Quote a string literal; single quotes are escaped by doubling them
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Get the `UNLOAD` statement that exports the results of the query to the Amazon S3 destination.
+/// The options are `iam_role=<arn>` (the default IAM role of the cluster is used when no role is
+/// specified), `format=csv|json|parquet`, `delimiter=<character>`, `header`, `parallel=on|off`,
+/// `allowoverwrite`, `manifest` and `region=<region>`.
+fn unload_statement(sql: &str, destination: &str, options: &[String]) -> Result<String> {
+    if !destination.starts_with("s3://") {
+        return Err(IoError(anyhow!(
+            "Invalid export destination: {destination}; expected an s3:// URL"
+        )));
+    }
+
+    let query = sql.trim().trim_end_matches(';').trim_end();
+    let mut iam_role = "IAM_ROLE default".to_string();
+    let mut parameters = Vec::new();
+    for option in options {
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => (name.to_lowercase(), Some(value)),
+            None => (option.to_lowercase(), None),
+        };
+        match (name.as_str(), value) {
+            ("iam_role", Some(arn)) => iam_role = format!("IAM_ROLE {}", quote(arn)),
+            ("format", Some(format)) => match format.to_lowercase().as_str() {
+                "csv" => parameters.push("FORMAT AS CSV".to_string()),
+                "json" => parameters.push("FORMAT AS JSON".to_string()),
+                "parquet" => parameters.push("FORMAT AS PARQUET".to_string()),
+                _ => return Err(IoError(anyhow!("Invalid export format: {format}"))),
+            },
+            ("delimiter", Some(delimiter)) => {
+                parameters.push(format!("DELIMITER AS {}", quote(delimiter)));
+            }
+            ("header", None) => parameters.push("HEADER".to_string()),
+            ("parallel", Some(parallel)) => match parallel.to_lowercase().as_str() {
+                "on" => parameters.push("PARALLEL ON".to_string()),
+                "off" => parameters.push("PARALLEL OFF".to_string()),
+                _ => return Err(IoError(anyhow!("Invalid export option: {option}"))),
+            },
+            ("allowoverwrite", None) => parameters.push("ALLOWOVERWRITE".to_string()),
+            ("manifest", None) => parameters.push("MANIFEST".to_string()),
+            ("region", Some(region)) => parameters.push(format!("REGION {}", quote(region))),
+            _ => return Err(IoError(anyhow!("Invalid export option: {option}"))),
+        }
+    }
+
+    let mut statement = format!(
+        "UNLOAD ({}) TO {} {iam_role}",
+        quote(query),
+        quote(destination)
+    );
+    for parameter in parameters {
+        statement.push(' ');
+        statement.push_str(&parameter);
+    }
+    Ok(statement)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(options: &[&str]) -> Vec<String> {
+        options.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_unload_statement() -> Result<()> {
+        let statement = unload_statement(
+            "SELECT * FROM sales WHERE region = 'EU';",
+            "s3://bucket/sales/",
+            &[],
+        )?;
+        assert_eq!(
+            statement,
+            "UNLOAD ('SELECT * FROM sales WHERE region = ''EU''') TO 's3://bucket/sales/' \
+             IAM_ROLE default"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unload_statement_options() -> Result<()> {
+        let options = options(&[
+            "iam_role=arn:aws:iam::123456789012:role/unload",
+            "format=csv",
+            "header",
+            "parallel=off",
+            "ALLOWOVERWRITE",
+            "region=eu-west-1",
+        ]);
+        let statement = unload_statement("SELECT 1", "s3://bucket/one_", &options)?;
+        assert_eq!(
+            statement,
+            "UNLOAD ('SELECT 1') TO 's3://bucket/one_' \
+             IAM_ROLE 'arn:aws:iam::123456789012:role/unload' FORMAT AS CSV HEADER PARALLEL OFF \
+             ALLOWOVERWRITE REGION 'eu-west-1'"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unload_statement_invalid() {
+        assert!(unload_statement("SELECT 1", "/tmp/one", &[]).is_err());
+        assert!(unload_statement("SELECT 1", "s3://bucket/", &options(&["format=xml"])).is_err());
+        assert!(unload_statement("SELECT 1", "s3://bucket/", &options(&["parallel=2"])).is_err());
+        assert!(unload_statement("SELECT 1", "s3://bucket/", &options(&["foo"])).is_err());
+    }
+}
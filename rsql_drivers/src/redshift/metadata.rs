@@ -0,0 +1,138 @@
+use crate::{Column, Connection, Metadata, Result, Schema, Table, Value};
+use indoc::indoc;
+use tracing::warn;
+
+/// Add the external (Redshift Spectrum) schemas, tables and columns to the metadata of the
+/// database; external tables are not listed in `information_schema`, so they are retrieved from
+/// the Redshift system views.
+pub(crate) async fn retrieve_external_tables(
+    connection: &mut dyn Connection,
+    metadata: &mut Metadata,
+) -> Result<()> {
+    let sql = indoc! { r"
+        SELECT
+            schemaname,
+            tablename,
+            columnname,
+            external_type,
+            is_nullable
+        FROM
+            svv_external_columns
+        ORDER BY
+            schemaname,
+            tablename,
+            columnnum
+    "};
+    let mut query_result = match connection.query(sql).await {
+        Ok(query_result) => query_result,
+        Err(error) => {
+            // The system views are not available, e.g. on PostgreSQL compatible test servers
+            warn!("Unable to retrieve external tables: {error}");
+            return Ok(());
+        }
+    };
+
+    while let Some(row) = query_result.next().await {
+        let (Some(schema_name), Some(table_name), Some(column_name), Some(column_type)) =
+            (row.first(), row.get(1), row.get(2), row.get(3))
+        else {
+            continue;
+        };
+        let not_null = matches!(row.get(4), Some(Value::String(value)) if value == "false");
+        let column = Column::new(
+            column_name.to_string(),
+            column_type.to_string(),
+            not_null,
+            None,
+        );
+        add_column(metadata, &schema_name.to_string(), &table_name.to_string(), column);
+    }
+
+    Ok(())
+}
+
+/// Add the column to the table of the schema, creating the schema and table when they do not
+/// exist in the metadata
+fn add_column(metadata: &mut Metadata, schema_name: &str, table_name: &str, column: Column) {
+    if metadata.get(schema_name).is_none() {
+        metadata.add(Schema::new(schema_name, false));
+    }
+    let Some(schema) = metadata.get_mut(schema_name) else {
+        return;
+    };
+    if let Some(table) = schema.get_mut(table_name) {
+        table.add_column(column);
+    } else {
+        let mut table = Table::new(table_name);
+        table.add_column(column);
+        schema.add(table);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Error, MemoryQueryResult, MockConnection};
+    use anyhow::anyhow;
+
+    fn external_columns() -> MemoryQueryResult {
+        let columns = ["schemaname", "tablename", "columnname", "external_type", "is_nullable"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let row = |table: &str, column: &str, data_type: &str, nullable: &str| {
+            vec![
+                Value::String("spectrum".to_string()),
+                Value::String(table.to_string()),
+                Value::String(column.to_string()),
+                Value::String(data_type.to_string()),
+                Value::String(nullable.to_string()),
+            ]
+        };
+        let rows = vec![
+            row("sales", "id", "bigint", "false"),
+            row("sales", "amount", "decimal(8,2)", "true"),
+            row("events", "name", "string", "true"),
+        ];
+        MemoryQueryResult::new(columns, rows)
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_external_tables() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_query()
+            .returning(|_| Ok(Box::new(external_columns())));
+        let mut metadata = Metadata::new();
+        metadata.add(Schema::new("public", true));
+
+        retrieve_external_tables(&mut connection, &mut metadata).await?;
+
+        let schema = metadata.get("spectrum").expect("schema");
+        assert!(!schema.current());
+        assert_eq!(schema.tables().len(), 2);
+        let sales = schema.get("sales").expect("table");
+        let id = sales.get_column("id").expect("column");
+        assert_eq!(id.data_type(), "bigint");
+        assert!(id.not_null());
+        let amount = sales.get_column("amount").expect("column");
+        assert_eq!(amount.data_type(), "decimal(8,2)");
+        assert!(!amount.not_null());
+        assert!(metadata.current_schema().is_some());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_external_tables_unavailable() -> anyhow::Result<()> {
+        let mut connection = MockConnection::new();
+        connection
+            .expect_query()
+            .returning(|_| Err(Error::IoError(anyhow!("relation does not exist"))));
+        let mut metadata = Metadata::new();
+
+        retrieve_external_tables(&mut connection, &mut metadata).await?;
+
+        assert!(metadata.schemas().is_empty());
+        Ok(())
+    }
+}
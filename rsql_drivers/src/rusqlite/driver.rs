@@ -1,7 +1,11 @@
+use crate::connection::limit_statement;
 use crate::error::{Error, Result};
+use crate::sqlite_options::SqliteOptions;
 use crate::url::UrlExtension;
 use crate::value::Value;
-use crate::{sqlite, MemoryQueryResult, Metadata, QueryResult, StatementMetadata};
+use crate::{
+    sqlite, ColumnMetadata, MemoryQueryResult, Metadata, QueryResult, StatementMetadata,
+};
 use anyhow::anyhow;
 use async_trait::async_trait;
 use file_type::FileType;
@@ -45,11 +49,23 @@ impl Connection {
     #[expect(clippy::unused_async)]
     pub(crate) async fn new(url: String) -> Result<Connection> {
         let parsed_url = Url::parse(url.as_str())?;
+        let sqlite_options = SqliteOptions::parse(parsed_url.query_pairs().into_owned())?;
+        // Loading extensions with rusqlite requires unsafe code, which is forbidden in this crate
+        if !sqlite_options.extensions.is_empty() {
+            return Err(Error::InvalidUrl(
+                "Extensions are not supported by the rusqlite driver; use the sqlite driver"
+                    .to_string(),
+            ));
+        }
+
         let connection = if let Ok(file_name) = parsed_url.to_file() {
             rusqlite::Connection::open(file_name)?
         } else {
             rusqlite::Connection::open_in_memory()?
         };
+        for (pragma, value) in &sqlite_options.pragmas {
+            connection.pragma_update(None, pragma, value)?;
+        }
 
         Ok(Connection {
             url,
@@ -90,6 +106,14 @@ impl crate::Connection for Connection {
             .iter()
             .map(|column| column.name().to_string())
             .collect();
+        let columns_metadata: Vec<ColumnMetadata> = statement
+            .columns()
+            .iter()
+            .map(|column| match column.decl_type() {
+                Some(data_type) => ColumnMetadata::new(column.name()).with_data_type(data_type),
+                None => ColumnMetadata::new(column.name()),
+            })
+            .collect();
 
         let mut query_rows = statement.query([])?;
         let mut rows = Vec::new();
@@ -102,7 +126,8 @@ impl crate::Connection for Connection {
             rows.push(row);
         }
 
-        let query_result = MemoryQueryResult::new(columns, rows);
+        let query_result =
+            MemoryQueryResult::new(columns, rows).with_columns_metadata(columns_metadata);
         Ok(Box::new(query_result))
     }
 
@@ -110,10 +135,26 @@ impl crate::Connection for Connection {
         Ok(())
     }
 
+    fn read_only_statement(&self, read_only: bool) -> Option<&'static str> {
+        if read_only {
+            Some("PRAGMA query_only = ON")
+        } else {
+            Some("PRAGMA query_only = OFF")
+        }
+    }
+
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        Some("BEGIN")
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(SQLiteDialect {})
     }
 
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
+
     fn match_statement(&self, statement: &Statement) -> StatementMetadata {
         let default = self.default_match_statement(statement);
         match default {
@@ -129,6 +170,8 @@ impl crate::Connection for Connection {
     }
 }
 
+impl crate::Capabilities for Connection {}
+
 impl Connection {
     fn convert_to_value(row: &Row, column_index: usize) -> Result<Value> {
         let value = match row.get_ref(column_index)? {
@@ -163,6 +206,97 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_pragma() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager
+            .connect("rusqlite://?pragma_foreign_keys=ON")
+            .await?;
+
+        let mut query_result = connection.query("PRAGMA foreign_keys").await?;
+
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(1)]));
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_with_limit() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+
+        let mut query_result = connection
+            .query_with_limit("SELECT 1 UNION ALL SELECT 2", 1)
+            .await?;
+
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(1)]));
+        assert_eq!(query_result.next().await, None);
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extensions_not_supported() {
+        let mut driver_manager = DriverManager::default();
+        driver_manager.set_load_extensions(true);
+        let result = driver_manager
+            .connect("rusqlite://?extensions=/invalid/extension")
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_only() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+        connection
+            .execute("CREATE TABLE users (id INTEGER)")
+            .await?;
+
+        let read_only = connection.read_only_statement(true).expect("read only");
+        connection.execute(read_only).await?;
+        assert!(connection
+            .execute("INSERT INTO users (id) VALUES (1)")
+            .await
+            .is_err());
+
+        let read_write = connection.read_only_statement(false).expect("read write");
+        connection.execute(read_write).await?;
+        assert_eq!(
+            connection
+                .execute("INSERT INTO users (id) VALUES (1)")
+                .await?,
+            1
+        );
+
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect(DATABASE_URL).await?;
+        connection
+            .execute("CREATE TABLE users (id INTEGER)")
+            .await?;
+
+        let begin = connection
+            .begin_transaction_statement()
+            .expect("begin transaction");
+        connection.execute(begin).await?;
+        connection
+            .execute("INSERT INTO users (id) VALUES (1)")
+            .await?;
+        connection.execute("ROLLBACK").await?;
+
+        let mut query_result = connection.query("SELECT COUNT(*) FROM users").await?;
+        assert_eq!(query_result.next().await, Some(vec![Value::I64(0)]));
+
+        connection.close().await?;
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_connection_interface() -> anyhow::Result<()> {
         let database_url = dataset_url("rusqlite", "users.sqlite3");
@@ -208,6 +342,17 @@ mod test {
             query_result.columns().await,
             vec!["t", "nu", "i", "r", "no"]
         );
+        let data_types: Vec<Option<String>> = query_result
+            .columns_metadata()
+            .await
+            .into_iter()
+            .map(|column| column.data_type)
+            .collect();
+        assert_eq!(
+            data_types,
+            ["TEXT", "NUMERIC", "INTEGER", "REAL", "BLOB"]
+                .map(|data_type| Some(data_type.to_string()))
+        );
         assert_eq!(
             query_result.next().await,
             Some(vec![
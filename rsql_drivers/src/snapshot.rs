@@ -0,0 +1,261 @@
+use crate::error::Result;
+use crate::Error::{ConversionError, IoError};
+use crate::{Interval, MemoryQueryResult, QueryResult, Row, Value};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use indexmap::IndexMap;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// The version of the snapshot format
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// A query result serialized with the type of every value, so that the result can be loaded
+/// without a database connection and formatted exactly as the original result
+#[derive(Debug, Deserialize, Serialize)]
+struct Snapshot {
+    version: u32,
+    columns: Vec<String>,
+    rows: Vec<Vec<TypedValue>>,
+}
+
+/// A value tagged with its type, e.g. `{"i64":42}`; values that JSON cannot represent without
+/// losing precision are stored as strings.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum TypedValue {
+    Null,
+    Bool(bool),
+    Bytes(String),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(String),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(String),
+    F32(f32),
+    F64(f64),
+    Decimal(String),
+    String(String),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    DateTime(NaiveDateTime),
+    Interval(i32, i32, i64),
+    Uuid(Uuid),
+    Json(serde_json::Value),
+    Array(Vec<TypedValue>),
+    Map(Vec<(TypedValue, TypedValue)>),
+}
+
+impl From<Value> for TypedValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => TypedValue::Null,
+            Value::Bool(value) => TypedValue::Bool(value),
+            Value::Bytes(value) => TypedValue::Bytes(STANDARD.encode(value)),
+            Value::I8(value) => TypedValue::I8(value),
+            Value::I16(value) => TypedValue::I16(value),
+            Value::I32(value) => TypedValue::I32(value),
+            Value::I64(value) => TypedValue::I64(value),
+            Value::I128(value) => TypedValue::I128(value.to_string()),
+            Value::U8(value) => TypedValue::U8(value),
+            Value::U16(value) => TypedValue::U16(value),
+            Value::U32(value) => TypedValue::U32(value),
+            Value::U64(value) => TypedValue::U64(value),
+            Value::U128(value) => TypedValue::U128(value.to_string()),
+            Value::F32(value) => TypedValue::F32(value),
+            Value::F64(value) => TypedValue::F64(value),
+            Value::Decimal(value) => TypedValue::Decimal(value.to_string()),
+            Value::String(value) => TypedValue::String(value),
+            Value::Date(value) => TypedValue::Date(value),
+            Value::Time(value) => TypedValue::Time(value),
+            Value::DateTime(value) => TypedValue::DateTime(value),
+            Value::Interval(value) => {
+                TypedValue::Interval(value.months(), value.days(), value.nanoseconds())
+            }
+            Value::Uuid(value) => TypedValue::Uuid(value),
+            Value::Json(value) => TypedValue::Json(value),
+            Value::Array(values) => {
+                TypedValue::Array(values.into_iter().map(TypedValue::from).collect())
+            }
+            Value::Map(values) => TypedValue::Map(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (TypedValue::from(key), TypedValue::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl TryFrom<TypedValue> for Value {
+    type Error = crate::Error;
+
+    fn try_from(value: TypedValue) -> Result<Self> {
+        let value = match value {
+            TypedValue::Null => Value::Null,
+            TypedValue::Bool(value) => Value::Bool(value),
+            TypedValue::Bytes(value) => Value::Bytes(
+                STANDARD
+                    .decode(value)
+                    .map_err(|error| ConversionError(error.to_string()))?,
+            ),
+            TypedValue::I8(value) => Value::I8(value),
+            TypedValue::I16(value) => Value::I16(value),
+            TypedValue::I32(value) => Value::I32(value),
+            TypedValue::I64(value) => Value::I64(value),
+            TypedValue::I128(value) => Value::I128(parse(&value)?),
+            TypedValue::U8(value) => Value::U8(value),
+            TypedValue::U16(value) => Value::U16(value),
+            TypedValue::U32(value) => Value::U32(value),
+            TypedValue::U64(value) => Value::U64(value),
+            TypedValue::U128(value) => Value::U128(parse(&value)?),
+            TypedValue::F32(value) => Value::F32(value),
+            TypedValue::F64(value) => Value::F64(value),
+            TypedValue::Decimal(value) => Value::Decimal(parse::<Decimal>(&value)?),
+            TypedValue::String(value) => Value::String(value),
+            TypedValue::Date(value) => Value::Date(value),
+            TypedValue::Time(value) => Value::Time(value),
+            TypedValue::DateTime(value) => Value::DateTime(value),
+            TypedValue::Interval(months, days, nanoseconds) => {
+                Value::Interval(Interval::new(months, days, nanoseconds))
+            }
+            TypedValue::Uuid(value) => Value::Uuid(value),
+            TypedValue::Json(value) => Value::Json(value),
+            TypedValue::Array(values) => Value::Array(
+                values
+                    .into_iter()
+                    .map(Value::try_from)
+                    .collect::<Result<Vec<Value>>>()?,
+            ),
+            TypedValue::Map(values) => {
+                let mut map = IndexMap::new();
+                for (key, value) in values {
+                    map.insert(Value::try_from(key)?, Value::try_from(value)?);
+                }
+                Value::Map(map)
+            }
+        };
+        Ok(value)
+    }
+}
+
+/// Parse a value that is stored as a string
+fn parse<T: FromStr>(value: &str) -> Result<T>
+where
+    T::Err: ToString,
+{
+    value
+        .parse()
+        .map_err(|error: T::Err| ConversionError(error.to_string()))
+}
+
+/// Write the columns and rows of the query result as a snapshot; the rows are consumed from the
+/// query result.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot cannot be written.
+pub async fn write_snapshot<W: Write>(query_result: &mut dyn QueryResult, writer: W) -> Result<()> {
+    let columns = query_result.columns().await;
+    let mut rows = Vec::new();
+    while let Some(row) = query_result.next().await {
+        rows.push(row.into_iter().map(TypedValue::from).collect());
+    }
+    if let Some(error) = query_result.take_error() {
+        return Err(error);
+    }
+    let snapshot = Snapshot {
+        version: SNAPSHOT_VERSION,
+        columns,
+        rows,
+    };
+    serde_json::to_writer(writer, &snapshot).map_err(|error| IoError(error.into()))
+}
+
+/// Read a snapshot written by [`write_snapshot`] as a query result.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot cannot be read, or it was written by an unsupported version.
+pub fn read_snapshot<R: Read>(reader: R) -> Result<MemoryQueryResult> {
+    let snapshot: Snapshot =
+        serde_json::from_reader(reader).map_err(|error| IoError(error.into()))?;
+    if snapshot.version != SNAPSHOT_VERSION {
+        return Err(ConversionError(format!(
+            "unsupported snapshot version: {}",
+            snapshot.version
+        )));
+    }
+    let rows = snapshot
+        .rows
+        .into_iter()
+        .map(|row| row.into_iter().map(Value::try_from).collect())
+        .collect::<Result<Vec<Row>>>()?;
+    Ok(MemoryQueryResult::new(snapshot.columns, rows))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_write_and_read_snapshot() -> anyhow::Result<()> {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).expect("date");
+        let time = NaiveTime::from_hms_milli_opt(12, 34, 56, 789).expect("time");
+        let mut map = IndexMap::new();
+        map.insert(Value::String("a".to_string()), Value::I32(1));
+        let row = vec![
+            Value::Null,
+            Value::Bool(true),
+            Value::Bytes(vec![1, 2, 3]),
+            Value::I8(-8),
+            Value::I128(i128::MAX),
+            Value::U128(u128::MAX),
+            Value::F32(1.5),
+            Value::F64(2.25),
+            Value::Decimal(Decimal::new(12345, 2)),
+            Value::String("foo".to_string()),
+            Value::Date(date),
+            Value::Time(time),
+            Value::DateTime(NaiveDateTime::new(date, time)),
+            Value::Interval(Interval::new(1, 2, 3)),
+            Value::Uuid(Uuid::nil()),
+            Value::Json(json!({"key": [1, 2]})),
+            Value::Array(vec![Value::I64(1), Value::Null]),
+            Value::Map(map),
+        ];
+        let columns: Vec<String> = (0..row.len()).map(|index| format!("c{index}")).collect();
+        let mut query_result = MemoryQueryResult::new(columns.clone(), vec![row.clone()]);
+
+        let mut snapshot = Vec::new();
+        write_snapshot(&mut query_result, &mut snapshot).await?;
+        let mut loaded = read_snapshot(snapshot.as_slice())?;
+
+        assert_eq!(loaded.columns().await, columns);
+        let loaded_row = loaded.next().await.expect("row");
+        assert_eq!(loaded_row, row);
+        assert!(matches!(loaded_row[3], Value::I8(-8)));
+        assert!(matches!(loaded_row[6], Value::F32(_)));
+        assert!(loaded.next().await.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_snapshot_invalid() {
+        assert!(read_snapshot("foo".as_bytes()).is_err());
+        let snapshot = r#"{"version":99,"columns":[],"rows":[]}"#;
+        assert!(read_snapshot(snapshot.as_bytes()).is_err());
+        let snapshot = r#"{"version":1,"columns":["id"],"rows":[[{"i128":"foo"}]]}"#;
+        assert!(read_snapshot(snapshot.as_bytes()).is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use crate::connection::limit_statement;
 use crate::connection::Row;
-use crate::{snowflake::SnowflakeError, MemoryQueryResult, Metadata, QueryResult, Result, Value};
+use crate::{
+    snowflake::SnowflakeError, MemoryQueryResult, Metadata, QueryResult, QueryStatistics, Result,
+    Value,
+};
 use async_trait::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -19,6 +23,8 @@ const TIME_FORMATS: (&str, &str) = ("HH24:MI:SS.FF", "%H:%M:%S.%f");
 const DATETIME_NO_TZ_FORMATS: (&str, &str) = ("YYYY-MM-DDTHH24:MI:SS.FF", "%Y-%m-%dT%H:%M:%S.%f");
 const DATETIME_TZ_FORMATS: (&str, &str) =
     ("YYYY-MM-DDTHH24:MI:SS.FFTZHTZM", "%Y-%m-%dT%H:%M:%S.%f%:z");
+/// The statement timeout in seconds used when no timeout is configured
+const DEFAULT_TIMEOUT: u64 = 10;
 
 #[derive(Debug)]
 pub struct Driver;
@@ -51,6 +57,8 @@ pub(crate) struct SnowflakeConnection {
     key_pair: Option<RS256KeyPair>,
     jwt_expires_at: Option<DateTime<Utc>>,
     client: Mutex<reqwest::Client>,
+    statistics: Option<QueryStatistics>,
+    timeout: u64,
 }
 
 impl SnowflakeConnection {
@@ -83,6 +91,8 @@ impl SnowflakeConnection {
                 key_pair: None,
                 jwt_expires_at: None,
                 client,
+                statistics: None,
+                timeout: DEFAULT_TIMEOUT,
             })
         } else {
             let private_key_file = query_params
@@ -113,6 +123,8 @@ impl SnowflakeConnection {
                 key_pair: Some(key_pair),
                 jwt_expires_at: Some(jwt_expires_at),
                 client,
+                statistics: None,
+                timeout: DEFAULT_TIMEOUT,
             })
         }
     }
@@ -222,7 +234,7 @@ impl SnowflakeConnection {
             .body(
                 json!({
                     "statement": sql,
-                    "timeout": 10,
+                    "timeout": self.timeout,
                     "parameters": {
                         "DATE_OUTPUT_FORMAT": DATE_FORMATS.0,
                         "TIME_OUTPUT_FORMAT": TIME_FORMATS.0,
@@ -300,6 +312,20 @@ fn get_issuer_and_subject(public_key: &str, account: &str, user: &str) -> Result
     Ok((issuer, subject))
 }
 
+/// Read the DML statistics from a statement response; Snowflake reports the number of rows
+/// inserted, updated and deleted.
+fn parse_statistics(response_json: &serde_json::Value) -> Option<QueryStatistics> {
+    let stats = response_json.get("stats")?.as_object()?;
+    let rows_affected = ["numRowsInserted", "numRowsUpdated", "numRowsDeleted"]
+        .iter()
+        .filter_map(|key| stats.get(*key).and_then(serde_json::Value::as_u64))
+        .sum();
+    Some(QueryStatistics {
+        rows_affected: Some(rows_affected),
+        ..Default::default()
+    })
+}
+
 #[async_trait]
 impl crate::Connection for SnowflakeConnection {
     fn url(&self) -> &String {
@@ -314,6 +340,7 @@ impl crate::Connection for SnowflakeConnection {
             .map_err(SnowflakeError::Response)?;
         let response_json: serde_json::Value =
             response.json().await.map_err(SnowflakeError::Response)?;
+        self.statistics = parse_statistics(&response_json);
         let row_count = response_json["data"][0][0]
             .as_str()
             .ok_or(SnowflakeError::ResponseContent(
@@ -342,6 +369,7 @@ impl crate::Connection for SnowflakeConnection {
             .json()
             .await
             .map_err(|e| SnowflakeError::ResponseContent(format!("Error parsing Response: {e}")))?;
+        self.statistics = parse_statistics(&response_json);
 
         let handle =
             response_json["statementHandle"]
@@ -395,11 +423,30 @@ impl crate::Connection for SnowflakeConnection {
         Ok(())
     }
 
+    fn statistics(&self) -> Option<QueryStatistics> {
+        self.statistics.clone()
+    }
+
+    async fn set_timeout(&mut self, timeout: std::time::Duration) -> Result<bool> {
+        self.timeout = if timeout.is_zero() {
+            DEFAULT_TIMEOUT
+        } else {
+            timeout.as_secs().max(1)
+        };
+        Ok(true)
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(SnowflakeDialect {})
     }
+
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
 }
 
+impl crate::Capabilities for SnowflakeConnection {}
+
 #[derive(Debug)]
 struct ColumnDefinition {
     pub name: String,
@@ -484,7 +531,7 @@ mod test {
 
     use super::*;
     use serde_json::json;
-    use wiremock::matchers::{method, path};
+    use wiremock::matchers::{body_partial_json, method, path};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[expect(clippy::too_many_lines)]
@@ -707,6 +754,47 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_set_timeout() -> anyhow::Result<()> {
+        let mock = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v2/statements"))
+            .and(body_partial_json(json!({"timeout": 30})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(initial_response_json()))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let database_url = "snowflake://abc123.snowflakecomputing.com/?user=test".to_string();
+        let mut connection =
+            SnowflakeConnection::new(database_url, Some("auth_token".to_string()))?;
+        connection.set_base_url(&mock.uri());
+        assert!(
+            connection
+                .set_timeout(std::time::Duration::from_secs(30))
+                .await?
+        );
+        let _ = connection.execute("SELECT 1").await?;
+
+        connection.set_timeout(std::time::Duration::ZERO).await?;
+        assert_eq!(connection.timeout, DEFAULT_TIMEOUT);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_statistics() {
+        let response_json = json!({
+            "stats": {
+                "numRowsInserted": 2,
+                "numRowsUpdated": 3,
+                "numRowsDeleted": 0
+            }
+        });
+        let statistics = parse_statistics(&response_json).expect("statistics");
+        assert_eq!(statistics.rows_affected, Some(5));
+        assert!(parse_statistics(&initial_response_json()).is_none());
+    }
+
     #[test]
     fn test_get_issuer_and_subject() {
         let keypair = RS256KeyPair::generate(2048).expect("cannot generate key for tests");
@@ -0,0 +1,226 @@
+use crate::error::Result;
+use crate::snapshot::TypedValue;
+use crate::Error::IoError;
+use crate::{MemoryQueryResult, Row, Value};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::Mutex;
+
+/// A buffer of rows that are kept in memory until the estimated size of the rows exceeds the
+/// memory limit; the remaining rows are written to a temporary file, so that buffering a large
+/// result does not exhaust the memory of the client.
+#[derive(Debug, Default)]
+pub struct RowBuffer {
+    memory_limit: usize,
+    memory_size: usize,
+    rows: Vec<Row>,
+    spilled_rows: Option<SpillFile>,
+}
+
+impl RowBuffer {
+    /// Create a buffer that keeps up to `memory_limit` bytes of rows in memory; a limit of zero
+    /// keeps all rows in memory.
+    #[must_use]
+    pub fn new(memory_limit: usize) -> Self {
+        Self {
+            memory_limit,
+            ..Default::default()
+        }
+    }
+
+    /// Add a row to the buffer; the row is written to the temporary file once the memory limit
+    /// has been exceeded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row cannot be written to the temporary file.
+    pub fn push(&mut self, row: Row) -> Result<()> {
+        if let Some(spilled_rows) = &mut self.spilled_rows {
+            return spilled_rows.push(row);
+        }
+
+        let size = row.iter().map(Value::estimated_size).sum::<usize>();
+        if self.memory_limit == 0 || self.memory_size + size <= self.memory_limit {
+            self.memory_size += size;
+            self.rows.push(row);
+            return Ok(());
+        }
+
+        let mut spilled_rows = SpillFile::new()?;
+        spilled_rows.push(row)?;
+        self.spilled_rows = Some(spilled_rows);
+        Ok(())
+    }
+
+    /// Get the number of rows in the buffer
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rows.len() + self.spilled_rows.as_ref().map_or(0, SpillFile::len)
+    }
+
+    /// Returns true if the buffer contains no rows
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if rows have been written to the temporary file
+    #[must_use]
+    pub fn is_spilled(&self) -> bool {
+        self.spilled_rows.is_some()
+    }
+
+    /// Convert the buffer into a query result with the columns; rows that were written to the
+    /// temporary file are read back as the query result is iterated.
+    #[must_use]
+    pub fn into_query_result(self, columns: Vec<String>) -> MemoryQueryResult {
+        let query_result = MemoryQueryResult::new(columns, self.rows);
+        match self.spilled_rows {
+            Some(spilled_rows) => query_result.with_spilled_rows(spilled_rows),
+            None => query_result,
+        }
+    }
+}
+
+/// Rows written to an anonymous temporary file, which is removed when it is closed; the offsets
+/// of the rows are kept in memory so that any row can be read.
+#[derive(Debug)]
+pub(crate) struct SpillFile {
+    file: Mutex<File>,
+    offsets: Vec<u64>,
+}
+
+impl SpillFile {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            file: Mutex::new(tempfile::tempfile()?),
+            offsets: vec![0],
+        })
+    }
+
+    fn push(&mut self, row: Row) -> Result<()> {
+        let values: Vec<TypedValue> = row.into_iter().map(TypedValue::from).collect();
+        let bytes = serde_json::to_vec(&values).map_err(|error| IoError(error.into()))?;
+        let file = self
+            .file
+            .get_mut()
+            .map_err(|error| IoError(anyhow::anyhow!("Error: {error:?}")))?;
+        file.write_all(&bytes)?;
+        let offset = self.offsets.last().copied().unwrap_or_default();
+        self.offsets.push(offset + bytes.len() as u64);
+        Ok(())
+    }
+
+    /// Get the number of rows in the file
+    pub(crate) fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Read the row at the index; returns `None` if the index is out of range.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row cannot be read from the file.
+    pub(crate) fn get(&self, index: usize) -> Result<Option<Row>> {
+        let (Some(start), Some(end)) = (self.offsets.get(index), self.offsets.get(index + 1))
+        else {
+            return Ok(None);
+        };
+        let mut bytes = vec![0; usize::try_from(end - start)?];
+        {
+            let mut file = self
+                .file
+                .lock()
+                .map_err(|error| IoError(anyhow::anyhow!("Error: {error:?}")))?;
+            file.seek(SeekFrom::Start(*start))?;
+            file.read_exact(&mut bytes)?;
+        }
+        let values: Vec<TypedValue> =
+            serde_json::from_slice(&bytes).map_err(|error| IoError(error.into()))?;
+        let row = values
+            .into_iter()
+            .map(Value::try_from)
+            .collect::<Result<Row>>()?;
+        Ok(Some(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QueryResult;
+
+    fn row(id: i64) -> Row {
+        vec![Value::I64(id), Value::String(format!("name {id}"))]
+    }
+
+    #[test]
+    fn test_row_buffer_without_limit() -> Result<()> {
+        let mut buffer = RowBuffer::new(0);
+        assert!(buffer.is_empty());
+        for id in 0..100 {
+            buffer.push(row(id))?;
+        }
+        assert_eq!(buffer.len(), 100);
+        assert!(!buffer.is_spilled());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_row_buffer_spilled() -> Result<()> {
+        let row_size = row(0).iter().map(Value::estimated_size).sum::<usize>();
+        let mut buffer = RowBuffer::new(2 * row_size);
+        for id in 0..5 {
+            buffer.push(row(id))?;
+        }
+        assert_eq!(buffer.len(), 5);
+        assert!(buffer.is_spilled());
+
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let mut query_result = buffer.into_query_result(columns);
+        let mut copy = query_result.clone();
+        for id in 0..5 {
+            assert_eq!(query_result.next().await, Some(row(id)));
+        }
+        assert!(query_result.next().await.is_none());
+        assert_eq!(copy.next().await, Some(row(0)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_spill_file() -> Result<()> {
+        let mut spill_file = SpillFile::new()?;
+        let values = vec![
+            Value::Null,
+            Value::Bytes(vec![1, 2]),
+            Value::Array(vec![Value::Bool(true)]),
+        ];
+        spill_file.push(row(1))?;
+        spill_file.push(values.clone())?;
+        assert_eq!(spill_file.len(), 2);
+        assert_eq!(spill_file.get(1)?, Some(values));
+        assert_eq!(spill_file.get(0)?, Some(row(1)));
+        assert_eq!(spill_file.get(2)?, None);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_spill_file_read_error() -> Result<()> {
+        let mut spill_file = SpillFile::new()?;
+        spill_file.push(row(1))?;
+        spill_file.push(row(2))?;
+        spill_file.file.get_mut().expect("file").set_len(0)?;
+        assert!(spill_file.get(0).is_err());
+
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let mut query_result =
+            MemoryQueryResult::new(columns, vec![row(0)]).with_spilled_rows(spill_file);
+        assert_eq!(query_result.next().await, Some(row(0)));
+        assert!(query_result.take_error().is_none());
+        assert!(query_result.next().await.is_none());
+        assert!(query_result.next().await.is_none());
+        assert!(query_result.take_error().is_some());
+        assert!(query_result.take_error().is_none());
+        Ok(())
+    }
+}
@@ -1,18 +1,22 @@
+use crate::connect_options::connect_timeout;
+use crate::connection::limit_statement;
 use crate::error::Result;
 use crate::sqlite::metadata;
-use crate::url::UrlExtension;
+use crate::sqlite_options::SqliteOptions;
+use crate::url::file_path;
 use crate::value::Value;
-use crate::Error::UnsupportedColumnType;
-use crate::{MemoryQueryResult, Metadata, QueryResult, StatementMetadata};
+use crate::Error::{InvalidUrl, UnsupportedColumnType};
+use crate::{
+    ColumnMetadata, ConnectOptions, MemoryQueryResult, Metadata, QueryResult, StatementMetadata,
+};
 use async_trait::async_trait;
 use file_type::FileType;
 use sqlparser::ast::Statement;
 use sqlparser::dialect::{Dialect, SQLiteDialect};
+use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{SqliteAutoVacuum, SqliteColumn, SqliteConnectOptions, SqliteRow};
-use sqlx::{Column, Row, SqlitePool, TypeInfo};
-use std::collections::HashMap;
+use sqlx::{Column, Row, Sqlite, SqlitePool, TypeInfo};
 use std::str::FromStr;
-use url::Url;
 
 #[derive(Debug)]
 pub struct Driver;
@@ -28,7 +32,15 @@ impl crate::Driver for Driver {
         url: String,
         _password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url).await?;
+        let options = ConnectOptions::from_url(&url)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
@@ -41,28 +53,57 @@ impl crate::Driver for Driver {
 pub(crate) struct Connection {
     url: String,
     pool: SqlitePool,
+    read_only: Option<bool>,
 }
 
 impl Connection {
-    pub(crate) async fn new(url: String) -> Result<Connection> {
-        let parsed_url = Url::parse(url.as_str())?;
-        let database_url = if let Ok(file_name) = parsed_url.to_file() {
-            let file_name = file_name.to_string_lossy();
-            let params: HashMap<String, String> = parsed_url.query_pairs().into_owned().collect();
-            let query: String = form_urlencoded::Serializer::new(String::new())
-                .extend_pairs(params.iter())
-                .finish();
-            format!("sqlite://{file_name}?{query}").to_string()
-        } else {
-            "sqlite::memory:".to_string()
+    /// Connect with the options; the database is in memory when the path of the options is
+    /// not an existing file or the `mode` parameter is `memory`
+    pub(crate) async fn new(url: String, options: &ConnectOptions) -> Result<Connection> {
+        let sqlite_options = SqliteOptions::parse(options.parameters().clone())?;
+        let in_memory = sqlite_options
+            .parameters
+            .iter()
+            .any(|(key, value)| key == "mode" && value == "memory");
+        let path = format!("{}{}", options.host().unwrap_or_default(), options.path());
+        let mut options = match file_path(&path) {
+            Ok(file_name) if !in_memory => SqliteConnectOptions::new().filename(file_name),
+            _ => SqliteConnectOptions::from_str("sqlite::memory:")?,
+        }
+        .auto_vacuum(SqliteAutoVacuum::None)
+        .create_if_missing(true);
+        for (key, value) in sqlite_options.parameters {
+            options = with_parameter(options, &key, &value)?;
+        }
+        // Extensions are only loaded when they are explicitly specified in the url
+        for extension in sqlite_options.extensions {
+            options = options.extension(extension);
+        }
+        for (pragma, value) in sqlite_options.pragmas {
+            options = options.pragma(pragma, value);
+        }
+        let pool = SqlitePool::connect_with(options).await?;
+        let connection = Connection {
+            url,
+            pool,
+            read_only: None,
         };
 
-        let options = SqliteConnectOptions::from_str(database_url.as_str())?
-            .auto_vacuum(SqliteAutoVacuum::None)
-            .create_if_missing(true);
-        let pool = SqlitePool::connect_with(options).await?;
-        let connection = Connection { url, pool };
+        Ok(connection)
+    }
 
+    /// Get a connection from the pool; the read-only mode is applied to the connection since
+    /// pragmas are not shared by the connections of the pool
+    async fn acquire(&self) -> Result<PoolConnection<Sqlite>> {
+        let mut connection = self.pool.acquire().await?;
+        if let Some(read_only) = self.read_only {
+            let statement = if read_only {
+                "PRAGMA query_only = ON"
+            } else {
+                "PRAGMA query_only = OFF"
+            };
+            sqlx::query(statement).execute(&mut *connection).await?;
+        }
         Ok(connection)
     }
 }
@@ -74,7 +115,11 @@ impl crate::Connection for Connection {
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
-        let rows = sqlx::query(sql).execute(&self.pool).await?.rows_affected();
+        let mut connection = self.acquire().await?;
+        let rows = sqlx::query(sql)
+            .execute(&mut *connection)
+            .await?
+            .rows_affected();
         Ok(rows)
     }
 
@@ -83,7 +128,8 @@ impl crate::Connection for Connection {
     }
 
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
-        let query_rows = sqlx::query(sql).fetch_all(&self.pool).await?;
+        let mut connection = self.acquire().await?;
+        let query_rows = sqlx::query(sql).fetch_all(&mut *connection).await?;
         let columns: Vec<String> = query_rows
             .first()
             .map(|row| {
@@ -93,6 +139,18 @@ impl crate::Connection for Connection {
                     .collect()
             })
             .unwrap_or_default();
+        let columns_metadata: Vec<ColumnMetadata> = query_rows
+            .first()
+            .map(|row| {
+                row.columns()
+                    .iter()
+                    .map(|column| {
+                        ColumnMetadata::new(column.name())
+                            .with_data_type(column.type_info().name())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
 
         let mut rows = Vec::new();
         for row in query_rows {
@@ -104,7 +162,8 @@ impl crate::Connection for Connection {
             rows.push(row_data);
         }
 
-        let query_result = MemoryQueryResult::new(columns, rows);
+        let query_result =
+            MemoryQueryResult::new(columns, rows).with_columns_metadata(columns_metadata);
         Ok(Box::new(query_result))
     }
 
@@ -113,10 +172,19 @@ impl crate::Connection for Connection {
         Ok(())
     }
 
+    async fn set_read_only(&mut self, read_only: bool) -> Result<()> {
+        self.read_only = Some(read_only);
+        Ok(())
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(SQLiteDialect {})
     }
 
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
+
     fn match_statement(&self, statement: &Statement) -> StatementMetadata {
         let default = self.default_match_statement(statement);
         match default {
@@ -132,6 +200,8 @@ impl crate::Connection for Connection {
     }
 }
 
+impl crate::Capabilities for Connection {}
+
 impl Connection {
     fn convert_to_value(row: &SqliteRow, column: &SqliteColumn) -> Result<Value> {
         let column_name = column.name();
@@ -215,10 +285,31 @@ impl Connection {
     }
 }
 
+/// Set a parameter of the `SQLite` connection, e.g. `mode=ro` or `cache=shared`
+fn with_parameter(
+    options: SqliteConnectOptions,
+    key: &str,
+    value: &str,
+) -> Result<SqliteConnectOptions> {
+    let options = match (key, value) {
+        ("mode", "ro") => options.read_only(true),
+        ("mode", "rw" | "rwc" | "memory") => options,
+        ("cache", "shared") => options.shared_cache(true),
+        ("cache", "private") => options.shared_cache(false),
+        ("immutable", "true" | "1") => options.immutable(true),
+        ("immutable", "false" | "0") => options.immutable(false),
+        ("vfs", vfs) => options.vfs(vfs.to_string()),
+        _ => return Err(InvalidUrl(format!("Invalid parameter: {key}={value}"))),
+    };
+    Ok(options)
+}
+
 #[cfg(test)]
 mod test {
+    use super::Connection;
     use crate::test::dataset_url;
-    use crate::{DriverManager, Value};
+    use crate::Error::InvalidUrl;
+    use crate::{ConnectOptions, Connection as _, DriverManager, Value};
 
     const DATABASE_URL: &str = "sqlite://";
 
@@ -231,6 +322,87 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_pragma() -> anyhow::Result<()> {
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager
+            .connect("sqlite://?pragma_foreign_keys=ON")
+            .await?;
+
+        let mut query_result = connection.query("PRAGMA foreign_keys").await?;
+
+        assert_eq!(query_result.next().await, Some(vec![Value::I8(1)]));
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_only_applies_to_pooled_connections() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let path = directory.path().join("read_only.sqlite3");
+        let url = format!("sqlite://{}", path.to_string_lossy().replace('\\', "/"));
+        let options = ConnectOptions::from_url(&url)?;
+        let mut connection = Connection::new(url, &options).await?;
+        connection.execute("CREATE TABLE users (id INTEGER)").await?;
+        connection.set_read_only(true).await?;
+        connection.query("SELECT id FROM users").await?;
+
+        // Hold a connection of the pool so that the statement runs on a second connection
+        let held = connection.acquire().await?;
+        let result = connection.execute("INSERT INTO users (id) VALUES (1)").await;
+        assert!(result.is_err());
+        drop(held);
+
+        connection.set_read_only(false).await?;
+        assert_eq!(
+            connection
+                .execute("INSERT INTO users (id) VALUES (1)")
+                .await?,
+            1
+        );
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_driver_connect_with() -> anyhow::Result<()> {
+        let database_url = dataset_url("sqlite", "users.sqlite3");
+        let options = ConnectOptions::from_url(&database_url)?.with_parameter("mode", "ro");
+        let driver_manager = DriverManager::default();
+        let mut connection = driver_manager.connect_with(&options).await?;
+        let mut query_result = connection.query("SELECT COUNT(*) FROM users").await?;
+        assert_eq!(query_result.next().await, Some(vec![Value::I8(2)]));
+        assert!(connection.execute("DELETE FROM users").await.is_err());
+        connection.close().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_invalid_parameter() {
+        let driver_manager = DriverManager::default();
+        let result = driver_manager.connect("sqlite://?foo=bar").await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extensions_not_enabled() {
+        let driver_manager = DriverManager::default();
+        let result = driver_manager
+            .connect("sqlite://?extensions=/invalid/extension")
+            .await;
+        assert!(matches!(result, Err(InvalidUrl(_))));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_extension() {
+        let mut driver_manager = DriverManager::default();
+        driver_manager.set_load_extensions(true);
+        let result = driver_manager
+            .connect("sqlite://?extensions=/invalid/extension")
+            .await;
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_connection_interface() -> anyhow::Result<()> {
         let database_url = dataset_url("sqlite", "users.sqlite3");
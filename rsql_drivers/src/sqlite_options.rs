@@ -0,0 +1,125 @@
+use crate::error::Result;
+use crate::Error::InvalidUrl;
+use url::Url;
+
+/// Query parameter with a comma separated list of the paths of the extensions to load
+const EXTENSIONS: &str = "extensions";
+/// Prefix of the query parameters that set pragmas when connecting, e.g. `pragma_journal_mode=WAL`
+const PRAGMA_PREFIX: &str = "pragma_";
+
+/// Connection options for the `SQLite` drivers, parsed from the query parameters of the url
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SqliteOptions {
+    /// Paths of the extensions to load
+    pub(crate) extensions: Vec<String>,
+    /// Pragmas to set on the connection
+    pub(crate) pragmas: Vec<(String, String)>,
+    /// Query parameters that are not `SQLite` driver options
+    pub(crate) parameters: Vec<(String, String)>,
+}
+
+impl SqliteOptions {
+    /// Parse the options from the query parameters of the url; pragma names and values are
+    /// restricted to alphanumeric characters, `_`, `-` and `.` since they cannot be bound as
+    /// parameters of the `PRAGMA` statement.
+    pub(crate) fn parse<I>(query_pairs: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut options = SqliteOptions::default();
+        for (key, value) in query_pairs {
+            if key == EXTENSIONS {
+                options.extensions.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|extension| !extension.is_empty())
+                        .map(ToString::to_string),
+                );
+            } else if let Some(pragma) = key.strip_prefix(PRAGMA_PREFIX) {
+                let valid_name = !pragma.is_empty()
+                    && pragma
+                        .chars()
+                        .all(|character| character.is_ascii_alphanumeric() || character == '_');
+                if !valid_name {
+                    return Err(InvalidUrl(format!("Invalid pragma: {pragma}")));
+                }
+                let valid_value = !value.is_empty()
+                    && value.chars().all(|character| {
+                        character.is_ascii_alphanumeric() || matches!(character, '_' | '-' | '.')
+                    });
+                if !valid_value {
+                    return Err(InvalidUrl(format!(
+                        "Invalid value for pragma {pragma}: {value}"
+                    )));
+                }
+                options.pragmas.push((pragma.to_string(), value));
+            } else {
+                options.parameters.push((key, value));
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Returns true if the url of a `SQLite` driver specifies extensions to load
+pub(crate) fn has_extensions(url: &Url) -> bool {
+    matches!(url.scheme(), "rusqlite" | "sqlite")
+        && url.query_pairs().any(|(key, _)| key == EXTENSIONS)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(parameters: &[(&str, &str)]) -> Result<SqliteOptions> {
+        SqliteOptions::parse(
+            parameters
+                .iter()
+                .map(|(key, value)| ((*key).to_string(), (*value).to_string())),
+        )
+    }
+
+    #[test]
+    fn test_parse() -> Result<()> {
+        let options = parse(&[
+            ("extensions", "/lib/mod_spatialite.so, vss0"),
+            ("pragma_journal_mode", "WAL"),
+            ("pragma_busy_timeout", "5000"),
+            ("mode", "ro"),
+        ])?;
+        assert_eq!(
+            options.extensions,
+            vec!["/lib/mod_spatialite.so".to_string(), "vss0".to_string()]
+        );
+        assert_eq!(
+            options.pragmas,
+            vec![
+                ("journal_mode".to_string(), "WAL".to_string()),
+                ("busy_timeout".to_string(), "5000".to_string()),
+            ]
+        );
+        assert_eq!(
+            options.parameters,
+            vec![("mode".to_string(), "ro".to_string())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_extensions() -> anyhow::Result<()> {
+        assert!(has_extensions(&Url::parse("sqlite://?extensions=vss0")?));
+        assert!(has_extensions(&Url::parse("rusqlite://?extensions=vss0")?));
+        assert!(!has_extensions(&Url::parse("sqlite://?mode=memory")?));
+        assert!(!has_extensions(&Url::parse("duckdb://?extensions=httpfs")?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid_pragma() {
+        assert!(parse(&[("pragma_", "WAL")]).is_err());
+        assert!(parse(&[("pragma_journal mode", "WAL")]).is_err());
+        assert!(parse(&[("pragma_journal_mode", "")]).is_err());
+        assert!(parse(&[("pragma_journal_mode", "WAL; DROP TABLE users")]).is_err());
+    }
+}
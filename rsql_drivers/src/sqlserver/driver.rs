@@ -1,13 +1,17 @@
+use crate::connect_options::connect_timeout;
+use crate::connection::limit_statement;
 use crate::error::Result;
+use crate::messages::capture_messages;
 use crate::sqlserver::metadata;
 use crate::value::Value;
-use crate::Error::UnsupportedColumnType;
-use crate::{MemoryQueryResult, Metadata, QueryResult};
+use crate::Error::{InvalidUrl, UnsupportedColumnType};
+use crate::{ConnectOptions, MemoryQueryResult, Metadata, QueryResult, StatementMetadata, TlsMode};
 use async_trait::async_trait;
 use file_type::FileType;
 use futures_util::stream::TryStreamExt;
+use sqlparser::ast::Statement;
 use sqlparser::dialect::{Dialect, MsSqlDialect};
-use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::string::ToString;
 use tiberius::{AuthMethod, Client, Column, Config, EncryptionLevel, QueryItem, Row};
 use tokio::net::TcpStream;
@@ -27,7 +31,15 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let connection = Connection::new(url, password).await?;
+        let options = ConnectOptions::from_connection_url(&url, password)?;
+        let connection = Connection::new(url, &options).await?;
+        Ok(Box::new(connection))
+    }
+
+    async fn connect_with(&self, options: &ConnectOptions) -> Result<Box<dyn crate::Connection>> {
+        let url = options.to_url()?;
+        let connection =
+            connect_timeout(options.connect_timeout(), Connection::new(url, options)).await?;
         Ok(Box::new(connection))
     }
 
@@ -40,58 +52,60 @@ impl crate::Driver for Driver {
 pub(crate) struct Connection {
     url: String,
     client: Client<Compat<TcpStream>>,
+    /// Result sets of the last query after the first, e.g. from a stored procedure
+    result_sets: VecDeque<MemoryQueryResult>,
+    /// Informational messages reported by the server for the last statement
+    messages: Vec<String>,
 }
 
 impl Connection {
-    pub(crate) async fn new(url: String, password: Option<String>) -> Result<Connection> {
-        let parsed_url = url::Url::parse(url.as_str())?;
-        let mut params: HashMap<String, String> = parsed_url.query_pairs().into_owned().collect();
-        let trust_server_certificate = params
-            .remove("TrustServerCertificate")
-            .is_some_and(|value| value == "true");
-        let encryption = params
-            .remove("encryption")
-            .unwrap_or("required".to_string());
-
-        let host = parsed_url.host_str().unwrap_or("localhost");
-        let port = parsed_url.port().unwrap_or(1433);
-        let database = parsed_url.path().replace('/', "");
-        let username = parsed_url.username();
-
-        let mut config = Config::new();
-        config.host(host);
-        config.port(port);
-
-        if !database.is_empty() {
-            config.database(database);
-        }
-
-        if !username.is_empty() {
-            config.authentication(AuthMethod::sql_server(
-                username,
-                password.expect("password is required"),
-            ));
-        }
-
-        if trust_server_certificate {
-            config.trust_cert();
-        }
-
-        match encryption.as_str() {
-            "off" => config.encryption(EncryptionLevel::Off),
-            "on" => config.encryption(EncryptionLevel::On),
-            "not_supported" => config.encryption(EncryptionLevel::NotSupported),
-            _ => config.encryption(EncryptionLevel::Required),
-        }
-
+    pub(crate) async fn new(url: String, options: &ConnectOptions) -> Result<Connection> {
+        let config = config(options)?;
         let tcp = TcpStream::connect(config.get_addr()).await?;
         tcp.set_nodelay(true)?;
 
         let client = Client::connect(config, tcp.compat_write()).await?;
-        let connection = Connection { url, client };
+        let connection = Connection {
+            url,
+            client,
+            result_sets: VecDeque::new(),
+            messages: Vec::new(),
+        };
 
         Ok(connection)
     }
+
+    /// Execute the query and read all the result sets returned
+    async fn query_result_sets(&mut self, sql: &str) -> Result<VecDeque<MemoryQueryResult>> {
+        let mut query_stream = self.client.query(sql, &[]).await?;
+        let mut result_sets: Vec<(Vec<String>, Vec<crate::Row>)> = Vec::new();
+
+        while let Some(item) = query_stream.try_next().await? {
+            if let QueryItem::Metadata(meta) = item {
+                let columns = meta
+                    .columns()
+                    .iter()
+                    .map(|column| column.name().to_string())
+                    .collect();
+                result_sets.push((columns, Vec::new()));
+            } else if let QueryItem::Row(row) = item {
+                let mut row_data = Vec::new();
+                for (index, column) in row.columns().iter().enumerate() {
+                    let value = convert_to_value(&row, column, index)?;
+                    row_data.push(value);
+                }
+                if let Some((_, rows)) = result_sets.last_mut() {
+                    rows.push(row_data);
+                }
+            }
+        }
+
+        let result_sets = result_sets
+            .into_iter()
+            .map(|(columns, rows)| MemoryQueryResult::new(columns, rows))
+            .collect();
+        Ok(result_sets)
+    }
 }
 
 #[async_trait]
@@ -101,8 +115,10 @@ impl crate::Connection for Connection {
     }
 
     async fn execute(&mut self, sql: &str) -> Result<u64> {
-        let result = self.client.execute(sql, &[]).await?;
-        let rows = result.rows_affected()[0];
+        self.result_sets.clear();
+        let (result, messages) = capture_messages("tiberius", self.client.execute(sql, &[])).await;
+        self.messages = messages.into_iter().map(|(_, message)| message).collect();
+        let rows = result?.rows_affected().first().copied().unwrap_or_default();
         Ok(rows)
     }
 
@@ -111,40 +127,59 @@ impl crate::Connection for Connection {
     }
 
     async fn query(&mut self, sql: &str) -> Result<Box<dyn QueryResult>> {
-        let mut query_stream = self.client.query(sql, &[]).await?;
-        let mut columns: Vec<String> = Vec::new();
-
-        let mut rows = Vec::new();
-        while let Some(item) = query_stream.try_next().await? {
-            if let QueryItem::Metadata(meta) = item {
-                if meta.result_index() == 0 {
-                    for column in meta.columns() {
-                        columns.push(column.name().to_string());
-                    }
-                }
-            } else if let QueryItem::Row(row) = item {
-                let mut row_data = Vec::new();
-                if row.result_index() == 0 {
-                    for (index, column) in row.columns().iter().enumerate() {
-                        let value = convert_to_value(&row, column, index)?;
-                        row_data.push(value);
-                    }
-                }
-                rows.push(row_data);
-            }
-        }
-
-        let query_result = MemoryQueryResult::new(columns, rows);
+        self.result_sets.clear();
+        let (result_sets, messages) =
+            capture_messages("tiberius", self.query_result_sets(sql)).await;
+        self.messages = messages.into_iter().map(|(_, message)| message).collect();
+        let mut result_sets = result_sets?;
+        let query_result = result_sets
+            .pop_front()
+            .unwrap_or_else(|| MemoryQueryResult::new(Vec::new(), Vec::new()));
+        self.result_sets = result_sets;
         Ok(Box::new(query_result))
     }
 
+    fn messages(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.messages)
+    }
+
     async fn close(&mut self) -> Result<()> {
         Ok(())
     }
 
+    fn begin_transaction_statement(&self) -> Option<&'static str> {
+        Some("BEGIN TRANSACTION")
+    }
+
     fn dialect(&self) -> Box<dyn Dialect> {
         Box::new(MsSqlDialect {})
     }
+
+    fn limit_sql(&self, sql: &str, limit: usize) -> Option<String> {
+        limit_statement(self.dialect().as_ref(), sql, limit)
+    }
+
+    fn match_statement(&self, statement: &Statement) -> StatementMetadata {
+        match statement {
+            // Stored procedures may return result sets
+            Statement::Execute { .. } => StatementMetadata::Query,
+            _ => self.default_match_statement(statement),
+        }
+    }
+}
+
+#[async_trait]
+impl crate::MultipleResults for Connection {
+    async fn next_result(&mut self) -> Result<Option<Box<dyn QueryResult>>> {
+        let query_result = self.result_sets.pop_front();
+        Ok(query_result.map(|query_result| Box::new(query_result) as Box<dyn QueryResult>))
+    }
+}
+
+impl crate::Capabilities for Connection {
+    fn as_multiple_results(&mut self) -> Option<&mut dyn crate::MultipleResults> {
+        Some(self)
+    }
 }
 
 #[expect(clippy::same_functions_in_if_condition)]
@@ -208,7 +243,7 @@ fn convert_to_value(row: &Row, column: &Column, index: usize) -> Result<Value> {
     } else if let Ok(value) = row.try_get(column_name) {
         let value: Option<rust_decimal::Decimal> = value;
         match value {
-            Some(v) => Ok(Value::String(v.to_string())),
+            Some(v) => Ok(Value::Decimal(v)),
             None => Ok(Value::Null),
         }
     } else if let Ok(value) = row.try_get(column_name) {
@@ -238,3 +273,74 @@ fn convert_to_value(row: &Row, column: &Column, index: usize) -> Result<Value> {
         });
     }
 }
+
+/// Build the `tiberius` configuration from the options; the `encryption` and
+/// `TrustServerCertificate` parameters take precedence over the TLS mode
+fn config(options: &ConnectOptions) -> Result<Config> {
+    let parameters = options.parameters();
+    let (mut encryption, mut trust_server_certificate) = match options.tls() {
+        Some(TlsMode::Disable) => (EncryptionLevel::Off, false),
+        Some(TlsMode::Require) => (EncryptionLevel::Required, true),
+        Some(TlsMode::VerifyFull) | None => (EncryptionLevel::Required, false),
+    };
+    if let Some(value) = parameters.get("encryption") {
+        encryption = match value.as_str() {
+            "off" => EncryptionLevel::Off,
+            "on" => EncryptionLevel::On,
+            "not_supported" => EncryptionLevel::NotSupported,
+            _ => EncryptionLevel::Required,
+        };
+    }
+    if let Some(value) = parameters.get("TrustServerCertificate") {
+        trust_server_certificate = value == "true";
+    }
+    let azure = parameters
+        .get("auth")
+        .is_some_and(|value| value.eq_ignore_ascii_case("azure"));
+
+    let mut config = Config::new();
+    config.host(options.host().unwrap_or("localhost"));
+    config.port(options.port().unwrap_or(1433));
+
+    if let Some(database) = options.database() {
+        config.database(database);
+    }
+
+    if azure {
+        let token = options.password().ok_or_else(|| {
+            InvalidUrl("Azure AD authentication requires the azure-ad feature".to_string())
+        })?;
+        config.authentication(AuthMethod::aad_token(token));
+    } else if let Some(username) = options.username() {
+        let password = options
+            .password()
+            .ok_or_else(|| InvalidUrl("password is required".to_string()))?;
+        config.authentication(AuthMethod::sql_server(username, password));
+    }
+
+    if trust_server_certificate {
+        config.trust_cert();
+    }
+    config.encryption(encryption);
+    Ok(config)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config() -> Result<()> {
+        let url = "sqlserver://sa@db.example.com:1434/app?encryption=off";
+        let options = ConnectOptions::from_connection_url(url, Some("secret".to_string()))?
+            .with_tls(TlsMode::Require);
+        let tiberius_config = config(&options)?;
+        assert_eq!(tiberius_config.get_addr(), "db.example.com:1434");
+
+        let options = ConnectOptions::from_url("sqlserver://sa@localhost")?;
+        assert!(config(&options).is_err());
+        let options = ConnectOptions::from_url("sqlserver://localhost?auth=azure")?;
+        assert!(config(&options).is_err());
+        Ok(())
+    }
+}
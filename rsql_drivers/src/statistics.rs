@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+/// Statistics reported by the database server for the last statement executed on a connection
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct QueryStatistics {
+    /// Number of bytes scanned by the server
+    pub bytes_scanned: Option<u64>,
+    /// Execution time reported by the server; this excludes network and client overhead
+    pub execution_time: Option<Duration>,
+    /// Number of rows inserted, updated or deleted
+    pub rows_affected: Option<u64>,
+    /// Number of rows scanned by the server
+    pub rows_scanned: Option<u64>,
+}
+
+impl QueryStatistics {
+    /// Returns true if none of the statistics are available
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bytes_scanned.is_none()
+            && self.execution_time.is_none()
+            && self.rows_affected.is_none()
+            && self.rows_scanned.is_none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_empty() {
+        assert!(QueryStatistics::default().is_empty());
+        let statistics = QueryStatistics {
+            bytes_scanned: Some(1024),
+            ..Default::default()
+        };
+        assert!(!statistics.is_empty());
+    }
+}
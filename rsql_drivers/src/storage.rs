@@ -0,0 +1,379 @@
+use crate::error::Result;
+use crate::url::UrlExtension;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use url::Url;
+
+#[cfg(feature = "object-store")]
+use crate::Error::{InvalidUrl, IoError};
+#[cfg(feature = "compression")]
+use flate2::read::MultiGzDecoder;
+#[cfg(feature = "object-store")]
+use object_store::aws::{AmazonS3Builder, AmazonS3ConfigKey};
+#[cfg(feature = "object-store")]
+use object_store::azure::{AzureConfigKey, MicrosoftAzureBuilder};
+#[cfg(feature = "object-store")]
+use object_store::gcp::{GoogleCloudStorageBuilder, GoogleConfigKey};
+#[cfg(feature = "object-store")]
+use object_store::ObjectStore;
+#[cfg(feature = "object-store")]
+use std::collections::HashMap;
+#[cfg(feature = "compression")]
+use std::ffi::OsStr;
+#[cfg(feature = "compression")]
+use std::fs::File;
+#[cfg(feature = "compression")]
+use std::io;
+#[cfg(feature = "object-store")]
+use std::str::FromStr;
+
+/// URL schemes of files stored in an object store
+const OBJECT_STORE_SCHEMES: [&str; 8] = ["abfs", "abfss", "adl", "az", "azure", "gs", "s3", "s3a"];
+
+/// A local file for a file driver URL. Files in an object store are downloaded, and compressed
+/// files are decompressed, to a temporary directory that is removed when the local file is dropped.
+#[derive(Debug)]
+pub(crate) struct LocalFile {
+    path: PathBuf,
+    temp_dir: Option<TempDir>,
+}
+
+impl LocalFile {
+    /// Get the path of the local file
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the file name of the local file
+    pub(crate) fn file_name(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+
+    /// Check if the local file is a temporary copy of the file, e.g. a file downloaded from an
+    /// object store or a decompressed file
+    pub(crate) fn is_temporary(&self) -> bool {
+        self.temp_dir.is_some()
+    }
+}
+
+/// Get a local file for a file driver URL; the URL may either reference a local file (e.g.
+/// `csv://data.csv`) or a file in an object store (e.g. `csv://s3://bucket/data.csv`). Files with
+/// a `.gz` or `.zst` extension are decompressed (e.g. `csv://data.csv.gz`).
+///
+/// # Errors
+/// if the local file does not exist, the file cannot be downloaded from the object store or the
+/// file cannot be decompressed
+pub(crate) async fn local_file<S: AsRef<str>>(url: S) -> Result<LocalFile> {
+    let local_file = file(url.as_ref()).await?;
+    decompress(local_file)
+}
+
+/// Get the local file, downloading the file if it is stored in an object store
+async fn file(url: &str) -> Result<LocalFile> {
+    match object_store_url(url)? {
+        #[cfg(feature = "object-store")]
+        Some(object_store_url) => {
+            let parsed_url = Url::parse(url)?;
+            let options: HashMap<String, String> = parsed_url.query_pairs().into_owned().collect();
+            download(&object_store_url, &options).await
+        }
+        #[cfg(not(feature = "object-store"))]
+        Some(object_store_url) => Err(crate::Error::InvalidUrl(format!(
+            "object store support is not enabled: {object_store_url}"
+        ))),
+        None => {
+            let parsed_url = Url::parse(url)?;
+            Ok(LocalFile {
+                path: parsed_url.to_file()?,
+                temp_dir: None,
+            })
+        }
+    }
+}
+
+/// Get the object store URL from a file driver URL (e.g. `s3://bucket/data.parquet` from
+/// `parquet://s3://bucket/data.parquet?region=us-east-1`); returns `None` for local files.
+fn object_store_url(url: &str) -> Result<Option<Url>> {
+    let Some((_, path)) = url.split_once("://") else {
+        return Ok(None);
+    };
+    let path = path.split('?').next().unwrap_or(path);
+    let Some((scheme, _)) = path.split_once("://") else {
+        return Ok(None);
+    };
+    if !OBJECT_STORE_SCHEMES.contains(&scheme.to_lowercase().as_str()) {
+        return Ok(None);
+    }
+    Ok(Some(Url::parse(path)?))
+}
+
+/// Decompress a file with a `.gz` (gzip) or `.zst` (zstandard) extension to a temporary directory;
+/// the compression extension is removed from the file name so that `data.csv.gz` becomes `data.csv`.
+#[cfg(feature = "compression")]
+fn decompress(local_file: LocalFile) -> Result<LocalFile> {
+    let Some(extension) = local_file.path.extension().and_then(OsStr::to_str) else {
+        return Ok(local_file);
+    };
+    let extension = extension.to_lowercase();
+    if extension != "gz" && extension != "zst" {
+        return Ok(local_file);
+    }
+    let Some(file_name) = local_file.path.file_stem() else {
+        return Ok(local_file);
+    };
+
+    let temp_dir = tempfile::tempdir()?;
+    let path = temp_dir.path().join(file_name);
+    let file = File::open(&local_file.path)?;
+    let mut decompressed_file = File::create(&path)?;
+    if extension == "gz" {
+        io::copy(&mut MultiGzDecoder::new(file), &mut decompressed_file)?;
+    } else {
+        io::copy(&mut zstd::Decoder::new(file)?, &mut decompressed_file)?;
+    }
+
+    Ok(LocalFile {
+        path,
+        temp_dir: Some(temp_dir),
+    })
+}
+
+/// Compressed files are read as is when compression support is not enabled
+#[cfg(not(feature = "compression"))]
+#[expect(clippy::unnecessary_wraps)]
+fn decompress(local_file: LocalFile) -> Result<LocalFile> {
+    Ok(local_file)
+}
+
+/// Download a file from an object store to a temporary directory
+#[cfg(feature = "object-store")]
+async fn download(url: &Url, options: &HashMap<String, String>) -> Result<LocalFile> {
+    let store = build_object_store(url, options)?;
+    let path = object_store::path::Path::from_url_path(url.path())
+        .map_err(|error| InvalidUrl(error.to_string()))?;
+    let file_name = path
+        .filename()
+        .ok_or_else(|| InvalidUrl(format!("Invalid file url: {url}")))?
+        .to_string();
+    let bytes = store
+        .get(&path)
+        .await
+        .map_err(|error| IoError(error.into()))?
+        .bytes()
+        .await
+        .map_err(|error| IoError(error.into()))?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let local_path = temp_dir.path().join(file_name);
+    tokio::fs::write(&local_path, bytes).await?;
+    Ok(LocalFile {
+        path: local_path,
+        temp_dir: Some(temp_dir),
+    })
+}
+
+/// Create an object store for the URL. Credentials are resolved from the standard environment
+/// variables for each provider and, for S3, the AWS shared credentials and config files; URL query
+/// parameters that match a provider configuration key (e.g. `region`) override these values.
+#[cfg(feature = "object-store")]
+fn build_object_store(
+    url: &Url,
+    options: &HashMap<String, String>,
+) -> Result<Box<dyn ObjectStore>> {
+    let object_store: Box<dyn ObjectStore> = match url.scheme() {
+        "s3" | "s3a" => {
+            let mut builder = AmazonS3Builder::from_env().with_url(url.as_str());
+            for (key, value) in aws_profile() {
+                if let Ok(key) = AmazonS3ConfigKey::from_str(&key) {
+                    if builder.get_config_value(&key).is_none() {
+                        builder = builder.with_config(key, value);
+                    }
+                }
+            }
+            for (key, value) in options {
+                if let Ok(key) = AmazonS3ConfigKey::from_str(key) {
+                    builder = builder.with_config(key, value);
+                }
+            }
+            Box::new(builder.build().map_err(|error| IoError(error.into()))?)
+        }
+        "gs" => {
+            let mut builder = GoogleCloudStorageBuilder::from_env().with_url(url.as_str());
+            for (key, value) in options {
+                if let Ok(key) = GoogleConfigKey::from_str(key) {
+                    builder = builder.with_config(key, value);
+                }
+            }
+            Box::new(builder.build().map_err(|error| IoError(error.into()))?)
+        }
+        _ => {
+            let mut builder = MicrosoftAzureBuilder::from_env().with_url(url.as_str());
+            for (key, value) in options {
+                if let Ok(key) = AzureConfigKey::from_str(key) {
+                    builder = builder.with_config(key, value);
+                }
+            }
+            Box::new(builder.build().map_err(|error| IoError(error.into()))?)
+        }
+    };
+    Ok(object_store)
+}
+
+/// Read the credentials and region of the current AWS profile (`AWS_PROFILE` or `default`) from
+/// the AWS shared credentials and config files.
+#[cfg(feature = "object-store")]
+fn aws_profile() -> HashMap<String, String> {
+    let profile = std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".to_string());
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let credentials_file = std::env::var_os("AWS_SHARED_CREDENTIALS_FILE")
+        .map_or_else(|| home.join(".aws").join("credentials"), PathBuf::from);
+    let config_file = std::env::var_os("AWS_CONFIG_FILE")
+        .map_or_else(|| home.join(".aws").join("config"), PathBuf::from);
+
+    let mut values = HashMap::new();
+    if let Ok(config) = std::fs::read_to_string(config_file) {
+        let section = if profile == "default" {
+            profile.clone()
+        } else {
+            format!("profile {profile}")
+        };
+        values.extend(read_ini_section(&config, &section));
+    }
+    if let Ok(credentials) = std::fs::read_to_string(credentials_file) {
+        values.extend(read_ini_section(&credentials, &profile));
+    }
+    values
+}
+
+/// Read the key/value pairs of a section from an INI formatted file
+#[cfg(feature = "object-store")]
+fn read_ini_section(contents: &str, section: &str) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let mut in_section = false;
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            in_section = name.trim() == section;
+            continue;
+        }
+        if in_section {
+            if let Some((key, value)) = line.split_once('=') {
+                values.insert(key.trim().to_lowercase(), value.trim().to_string());
+            }
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::dataset_url;
+
+    #[test]
+    fn test_object_store_url() -> Result<()> {
+        let url = object_store_url("parquet://s3://bucket/path/users.parquet?region=us-east-1")?;
+        assert_eq!(
+            url.as_ref().map(Url::as_str),
+            Some("s3://bucket/path/users.parquet")
+        );
+        let url = object_store_url("csv://gs://bucket/users.csv")?;
+        assert_eq!(url.as_ref().map(Url::as_str), Some("gs://bucket/users.csv"));
+        let url = object_store_url("json://az://container/users.json")?;
+        assert_eq!(
+            url.as_ref().map(Url::as_str),
+            Some("az://container/users.json")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_object_store_url_local_file() -> Result<()> {
+        assert!(object_store_url("csv://users.csv")?.is_none());
+        assert!(object_store_url("csv:///tmp/users.csv?has_header=true")?.is_none());
+        assert!(object_store_url("csv://foo://users.csv")?.is_none());
+        assert!(object_store_url("csv:")?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_file() -> Result<()> {
+        let url = dataset_url("csv", "users.csv");
+        let local_file = local_file(&url).await?;
+        assert!(local_file.path().exists());
+        assert!(local_file.file_name().ends_with("users.csv"));
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_local_file_gzip() -> Result<()> {
+        let url = dataset_url("csv", "users.csv.gz");
+        let local_file = local_file(&url).await?;
+        assert!(local_file.file_name().ends_with("users.csv"));
+        let contents = std::fs::read_to_string(local_file.path())?;
+        assert!(contents.starts_with("id,name"));
+        Ok(())
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_local_file_zstd() -> Result<()> {
+        let url = dataset_url("jsonl", "users.jsonl.zst");
+        let local_file = local_file(&url).await?;
+        let path = local_file.path().to_path_buf();
+        assert!(local_file.file_name().ends_with("users.jsonl"));
+        let contents = std::fs::read_to_string(&path)?;
+        assert!(contents.contains("John Doe"));
+        drop(local_file);
+        assert!(!path.exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_local_file_not_found() {
+        assert!(local_file("csv:///foo.csv").await.is_err());
+    }
+
+    #[cfg(feature = "object-store")]
+    #[test]
+    fn test_read_ini_section() {
+        let contents = indoc::indoc! {r"
+            # comment
+            [default]
+            aws_access_key_id = default_key
+
+            [profile test]
+            aws_access_key_id = test_key
+            aws_secret_access_key=test_secret
+            region = us-west-2
+        "};
+
+        let values = read_ini_section(contents, "profile test");
+        assert_eq!(values.len(), 3);
+        assert_eq!(
+            values.get("aws_access_key_id"),
+            Some(&"test_key".to_string())
+        );
+        assert_eq!(
+            values.get("aws_secret_access_key"),
+            Some(&"test_secret".to_string())
+        );
+        assert_eq!(values.get("region"), Some(&"us-west-2".to_string()));
+        assert!(read_ini_section(contents, "missing").is_empty());
+    }
+
+    #[cfg(not(feature = "object-store"))]
+    #[tokio::test]
+    async fn test_local_file_object_store_not_enabled() {
+        assert!(local_file("csv://s3://bucket/users.csv").await.is_err());
+    }
+}
@@ -17,7 +17,8 @@ impl crate::Driver for Driver {
         url: String,
         password: Option<String>,
     ) -> Result<Box<dyn crate::Connection>> {
-        let url = format!("{url}?separator=%09");
+        let query_separator = if url.contains('?') { '&' } else { '?' };
+        let url = format!("{url}{query_separator}separator=%09");
         DelimitedDriver.connect(url, password).await
     }
 
@@ -1,4 +1,4 @@
-use crate::Error::IoError;
+use crate::Error::{InvalidUrl, IoError};
 use crate::Result;
 use anyhow::anyhow;
 use std::path::{PathBuf, MAIN_SEPARATOR_STR};
@@ -18,26 +18,35 @@ impl UrlExtension for Url {
         let scheme = self.scheme();
         let start_index = scheme.len() + 3;
         let end_index = url.find('?').unwrap_or(url.len());
-        let path = &url[start_index..end_index];
-
-        #[cfg(target_os = "windows")]
-        let path = if path.contains(':') {
-            // Strip preceding '/' character for Windows absolute path (e.g. /C:/foo)
-            path.strip_prefix('/').unwrap_or(path)
-        } else {
-            path
-        };
-
-        let path = path.replace('/', MAIN_SEPARATOR_STR);
-        let file_path = PathBuf::from(path);
-        if !file_path.exists() && !file_path.is_file() {
-            let file_path = file_path.to_string_lossy();
-            return Err(IoError(anyhow!("File not found: {file_path}")));
-        }
-        Ok(file_path)
+        let path = url
+            .get(start_index..end_index)
+            .ok_or_else(|| InvalidUrl(format!("Invalid file url: {url}")))?;
+        file_path(path)
     }
 }
 
+/// Get the path of an existing file from the path of a URL
+///
+/// # Errors
+/// if the file does not exist
+pub(crate) fn file_path(path: &str) -> Result<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let path = if path.contains(':') {
+        // Strip preceding '/' character for Windows absolute path (e.g. /C:/foo)
+        path.strip_prefix('/').unwrap_or(path)
+    } else {
+        path
+    };
+
+    let path = path.replace('/', MAIN_SEPARATOR_STR);
+    let file_path = PathBuf::from(path);
+    if !file_path.exists() && !file_path.is_file() {
+        let file_path = file_path.to_string_lossy();
+        return Err(IoError(anyhow!("File not found: {file_path}")));
+    }
+    Ok(file_path)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -58,4 +67,18 @@ mod test {
         assert!(url.to_file().is_err());
         Ok(())
     }
+
+    #[test]
+    fn test_file_without_authority() -> Result<()> {
+        let url = Url::parse("csv:")?;
+        assert!(url.to_file().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_query_before_path() -> Result<()> {
+        let url = Url::parse("csv:?")?;
+        assert!(url.to_file().is_err());
+        Ok(())
+    }
 }
@@ -1,7 +1,10 @@
+use crate::{DateFormat, Interval};
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
 use indexmap::IndexMap;
 use num_format::{Locale, ToFormattedString};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use serde::{Serialize, Serializer};
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -23,19 +26,154 @@ pub enum Value {
     U128(u128),
     F32(f32),
     F64(f64),
+    Decimal(Decimal),
     String(String),
     Date(chrono::NaiveDate),
     Time(chrono::NaiveTime),
     DateTime(chrono::NaiveDateTime),
+    Interval(Interval),
     Uuid(uuid::Uuid),
     Json(serde_json::Value),
     Array(Vec<Value>),
     Map(IndexMap<Value, Value>),
 }
 
+/// The kind of a [Value], without the data; e.g. the kind of `Value::I64(42)` is `ValueKind::I64`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ValueKind {
+    Bool,
+    Bytes,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+    Decimal,
+    String,
+    Date,
+    Time,
+    DateTime,
+    Interval,
+    Uuid,
+    Json,
+    Array,
+    Map,
+}
+
+impl ValueKind {
+    /// Get the name of the kind, e.g. `i64` or `datetime`
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            ValueKind::Bool => "bool",
+            ValueKind::Bytes => "bytes",
+            ValueKind::I8 => "i8",
+            ValueKind::I16 => "i16",
+            ValueKind::I32 => "i32",
+            ValueKind::I64 => "i64",
+            ValueKind::I128 => "i128",
+            ValueKind::U8 => "u8",
+            ValueKind::U16 => "u16",
+            ValueKind::U32 => "u32",
+            ValueKind::U64 => "u64",
+            ValueKind::U128 => "u128",
+            ValueKind::F32 => "f32",
+            ValueKind::F64 => "f64",
+            ValueKind::Decimal => "decimal",
+            ValueKind::String => "string",
+            ValueKind::Date => "date",
+            ValueKind::Time => "time",
+            ValueKind::DateTime => "datetime",
+            ValueKind::Interval => "interval",
+            ValueKind::Uuid => "uuid",
+            ValueKind::Json => "json",
+            ValueKind::Array => "array",
+            ValueKind::Map => "map",
+        }
+    }
+
+    /// Returns true if values of the kind are numeric
+    #[must_use]
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            ValueKind::I8
+                | ValueKind::I16
+                | ValueKind::I32
+                | ValueKind::I64
+                | ValueKind::I128
+                | ValueKind::U8
+                | ValueKind::U16
+                | ValueKind::U32
+                | ValueKind::U64
+                | ValueKind::U128
+                | ValueKind::F32
+                | ValueKind::F64
+                | ValueKind::Decimal
+        )
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 impl Value {
+    /// Get the kind of the value; returns `None` for null values, which have no kind.
+    #[must_use]
+    pub fn kind(&self) -> Option<ValueKind> {
+        let kind = match self {
+            Value::Null => return None,
+            Value::Bool(_) => ValueKind::Bool,
+            Value::Bytes(_) => ValueKind::Bytes,
+            Value::I8(_) => ValueKind::I8,
+            Value::I16(_) => ValueKind::I16,
+            Value::I32(_) => ValueKind::I32,
+            Value::I64(_) => ValueKind::I64,
+            Value::I128(_) => ValueKind::I128,
+            Value::U8(_) => ValueKind::U8,
+            Value::U16(_) => ValueKind::U16,
+            Value::U32(_) => ValueKind::U32,
+            Value::U64(_) => ValueKind::U64,
+            Value::U128(_) => ValueKind::U128,
+            Value::F32(_) => ValueKind::F32,
+            Value::F64(_) => ValueKind::F64,
+            Value::Decimal(_) => ValueKind::Decimal,
+            Value::String(_) => ValueKind::String,
+            Value::Date(_) => ValueKind::Date,
+            Value::Time(_) => ValueKind::Time,
+            Value::DateTime(_) => ValueKind::DateTime,
+            Value::Interval(_) => ValueKind::Interval,
+            Value::Uuid(_) => ValueKind::Uuid,
+            Value::Json(_) => ValueKind::Json,
+            Value::Array(_) => ValueKind::Array,
+            Value::Map(_) => ValueKind::Map,
+        };
+        Some(kind)
+    }
+
     #[must_use]
     pub fn to_formatted_string(&self, locale: &Locale) -> String {
+        self.to_formatted_string_with(locale, &DateFormat::default())
+    }
+
+    /// Format the value for the locale, with date, time and date time values, including those in
+    /// arrays and maps, formatted using the patterns of the date format.
+    #[must_use]
+    pub fn to_formatted_string_with(&self, locale: &Locale, date_format: &DateFormat) -> String {
+        if let Some(formatted) = date_format.format(self) {
+            return formatted;
+        }
+
         match self {
             Value::Null => "null".to_string(),
             Value::Bool(value) => value.to_string(),
@@ -52,17 +190,19 @@ impl Value {
             Value::U128(value) => value.to_formatted_string(locale),
             Value::F32(value) => value.to_string(),
             Value::F64(value) => value.to_string(),
+            Value::Decimal(value) => format_decimal(value, *locale),
             Value::String(value) => value.to_string(),
             Value::Date(value) => value.to_string(),
             Value::Time(value) => value.to_string(),
             Value::DateTime(value) => value.to_string(),
+            Value::Interval(value) => value.to_formatted_string(locale),
             Value::Uuid(value) => value.to_string(),
             Value::Json(value) => value.to_string(),
             Value::Array(value) => {
                 let list_delimiter = t!("list_delimiter", locale = locale.name()).to_string();
                 value
                     .iter()
-                    .map(|value| value.to_formatted_string(locale))
+                    .map(|value| value.to_formatted_string_with(locale, date_format))
                     .collect::<Vec<String>>()
                     .join(list_delimiter.as_str())
             }
@@ -75,9 +215,9 @@ impl Value {
                     .map(|(key, value)| {
                         format!(
                             "{}{}{}",
-                            key.to_formatted_string(locale),
+                            key.to_formatted_string_with(locale, date_format),
                             key_value_delimiter,
-                            value.to_formatted_string(locale)
+                            value.to_formatted_string_with(locale, date_format)
                         )
                     })
                     .collect::<Vec<String>>()
@@ -97,10 +237,76 @@ impl Value {
         match self {
             Value::I8(_) | Value::I16(_) | Value::I32(_) | Value::I64(_) | Value::I128(_) => true,
             Value::U8(_) | Value::U16(_) | Value::U32(_) | Value::U64(_) | Value::U128(_) => true,
-            Value::F32(_) | Value::F64(_) => true,
+            Value::F32(_) | Value::F64(_) | Value::Decimal(_) => true,
             _ => false,
         }
     }
+
+    /// Convert a numeric value to a 64-bit floating point number; large integers may lose
+    /// precision. Returns `None` if the value is not numeric.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn to_f64(&self) -> Option<f64> {
+        let number = match self {
+            Value::I8(value) => f64::from(*value),
+            Value::I16(value) => f64::from(*value),
+            Value::I32(value) => f64::from(*value),
+            Value::I64(value) => *value as f64,
+            Value::I128(value) => *value as f64,
+            Value::U8(value) => f64::from(*value),
+            Value::U16(value) => f64::from(*value),
+            Value::U32(value) => f64::from(*value),
+            Value::U64(value) => *value as f64,
+            Value::U128(value) => *value as f64,
+            Value::F32(value) => f64::from(*value),
+            Value::F64(value) => *value,
+            Value::Decimal(value) => value.to_f64()?,
+            _ => return None,
+        };
+        Some(number)
+    }
+
+    /// Get an estimate of the memory used by the value in bytes, including the memory allocated
+    /// for strings, bytes and nested values.
+    #[must_use]
+    pub fn estimated_size(&self) -> usize {
+        let allocated = match self {
+            Value::Bytes(value) => value.capacity(),
+            Value::String(value) => value.capacity(),
+            Value::Json(value) => value.to_string().len(),
+            Value::Array(values) => values.iter().map(Value::estimated_size).sum(),
+            Value::Map(values) => values
+                .iter()
+                .map(|(key, value)| key.estimated_size() + value.estimated_size())
+                .sum(),
+            _ => 0,
+        };
+        size_of::<Value>() + allocated
+    }
+}
+
+/// Format a decimal using the grouping, decimal and minus sign characters of the locale; the
+/// scale of the decimal is preserved.
+fn format_decimal(value: &Decimal, locale: Locale) -> String {
+    let digits = value.abs().to_string();
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+
+    let mut formatted = String::new();
+    if value.is_sign_negative() && !value.is_zero() {
+        formatted.push_str(locale.minus_sign());
+    }
+    match integer.parse::<u128>() {
+        Ok(integer) => formatted.push_str(&integer.to_formatted_string(&locale)),
+        Err(_) => formatted.push_str(integer),
+    }
+    if let Some(fraction) = fraction {
+        formatted.push_str(locale.decimal());
+        formatted.push_str(fraction);
+    }
+    formatted
 }
 
 impl fmt::Display for Value {
@@ -121,10 +327,12 @@ impl fmt::Display for Value {
             Value::U128(value) => value.to_string(),
             Value::F32(value) => value.to_string(),
             Value::F64(value) => value.to_string(),
+            Value::Decimal(value) => value.to_string(),
             Value::String(value) => value.to_string(),
             Value::Date(value) => value.to_string(),
             Value::Time(value) => value.to_string(),
             Value::DateTime(value) => value.to_string(),
+            Value::Interval(value) => value.to_string(),
             Value::Uuid(value) => value.to_string(),
             Value::Json(value) => value.to_string(),
             Value::Array(value) => value
@@ -162,10 +370,12 @@ impl Hash for Value {
             Value::U128(value) => value.hash(state),
             Value::F32(value) => value.to_bits().hash(state),
             Value::F64(value) => value.to_bits().hash(state),
+            Value::Decimal(value) => value.hash(state),
             Value::String(value) => value.hash(state),
             Value::Date(value) => value.hash(state),
             Value::Time(value) => value.hash(state),
             Value::DateTime(value) => value.hash(state),
+            Value::Interval(value) => value.hash(state),
             Value::Uuid(value) => value.hash(state),
             Value::Json(value) => value.hash(state),
             Value::Array(value) => value.hash(state),
@@ -197,10 +407,12 @@ impl PartialEq for Value {
             (Value::U128(a), Value::U128(b)) => a == b,
             (Value::F32(a), Value::F32(b)) => a == b,
             (Value::F64(a), Value::F64(b)) => a == b,
+            (Value::Decimal(a), Value::Decimal(b)) => a == b,
             (Value::String(a), Value::String(b)) => a == b,
             (Value::Date(a), Value::Date(b)) => a == b,
             (Value::Time(a), Value::Time(b)) => a == b,
             (Value::DateTime(a), Value::DateTime(b)) => a == b,
+            (Value::Interval(a), Value::Interval(b)) => a == b,
             (Value::Uuid(a), Value::Uuid(b)) => a == b,
             (Value::Json(a), Value::Json(b)) => a == b,
             (Value::Array(a), Value::Array(b)) => a == b,
@@ -242,10 +454,12 @@ impl Serialize for Value {
             Value::U128(value) => serializer.serialize_str(&value.to_string()),
             Value::F32(value) => serializer.serialize_f32(value),
             Value::F64(value) => serializer.serialize_f64(value),
+            Value::Decimal(ref value) => serializer.serialize_str(&value.to_string()),
             Value::String(ref value) => serializer.serialize_str(value),
             Value::Date(value) => serializer.serialize_str(&value.to_string()),
             Value::Time(value) => serializer.serialize_str(&value.to_string()),
             Value::DateTime(value) => serializer.serialize_str(&value.to_string()),
+            Value::Interval(value) => serializer.serialize_str(&value.to_iso8601()),
             Value::Uuid(value) => serializer.serialize_str(&value.to_string()),
             Value::Json(ref value) => value.serialize(serializer),
             Value::Array(ref value) => value.serialize(serializer),
@@ -356,6 +570,12 @@ impl From<f64> for Value {
     }
 }
 
+impl From<Decimal> for Value {
+    fn from(value: Decimal) -> Self {
+        Value::Decimal(value)
+    }
+}
+
 impl From<&str> for Value {
     fn from(value: &str) -> Self {
         Value::String(value.to_string())
@@ -386,6 +606,12 @@ impl From<chrono::NaiveDateTime> for Value {
     }
 }
 
+impl From<Interval> for Value {
+    fn from(value: Interval) -> Self {
+        Value::Interval(value)
+    }
+}
+
 impl From<uuid::Uuid> for Value {
     fn from(value: uuid::Uuid) -> Self {
         Value::Uuid(value)
@@ -419,6 +645,44 @@ mod tests {
     use std::str::FromStr;
     use uuid::Uuid;
 
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(Value::I8(1).to_f64(), Some(1.0));
+        assert_eq!(Value::I128(-2).to_f64(), Some(-2.0));
+        assert_eq!(Value::U64(42).to_f64(), Some(42.0));
+        assert_eq!(Value::F32(1.5).to_f64(), Some(1.5));
+        assert_eq!(Value::F64(3.25).to_f64(), Some(3.25));
+        assert_eq!(Value::Decimal(Decimal::new(125, 2)).to_f64(), Some(1.25));
+        assert_eq!(Value::String("1".to_string()).to_f64(), None);
+        assert_eq!(Value::Null.to_f64(), None);
+    }
+
+    #[test]
+    fn test_estimated_size() {
+        let size = size_of::<Value>();
+        assert_eq!(Value::Null.estimated_size(), size);
+        assert_eq!(Value::I64(42).estimated_size(), size);
+        assert_eq!(Value::Bytes(vec![1, 2, 3]).estimated_size(), size + 3);
+        assert_eq!(
+            Value::Array(vec![Value::I8(1), Value::I8(2)]).estimated_size(),
+            3 * size
+        );
+        assert!(Value::String("foo".to_string()).estimated_size() >= size + 3);
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(Value::Null.kind(), None);
+        assert_eq!(Value::I64(42).kind(), Some(ValueKind::I64));
+        assert_eq!(
+            Value::String("foo".to_string()).kind(),
+            Some(ValueKind::String)
+        );
+        assert!(ValueKind::Decimal.is_numeric());
+        assert!(!ValueKind::Date.is_numeric());
+        assert_eq!(ValueKind::DateTime.to_string(), "datetime");
+    }
+
     #[test]
     fn test_null() {
         assert!(Value::Null.is_null());
@@ -638,6 +902,27 @@ mod tests {
         assert_eq!(json!(Value::F64(12_345.678_90)), json!(12_345.678_90));
     }
 
+    #[test]
+    fn test_decimal() -> Result<()> {
+        let value = Value::Decimal(Decimal::from_str("-1234567.890")?);
+        assert!(!value.is_null());
+        assert!(value.is_numeric());
+        assert_eq!(value.to_formatted_string(&Locale::en), "-1,234,567.890");
+        assert_eq!(value.to_formatted_string(&Locale::de), "-1.234.567,890");
+        assert_eq!(value.to_string(), "-1234567.890");
+        assert_eq!(json!(value), json!("-1234567.890"));
+
+        // Serialization must not lose digits beyond the precision of f64
+        let value = Value::Decimal(Decimal::from_str("12345678901234567890.123456789")?);
+        assert_eq!(json!(value), json!("12345678901234567890.123456789"));
+
+        let value = Value::Decimal(Decimal::from_str("-0.5")?);
+        assert_eq!(value.to_formatted_string(&Locale::en), "-0.5");
+        let value = Value::Decimal(Decimal::from(42));
+        assert_eq!(value.to_formatted_string(&Locale::en), "42");
+        Ok(())
+    }
+
     #[test]
     fn test_string() {
         assert!(!Value::String("foo".to_string()).is_null());
@@ -666,6 +951,29 @@ mod tests {
         assert_eq!(json!(Value::Date(date)), json!("2000-12-31"));
     }
 
+    #[test]
+    fn test_to_formatted_string_with() {
+        let date_format = DateFormat {
+            date: Some("%d/%m/%Y".to_string()),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2000, 12, 31).expect("Invalid date");
+        let time = NaiveTime::from_hms_opt(12, 13, 14).expect("Invalid time");
+        assert_eq!(
+            Value::Date(date).to_formatted_string_with(&Locale::en, &date_format),
+            "31/12/2000"
+        );
+        assert_eq!(
+            Value::Time(time).to_formatted_string_with(&Locale::en, &date_format),
+            "12:13:14"
+        );
+        assert_eq!(
+            Value::Array(vec![Value::Date(date), Value::I64(1234)])
+                .to_formatted_string_with(&Locale::en, &date_format),
+            "31/12/2000, 1,234"
+        );
+    }
+
     #[test]
     fn test_time() {
         let time = NaiveTime::from_hms_milli_opt(12, 13, 14, 15).expect("Invalid time");
@@ -700,6 +1008,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_interval() {
+        let value = Value::Interval(Interval::new(14, 3, 3_600_000_000_000));
+        assert!(!value.is_null());
+        assert!(!value.is_numeric());
+        assert_eq!(
+            value.to_formatted_string(&Locale::en),
+            "1 year 2 months 3 days 01:00:00"
+        );
+        assert_eq!(value.to_string(), "1 year 2 months 3 days 01:00:00");
+        assert_eq!(json!(value), json!("P1Y2M3DT1H"));
+    }
+
     #[test]
     fn test_uuid() -> Result<()> {
         let uuid = "acf5b3e3-4099-4f34-81c7-5803cbc87a2d";
@@ -875,6 +1196,14 @@ mod tests {
         assert_eq!(Value::from(42.1f64), Value::F64(42.1f64));
     }
 
+    #[test]
+    fn test_from_decimal() {
+        assert_eq!(
+            Value::from(Decimal::new(421, 1)),
+            Value::Decimal(Decimal::new(421, 1))
+        );
+    }
+
     #[test]
     fn test_from_str() {
         assert_eq!(Value::from("foo"), Value::String("foo".to_string()));
@@ -908,6 +1237,12 @@ mod tests {
         assert_eq!(Value::from(datetime), Value::DateTime(datetime));
     }
 
+    #[test]
+    fn test_from_interval() {
+        let interval = Interval::new(1, 2, 3);
+        assert_eq!(Value::from(interval), Value::Interval(interval));
+    }
+
     #[test]
     fn test_from_uuid() -> Result<()> {
         let uuid = "acf5b3e3-4099-4f34-81c7-5803cbc87a2d";
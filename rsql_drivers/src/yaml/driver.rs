@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::polars::Connection;
-use crate::url::UrlExtension;
+use crate::storage::local_file;
 use crate::Error::{ConversionError, IoError};
 use async_trait::async_trait;
 use file_type::FileType;
@@ -32,7 +32,8 @@ impl crate::Driver for Driver {
         let query_parameters: HashMap<String, String> =
             parsed_url.query_pairs().into_owned().collect();
 
-        let file_name = parsed_url.to_file()?.to_string_lossy().to_string();
+        let local_file = local_file(&url).await?;
+        let file_name = local_file.file_name();
         let json = {
             let yaml = read_to_string(&file_name).await?;
             let yaml_value: serde_yaml::Value =
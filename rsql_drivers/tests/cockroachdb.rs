@@ -1,5 +1,5 @@
 #[cfg(target_os = "linux")]
-use rsql_drivers::{DriverManager, Value};
+use rsql_drivers::{Connection, DriverManager, Value};
 #[cfg(target_os = "linux")]
 use testcontainers::runners::AsyncRunner;
 
@@ -23,7 +23,34 @@ async fn test_cockroachdb_driver() -> anyhow::Result<()> {
 
     assert_eq!(*value, Value::I64(1));
 
+    test_as_of(&mut *connection).await?;
+
     container.stop().await?;
     container.rm().await?;
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+async fn test_as_of(connection: &mut dyn Connection) -> anyhow::Result<()> {
+    let _ = connection.execute("CREATE TABLE users (id INT)").await?;
+    let _ = connection.execute("INSERT INTO users VALUES (1)").await?;
+    let mut query_result = connection
+        .query("SELECT cluster_logical_timestamp()::STRING")
+        .await?;
+    let row = query_result.next().await.expect("no row");
+    let timestamp = row.first().expect("no value").to_string();
+    let _ = connection.execute("INSERT INTO users VALUES (2)").await?;
+
+    let historical_reads = connection.as_historical_reads().expect("historical reads");
+    historical_reads.set_as_of(Some(timestamp)).await?;
+    let mut query_result = connection.query("SELECT count(*) FROM users").await?;
+    let row = query_result.next().await.expect("no row");
+    assert_eq!(row.first(), Some(&Value::I64(1)));
+
+    let historical_reads = connection.as_historical_reads().expect("historical reads");
+    historical_reads.set_as_of(None).await?;
+    let mut query_result = connection.query("SELECT count(*) FROM users").await?;
+    let row = query_result.next().await.expect("no row");
+    assert_eq!(row.first(), Some(&Value::I64(2)));
+    Ok(())
+}
@@ -1,5 +1,7 @@
 #[cfg(target_os = "linux")]
-use rsql_drivers::{Connection, DriverManager};
+use indoc::indoc;
+#[cfg(target_os = "linux")]
+use rsql_drivers::{Connection, DriverManager, Value};
 #[cfg(target_os = "linux")]
 use testcontainers::runners::AsyncRunner;
 
@@ -17,6 +19,7 @@ async fn test_mariadb_driver() -> anyhow::Result<()> {
     assert_eq!(database_url, connection.url().as_str());
 
     test_schema(&mut *connection).await?;
+    test_extended_types(&mut *connection).await?;
 
     container.stop().await?;
     container.rm().await?;
@@ -60,3 +63,49 @@ async fn test_schema(connection: &mut dyn Connection) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+async fn test_extended_types(connection: &mut dyn Connection) -> anyhow::Result<()> {
+    let sql = indoc! {r"
+            CREATE TABLE extended_types (
+                enum_type ENUM('small', 'medium', 'large'),
+                set_type SET('read', 'write', 'execute'),
+                tinyint_unsigned_type TINYINT UNSIGNED,
+                int_unsigned_type INT UNSIGNED,
+                bigint_unsigned_type BIGINT UNSIGNED
+            )
+        "};
+    let _ = connection.execute(sql).await?;
+
+    let sql = indoc! {r"
+            INSERT INTO extended_types (
+                enum_type, set_type, tinyint_unsigned_type, int_unsigned_type, bigint_unsigned_type
+            ) VALUES (
+                'medium', 'read,write', 255, 4294967295, 18446744073709551615
+            )
+        "};
+    let _ = connection.execute(sql).await?;
+
+    let sql = indoc! {r"
+            SELECT enum_type, set_type, tinyint_unsigned_type, int_unsigned_type,
+                   bigint_unsigned_type
+              FROM extended_types
+        "};
+    let mut query_result = connection.query(sql).await?;
+    assert_eq!(
+        query_result.next().await,
+        Some(vec![
+            Value::String("medium".to_string()),
+            Value::Array(vec![
+                Value::String("read".to_string()),
+                Value::String("write".to_string()),
+            ]),
+            Value::U16(255),
+            Value::U32(4_294_967_295),
+            Value::U64(18_446_744_073_709_551_615),
+        ])
+    );
+    assert!(query_result.next().await.is_none());
+
+    Ok(())
+}
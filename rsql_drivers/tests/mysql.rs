@@ -23,7 +23,9 @@ async fn test_mysql_driver() -> anyhow::Result<()> {
     assert_eq!(database_url, connection.url().as_str());
 
     test_connection_interface(&mut *connection).await?;
+    test_messages(&mut *connection).await?;
     test_data_types(&mut *connection).await?;
+    test_extended_types(&mut *connection).await?;
 
     container.stop().await?;
     container.rm().await?;
@@ -58,6 +60,18 @@ async fn test_connection_interface(connection: &mut dyn Connection) -> anyhow::R
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+async fn test_messages(connection: &mut dyn Connection) -> anyhow::Result<()> {
+    let _ = connection.query("SELECT CAST('abc' AS SIGNED)").await?;
+    let messages = connection.messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].starts_with("Warning (Code 1292): "));
+
+    let _ = connection.query("SELECT 1").await?;
+    assert!(connection.messages().is_empty());
+    Ok(())
+}
+
 #[cfg(target_os = "linux")]
 async fn test_data_types(connection: &mut dyn Connection) -> anyhow::Result<()> {
     let sql = indoc! {r"
@@ -122,7 +136,7 @@ async fn test_data_types(connection: &mut dyn Connection) -> anyhow::Result<()>
             Value::I32(8_388_607),
             Value::I32(2_147_483_647),
             Value::I64(9_223_372_036_854_775_807),
-            Value::String("123.45".to_string()),
+            Value::Decimal(rust_decimal::Decimal::new(12_345, 2)),
             Value::F32(123.0),
             Value::F32(123.0),
             Value::Date(NaiveDate::from_ymd_opt(2022, 1, 1).expect("invalid date")),
@@ -143,6 +157,52 @@ async fn test_data_types(connection: &mut dyn Connection) -> anyhow::Result<()>
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+async fn test_extended_types(connection: &mut dyn Connection) -> anyhow::Result<()> {
+    let sql = indoc! {r"
+            CREATE TABLE extended_types (
+                enum_type ENUM('small', 'medium', 'large'),
+                set_type SET('read', 'write', 'execute'),
+                tinyint_unsigned_type TINYINT UNSIGNED,
+                int_unsigned_type INT UNSIGNED,
+                bigint_unsigned_type BIGINT UNSIGNED
+            )
+        "};
+    let _ = connection.execute(sql).await?;
+
+    let sql = indoc! {r"
+            INSERT INTO extended_types (
+                enum_type, set_type, tinyint_unsigned_type, int_unsigned_type, bigint_unsigned_type
+            ) VALUES (
+                'medium', 'read,write', 255, 4294967295, 18446744073709551615
+            )
+        "};
+    let _ = connection.execute(sql).await?;
+
+    let sql = indoc! {r"
+            SELECT enum_type, set_type, tinyint_unsigned_type, int_unsigned_type,
+                   bigint_unsigned_type
+              FROM extended_types
+        "};
+    let mut query_result = connection.query(sql).await?;
+    assert_eq!(
+        query_result.next().await,
+        Some(vec![
+            Value::String("medium".to_string()),
+            Value::Array(vec![
+                Value::String("read".to_string()),
+                Value::String("write".to_string()),
+            ]),
+            Value::U16(255),
+            Value::U32(4_294_967_295),
+            Value::U64(18_446_744_073_709_551_615),
+        ])
+    );
+    assert!(query_result.next().await.is_none());
+
+    Ok(())
+}
+
 #[cfg(target_os = "linux")]
 #[tokio::test]
 async fn test_mysql_metadata() -> anyhow::Result<()> {
@@ -32,6 +32,7 @@ async fn test_sqlserver_driver() -> anyhow::Result<()> {
 
     test_connection_interface(&mut *connection).await?;
     test_data_types(&mut *connection).await?;
+    test_result_sets(&mut *connection).await?;
 
     container.stop().await?;
     container.rm().await?;
@@ -66,6 +67,32 @@ async fn test_connection_interface(connection: &mut dyn Connection) -> anyhow::R
     Ok(())
 }
 
+#[cfg(target_os = "linux")]
+async fn test_result_sets(connection: &mut dyn Connection) -> anyhow::Result<()> {
+    let mut query_result = connection
+        .query("PRINT 'first'; SELECT 1 AS a; RAISERROR('second', 10, 1); SELECT 2 AS b")
+        .await?;
+    assert_eq!(query_result.columns().await, vec!["a"]);
+    assert_eq!(query_result.next().await, Some(vec![Value::I32(1)]));
+    assert_eq!(
+        connection.messages(),
+        vec!["first".to_string(), "second".to_string()]
+    );
+
+    let multiple_results = connection
+        .as_multiple_results()
+        .expect("multiple result sets");
+    let mut query_result = multiple_results
+        .next_result()
+        .await?
+        .expect("second result set");
+    assert_eq!(query_result.columns().await, vec!["b"]);
+    assert_eq!(query_result.next().await, Some(vec![Value::I32(2)]));
+    assert!(multiple_results.next_result().await?.is_none());
+    assert!(connection.messages().is_empty());
+    Ok(())
+}
+
 #[cfg(target_os = "linux")]
 async fn test_data_types(connection: &mut dyn Connection) -> anyhow::Result<()> {
     let sql = indoc! {r"
@@ -137,7 +164,7 @@ async fn test_data_types(connection: &mut dyn Connection) -> anyhow::Result<()>
             Value::F32(123.45),
             Value::F64(123.0),
             Value::Bool(true),
-            Value::String("123.00".to_string()),
+            Value::Decimal(rust_decimal::Decimal::new(12_300, 2)),
             Value::Date(NaiveDate::from_ymd_opt(2022, 1, 1).expect("invalid date")),
             Value::Time(NaiveTime::from_hms_opt(14, 30, 00).expect("invalid time")),
             Value::DateTime(NaiveDateTime::parse_from_str(
@@ -0,0 +1,115 @@
+use crate::error::Result;
+use crate::footer::write_footer;
+use crate::formatter::FormatterOptions;
+use crate::record_batch::to_record_batch;
+use crate::writers::Output;
+use crate::Results;
+use crate::Results::{Execute, Query};
+use arrow::ipc::writer::StreamWriter;
+use async_trait::async_trait;
+use std::io::Write;
+
+/// A formatter for Arrow IPC streams (Feather v2)
+#[derive(Debug, Default)]
+pub struct Formatter;
+
+#[async_trait]
+impl crate::Formatter for Formatter {
+    fn identifier(&self) -> &'static str {
+        "arrow"
+    }
+
+    async fn format(
+        &self,
+        options: &FormatterOptions,
+        results: &mut Results,
+        output: &mut Output,
+    ) -> Result<()> {
+        let query_result = match results {
+            Query(query_result) => query_result,
+            Execute(_) => return write_footer(options, results, 0, output).await,
+        };
+
+        let record_batch = to_record_batch(query_result.as_mut()).await?;
+        let mut writer = StreamWriter::try_new(&mut *output, &record_batch.schema())?;
+        writer.write(&record_batch)?;
+        writer.finish()?;
+        drop(writer);
+
+        output.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::formatter::FormatterOptions;
+    use crate::writers::{FileWriter, Output};
+    use crate::Formatter;
+    use crate::Results::{Execute, Query};
+    use arrow::array::{Array, Int64Array, StringArray};
+    use arrow::ipc::reader::StreamReader;
+    use rsql_drivers::{MemoryQueryResult, Value};
+    use std::fs::File;
+    use std::time::Duration;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_format_execute() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+
+        let formatter = Formatter;
+        formatter.format(&options, &mut Execute(1), output).await?;
+
+        let output = output.to_string().replace("\r\n", "\n");
+        let expected = "1 row (9ns)\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_query() -> anyhow::Result<()> {
+        let options = FormatterOptions::default();
+        let mut query_result = Query(Box::new(MemoryQueryResult::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Value::I64(1), Value::String("foo".to_string())],
+                vec![Value::I64(2), Value::Null],
+            ],
+        )));
+        let file = NamedTempFile::new()?;
+        let writer = FileWriter::from_path(file.path())?;
+        let output = &mut Output::new(Box::new(writer));
+
+        let formatter = Formatter;
+        formatter
+            .format(&options, &mut query_result, output)
+            .await?;
+
+        let reader = StreamReader::try_new(File::open(file.path())?, None)?;
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .expect("int64 array");
+        assert_eq!(ids.values(), &[1, 2]);
+        let names = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("string array");
+        assert_eq!(names.value(0), "foo");
+        assert!(names.is_null(1));
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use num_format::{Locale, ToFormattedString};
+use std::fmt;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// The format used to display binary values
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BytesFormat {
+    /// Base64 encoded, e.g. `AQID`
+    #[default]
+    Base64,
+    /// Hexadecimal with a `\x` prefix, e.g. `\x010203`
+    Hex,
+    /// Printable ASCII characters, with other bytes escaped as octal, e.g. `a\001`
+    Escape,
+    /// Only the length of the value, e.g. `<3 bytes>`
+    Omit,
+}
+
+impl BytesFormat {
+    /// Format the bytes for display
+    #[must_use]
+    pub fn format(self, bytes: &[u8], locale: &str) -> String {
+        match self {
+            BytesFormat::Base64 => STANDARD.encode(bytes),
+            BytesFormat::Hex => {
+                let mut hex = String::with_capacity(2 + bytes.len() * 2);
+                hex.push_str("\\x");
+                for byte in bytes {
+                    let _ = write!(hex, "{byte:02x}");
+                }
+                hex
+            }
+            BytesFormat::Escape => {
+                let mut escaped = String::with_capacity(bytes.len());
+                for byte in bytes {
+                    match byte {
+                        b'\\' => escaped.push_str("\\\\"),
+                        0x20..=0x7e => escaped.push(char::from(*byte)),
+                        _ => {
+                            let _ = write!(escaped, "\\{byte:03o}");
+                        }
+                    }
+                }
+                escaped
+            }
+            BytesFormat::Omit => {
+                let format_locale = Locale::from_str(locale).unwrap_or(Locale::en);
+                let bytes = bytes.len().to_formatted_string(&format_locale);
+                t!("bytes_omitted", locale = locale, bytes = bytes).to_string()
+            }
+        }
+    }
+}
+
+impl FromStr for BytesFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "base64" => Ok(Self::Base64),
+            "hex" => Ok(Self::Hex),
+            "escape" => Ok(Self::Escape),
+            "omit" => Ok(Self::Omit),
+            _ => Err(format!("Invalid bytes format: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for BytesFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let format = match self {
+            BytesFormat::Base64 => "base64",
+            BytesFormat::Hex => "hex",
+            BytesFormat::Escape => "escape",
+            BytesFormat::Omit => "omit",
+        };
+        write!(f, "{format}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let bytes = b"a\\\x01\xff";
+        assert_eq!(BytesFormat::Base64.format(bytes, "en"), "YVwB/w==");
+        assert_eq!(BytesFormat::Hex.format(bytes, "en"), "\\x615c01ff");
+        assert_eq!(BytesFormat::Escape.format(bytes, "en"), "a\\\\\\001\\377");
+        assert_eq!(BytesFormat::Omit.format(bytes, "en"), "<4 bytes>");
+        assert_eq!(BytesFormat::Omit.format(&[0; 1234], "de"), "<1.234 Bytes>");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(BytesFormat::from_str("base64"), Ok(BytesFormat::Base64));
+        assert_eq!(BytesFormat::from_str("HEX"), Ok(BytesFormat::Hex));
+        assert_eq!(BytesFormat::from_str("escape"), Ok(BytesFormat::Escape));
+        assert_eq!(BytesFormat::from_str("omit"), Ok(BytesFormat::Omit));
+        assert!(BytesFormat::from_str("foo").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(BytesFormat::Base64.to_string(), "base64");
+        assert_eq!(BytesFormat::Hex.to_string(), "hex");
+        assert_eq!(BytesFormat::Escape.to_string(), "escape");
+        assert_eq!(BytesFormat::Omit.to_string(), "omit");
+    }
+}
@@ -0,0 +1,394 @@
+use crate::error::Result;
+use crate::footer::write_footer;
+use crate::formatter::FormatterOptions;
+use crate::writers::Output;
+use crate::Results;
+use crate::Results::{Execute, Query};
+use async_trait::async_trait;
+use colored::{Color, Colorize};
+use num_format::Locale;
+use rsql_drivers::{Row, Value};
+use std::str::FromStr;
+use unicode_width::UnicodeWidthStr;
+
+/// Partial blocks used to draw the end of a bar, in eighths
+const BAR_BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+/// Blocks used to draw sparklines, from lowest to highest
+const SPARK_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Colors used for each numeric column when color is enabled
+const COLORS: [Color; 6] = [
+    Color::Blue,
+    Color::Green,
+    Color::Yellow,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Red,
+];
+const SEPARATOR: &str = "│";
+
+/// A formatter for charts; when the first column is a label the numeric columns are displayed as
+/// horizontal bar charts, otherwise each numeric column is displayed as a sparkline.
+#[derive(Debug, Default)]
+pub struct Formatter;
+
+#[async_trait]
+impl crate::Formatter for Formatter {
+    fn identifier(&self) -> &'static str {
+        "chart"
+    }
+
+    async fn format(
+        &self,
+        options: &FormatterOptions,
+        results: &mut Results,
+        output: &mut Output,
+    ) -> Result<()> {
+        let query_result = match results {
+            Query(query_result) => query_result,
+            Execute(_) => return write_footer(options, results, 0, output).await,
+        };
+
+        let columns = query_result.columns().await;
+        let mut rows = Vec::new();
+        while let Some(row) = query_result.next().await {
+            rows.push(row);
+        }
+
+        let numeric_columns: Vec<usize> = (0..columns.len())
+            .filter(|column| is_numeric_column(&rows, *column))
+            .collect();
+
+        if numeric_columns.first() == Some(&0) {
+            write_sparklines(options, &columns, &rows, &numeric_columns, output)?;
+        } else if !numeric_columns.is_empty() {
+            write_bars(options, &columns, &rows, &numeric_columns, output)?;
+        }
+
+        let row_count = u64::try_from(rows.len())?;
+        write_footer(options, results, row_count, output).await
+    }
+}
+
+/// Returns true if the column contains at least one numeric value and all other values are null
+fn is_numeric_column(rows: &[Row], column: usize) -> bool {
+    let mut numeric = false;
+    for value in rows.iter().filter_map(|row| row.get(column)) {
+        if value.is_numeric() {
+            numeric = true;
+        } else if !value.is_null() {
+            return false;
+        }
+    }
+    numeric
+}
+
+/// Get the numeric values of a column; non-numeric values are returned as `None`
+fn column_values(rows: &[Row], column: usize) -> Vec<Option<f64>> {
+    rows.iter()
+        .map(|row| row.get(column).and_then(Value::to_f64))
+        .collect()
+}
+
+/// Write a bar chart with a line per label and numeric column
+fn write_bars(
+    options: &FormatterOptions,
+    columns: &[String],
+    rows: &[Row],
+    numeric_columns: &[usize],
+    output: &mut Output,
+) -> Result<()> {
+    let locale = Locale::from_str(options.locale.as_str()).unwrap_or(Locale::en);
+    let labels: Vec<String> = rows
+        .iter()
+        .map(|row| match row.first() {
+            Some(Value::Null) | None => "NULL".to_string(),
+            Some(Value::Bytes(bytes)) => options.bytes_format.format(bytes, &options.locale),
+            Some(value) => value.to_formatted_string_with(&locale, &options.date_format),
+        })
+        .collect();
+    let mut label_width = labels.iter().map(|label| label.width()).max().unwrap_or(0);
+    if options.header {
+        label_width = label_width.max(columns[0].width());
+    }
+    let series_width = numeric_columns
+        .iter()
+        .map(|column| columns[*column].width())
+        .max()
+        .unwrap_or(0);
+    let max = numeric_columns
+        .iter()
+        .flat_map(|column| column_values(rows, *column))
+        .flatten()
+        .fold(0.0_f64, |max, value| max.max(value.abs()));
+
+    if options.header {
+        let label = pad(&columns[0], label_width);
+        if numeric_columns.len() == 1 {
+            let series = &columns[numeric_columns[0]];
+            writeln!(output, "{label} {SEPARATOR} {series}")?;
+        } else {
+            writeln!(output, "{label} {SEPARATOR}")?;
+        }
+    }
+
+    for (row, label) in rows.iter().zip(labels) {
+        for (index, column) in numeric_columns.iter().enumerate() {
+            let label = if index == 0 {
+                pad(&label, label_width)
+            } else {
+                pad("", label_width)
+            };
+            let series = if numeric_columns.len() == 1 {
+                String::new()
+            } else {
+                format!("{} ", pad(&columns[*column], series_width))
+            };
+            let value = row.get(*column).unwrap_or(&Value::Null);
+            let (bar, value) = match value.to_f64() {
+                Some(number) => (
+                    bar(number, max, options.chart_width),
+                    value.to_formatted_string_with(&locale, &options.date_format),
+                ),
+                None => (String::new(), "NULL".to_string()),
+            };
+            let bar = colorize(options, bar, index);
+            writeln!(output, "{label} {SEPARATOR} {series}{bar} {value}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a sparkline for each numeric column
+fn write_sparklines(
+    options: &FormatterOptions,
+    columns: &[String],
+    rows: &[Row],
+    numeric_columns: &[usize],
+    output: &mut Output,
+) -> Result<()> {
+    let series_width = numeric_columns
+        .iter()
+        .map(|column| columns[*column].width())
+        .max()
+        .unwrap_or(0);
+
+    for (index, column) in numeric_columns.iter().enumerate() {
+        let values = column_values(rows, *column);
+        let sparkline = colorize(options, sparkline(&values), index);
+        if options.header {
+            let series = pad(&columns[*column], series_width);
+            writeln!(output, "{series} {SEPARATOR} {sparkline}")?;
+        } else {
+            writeln!(output, "{sparkline}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Create a bar for the value scaled to the maximum absolute value and width, using partial
+/// blocks to display fractions of a character.
+#[expect(clippy::cast_possible_truncation)]
+#[expect(clippy::cast_precision_loss)]
+#[expect(clippy::cast_sign_loss)]
+fn bar(value: f64, max: f64, width: usize) -> String {
+    if max <= 0.0 || !value.is_finite() {
+        return String::new();
+    }
+
+    let eighths = (value.abs() / max * width as f64 * 8.0).round() as usize;
+    let mut bar = BAR_BLOCKS[7].to_string().repeat(eighths / 8);
+    let remainder = eighths % 8;
+    if remainder > 0 {
+        bar.push(BAR_BLOCKS[remainder - 1]);
+    }
+    bar
+}
+
+/// Create a sparkline with a block per value scaled between the minimum and maximum values;
+/// missing values are displayed as a space.
+#[expect(clippy::cast_possible_truncation)]
+#[expect(clippy::cast_precision_loss)]
+#[expect(clippy::cast_sign_loss)]
+fn sparkline(values: &[Option<f64>]) -> String {
+    let numbers = values.iter().flatten().filter(|value| value.is_finite());
+    let min = numbers
+        .clone()
+        .fold(f64::INFINITY, |min, value| min.min(*value));
+    let max = numbers.fold(f64::NEG_INFINITY, |max, value| max.max(*value));
+    let range = max - min;
+    let steps = (SPARK_BLOCKS.len() - 1) as f64;
+
+    values
+        .iter()
+        .map(|value| match value {
+            Some(value) if value.is_finite() => {
+                let index = if range > 0.0 {
+                    ((value - min) / range * steps).round() as usize
+                } else {
+                    SPARK_BLOCKS.len() / 2
+                };
+                SPARK_BLOCKS[index.min(SPARK_BLOCKS.len() - 1)]
+            }
+            _ => ' ',
+        })
+        .collect()
+}
+
+/// Pad a value with spaces to the specified display width
+fn pad(value: &str, width: usize) -> String {
+    let padding = width.saturating_sub(value.width());
+    format!("{value}{}", " ".repeat(padding))
+}
+
+/// Color the value if color is enabled; each numeric column uses a different color
+fn colorize(options: &FormatterOptions, value: String, index: usize) -> String {
+    if options.color {
+        let color = COLORS[index % COLORS.len()];
+        value.color(color).to_string()
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::formatter::FormatterOptions;
+    use crate::writers::Output;
+    use crate::Formatter;
+    use indoc::indoc;
+    use rsql_drivers::MemoryQueryResult;
+    use std::time::Duration;
+
+    async fn test_format(options: &FormatterOptions, results: &mut Results) -> Result<String> {
+        let output = &mut Output::default();
+        let formatter = Formatter;
+        formatter.format(options, results, output).await?;
+        Ok(output.to_string().replace("\r\n", "\n"))
+    }
+
+    fn options() -> FormatterOptions {
+        FormatterOptions {
+            chart_width: 8,
+            color: false,
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_format_execute() -> anyhow::Result<()> {
+        let output = test_format(&options(), &mut Execute(1)).await?;
+        assert_eq!(output, "1 row (9ns)\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_bars() -> anyhow::Result<()> {
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec!["name".to_string(), "total".to_string()],
+            vec![
+                vec![Value::String("foo".to_string()), Value::I64(8)],
+                vec![Value::String("bar".to_string()), Value::F64(3.5)],
+                vec![Value::String("baz".to_string()), Value::Null],
+            ],
+        )));
+
+        let output = test_format(&options(), &mut results).await?;
+
+        let expected = indoc! {r"
+            name │ total
+            foo  │ ████████ 8
+            bar  │ ███▌ 3.5
+            baz  │  NULL
+            3 rows (9ns)
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_bars_multiple_columns() -> anyhow::Result<()> {
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec!["name".to_string(), "a".to_string(), "bb".to_string()],
+            vec![
+                vec![
+                    Value::String("foo".to_string()),
+                    Value::I32(4),
+                    Value::I32(8),
+                ],
+                vec![Value::String("x".to_string()), Value::I32(2), Value::I32(1)],
+            ],
+        )));
+        let options = FormatterOptions {
+            footer: false,
+            header: false,
+            ..options()
+        };
+
+        let output = test_format(&options, &mut results).await?;
+
+        let expected = indoc! {r"
+            foo │ a  ████ 4
+                │ bb ████████ 8
+            x   │ a  ██ 2
+                │ bb █ 1
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_sparklines() -> anyhow::Result<()> {
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec!["value".to_string()],
+            vec![
+                vec![Value::I64(1)],
+                vec![Value::I64(8)],
+                vec![Value::Null],
+                vec![Value::I64(4)],
+            ],
+        )));
+        let options = FormatterOptions {
+            footer: false,
+            ..options()
+        };
+
+        let output = test_format(&options, &mut results).await?;
+
+        assert_eq!(output, "value │ ▁█ ▄\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_no_numeric_columns() -> anyhow::Result<()> {
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec!["name".to_string()],
+            vec![vec![Value::String("foo".to_string())]],
+        )));
+
+        let output = test_format(&options(), &mut results).await?;
+
+        assert_eq!(output, "1 row (9ns)\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_bar() {
+        assert_eq!(bar(0.0, 10.0, 4), "");
+        assert_eq!(bar(10.0, 10.0, 4), "████");
+        assert_eq!(bar(5.0, 10.0, 4), "██");
+        assert_eq!(bar(-5.0, 10.0, 4), "██");
+        assert_eq!(bar(1.0, 10.0, 4), "▍");
+        assert_eq!(bar(1.0, 0.0, 4), "");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[Some(1.0), Some(1.0)]), "▅▅");
+        assert_eq!(sparkline(&[Some(0.0), None, Some(7.0)]), "▁ █");
+    }
+}
@@ -0,0 +1,251 @@
+use colored::{Color, ColoredString, Colorize};
+use rsql_drivers::Value;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// The operator used to compare the value of a column with the value of a rule
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
+    Contains,
+}
+
+impl Operator {
+    /// Get the operators in the order they are matched; longer operators are matched first so
+    /// that `>=` is not parsed as `>`
+    const ALL: [(&'static str, Operator); 7] = [
+        ("!=", Operator::NotEqual),
+        (">=", Operator::GreaterThanOrEqual),
+        ("<=", Operator::LessThanOrEqual),
+        ("=", Operator::Equal),
+        (">", Operator::GreaterThan),
+        ("<", Operator::LessThan),
+        ("contains", Operator::Contains),
+    ];
+}
+
+/// A conditional formatting rule that styles a cell, or the entire row, of a table when the value
+/// of a column matches the condition, e.g. `status = 'FAILED' => red row` or
+/// `amount > 1000 => bold`.
+///
+/// Numeric values are compared numerically when the value of the rule is a number; all other
+/// values are compared as strings. Null values never match a rule.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColorRule {
+    column: String,
+    operator: Operator,
+    value: String,
+    color: Option<Color>,
+    bold: bool,
+    row: bool,
+    /// The text the rule was parsed from
+    source: String,
+}
+
+impl ColorRule {
+    /// Get the name of the column the condition is evaluated against
+    #[must_use]
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// Indicates if the style is applied to the entire row rather than the cell of the column
+    #[must_use]
+    pub fn row(&self) -> bool {
+        self.row
+    }
+
+    /// Check if the value matches the condition of the rule
+    #[must_use]
+    pub fn matches(&self, value: &Value) -> bool {
+        if value.is_null() {
+            return false;
+        }
+
+        if self.operator == Operator::Contains {
+            return value.to_string().contains(&self.value);
+        }
+
+        let ordering = match (value.to_f64(), self.value.parse::<f64>()) {
+            (Some(value), Ok(rule_value)) => value.partial_cmp(&rule_value),
+            _ => Some(value.to_string().as_str().cmp(self.value.as_str())),
+        };
+        let Some(ordering) = ordering else {
+            return false;
+        };
+
+        match self.operator {
+            Operator::Equal | Operator::Contains => ordering == Ordering::Equal,
+            Operator::NotEqual => ordering != Ordering::Equal,
+            Operator::GreaterThan => ordering == Ordering::Greater,
+            Operator::GreaterThanOrEqual => ordering != Ordering::Less,
+            Operator::LessThan => ordering == Ordering::Less,
+            Operator::LessThanOrEqual => ordering != Ordering::Greater,
+        }
+    }
+
+    /// Apply the style of the rule to the value
+    #[must_use]
+    pub fn style(&self, value: &str) -> String {
+        let mut styled = ColoredString::from(value);
+        if let Some(color) = self.color {
+            styled = styled.color(color);
+        }
+        if self.bold {
+            styled = styled.bold();
+        }
+        styled.to_string()
+    }
+}
+
+impl fmt::Display for ColorRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FromStr for ColorRule {
+    type Err = String;
+
+    /// Parse a rule in the form `<column> <operator> <value> => <style>...`, where the operator
+    /// is one of `=`, `!=`, `>`, `>=`, `<`, `<=` or `contains`, the value may be enclosed in
+    /// single quotes and the styles are a color name, e.g. `red` or `bright_yellow`, `bold` and/or
+    /// `row`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid color rule: {s}");
+        let (condition, styles) = s.rsplit_once("=>").ok_or_else(invalid)?;
+        let condition = condition.trim();
+        let (column, condition) = condition
+            .split_once(char::is_whitespace)
+            .ok_or_else(invalid)?;
+        let condition = condition.trim_start();
+        let (operator, value) = Operator::ALL
+            .iter()
+            .find_map(|(symbol, operator)| {
+                condition
+                    .strip_prefix(symbol)
+                    .map(|value| (*operator, value.trim()))
+            })
+            .ok_or_else(invalid)?;
+        let value = value
+            .strip_prefix('\'')
+            .and_then(|value| value.strip_suffix('\''))
+            .unwrap_or(value);
+        if value.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut rule = ColorRule {
+            column: column.to_string(),
+            operator,
+            value: value.to_string(),
+            color: None,
+            bold: false,
+            row: false,
+            source: s.trim().to_string(),
+        };
+        for style in styles.split_whitespace() {
+            match style.to_lowercase().as_str() {
+                "bold" => rule.bold = true,
+                "row" => rule.row = true,
+                color => {
+                    let color = color.replace('_', " ");
+                    rule.color = Some(Color::from_str(&color).map_err(|()| invalid())?);
+                }
+            }
+        }
+        if rule.color.is_none() && !rule.bold {
+            return Err(invalid());
+        }
+
+        Ok(rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), String> {
+        let rule = ColorRule::from_str("status = 'FAILED' => red row")?;
+        assert_eq!(rule.column(), "status");
+        assert_eq!(rule.operator, Operator::Equal);
+        assert_eq!(rule.value, "FAILED");
+        assert_eq!(rule.color, Some(Color::Red));
+        assert!(!rule.bold);
+        assert!(rule.row());
+
+        let rule = ColorRule::from_str("amount >= 1000 => bold")?;
+        assert_eq!(rule.column(), "amount");
+        assert_eq!(rule.operator, Operator::GreaterThanOrEqual);
+        assert_eq!(rule.value, "1000");
+        assert_eq!(rule.color, None);
+        assert!(rule.bold);
+        assert!(!rule.row());
+
+        let rule = ColorRule::from_str("name contains foo => Bright_Green BOLD")?;
+        assert_eq!(rule.operator, Operator::Contains);
+        assert_eq!(rule.color, Some(Color::BrightGreen));
+        assert!(rule.bold);
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> Result<(), String> {
+        let rule = ColorRule::from_str(" status = 'FAILED' => red row ")?;
+        assert_eq!(rule.to_string(), "status = 'FAILED' => red row");
+        assert_eq!(ColorRule::from_str(&rule.to_string())?, rule);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert!(ColorRule::from_str("status = FAILED").is_err());
+        assert!(ColorRule::from_str("status => red").is_err());
+        assert!(ColorRule::from_str("status ~ FAILED => red").is_err());
+        assert!(ColorRule::from_str("status = => red").is_err());
+        assert!(ColorRule::from_str("status = FAILED => row").is_err());
+        assert!(ColorRule::from_str("status = FAILED => foo").is_err());
+    }
+
+    #[test]
+    fn test_matches() -> Result<(), String> {
+        let rule = ColorRule::from_str("status = FAILED => red")?;
+        assert!(rule.matches(&Value::String("FAILED".to_string())));
+        assert!(!rule.matches(&Value::String("OK".to_string())));
+        assert!(!rule.matches(&Value::Null));
+
+        let rule = ColorRule::from_str("status != FAILED => red")?;
+        assert!(rule.matches(&Value::String("OK".to_string())));
+
+        let rule = ColorRule::from_str("amount > 100 => bold")?;
+        assert!(rule.matches(&Value::I64(1000)));
+        assert!(rule.matches(&Value::F64(100.5)));
+        assert!(!rule.matches(&Value::I32(100)));
+        assert!(!rule.matches(&Value::I32(99)));
+
+        let rule = ColorRule::from_str("amount <= 100 => bold")?;
+        assert!(rule.matches(&Value::I32(100)));
+        assert!(!rule.matches(&Value::I32(101)));
+
+        let rule = ColorRule::from_str("name contains oo => bold")?;
+        assert!(rule.matches(&Value::String("foo".to_string())));
+        assert!(!rule.matches(&Value::String("bar".to_string())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_style() -> Result<(), String> {
+        colored::control::set_override(true);
+        let rule = ColorRule::from_str("status = FAILED => red bold")?;
+        assert_eq!(rule.style("FAILED"), "\u{1b}[1;31mFAILED\u{1b}[0m");
+        Ok(())
+    }
+}
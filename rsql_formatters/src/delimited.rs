@@ -43,11 +43,14 @@ async fn format_delimited(
             for data in &row {
                 let bytes = match data {
                     Value::Null => Vec::new(),
-                    _ => Vec::from(data.to_string().as_bytes()),
+                    _ => Vec::from(options.value_to_string(data).as_bytes()),
                 };
                 csv_row.push(bytes);
             }
             writer.write_record(csv_row)?;
+            if options.stream_rows > 0 {
+                writer.flush()?;
+            }
             rows += 1;
         }
         writer.flush()?;
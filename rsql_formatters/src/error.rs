@@ -27,6 +27,14 @@ impl From<csv::Error> for Error {
     }
 }
 
+#[cfg(feature = "arrow")]
+/// Converts a [`arrow::error::ArrowError`] into an [`IoError`](Error::IoError)
+impl From<arrow::error::ArrowError> for Error {
+    fn from(error: arrow::error::ArrowError) -> Self {
+        Error::IoError(error.into())
+    }
+}
+
 #[cfg(any(feature = "html", feature = "xml"))]
 /// Converts a [`quick_xml::Error`] into an [`IoError`](Error::IoError)
 impl From<quick_xml::Error> for Error {
@@ -51,6 +59,14 @@ impl From<serde_yaml::Error> for Error {
     }
 }
 
+#[cfg(feature = "xlsx")]
+/// Converts a [`rust_xlsxwriter::XlsxError`] into an [`IoError`](Error::IoError)
+impl From<rust_xlsxwriter::XlsxError> for Error {
+    fn from(error: rust_xlsxwriter::XlsxError) -> Self {
+        Error::IoError(error.into())
+    }
+}
+
 /// Converts a [`std::io::Error`] into an [`IoError`](Error::IoError)
 impl From<std::io::Error> for Error {
     fn from(error: std::io::Error) -> Self {
@@ -87,6 +103,14 @@ mod tests {
         assert_eq!(io_error.to_string(), "test");
     }
 
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_arrow_error() {
+        let arrow_error = arrow::error::ArrowError::ComputeError("test".to_string());
+        let error = Error::from(arrow_error);
+        assert_eq!(error.to_string(), "Compute error: test");
+    }
+
     #[cfg(any(feature = "html", feature = "xml"))]
     #[test]
     fn test_quick_xml_error() {
@@ -114,6 +138,14 @@ mod tests {
         assert_eq!(io_error.to_string(), "found character that cannot start any token at line 2 column 1, while scanning for the next token");
     }
 
+    #[cfg(feature = "xlsx")]
+    #[test]
+    fn test_xlsx_error() {
+        let xlsx_error = rust_xlsxwriter::XlsxError::RowColumnLimitError;
+        let error = Error::from(xlsx_error);
+        assert!(matches!(error, Error::IoError(_)));
+    }
+
     #[test]
     fn test_std_io_error() {
         let error = std::io::Error::new(std::io::ErrorKind::Other, "test");
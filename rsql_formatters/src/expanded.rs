@@ -1,6 +1,8 @@
 use crate::error::Result;
 use crate::footer::write_footer;
 use crate::formatter::FormatterOptions;
+use crate::table::truncate;
+use crate::value::format_value;
 use crate::writers::Output;
 use crate::Results;
 use crate::Results::Query;
@@ -63,11 +65,11 @@ async fn process_data(
 
         for data in &row {
             let data = match data {
-                Value::Null => "NULL".to_string(),
-                _ => data.to_formatted_string(&locale),
+                Value::Null => options.null_display.clone(),
+                _ => format_value(options, locale, data),
             };
 
-            row_data.push(data);
+            row_data.push(truncate(data, options.max_column_width));
         }
 
         rows += 1;
@@ -4,15 +4,17 @@ use crate::Results::{Execute, Query};
 use crate::{FormatterOptions, Results};
 use colored::Colorize;
 use num_format::{Locale, ToFormattedString};
+use rsql_drivers::QueryStatistics;
 use std::io::Write;
 use std::str::FromStr;
 
 /// Display the footer of the result set.
 /// This includes the number of rows returned and the elapsed time.
-/// If the timing option is enabled, the elapsed time will be displayed.
+/// If the timing option is enabled, the elapsed time and any statistics reported by the server
+/// will be displayed.
 /// The number of rows will be formatted based on the locale.
 ///
-/// Example: "N,NNN,NNN rows (M.MMMs)"
+/// Example: "N,NNN,NNN rows (M.MMMs, server M.MMMs, N,NNN bytes scanned)"
 pub async fn write_footer(
     options: &FormatterOptions,
     results: &Results,
@@ -23,26 +25,8 @@ pub async fn write_footer(
         return Ok(());
     }
 
-    let (display_rows, rows_affected) = match results {
-        Execute(rows_affected) => (options.changes, *rows_affected),
-        Query(_query_result) => (options.rows, query_rows),
-    };
     let locale = &options.locale;
-    let num_locale = Locale::from_str(locale).unwrap_or(Locale::en);
-    let rows = rows_affected.to_formatted_string(&num_locale);
-    let rows_label = if !display_rows {
-        String::new()
-    } else if rows_affected == 1 {
-        t!("row", locale = locale, rows = rows).to_string()
-    } else {
-        t!("rows", locale = locale, rows = rows).to_string()
-    };
-    let elapsed_display = if options.timer {
-        let elapsed = format!("{:?}", options.elapsed);
-        t!("elapsed_format", locale = locale, elapsed = elapsed).to_string()
-    } else {
-        String::new()
-    };
+    let (rows_label, elapsed_display) = footer_labels(options, results, query_rows);
 
     if options.color {
         let footer = t!(
@@ -70,6 +54,121 @@ pub async fn write_footer(
     Ok(())
 }
 
+/// Build the rows and elapsed time labels used by the footer.
+fn footer_labels(
+    options: &FormatterOptions,
+    results: &Results,
+    query_rows: u64,
+) -> (String, String) {
+    let (display_rows, rows_affected) = match results {
+        Execute(rows_affected) => (options.changes, *rows_affected),
+        Query(_query_result) => (options.rows, query_rows),
+    };
+    let locale = &options.locale;
+    let num_locale = Locale::from_str(locale).unwrap_or(Locale::en);
+    let rows = rows_affected.to_formatted_string(&num_locale);
+    let rows_label = if !display_rows {
+        String::new()
+    } else if rows_affected == 1 {
+        t!("row", locale = locale, rows = rows).to_string()
+    } else {
+        t!("rows", locale = locale, rows = rows).to_string()
+    };
+    let elapsed_display = if options.timer {
+        let mut details = vec![format!("{:?}", options.elapsed)];
+        if let Some(statistics) = &options.statistics {
+            details.extend(statistics_labels(options, results, statistics, num_locale));
+        }
+        let elapsed = details.join(", ");
+        t!("elapsed_format", locale = locale, elapsed = elapsed).to_string()
+    } else {
+        String::new()
+    };
+
+    (rows_label, elapsed_display)
+}
+
+/// Build the labels for the statistics reported by the server.  Rows affected are only displayed
+/// for queries, since the footer of an execute already displays the rows affected.
+fn statistics_labels(
+    options: &FormatterOptions,
+    results: &Results,
+    statistics: &QueryStatistics,
+    num_locale: Locale,
+) -> Vec<String> {
+    let locale = &options.locale;
+    let mut labels = Vec::new();
+
+    if let (Query(_), Some(rows_affected)) = (results, statistics.rows_affected) {
+        let rows = rows_affected.to_formatted_string(&num_locale);
+        labels.push(t!("statistics_rows_affected", locale = locale, rows = rows).to_string());
+    }
+    if let Some(execution_time) = statistics.execution_time {
+        let elapsed = format!("{execution_time:?}");
+        labels.push(
+            t!(
+                "statistics_execution_time",
+                locale = locale,
+                elapsed = elapsed
+            )
+            .to_string(),
+        );
+    }
+    if let Some(rows_scanned) = statistics.rows_scanned {
+        let rows = rows_scanned.to_formatted_string(&num_locale);
+        labels.push(t!("statistics_rows_scanned", locale = locale, rows = rows).to_string());
+    }
+    if let Some(bytes_scanned) = statistics.bytes_scanned {
+        let bytes = bytes_scanned.to_formatted_string(&num_locale);
+        labels.push(t!("statistics_bytes_scanned", locale = locale, bytes = bytes).to_string());
+    }
+
+    labels
+}
+
+/// Display the footer of the result set as a comment of the output format, for formats where
+/// text after the results would be read as markup; the footer is written without color.
+#[cfg(any(feature = "latex", feature = "rst"))]
+pub(crate) fn write_comment_footer(
+    options: &FormatterOptions,
+    results: &Results,
+    query_rows: u64,
+    comment_prefix: &str,
+    output: &mut Output,
+) -> Result<()> {
+    if let Some(footer) = footer_text(options, results, query_rows) {
+        writeln!(output, "{comment_prefix}{footer}")?;
+    }
+
+    output.flush()?;
+    Ok(())
+}
+
+/// Returns the footer of the result set as plain text, or `None` if the footer is disabled.
+/// This is used by formatters that cannot write the footer to the output directly.
+#[cfg(any(feature = "latex", feature = "rst", feature = "xlsx"))]
+pub(crate) fn footer_text(
+    options: &FormatterOptions,
+    results: &Results,
+    query_rows: u64,
+) -> Option<String> {
+    if !options.footer {
+        return None;
+    }
+
+    let locale = &options.locale;
+    let (rows_label, elapsed_display) = footer_labels(options, results, query_rows);
+    let footer = t!(
+        "footer_format",
+        locale = locale,
+        rows = rows_label,
+        elapsed = elapsed_display
+    )
+    .trim()
+    .to_string();
+    Some(footer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,4 +278,67 @@ mod tests {
         assert!(!output.contains("(9ns)"));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_write_footer_statistics() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            statistics: Some(QueryStatistics {
+                bytes_scanned: Some(1_048_576),
+                execution_time: Some(Duration::from_millis(1500)),
+                rows_affected: Some(3),
+                rows_scanned: Some(1000),
+            }),
+            ..Default::default()
+        };
+        let output = test_write_footer(&mut options, &query_result(1), 1).await?;
+        assert_eq!(
+            output,
+            "1 row (9ns, 3 affected, server 1.5s, 1,000 rows scanned, 1,048,576 bytes scanned)\n"
+        );
+
+        let output = test_write_footer(&mut options, &Execute(3), 0).await?;
+        assert_eq!(
+            output,
+            "3 rows (9ns, server 1.5s, 1,000 rows scanned, 1,048,576 bytes scanned)\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_write_footer_statistics_no_timer() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            timer: false,
+            statistics: Some(QueryStatistics {
+                bytes_scanned: Some(1024),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let output = test_write_footer(&mut options, &query_result(1), 1).await?;
+        assert!(!output.contains("scanned"));
+        Ok(())
+    }
+
+    #[cfg(any(feature = "latex", feature = "rst", feature = "xlsx"))]
+    #[test]
+    fn test_footer_text() {
+        let options = FormatterOptions {
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        };
+        let footer = footer_text(&options, &query_result(2), 2);
+        assert_eq!(footer, Some("2 rows (9ns)".to_string()));
+    }
+
+    #[cfg(any(feature = "latex", feature = "rst", feature = "xlsx"))]
+    #[test]
+    fn test_footer_text_disabled() {
+        let options = FormatterOptions {
+            footer: false,
+            ..Default::default()
+        };
+        assert_eq!(footer_text(&options, &query_result(2), 2), None);
+    }
 }
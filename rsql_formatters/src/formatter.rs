@@ -1,42 +1,119 @@
 use crate::error::Result;
 use crate::writers::Output;
+use crate::{BytesFormat, ColorRule};
 use async_trait::async_trait;
-use rsql_drivers::QueryResult;
+use rsql_drivers::{DateFormat, QueryResult, QueryStatistics, Value};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::sync::Mutex;
 use std::time::Duration;
 
 /// Options for formatters
 #[derive(Debug)]
 #[expect(clippy::struct_excessive_bools)]
 pub struct FormatterOptions {
+    /// The format used to display binary values
+    pub bytes_format: BytesFormat,
     pub changes: bool,
+    pub chart_width: usize,
     pub color: bool,
+    /// Conditional formatting rules applied to the cells and rows of tables when color is enabled
+    pub color_rules: Vec<ColorRule>,
+    /// The strftime patterns used to display date, time and date time values
+    pub date_format: DateFormat,
+    /// The number of digits after the decimal point for floating point and decimal values; when
+    /// not set, all the digits of the value are displayed
+    pub decimal_places: Option<usize>,
     pub elapsed: Duration,
+    /// When set, table formats display results that are wider than the width in the expanded
+    /// format, one line per column
+    pub expanded_width: Option<usize>,
     pub footer: bool,
     pub header: bool,
     pub locale: String,
+    /// The maximum width of values in tables; longer values are truncated with an ellipsis and
+    /// zero does not limit the width
+    pub max_column_width: usize,
+    /// When not zero, tables are written as the rows are fetched once the estimated size of the
+    /// buffered rows exceeds the limit in bytes
+    pub memory_limit: usize,
+    /// The string displayed for null values in tables
+    pub null_display: String,
     pub rows: bool,
+    /// Floating point and decimal values with an absolute value of at least 10^n, or less than
+    /// 10^-n, are displayed in scientific notation
+    pub scientific_notation: Option<u32>,
+    pub statistics: Option<QueryStatistics>,
+    /// When not zero, rows are written as they are fetched instead of when all the rows have been
+    /// fetched; tables calculate the column widths from the first `stream_rows` rows
+    pub stream_rows: usize,
     pub theme: String,
+    /// Display the thousands separator of the locale in numeric values
+    pub thousands_separator: bool,
     pub timer: bool,
+    /// Warnings raised while formatting the results, e.g. when the results exceed the memory
+    /// limit; the caller takes and displays them once the results are formatted
+    pub warnings: Mutex<Vec<String>>,
 }
 
 impl Default for FormatterOptions {
     fn default() -> Self {
         FormatterOptions {
+            bytes_format: BytesFormat::default(),
             changes: true,
+            chart_width: 40,
             color: true,
+            color_rules: Vec::new(),
+            date_format: DateFormat::default(),
+            decimal_places: None,
             elapsed: Duration::default(),
+            expanded_width: None,
             footer: true,
             header: true,
             locale: "en".to_string(),
+            max_column_width: 0,
+            memory_limit: 0,
+            null_display: "NULL".to_string(),
             rows: true,
+            scientific_notation: None,
+            statistics: None,
+            stream_rows: 0,
             theme: "Solarized (dark)".to_string(),
+            thousands_separator: true,
             timer: true,
+            warnings: Mutex::new(Vec::new()),
         }
     }
 }
 
+impl FormatterOptions {
+    /// Convert the value to a string, formatting binary values with the bytes format and date,
+    /// time and date time values with the date format
+    pub(crate) fn value_to_string(&self, value: &Value) -> String {
+        if let Value::Bytes(bytes) = value {
+            return self.bytes_format.format(bytes, &self.locale);
+        }
+        self.date_format
+            .format(value)
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    /// Add a warning to display once the results are formatted
+    pub(crate) fn add_warning(&self, warning: String) {
+        if let Ok(mut warnings) = self.warnings.lock() {
+            warnings.push(warning);
+        }
+    }
+
+    /// Take the warnings raised while formatting the results
+    pub fn take_warnings(&self) -> Vec<String> {
+        self.warnings
+            .lock()
+            .map(|mut warnings| std::mem::take(&mut *warnings))
+            .unwrap_or_default()
+    }
+}
+
 /// Results from a query or execute
 #[derive(Debug)]
 pub enum Results {
@@ -105,8 +182,12 @@ impl Default for FormatterManager {
     fn default() -> Self {
         let mut formatter_manager = FormatterManager::new();
 
+        #[cfg(feature = "arrow")]
+        formatter_manager.add(Box::new(crate::arrow::Formatter));
         #[cfg(feature = "ascii")]
         formatter_manager.add(Box::new(crate::ascii::Formatter));
+        #[cfg(feature = "chart")]
+        formatter_manager.add(Box::new(crate::chart::Formatter));
         #[cfg(feature = "csv")]
         formatter_manager.add(Box::new(crate::csv::Formatter));
         #[cfg(feature = "expanded")]
@@ -117,18 +198,24 @@ impl Default for FormatterManager {
         formatter_manager.add(Box::new(crate::json::Formatter));
         #[cfg(feature = "jsonl")]
         formatter_manager.add(Box::new(crate::jsonl::Formatter));
+        #[cfg(feature = "latex")]
+        formatter_manager.add(Box::new(crate::latex::Formatter));
         #[cfg(feature = "markdown")]
         formatter_manager.add(Box::new(crate::markdown::Formatter));
         #[cfg(feature = "plain")]
         formatter_manager.add(Box::new(crate::plain::Formatter));
         #[cfg(feature = "psql")]
         formatter_manager.add(Box::new(crate::psql::Formatter));
+        #[cfg(feature = "rst")]
+        formatter_manager.add(Box::new(crate::rst::Formatter));
         #[cfg(feature = "sqlite")]
         formatter_manager.add(Box::new(crate::sqlite::Formatter));
         #[cfg(feature = "tsv")]
         formatter_manager.add(Box::new(crate::tsv::Formatter));
         #[cfg(feature = "unicode")]
         formatter_manager.add(Box::new(crate::unicode::Formatter));
+        #[cfg(feature = "xlsx")]
+        formatter_manager.add(Box::new(crate::xlsx::Formatter));
         #[cfg(feature = "xml")]
         formatter_manager.add(Box::new(crate::xml::Formatter));
         #[cfg(feature = "yaml")]
@@ -143,6 +230,25 @@ mod tests {
     use super::*;
     use rsql_drivers::MemoryQueryResult;
 
+    #[test]
+    fn test_value_to_string() {
+        let options = FormatterOptions {
+            bytes_format: BytesFormat::Hex,
+            date_format: DateFormat {
+                date: Some("%d/%m/%Y".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let date = chrono::NaiveDate::from_ymd_opt(2000, 12, 31).expect("invalid date");
+        assert_eq!(
+            options.value_to_string(&Value::Bytes(vec![1, 2])),
+            "\\x0102"
+        );
+        assert_eq!(options.value_to_string(&Value::Date(date)), "31/12/2000");
+        assert_eq!(options.value_to_string(&Value::I64(12345)), "12345");
+    }
+
     #[test]
     fn test_results_is_query() {
         let query_results = Box::<MemoryQueryResult>::default();
@@ -179,8 +285,12 @@ mod tests {
         let formatters = FormatterManager::default();
         let formatter_count = 0;
 
+        #[cfg(feature = "arrow")]
+        let formatter_count = formatter_count + 1;
         #[cfg(feature = "ascii")]
         let formatter_count = formatter_count + 1;
+        #[cfg(feature = "chart")]
+        let formatter_count = formatter_count + 1;
         #[cfg(feature = "csv")]
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "expanded")]
@@ -191,18 +301,24 @@ mod tests {
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "jsonl")]
         let formatter_count = formatter_count + 1;
+        #[cfg(feature = "latex")]
+        let formatter_count = formatter_count + 1;
         #[cfg(feature = "markdown")]
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "plain")]
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "psql")]
         let formatter_count = formatter_count + 1;
+        #[cfg(feature = "rst")]
+        let formatter_count = formatter_count + 1;
         #[cfg(feature = "sqlite")]
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "tsv")]
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "unicode")]
         let formatter_count = formatter_count + 1;
+        #[cfg(feature = "xlsx")]
+        let formatter_count = formatter_count + 1;
         #[cfg(feature = "xml")]
         let formatter_count = formatter_count + 1;
         #[cfg(feature = "yaml")]
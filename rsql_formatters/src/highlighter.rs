@@ -48,6 +48,12 @@ impl Highlighter {
         }
     }
 
+    /// Get the sorted names of the available themes
+    #[must_use]
+    pub fn themes() -> Vec<String> {
+        ThemeSet::load_defaults().themes.into_keys().collect()
+    }
+
     /// Highlight the content
     ///
     /// # Errors
@@ -115,6 +121,13 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_themes() {
+        let themes = Highlighter::themes();
+        assert!(themes.contains(&"Solarized (dark)".to_string()));
+        assert!(themes.contains(&"base16-ocean.light".to_string()));
+    }
+
     #[test]
     fn test_highlight_color_forced() -> Result<()> {
         let options = FormatterOptions {
@@ -8,6 +8,7 @@ use async_trait::async_trait;
 use indexmap::IndexMap;
 use rsql_drivers::Value;
 use serde_json::{json, to_string_pretty};
+use std::io::Write;
 
 /// A formatter for JSON
 #[derive(Debug, Default)]
@@ -53,16 +54,20 @@ pub(crate) async fn format_json(
 
         for (c, data) in row.into_iter().enumerate() {
             let column = columns.get(c).expect("column not found");
-            if let Value::Bytes(ref _bytes) = data {
-                let value = Value::String(data.to_string());
-                json_row.insert(column, value);
-            } else {
-                json_row.insert(column, data.clone());
-            }
+            let value = match data {
+                Value::Bytes(_) | Value::Date(_) | Value::Time(_) | Value::DateTime(_) => {
+                    Value::String(options.value_to_string(&data))
+                }
+                _ => data,
+            };
+            json_row.insert(column, value);
         }
         if jsonl {
             let json = json!(json_row).to_string();
             write!(output, "{}", highlighter.highlight(json.as_str())?)?;
+            if options.stream_rows > 0 {
+                output.flush()?;
+            }
         } else {
             json_rows.push(json_row);
         }
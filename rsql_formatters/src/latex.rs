@@ -0,0 +1,158 @@
+use crate::error::Result;
+use crate::footer::write_comment_footer;
+use crate::formatter::FormatterOptions;
+use crate::writers::Output;
+use crate::Results::{Execute, Query};
+use crate::{table, Results};
+use async_trait::async_trait;
+use tabled::settings::{Style, Theme};
+
+/// A formatter for LaTeX tables
+#[derive(Debug, Default)]
+pub(crate) struct Formatter;
+
+#[async_trait]
+impl crate::Formatter for Formatter {
+    fn identifier(&self) -> &'static str {
+        "latex"
+    }
+
+    async fn format(
+        &self,
+        options: &FormatterOptions,
+        results: &mut Results,
+        output: &mut Output,
+    ) -> Result<()> {
+        let query_result = match results {
+            Query(query_result) => query_result,
+            Execute(_) => return write_comment_footer(options, results, 0, "% ", output),
+        };
+
+        let columns = query_result.columns().await.len();
+        if columns == 0 {
+            return write_comment_footer(options, results, 0, "% ", output);
+        }
+
+        let (rows, mut table, numeric_columns) =
+            table::build(options, query_result, escape, false).await?;
+        table.with(Theme::from_style(Style::empty().vertical('&')));
+
+        let column_spec: String = (0..columns)
+            .map(|column| {
+                if numeric_columns.contains(&column) {
+                    'r'
+                } else {
+                    'l'
+                }
+            })
+            .collect();
+
+        writeln!(output, "\\begin{{tabular}}{{{column_spec}}}")?;
+        writeln!(output, "\\hline")?;
+        for (index, line) in table.to_string().lines().enumerate() {
+            writeln!(output, "{} \\\\", line.trim())?;
+            if index == 0 && options.header {
+                writeln!(output, "\\hline")?;
+            }
+        }
+        writeln!(output, "\\hline")?;
+        writeln!(output, "\\end{{tabular}}")?;
+
+        write_comment_footer(options, results, rows, "% ", output)
+    }
+}
+
+/// Escape the characters that have a special meaning in LaTeX.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '\r' | '\n' => escaped.push(' '),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writers::Output;
+    use crate::Formatter;
+    use indoc::indoc;
+    use rsql_drivers::{MemoryQueryResult, Value};
+    use std::time::Duration;
+
+    fn query_result() -> Results {
+        let query_result = MemoryQueryResult::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Value::I64(12345), Value::String("foo & bar".to_string())],
+                vec![Value::I64(2), Value::Null],
+            ],
+        );
+
+        Query(Box::new(query_result))
+    }
+
+    #[tokio::test]
+    async fn test_format() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(5678),
+            ..Default::default()
+        };
+        let mut results = query_result();
+        let output = &mut Output::default();
+        let formatter = Formatter;
+
+        formatter.format(&options, &mut results, output).await?;
+
+        let latex_output = output.to_string().replace("\r\n", "\n");
+        let expected = indoc! {r"
+            \begin{tabular}{rl}
+            \hline
+            id   &    name \\
+            \hline
+            12,345 & foo \& bar \\
+            2 & NULL \\
+            \hline
+            \end{tabular}
+            % 2 rows (5.678µs)
+        "};
+        assert_eq!(latex_output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_execute() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+
+        Formatter.format(&options, &mut Execute(1), output).await?;
+
+        assert_eq!(output.to_string().replace("\r\n", "\n"), "% 1 row (9ns)\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a & b"), "a \\& b");
+        assert_eq!(escape("100%"), "100\\%");
+        assert_eq!(escape("$x_1#{y}"), "\\$x\\_1\\#\\{y\\}");
+        assert_eq!(escape("a\\b"), "a\\textbackslash{}b");
+        assert_eq!(escape("~^"), "\\textasciitilde{}\\textasciicircum{}");
+        assert_eq!(escape("a\nb"), "a b");
+    }
+}
@@ -6,8 +6,14 @@
 #[macro_use]
 extern crate rust_i18n;
 
+#[cfg(feature = "arrow")]
+mod arrow;
 #[cfg(feature = "ascii")]
 mod ascii;
+mod bytes;
+#[cfg(feature = "chart")]
+mod chart;
+mod color_rule;
 #[cfg(feature = "csv")]
 mod csv;
 #[cfg(any(feature = "csv", feature = "sqlite", feature = "tsv"))]
@@ -24,19 +30,28 @@ mod html;
 mod json;
 #[cfg(feature = "jsonl")]
 mod jsonl;
+#[cfg(feature = "latex")]
+mod latex;
 #[cfg(feature = "markdown")]
 mod markdown;
 #[cfg(feature = "plain")]
 mod plain;
 #[cfg(feature = "psql")]
 mod psql;
+#[cfg(feature = "arrow")]
+mod record_batch;
+#[cfg(feature = "rst")]
+mod rst;
 #[cfg(feature = "sqlite")]
 mod sqlite;
 #[cfg(any(
     feature = "ascii",
+    feature = "expanded",
+    feature = "latex",
     feature = "markdown",
     feature = "plain",
     feature = "psql",
+    feature = "rst",
     feature = "unicode"
 ))]
 mod table;
@@ -44,12 +59,27 @@ mod table;
 mod tsv;
 #[cfg(feature = "unicode")]
 mod unicode;
+#[cfg(any(
+    feature = "ascii",
+    feature = "expanded",
+    feature = "latex",
+    feature = "markdown",
+    feature = "plain",
+    feature = "psql",
+    feature = "rst",
+    feature = "unicode"
+))]
+mod value;
 pub mod writers;
+#[cfg(feature = "xlsx")]
+mod xlsx;
 #[cfg(feature = "xml")]
 mod xml;
 #[cfg(feature = "yaml")]
 mod yaml;
 
+pub use bytes::BytesFormat;
+pub use color_rule::ColorRule;
 pub use error::{Error, Result};
 pub use formatter::{Formatter, FormatterManager, FormatterOptions, Results};
 pub use highlighter::Highlighter;
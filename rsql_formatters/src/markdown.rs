@@ -1,8 +1,11 @@
 use crate::error::Result;
+use crate::footer::write_footer;
 use crate::formatter::FormatterOptions;
 use crate::writers::Output;
+use crate::Results::{Execute, Query};
 use crate::{table, Results};
 use async_trait::async_trait;
+use std::collections::BTreeSet;
 use tabled::settings::{Style, Theme};
 
 /// A formatter for markdown tables
@@ -21,11 +24,47 @@ impl crate::Formatter for Formatter {
         results: &mut Results,
         output: &mut Output,
     ) -> Result<()> {
-        let theme = Theme::from_style(Style::markdown());
-        table::format(theme, options, results, output).await
+        let query_result = match results {
+            Query(query_result) => query_result,
+            Execute(_) => return write_footer(options, results, 0, output).await,
+        };
+
+        if query_result.columns().await.is_empty() {
+            return write_footer(options, results, 0, output).await;
+        }
+
+        let (rows, mut table, numeric_columns) =
+            table::build(options, query_result, str::to_string, false).await?;
+        table.with(Theme::from_style(Style::markdown()));
+
+        for (index, line) in table.to_string().lines().enumerate() {
+            if index == 1 && line.starts_with("|-") {
+                writeln!(output, "{}", align_separator(line, &numeric_columns))?;
+            } else {
+                writeln!(output, "{line}")?;
+            }
+        }
+
+        write_footer(options, results, rows, output).await
     }
 }
 
+/// Mark the numeric columns as right aligned in the separator line, e.g. `|------|-----:|`, as
+/// markdown renderers ignore the padding of the values.
+fn align_separator(line: &str, numeric_columns: &BTreeSet<usize>) -> String {
+    line.split('|')
+        .enumerate()
+        .map(|(index, segment)| {
+            let numeric = index > 0 && numeric_columns.contains(&(index - 1));
+            match segment.strip_suffix('-') {
+                Some(prefix) if numeric => format!("{prefix}:"),
+                _ => segment.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("|")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -58,11 +97,20 @@ mod tests {
         let plain_output = output.to_string().replace("\r\n", "\n");
         let expected = indoc! {r"
             |   id   |
-            |--------|
+            |-------:|
             | 12,345 |
             1 row (5.678µs)
         "};
         assert_eq!(plain_output, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_align_separator() {
+        let numeric_columns = BTreeSet::from([1]);
+        assert_eq!(
+            align_separator("|------|-----|", &numeric_columns),
+            "|------|----:|"
+        );
+    }
 }
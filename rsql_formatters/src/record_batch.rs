@@ -0,0 +1,236 @@
+use crate::error::Result;
+use arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Float32Array, Float64Array, Int16Array,
+    Int32Array, Int64Array, Int8Array, StringArray, Time64NanosecondArray,
+    TimestampMicrosecondArray, UInt16Array, UInt32Array, UInt64Array, UInt8Array,
+};
+use arrow::datatypes::{DataType, Date32Type, Field, Schema, TimeUnit};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use chrono::Timelike;
+use rsql_drivers::{QueryResult, Value};
+use std::sync::Arc;
+
+/// Read all the rows from a query result into an Arrow [`RecordBatch`]; each column is converted
+/// to the Arrow type returned by [`data_type`].
+pub(crate) async fn to_record_batch(query_result: &mut dyn QueryResult) -> Result<RecordBatch> {
+    let columns = query_result.columns().await;
+    let mut rows = Vec::new();
+    while let Some(row) = query_result.next().await {
+        rows.push(row);
+    }
+
+    let mut fields = Vec::with_capacity(columns.len());
+    let mut arrays = Vec::with_capacity(columns.len());
+    for (index, column) in columns.iter().enumerate() {
+        let values: Vec<&Value> = rows
+            .iter()
+            .map(|row| row.get(index).unwrap_or(&Value::Null))
+            .collect();
+        let data_type = data_type(&values);
+        arrays.push(to_array(&data_type, &values));
+        fields.push(Field::new(column, data_type, true));
+    }
+
+    let schema = Arc::new(Schema::new(fields));
+    let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+    let record_batch = RecordBatch::try_new_with_options(schema, arrays, &options)?;
+    Ok(record_batch)
+}
+
+/// Get the Arrow data type for a column of values. Nulls are ignored when determining the type;
+/// columns with mixed types and values without an equivalent Arrow type (128-bit integers, UUIDs,
+/// JSON, arrays and maps) are mapped to [`DataType::Utf8`].
+pub(crate) fn data_type(values: &[&Value]) -> DataType {
+    let mut data_type = None;
+    for value in values {
+        let value_type = match value {
+            Value::Null => continue,
+            Value::Bool(_) => DataType::Boolean,
+            Value::Bytes(_) => DataType::Binary,
+            Value::I8(_) => DataType::Int8,
+            Value::I16(_) => DataType::Int16,
+            Value::I32(_) => DataType::Int32,
+            Value::I64(_) => DataType::Int64,
+            Value::U8(_) => DataType::UInt8,
+            Value::U16(_) => DataType::UInt16,
+            Value::U32(_) => DataType::UInt32,
+            Value::U64(_) => DataType::UInt64,
+            Value::F32(_) => DataType::Float32,
+            Value::F64(_) => DataType::Float64,
+            Value::Date(_) => DataType::Date32,
+            Value::Time(_) => DataType::Time64(TimeUnit::Nanosecond),
+            Value::DateTime(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+            _ => return DataType::Utf8,
+        };
+
+        match &data_type {
+            None => data_type = Some(value_type),
+            Some(data_type) if *data_type != value_type => return DataType::Utf8,
+            Some(_) => {}
+        }
+    }
+    data_type.unwrap_or(DataType::Utf8)
+}
+
+/// Convert a column of values to an Arrow array of the specified data type
+fn to_array(data_type: &DataType, values: &[&Value]) -> ArrayRef {
+    macro_rules! array {
+        ($array:ty, $variant:ident) => {
+            Arc::new(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Value::$variant(value) => Some(*value),
+                        _ => None,
+                    })
+                    .collect::<$array>(),
+            )
+        };
+    }
+
+    match data_type {
+        DataType::Boolean => array!(BooleanArray, Bool),
+        DataType::Binary => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Bytes(value) => Some(value.as_slice()),
+                    _ => None,
+                })
+                .collect::<BinaryArray>(),
+        ),
+        DataType::Int8 => array!(Int8Array, I8),
+        DataType::Int16 => array!(Int16Array, I16),
+        DataType::Int32 => array!(Int32Array, I32),
+        DataType::Int64 => array!(Int64Array, I64),
+        DataType::UInt8 => array!(UInt8Array, U8),
+        DataType::UInt16 => array!(UInt16Array, U16),
+        DataType::UInt32 => array!(UInt32Array, U32),
+        DataType::UInt64 => array!(UInt64Array, U64),
+        DataType::Float32 => array!(Float32Array, F32),
+        DataType::Float64 => array!(Float64Array, F64),
+        DataType::Date32 => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Date(value) => Some(Date32Type::from_naive_date(*value)),
+                    _ => None,
+                })
+                .collect::<Date32Array>(),
+        ),
+        DataType::Time64(_) => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Time(value) => Some(
+                        i64::from(value.num_seconds_from_midnight()) * 1_000_000_000
+                            + i64::from(value.nanosecond()),
+                    ),
+                    _ => None,
+                })
+                .collect::<Time64NanosecondArray>(),
+        ),
+        DataType::Timestamp(_, _) => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::DateTime(value) => Some(value.and_utc().timestamp_micros()),
+                    _ => None,
+                })
+                .collect::<TimestampMicrosecondArray>(),
+        ),
+        _ => Arc::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Value::Null => None,
+                    value => Some(value.to_string()),
+                })
+                .collect::<StringArray>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use arrow::array::Array;
+    use chrono::{NaiveDate, NaiveTime};
+    use rsql_drivers::MemoryQueryResult;
+
+    #[test]
+    fn test_data_type() {
+        assert_eq!(data_type(&[&Value::Bool(true)]), DataType::Boolean);
+        assert_eq!(data_type(&[&Value::Bytes(vec![1])]), DataType::Binary);
+        assert_eq!(data_type(&[&Value::I8(1)]), DataType::Int8);
+        assert_eq!(data_type(&[&Value::I64(1)]), DataType::Int64);
+        assert_eq!(data_type(&[&Value::U32(1)]), DataType::UInt32);
+        assert_eq!(data_type(&[&Value::F64(1.0)]), DataType::Float64);
+        assert_eq!(data_type(&[&Value::I128(1)]), DataType::Utf8);
+        assert_eq!(
+            data_type(&[&Value::String("foo".to_string())]),
+            DataType::Utf8
+        );
+        assert_eq!(
+            data_type(&[&Value::Time(NaiveTime::MIN)]),
+            DataType::Time64(TimeUnit::Nanosecond)
+        );
+    }
+
+    #[test]
+    fn test_data_type_nulls() {
+        assert_eq!(data_type(&[]), DataType::Utf8);
+        assert_eq!(data_type(&[&Value::Null]), DataType::Utf8);
+        assert_eq!(data_type(&[&Value::Null, &Value::I32(1)]), DataType::Int32);
+    }
+
+    #[test]
+    fn test_data_type_mixed() {
+        assert_eq!(
+            data_type(&[&Value::I32(1), &Value::String("foo".to_string())]),
+            DataType::Utf8
+        );
+    }
+
+    #[tokio::test]
+    async fn test_to_record_batch() -> anyhow::Result<()> {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).expect("invalid date");
+        let mut query_result = MemoryQueryResult::new(
+            vec!["id".to_string(), "created".to_string(), "data".to_string()],
+            vec![
+                vec![
+                    Value::I64(1),
+                    Value::Date(date),
+                    Value::String("foo".to_string()),
+                ],
+                vec![Value::I64(2), Value::Null, Value::I32(42)],
+            ],
+        );
+
+        let record_batch = to_record_batch(&mut query_result).await?;
+
+        assert_eq!(record_batch.num_rows(), 2);
+        let schema = record_batch.schema();
+        assert_eq!(schema.field(0).name(), "id");
+        assert_eq!(schema.field(0).data_type(), &DataType::Int64);
+        assert_eq!(schema.field(1).data_type(), &DataType::Date32);
+        assert_eq!(schema.field(2).data_type(), &DataType::Utf8);
+        assert_eq!(record_batch.column(1).null_count(), 1);
+        let strings = record_batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .expect("string array");
+        assert_eq!(strings.value(1), "42");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_to_record_batch_no_columns() -> anyhow::Result<()> {
+        let mut query_result = MemoryQueryResult::new(vec![], vec![]);
+        let record_batch = to_record_batch(&mut query_result).await?;
+        assert_eq!(record_batch.num_columns(), 0);
+        assert_eq!(record_batch.num_rows(), 0);
+        Ok(())
+    }
+}
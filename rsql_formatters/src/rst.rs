@@ -0,0 +1,141 @@
+use crate::error::Result;
+use crate::footer::write_comment_footer;
+use crate::formatter::FormatterOptions;
+use crate::writers::Output;
+use crate::Results::{Execute, Query};
+use crate::{table, Results};
+use async_trait::async_trait;
+use tabled::settings::{Style, Theme};
+
+/// A formatter for reStructuredText simple tables
+#[derive(Debug, Default)]
+pub(crate) struct Formatter;
+
+#[async_trait]
+impl crate::Formatter for Formatter {
+    fn identifier(&self) -> &'static str {
+        "rst"
+    }
+
+    async fn format(
+        &self,
+        options: &FormatterOptions,
+        results: &mut Results,
+        output: &mut Output,
+    ) -> Result<()> {
+        let query_result = match results {
+            Query(query_result) => query_result,
+            Execute(_) => return write_comment_footer(options, results, 0, "\n.. ", output),
+        };
+
+        if query_result.columns().await.is_empty() {
+            return write_comment_footer(options, results, 0, "\n.. ", output);
+        }
+
+        let (rows, mut table, _) = table::build(options, query_result, escape, false).await?;
+        table.with(Theme::from_style(Style::re_structured_text()));
+        writeln!(output, "{table}")?;
+
+        write_comment_footer(options, results, rows, "\n.. ", output)
+    }
+}
+
+/// Escape the characters that have a special meaning in reStructuredText inline markup.  Empty
+/// values are replaced with an escaped space, as an empty first column denotes a continuation
+/// line in a simple table.
+fn escape(value: &str) -> String {
+    if value.is_empty() {
+        return "\\ ".to_string();
+    }
+
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '\\' | '*' | '`' | '_' | '|' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            '\r' | '\n' => escaped.push(' '),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::writers::Output;
+    use crate::Formatter;
+    use indoc::indoc;
+    use rsql_drivers::{MemoryQueryResult, Value};
+    use std::time::Duration;
+
+    fn query_result() -> Results {
+        let query_result = MemoryQueryResult::new(
+            vec!["id".to_string(), "name".to_string()],
+            vec![
+                vec![Value::I64(12345), Value::String("*foo*".to_string())],
+                vec![Value::I64(2), Value::Null],
+            ],
+        );
+
+        Query(Box::new(query_result))
+    }
+
+    #[tokio::test]
+    async fn test_format() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(5678),
+            ..Default::default()
+        };
+        let mut results = query_result();
+        let output = &mut Output::default();
+        let formatter = Formatter;
+
+        formatter.format(&options, &mut results, output).await?;
+
+        let rst_output = output
+            .to_string()
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let expected = indoc! {r"
+            ======== =========
+               id      name
+            ======== =========
+             12,345   \*foo\*
+                  2   NULL
+            ======== =========
+
+            .. 2 rows (5.678µs)"};
+        assert_eq!(rst_output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_execute() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+
+        Formatter.format(&options, &mut Execute(1), output).await?;
+
+        assert_eq!(output.to_string().replace("\r\n", "\n"), "\n.. 1 row (9ns)\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(""), "\\ ");
+        assert_eq!(escape("*a*"), "\\*a\\*");
+        assert_eq!(escape("`a`_"), "\\`a\\`\\_");
+        assert_eq!(escape("a|b\\c"), "a\\|b\\\\c");
+        assert_eq!(escape("a\nb"), "a b");
+    }
+}
@@ -1,15 +1,28 @@
+use crate::error::Error::IoError;
 use crate::error::Result;
 use crate::footer::write_footer;
 use crate::formatter::FormatterOptions;
+use crate::value::format_value;
 use crate::writers::Output;
+use crate::ColorRule;
 use crate::Results;
 use crate::Results::Query;
+#[cfg(feature = "expanded")]
+use async_trait::async_trait;
 use num_format::Locale;
-use rsql_drivers::{QueryResult, Value};
+#[cfg(feature = "expanded")]
+use rsql_drivers::ColumnMetadata;
+use rsql_drivers::{MemoryQueryResult, QueryResult, Row, Value};
+use std::collections::BTreeSet;
+use std::io::Write;
+#[cfg(feature = "expanded")]
+use std::mem;
 use std::str::FromStr;
 use tabled::builder::Builder;
 use tabled::settings::object::{Cell, Rows};
 use tabled::settings::{Alignment, Theme};
+use tabled::Table;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Format the results of a query into a table and write to the output.
 pub async fn format(
@@ -26,65 +39,559 @@ pub async fn format(
             return Ok(());
         }
 
-        let mut builder = Builder::default();
-
-        if options.header {
-            builder.push_record(query_result.columns().await);
+        #[cfg(feature = "expanded")]
+        if let Some(width) = options.expanded_width {
+            if expanded(&theme, options, query_result, width).await {
+                let formatter = crate::expanded::Formatter;
+                return crate::Formatter::format(&formatter, options, results, output).await;
+            }
         }
 
-        let cells;
-        (rows, cells) = process_data(options, query_result, &mut builder).await?;
+        let streaming = options.stream_rows > 0 || options.memory_limit > 0;
+        if streaming {
+            if let Some(rows) = stream(&theme, options, query_result, output).await? {
+                return write_footer(options, results, rows, output).await;
+            }
+        }
 
-        let mut table = builder.build();
+        let mut table;
+        (rows, table, _) = build(options, query_result, str::to_string, options.color).await?;
         table.with(theme);
+        writeln!(output, "{table}")?;
+    }
+
+    write_footer(options, results, rows, output).await?;
+    Ok(())
+}
 
-        if options.header {
-            table.modify(Rows::first(), Alignment::center());
+/// The number of rows used to decide whether results are displayed in the expanded format when
+/// rows are not streamed.
+#[cfg(feature = "expanded")]
+const EXPANDED_ROWS: usize = 1_000;
+
+/// Returns true if the table of the first rows of the query result is wider than the width, and
+/// the results should be displayed in the expanded format.  The layout is decided from the first
+/// `stream_rows` rows, or the first [`EXPANDED_ROWS`] rows when rows are not streamed, so that
+/// the results are not read into memory; the rows that are read are returned again by the query
+/// result.
+#[cfg(feature = "expanded")]
+async fn expanded(
+    theme: &Theme,
+    options: &FormatterOptions,
+    query_result: &mut Box<dyn QueryResult>,
+    width: usize,
+) -> bool {
+    let sample_rows = match options.stream_rows {
+        0 => EXPANDED_ROWS,
+        stream_rows => stream_rows,
+    };
+    let columns = query_result.columns().await;
+    let mut rows = Vec::new();
+    while rows.len() < sample_rows {
+        match query_result.next().await {
+            Some(row) => rows.push(row),
+            None => break,
         }
+    }
 
-        // Align numeric columns to the right
-        for cell in cells {
-            table.modify(cell, Alignment::right());
+    let locale = Locale::from_str(options.locale.as_str()).unwrap_or(Locale::en);
+    let widths = column_widths(options, locale, &columns, &rows);
+    let mut builder = Builder::default();
+    builder.push_record(widths.iter().map(|width| "x".repeat(*width)));
+    let mut table = builder.build();
+    table.with(theme.clone());
+
+    let inner = mem::replace(
+        query_result,
+        Box::new(MemoryQueryResult::new(Vec::new(), Vec::new())),
+    );
+    *query_result = Box::new(PrefetchedQueryResult {
+        rows: rows.into_iter(),
+        inner,
+    });
+    table.total_width() > width
+}
+
+/// Query result that returns the rows that were read ahead to decide the layout of the results,
+/// followed by the remaining rows of the query result.
+#[cfg(feature = "expanded")]
+#[derive(Debug)]
+struct PrefetchedQueryResult {
+    rows: std::vec::IntoIter<Row>,
+    inner: Box<dyn QueryResult>,
+}
+
+#[cfg(feature = "expanded")]
+#[async_trait]
+impl QueryResult for PrefetchedQueryResult {
+    async fn columns(&self) -> Vec<String> {
+        self.inner.columns().await
+    }
+
+    async fn columns_metadata(&self) -> Vec<ColumnMetadata> {
+        self.inner.columns_metadata().await
+    }
+
+    async fn next(&mut self) -> Option<Row> {
+        match self.rows.next() {
+            Some(row) => Some(row),
+            None => self.inner.next().await,
         }
+    }
 
-        writeln!(output, "{table}")?;
+    fn take_error(&mut self) -> Option<rsql_drivers::Error> {
+        self.inner.take_error()
     }
+}
 
-    write_footer(options, results, rows, output).await?;
-    Ok(())
+/// Build a table from the results of a query. Every header and value is passed through the
+/// `escape` function before being added to the table; when `styled` is set, the color rules of the
+/// options are applied to the values with ANSI styles.
+///
+/// Returns the number of rows, the table and the indexes of the columns that contain numeric
+/// values; the values of columns that the column metadata reports as numeric, including nulls,
+/// are aligned to the right.
+pub(crate) async fn build(
+    options: &FormatterOptions,
+    query_result: &mut Box<dyn QueryResult>,
+    escape: fn(&str) -> String,
+    styled: bool,
+) -> Result<(u64, Table, BTreeSet<usize>)> {
+    let mut builder = Builder::default();
+
+    let header = options.header;
+    let header_rows = if header {
+        let columns = query_result.columns().await;
+        builder.push_record(columns.iter().map(|column| escape(column.as_str())));
+        1
+    } else {
+        0
+    };
+
+    let (rows, cells, numeric_columns) = process_data(
+        options,
+        query_result,
+        &mut builder,
+        escape,
+        styled,
+        header_rows,
+    )
+    .await?;
+
+    let mut table = builder.build();
+
+    if header {
+        table.modify(Rows::first(), Alignment::center());
+    }
+
+    // Align numeric columns to the right
+    for cell in cells {
+        table.modify(cell, Alignment::right());
+    }
+
+    Ok((rows, table, numeric_columns))
 }
 
 async fn process_data(
     options: &FormatterOptions,
     query_result: &mut Box<dyn QueryResult>,
     builder: &mut Builder,
-) -> Result<(u64, Vec<Cell>)> {
+    escape: fn(&str) -> String,
+    styled: bool,
+    header_rows: u64,
+) -> Result<(u64, Vec<Cell>, BTreeSet<usize>)> {
     let locale = Locale::from_str(options.locale.as_str()).unwrap_or(Locale::en);
+    let rules = if styled {
+        color_rules(options, &**query_result).await
+    } else {
+        Vec::new()
+    };
     let mut rows: u64 = 0;
     let mut cells = Vec::new();
+    let mut numeric_columns: BTreeSet<usize> = query_result
+        .columns_metadata()
+        .await
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.is_numeric())
+        .map(|(index, _)| index)
+        .collect();
     while let Some(row) = query_result.next().await {
         let mut row_data = Vec::new();
+        let row_rule = row_rule(&rules, &row);
 
         for (column, data) in row.into_iter().enumerate() {
-            let data = if data == Value::Null {
-                "NULL".to_string()
-            } else {
-                if data.is_numeric() {
-                    let row = if options.header { rows + 1 } else { rows };
-                    let cell = Cell::new(usize::try_from(row)?, column);
-                    cells.push(cell);
-                }
-                data.to_formatted_string(&locale)
-            };
+            let rule = cell_rule(&rules, column, &data).or(row_rule);
+            if data.is_numeric() {
+                numeric_columns.insert(column);
+            }
+            if numeric_columns.contains(&column) {
+                let cell = Cell::new(usize::try_from(rows + header_rows)?, column);
+                cells.push(cell);
+            }
+            let data = cell_value(options, locale, &data);
+            let data = escape(data.as_str());
 
-            row_data.push(data);
+            match rule {
+                Some(rule) => row_data.push(rule.style(&data)),
+                None => row_data.push(data),
+            }
         }
 
         rows += 1;
         builder.push_record(row_data);
     }
 
-    Ok((rows, cells))
+    Ok((rows, cells, numeric_columns))
+}
+
+/// Get the first row rule of the color rules that matches the row
+fn row_rule<'a>(rules: &[(usize, &'a ColorRule)], row: &Row) -> Option<&'a ColorRule> {
+    rules
+        .iter()
+        .find(|(index, rule)| rule.row() && row.get(*index).is_some_and(|v| rule.matches(v)))
+        .map(|(_, rule)| *rule)
+}
+
+/// Get the first cell rule of the color rules for the column that matches the value
+fn cell_rule<'a>(
+    rules: &[(usize, &'a ColorRule)],
+    column: usize,
+    value: &Value,
+) -> Option<&'a ColorRule> {
+    rules
+        .iter()
+        .find(|(index, rule)| !rule.row() && *index == column && rule.matches(value))
+        .map(|(_, rule)| *rule)
+}
+
+/// Format the value of a cell, truncated to the maximum column width of the options
+fn cell_value(options: &FormatterOptions, locale: Locale, value: &Value) -> String {
+    let value = if *value == Value::Null {
+        options.null_display.clone()
+    } else {
+        format_value(options, locale, value)
+    };
+    truncate(value, options.max_column_width)
+}
+
+/// Format the rows of the query result as they are fetched.  The first `stream_rows` rows, or the
+/// rows that fit in the memory limit, are buffered to calculate the widths of the columns; the
+/// following rows are padded or truncated to the same widths and written as soon as each row is
+/// fetched.  The lines of the rows are written directly with the borders of the theme, so that
+/// every row is written the same way whether it was buffered or not.
+///
+/// Returns `None` when the query result does not have more rows than the rows that are buffered;
+/// the buffered rows are restored to the query result so that the table can be formatted as usual.
+async fn stream(
+    theme: &Theme,
+    options: &FormatterOptions,
+    query_result: &mut Box<dyn QueryResult>,
+    output: &mut Output,
+) -> Result<Option<u64>> {
+    let columns = query_result.columns().await;
+    let columns_metadata = query_result.columns_metadata().await;
+    let mut buffered_rows = Vec::new();
+    let mut buffered_size = 0;
+    let mut next_row = None;
+    while next_row.is_none() {
+        let buffer_full = (options.stream_rows > 0 && buffered_rows.len() >= options.stream_rows)
+            || (options.memory_limit > 0 && buffered_size > options.memory_limit);
+        match query_result.next().await {
+            Some(row) if !buffer_full => {
+                buffered_size += row.iter().map(Value::estimated_size).sum::<usize>();
+                buffered_rows.push(row);
+            }
+            Some(row) => next_row = Some(row),
+            None => {
+                if let Some(error) = query_result.take_error() {
+                    return Err(IoError(error.into()));
+                }
+                let memory_result = MemoryQueryResult::new(columns, buffered_rows)
+                    .with_columns_metadata(columns_metadata);
+                *query_result = Box::new(memory_result);
+                return Ok(None);
+            }
+        }
+    }
+
+    if options.memory_limit > 0 && buffered_size > options.memory_limit {
+        options.add_warning(
+            t!("memory_limit_streaming", locale = options.locale.as_str()).to_string(),
+        );
+    }
+
+    let locale = Locale::from_str(options.locale.as_str()).unwrap_or(Locale::en);
+    let widths = column_widths(options, locale, &columns, &buffered_rows);
+    let layout = Layout::new(theme, &widths);
+    let rules = if options.color {
+        color_rules(options, &**query_result).await
+    } else {
+        Vec::new()
+    };
+    let mut numeric_columns: BTreeSet<usize> = columns_metadata
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.is_numeric())
+        .map(|(index, _)| index)
+        .collect();
+
+    write_lines(output, &layout.top)?;
+    if options.header {
+        let cells: Vec<Vec<String>> = columns
+            .iter()
+            .zip(&widths)
+            .map(|(column, width)| fit(column, *width, Align::Center, None))
+            .collect();
+        layout.write_row(output, &cells)?;
+        write_lines(output, &layout.header)?;
+    }
+
+    buffered_rows.extend(next_row);
+    let mut buffered_rows = buffered_rows.into_iter();
+    let mut rows: u64 = 0;
+    loop {
+        let row = match buffered_rows.next() {
+            Some(row) => row,
+            None => match query_result.next().await {
+                Some(row) => row,
+                None => break,
+            },
+        };
+        if rows == 1 && !options.header {
+            write_lines(output, &layout.header)?;
+        } else if rows > 0 {
+            write_lines(output, &layout.separator)?;
+        }
+
+        let row_rule = row_rule(&rules, &row);
+        let mut cells = Vec::with_capacity(widths.len());
+        for (column, (data, width)) in row.iter().zip(&widths).enumerate() {
+            let rule = cell_rule(&rules, column, data).or(row_rule);
+            if data.is_numeric() {
+                numeric_columns.insert(column);
+            }
+            let align = if numeric_columns.contains(&column) {
+                Align::Right
+            } else {
+                Align::Left
+            };
+            let value = cell_value(options, locale, data);
+            cells.push(fit(&value, *width, align, rule));
+        }
+        layout.write_row(output, &cells)?;
+        output.flush()?;
+        rows += 1;
+    }
+
+    write_lines(output, &layout.bottom)?;
+    Ok(Some(rows))
+}
+
+/// Get the widths of the columns for the header and the values of the rows; the width of a value
+/// with multiple lines is the width of its longest line.
+fn column_widths(
+    options: &FormatterOptions,
+    locale: Locale,
+    columns: &[String],
+    rows: &[Row],
+) -> Vec<usize> {
+    let width = |value: &str| value.lines().map(UnicodeWidthStr::width).max();
+    columns
+        .iter()
+        .enumerate()
+        .map(|(index, column)| {
+            let header_width = if options.header { width(column) } else { None };
+            rows.iter()
+                .filter_map(|row| row.get(index))
+                .filter_map(|value| width(&cell_value(options, locale, value)))
+                .chain(header_width)
+                .max()
+                .unwrap_or_default()
+                .max(1)
+        })
+        .collect()
+}
+
+/// The alignment of the lines of a cell
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Pad or truncate each line of the value to the width; the style of the color rule is applied to
+/// the value of each line, but not to the padding.
+fn fit(value: &str, width: usize, align: Align, rule: Option<&ColorRule>) -> Vec<String> {
+    let lines: Vec<&str> = value.lines().collect();
+    let lines = if lines.is_empty() { vec![""] } else { lines };
+    lines
+        .into_iter()
+        .map(|line| {
+            let line = truncate(line.to_string(), width);
+            let padding = width.saturating_sub(line.width());
+            let (left, right) = match align {
+                Align::Left => (0, padding),
+                Align::Center => (padding / 2, padding - padding / 2),
+                Align::Right => (padding, 0),
+            };
+            let line = match rule {
+                Some(rule) => rule.style(&line),
+                None => line,
+            };
+            format!("{}{line}{}", " ".repeat(left), " ".repeat(right))
+        })
+        .collect()
+}
+
+/// The lines of a table with a theme, used to write the rows of a table without building a table
+/// for each row: the borders before the first row, after the header, between rows and after the
+/// last row, and the text before, between and after the values of a row.  The layout is read from
+/// a table of the theme with placeholder values, so that the borders and padding of every theme are
+/// written as the theme would write them.
+#[derive(Debug, Default, PartialEq)]
+struct Layout {
+    top: Vec<String>,
+    header: Vec<String>,
+    separator: Vec<String>,
+    bottom: Vec<String>,
+    left: String,
+    verticals: Vec<String>,
+    right: String,
+    widths: Vec<usize>,
+}
+
+impl Layout {
+    /// Get the layout of a table of the theme with columns of the widths; the borders after the
+    /// first row are the borders after the header, or after the first row of a table without a
+    /// header.
+    fn new(theme: &Theme, widths: &[usize]) -> Self {
+        let placeholder: Vec<String> = widths.iter().map(|width| "x".repeat(*width)).collect();
+        let mut builder = Builder::default();
+        for _ in 0..3 {
+            builder.push_record(placeholder.clone());
+        }
+        let mut table = builder.build();
+        table.with(theme.clone());
+        let table = table.to_string();
+        let lines: Vec<&str> = table.lines().collect();
+        let rows: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.contains('x'))
+            .map(|(index, _)| index)
+            .collect();
+        let [first, second, last] = rows[..] else {
+            return Layout::default();
+        };
+        let borders = |start: usize, end: usize| -> Vec<String> {
+            lines[start..end].iter().map(ToString::to_string).collect()
+        };
+
+        // Split the line of a row into the text around the placeholder values
+        let mut parts = Vec::with_capacity(widths.len() + 1);
+        let mut chars = lines[first].chars().peekable();
+        for width in widths {
+            let mut part = String::new();
+            while let Some(char) = chars.next_if(|char| *char != 'x') {
+                part.push(char);
+            }
+            parts.push(part);
+            for _ in 0..*width {
+                chars.next();
+            }
+        }
+        parts.push(chars.collect());
+        let right = parts.pop().unwrap_or_default();
+        let left = if parts.is_empty() {
+            String::new()
+        } else {
+            parts.remove(0)
+        };
+
+        Layout {
+            top: borders(0, first),
+            header: borders(first + 1, second),
+            separator: borders(second + 1, last),
+            bottom: borders(last + 1, lines.len()),
+            left,
+            verticals: parts,
+            right,
+            widths: widths.to_vec(),
+        }
+    }
+
+    /// Write a row of cells, each with the lines of the cell padded to the width of the column;
+    /// cells with fewer lines than the row are padded with blank lines.
+    fn write_row(&self, output: &mut Output, cells: &[Vec<String>]) -> Result<()> {
+        let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+        for index in 0..height {
+            let mut line = self.left.clone();
+            for (column, cell) in cells.iter().enumerate() {
+                if column > 0 {
+                    line.push_str(self.verticals.get(column - 1).map_or("", String::as_str));
+                }
+                if let Some(value) = cell.get(index) {
+                    line.push_str(value);
+                } else {
+                    let width = self.widths.get(column).copied().unwrap_or_default();
+                    line.push_str(&" ".repeat(width));
+                }
+            }
+            line.push_str(&self.right);
+            writeln!(output, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the lines of a border
+fn write_lines(output: &mut Output, lines: &[String]) -> Result<()> {
+    for line in lines {
+        writeln!(output, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Get the color rules of the options, with the index of the column each rule is evaluated
+/// against; rules for columns that are not in the results are ignored. Cell rules take precedence
+/// over row rules, and the first matching rule is applied.
+async fn color_rules<'a>(
+    options: &'a FormatterOptions,
+    query_result: &dyn QueryResult,
+) -> Vec<(usize, &'a ColorRule)> {
+    let columns = query_result.columns().await;
+    options
+        .color_rules
+        .iter()
+        .filter_map(|rule| {
+            columns
+                .iter()
+                .position(|column| column == rule.column())
+                .map(|index| (index, rule))
+        })
+        .collect()
+}
+
+/// Truncate the value to the maximum width, ending the value with an ellipsis when it is
+/// truncated; a maximum width of zero does not truncate the value.
+pub(crate) fn truncate(value: String, max_width: usize) -> String {
+    if max_width == 0 || value.width() <= max_width {
+        return value;
+    }
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for char in value.chars() {
+        width += char.width().unwrap_or_default();
+        if width >= max_width {
+            break;
+        }
+        truncated.push(char);
+    }
+    truncated.push('…');
+    truncated
 }
 
 #[cfg(test)]
@@ -225,7 +732,7 @@ mod tests {
             +--------+
             |   id   |
             +--------+
-            | NULL   |
+            |   NULL |
             +--------+
             | 12,345 |
             +--------+
@@ -235,6 +742,195 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_query_format_stream() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            locale: "en".to_string(),
+            stream_rows: 1,
+            ..Default::default()
+        };
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec![COLUMN_HEADER.to_string(), "name".to_string()],
+            vec![
+                vec![Value::I64(1), Value::String("foo".to_string())],
+                vec![Value::I64(2), Value::String("ab".to_string())],
+                vec![Value::I64(3), Value::String("longer".to_string())],
+            ],
+        )));
+
+        let output = test_format(&mut options, &mut results).await?;
+        let expected = indoc! {r"
+            +----+------+
+            | id | name |
+            +----+------+
+            |  1 | foo  |
+            +----+------+
+            |  2 | ab   |
+            +----+------+
+            |  3 | lon… |
+            +----+------+
+            3 rows (9ns)
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_format_memory_limit() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            locale: "en".to_string(),
+            memory_limit: 1,
+            ..Default::default()
+        };
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec![COLUMN_HEADER.to_string()],
+            vec![vec![Value::I64(1)], vec![Value::I64(22)]],
+        )));
+
+        let output = test_format(&mut options, &mut results).await?;
+        let expected = indoc! {r"
+            +----+
+            | id |
+            +----+
+            |  1 |
+            +----+
+            | 22 |
+            +----+
+            2 rows (9ns)
+        "};
+        assert_eq!(output, expected);
+        assert_eq!(
+            options.take_warnings(),
+            vec!["Warning: the results exceed the memory limit; rows are written as they are fetched"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_format_stream_buffered_rows() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            locale: "en".to_string(),
+            stream_rows: 2,
+            ..Default::default()
+        };
+        let mut results = query_result_two_rows();
+
+        let output = test_format(&mut options, &mut results).await?;
+        let expected = indoc! {r"
+            +--------+
+            |   id   |
+            +--------+
+            |   NULL |
+            +--------+
+            | 12,345 |
+            +--------+
+            2 rows (9ns)
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_layout() {
+        let layout = Layout::new(&Theme::from_style(Style::ascii()), &[2, 4]);
+        assert_eq!(
+            layout,
+            Layout {
+                top: vec!["+----+------+".to_string()],
+                header: vec!["+----+------+".to_string()],
+                separator: vec!["+----+------+".to_string()],
+                bottom: vec!["+----+------+".to_string()],
+                left: "| ".to_string(),
+                verticals: vec![" | ".to_string()],
+                right: " |".to_string(),
+                widths: vec![2, 4],
+            }
+        );
+
+        let layout = Layout::new(&Theme::from_style(Style::psql()), &[2]);
+        assert!(layout.top.is_empty());
+        assert_eq!(layout.header, vec!["----".to_string()]);
+        assert!(layout.separator.is_empty());
+        assert!(layout.bottom.is_empty());
+    }
+
+    #[test]
+    fn test_fit() {
+        assert_eq!(fit("1", 3, Align::Right, None), vec!["  1"]);
+        assert_eq!(fit("a", 3, Align::Left, None), vec!["a  "]);
+        assert_eq!(fit("a", 4, Align::Center, None), vec![" a  "]);
+        assert_eq!(fit("abcd", 3, Align::Left, None), vec!["ab…"]);
+        assert_eq!(fit("a\nbcde", 3, Align::Left, None), vec!["a  ", "bc…"]);
+        assert_eq!(fit("", 2, Align::Left, None), vec!["  "]);
+        assert_eq!(fit("日本", 5, Align::Left, None), vec!["日本 "]);
+    }
+
+    #[tokio::test]
+    async fn test_query_format_stream_multiline_and_wide_values() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            locale: "en".to_string(),
+            stream_rows: 1,
+            ..Default::default()
+        };
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec![COLUMN_HEADER.to_string(), "name".to_string()],
+            vec![
+                vec![
+                    Value::String("a".to_string()),
+                    Value::String("日本".to_string()),
+                ],
+                vec![
+                    Value::String("b".to_string()),
+                    Value::String("foo\nbar".to_string()),
+                ],
+            ],
+        )));
+
+        let output = test_format(&mut options, &mut results).await?;
+        let expected = indoc! {r"
+            +----+------+
+            | id | name |
+            +----+------+
+            | a  | 日本 |
+            +----+------+
+            | b  | foo  |
+            |    | bar  |
+            +----+------+
+            2 rows (9ns)
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_format_stream_header_separator() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            locale: "en".to_string(),
+            elapsed: Duration::from_nanos(9),
+            stream_rows: 1,
+            ..Default::default()
+        };
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec![COLUMN_HEADER.to_string()],
+            vec![
+                vec![Value::String("a".to_string())],
+                vec![Value::String("b".to_string())],
+            ],
+        )));
+        let output = &mut Output::default();
+
+        format(Theme::from_style(Style::psql()), &options, &mut results, output).await?;
+        let output = output.to_string();
+        let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+        assert_eq!(lines, vec![" id", "----", " a", " b", "2 rows (9ns)"]);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_query_format_two_rows_with_color() -> anyhow::Result<()> {
         let mut options = FormatterOptions {
@@ -312,4 +1008,145 @@ mod tests {
         assert_eq!(output, expected);
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_query_null_display_and_max_column_width() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            locale: "en".to_string(),
+            max_column_width: 10,
+            null_display: "∅".to_string(),
+            ..Default::default()
+        };
+        let query_result = MemoryQueryResult::new(
+            vec![COLUMN_HEADER.to_string(), "text".to_string()],
+            vec![vec![
+                Value::Null,
+                Value::String("Lorem ipsum dolor sit amet".to_string()),
+            ]],
+        );
+        let mut results = Query(Box::new(query_result));
+
+        let output = test_format(&mut options, &mut results).await?;
+        let expected = indoc! {r"
+            +----+------------+
+            | id |    text    |
+            +----+------------+
+            | ∅  | Lorem ips… |
+            +----+------------+
+            1 row (9ns)
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_query_color_rules() -> anyhow::Result<()> {
+        colored::control::set_override(true);
+        let mut options = FormatterOptions {
+            color: true,
+            color_rules: vec![
+                ColorRule::from_str("number > 40 => bold").map_err(anyhow::Error::msg)?,
+                ColorRule::from_str("string = 'foo' => red row").map_err(anyhow::Error::msg)?,
+            ],
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let mut results = query_result_number_and_string();
+
+        let output = test_format(&mut options, &mut results).await?;
+        assert!(output.contains("\u{1b}[1m42\u{1b}[0m"));
+        assert!(output.contains("\u{1b}[31mfoo\u{1b}[0m"));
+        assert!(output.contains("\u{1b}[31mLorem ipsum dolor sit amet\u{1b}[0m"));
+        assert!(!output.contains("\u{1b}[31m42"));
+        Ok(())
+    }
+
+    #[cfg(feature = "expanded")]
+    #[tokio::test]
+    async fn test_query_expanded_width() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            expanded_width: Some(80),
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let mut results = query_result_number_and_string();
+        let output = test_format(&mut options, &mut results).await?;
+        assert!(output.starts_with("+--------+--------+"));
+
+        options.expanded_width = Some(20);
+        let mut results = query_result_number_and_string();
+        let output = test_format(&mut options, &mut results).await?;
+        assert!(output.starts_with("-[ RECORD 1 ]"));
+        assert!(output.contains("number | 42\n"));
+        assert!(output.contains("text   | Lorem ipsum dolor sit amet\n"));
+        assert!(output.ends_with("1 row (9ns)\n"));
+        Ok(())
+    }
+
+    #[cfg(feature = "expanded")]
+    #[tokio::test]
+    async fn test_query_expanded_width_first_rows() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            color: false,
+            expanded_width: Some(20),
+            locale: "en".to_string(),
+            stream_rows: 1,
+            ..Default::default()
+        };
+        let mut results = Query(Box::new(MemoryQueryResult::new(
+            vec![COLUMN_HEADER.to_string()],
+            vec![
+                vec![Value::String("a".to_string())],
+                vec![Value::String("Lorem ipsum dolor sit amet".to_string())],
+            ],
+        )));
+
+        let output = test_format(&mut options, &mut results).await?;
+        let expected = indoc! {r"
+            +----+
+            | id |
+            +----+
+            | a  |
+            +----+
+            | L… |
+            +----+
+            2 rows (9ns)
+        "};
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("foo".to_string(), 0), "foo");
+        assert_eq!(truncate("foo".to_string(), 3), "foo");
+        assert_eq!(truncate("foobar".to_string(), 4), "foo…");
+        assert_eq!(truncate("ñandú".to_string(), 2), "ñ…");
+        assert_eq!(truncate("foo".to_string(), 1), "…");
+        assert_eq!(truncate("日本語".to_string(), 4), "日…");
+        assert_eq!(truncate("日本語".to_string(), 6), "日本語");
+    }
+
+    #[tokio::test]
+    async fn test_build_escape() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            locale: "en".to_string(),
+            ..Default::default()
+        };
+        let mut query_result: Box<dyn QueryResult> = Box::new(MemoryQueryResult::new(
+            vec!["number".to_string(), "string".to_string()],
+            vec![vec![Value::I64(42), Value::String("foo".to_string())]],
+        ));
+
+        let (rows, table, numeric_columns) =
+            build(&options, &mut query_result, str::to_uppercase, false).await?;
+
+        assert_eq!(rows, 1);
+        assert!(table.to_string().contains("NUMBER"));
+        assert!(table.to_string().contains("FOO"));
+        assert_eq!(numeric_columns, BTreeSet::from([0]));
+        Ok(())
+    }
 }
@@ -0,0 +1,202 @@
+use crate::formatter::FormatterOptions;
+use num_format::{Locale, ToFormattedString};
+use rsql_drivers::Value;
+use rust_decimal::RoundingStrategy;
+
+/// Format a value for display, applying the decimal places, thousands separator and scientific
+/// notation options to numeric values and the bytes format to binary values; other values are
+/// formatted for the locale.
+pub(crate) fn format_value(options: &FormatterOptions, locale: Locale, value: &Value) -> String {
+    match value {
+        Value::Bytes(bytes) => options.bytes_format.format(bytes, &options.locale),
+        Value::F32(number) => format_float(options, locale, f64::from(*number), number.to_string()),
+        Value::F64(number) => format_float(options, locale, *number, number.to_string()),
+        Value::Decimal(number) => {
+            if let Some(float) = value.to_f64() {
+                if is_scientific(options, float) {
+                    return scientific(options, float);
+                }
+            }
+            // The precision of a decimal truncates the digits, so the number is rounded first
+            let digits = match options.decimal_places {
+                Some(places) => {
+                    let scale = u32::try_from(places).unwrap_or(u32::MAX);
+                    let number = number
+                        .round_dp_with_strategy(scale, RoundingStrategy::MidpointAwayFromZero);
+                    format!("{number:.places$}")
+                }
+                None => number.to_string(),
+            };
+            localize(&digits, locale, options.thousands_separator)
+        }
+        _ if value.is_numeric() && !options.thousands_separator => value.to_string(),
+        _ => value.to_formatted_string_with(&locale, &options.date_format),
+    }
+}
+
+/// Format a floating point number; without decimal places, the default representation of the
+/// number is used.
+fn format_float(
+    options: &FormatterOptions,
+    locale: Locale,
+    number: f64,
+    default: String,
+) -> String {
+    if is_scientific(options, number) {
+        return scientific(options, number);
+    }
+    match options.decimal_places {
+        Some(places) => localize(
+            &format!("{number:.places$}"),
+            locale,
+            options.thousands_separator,
+        ),
+        None => default,
+    }
+}
+
+/// Determine if the number is displayed in scientific notation; numbers with an absolute value of
+/// at least 10^n, or less than 10^-n, are displayed in scientific notation.
+fn is_scientific(options: &FormatterOptions, number: f64) -> bool {
+    let Some(exponent) = options.scientific_notation else {
+        return false;
+    };
+    if !number.is_finite() || number == 0.0 {
+        return false;
+    }
+
+    let threshold = 10f64.powi(i32::try_from(exponent).unwrap_or(i32::MAX));
+    let absolute = number.abs();
+    absolute >= threshold || absolute < threshold.recip()
+}
+
+/// Format the number in scientific notation, e.g. `1.5e10`
+fn scientific(options: &FormatterOptions, number: f64) -> String {
+    match options.decimal_places {
+        Some(places) => format!("{number:.places$e}"),
+        None => format!("{number:e}"),
+    }
+}
+
+/// Localize the digits of a number, e.g. `-1234.5`, using the minus sign, thousands separator
+/// and decimal separator of the locale.
+fn localize(digits: &str, locale: Locale, thousands_separator: bool) -> String {
+    let (negative, digits) = match digits.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, digits),
+    };
+    let (integer, fraction) = match digits.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (digits, None),
+    };
+
+    let mut formatted = String::new();
+    if negative {
+        formatted.push_str(locale.minus_sign());
+    }
+    match integer.parse::<u128>() {
+        Ok(integer) if thousands_separator => {
+            formatted.push_str(&integer.to_formatted_string(&locale));
+        }
+        _ => formatted.push_str(integer),
+    }
+    if let Some(fraction) = fraction {
+        formatted.push_str(locale.decimal());
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BytesFormat;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn format(options: &FormatterOptions, value: &Value) -> String {
+        format_value(options, Locale::en, value)
+    }
+
+    #[test]
+    fn test_format_value_default() -> anyhow::Result<()> {
+        let options = FormatterOptions::default();
+        assert_eq!(format(&options, &Value::I64(12345)), "12,345");
+        assert_eq!(format(&options, &Value::F64(12345.678)), "12345.678");
+        assert_eq!(
+            format(&options, &Value::Decimal(Decimal::from_str("-12345.678")?)),
+            "-12,345.678"
+        );
+        assert_eq!(format(&options, &Value::String("foo".to_string())), "foo");
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_value_bytes() {
+        let options = FormatterOptions {
+            bytes_format: BytesFormat::Omit,
+            ..Default::default()
+        };
+        assert_eq!(format(&options, &Value::Bytes(vec![1, 2, 3])), "<3 bytes>");
+    }
+
+    #[test]
+    fn test_format_value_decimal_places() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            decimal_places: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(format(&options, &Value::I64(12345)), "12,345");
+        assert_eq!(format(&options, &Value::F32(1.5)), "1.50");
+        assert_eq!(format(&options, &Value::F64(-12345.678)), "-12,345.68");
+        assert_eq!(
+            format(&options, &Value::Decimal(Decimal::from_str("12345.678")?)),
+            "12,345.68"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_value_without_thousands_separator() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            decimal_places: Some(1),
+            thousands_separator: false,
+            ..Default::default()
+        };
+        assert_eq!(format(&options, &Value::I64(12345)), "12345");
+        assert_eq!(format(&options, &Value::U128(12345)), "12345");
+        assert_eq!(format(&options, &Value::F64(12345.678)), "12345.7");
+        assert_eq!(
+            format(&options, &Value::Decimal(Decimal::from_str("12345.678")?)),
+            "12345.7"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_value_scientific_notation() -> anyhow::Result<()> {
+        let mut options = FormatterOptions {
+            scientific_notation: Some(6),
+            ..Default::default()
+        };
+        assert_eq!(format(&options, &Value::F64(1_234_567.0)), "1.234567e6");
+        assert_eq!(format(&options, &Value::F64(0.000_000_15)), "1.5e-7");
+        assert_eq!(format(&options, &Value::F64(123.5)), "123.5");
+        assert_eq!(format(&options, &Value::F64(0.0)), "0");
+        assert_eq!(
+            format(&options, &Value::Decimal(Decimal::from_str("12345678")?)),
+            "1.2345678e7"
+        );
+
+        options.decimal_places = Some(2);
+        assert_eq!(format(&options, &Value::F64(1_234_567.0)), "1.23e6");
+        assert_eq!(format(&options, &Value::F64(f64::INFINITY)), "inf");
+        Ok(())
+    }
+
+    #[test]
+    fn test_localize() {
+        assert_eq!(localize("-1234567.5", Locale::de, true), "-1.234.567,5");
+        assert_eq!(localize("1234567", Locale::de, false), "1234567");
+    }
+}
@@ -0,0 +1,194 @@
+use crate::writers::writer::Writer;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{Result, Write};
+use std::path::PathBuf;
+
+/// The placeholder in the path that is replaced with the chunk number
+const CHUNK_PLACEHOLDER: &str = "{n}";
+
+/// Writer that splits the output into multiple files, starting a new file after the specified
+/// number of lines have been written.  The `{n}` placeholder in the path is replaced with the
+/// chunk number, starting at 1; when the path does not contain the placeholder, the chunk number
+/// is added before the file extension.  Header lines written at the start of the output are
+/// repeated at the start of each file.
+#[derive(Debug)]
+pub struct ChunkedWriter {
+    path: String,
+    rows: usize,
+    header_lines: usize,
+    header: Vec<u8>,
+    header_lines_written: usize,
+    chunk: usize,
+    lines: usize,
+    line_start: bool,
+    chunk_path: PathBuf,
+    file: File,
+}
+
+impl ChunkedWriter {
+    /// Create a new `ChunkedWriter` that starts a new file after `rows` lines
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the first file cannot be created
+    pub fn new<S: Into<String>>(path: S, rows: usize) -> Result<Self> {
+        let path = path.into();
+        let chunk_path = Self::path_for_chunk(&path, 1);
+        let file = File::create(&chunk_path)?;
+        Ok(Self {
+            path,
+            rows,
+            header_lines: 0,
+            header: Vec::new(),
+            header_lines_written: 0,
+            chunk: 1,
+            lines: 0,
+            line_start: true,
+            chunk_path,
+            file,
+        })
+    }
+
+    /// Set the number of lines at the start of the output that are repeated in each file
+    #[must_use]
+    pub fn with_header_lines(mut self, header_lines: usize) -> Self {
+        self.header_lines = header_lines;
+        self
+    }
+
+    /// Get the path of the file that is currently being written
+    #[must_use]
+    pub fn chunk_path(&self) -> &PathBuf {
+        &self.chunk_path
+    }
+
+    /// Get the path for the chunk number
+    fn path_for_chunk(path: &str, chunk: usize) -> PathBuf {
+        if path.contains(CHUNK_PLACEHOLDER) {
+            return PathBuf::from(path.replace(CHUNK_PLACEHOLDER, &chunk.to_string()));
+        }
+
+        let path = PathBuf::from(path);
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let file_name = match path.extension() {
+            Some(extension) => format!("{stem}-{chunk}.{}", extension.to_string_lossy()),
+            None => format!("{stem}-{chunk}"),
+        };
+        path.with_file_name(file_name)
+    }
+
+    /// Close the current file and start the next chunk, repeating the header lines
+    fn next_chunk(&mut self) -> Result<()> {
+        self.file.flush()?;
+        self.chunk += 1;
+        self.chunk_path = Self::path_for_chunk(&self.path, self.chunk);
+        self.file = File::create(&self.chunk_path)?;
+        self.file.write_all(&self.header)?;
+        self.lines = 0;
+        Ok(())
+    }
+
+    /// Write a segment of a line; the segment ends with a newline if it completes the line
+    fn write_segment(&mut self, segment: &[u8]) -> Result<()> {
+        let line_end = segment.ends_with(b"\n");
+        if self.header_lines_written < self.header_lines {
+            self.header.extend_from_slice(segment);
+            if line_end {
+                self.header_lines_written += 1;
+            }
+        } else {
+            if self.line_start && self.rows > 0 && self.lines >= self.rows {
+                self.next_chunk()?;
+            }
+            if line_end {
+                self.lines += 1;
+            }
+        }
+        self.line_start = line_end;
+        self.file.write_all(segment)
+    }
+}
+
+impl Display for ChunkedWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.chunk_path.to_string_lossy())
+    }
+}
+
+impl Write for ChunkedWriter {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        for segment in buf.split_inclusive(|byte| *byte == b'\n') {
+            self.write_segment(segment)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.file.flush()
+    }
+}
+
+impl Writer for ChunkedWriter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_path_for_chunk() {
+        assert_eq!(
+            ChunkedWriter::path_for_chunk("results-{n}.csv", 2),
+            PathBuf::from("results-2.csv")
+        );
+        assert_eq!(
+            ChunkedWriter::path_for_chunk("results.csv", 3),
+            PathBuf::from("results-3.csv")
+        );
+        assert_eq!(
+            ChunkedWriter::path_for_chunk("results", 1),
+            PathBuf::from("results-1")
+        );
+    }
+
+    #[test]
+    fn test_writer() -> anyhow::Result<()> {
+        let directory = tempdir()?;
+        let path = directory.path().join("results-{n}.csv");
+        let path = path.to_string_lossy().to_string();
+        let mut writer = ChunkedWriter::new(path, 2)?.with_header_lines(1);
+        writer.write_all(b"id,name\n1,")?;
+        writer.write_all(b"foo\n2,bar\n3,baz\n4")?;
+        writer.write_all(b",qux\n5,quux\n")?;
+        writer.flush()?;
+        assert!(writer.to_string().ends_with("results-3.csv"));
+
+        let chunk =
+            |n: usize| fs::read_to_string(directory.path().join(format!("results-{n}.csv")));
+        assert_eq!(chunk(1)?, "id,name\n1,foo\n2,bar\n");
+        assert_eq!(chunk(2)?, "id,name\n3,baz\n4,qux\n");
+        assert_eq!(chunk(3)?, "id,name\n5,quux\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_writer_without_header() -> anyhow::Result<()> {
+        let directory = tempdir()?;
+        let path = directory.path().join("results.txt");
+        let path = path.to_string_lossy().to_string();
+        let mut writer = ChunkedWriter::new(path, 1)?;
+        writer.write_all(b"1\n2\n")?;
+        writer.flush()?;
+
+        let chunk =
+            |n: usize| fs::read_to_string(directory.path().join(format!("results-{n}.txt")));
+        assert_eq!(chunk(1)?, "1\n");
+        assert_eq!(chunk(2)?, "2\n");
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+mod chunked;
 mod clipboard;
 mod fanout;
 mod file;
@@ -6,6 +7,7 @@ mod stderr;
 mod stdout;
 mod writer;
 
+pub use chunked::ChunkedWriter;
 pub use clipboard::ClipboardWriter;
 pub use fanout::FanoutWriter;
 pub use file::FileWriter;
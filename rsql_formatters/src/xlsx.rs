@@ -0,0 +1,198 @@
+use crate::error::Result;
+use crate::footer::{footer_text, write_footer};
+use crate::formatter::FormatterOptions;
+use crate::writers::Output;
+use crate::Results;
+use crate::Results::{Execute, Query};
+use async_trait::async_trait;
+use rsql_drivers::Value;
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use std::io::Write;
+
+const DATE_FORMAT: &str = "yyyy-mm-dd";
+const TIME_FORMAT: &str = "hh:mm:ss";
+const DATE_TIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+
+/// A formatter for Excel spreadsheets (XLSX)
+#[derive(Debug, Default)]
+pub struct Formatter;
+
+#[async_trait]
+impl crate::Formatter for Formatter {
+    fn identifier(&self) -> &'static str {
+        "xlsx"
+    }
+
+    async fn format(
+        &self,
+        options: &FormatterOptions,
+        results: &mut Results,
+        output: &mut Output,
+    ) -> Result<()> {
+        let query_result = match results {
+            Query(query_result) => query_result,
+            Execute(_) => return write_footer(options, results, 0, output).await,
+        };
+
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let mut row_index: u32 = 0;
+
+        if options.header {
+            let header_format = Format::new().set_bold();
+            for (c, column) in query_result.columns().await.iter().enumerate() {
+                let column_index = u16::try_from(c)?;
+                worksheet.write_string_with_format(
+                    row_index,
+                    column_index,
+                    column,
+                    &header_format,
+                )?;
+            }
+            worksheet.set_freeze_panes(1, 0)?;
+            row_index += 1;
+        }
+
+        let mut rows: u64 = 0;
+        while let Some(row) = query_result.next().await {
+            for (c, data) in row.iter().enumerate() {
+                let column_index = u16::try_from(c)?;
+                write_cell(options, worksheet, row_index, column_index, data)?;
+            }
+            row_index += 1;
+            rows += 1;
+        }
+
+        if let Some(footer) = footer_text(options, results, rows) {
+            // Ampersands are control characters in Excel headers and footers
+            let footer = footer.replace('&', "&&");
+            worksheet.set_footer(format!("&L{footer}"));
+        }
+
+        let buffer = workbook.save_to_buffer()?;
+        output.write_all(&buffer)?;
+        output.flush()?;
+        Ok(())
+    }
+}
+
+/// Write a value to a cell using the closest native Excel type; numbers, booleans and dates are
+/// written as typed cells so that they can be used in calculations, all other values are written
+/// as strings, with binary values formatted using the bytes format. Excel stores all numbers as
+/// 64-bit floating point values, so large integers may lose precision.
+fn write_cell(
+    options: &FormatterOptions,
+    worksheet: &mut Worksheet,
+    row: u32,
+    column: u16,
+    value: &Value,
+) -> Result<()> {
+    match value {
+        Value::Null => {}
+        Value::Bytes(bytes) => {
+            let bytes = options.bytes_format.format(bytes, &options.locale);
+            worksheet.write_string(row, column, bytes)?;
+        }
+        Value::Bool(value) => {
+            worksheet.write_boolean(row, column, *value)?;
+        }
+        Value::Date(value) => {
+            let format = Format::new().set_num_format(DATE_FORMAT);
+            worksheet.write_datetime_with_format(row, column, value, &format)?;
+        }
+        Value::Time(value) => {
+            let format = Format::new().set_num_format(TIME_FORMAT);
+            worksheet.write_datetime_with_format(row, column, value, &format)?;
+        }
+        Value::DateTime(value) => {
+            let format = Format::new().set_num_format(DATE_TIME_FORMAT);
+            worksheet.write_datetime_with_format(row, column, value, &format)?;
+        }
+        _ => {
+            if let Some(number) = value.to_f64() {
+                worksheet.write_number(row, column, number)?;
+            } else {
+                worksheet.write_string(row, column, value.to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::formatter::FormatterOptions;
+    use crate::writers::{FileWriter, Output};
+    use crate::Formatter;
+    use crate::Results::{Execute, Query};
+    use chrono::NaiveDate;
+    use rsql_drivers::{MemoryQueryResult, Value};
+    use std::time::Duration;
+    use tempfile::NamedTempFile;
+
+    const ZIP_SIGNATURE: &[u8] = b"PK\x03\x04";
+
+    #[tokio::test]
+    async fn test_format_execute() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        };
+        let output = &mut Output::default();
+
+        let formatter = Formatter;
+        formatter.format(&options, &mut Execute(1), output).await?;
+
+        let output = output.to_string().replace("\r\n", "\n");
+        let expected = "1 row (9ns)\n";
+        assert_eq!(output, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_format_query() -> anyhow::Result<()> {
+        let options = FormatterOptions {
+            color: false,
+            elapsed: Duration::from_nanos(9),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).expect("invalid date");
+        let mut query_result = Query(Box::new(MemoryQueryResult::new(
+            vec![
+                "id".to_string(),
+                "active".to_string(),
+                "created".to_string(),
+                "data".to_string(),
+            ],
+            vec![
+                vec![
+                    Value::I64(1),
+                    Value::Bool(true),
+                    Value::Date(date),
+                    Value::Bytes(b"bytes".to_vec()),
+                ],
+                vec![
+                    Value::I64(2),
+                    Value::Bool(false),
+                    Value::Date(date),
+                    Value::String("foo".to_string()),
+                ],
+                vec![Value::I64(3), Value::Null, Value::Null, Value::Null],
+            ],
+        )));
+        let file = NamedTempFile::new()?;
+        let writer = FileWriter::from_path(file.path())?;
+        let output = &mut Output::new(Box::new(writer));
+
+        let formatter = Formatter;
+        formatter
+            .format(&options, &mut query_result, output)
+            .await?;
+
+        let bytes = std::fs::read(file.path())?;
+        assert!(bytes.starts_with(ZIP_SIGNATURE));
+        Ok(())
+    }
+}
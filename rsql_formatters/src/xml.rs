@@ -53,7 +53,7 @@ pub(crate) async fn format_xml(
             if data.is_null() {
                 writer.write_event(Event::Empty(BytesStart::new(column)))?;
             } else {
-                let string_value = data.to_string();
+                let string_value = options.value_to_string(&data);
                 writer.write_event(Event::Start(BytesStart::new(column)))?;
                 writer.write_event(Event::Text(BytesText::new(string_value.as_str())))?;
                 writer.write_event(Event::End(BytesEnd::new(column)))?;
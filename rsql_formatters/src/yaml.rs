@@ -48,12 +48,13 @@ pub(crate) async fn format_yaml(
 
         for (c, data) in row.into_iter().enumerate() {
             let column = columns.get(c).expect("column not found");
-            if let Value::Bytes(ref _bytes) = data {
-                let value = Value::String(data.to_string());
-                yaml_row.insert(column, value);
-            } else {
-                yaml_row.insert(column, data.clone());
-            }
+            let value = match data {
+                Value::Bytes(_) | Value::Date(_) | Value::Time(_) | Value::DateTime(_) => {
+                    Value::String(options.value_to_string(&data))
+                }
+                _ => data,
+            };
+            yaml_row.insert(column, value);
         }
 
         yaml_rows.push(yaml_row);